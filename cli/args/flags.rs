@@ -59,7 +59,9 @@ use deno_semver::jsr::JsrDepPackageReq;
 use deno_semver::package::PackageKind;
 use deno_telemetry::OtelConfig;
 use deno_telemetry::OtelConsoleConfig;
+use deno_telemetry::OtelInstrumentations;
 use deno_telemetry::OtelPropagators;
+use deno_telemetry::OtelSampler;
 use log::Level;
 use log::debug;
 use node_shim::parse_node_options_env_var;
@@ -304,10 +306,26 @@ pub struct InitFlags {
   pub yes: bool,
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InfoGraphFormat {
+  Dot,
+  Mermaid,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InfoGraphCollapse {
+  Package,
+  Directory,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct InfoFlags {
   pub json: bool,
   pub file: Option<String>,
+  pub why: Option<String>,
+  pub format: Option<InfoGraphFormat>,
+  pub collapse: Option<InfoGraphCollapse>,
+  pub cycles: bool,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -336,6 +354,7 @@ pub enum InstallFlagsLocal {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct InstallTopLevelFlags {
   pub lockfile_only: bool,
+  pub import_lockfile: bool,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -405,6 +424,23 @@ pub struct ReplFlags {
   pub json: bool,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct PprofFlags {
+  /// A directory or an `http:` URL.
+  pub target: Option<String>,
+  /// Seconds between exported profiles.
+  pub period: Option<u64>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct HeapProfFlags {
+  /// Signal name, like `SIGUSR2`, that triggers a heap snapshot.
+  pub snapshot_signal: Option<String>,
+  /// Whether to write an allocation sampling profile on exit.
+  pub sampling: bool,
+  pub dir: Option<String>,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Default)]
 pub struct RunFlags {
   pub script: String,
@@ -585,6 +621,27 @@ pub struct CleanFlags {
   pub dry_run: bool,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FfiBindgenFlags {
+  pub header: String,
+  pub output: Option<String>,
+  /// Macros defined before reading the header, as `(name, value)`.
+  pub defines: Vec<(String, String)>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FfiFlags {
+  Bindgen(FfiBindgenFlags),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HeapDiffFlags {
+  pub before: String,
+  pub after: String,
+  pub top: usize,
+  pub json: bool,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BundleFlags {
   pub entrypoints: Vec<String>,
@@ -619,7 +676,9 @@ pub enum DenoSubcommand {
   Deploy(DeployFlags),
   Doc(DocFlags),
   Eval(EvalFlags),
+  Ffi(FfiFlags),
   Fmt(FmtFlags),
+  HeapDiff(HeapDiffFlags),
   Init(InitFlags),
   Info(InfoFlags),
   Install(InstallFlags),
@@ -882,6 +941,8 @@ pub struct Flags {
   pub ignore: Vec<String>,
   pub import_map_path: Option<String>,
   pub env_file: Option<Vec<String>>,
+  pub env_mode: Option<String>,
+  pub env_expand: bool,
   pub inspect_brk: Option<SocketAddr>,
   pub inspect_wait: Option<SocketAddr>,
   pub inspect: Option<SocketAddr>,
@@ -895,6 +956,8 @@ pub struct Flags {
   pub no_npm: bool,
   pub reload: bool,
   pub seed: Option<u64>,
+  pub storage_dir: Option<String>,
+  pub storage_quota: Option<u64>,
   pub trace_ops: Option<Vec<String>>,
   pub unstable_config: UnstableConfig,
   pub unsafely_ignore_certificate_errors: Option<Vec<String>>,
@@ -908,6 +971,8 @@ pub struct Flags {
   pub preload: Vec<String>,
   pub require: Vec<String>,
   pub tunnel: bool,
+  pub pprof: Option<PprofFlags>,
+  pub heap_prof: Option<HeapProfFlags>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Default, Serialize, Deserialize)]
@@ -1222,6 +1287,9 @@ impl Flags {
           .filter_map(|p| match p.trim() {
             "tracecontext" => Some(OtelPropagators::TraceContext),
             "baggage" => Some(OtelPropagators::Baggage),
+            "b3" => Some(OtelPropagators::B3),
+            "b3multi" => Some(OtelPropagators::B3Multi),
+            "jaeger" => Some(OtelPropagators::Jaeger),
             _ => None,
           })
           .collect()
@@ -1232,12 +1300,77 @@ impl Flags {
       HashSet::default()
     };
 
+    let sampler_arg = || match std::env::var("OTEL_TRACES_SAMPLER_ARG") {
+      Ok(arg) => match arg.trim().parse::<f64>() {
+        Ok(ratio) if (0.0..=1.0).contains(&ratio) => ratio,
+        _ => {
+          log::warn!(
+            "'OTEL_TRACES_SAMPLER_ARG' env var value not recognized, only numbers between 0 and 1 are accepted"
+          );
+          1.0
+        }
+      },
+      Err(_) => 1.0,
+    };
+    let sampler = match std::env::var("OTEL_TRACES_SAMPLER").as_deref() {
+      Ok("always_on") => OtelSampler::AlwaysOn,
+      Ok("always_off") => OtelSampler::AlwaysOff,
+      Ok("traceidratio") => OtelSampler::TraceIdRatio(sampler_arg()),
+      Ok("parentbased_always_on") => OtelSampler::ParentBasedAlwaysOn,
+      Ok("parentbased_always_off") => OtelSampler::ParentBasedAlwaysOff,
+      Ok("parentbased_traceidratio") => {
+        OtelSampler::ParentBasedTraceIdRatio(sampler_arg())
+      }
+      Ok(_) => {
+        log::warn!(
+          "'OTEL_TRACES_SAMPLER' env var value not recognized, only 'always_on', 'always_off', 'traceidratio', 'parentbased_always_on', 'parentbased_always_off', or 'parentbased_traceidratio' are accepted"
+        );
+        OtelSampler::default()
+      }
+      Err(_) => OtelSampler::default(),
+    };
+
+    let mut instrumentations = OtelInstrumentations::default();
+    if let Ok(names) = std::env::var("OTEL_DENO_INSTRUMENTATIONS") {
+      for name in names.split(',').map(str::trim) {
+        match name {
+          "kv" => instrumentations.kv = true,
+          "command" => instrumentations.command = true,
+          "fs" => instrumentations.fs = true,
+          "all" => {
+            instrumentations.kv = true;
+            instrumentations.command = true;
+            instrumentations.fs = true;
+          }
+          "" => {}
+          name => {
+            log::warn!(
+              "'OTEL_DENO_INSTRUMENTATIONS' env var value '{name}' not recognized, only 'kv', 'command', 'fs', or 'all' are accepted"
+            );
+          }
+        }
+      }
+    }
+    if let Ok(threshold) = std::env::var("OTEL_DENO_FS_THRESHOLD_MS") {
+      match threshold.trim().parse() {
+        Ok(threshold) => instrumentations.fs_threshold_ms = threshold,
+        Err(_) => {
+          log::warn!(
+            "'OTEL_DENO_FS_THRESHOLD_MS' env var value not recognized, only non-negative integers are accepted"
+          );
+        }
+      }
+    }
+
     OtelConfig {
       tracing_enabled: !disabled
         && otel_var("OTEL_DENO_TRACING").unwrap_or(default),
       metrics_enabled: !disabled
         && otel_var("OTEL_DENO_METRICS").unwrap_or(default),
       propagators,
+      sampler,
+      instrumentations,
+      span_marks: false,
       console: match std::env::var("OTEL_DENO_CONSOLE").as_deref() {
         Ok(_) if disabled => OtelConsoleConfig::Ignore,
         Ok("ignore") => OtelConsoleConfig::Ignore,
@@ -1504,6 +1637,11 @@ static ENV_VARS: &[EnvVar] = &[
     description: "Set to disable checking if a newer Deno version is available",
     example: None,
   },
+  EnvVar {
+    name: "DENO_PPROF",
+    description: "Set the directory or http: URL for continuously collected CPU profiles.\nEquivalent to using the --pprof flag.",
+    example: None,
+  },
   EnvVar {
     name: "DENO_SERVE_ADDRESS",
     description: "Override address for Deno.serve",
@@ -1655,8 +1793,11 @@ static DENO_HELP: &str = cstr!(
     <g>deploy</>       Manage and publish applications with Deno Deploy
     <g>doc</>          Generate and show documentation for a module or built-ins
                   <p(245)>deno doc  |  deno doc --json  |  deno doc --html mod.ts</>
+    <g>ffi</>          Generate FFI bindings from C headers
+                  <p(245)>deno ffi bindgen lib.h -o lib.ts</>
     <g>fmt</>          Format source files
                   <p(245)>deno fmt  |  deno fmt main.ts</>
+    <g>heap-diff</>    Compare two heap snapshots and show what grew
     <g>info</>         Show info about cache or info related to source file
     <g>jupyter</>      Deno kernel for Jupyter notebooks
     <g>lint</>         Lint source files
@@ -1877,7 +2018,9 @@ pub fn flags_from_vec_with_initial_cwd(
         "coverage" => coverage_parse(&mut flags, &mut m)?,
         "doc" => doc_parse(&mut flags, &mut m)?,
         "eval" => eval_parse(&mut flags, &mut m)?,
+        "ffi" => ffi_parse(&mut flags, &mut m),
         "fmt" => fmt_parse(&mut flags, &mut m)?,
+        "heap-diff" => heap_diff_parse(&mut flags, &mut m),
         "init" => init_parse(&mut flags, &mut m)?,
         "info" => info_parse(&mut flags, &mut m)?,
         "install" => install_parse(&mut flags, &mut m, app)?,
@@ -2146,7 +2289,9 @@ pub fn clap_root() -> Command {
         .subcommand(deploy_subcommand())
         .subcommand(sandbox_subcommand())
         .subcommand(eval_subcommand())
+        .subcommand(ffi_subcommand())
         .subcommand(fmt_subcommand())
+        .subcommand(heap_diff_subcommand())
         .subcommand(init_subcommand())
         .subcommand(info_subcommand())
         .subcommand(install_subcommand())
@@ -2442,6 +2587,8 @@ If you specify a directory instead of a file, the path is expanded to all contai
       .arg(no_clear_screen_arg())
       .arg(script_arg().last(true))
       .arg(env_file_arg())
+      .arg(env_mode_arg())
+      .arg(env_expand_arg())
       .arg(executable_ext_arg())
   })
 }
@@ -2623,6 +2770,8 @@ Future runs of this module will trigger no downloads or compilation unless --rel
       .arg(allow_import_arg())
       .arg(deny_import_arg())
       .arg(env_file_arg())
+      .arg(env_mode_arg())
+      .arg(env_expand_arg())
   })
 }
 
@@ -2817,6 +2966,8 @@ On the first invocation of `deno compile`, Deno will download the relevant binar
       )
       .arg(executable_ext_arg())
       .arg(env_file_arg())
+      .arg(env_mode_arg())
+      .arg(env_expand_arg())
       .arg(
         script_arg()
           .required_unless_present("help")
@@ -3132,6 +3283,8 @@ This command has implicit access to all permissions.
           .required_unless_present("help"),
       )
       .arg(env_file_arg())
+      .arg(env_mode_arg())
+      .arg(env_expand_arg())
   })
 }
 
@@ -3432,6 +3585,15 @@ The following information is shown:
   emit: Local path of compiled source code (TypeScript only)
   dependencies: Dependency tree of the source file
 
+Explain why an npm package is in the dependency tree:
+  <p(245)>deno info --why chalk</>
+  <p(245)>deno info --why chalk@^5 main.ts</>
+
+Output the module graph for Graphviz or Mermaid, or find import cycles:
+  <p(245)>deno info --format dot main.ts | dot -Tsvg > graph.svg</>
+  <p(245)>deno info --format mermaid --collapse package main.ts</>
+  <p(245)>deno info --cycles</>
+
 <y>Read more:</> <c>https://docs.deno.com/go/info</>"),
           UnstableArgsConfig::ResolutionOnly
     )
@@ -3459,6 +3621,36 @@ The following information is shown:
           .long("json")
           .help("UNSTABLE: Outputs the information in JSON format")
           .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("why")
+          .long("why")
+          .value_name("PACKAGE")
+          .conflicts_with("location")
+          .help(cstr!("Show every import and dependency path that leads to an npm package (ex. <p(245)>--why chalk</> or <p(245)>--why chalk@^5</>)"))
+      )
+      .arg(
+        Arg::new("format")
+          .long("format")
+          .value_name("FORMAT")
+          .value_parser(["dot", "mermaid"])
+          .conflicts_with_all(["json", "why", "location", "cycles"])
+          .help("Output the module graph in the DOT (Graphviz) or Mermaid format")
+      )
+      .arg(
+        Arg::new("collapse")
+          .long("collapse")
+          .value_name("LEVEL")
+          .value_parser(["package", "directory"])
+          .requires("format")
+          .help("Collapse the modules of the graph into a node per package or per directory. With 'package', other modules are collapsed per directory, or per host when remote")
+      )
+      .arg(
+        Arg::new("cycles")
+          .long("cycles")
+          .conflicts_with_all(["why", "location"])
+          .help("Find the import cycles in the module graph and the imports that form them")
+          .action(ArgAction::SetTrue),
       ))
       .arg(allow_import_arg())
       .arg(deny_import_arg())
@@ -3563,6 +3755,8 @@ These must be added to the path manually if required."), UnstableArgsConfig::Res
             .help("Install dependents of the specified entrypoint(s)"),
         )
         .arg(env_file_arg())
+        .arg(env_mode_arg())
+        .arg(env_expand_arg())
         .arg(add_dev_arg().conflicts_with("entrypoint").conflicts_with("global"))
         .args(default_registry_args().into_iter().map(|arg| arg.conflicts_with("entrypoint").conflicts_with("global")))
         .arg(
@@ -3575,6 +3769,15 @@ These must be added to the path manually if required."), UnstableArgsConfig::Res
             .conflicts_with("global"),
         )
         .arg(lockfile_only_arg().conflicts_with("global"))
+        .arg(
+          Arg::new("import-lockfile")
+            .long("import-lockfile")
+            .help("Seed the lockfile with the npm packages resolved by a package-lock.json, pnpm-lock.yaml or yarn.lock")
+            .action(ArgAction::SetTrue)
+            .conflicts_with("cmd")
+            .conflicts_with("entrypoint")
+            .conflicts_with("global"),
+        )
     })
 }
 
@@ -3833,6 +4036,8 @@ fn x_subcommand() -> Command {
       )
       .arg(check_arg(false))
       .arg(env_file_arg())
+      .arg(env_mode_arg())
+      .arg(env_expand_arg())
       .arg(
         Arg::new("install-alias")
           .long("install-alias")
@@ -4018,6 +4223,8 @@ TypeScript is supported, however it is not type-checked, only transpiled."
                        <p(245)>[default: $DENO_DIR/deno_history.txt]</>"))
     })
     .arg(env_file_arg())
+    .arg(env_mode_arg())
+    .arg(env_expand_arg())
     .arg(
       Arg::new("args")
         .num_args(0..)
@@ -4041,9 +4248,13 @@ fn run_args(command: Command, top_level: bool) -> Command {
       script_arg().trailing_var_arg(true)
     })
     .arg(env_file_arg())
+    .arg(env_mode_arg())
+    .arg(env_expand_arg())
     .arg(no_code_cache_arg())
     .arg(coverage_arg())
     .arg(tunnel_arg())
+    .args(pprof_args())
+    .args(heap_prof_args())
 }
 
 #[cfg(test)]
@@ -4184,8 +4395,12 @@ Start a server defined in server.ts, watching for changes and running on port 50
         .trailing_var_arg(true),
     )
     .arg(env_file_arg())
+    .arg(env_mode_arg())
+    .arg(env_expand_arg())
     .arg(no_code_cache_arg())
     .arg(tunnel_arg())
+    .args(pprof_args())
+    .args(heap_prof_args())
 }
 
 fn task_subcommand() -> Command {
@@ -4447,6 +4662,8 @@ or <c>**/__tests__/**</>:
           .action(ArgAction::SetTrue)
       )
       .arg(env_file_arg())
+      .arg(env_mode_arg())
+      .arg(env_expand_arg())
       .arg(executable_ext_arg())
     )
 }
@@ -4458,6 +4675,100 @@ fn parallel_arg(descr: &str) -> Arg {
     .action(ArgAction::SetTrue)
 }
 
+fn ffi_subcommand() -> Command {
+  command(
+    "ffi",
+    "Tools for working with native libraries through the FFI API.",
+    UnstableArgsConfig::None,
+  )
+  .subcommand_required(true)
+  .arg_required_else_help(true)
+  .subcommand(
+    command(
+      "bindgen",
+      cstr!(
+        "Generate a TypeScript module with bindings for a C header.
+
+  <p(245)>deno ffi bindgen mylib.h -o mylib.ts</>
+
+The module exports the symbol definitions for <c>Deno.dlopen()</>, constants from <c>#define</> directives, enums and,
+for each struct, its layout as an FFI struct type along with functions to encode and decode it.
+Included headers are not read, so types from them are only known if they come from the standard library.
+Declarations that can't be represented with the FFI API are skipped with a warning.
+Struct layouts and the size of <c>long</> are computed for the current platform."
+      ),
+      UnstableArgsConfig::None,
+    )
+    .arg(
+      Arg::new("header")
+        .required(true)
+        .help("The C header to generate bindings for")
+        .value_hint(ValueHint::FilePath),
+    )
+    .arg(
+      Arg::new("output")
+        .long("output")
+        .short('o')
+        .value_name("FILE")
+        .help("Write the bindings to a file instead of stdout")
+        .value_hint(ValueHint::FilePath),
+    )
+    .arg(
+      Arg::new("define")
+        .long("define")
+        .short('D')
+        .value_name("NAME[=VALUE]")
+        .action(ArgAction::Append)
+        .help("Define a macro before reading the header"),
+    ),
+  )
+}
+
+fn heap_diff_subcommand() -> Command {
+  command(
+    "heap-diff",
+    cstr!(
+      "Compare two heap snapshots and show the constructors that grew the most, along with what retains their new objects.
+
+  <p(245)>deno heap-diff before.heapsnapshot after.heapsnapshot</>
+
+Snapshots can be written by a running program with <c>--heap-snapshot-signal</> or with <c>writeHeapSnapshot()</> from <c>node:v8</>.
+Both snapshots need to come from the same process for new objects to be told apart from existing ones."
+    ),
+    UnstableArgsConfig::None,
+  )
+  .defer(|cmd| {
+    cmd
+      .arg(
+        Arg::new("before")
+          .required(true)
+          .help("The earlier heap snapshot")
+          .value_hint(ValueHint::FilePath),
+      )
+      .arg(
+        Arg::new("after")
+          .required(true)
+          .help("The later heap snapshot")
+          .value_hint(ValueHint::FilePath),
+      )
+      .arg(
+        Arg::new("top")
+          .long("top")
+          .value_name("N")
+          .require_equals(true)
+          .default_value("10")
+          .value_parser(value_parser!(usize))
+          .help("Number of constructors to show"),
+      )
+      .arg(
+        Arg::new("json")
+          .long("json")
+          .help("Output the diff in JSON format")
+          .action(ArgAction::SetTrue),
+      )
+  })
+}
+
 fn types_subcommand() -> Command {
   command(
     "types",
@@ -5109,6 +5420,8 @@ fn runtime_misc_args(app: Command) -> Command {
   app
     .arg(cached_only_arg())
     .arg(location_arg())
+    .arg(storage_dir_arg())
+    .arg(storage_quota_arg())
     .arg(v8_flags_arg())
     .arg(seed_arg())
     .arg(enable_testing_features_arg())
@@ -5303,6 +5616,28 @@ fn env_file_arg() -> Arg {
     .action(ArgAction::Append)
 }
 
+fn env_mode_arg() -> Arg {
+  Arg::new("env-mode")
+    .long("env-mode")
+    .value_name("MODE")
+    .help(cstr!(
+      "Layer the environment files for a mode, defaults to loading .env
+  <p(245)>Each environment file is loaded along with its .local, .MODE and .MODE.local variants, which take precedence in that order.
+  Variants that don't exist are skipped.</>"
+    ))
+    .require_equals(true)
+}
+
+fn env_expand_arg() -> Arg {
+  Arg::new("env-expand")
+    .long("env-expand")
+    .help(cstr!(
+      "Expand variable references in the values of the environment files
+  <p(245)>Supports $VAR, ${VAR}, ${VAR:-default} and ${VAR-default}, resolving with the process environment first. Single quoted values are not expanded.</>"
+    ))
+    .action(ArgAction::SetTrue)
+}
+
 fn reload_arg() -> Arg {
   Arg::new("reload")
     .short('r')
@@ -5400,6 +5735,26 @@ fn location_arg() -> Arg {
     .value_hint(ValueHint::Url)
 }
 
+fn storage_dir_arg() -> Arg {
+  Arg::new("storage-dir")
+    .long("storage-dir")
+    .value_name("DIR")
+    .help(cstr!(
+      "Directory to persist <p(245)>localStorage</> in, instead of one derived from the location, config file or main module"
+    ))
+    .value_hint(ValueHint::DirPath)
+}
+
+fn storage_quota_arg() -> Arg {
+  Arg::new("storage-quota")
+    .long("storage-quota")
+    .value_name("BYTES")
+    .help(cstr!(
+      "Maximum size of <p(245)>localStorage</> and <p(245)>sessionStorage</> in bytes [default: 10485760]"
+    ))
+    .value_parser(value_parser!(u64))
+}
+
 fn enable_testing_features_arg() -> Arg {
   Arg::new("enable-testing-features-do-not-use")
     .long("enable-testing-features-do-not-use")
@@ -5526,6 +5881,91 @@ fn coverage_arg() -> Arg {
     .value_hint(ValueHint::AnyPath)
 }
 
+fn pprof_args() -> [Arg; 2] {
+  [
+    Arg::new("pprof")
+      .long("pprof")
+      .value_name("DIR_OR_URL")
+      .num_args(0..=1)
+      .require_equals(true)
+      .default_missing_value("pprof")
+      .conflicts_with("inspect")
+      .conflicts_with("inspect-wait")
+      .conflicts_with("inspect-brk")
+      .help(cstr!("Continuously collect CPU profiles in pprof format. Profiles are written to DIR, or POSTed to an http: URL. If not specified, it uses 'pprof/'.
+  <p(245)>Samples are labelled with the current OpenTelemetry trace and span id when tracing is enabled.
+  This option can also be set via the DENO_PPROF environment variable.</>"))
+      .value_hint(ValueHint::AnyPath)
+      .help_heading(DEBUGGING_HEADING),
+    Arg::new("pprof-period")
+      .long("pprof-period")
+      .value_name("SECONDS")
+      .require_equals(true)
+      .value_parser(value_parser!(u64).range(1..))
+      .help(cstr!("How often a CPU profile is exported when using --pprof <p(245)>[default: 60]</>"))
+      .help_heading(DEBUGGING_HEADING),
+  ]
+}
+
+fn pprof_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  let target = matches.remove_one::<String>("pprof");
+  let period = matches.remove_one::<u64>("pprof-period");
+  if target.is_some() || period.is_some() {
+    flags.pprof = Some(PprofFlags { target, period });
+  }
+}
+
+fn heap_prof_args() -> [Arg; 3] {
+  [
+    Arg::new("heap-snapshot-signal")
+      .long("heap-snapshot-signal")
+      .value_name("SIGNAL")
+      .require_equals(true)
+      .value_parser(|signal: &str| {
+        match deno_signals::signal_str_to_int(signal) {
+          Ok(signo) if !deno_signals::is_forbidden(signo) => {
+            Ok(signal.to_string())
+          }
+          _ => Err(format!("Invalid signal: {signal}")),
+        }
+      })
+      .conflicts_with("inspect")
+      .conflicts_with("inspect-wait")
+      .conflicts_with("inspect-brk")
+      .help(cstr!("Write a heap snapshot to the current directory, or to --heap-prof-dir, whenever SIGNAL is received
+  <p(245)>The snapshot can be opened in Chrome DevTools or compared to another one with `deno heap-diff`.</>"))
+      .help_heading(DEBUGGING_HEADING),
+    Arg::new("heap-prof")
+      .long("heap-prof")
+      .action(ArgAction::SetTrue)
+      .conflicts_with("inspect")
+      .conflicts_with("inspect-wait")
+      .conflicts_with("inspect-brk")
+      .help(cstr!("Sample allocations while the program runs and write a .heapprofile to the current directory, or to --heap-prof-dir, on exit"))
+      .help_heading(DEBUGGING_HEADING),
+    Arg::new("heap-prof-dir")
+      .long("heap-prof-dir")
+      .value_name("DIR")
+      .require_equals(true)
+      .help("Directory that heap snapshots and heap profiles are written to")
+      .value_hint(ValueHint::DirPath)
+      .help_heading(DEBUGGING_HEADING),
+  ]
+}
+
+fn heap_prof_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  let snapshot_signal = matches.remove_one::<String>("heap-snapshot-signal");
+  let sampling = matches.get_flag("heap-prof");
+  let dir = matches.remove_one::<String>("heap-prof-dir");
+  if snapshot_signal.is_some() || sampling || dir.is_some() {
+    flags.heap_prof = Some(HeapProfFlags {
+      snapshot_signal,
+      sampling,
+      dir,
+    });
+  }
+}
+
 fn permit_no_files_arg() -> Arg {
   Arg::new("permit-no-files")
     .long("permit-no-files")
@@ -6586,6 +7026,22 @@ fn info_parse(
   flags.subcommand = DenoSubcommand::Info(InfoFlags {
     file: matches.remove_one::<String>("file"),
     json,
+    why: matches.remove_one::<String>("why"),
+    format: matches.remove_one::<String>("format").map(|format| {
+      match format.as_str() {
+        "dot" => InfoGraphFormat::Dot,
+        "mermaid" => InfoGraphFormat::Mermaid,
+        _ => unreachable!(),
+      }
+    }),
+    collapse: matches.remove_one::<String>("collapse").map(|collapse| {
+      match collapse.as_str() {
+        "package" => InfoGraphCollapse::Package,
+        "directory" => InfoGraphCollapse::Directory,
+        _ => unreachable!(),
+      }
+    }),
+    cycles: matches.get_flag("cycles"),
   });
 
   Ok(())
@@ -6675,7 +7131,10 @@ fn install_parse(
     ))
   } else {
     flags.subcommand = DenoSubcommand::Install(InstallFlags::Local(
-      InstallFlagsLocal::TopLevel(InstallTopLevelFlags { lockfile_only }),
+      InstallFlagsLocal::TopLevel(InstallTopLevelFlags {
+        lockfile_only,
+        import_lockfile: matches.get_flag("import-lockfile"),
+      }),
     ));
   }
   Ok(())
@@ -6884,6 +7343,7 @@ fn repl_parse(
   permission_args_parse(flags, matches)?;
   inspect_arg_parse(flags, matches);
   location_arg_parse(flags, matches);
+  storage_args_parse(flags, matches);
   v8_flags_arg_parse(flags, matches);
   seed_arg_parse(flags, matches);
   enable_testing_features_arg_parse(flags, matches);
@@ -6929,6 +7389,8 @@ fn run_parse(
   flags.tunnel = matches.get_flag("tunnel");
   flags.code_cache_enabled = !matches.get_flag("no-code-cache");
   let coverage_dir = matches.remove_one::<String>("coverage");
+  pprof_arg_parse(flags, matches);
+  heap_prof_arg_parse(flags, matches);
 
   match matches.remove_many::<String>("script_arg") {
     Some(mut script_arg) => {
@@ -6980,6 +7442,8 @@ fn serve_parse(
   flags.code_cache_enabled = !matches.get_flag("no-code-cache");
 
   flags.tunnel = matches.get_flag("tunnel");
+  pprof_arg_parse(flags, matches);
+  heap_prof_arg_parse(flags, matches);
 
   let mut script_arg =
     matches.remove_many::<String>("script_arg").ok_or_else(|| {
@@ -7274,6 +7738,35 @@ fn test_parse(
   Ok(())
 }
 
+fn ffi_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  let (_, mut matches) = matches.remove_subcommand().unwrap();
+  let defines = matches
+    .remove_many::<String>("define")
+    .map(|defines| {
+      defines
+        .map(|define| match define.split_once('=') {
+          Some((name, value)) => (name.to_string(), value.to_string()),
+          None => (define, "1".to_string()),
+        })
+        .collect()
+    })
+    .unwrap_or_default();
+  flags.subcommand = DenoSubcommand::Ffi(FfiFlags::Bindgen(FfiBindgenFlags {
+    header: matches.remove_one::<String>("header").unwrap(),
+    output: matches.remove_one::<String>("output"),
+    defines,
+  }));
+}
+
+fn heap_diff_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  flags.subcommand = DenoSubcommand::HeapDiff(HeapDiffFlags {
+    before: matches.remove_one::<String>("before").unwrap(),
+    after: matches.remove_one::<String>("after").unwrap(),
+    top: matches.remove_one::<usize>("top").unwrap(),
+    json: matches.get_flag("json"),
+  });
+}
+
 fn types_parse(flags: &mut Flags, _matches: &mut ArgMatches) {
   flags.subcommand = DenoSubcommand::Types;
 }
@@ -7612,6 +8105,7 @@ fn runtime_args_parse(
     allow_scripts_arg_parse(flags, matches)?;
   }
   location_arg_parse(flags, matches);
+  storage_args_parse(flags, matches);
   v8_flags_arg_parse(flags, matches);
   seed_arg_parse(flags, matches);
   enable_testing_features_arg_parse(flags, matches);
@@ -7645,6 +8139,11 @@ fn env_file_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   flags.env_file = matches
     .get_many::<String>("env-file")
     .map(|values| values.cloned().collect());
+  flags.env_mode = matches.remove_one::<String>("env-mode");
+  flags.env_expand = matches.get_flag("env-expand");
+  if flags.env_mode.is_some() && flags.env_file.is_none() {
+    flags.env_file = Some(vec![".env".to_string()]);
+  }
 }
 
 fn reload_arg_parse(
@@ -7717,6 +8216,11 @@ fn location_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   flags.location = matches.remove_one::<Url>("location");
 }
 
+fn storage_args_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  flags.storage_dir = matches.remove_one::<String>("storage-dir");
+  flags.storage_quota = matches.remove_one::<u64>("storage-quota");
+}
+
 fn v8_flags_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   if let Some(v8_flags) = matches.remove_many::<String>("v8-flags") {
     flags.v8_flags = v8_flags.collect();
@@ -8411,6 +8915,115 @@ mod tests {
     );
   }
 
+  #[test]
+  fn run_pprof() {
+    let r = flags_from_vec(svec!["deno", "run", "--pprof", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        pprof: Some(PprofFlags {
+          target: Some("pprof".to_string()),
+          period: None,
+        }),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "serve",
+      "--pprof=http://localhost:4040/ingest",
+      "--pprof-period=10",
+      "main.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Serve(ServeFlags::new_default(
+          "main.ts".to_string(),
+          8000,
+          "0.0.0.0"
+        )),
+        pprof: Some(PprofFlags {
+          target: Some("http://localhost:4040/ingest".to_string()),
+          period: Some(10),
+        }),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "run", "--pprof-period=0", "script.ts"]);
+    assert!(r.is_err());
+    let r =
+      flags_from_vec(svec!["deno", "run", "--pprof", "--inspect", "script.ts"]);
+    assert!(r.is_err());
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn run_heap_prof() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--heap-snapshot-signal=SIGUSR2",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        heap_prof: Some(HeapProfFlags {
+          snapshot_signal: Some("SIGUSR2".to_string()),
+          sampling: false,
+          dir: None,
+        }),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "serve",
+      "--heap-prof",
+      "--heap-prof-dir=profiles",
+      "main.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Serve(ServeFlags::new_default(
+          "main.ts".to_string(),
+          8000,
+          "0.0.0.0"
+        )),
+        heap_prof: Some(HeapProfFlags {
+          snapshot_signal: None,
+          sampling: true,
+          dir: Some("profiles".to_string()),
+        }),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--heap-snapshot-signal=SIGNOPE",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn run_v8_flags() {
     let r = flags_from_vec(svec!["deno", "run", "--v8-flags=--help"]);
@@ -9502,6 +10115,10 @@ mod tests {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
           file: Some("script.ts".to_string()),
+          why: None,
+          format: None,
+          collapse: None,
+          cycles: false,
         }),
         ..Flags::default()
       }
@@ -9514,6 +10131,10 @@ mod tests {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
           file: Some("script.ts".to_string()),
+          why: None,
+          format: None,
+          collapse: None,
+          cycles: false,
         }),
         reload: true,
         ..Flags::default()
@@ -9527,6 +10148,10 @@ mod tests {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: true,
           file: Some("script.ts".to_string()),
+          why: None,
+          format: None,
+          collapse: None,
+          cycles: false,
         }),
         ..Flags::default()
       }
@@ -9538,7 +10163,11 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
-          file: None
+          file: None,
+          why: None,
+          format: None,
+          collapse: None,
+          cycles: false,
         }),
         ..Flags::default()
       }
@@ -9550,7 +10179,11 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: true,
-          file: None
+          file: None,
+          why: None,
+          format: None,
+          collapse: None,
+          cycles: false,
         }),
         ..Flags::default()
       }
@@ -9569,7 +10202,11 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
-          file: None
+          file: None,
+          why: None,
+          format: None,
+          collapse: None,
+          cycles: false,
         }),
         config_flag: ConfigFlag::Path("tsconfig.json".to_owned()),
         no_npm: true,
@@ -9577,6 +10214,86 @@ mod tests {
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec!["deno", "info", "--why", "chalk@5"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: false,
+          file: None,
+          why: Some("chalk@5".to_string()),
+          format: None,
+          collapse: None,
+          cycles: false,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno", "info", "--json", "--why", "chalk", "main.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: true,
+          file: Some("main.ts".to_string()),
+          why: Some("chalk".to_string()),
+          format: None,
+          collapse: None,
+          cycles: false,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--format",
+      "mermaid",
+      "--collapse",
+      "package",
+      "main.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: false,
+          file: Some("main.ts".to_string()),
+          why: None,
+          format: Some(InfoGraphFormat::Mermaid),
+          collapse: Some(InfoGraphCollapse::Package),
+          cycles: false,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "info", "--json", "--cycles"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: true,
+          file: None,
+          why: None,
+          format: None,
+          collapse: None,
+          cycles: true,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "info", "--format", "dot", "--cycles"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "info", "--collapse", "directory"]);
+    assert!(r.is_err());
   }
 
   #[test]
@@ -10504,6 +11221,10 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           file: Some("script.ts".to_string()),
+          why: None,
+          format: None,
+          collapse: None,
+          cycles: false,
           json: false,
         }),
         import_map_path: Some("import_map.json".to_owned()),
@@ -10667,6 +11388,71 @@ mod tests {
     );
   }
 
+  #[test]
+  fn run_env_mode() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--env-mode=production",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        env_file: Some(vec![".env".to_owned()]),
+        env_mode: Some("production".to_owned()),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--env-file=.another_env",
+      "--env-mode=test",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        env_file: Some(vec![".another_env".to_owned()]),
+        env_mode: Some("test".to_owned()),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn run_env_expand() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--env-file",
+      "--env-expand",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        env_file: Some(vec![".env".to_owned()]),
+        env_expand: true,
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
   #[test]
   fn cache_multiple() {
     let r =
@@ -10699,6 +11485,34 @@ mod tests {
     );
   }
 
+  #[test]
+  fn run_storage() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--storage-dir",
+      "data",
+      "--storage-quota=1048576",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        storage_dir: Some("data".to_string()),
+        storage_quota: Some(1048576),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "run", "--storage-quota=10MB", "script.ts"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn run_seed_with_v8_flags() {
     let r = flags_from_vec(svec![
@@ -12162,6 +12976,10 @@ mod tests {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
           file: Some("https://example.com".to_string()),
+          why: None,
+          format: None,
+          collapse: None,
+          cycles: false,
         }),
         ca_data: Some(CaData::File("example.crt".to_owned())),
         ..Flags::default()
@@ -14170,6 +14988,31 @@ mod tests {
     }
   }
 
+  #[test]
+  fn install_import_lockfile() {
+    let r = flags_from_vec(svec!["deno", "install", "--import-lockfile"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Install(InstallFlags::Local(
+          InstallFlagsLocal::TopLevel(InstallTopLevelFlags {
+            lockfile_only: false,
+            import_lockfile: true,
+          }),
+        )),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "install",
+      "--import-lockfile",
+      "npm:chalk"
+    ]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn install_permissions_non_global() {
     let r =
@@ -14649,6 +15492,95 @@ Usage: deno repl [OPTIONS] [-- [ARGS]...]\n"
     }
   }
 
+  #[test]
+  fn ffi_bindgen() {
+    let r = flags_from_vec(svec!["deno", "ffi", "bindgen", "lib.h"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Ffi(FfiFlags::Bindgen(FfiBindgenFlags {
+          header: "lib.h".to_string(),
+          output: None,
+          defines: vec![],
+        })),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "ffi",
+      "bindgen",
+      "include/lib.h",
+      "-o",
+      "lib.ts",
+      "-D",
+      "LIB_STATIC",
+      "--define",
+      "LIB_API_VERSION=2",
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Ffi(FfiFlags::Bindgen(FfiBindgenFlags {
+          header: "include/lib.h".to_string(),
+          output: Some("lib.ts".to_string()),
+          defines: vec![
+            ("LIB_STATIC".to_string(), "1".to_string()),
+            ("LIB_API_VERSION".to_string(), "2".to_string()),
+          ],
+        })),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "ffi"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "ffi", "bindgen"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn heap_diff() {
+    let r = flags_from_vec(svec!["deno", "heap-diff", "a.heapsnapshot", "b"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::HeapDiff(HeapDiffFlags {
+          before: "a.heapsnapshot".to_string(),
+          after: "b".to_string(),
+          top: 10,
+          json: false,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "heap-diff",
+      "--top=3",
+      "--json",
+      "a.heapsnapshot",
+      "b.heapsnapshot"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::HeapDiff(HeapDiffFlags {
+          before: "a.heapsnapshot".to_string(),
+          after: "b.heapsnapshot".to_string(),
+          top: 3,
+          json: true,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "heap-diff", "a.heapsnapshot"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn clean_subcommand() {
     let cases = [
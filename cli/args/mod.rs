@@ -11,6 +11,7 @@ use std::num::NonZeroUsize;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use deno_ast::MediaType;
 use deno_ast::ModuleSpecifier;
@@ -48,10 +49,13 @@ use deno_npm_installer::LifecycleScriptsConfig;
 use deno_npm_installer::graph::NpmCachingStrategy;
 use deno_path_util::resolve_url_or_path;
 use deno_resolver::factory::resolve_jsr_url;
+use deno_runtime::cpu_profiler::CpuProfileTarget;
+use deno_runtime::cpu_profiler::CpuProfilerOptions;
 use deno_runtime::deno_node::ops::ipc::ChildIpcSerialization;
 use deno_runtime::deno_permissions::AllowRunDescriptor;
 use deno_runtime::deno_permissions::PathDescriptor;
 use deno_runtime::deno_permissions::PermissionsOptions;
+use deno_runtime::heap_profiler::HeapProfilerOptions;
 use deno_semver::StackString;
 use deno_semver::npm::NpmPackageReqReference;
 use deno_telemetry::OtelConfig;
@@ -447,6 +451,16 @@ impl WorkspaceMainModuleResolver {
               )?
               .into_url()?
           }
+          deno_package_json::PackageJsonDepValue::Catalog(catalog) => {
+            return Err(
+              deno_package_json::PackageJsonDepValueParseErrorKind::CatalogEntryNotFound {
+                catalog: catalog.to_string(),
+                alias: alias.to_string(),
+              }
+              .into_box()
+              .into(),
+            );
+          }
         }
       }
       deno_resolver::workspace::MappedResolution::PackageJsonImport {
@@ -633,7 +647,10 @@ impl CliOptions {
   }
 
   pub fn otel_config(&self) -> OtelConfig {
-    self.flags.otel_config()
+    let mut config = self.flags.otel_config();
+    config.span_marks =
+      config.tracing_enabled && self.cpu_profiler_options().is_some();
+    config
   }
 
   pub fn no_legacy_abort(&self) -> bool {
@@ -644,6 +661,14 @@ impl CliOptions {
     self.flags.env_file.as_ref()
   }
 
+  pub fn env_mode(&self) -> Option<&str> {
+    self.flags.env_mode.as_deref()
+  }
+
+  pub fn env_expand(&self) -> bool {
+    self.flags.env_expand
+  }
+
   pub fn preload_modules(&self) -> Result<Vec<ModuleSpecifier>, AnyError> {
     if self.flags.preload.is_empty() {
       return Ok(vec![]);
@@ -969,6 +994,50 @@ impl CliOptions {
     }
   }
 
+  pub fn cpu_profiler_options(&self) -> Option<CpuProfilerOptions> {
+    if !matches!(
+      self.flags.subcommand,
+      DenoSubcommand::Run(_) | DenoSubcommand::Serve(_)
+    ) {
+      return None;
+    }
+    let pprof = self.flags.pprof.as_ref();
+    let target = pprof
+      .and_then(|flags| flags.target.clone())
+      .or_else(|| env::var("DENO_PPROF").ok())?;
+    let target = match Url::parse(&target) {
+      Ok(url) if url.scheme() == "http" => CpuProfileTarget::Endpoint(url),
+      _ => CpuProfileTarget::Dir(self.initial_cwd.join(target)),
+    };
+    let period = pprof.and_then(|flags| flags.period).unwrap_or(60);
+    Some(CpuProfilerOptions {
+      target,
+      period: Duration::from_secs(period),
+    })
+  }
+
+  pub fn heap_profiler_options(&self) -> Option<HeapProfilerOptions> {
+    if !matches!(
+      self.flags.subcommand,
+      DenoSubcommand::Run(_) | DenoSubcommand::Serve(_)
+    ) {
+      return None;
+    }
+    let heap_prof = self.flags.heap_prof.as_ref()?;
+    let dir = match &heap_prof.dir {
+      Some(dir) => self.initial_cwd.join(dir),
+      None => self.initial_cwd.clone(),
+    };
+    Some(HeapProfilerOptions {
+      dir,
+      snapshot_signal: heap_prof
+        .snapshot_signal
+        .as_deref()
+        .and_then(|signal| deno_signals::signal_str_to_int(signal).ok()),
+      sampling: heap_prof.sampling,
+    })
+  }
+
   pub fn enable_op_summary_metrics(&self) -> bool {
     self.flags.enable_op_summary_metrics
       || matches!(
@@ -1035,6 +1104,24 @@ impl CliOptions {
     &self.flags.location
   }
 
+  pub fn storage_dir_flag(&self) -> &Option<String> {
+    &self.flags.storage_dir
+  }
+
+  /// Directory to persist origin storage such as `localStorage` in, when
+  /// set with `--storage-dir`.
+  pub fn storage_dir(&self) -> Option<PathBuf> {
+    self
+      .flags
+      .storage_dir
+      .as_ref()
+      .map(|dir| self.initial_cwd.join(dir))
+  }
+
+  pub fn storage_quota(&self) -> Option<u64> {
+    self.flags.storage_quota
+  }
+
   pub fn no_remote(&self) -> bool {
     self.flags.no_remote
   }
@@ -1356,9 +1443,12 @@ impl CliOptions {
     if let Some(env_file_names) = &self.flags.env_file {
       // Only watch the exact environment files specified
       full_paths.extend(
-        env_file_names
-          .iter()
-          .map(|name| self.initial_cwd.join(name)),
+        crate::util::env::resolve_env_file_paths(
+          env_file_names,
+          self.env_mode(),
+        )
+        .iter()
+        .map(|path| self.initial_cwd.join(path)),
       );
     }
 
@@ -67,6 +67,7 @@ use crate::args::DenoSubcommand;
 use crate::args::Flags;
 use crate::args::InstallFlags;
 use crate::args::InstallFlagsLocal;
+use crate::args::InstallTopLevelFlags;
 use crate::cache::Caches;
 use crate::cache::CodeCache;
 use crate::cache::DenoDir;
@@ -639,7 +640,9 @@ impl CliFactory {
             | DenoSubcommand::Deploy { .. }
             | DenoSubcommand::Doc { .. }
             | DenoSubcommand::Eval { .. }
+            | DenoSubcommand::Ffi { .. }
             | DenoSubcommand::Fmt { .. }
+            | DenoSubcommand::HeapDiff { .. }
             | DenoSubcommand::Init { .. }
             | DenoSubcommand::Info { .. }
             | DenoSubcommand::JSONReference { .. }
@@ -1190,6 +1193,8 @@ impl CliFactory {
         .or(std::env::args().next()),
       node_debug: std::env::var("NODE_DEBUG").ok(),
       origin_data_folder_path: Some(self.deno_dir()?.origin_data_folder_path()),
+      storage_dir: cli_options.storage_dir(),
+      storage_quota: cli_options.storage_quota(),
       seed: cli_options.seed(),
       unsafely_ignore_certificate_errors: cli_options
         .unsafely_ignore_certificate_errors()
@@ -1198,6 +1203,8 @@ impl CliFactory {
       serve_port: cli_options.serve_port(),
       serve_host: cli_options.serve_host(),
       otel_config: cli_options.otel_config(),
+      cpu_profiler: cli_options.cpu_profiler_options(),
+      heap_profiler: cli_options.heap_profiler_options(),
       no_legacy_abort: cli_options.no_legacy_abort(),
       startup_snapshot: deno_snapshots::CLI_SNAPSHOT,
       enable_raw_imports: cli_options.unstable_raw_imports(),
@@ -1222,6 +1229,8 @@ impl CliFactory {
       None
     };
     let maybe_coverage_dir = cli_options.coverage_dir();
+    let maybe_cpu_profiler = cli_options.cpu_profiler_options();
+    let maybe_heap_profiler = cli_options.heap_profiler_options();
 
     let initial_cwd =
       deno_path_util::url_from_directory_path(cli_options.initial_cwd())?;
@@ -1230,6 +1239,8 @@ impl CliFactory {
       needs_test_modules: cli_options.sub_command().needs_test(),
       create_hmr_runner,
       maybe_coverage_dir,
+      maybe_cpu_profiler,
+      maybe_heap_profiler,
       default_npm_caching_strategy: cli_options.default_npm_caching_strategy(),
       initial_cwd: Arc::new(initial_cwd),
     })
@@ -1389,6 +1400,15 @@ fn new_workspace_factory_options(
           | DenoSubcommand::Uninstall(_)
       ),
     frozen_lockfile: flags.frozen_lockfile,
+    import_external_lockfile: matches!(
+      &flags.subcommand,
+      DenoSubcommand::Install(InstallFlags::Local(
+        InstallFlagsLocal::TopLevel(InstallTopLevelFlags {
+          import_lockfile: true,
+          ..
+        })
+      ))
+    ),
     lock_arg: flags.lock.as_ref().map(|l| initial_cwd.join(l)),
     lockfile_skip_write: flags.internal.lockfile_skip_write,
     no_npm: flags.no_npm,
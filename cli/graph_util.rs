@@ -1,7 +1,9 @@
 // Copyright 2018-2026 the Deno authors. MIT license.
 
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -18,6 +20,7 @@ use deno_error::JsErrorClass;
 use deno_graph::CheckJsOption;
 use deno_graph::GraphKind;
 use deno_graph::JsrLoadError;
+use deno_graph::Module;
 use deno_graph::ModuleError;
 use deno_graph::ModuleErrorKind;
 use deno_graph::ModuleGraph;
@@ -382,6 +385,163 @@ fn exit_for_integrity_error(err: &ModuleError) {
   }
 }
 
+/// Gets the modules the provided module imports, including type imports.
+fn module_graph_dependencies<'a>(
+  graph: &'a ModuleGraph,
+  module: &'a Module,
+) -> Vec<&'a Module> {
+  let (maybe_types_dep, dependencies) = match module {
+    Module::Js(module) => {
+      (module.maybe_types_dependency.as_ref(), &module.dependencies)
+    }
+    Module::Wasm(module) => (None, &module.dependencies),
+    Module::Json(_)
+    | Module::Npm(_)
+    | Module::Node(_)
+    | Module::External(_) => return Vec::new(),
+  };
+  let resolutions = maybe_types_dep.map(|d| &d.dependency).into_iter().chain(
+    dependencies
+      .values()
+      .flat_map(|dep| [&dep.maybe_code, &dep.maybe_type]),
+  );
+  let mut seen = HashSet::new();
+  let mut modules = Vec::new();
+  for resolution in resolutions {
+    if let Some(specifier) = resolution.maybe_specifier()
+      && let Some(dep) = graph.get(specifier)
+      && seen.insert(dep.specifier())
+    {
+      modules.push(dep);
+    }
+  }
+  modules
+}
+
+/// Gets every chain of imports from the graph roots to the modules
+/// matching `is_target`.
+///
+/// A module is never visited twice within the same chain, so circular
+/// imports won't cause infinite chains. The number of chains grows
+/// exponentially with the number of shared imports, so this stops once
+/// `max_paths` chains have been found, and remembers the modules that
+/// led to no chain so they aren't walked again.
+pub fn graph_import_paths(
+  graph: &ModuleGraph,
+  is_target: impl Fn(&Module) -> bool,
+  max_paths: usize,
+) -> Vec<Vec<&Module>> {
+  // only walk into modules that have a target somewhere below them
+  let mut dependents: HashMap<&ModuleSpecifier, Vec<&Module>> = HashMap::new();
+  for module in graph.modules() {
+    for dep in module_graph_dependencies(graph, module) {
+      dependents.entry(dep.specifier()).or_default().push(module);
+    }
+  }
+  let mut reaches_target = HashSet::new();
+  let mut pending = graph
+    .modules()
+    .filter(|m| is_target(m))
+    .collect::<VecDeque<_>>();
+  while let Some(module) = pending.pop_front() {
+    if reaches_target.insert(module.specifier())
+      && let Some(dependents) = dependents.get(module.specifier())
+    {
+      pending.extend(dependents.iter().copied());
+    }
+  }
+
+  struct Context<'a, F: Fn(&Module) -> bool> {
+    graph: &'a ModuleGraph,
+    is_target: F,
+    reaches_target: HashSet<&'a ModuleSpecifier>,
+    max_paths: usize,
+    current_path: Vec<&'a Module>,
+    paths: Vec<Vec<&'a Module>>,
+    /// Modules that led to no chain, with how many modules of the current
+    /// chain blocked them.
+    dead_ends: HashMap<&'a ModuleSpecifier, usize>,
+    /// The dead ends to forget once the chain gets shorter than the index.
+    dead_ends_by_len: Vec<Vec<&'a ModuleSpecifier>>,
+  }
+
+  /// Returns `None` if a chain was found below the module, otherwise how
+  /// many modules at the start of the current chain kept it from finding one.
+  fn visit<'a, F: Fn(&Module) -> bool>(
+    ctx: &mut Context<'a, F>,
+    module: &'a Module,
+  ) -> Option<usize> {
+    let specifier = module.specifier();
+    if ctx.paths.len() >= ctx.max_paths {
+      return None;
+    }
+    if !ctx.reaches_target.contains(specifier) {
+      return Some(0);
+    }
+    if let Some(index) = ctx
+      .current_path
+      .iter()
+      .position(|m| m.specifier() == specifier)
+    {
+      return Some(index + 1);
+    }
+    if let Some(blocked_by) = ctx.dead_ends.get(specifier) {
+      return Some(*blocked_by);
+    }
+
+    let len = ctx.current_path.len();
+    ctx.current_path.push(module);
+    let mut found = (ctx.is_target)(module);
+    if found {
+      ctx.paths.push(ctx.current_path.clone());
+    }
+    let mut blocked_by = 0;
+    for dep in module_graph_dependencies(ctx.graph, module) {
+      match visit(ctx, dep) {
+        Some(dep_blocked_by) => blocked_by = blocked_by.max(dep_blocked_by),
+        None => found = true,
+      }
+    }
+    ctx.current_path.pop();
+
+    // dead ends below this module may have been blocked by it
+    if let Some(specifiers) = ctx.dead_ends_by_len.get_mut(len + 1) {
+      for specifier in specifiers.drain(..) {
+        ctx.dead_ends.remove(specifier);
+      }
+    }
+    if found {
+      return None;
+    }
+    let blocked_by = blocked_by.min(len);
+    ctx.dead_ends.insert(specifier, blocked_by);
+    if blocked_by > 0 {
+      if ctx.dead_ends_by_len.len() <= blocked_by {
+        ctx.dead_ends_by_len.resize_with(blocked_by + 1, Vec::new);
+      }
+      ctx.dead_ends_by_len[blocked_by].push(specifier);
+    }
+    Some(blocked_by)
+  }
+
+  let mut ctx = Context {
+    graph,
+    is_target,
+    reaches_target,
+    max_paths,
+    current_path: Vec::new(),
+    paths: Vec::new(),
+    dead_ends: HashMap::new(),
+    dead_ends_by_len: Vec::new(),
+  };
+  for root in &graph.roots {
+    if let Some(module) = graph.get(root) {
+      visit(&mut ctx, module);
+    }
+  }
+  ctx.paths
+}
+
 pub struct CreateGraphOptions<'a> {
   pub graph_kind: GraphKind,
   pub roots: Vec<ModuleSpecifier>,
@@ -71,6 +71,7 @@ use crate::args::get_default_v8_flags;
 use crate::util::display;
 use crate::util::env::WatchEnvTracker;
 use crate::util::env::load_env_variables_from_env_files;
+use crate::util::env::resolve_env_file_paths;
 use crate::util::v8::get_v8_flags_from_env;
 use crate::util::v8::init_v8_flags;
 
@@ -193,9 +194,17 @@ async fn run_subcommand(
         &[&*reporter],
       )
     }),
+    DenoSubcommand::Ffi(ffi_flags) => {
+      spawn_subcommand(async move { tools::ffi::ffi(ffi_flags) })
+    }
     DenoSubcommand::Fmt(fmt_flags) => spawn_subcommand(async move {
       tools::fmt::format(Arc::new(flags), fmt_flags).await
     }),
+    DenoSubcommand::HeapDiff(heap_diff_flags) => {
+      spawn_subcommand(
+        async move { tools::heap_diff::heap_diff(heap_diff_flags) },
+      )
+    }
     DenoSubcommand::Init(init_flags) => spawn_subcommand(async {
       tools::init::init_project(flags, init_flags).await
     }),
@@ -735,8 +744,12 @@ async fn resolve_flags_and_init(
   let env_file_paths: Option<Vec<std::path::PathBuf>> = flags
     .env_file
     .as_ref()
-    .map(|files| files.iter().map(PathBuf::from).collect());
-  load_env_variables_from_env_files(env_file_paths.as_ref(), flags.log_level);
+    .map(|files| resolve_env_file_paths(files, flags.env_mode.as_deref()));
+  load_env_variables_from_env_files(
+    env_file_paths.as_ref(),
+    flags.env_expand,
+    flags.log_level,
+  );
 
   if deno_lib::args::has_flag_env_var("DENO_CONNECTED") {
     flags.tunnel = true;
@@ -78,12 +78,20 @@ pub struct Metadata {
   pub code_cache_key: Option<u64>,
   pub permissions: PermissionsOptions,
   pub location: Option<Url>,
+  /// Value of `--storage-dir`, resolved against the current directory
+  /// when the binary runs.
+  pub storage_dir: Option<String>,
+  pub storage_quota: Option<u64>,
   pub v8_flags: Vec<String>,
   pub log_level: Option<log::Level>,
   pub ca_stores: Option<Vec<String>>,
   pub ca_data: Option<Vec<u8>>,
   pub unsafely_ignore_certificate_errors: Option<Vec<String>>,
   pub env_vars_from_env_file: IndexMap<String, String>,
+  /// Set when the references in `env_vars_from_env_file` should be
+  /// expanded when the binary runs, to the keys of the variables whose
+  /// values are literal.
+  pub env_vars_literal_keys: Option<Vec<String>>,
   pub workspace_resolver: SerializedWorkspaceResolver,
   pub entrypoint_key: String,
   pub preload_modules: Vec<String>,
@@ -1,5 +1,6 @@
 // Copyright 2018-2026 the Deno authors. MIT license.
 
+use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
@@ -19,6 +20,7 @@ use deno_runtime::UNSTABLE_FEATURES;
 use deno_runtime::WorkerExecutionMode;
 use deno_runtime::WorkerLogLevel;
 use deno_runtime::colors;
+use deno_runtime::cpu_profiler::CpuProfilerOptions;
 use deno_runtime::deno_core;
 use deno_runtime::deno_core::CompiledWasmModuleStore;
 use deno_runtime::deno_core::Extension;
@@ -40,7 +42,11 @@ use deno_runtime::deno_telemetry::OtelConfig;
 use deno_runtime::deno_tls::RootCertStoreProvider;
 use deno_runtime::deno_web::BlobStore;
 use deno_runtime::deno_web::InMemoryBroadcastChannel;
+#[cfg(unix)]
+use deno_runtime::deno_web::UnixSocketBroadcastChannelBackend;
+use deno_runtime::deno_webstorage::WebStorageRegistry;
 use deno_runtime::fmt_errors::format_js_error;
+use deno_runtime::heap_profiler::HeapProfilerOptions;
 use deno_runtime::ops::worker_host::CreateWebWorkerCb;
 use deno_runtime::web_worker::WebWorker;
 use deno_runtime::web_worker::WebWorkerOptions;
@@ -50,6 +56,7 @@ use deno_runtime::worker::WorkerOptions;
 use deno_runtime::worker::WorkerServiceOptions;
 use node_resolver::UrlOrPath;
 use node_resolver::errors::ResolvePkgJsonBinExportError;
+use parking_lot::Mutex;
 use url::Url;
 
 use crate::args::has_trace_permissions_enabled;
@@ -128,6 +135,20 @@ pub fn get_cache_storage_dir() -> PathBuf {
   std::env::temp_dir().join("deno_cache")
 }
 
+/// Unix sockets used to share `BroadcastChannel` messages between processes
+/// are created under this directory.
+#[cfg(unix)]
+pub fn get_broadcast_channel_root_dir() -> PathBuf {
+  // ok because this won't ever be used by the js runtime
+  #[allow(clippy::disallowed_methods)]
+  // Prefer the per-user runtime directory, as socket paths are limited in
+  // length and the temp directory can be long on macOS.
+  std::env::var_os("XDG_RUNTIME_DIR")
+    .filter(|dir| !dir.is_empty())
+    .map(PathBuf::from)
+    .unwrap_or_else(std::env::temp_dir)
+}
+
 /// By default V8 uses 1.4Gb heap limit which is meant for browser tabs.
 /// Instead probe for the total memory on the system and use it instead
 /// as a default. In case the platform is Linux and `DENO_USE_CGROUPS` is set,
@@ -247,7 +268,13 @@ pub struct LibMainWorkerOptions {
   pub argv0: Option<String>,
   pub node_debug: Option<String>,
   pub otel_config: OtelConfig,
+  pub cpu_profiler: Option<CpuProfilerOptions>,
+  pub heap_profiler: Option<HeapProfilerOptions>,
   pub origin_data_folder_path: Option<PathBuf>,
+  /// Directory to persist origin storage in, instead of a directory derived
+  /// from the storage key.
+  pub storage_dir: Option<PathBuf>,
+  pub storage_quota: Option<u64>,
   pub seed: Option<u64>,
   pub unsafely_ignore_certificate_errors: Option<Vec<String>>,
   pub skip_op_registration: bool,
@@ -268,6 +295,8 @@ pub struct LibWorkerFactoryRoots {
 struct LibWorkerFactorySharedState<TSys: DenoLibSys> {
   blob_store: Arc<BlobStore>,
   broadcast_channel: InMemoryBroadcastChannel,
+  /// Channels shared with other processes of the same app, by storage key.
+  app_broadcast_channels: Mutex<HashMap<String, InMemoryBroadcastChannel>>,
   code_cache: Option<Arc<dyn deno_runtime::code_cache::CodeCache>>,
   compiled_wasm_module_store: CompiledWasmModuleStore,
   deno_rt_native_addon_loader: Option<DenoRtNativeAddonLoaderRc>,
@@ -286,6 +315,7 @@ struct LibWorkerFactorySharedState<TSys: DenoLibSys> {
   sys: TSys,
   options: LibMainWorkerOptions,
   bundle_provider: Option<Arc<dyn BundleProvider>>,
+  web_storage: WebStorageRegistry,
 }
 
 impl<TSys: DenoLibSys> LibWorkerFactorySharedState<TSys> {
@@ -314,9 +344,43 @@ impl<TSys: DenoLibSys> LibWorkerFactorySharedState<TSys> {
     }
   }
 
+  /// Returns the broadcast channel for workers of the app identified by
+  /// `storage_key`. With `--unstable-broadcast-channel`, it is shared with
+  /// other processes of the same app where supported.
+  fn broadcast_channel(
+    &self,
+    storage_key: Option<&str>,
+  ) -> InMemoryBroadcastChannel {
+    #[cfg(unix)]
+    if let Some(storage_key) = storage_key
+      && self.feature_checker.check("broadcast-channel")
+    {
+      return self
+        .app_broadcast_channels
+        .lock()
+        .entry(storage_key.to_string())
+        .or_insert_with(|| {
+          let app_id = checksum::r#gen(&[storage_key.as_bytes()]);
+          InMemoryBroadcastChannel::with_backend(Box::new(
+            UnixSocketBroadcastChannelBackend::new(
+              &get_broadcast_channel_root_dir(),
+              // Socket paths are limited in length.
+              &app_id[..16],
+            ),
+          ))
+        })
+        .clone();
+    }
+    #[cfg(not(unix))]
+    let _ = storage_key;
+    self.broadcast_channel.clone()
+  }
+
   fn create_web_worker_callback(
     self: &Arc<Self>,
     stdio: deno_runtime::deno_io::Stdio,
+    broadcast_channel: InMemoryBroadcastChannel,
+    origin_storage_dir: Option<PathBuf>,
   ) -> Arc<CreateWebWorkerCb> {
     let shared = self.clone();
     Arc::new(move |args| {
@@ -327,8 +391,11 @@ impl<TSys: DenoLibSys> LibWorkerFactorySharedState<TSys> {
         args.parent_permissions.clone(),
         args.permissions.clone(),
       );
-      let create_web_worker_cb =
-        shared.create_web_worker_callback(stdio.clone());
+      let create_web_worker_cb = shared.create_web_worker_callback(
+        stdio.clone(),
+        broadcast_channel.clone(),
+        origin_storage_dir.clone(),
+      );
 
       let maybe_storage_key = shared
         .storage_key_resolver
@@ -353,7 +420,7 @@ impl<TSys: DenoLibSys> LibWorkerFactorySharedState<TSys> {
           shared.create_node_init_services(node_require_loader),
         ),
         blob_store: shared.blob_store.clone(),
-        broadcast_channel: shared.broadcast_channel.clone(),
+        broadcast_channel: broadcast_channel.clone(),
         shared_array_buffer_store: Some(
           shared.shared_array_buffer_store.clone(),
         ),
@@ -367,6 +434,7 @@ impl<TSys: DenoLibSys> LibWorkerFactorySharedState<TSys> {
         ),
         permissions: args.permissions,
         bundle_provider: shared.bundle_provider.clone(),
+        web_storage: shared.web_storage.clone(),
       };
       let maybe_initial_cwd = shared.options.maybe_initial_cwd.clone();
       let options = WebWorkerOptions {
@@ -416,10 +484,14 @@ impl<TSys: DenoLibSys> LibWorkerFactorySharedState<TSys> {
         worker_type: args.worker_type,
         stdio: stdio.clone(),
         cache_storage_dir,
+        origin_storage_dir: origin_storage_dir.clone(),
+        storage_quota: shared.options.storage_quota,
         trace_ops: shared.options.trace_ops.clone(),
         close_on_idle: args.close_on_idle,
         maybe_worker_metadata: args.maybe_worker_metadata,
         maybe_coverage_dir: shared.maybe_coverage_dir.clone(),
+        maybe_cpu_profiler: shared.options.cpu_profiler.clone(),
+        maybe_heap_profiler: shared.options.heap_profiler.clone(),
         enable_raw_imports: shared.options.enable_raw_imports,
         enable_stack_trace_arg_in_ops: has_trace_permissions_enabled(),
       };
@@ -459,6 +531,7 @@ impl<TSys: DenoLibSys> LibMainWorkerFactory<TSys> {
       shared: Arc::new(LibWorkerFactorySharedState {
         blob_store,
         broadcast_channel: Default::default(),
+        app_broadcast_channels: Default::default(),
         code_cache,
         compiled_wasm_module_store: roots.compiled_wasm_module_store,
         deno_rt_native_addon_loader,
@@ -476,6 +549,7 @@ impl<TSys: DenoLibSys> LibMainWorkerFactory<TSys> {
         sys,
         options,
         bundle_provider,
+        web_storage: Default::default(),
       }),
     }
   }
@@ -530,14 +604,18 @@ impl<TSys: DenoLibSys> LibMainWorkerFactory<TSys> {
     let maybe_storage_key = shared
       .storage_key_resolver
       .resolve_storage_key(&main_module);
+    let broadcast_channel =
+      shared.broadcast_channel(maybe_storage_key.as_deref());
     let origin_storage_dir: Option<PathBuf> =
-      maybe_storage_key.as_ref().map(|key| {
-        shared
-          .options
-          .origin_data_folder_path
-          .as_ref()
-          .unwrap() // must be set if storage key resolver returns a value
-          .join(checksum::r#gen(&[key.as_bytes()]))
+      shared.options.storage_dir.clone().or_else(|| {
+        maybe_storage_key.as_ref().map(|key| {
+          shared
+            .options
+            .origin_data_folder_path
+            .as_ref()
+            .unwrap() // must be set if storage key resolver returns a value
+            .join(checksum::r#gen(&[key.as_bytes()]))
+        })
       });
     let cache_storage_dir = maybe_storage_key.map(|key| {
       // TODO(@satyarohith): storage quota management
@@ -556,8 +634,9 @@ impl<TSys: DenoLibSys> LibMainWorkerFactory<TSys> {
         shared.npm_process_state_provider.clone(),
       ),
       blob_store: shared.blob_store.clone(),
-      broadcast_channel: shared.broadcast_channel.clone(),
+      broadcast_channel: broadcast_channel.clone(),
       fetch_dns_resolver: Default::default(),
+      web_storage: shared.web_storage.clone(),
       shared_array_buffer_store: Some(shared.shared_array_buffer_store.clone()),
       compiled_wasm_module_store: Some(
         shared.compiled_wasm_module_store.clone(),
@@ -610,12 +689,17 @@ impl<TSys: DenoLibSys> LibMainWorkerFactory<TSys> {
       format_js_error_fn: Some(Arc::new(move |e| {
         format_js_error(e, maybe_initial_cwd.as_ref())
       })),
-      create_web_worker_cb: shared.create_web_worker_callback(stdio.clone()),
+      create_web_worker_cb: shared.create_web_worker_callback(
+        stdio.clone(),
+        broadcast_channel,
+        origin_storage_dir.clone(),
+      ),
       should_break_on_first_statement: shared.options.inspect_brk,
       should_wait_for_inspector_session: shared.options.inspect_wait,
       trace_ops: shared.options.trace_ops.clone(),
       cache_storage_dir,
       origin_storage_dir,
+      storage_quota: shared.options.storage_quota,
       stdio,
       skip_op_registration: shared.options.skip_op_registration,
       enable_raw_imports: shared.options.enable_raw_imports,
@@ -1437,6 +1437,7 @@ impl ConfigData {
         maybe_custom_deno_dir_root: None,
         is_package_manager_subcommand: false,
         frozen_lockfile: None,
+        import_external_lockfile: false,
         lock_arg: None,
         lockfile_skip_write: true,
         node_modules_dir: Some(resolve_node_modules_dir_mode(
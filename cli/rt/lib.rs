@@ -53,12 +53,28 @@ fn unwrap_or_exit<T>(result: Result<T, AnyError>) -> T {
   }
 }
 
-fn load_env_vars(env_vars: &IndexMap<String, String>) {
-  env_vars.iter().for_each(|env_var| {
-    if env::var(env_var.0).is_err() {
+fn load_env_vars(
+  env_vars: &IndexMap<String, String>,
+  literal_keys: Option<&[String]>,
+) {
+  let mut env_vars = env_vars
+    .iter()
+    .map(|(key, value)| deno_dotenv::EnvVar {
+      key: key.clone(),
+      value: value.clone(),
+      is_literal: literal_keys.is_some_and(|keys| keys.contains(key)),
+    })
+    .collect::<Vec<_>>();
+  if literal_keys.is_some() {
+    // expanded with the environment the binary runs in rather than the
+    // one it was compiled in
+    deno_dotenv::expand_vars(&mut env_vars, |name| env::var(name).ok());
+  }
+  env_vars.into_iter().for_each(|env_var| {
+    if env::var(&env_var.key).is_err() {
       #[allow(clippy::undocumented_unsafe_blocks)]
       unsafe {
-        std::env::set_var(env_var.0, env_var.1)
+        std::env::set_var(env_var.key, env_var.value)
       };
     }
   })
@@ -87,7 +103,10 @@ pub fn main() {
           data.metadata.log_level,
           Some(data.metadata.otel_config.clone()),
         );
-        load_env_vars(&data.metadata.env_vars_from_env_file);
+        load_env_vars(
+          &data.metadata.env_vars_from_env_file,
+          data.metadata.env_vars_literal_keys.as_deref(),
+        );
         let sys = if data.metadata.self_extracting.is_some() {
           binary::extract_vfs_to_disk(&data.vfs, &data.root_path)?;
           DenoRtSys::new_self_extracting(data.vfs.clone())
@@ -49,6 +49,7 @@ use deno_media_type::MediaType;
 use deno_npm::npm_rc::ResolvedNpmRc;
 use deno_npm::resolution::NpmResolutionSnapshot;
 use deno_package_json::PackageJsonDepValue;
+use deno_package_json::PackageJsonDepValueParseErrorKind;
 use deno_resolver::DenoResolveErrorKind;
 use deno_resolver::cjs::CjsTracker;
 use deno_resolver::cjs::IsCjsResolutionMode;
@@ -287,6 +288,13 @@ impl ModuleLoader for EmbeddedModuleLoader {
               })?,
           )
         }
+        PackageJsonDepValue::Catalog(catalog) => Err(JsErrorBox::from_err(
+          PackageJsonDepValueParseErrorKind::CatalogEntryNotFound {
+            catalog: catalog.to_string(),
+            alias: alias.to_string(),
+          }
+          .into_box(),
+        )),
       },
       Ok(MappedResolution::PackageJsonImport { pkg_json }) => self
         .shared
@@ -1052,6 +1060,10 @@ pub async fn run(
       .or(std::env::args().next()),
     node_debug: std::env::var("NODE_DEBUG").ok(),
     origin_data_folder_path: None,
+    storage_dir: metadata
+      .storage_dir
+      .map(|dir| std::path::absolute(&dir).unwrap_or_else(|_| dir.into())),
+    storage_quota: metadata.storage_quota,
     seed: metadata.seed,
     unsafely_ignore_certificate_errors: metadata
       .unsafely_ignore_certificate_errors,
@@ -1059,6 +1071,8 @@ pub async fn run(
     serve_port: None,
     serve_host: None,
     otel_config: metadata.otel_config,
+    cpu_profiler: None,
+    heap_profiler: None,
     no_legacy_abort: false,
     startup_snapshot: deno_snapshots::CLI_SNAPSHOT,
     enable_raw_imports: metadata.unstable_config.raw_imports,
@@ -74,6 +74,7 @@ use crate::npm::CliNpmResolver;
 use crate::resolver::CliCjsTracker;
 use crate::sys::CliSys;
 use crate::util::archive;
+use crate::util::env::resolve_env_file_paths;
 use crate::util::progress_bar::ProgressBar;
 use crate::util::progress_bar::ProgressBarStyle;
 
@@ -711,20 +712,40 @@ impl<'a> DenoCompileBinaryWriter<'a> {
       }),
     };
 
-    let env_vars_from_env_file = match self.cli_options.env_file_name() {
+    let (env_vars_from_env_file, env_vars_literal_keys) = match self
+      .cli_options
+      .env_file_name()
+    {
       Some(env_filenames) => {
+        let env_paths =
+          resolve_env_file_paths(env_filenames, self.cli_options.env_mode());
         let mut aggregated_env_vars = IndexMap::new();
-        for env_filename in env_filenames.iter().rev() {
+        // later files take precedence, the same as when running
+        for env_path in env_paths.iter().rev() {
           log::info!(
             "{} Environment variables from the file \"{}\" were embedded in the generated executable file",
             crate::colors::yellow("Warning"),
-            env_filename
+            env_path.display()
           );
 
-          let env_vars = get_file_env_vars(env_filename.to_string())?;
-          aggregated_env_vars.extend(env_vars);
+          for (key, var) in get_file_env_vars(env_path)? {
+            aggregated_env_vars.entry(key).or_insert(var);
+          }
         }
-        aggregated_env_vars
+        // the references are expanded when the binary runs so that they
+        // resolve with the environment it runs in
+        let literal_keys = self.cli_options.env_expand().then(|| {
+          aggregated_env_vars
+            .values()
+            .filter(|var| var.is_literal)
+            .map(|var| var.key.clone())
+            .collect()
+        });
+        let env_vars = aggregated_env_vars
+          .into_iter()
+          .map(|(key, var)| (key, var.value))
+          .collect();
+        (env_vars, literal_keys)
       }
       None => Default::default(),
     };
@@ -750,6 +771,8 @@ impl<'a> DenoCompileBinaryWriter<'a> {
       seed: self.cli_options.seed(),
       code_cache_key,
       location: self.cli_options.location_flag().clone(),
+      storage_dir: self.cli_options.storage_dir_flag().clone(),
+      storage_quota: self.cli_options.storage_quota(),
       permissions: self.cli_options.permissions_options()?,
       v8_flags: construct_v8_flags(
         &get_default_v8_flags(),
@@ -764,6 +787,7 @@ impl<'a> DenoCompileBinaryWriter<'a> {
       ca_stores: self.cli_options.ca_stores().clone(),
       ca_data,
       env_vars_from_env_file,
+      env_vars_literal_keys,
       entrypoint_key: root_dir_url.specifier_key(entrypoint).into_owned(),
       preload_modules,
       require_modules,
@@ -1266,14 +1290,14 @@ fn get_dev_binary_path() -> Option<OsString> {
 /// This function returns the environment variables specified
 /// in the passed environment file.
 fn get_file_env_vars(
-  filename: String,
-) -> Result<IndexMap<String, String>, deno_dotenv::Error> {
+  path: &Path,
+) -> Result<IndexMap<String, deno_dotenv::EnvVar>, deno_dotenv::Error> {
   let mut file_env_vars = IndexMap::new();
-  for item in deno_dotenv::from_path_sanitized_iter(filename)? {
-    let Ok((key, val)) = item else {
+  for item in deno_dotenv::from_path_sanitized_vars(path)? {
+    let Ok(var) = item else {
       continue; // this failure will be warned about on load
     };
-    file_env_vars.insert(key, val);
+    file_env_vars.insert(var.key.clone(), var);
   }
   Ok(file_env_vars)
 }
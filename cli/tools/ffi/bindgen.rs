@@ -0,0 +1,1144 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+//! Generates a TypeScript module of `Deno.dlopen` bindings from a parsed C
+//! header.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::rc::Rc;
+
+use super::c_header::CType;
+use super::c_header::ConstValue;
+use super::c_header::Header;
+use super::c_header::Record;
+
+pub struct Bindings {
+  pub code: String,
+  pub warnings: Vec<String>,
+}
+
+/// Generates bindings for `header`, which was read from `source_name`.
+pub fn generate(header: &Header, source_name: &str) -> Bindings {
+  let mut generator = Generator::new(header);
+  let body = generator.body();
+  let mut code = String::new();
+  writeln!(
+    code,
+    "// Generated by `deno ffi bindgen` from {source_name}."
+  )
+  .unwrap();
+  writeln!(
+    code,
+    "// Struct layouts and `long` are sized for {}-{}.",
+    std::env::consts::ARCH,
+    std::env::consts::OS
+  )
+  .unwrap();
+  code.push_str(PRELUDE);
+  if generator.uses_structs {
+    code.push_str(STRUCT_PRELUDE);
+  }
+  code.push_str(&body);
+  Bindings {
+    code,
+    warnings: generator.warnings,
+  }
+}
+
+const PRELUDE: &str = r#"
+const encoder = new TextEncoder();
+
+/** Encodes a string as a NUL-terminated UTF-8 buffer for `char *` arguments. */
+export function cstring(value: string): Uint8Array<ArrayBuffer> {
+  return encoder.encode(`${value}\0`);
+}
+
+/** Reads a NUL-terminated UTF-8 string, or `null` for a null pointer. */
+export function readCString(pointer: Deno.PointerValue): string | null {
+  return pointer === null ? null : Deno.UnsafePointerView.getCString(pointer);
+}
+"#;
+
+const STRUCT_PRELUDE: &str = r#"
+// Deno only runs on little-endian targets.
+const littleEndian = true;
+
+function toView(
+  source: Uint8Array | Deno.PointerObject,
+  size: number,
+): DataView {
+  if (source instanceof Uint8Array) {
+    if (source.byteLength < size) {
+      throw new RangeError(
+        `Expected at least ${size} bytes, received ${source.byteLength}`,
+      );
+    }
+    return new DataView(source.buffer, source.byteOffset, size);
+  }
+  return new DataView(Deno.UnsafePointerView.getArrayBuffer(source, size));
+}
+"#;
+
+const RESERVED_WORDS: &[&str] = &[
+  "arguments",
+  "await",
+  "break",
+  "case",
+  "catch",
+  "class",
+  "const",
+  "continue",
+  "debugger",
+  "default",
+  "delete",
+  "do",
+  "else",
+  "enum",
+  "eval",
+  "export",
+  "extends",
+  "false",
+  "finally",
+  "for",
+  "function",
+  "if",
+  "implements",
+  "import",
+  "in",
+  "instanceof",
+  "interface",
+  "let",
+  "new",
+  "null",
+  "package",
+  "private",
+  "protected",
+  "public",
+  "return",
+  "static",
+  "super",
+  "switch",
+  "this",
+  "throw",
+  "true",
+  "try",
+  "typeof",
+  "var",
+  "void",
+  "while",
+  "with",
+  "yield",
+];
+
+/// Names used by the generated module itself.
+const GENERATED_NAMES: &[&str] = &[
+  "encoder",
+  "cstring",
+  "readCString",
+  "littleEndian",
+  "toView",
+  "symbols",
+  "Library",
+  "open",
+];
+
+#[derive(Clone, Copy, PartialEq)]
+enum Position {
+  Parameter,
+  Result,
+  Static,
+  Field,
+}
+
+struct FieldLayout {
+  name: String,
+  ty: CType,
+  offset: u64,
+  /// Whether the field is a union member, sharing its storage with others.
+  shared: bool,
+}
+
+struct RecordLayout {
+  is_union: bool,
+  size: u64,
+  align: u64,
+  fields: Vec<FieldLayout>,
+}
+
+struct Generator<'a> {
+  header: &'a Header,
+  /// The TypeScript names of records, with anonymous records named after
+  /// the field holding them.
+  record_names: Vec<Option<String>>,
+  /// The TypeScript names of named enums.
+  enum_names: Vec<Option<String>>,
+  layouts: Vec<Option<Result<Rc<RecordLayout>, String>>>,
+  /// Top-level names in the generated module.
+  names: HashSet<String>,
+  uses_structs: bool,
+  warnings: Vec<String>,
+}
+
+impl<'a> Generator<'a> {
+  fn new(header: &'a Header) -> Self {
+    let mut record_names = header
+      .records
+      .iter()
+      .map(|record| record.name.clone())
+      .collect::<Vec<_>>();
+    let mut by_order = (0..header.records.len()).collect::<Vec<_>>();
+    by_order
+      .sort_by_key(|index| std::cmp::Reverse(header.records[*index].order));
+    for index in by_order {
+      let Some(parent) = record_names[index].clone() else {
+        continue;
+      };
+      for field in header.records[index].fields.iter().flatten() {
+        let mut ty = &field.ty;
+        while let CType::Array(element, _) = ty {
+          ty = element;
+        }
+        if let (Some(name), CType::Record(child)) = (&field.name, ty)
+          && record_names[*child].is_none()
+        {
+          record_names[*child] = Some(format!("{parent}_{name}"));
+        }
+      }
+    }
+    Generator {
+      header,
+      record_names,
+      enum_names: vec![None; header.enums.len()],
+      layouts: (0..header.records.len()).map(|_| None).collect(),
+      names: GENERATED_NAMES
+        .iter()
+        .map(|name| name.to_string())
+        .collect(),
+      uses_structs: false,
+      warnings: Vec::new(),
+    }
+  }
+
+  /// Reserves a top-level name, renaming it if it is a reserved word or
+  /// already taken.
+  fn unique_name(&mut self, name: &str) -> String {
+    let mut name = name.to_string();
+    if RESERVED_WORDS.contains(&name.as_str()) {
+      name.push('_');
+    }
+    while !self.names.insert(name.clone()) {
+      name.push('_');
+    }
+    name
+  }
+
+  fn body(&mut self) -> String {
+    let mut out = String::new();
+    self.constants(&mut out);
+    self.enums(&mut out);
+    self.records(&mut out);
+    self.symbols(&mut out);
+    out
+  }
+
+  fn constants(&mut self, out: &mut String) {
+    if self.header.constants.is_empty() {
+      return;
+    }
+    out.push('\n');
+    for constant in &self.header.constants {
+      let name = self.unique_name(&constant.name);
+      let value = match &constant.value {
+        ConstValue::Int(value) => int_literal(*value),
+        ConstValue::Float(value) => float_literal(*value),
+        ConstValue::Str(value) => string_literal(value),
+      };
+      writeln!(out, "export const {name} = {value};").unwrap();
+    }
+  }
+
+  fn enums(&mut self, out: &mut String) {
+    for (index, enum_) in self.header.enums.iter().enumerate() {
+      if enum_.variants.is_empty() {
+        continue;
+      }
+      out.push('\n');
+      let Some(name) = &enum_.name else {
+        // The enumerators of anonymous enums are plain constants.
+        for (variant, value) in &enum_.variants {
+          let variant = self.unique_name(variant);
+          writeln!(out, "export const {variant} = {};", int_literal(*value))
+            .unwrap();
+        }
+        continue;
+      };
+      let name = self.unique_name(name);
+      writeln!(out, "export const {name} = {{").unwrap();
+      for (variant, value) in &enum_.variants {
+        writeln!(out, "  {variant}: {},", int_literal(*value)).unwrap();
+      }
+      writeln!(out, "}} as const;").unwrap();
+      writeln!(
+        out,
+        "export type {name} = typeof {name}[keyof typeof {name}];"
+      )
+      .unwrap();
+      self.enum_names[index] = Some(name);
+    }
+  }
+
+  fn records(&mut self, out: &mut String) {
+    let mut records = (0..self.header.records.len())
+      .filter(|index| {
+        let record = &self.header.records[*index];
+        record.fields.is_some() && self.record_names[*index].is_some()
+      })
+      .collect::<Vec<_>>();
+    records.sort_by_key(|index| self.header.records[*index].order);
+    for index in records {
+      let name = self.record_names[index].clone().unwrap();
+      let kind = record_kind(&self.header.records[index]);
+      let layout = match self.record_layout(index) {
+        Ok(layout) => layout,
+        Err(err) => {
+          self
+            .warnings
+            .push(format!("skipping {kind} `{name}`: {err}"));
+          continue;
+        }
+      };
+      let native_types = match self.record_native_types(index) {
+        Ok(native_types) => native_types,
+        Err(err) => {
+          self
+            .warnings
+            .push(format!("skipping {kind} `{name}`: {err}"));
+          self.layouts[index] = Some(Err(err));
+          continue;
+        }
+      };
+      let name = self.unique_name(&name);
+      self.record_names[index] = Some(name.clone());
+      self.uses_structs = true;
+      self.record(out, &name, &layout, &native_types);
+    }
+  }
+
+  fn record(
+    &self,
+    out: &mut String,
+    name: &str,
+    layout: &RecordLayout,
+    native_types: &[String],
+  ) {
+    let read = prefixed("read", name);
+    let write = prefixed("write", name);
+    let sizeof = prefixed("sizeof", name);
+    let decode = prefixed("decode", name);
+    let encode = prefixed("encode", name);
+    out.push('\n');
+    // Union members share their storage, so only the members present in a
+    // value are encoded.
+    let kind = if layout.is_union { "union" } else { "struct" };
+    writeln!(out, "export interface {name} {{").unwrap();
+    for field in &layout.fields {
+      let optional = if field.shared { "?" } else { "" };
+      let ty = self.ts_type(&field.ty);
+      writeln!(out, "  {}{optional}: {ty};", field.name).unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+    let single_line = format!(
+      "export const {name} = {{ {kind}: [{}] }} as const;",
+      native_types.join(", ")
+    );
+    if single_line.len() <= 80 {
+      writeln!(out, "{single_line}").unwrap();
+    } else {
+      writeln!(out, "export const {name} = {{").unwrap();
+      writeln!(out, "  {kind}: [").unwrap();
+      for native_type in native_types {
+        writeln!(out, "    {native_type},").unwrap();
+      }
+      writeln!(out, "  ],").unwrap();
+      writeln!(out, "}} as const;").unwrap();
+    }
+    writeln!(out, "export const {sizeof} = {};", layout.size).unwrap();
+
+    out.push('\n');
+    writeln!(
+      out,
+      "function {read}(view: DataView, offset: number): {name} {{"
+    )
+    .unwrap();
+    writeln!(out, "  return {{").unwrap();
+    for field in &layout.fields {
+      let offset = offset_expr("offset", field.offset);
+      let value = self.read(&field.ty, &offset, 0);
+      writeln!(out, "    {}: {value},", field.name).unwrap();
+    }
+    writeln!(out, "  }};").unwrap();
+    writeln!(out, "}}").unwrap();
+
+    out.push('\n');
+    writeln!(
+      out,
+      "function {write}(view: DataView, offset: number, value: {name}) {{"
+    )
+    .unwrap();
+    for field in &layout.fields {
+      let offset = offset_expr("offset", field.offset);
+      let value = format!("value.{}", field.name);
+      if field.shared {
+        let statement = self.write(&field.ty, &offset, &value, 0, "    ");
+        writeln!(out, "  if ({value} !== undefined) {{").unwrap();
+        writeln!(out, "    {statement}").unwrap();
+        writeln!(out, "  }}").unwrap();
+      } else {
+        let statement = self.write(&field.ty, &offset, &value, 0, "  ");
+        writeln!(out, "  {statement}").unwrap();
+      }
+    }
+    writeln!(out, "}}").unwrap();
+
+    out.push('\n');
+    writeln!(
+      out,
+      "/** Reads a `{name}` returned by value or stored behind a pointer. */"
+    )
+    .unwrap();
+    writeln!(out, "export function {decode}(").unwrap();
+    writeln!(out, "  source: Uint8Array | Deno.PointerObject,").unwrap();
+    writeln!(out, "): {name} {{").unwrap();
+    writeln!(out, "  return {read}(toView(source, {sizeof}), 0);").unwrap();
+    writeln!(out, "}}").unwrap();
+
+    out.push('\n');
+    writeln!(
+      out,
+      "/** Encodes a `{name}` to pass by value or by pointer. */"
+    )
+    .unwrap();
+    writeln!(
+      out,
+      "export function {encode}(value: {name}): Uint8Array<ArrayBuffer> {{"
+    )
+    .unwrap();
+    writeln!(out, "  const buffer = new Uint8Array({sizeof});").unwrap();
+    writeln!(out, "  {write}(new DataView(buffer.buffer), 0, value);").unwrap();
+    writeln!(out, "  return buffer;").unwrap();
+    writeln!(out, "}}").unwrap();
+  }
+
+  fn symbols(&mut self, out: &mut String) {
+    let mut entries = Vec::new();
+    for function in &self.header.functions {
+      let parameters = function
+        .ty
+        .params
+        .iter()
+        .map(|param| self.native_type(&param.ty, Position::Parameter))
+        .collect::<Result<Vec<_>, _>>();
+      let result = self.native_type(&function.ty.result, Position::Result);
+      let entry = match (parameters, result) {
+        (Ok(parameters), Ok(result)) => Ok((parameters, result)),
+        (Err(err), _) | (_, Err(err)) => Err(err),
+      };
+      match entry {
+        Ok((parameters, result)) => {
+          entries.push(function_entry(
+            &function.name,
+            &parameters,
+            &result,
+            function.ty.variadic,
+          ));
+        }
+        Err(err) => self.warnings.push(format!(
+          "line {}: skipping function `{}`: {err}",
+          function.line, function.name
+        )),
+      }
+    }
+    for variable in &self.header.variables {
+      match self.native_type(&variable.ty, Position::Static) {
+        Ok(ty) => {
+          entries.push(format!("  {}: {{ type: {ty} }},", variable.name))
+        }
+        Err(err) => self.warnings.push(format!(
+          "line {}: skipping variable `{}`: {err}",
+          variable.line, variable.name
+        )),
+      }
+    }
+
+    out.push('\n');
+    writeln!(out, "export const symbols = {{").unwrap();
+    for entry in entries {
+      writeln!(out, "{entry}").unwrap();
+    }
+    writeln!(out, "}} as const satisfies Deno.ForeignLibraryInterface;")
+      .unwrap();
+    out.push('\n');
+    writeln!(
+      out,
+      "export type Library = Deno.DynamicLibrary<typeof symbols>;"
+    )
+    .unwrap();
+    out.push('\n');
+    writeln!(out, "export function open(path: string | URL): Library {{")
+      .unwrap();
+    writeln!(out, "  return Deno.dlopen(path, symbols);").unwrap();
+    writeln!(out, "}}").unwrap();
+  }
+
+  fn record_layout(
+    &mut self,
+    index: usize,
+  ) -> Result<Rc<RecordLayout>, String> {
+    if let Some(layout) = &self.layouts[index] {
+      return layout.clone();
+    }
+    // Guards against records that contain themselves.
+    let kind = record_kind(&self.header.records[index]);
+    self.layouts[index] = Some(Err(format!("{kind} contains itself")));
+    let layout = self.compute_record_layout(index).map(Rc::new);
+    self.layouts[index] = Some(layout.clone());
+    layout
+  }
+
+  fn compute_record_layout(
+    &mut self,
+    index: usize,
+  ) -> Result<RecordLayout, String> {
+    let record = &self.header.records[index];
+    let Some(fields) = &record.fields else {
+      return Err(format!("{} is only forward declared", record_kind(record)));
+    };
+    if let Some(reason) = record.unsupported {
+      return Err(reason.to_string());
+    }
+    let is_union = record.is_union;
+    if fields.is_empty() {
+      return Err(format!("empty {}s are not supported", record_kind(record)));
+    }
+    let mut layout = RecordLayout {
+      is_union,
+      size: 0,
+      align: 1,
+      fields: Vec::new(),
+    };
+    for field in fields {
+      if field.bit_width.is_some() {
+        return Err("bit-fields are not supported".to_string());
+      }
+      let (size, align) = self.type_layout(&field.ty)?;
+      // Union members all start at the beginning of the union.
+      let offset = if is_union {
+        0
+      } else {
+        layout.size.next_multiple_of(align)
+      };
+      match (&field.name, &field.ty) {
+        (Some(name), _) => layout.fields.push(FieldLayout {
+          name: name.clone(),
+          ty: field.ty.clone(),
+          offset,
+          shared: is_union,
+        }),
+        // Members of anonymous structs belong to the containing struct.
+        (None, CType::Record(inner)) => {
+          let inner = self.record_layout(*inner)?;
+          layout
+            .fields
+            .extend(inner.fields.iter().map(|field| FieldLayout {
+              name: field.name.clone(),
+              ty: field.ty.clone(),
+              offset: offset + field.offset,
+              shared: is_union || field.shared,
+            }));
+        }
+        (None, _) => {}
+      }
+      layout.size = layout.size.max(offset + size);
+      layout.align = layout.align.max(align);
+    }
+    layout.size = layout.size.next_multiple_of(layout.align);
+    Ok(layout)
+  }
+
+  /// The size and alignment of a type.
+  fn type_layout(&mut self, ty: &CType) -> Result<(u64, u64), String> {
+    Ok(match ty {
+      CType::Bool => (1, 1),
+      CType::Int { size, .. } => (*size as u64, *size as u64),
+      CType::Float => (4, 4),
+      CType::Double | CType::Pointer(_) => (8, 8),
+      CType::Array(_, Some(0)) => {
+        return Err("zero-length arrays are not supported".to_string());
+      }
+      CType::Array(element, Some(len)) => {
+        let (size, align) = self.type_layout(element)?;
+        (size * len, align)
+      }
+      CType::Array(_, None) => {
+        return Err("flexible array members are not supported".to_string());
+      }
+      CType::Record(index) => {
+        let layout = self.record_layout(*index)?;
+        (layout.size, layout.align)
+      }
+      CType::Enum(index) => {
+        return self.type_layout(&self.header.enums[*index].ty);
+      }
+      CType::Void | CType::Function(_) => {
+        return Err("function and `void` types have no size".to_string());
+      }
+      CType::Unknown(name) => return Err(format!("unknown type `{name}`")),
+      CType::Unsupported(what) => {
+        return Err(format!("`{what}` is not supported"));
+      }
+    })
+  }
+
+  /// The field entries of a record type. Anonymous members stay nested, as
+  /// they are laid out like a named member of their type.
+  fn record_native_types(
+    &mut self,
+    index: usize,
+  ) -> Result<Vec<String>, String> {
+    let header = self.header;
+    let mut native_types = Vec::new();
+    for field in header.records[index].fields.iter().flatten() {
+      match (&field.name, &field.ty) {
+        (Some(_), ty) => native_types.push(self.field_native_type(ty)?),
+        (None, CType::Record(inner)) => native_types.push(format!(
+          "{{ {}: [{}] }}",
+          record_kind(&header.records[*inner]),
+          self.record_native_types(*inner)?.join(", ")
+        )),
+        (None, _) => {}
+      }
+    }
+    Ok(native_types)
+  }
+
+  /// The `Deno.NativeFieldType` expression for a struct or union field.
+  fn field_native_type(&mut self, ty: &CType) -> Result<String, String> {
+    match ty {
+      CType::Array(element, Some(len)) => Ok(format!(
+        "{{ array: {{ type: {}, length: {len} }} }}",
+        self.field_native_type(element)?
+      )),
+      ty => self.native_type(ty, Position::Field),
+    }
+  }
+
+  /// The `Deno.NativeType` expression for a type.
+  fn native_type(
+    &mut self,
+    ty: &CType,
+    position: Position,
+  ) -> Result<String, String> {
+    Ok(match ty {
+      CType::Void if position == Position::Result => "\"void\"".to_string(),
+      CType::Bool => "\"bool\"".to_string(),
+      CType::Int { .. } => format!("\"{}\"", int_native_type(ty)),
+      CType::Float => "\"f32\"".to_string(),
+      CType::Double => "\"f64\"".to_string(),
+      CType::Pointer(pointee) => match (position, &**pointee) {
+        (
+          Position::Parameter,
+          CType::Bool
+          | CType::Int { .. }
+          | CType::Float
+          | CType::Double
+          | CType::Enum(_),
+        ) => "\"buffer\"".to_string(),
+        (Position::Parameter | Position::Result, CType::Function(_)) => {
+          "\"function\"".to_string()
+        }
+        _ => "\"pointer\"".to_string(),
+      },
+      CType::Record(index) if position == Position::Static => {
+        let kind = record_kind(&self.header.records[*index]);
+        return Err(format!("{kind} variables are not supported"));
+      }
+      CType::Record(index) => {
+        let kind = record_kind(&self.header.records[*index]);
+        let name = self.record_names[*index]
+          .clone()
+          .ok_or_else(|| format!("anonymous {kind}s are not supported"))?;
+        self
+          .record_layout(*index)
+          .map_err(|err| format!("{kind} `{name}`: {err}"))?;
+        name
+      }
+      CType::Enum(index) => {
+        let ty = &self.header.enums[*index].ty;
+        let native = int_native_type(ty);
+        match (&self.enum_names[*index], ty) {
+          (
+            Some(name),
+            CType::Int {
+              size: 1 | 2 | 4,
+              signed,
+              ..
+            },
+          ) if matches!(position, Position::Parameter | Position::Result) => {
+            let brand = format!(
+              "Native{}{}Enum",
+              if *signed { "I" } else { "U" },
+              &native[1..]
+            );
+            format!("\"{native}\" as Deno.{brand}<{name}>")
+          }
+          _ => format!("\"{native}\""),
+        }
+      }
+      CType::Void => {
+        return Err("`void` is only valid as a return type".to_string());
+      }
+      CType::Array(..) => {
+        return Err("arrays outside of structs are not supported".to_string());
+      }
+      CType::Function(_) => {
+        return Err("functions can only be passed as pointers".to_string());
+      }
+      CType::Unknown(name) => return Err(format!("unknown type `{name}`")),
+      CType::Unsupported(what) => {
+        return Err(format!("`{what}` is not supported"));
+      }
+    })
+  }
+
+  /// The TypeScript type of a struct field.
+  fn ts_type(&self, ty: &CType) -> String {
+    match ty {
+      CType::Bool => "boolean".to_string(),
+      CType::Int { size: 8, .. } => "bigint".to_string(),
+      CType::Int { .. } | CType::Float | CType::Double => "number".to_string(),
+      CType::Pointer(_) => "Deno.PointerValue".to_string(),
+      CType::Array(element, _) => format!("{}[]", self.ts_type(element)),
+      CType::Record(index) => self.record_names[*index].clone().unwrap(),
+      CType::Enum(index) => match &self.enum_names[*index] {
+        Some(name) => name.clone(),
+        None => self.ts_type(&self.header.enums[*index].ty),
+      },
+      _ => "never".to_string(),
+    }
+  }
+
+  /// An expression reading a field of type `ty` at `offset` in `view`.
+  fn read(&self, ty: &CType, offset: &str, depth: usize) -> String {
+    match ty {
+      CType::Bool => format!("view.getUint8({offset}) !== 0"),
+      CType::Int {
+        size: 1, signed, ..
+      } => {
+        let method = if *signed { "getInt8" } else { "getUint8" };
+        format!("view.{method}({offset})")
+      }
+      CType::Int { size, signed, .. } => {
+        let method = match (size, signed) {
+          (2, true) => "getInt16",
+          (2, false) => "getUint16",
+          (4, true) => "getInt32",
+          (4, false) => "getUint32",
+          (_, true) => "getBigInt64",
+          (_, false) => "getBigUint64",
+        };
+        format!("view.{method}({offset}, littleEndian)")
+      }
+      CType::Float => format!("view.getFloat32({offset}, littleEndian)"),
+      CType::Double => format!("view.getFloat64({offset}, littleEndian)"),
+      CType::Pointer(_) => format!(
+        "Deno.UnsafePointer.create(view.getBigUint64({offset}, littleEndian))"
+      ),
+      CType::Array(element, len) => {
+        let index = loop_variable(depth);
+        let size = self.element_size(element);
+        let element_offset = format!("{offset} + {index} * {size}");
+        format!(
+          "Array.from({{ length: {} }}, (_, {index}) => {})",
+          len.unwrap_or_default(),
+          self.read(element, &element_offset, depth + 1)
+        )
+      }
+      CType::Record(index) => format!(
+        "{}(view, {offset})",
+        prefixed("read", self.record_names[*index].as_ref().unwrap())
+      ),
+      CType::Enum(index) => {
+        let read = self.read(&self.header.enums[*index].ty, offset, depth);
+        match &self.enum_names[*index] {
+          Some(name) => format!("{read} as {name}"),
+          None => read,
+        }
+      }
+      _ => unreachable!("unsupported field type"),
+    }
+  }
+
+  /// A statement writing `value` of type `ty` at `offset` in `view`.
+  fn write(
+    &self,
+    ty: &CType,
+    offset: &str,
+    value: &str,
+    depth: usize,
+    indent: &str,
+  ) -> String {
+    match ty {
+      CType::Bool => format!("view.setUint8({offset}, {value} ? 1 : 0);"),
+      CType::Int {
+        size: 1, signed, ..
+      } => {
+        let method = if *signed { "setInt8" } else { "setUint8" };
+        format!("view.{method}({offset}, {value});")
+      }
+      CType::Int { size, signed, .. } => {
+        let method = match (size, signed) {
+          (2, true) => "setInt16",
+          (2, false) => "setUint16",
+          (4, true) => "setInt32",
+          (4, false) => "setUint32",
+          (_, true) => "setBigInt64",
+          (_, false) => "setBigUint64",
+        };
+        format!("view.{method}({offset}, {value}, littleEndian);")
+      }
+      CType::Float => {
+        format!("view.setFloat32({offset}, {value}, littleEndian);")
+      }
+      CType::Double => {
+        format!("view.setFloat64({offset}, {value}, littleEndian);")
+      }
+      CType::Pointer(_) => format!(
+        "view.setBigUint64(\n{indent}  {offset},\n{indent}  \
+         BigInt(Deno.UnsafePointer.value({value})),\n{indent}  \
+         littleEndian,\n{indent});"
+      ),
+      CType::Array(element, len) => {
+        let index = loop_variable(depth);
+        let size = self.element_size(element);
+        let element_offset = format!("{offset} + {index} * {size}");
+        let inner_indent = format!("{indent}  ");
+        let write = self.write(
+          element,
+          &element_offset,
+          &format!("{value}[{index}]"),
+          depth + 1,
+          &inner_indent,
+        );
+        format!(
+          "for (let {index} = 0; {index} < {}; {index}++) {{\n\
+           {inner_indent}{write}\n{indent}}}",
+          len.unwrap_or_default(),
+        )
+      }
+      CType::Record(index) => format!(
+        "{}(view, {offset}, {value});",
+        prefixed("write", self.record_names[*index].as_ref().unwrap())
+      ),
+      CType::Enum(index) => {
+        self.write(&self.header.enums[*index].ty, offset, value, depth, indent)
+      }
+      _ => unreachable!("unsupported field type"),
+    }
+  }
+
+  /// The size of an array element, whose layout is known to be valid.
+  fn element_size(&self, ty: &CType) -> u64 {
+    match ty {
+      CType::Bool => 1,
+      CType::Int { size, .. } => *size as u64,
+      CType::Float => 4,
+      CType::Array(element, len) => {
+        self.element_size(element) * len.unwrap_or_default()
+      }
+      CType::Record(index) => match &self.layouts[*index] {
+        Some(Ok(layout)) => layout.size,
+        _ => unreachable!("layout of a field type is computed"),
+      },
+      CType::Enum(index) => self.element_size(&self.header.enums[*index].ty),
+      _ => 8,
+    }
+  }
+}
+
+fn record_kind(record: &Record) -> &'static str {
+  if record.is_union { "union" } else { "struct" }
+}
+
+/// Prefixes the name of a struct for its helpers, like `decodePoint` or
+/// `decode_point` depending on the naming style of the header.
+fn prefixed(prefix: &str, name: &str) -> String {
+  if name.starts_with(|c: char| c.is_ascii_uppercase()) {
+    format!("{prefix}{name}")
+  } else {
+    format!("{prefix}_{name}")
+  }
+}
+
+fn int_native_type(ty: &CType) -> String {
+  match ty {
+    CType::Int {
+      pointer_sized: true,
+      signed,
+      ..
+    } => if *signed { "isize" } else { "usize" }.to_string(),
+    CType::Int { size, signed, .. } => {
+      format!("{}{}", if *signed { "i" } else { "u" }, size * 8)
+    }
+    _ => unreachable!("not an integer type"),
+  }
+}
+
+fn function_entry(
+  name: &str,
+  parameters: &[String],
+  result: &str,
+  variadic: bool,
+) -> String {
+  let parameters = format!("[{}]", parameters.join(", "));
+  let variadic = if variadic { ", variadic: true" } else { "" };
+  let single_line = format!(
+    "  {name}: {{ parameters: {parameters}, result: {result}{variadic} }},"
+  );
+  if single_line.len() <= 80 {
+    return single_line;
+  }
+  let mut entry = format!(
+    "  {name}: {{\n    parameters: {parameters},\n    result: {result},\n"
+  );
+  if !variadic.is_empty() {
+    entry.push_str("    variadic: true,\n");
+  }
+  entry.push_str("  },");
+  entry
+}
+
+fn offset_expr(base: &str, offset: u64) -> String {
+  if offset == 0 {
+    base.to_string()
+  } else {
+    format!("{base} + {offset}")
+  }
+}
+
+fn loop_variable(depth: usize) -> String {
+  ["i", "j", "k"]
+    .get(depth)
+    .map(|name| name.to_string())
+    .unwrap_or_else(|| format!("i{depth}"))
+}
+
+fn int_literal(value: i128) -> String {
+  // Values that don't fit in a double exactly are bigints.
+  const MAX_SAFE_INTEGER: i128 = (1 << 53) - 1;
+  if (-MAX_SAFE_INTEGER..=MAX_SAFE_INTEGER).contains(&value) {
+    value.to_string()
+  } else {
+    format!("{value}n")
+  }
+}
+
+fn float_literal(value: f64) -> String {
+  if value.is_nan() {
+    "NaN".to_string()
+  } else if value.is_infinite() {
+    if value > 0.0 { "Infinity" } else { "-Infinity" }.to_string()
+  } else {
+    format!("{value:?}")
+  }
+}
+
+fn string_literal(value: &str) -> String {
+  let mut literal = String::from('"');
+  for c in value.chars() {
+    match c {
+      '"' => literal.push_str("\\\""),
+      '\\' => literal.push_str("\\\\"),
+      '\n' => literal.push_str("\\n"),
+      '\r' => literal.push_str("\\r"),
+      '\t' => literal.push_str("\\t"),
+      c if c.is_control() => write!(literal, "\\u{{{:x}}}", c as u32).unwrap(),
+      c => literal.push(c),
+    }
+  }
+  literal.push('"');
+  literal
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::tools::ffi::c_header;
+
+  fn generate_ok(source: &str) -> String {
+    let header = c_header::parse(source, &[]);
+    assert_eq!(header.warnings, Vec::<String>::new());
+    let bindings = generate(&header, "test.h");
+    assert_eq!(bindings.warnings, Vec::<String>::new());
+    bindings.code
+  }
+
+  #[test]
+  fn symbols() {
+    let code = generate_ok(
+      r#"
+typedef enum { MODE_READ = 1, MODE_WRITE = 2 } mode_t;
+typedef struct handle handle_t;
+typedef void (*callback_t)(int);
+handle_t *handle_open(const char *path, mode_t mode, size_t *out_len);
+void handle_close(handle_t *handle);
+mode_t handle_mode(const handle_t *handle);
+void set_callback(callback_t callback);
+double average(const double *values, unsigned int count);
+extern const char *lib_version;
+"#,
+    );
+    assert!(code.contains(
+      "export const mode_t = {\n  MODE_READ: 1,\n  MODE_WRITE: 2,\n} as const;\n\
+       export type mode_t = typeof mode_t[keyof typeof mode_t];\n"
+    ));
+    assert!(code.contains(
+      "  handle_open: {\n    parameters: [\"buffer\", \"i32\" as \
+       Deno.NativeI32Enum<mode_t>, \"buffer\"],\n    result: \"pointer\",\n  },\n"
+    ));
+    assert!(code.contains(
+      "  handle_close: { parameters: [\"pointer\"], result: \"void\" },\n"
+    ));
+    assert!(code.contains(
+      "  set_callback: { parameters: [\"function\"], result: \"void\" },\n"
+    ));
+    assert!(code.contains(
+      "  average: { parameters: [\"buffer\", \"u32\"], result: \"f64\" },\n"
+    ));
+    assert!(code.contains("  lib_version: { type: \"pointer\" },\n"));
+    assert!(!code.contains("toView"));
+  }
+
+  #[test]
+  fn structs() {
+    let code = generate_ok(
+      r#"
+struct point { double x, y; };
+typedef struct {
+  struct point corners[2];
+  unsigned char flags;
+  int64_t id;
+  struct { float weight; } meta;
+  void *data;
+} rect_t;
+struct point rect_center(rect_t rect);
+"#,
+    );
+    assert!(code.contains(
+      "export const point = { struct: [\"f64\", \"f64\"] } as const;\n\
+       export const sizeof_point = 16;\n"
+    ));
+    assert!(code.contains(
+      "export interface rect_t {\n  corners: point[];\n  flags: number;\n  \
+       id: bigint;\n  meta: rect_t_meta;\n  data: Deno.PointerValue;\n}\n"
+    ));
+    assert!(code.contains("export const sizeof_rect_t = 64;\n"));
+    assert!(code.contains(
+      "    corners: Array.from({ length: 2 }, (_, i) => read_point(view, offset + i * 16)),\n\
+       \x20   flags: view.getUint8(offset + 32),\n\
+       \x20   id: view.getBigInt64(offset + 40, littleEndian),\n\
+       \x20   meta: read_rect_t_meta(view, offset + 48),\n"
+    ));
+    assert!(code.contains(
+      "  for (let i = 0; i < 2; i++) {\n    \
+       write_point(view, offset + i * 16, value.corners[i]);\n  }\n"
+    ));
+    assert!(
+      code
+        .contains("  rect_center: { parameters: [rect_t], result: point },\n")
+    );
+    let meta = code.find("export interface rect_t_meta").unwrap();
+    assert!(meta < code.find("export interface rect_t {").unwrap());
+  }
+
+  #[test]
+  fn unions_and_variadics() {
+    let code = generate_ok(
+      r#"
+union value { int i; float f; };
+typedef struct {
+  unsigned char kind;
+  union value value;
+  char name[8];
+  union { short s; double d; };
+} tagged_t;
+int log_message(const char *format, ...);
+union value get_value(void);
+"#,
+    );
+    assert!(code.contains(
+      "export interface value {\n  i?: number;\n  f?: number;\n}\n\
+       export const value = { union: [\"i32\", \"f32\"] } as const;\n\
+       export const sizeof_value = 4;\n"
+    ));
+    assert!(code.contains(
+      "  if (value.f !== undefined) {\n    \
+       view.setFloat32(offset, value.f, littleEndian);\n  }\n"
+    ));
+    assert!(code.contains(
+      "export const tagged_t = {\n  struct: [\n    \"u8\",\n    value,\n    \
+       { array: { type: \"i8\", length: 8 } },\n    \
+       { union: [\"i16\", \"f64\"] },\n  ],\n} as const;\n\
+       export const sizeof_tagged_t = 24;\n"
+    ));
+    assert!(code.contains("  s?: number;\n  d?: number;\n"));
+    assert!(code.contains(
+      "  log_message: { parameters: [\"buffer\"], result: \"i32\", \
+       variadic: true },\n"
+    ));
+    assert!(code.contains("  get_value: { parameters: [], result: value },\n"));
+  }
+
+  #[test]
+  fn unsupported_declarations() {
+    let header = c_header::parse(
+      r#"
+struct bits { int a : 1; };
+struct opaque *opaque_new(void);
+void opaque_use(struct opaque value);
+struct list { int len; int items[]; };
+void list_use(struct list list);
+int ok(void);
+"#,
+      &[],
+    );
+    let bindings = generate(&header, "test.h");
+    assert_eq!(
+      bindings.warnings,
+      vec![
+        "skipping struct `bits`: bit-fields are not supported",
+        "skipping struct `list`: flexible array members are not supported",
+        "line 4: skipping function `opaque_use`: struct `opaque`: struct is \
+         only forward declared",
+        "line 6: skipping function `list_use`: struct `list`: flexible array \
+         members are not supported",
+      ]
+    );
+    assert!(bindings.code.contains("  opaque_new: {"));
+    assert!(bindings.code.contains("  ok: {"));
+  }
+
+  #[test]
+  fn constants() {
+    let code = generate_ok(
+      "#define NAME \"a\\\"b\\n\"\n#define BIG 0xffffffffffffffff\n\
+       #define PI 3.14\n#define delete 1\n#define open 2\n",
+    );
+    assert!(code.contains(
+      "export const NAME = \"a\\\"b\\n\";\n\
+       export const BIG = 18446744073709551615n;\n\
+       export const PI = 3.14;\n\
+       export const delete_ = 1;\n\
+       export const open_ = 2;\n"
+    ));
+  }
+}
@@ -0,0 +1,2289 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+//! A reader for C headers used by `deno ffi bindgen`.
+//!
+//! This implements the parts of the C preprocessor and declaration grammar
+//! that library headers use to describe their API: macros and conditionals,
+//! typedefs, structs, unions, enums, function prototypes and extern
+//! variables. `#include` directives are not followed, so of the types
+//! declared in other headers only the standard fixed-width and size types
+//! are known. Declarations that cannot be parsed are skipped with a warning.
+
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CType {
+  Void,
+  Bool,
+  Int {
+    size: u8,
+    signed: bool,
+    /// `size_t` and friends, which map to `usize` and `isize`.
+    pointer_sized: bool,
+  },
+  Float,
+  Double,
+  Pointer(Box<CType>),
+  /// An array with its length, which is `None` for `T x[]`.
+  Array(Box<CType>, Option<u64>),
+  Function(Box<FunctionType>),
+  /// A struct or union, indexing into [`Header::records`].
+  Record(usize),
+  /// An enum, indexing into [`Header::enums`].
+  Enum(usize),
+  /// A type name declared in a header that was not read.
+  Unknown(String),
+  /// A type that exists in C but can't be passed through FFI.
+  Unsupported(&'static str),
+}
+
+impl CType {
+  fn int(size: u8, signed: bool) -> CType {
+    CType::Int {
+      size,
+      signed,
+      pointer_sized: false,
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionType {
+  pub result: CType,
+  pub params: Vec<Param>,
+  pub variadic: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Param {
+  pub name: Option<String>,
+  pub ty: CType,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstValue {
+  Int(i128),
+  Float(f64),
+  Str(String),
+}
+
+#[derive(Debug)]
+pub struct Constant {
+  pub name: String,
+  pub value: ConstValue,
+}
+
+#[derive(Debug)]
+pub struct Enum {
+  /// The tag or, once a typedef names it, the typedef name. `None` for
+  /// anonymous enums, whose enumerators are plain constants.
+  pub name: Option<String>,
+  /// The underlying integer type.
+  pub ty: CType,
+  pub variants: Vec<(String, i128)>,
+  aliased: bool,
+}
+
+#[derive(Debug)]
+pub struct Record {
+  /// The tag or, once a typedef names it, the typedef name.
+  pub name: Option<String>,
+  pub is_union: bool,
+  /// `None` while the record is only forward declared.
+  pub fields: Option<Vec<Field>>,
+  /// Set when the layout is changed by packing or alignment attributes.
+  pub unsupported: Option<&'static str>,
+  /// Position among record definitions, so that records can be emitted
+  /// after the records they contain.
+  pub order: usize,
+  aliased: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+  /// `None` for anonymous struct and union members.
+  pub name: Option<String>,
+  pub ty: CType,
+  pub bit_width: Option<u64>,
+}
+
+#[derive(Debug)]
+pub struct Function {
+  pub name: String,
+  pub ty: FunctionType,
+  pub line: usize,
+}
+
+#[derive(Debug)]
+pub struct Variable {
+  pub name: String,
+  pub ty: CType,
+  pub line: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct Header {
+  pub constants: Vec<Constant>,
+  pub enums: Vec<Enum>,
+  pub records: Vec<Record>,
+  pub functions: Vec<Function>,
+  pub variables: Vec<Variable>,
+  pub warnings: Vec<String>,
+}
+
+/// Parses the header `source`. `defines` are predefined object-like macros
+/// in addition to the ones describing the host platform.
+pub fn parse(source: &str, defines: &[(String, String)]) -> Header {
+  let mut preprocessor = Preprocessor::new(defines);
+  preprocessor.run(source);
+  let mut parser = Parser::new(std::mem::take(&mut preprocessor.tokens));
+  parser.header.warnings = std::mem::take(&mut preprocessor.warnings);
+  parser.parse();
+  for (name, body) in preprocessor.constants() {
+    if let Some(value) = parser.constant_value(&body) {
+      parser.header.constants.push(Constant { name, value });
+    }
+  }
+  parser.header
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+  Ident(String),
+  Number(String),
+  Str(String),
+  Char(i128),
+  Punct(&'static str),
+  /// Emitted for `#pragma pack`, with whether packing is now in effect.
+  Pack(bool),
+}
+
+impl Tok {
+  fn is_punct(&self, punct: &str) -> bool {
+    matches!(self, Tok::Punct(p) if *p == punct)
+  }
+
+  fn text(&self) -> String {
+    match self {
+      Tok::Ident(text) | Tok::Number(text) => text.clone(),
+      Tok::Str(text) => format!("{text:?}"),
+      Tok::Char(value) => value.to_string(),
+      Tok::Punct(punct) => punct.to_string(),
+      Tok::Pack(_) => String::new(),
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+  tok: Tok,
+  line: usize,
+  /// Whether whitespace precedes the token, which distinguishes
+  /// `#define F(x)` from `#define F (x)`.
+  spaced: bool,
+}
+
+const PUNCTUATORS: &[&str] = &[
+  "...", "<<=", ">>=", "->", "++", "--", "<<", ">>", "<=", ">=", "==", "!=",
+  "&&", "||", "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=", "##", "::", "(",
+  ")", "[", "]", "{", "}", ",", ";", ":", "?", "=", "<", ">", "+", "-", "*",
+  "/", "%", "&", "|", "^", "~", "!", ".", "#", "@",
+];
+
+/// Splits `source` into logical lines with comments removed and line
+/// continuations joined, each with the number of its first physical line.
+fn logical_lines(source: &str) -> Vec<(usize, String)> {
+  let mut lines = Vec::new();
+  let mut current = String::new();
+  let mut start_line = 1;
+  let mut line = 1;
+  let mut quote = None;
+  let mut chars = source.chars().peekable();
+  while let Some(c) = chars.next() {
+    match c {
+      '\r' => {}
+      '\\' if matches!(chars.peek(), Some('\n' | '\r')) => {
+        if chars.next() == Some('\r') && chars.peek() == Some(&'\n') {
+          chars.next();
+        }
+        line += 1;
+      }
+      '\n' => {
+        lines.push((start_line, std::mem::take(&mut current)));
+        line += 1;
+        start_line = line;
+        quote = None;
+      }
+      _ if quote.is_some() => {
+        current.push(c);
+        if c == '\\' {
+          if let Some(next) = chars.next_if(|c| *c != '\n') {
+            current.push(next);
+          }
+        } else if Some(c) == quote {
+          quote = None;
+        }
+      }
+      '"' | '\'' => {
+        quote = Some(c);
+        current.push(c);
+      }
+      '/' if chars.peek() == Some(&'/') => {
+        while chars.next_if(|c| *c != '\n').is_some() {}
+      }
+      '/' if chars.peek() == Some(&'*') => {
+        chars.next();
+        let mut previous = ' ';
+        for c in chars.by_ref() {
+          if c == '\n' {
+            line += 1;
+          } else if previous == '*' && c == '/' {
+            break;
+          }
+          previous = c;
+        }
+        current.push(' ');
+      }
+      _ => current.push(c),
+    }
+  }
+  lines.push((start_line, current));
+  lines
+}
+
+fn lex(text: &str, line: usize) -> Result<Vec<Token>, String> {
+  let mut tokens = Vec::new();
+  let mut i = 0;
+  let mut spaced = true;
+  while let Some(c) = text[i..].chars().next() {
+    if c.is_whitespace() {
+      i += c.len_utf8();
+      spaced = true;
+      continue;
+    }
+    let tok = if c.is_alphabetic() || c == '_' || c == '$' {
+      let start = i;
+      i += text[i..]
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+        .unwrap_or(text.len() - i);
+      let word = &text[start..i];
+      // Encoding prefixes of string and character literals.
+      if matches!(word, "L" | "u" | "U" | "u8")
+        && text[i..].starts_with(['"', '\''])
+      {
+        continue;
+      }
+      Tok::Ident(word.to_string())
+    } else if c.is_ascii_digit()
+      || (c == '.' && text[i + 1..].starts_with(|c: char| c.is_ascii_digit()))
+    {
+      let start = i;
+      i += 1;
+      while let Some(c) = text[i..].chars().next() {
+        let exponent =
+          matches!(c, '+' | '-') && text[..i].ends_with(['e', 'E', 'p', 'P']);
+        if c.is_ascii_alphanumeric() || c == '_' || c == '.' || exponent {
+          i += 1;
+        } else {
+          break;
+        }
+      }
+      Tok::Number(text[start..i].to_string())
+    } else if c == '"' || c == '\'' {
+      let (value, end) = unquote(text, i, c)?;
+      i = end;
+      if c == '"' {
+        Tok::Str(value)
+      } else {
+        Tok::Char(value.chars().fold(0, |acc, c| (acc << 8) | c as i128))
+      }
+    } else if let Some(punct) = PUNCTUATORS
+      .iter()
+      .find(|punct| text[i..].starts_with(**punct))
+    {
+      i += punct.len();
+      Tok::Punct(punct)
+    } else {
+      return Err(format!("unexpected character `{c}`"));
+    };
+    tokens.push(Token { tok, line, spaced });
+    spaced = false;
+  }
+  Ok(tokens)
+}
+
+/// Decodes the string or character literal starting with `quote` at
+/// `start`, returning its value and the index after the closing quote.
+fn unquote(
+  text: &str,
+  start: usize,
+  quote: char,
+) -> Result<(String, usize), String> {
+  let mut value = String::new();
+  let mut chars = text[start + 1..].char_indices().peekable();
+  while let Some((i, c)) = chars.next() {
+    if c == quote {
+      return Ok((value, start + 1 + i + 1));
+    }
+    if c != '\\' {
+      value.push(c);
+      continue;
+    }
+    let Some((_, escape)) = chars.next() else {
+      break;
+    };
+    let code = match escape {
+      'n' => '\n' as u32,
+      't' => '\t' as u32,
+      'r' => '\r' as u32,
+      'a' => 7,
+      'b' => 8,
+      'f' => 12,
+      'v' => 11,
+      'e' => 27,
+      '0'..='7' => {
+        let mut code = escape.to_digit(8).unwrap();
+        for _ in 0..2 {
+          match chars.next_if(|(_, c)| c.is_digit(8)) {
+            Some((_, c)) => code = code * 8 + c.to_digit(8).unwrap(),
+            None => break,
+          }
+        }
+        code
+      }
+      'x' | 'u' | 'U' => {
+        let max = match escape {
+          'u' => 4,
+          'U' => 8,
+          _ => usize::MAX,
+        };
+        let mut code = 0u32;
+        let mut digits = 0;
+        while digits < max {
+          match chars.next_if(|(_, c)| c.is_ascii_hexdigit()) {
+            Some((_, c)) => {
+              code = code.wrapping_mul(16) + c.to_digit(16).unwrap();
+              digits += 1;
+            }
+            None => break,
+          }
+        }
+        code
+      }
+      other => other as u32,
+    };
+    value.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+  }
+  Err("unterminated literal".to_string())
+}
+
+fn parse_int(text: &str) -> Option<i128> {
+  let digits = text
+    .trim_end_matches(['u', 'U', 'l', 'L', 'z', 'Z'])
+    .replace('\'', "")
+    .to_ascii_lowercase();
+  if let Some(hex) = digits.strip_prefix("0x") {
+    i128::from_str_radix(hex, 16).ok()
+  } else if let Some(binary) = digits.strip_prefix("0b") {
+    i128::from_str_radix(binary, 2).ok()
+  } else if digits.len() > 1 && digits.starts_with('0') {
+    i128::from_str_radix(&digits[1..], 8).ok()
+  } else {
+    digits.parse().ok()
+  }
+}
+
+fn parse_float(text: &str) -> Option<f64> {
+  if text.starts_with("0x") || text.starts_with("0X") {
+    return None;
+  }
+  text.trim_end_matches(['f', 'F', 'l', 'L']).parse().ok()
+}
+
+#[derive(Debug, Clone)]
+enum Macro {
+  Object(Vec<Tok>),
+  Function {
+    params: Vec<String>,
+    variadic: bool,
+    body: Vec<Tok>,
+  },
+}
+
+struct Condition {
+  /// Whether the current branch is being read.
+  active: bool,
+  /// Whether any branch of the conditional has been taken.
+  taken: bool,
+  parent_active: bool,
+}
+
+struct Preprocessor {
+  macros: HashMap<String, Macro>,
+  /// Object-like macros defined by the header, in definition order.
+  defined: Vec<String>,
+  conditions: Vec<Condition>,
+  pack: Vec<Option<String>>,
+  /// Tokens of the current run of non-directive lines.
+  pending: Vec<Token>,
+  tokens: Vec<Token>,
+  warnings: Vec<String>,
+}
+
+impl Preprocessor {
+  fn new(defines: &[(String, String)]) -> Self {
+    let mut preprocessor = Preprocessor {
+      macros: HashMap::new(),
+      defined: Vec::new(),
+      conditions: Vec::new(),
+      pack: vec![None],
+      pending: Vec::new(),
+      tokens: Vec::new(),
+      warnings: Vec::new(),
+    };
+    let mut predefined = vec![
+      ("__STDC__", "1"),
+      ("__STDC_VERSION__", "201710L"),
+      ("__STDC_HOSTED__", "1"),
+      ("__SIZEOF_POINTER__", "8"),
+      ("__CHAR_BIT__", "8"),
+    ];
+    match std::env::consts::OS {
+      "windows" => predefined.extend([("_WIN32", "1"), ("_WIN64", "1")]),
+      "macos" => predefined.extend([
+        ("__APPLE__", "1"),
+        ("__MACH__", "1"),
+        ("__unix__", "1"),
+        ("__LP64__", "1"),
+      ]),
+      _ => predefined.extend([
+        ("__linux__", "1"),
+        ("__unix__", "1"),
+        ("__LP64__", "1"),
+      ]),
+    }
+    match std::env::consts::ARCH {
+      "x86_64" => predefined.extend([("__x86_64__", "1"), ("_M_X64", "1")]),
+      "aarch64" => predefined.extend([("__aarch64__", "1"), ("_M_ARM64", "1")]),
+      _ => {}
+    }
+    let predefined = predefined
+      .into_iter()
+      .map(|(name, value)| (name.to_string(), value.to_string()))
+      .chain(defines.iter().cloned());
+    for (name, value) in predefined {
+      let body = lex(&value, 0)
+        .map(|tokens| tokens.into_iter().map(|token| token.tok).collect())
+        .unwrap_or_default();
+      preprocessor.macros.insert(name, Macro::Object(body));
+    }
+    preprocessor
+  }
+
+  fn active(&self) -> bool {
+    self.conditions.last().is_none_or(|c| c.active)
+  }
+
+  fn run(&mut self, source: &str) {
+    for (line, text) in logical_lines(source) {
+      let is_directive = text.trim_start().starts_with('#');
+      if !is_directive && !self.active() {
+        continue;
+      }
+      let tokens = match lex(&text, line) {
+        Ok(tokens) => tokens,
+        Err(message) => {
+          if self.active() {
+            self.warn(line, &message);
+          }
+          continue;
+        }
+      };
+      if is_directive {
+        self.flush();
+        self.directive(line, &tokens[1..]);
+      } else {
+        self.pending.extend(tokens);
+      }
+    }
+    self.flush();
+    if !self.conditions.is_empty() {
+      self.warn(0, "unterminated conditional directive");
+    }
+  }
+
+  fn warn(&mut self, line: usize, message: &str) {
+    self.warnings.push(format!("line {line}: {message}"));
+  }
+
+  /// Macro expands the pending tokens into the output.
+  fn flush(&mut self) {
+    let pending = std::mem::take(&mut self.pending);
+    let mut tokens = std::mem::take(&mut self.tokens);
+    self.expand(&pending, &mut Vec::new(), &mut tokens);
+    self.tokens = tokens;
+  }
+
+  fn directive(&mut self, line: usize, tokens: &[Token]) {
+    let Some(Tok::Ident(name)) = tokens.first().map(|t| &t.tok) else {
+      return;
+    };
+    let rest = &tokens[1..];
+    match name.as_str() {
+      "if" | "ifdef" | "ifndef" => {
+        let parent_active = self.active();
+        let active = parent_active && self.condition(name, rest);
+        self.conditions.push(Condition {
+          active,
+          taken: active,
+          parent_active,
+        });
+      }
+      "elif" | "elifdef" | "elifndef" => {
+        let Some(condition) = self.conditions.last() else {
+          return self.warn(line, "#elif without #if");
+        };
+        let active = condition.parent_active
+          && !condition.taken
+          && self.condition(name.trim_start_matches("el"), rest);
+        let condition = self.conditions.last_mut().unwrap();
+        condition.active = active;
+        condition.taken |= active;
+      }
+      "else" => match self.conditions.last_mut() {
+        Some(condition) => {
+          condition.active = condition.parent_active && !condition.taken;
+          condition.taken = true;
+        }
+        None => self.warn(line, "#else without #if"),
+      },
+      "endif" => match self.conditions.pop() {
+        Some(_) => {}
+        None => self.warn(line, "#endif without #if"),
+      },
+      _ if !self.active() => {}
+      "define" => self.define(line, rest),
+      "undef" => {
+        if let Some(Tok::Ident(name)) = rest.first().map(|t| &t.tok) {
+          self.macros.remove(name);
+        }
+      }
+      "pragma" => self.pragma(line, rest),
+      _ => {}
+    }
+  }
+
+  fn condition(&mut self, directive: &str, tokens: &[Token]) -> bool {
+    let defined = |tokens: &[Token]| match tokens.first().map(|t| &t.tok) {
+      Some(Tok::Ident(name)) => self.macros.contains_key(name),
+      _ => false,
+    };
+    match directive {
+      "ifdef" => defined(tokens),
+      "ifndef" => !defined(tokens),
+      _ => {
+        // `defined` is resolved before macro expansion.
+        let mut replaced = Vec::new();
+        let mut i = 0;
+        while i < tokens.len() {
+          if tokens[i].tok != Tok::Ident("defined".to_string()) {
+            replaced.push(tokens[i].clone());
+            i += 1;
+            continue;
+          }
+          let parenthesized =
+            tokens.get(i + 1).is_some_and(|t| t.tok.is_punct("("));
+          let name = if parenthesized { i + 2 } else { i + 1 };
+          let value = defined(tokens.get(name..).unwrap_or_default());
+          replaced.push(Token {
+            tok: Tok::Number(if value { "1" } else { "0" }.to_string()),
+            ..tokens[i].clone()
+          });
+          i = name + 1 + parenthesized as usize;
+        }
+        let mut expanded = Vec::new();
+        self.expand(&replaced, &mut Vec::new(), &mut expanded);
+        let tokens = expanded.into_iter().map(|t| t.tok).collect::<Vec<_>>();
+        match eval(&tokens, &PreprocessorScope) {
+          Ok(value) => value != 0,
+          Err(message) => {
+            let line = replaced.first().map(|t| t.line).unwrap_or_default();
+            self.warn(line, &format!("{message} in #{directive}"));
+            false
+          }
+        }
+      }
+    }
+  }
+
+  fn define(&mut self, line: usize, tokens: &[Token]) {
+    let Some(Tok::Ident(name)) = tokens.first().map(|t| &t.tok) else {
+      return self.warn(line, "expected a macro name after #define");
+    };
+    let is_function = tokens
+      .get(1)
+      .is_some_and(|t| t.tok.is_punct("(") && !t.spaced);
+    if !is_function {
+      let body = tokens[1..].iter().map(|t| t.tok.clone()).collect();
+      self.macros.insert(name.clone(), Macro::Object(body));
+      self.defined.retain(|defined| defined != name);
+      self.defined.push(name.clone());
+      return;
+    }
+    let mut params = Vec::new();
+    let mut variadic = false;
+    let mut i = 2;
+    loop {
+      match tokens.get(i).map(|t| &t.tok) {
+        Some(Tok::Ident(param)) => params.push(param.clone()),
+        Some(Tok::Punct("...")) => variadic = true,
+        Some(Tok::Punct(")")) if params.is_empty() && !variadic => break,
+        _ => return self.warn(line, "invalid macro parameter list"),
+      }
+      i += 1;
+      match tokens.get(i).map(|t| &t.tok) {
+        Some(Tok::Punct(",")) if !variadic => i += 1,
+        Some(Tok::Punct(")")) => break,
+        _ => return self.warn(line, "invalid macro parameter list"),
+      }
+    }
+    let body = tokens[i + 1..].iter().map(|t| t.tok.clone()).collect();
+    self.macros.insert(
+      name.clone(),
+      Macro::Function {
+        params,
+        variadic,
+        body,
+      },
+    );
+  }
+
+  fn pragma(&mut self, line: usize, tokens: &[Token]) {
+    let tokens = tokens.iter().map(|t| &t.tok).collect::<Vec<_>>();
+    let [
+      Tok::Ident(pragma),
+      Tok::Punct("("),
+      args @ ..,
+      Tok::Punct(")"),
+    ] = tokens.as_slice()
+    else {
+      return;
+    };
+    if pragma != "pack" {
+      return;
+    }
+    let current = self.pack.last().cloned().flatten();
+    let value = |tok: Option<&&Tok>| match tok {
+      Some(Tok::Number(value)) => Some(value.clone()),
+      _ => None,
+    };
+    match args {
+      [] => *self.pack.last_mut().unwrap() = None,
+      [Tok::Ident(action), rest @ ..] if action == "push" => {
+        let value = value(rest.get(1)).or(current);
+        self.pack.push(value);
+      }
+      [Tok::Ident(action), ..] if action == "pop" => {
+        if self.pack.len() > 1 {
+          self.pack.pop();
+        }
+      }
+      [value @ Tok::Number(_)] => {
+        *self.pack.last_mut().unwrap() = Some(value.text());
+      }
+      _ => return self.warn(line, "unrecognized #pragma pack"),
+    }
+    self.tokens.push(Token {
+      tok: Tok::Pack(self.pack.last().unwrap().is_some()),
+      line,
+      spaced: true,
+    });
+  }
+
+  fn expand(
+    &self,
+    input: &[Token],
+    disabled: &mut Vec<String>,
+    out: &mut Vec<Token>,
+  ) {
+    let mut i = 0;
+    while i < input.len() {
+      let token = &input[i];
+      i += 1;
+      let Tok::Ident(name) = &token.tok else {
+        out.push(token.clone());
+        continue;
+      };
+      if disabled.contains(name) {
+        out.push(token.clone());
+        continue;
+      }
+      let retag = |body: Vec<Tok>| {
+        body
+          .into_iter()
+          .map(|tok| Token {
+            tok,
+            line: token.line,
+            spaced: true,
+          })
+          .collect::<Vec<_>>()
+      };
+      let replacement = match self.macros.get(name) {
+        None => {
+          out.push(token.clone());
+          continue;
+        }
+        Some(Macro::Object(body)) => retag(body.clone()),
+        Some(Macro::Function {
+          params,
+          variadic,
+          body,
+        }) => {
+          let args = if input.get(i).is_some_and(|t| t.tok.is_punct("(")) {
+            collect_args(input, i + 1)
+          } else {
+            None
+          };
+          let Some((mut args, end)) = args else {
+            out.push(token.clone());
+            continue;
+          };
+          if params.is_empty() && args.len() == 1 && args[0].is_empty() {
+            args.clear();
+          }
+          if *variadic && args.len() > params.len() {
+            let rest = args.split_off(params.len());
+            args.push(rest.join(&Tok::Punct(",")));
+          } else if *variadic && args.len() == params.len() {
+            args.push(Vec::new());
+          }
+          if args.len() != params.len() + *variadic as usize {
+            out.push(token.clone());
+            continue;
+          }
+          i = end;
+          let expanded = args
+            .iter()
+            .map(|arg| {
+              let mut expanded = Vec::new();
+              self.expand(&retag(arg.clone()), disabled, &mut expanded);
+              expanded.into_iter().map(|t| t.tok).collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+          let find_param = |tok: &Tok| match tok {
+            Tok::Ident(ident) if ident == "__VA_ARGS__" && *variadic => {
+              Some(params.len())
+            }
+            Tok::Ident(ident) => params.iter().position(|p| p == ident),
+            _ => None,
+          };
+          let mut substituted = Vec::new();
+          let mut j = 0;
+          while j < body.len() {
+            let tok = &body[j];
+            j += 1;
+            if tok.is_punct("#")
+              && let Some(index) = body.get(j).and_then(find_param)
+            {
+              j += 1;
+              let text = args[index].iter().map(Tok::text).collect::<Vec<_>>();
+              substituted.push(Tok::Str(text.join(" ")));
+              continue;
+            }
+            let Some(index) = find_param(tok) else {
+              substituted.push(tok.clone());
+              continue;
+            };
+            let pasted = (j >= 2 && body[j - 2].is_punct("##"))
+              || body.get(j).is_some_and(|t| t.is_punct("##"));
+            if pasted {
+              substituted.extend(args[index].iter().cloned());
+            } else {
+              substituted.extend(expanded[index].iter().cloned());
+            }
+          }
+          retag(paste(substituted))
+        }
+      };
+      disabled.push(name.clone());
+      self.expand(&replacement, disabled, out);
+      disabled.pop();
+    }
+  }
+
+  /// The object-like macros that are still defined at the end of the
+  /// header, with their expansions.
+  fn constants(&self) -> Vec<(String, Vec<Tok>)> {
+    let mut constants = Vec::new();
+    for name in &self.defined {
+      if name.starts_with("__") {
+        continue;
+      }
+      let Some(Macro::Object(body)) = self.macros.get(name) else {
+        continue;
+      };
+      if body.is_empty() {
+        continue;
+      }
+      let body = body
+        .iter()
+        .map(|tok| Token {
+          tok: tok.clone(),
+          line: 0,
+          spaced: true,
+        })
+        .collect::<Vec<_>>();
+      let mut expanded = Vec::new();
+      self.expand(&body, &mut vec![name.clone()], &mut expanded);
+      constants
+        .push((name.clone(), expanded.into_iter().map(|t| t.tok).collect()));
+    }
+    constants
+  }
+}
+
+/// Splits the arguments of a function-like macro invocation whose opening
+/// parenthesis precedes `start`, returning them with the index after the
+/// closing parenthesis.
+fn collect_args(
+  input: &[Token],
+  start: usize,
+) -> Option<(Vec<Vec<Tok>>, usize)> {
+  let mut args = vec![Vec::new()];
+  let mut depth = 0;
+  for (i, token) in input.iter().enumerate().skip(start) {
+    match &token.tok {
+      Tok::Punct(")") if depth == 0 => return Some((args, i + 1)),
+      Tok::Punct(",") if depth == 0 => {
+        args.push(Vec::new());
+        continue;
+      }
+      Tok::Punct("(") => depth += 1,
+      Tok::Punct(")") => depth -= 1,
+      _ => {}
+    }
+    args.last_mut().unwrap().push(token.tok.clone());
+  }
+  None
+}
+
+/// Applies the `##` operator to a substituted macro body.
+fn paste(tokens: Vec<Tok>) -> Vec<Tok> {
+  let mut out: Vec<Tok> = Vec::new();
+  let mut tokens = tokens.into_iter();
+  while let Some(tok) = tokens.next() {
+    if !tok.is_punct("##") {
+      out.push(tok);
+      continue;
+    }
+    let Some(right) = tokens.next() else {
+      break;
+    };
+    let Some(left) = out.pop() else {
+      out.push(right);
+      continue;
+    };
+    let text = format!("{}{}", left.text(), right.text());
+    match lex(&text, 0).as_deref() {
+      Ok([token]) => out.push(token.tok.clone()),
+      _ => out.extend([left, right]),
+    }
+  }
+  out
+}
+
+trait Scope {
+  fn value(&self, name: &str) -> Option<i128>;
+  fn is_type(&self, name: &str) -> bool;
+}
+
+/// In `#if` expressions, identifiers that are not macros evaluate to zero.
+struct PreprocessorScope;
+
+impl Scope for PreprocessorScope {
+  fn value(&self, _name: &str) -> Option<i128> {
+    Some(0)
+  }
+
+  fn is_type(&self, _name: &str) -> bool {
+    false
+  }
+}
+
+/// Evaluates an integer constant expression.
+fn eval(tokens: &[Tok], scope: &dyn Scope) -> Result<i128, String> {
+  let mut evaluator = Evaluator {
+    tokens,
+    pos: 0,
+    scope,
+  };
+  let value = evaluator.conditional()?;
+  if evaluator.pos != tokens.len() {
+    return Err(format!(
+      "unexpected `{}` in constant expression",
+      tokens[evaluator.pos].text()
+    ));
+  }
+  Ok(value)
+}
+
+struct Evaluator<'a> {
+  tokens: &'a [Tok],
+  pos: usize,
+  scope: &'a dyn Scope,
+}
+
+impl Evaluator<'_> {
+  fn eat(&mut self, punct: &str) -> bool {
+    let matches = self.tokens.get(self.pos).is_some_and(|t| t.is_punct(punct));
+    self.pos += matches as usize;
+    matches
+  }
+
+  fn conditional(&mut self) -> Result<i128, String> {
+    let condition = self.binary(0)?;
+    if !self.eat("?") {
+      return Ok(condition);
+    }
+    let then = self.conditional()?;
+    if !self.eat(":") {
+      return Err("expected `:` in constant expression".to_string());
+    }
+    let otherwise = self.conditional()?;
+    Ok(if condition != 0 { then } else { otherwise })
+  }
+
+  fn binary(&mut self, min_precedence: u8) -> Result<i128, String> {
+    let mut lhs = self.unary()?;
+    loop {
+      let Some(Tok::Punct(op)) = self.tokens.get(self.pos) else {
+        return Ok(lhs);
+      };
+      let precedence = match *op {
+        "||" => 1,
+        "&&" => 2,
+        "|" => 3,
+        "^" => 4,
+        "&" => 5,
+        "==" | "!=" => 6,
+        "<" | ">" | "<=" | ">=" => 7,
+        "<<" | ">>" => 8,
+        "+" | "-" => 9,
+        "*" | "/" | "%" => 10,
+        _ => return Ok(lhs),
+      };
+      if precedence < min_precedence {
+        return Ok(lhs);
+      }
+      self.pos += 1;
+      let rhs = self.binary(precedence + 1)?;
+      lhs = match *op {
+        "||" => (lhs != 0 || rhs != 0) as i128,
+        "&&" => (lhs != 0 && rhs != 0) as i128,
+        "|" => lhs | rhs,
+        "^" => lhs ^ rhs,
+        "&" => lhs & rhs,
+        "==" => (lhs == rhs) as i128,
+        "!=" => (lhs != rhs) as i128,
+        "<" => (lhs < rhs) as i128,
+        ">" => (lhs > rhs) as i128,
+        "<=" => (lhs <= rhs) as i128,
+        ">=" => (lhs >= rhs) as i128,
+        "<<" | ">>" => {
+          let shift = u32::try_from(rhs)
+            .ok()
+            .filter(|shift| *shift < 128)
+            .ok_or("shift out of range in constant expression")?;
+          if *op == "<<" {
+            lhs.wrapping_shl(shift)
+          } else {
+            lhs >> shift
+          }
+        }
+        "+" => lhs.wrapping_add(rhs),
+        "-" => lhs.wrapping_sub(rhs),
+        "*" => lhs.wrapping_mul(rhs),
+        _ if rhs == 0 => {
+          return Err("division by zero in constant expression".to_string());
+        }
+        "/" => lhs.wrapping_div(rhs),
+        _ => lhs.wrapping_rem(rhs),
+      };
+    }
+  }
+
+  fn unary(&mut self) -> Result<i128, String> {
+    let Some(tok) = self.tokens.get(self.pos) else {
+      return Err("unexpected end of constant expression".to_string());
+    };
+    self.pos += 1;
+    match tok {
+      Tok::Punct("-") => Ok(self.unary()?.wrapping_neg()),
+      Tok::Punct("+") => self.unary(),
+      Tok::Punct("~") => Ok(!self.unary()?),
+      Tok::Punct("!") => Ok((self.unary()? == 0) as i128),
+      Tok::Punct("(") => {
+        if self.is_cast() {
+          while !self.eat(")") {
+            self.pos += 1;
+          }
+          return self.unary();
+        }
+        let value = self.conditional()?;
+        if !self.eat(")") {
+          return Err("expected `)` in constant expression".to_string());
+        }
+        Ok(value)
+      }
+      Tok::Number(text) => parse_int(text)
+        .ok_or_else(|| format!("`{text}` is not an integer constant")),
+      Tok::Char(value) => Ok(*value),
+      Tok::Ident(name) if name == "true" => Ok(1),
+      Tok::Ident(name) if name == "false" => Ok(0),
+      Tok::Ident(name) => {
+        // Unknown function-like macros in `#if`, like `__has_include(x)`.
+        if self.tokens.get(self.pos).is_some_and(|t| t.is_punct("(")) {
+          let mut depth = 0;
+          while let Some(tok) = self.tokens.get(self.pos) {
+            self.pos += 1;
+            match tok {
+              Tok::Punct("(") => depth += 1,
+              Tok::Punct(")") if depth == 1 => break,
+              Tok::Punct(")") => depth -= 1,
+              _ => {}
+            }
+          }
+          return self
+            .scope
+            .value(name)
+            .map(|_| 0)
+            .ok_or_else(|| format!("`{name}` is not a constant"));
+        }
+        self
+          .scope
+          .value(name)
+          .ok_or_else(|| format!("`{name}` is not a constant"))
+      }
+      tok => Err(format!(
+        "unexpected `{}` in constant expression",
+        tok.text()
+      )),
+    }
+  }
+
+  /// Whether the parenthesized tokens at the current position are a type
+  /// name, making the parentheses a cast.
+  fn is_cast(&self) -> bool {
+    let mut saw_type = false;
+    for tok in &self.tokens[self.pos..] {
+      match tok {
+        Tok::Punct(")") => return saw_type,
+        Tok::Punct("*") => {}
+        Tok::Ident(name)
+          if self.scope.is_type(name) || is_type_keyword(name) =>
+        {
+          saw_type = true;
+        }
+        _ => return false,
+      }
+    }
+    false
+  }
+}
+
+const STORAGE_CLASSES: &[&str] = &[
+  "typedef",
+  "extern",
+  "static",
+  "register",
+  "auto",
+  "thread_local",
+  "_Thread_local",
+  "__thread",
+  "constexpr",
+  "inline",
+  "__inline",
+  "__inline__",
+  "__forceinline",
+  "_Noreturn",
+  "noreturn",
+];
+
+const QUALIFIERS: &[&str] = &[
+  "const",
+  "volatile",
+  "restrict",
+  "__restrict",
+  "__restrict__",
+  "__const",
+  "__const__",
+  "__volatile__",
+  "_Atomic",
+  "_Nonnull",
+  "_Nullable",
+  "_Null_unspecified",
+  "__unaligned",
+  "__ptr32",
+  "__ptr64",
+  "__w64",
+  "__extension__",
+  "__cdecl",
+  "_cdecl",
+  "__stdcall",
+  "_stdcall",
+  "__fastcall",
+  "__vectorcall",
+  "__thiscall",
+];
+
+/// Keywords that are followed by a parenthesized argument and don't affect
+/// the type being declared, except for the layout attributes.
+const ATTRIBUTES: &[&str] = &[
+  "__attribute__",
+  "__attribute",
+  "__declspec",
+  "__asm__",
+  "__asm",
+  "asm",
+  "_Alignas",
+  "alignas",
+  "__pragma",
+  "_Pragma",
+];
+
+const PRIMITIVES: &[&str] = &[
+  "void",
+  "char",
+  "short",
+  "int",
+  "long",
+  "float",
+  "double",
+  "signed",
+  "__signed",
+  "__signed__",
+  "unsigned",
+  "_Bool",
+  "bool",
+  "_Complex",
+  "__int8",
+  "__int16",
+  "__int32",
+  "__int64",
+  "__int128",
+];
+
+fn is_type_keyword(name: &str) -> bool {
+  PRIMITIVES.contains(&name)
+    || QUALIFIERS.contains(&name)
+    || matches!(name, "struct" | "union" | "enum")
+    || builtin_type(name).is_some()
+}
+
+fn is_keyword(name: &str) -> bool {
+  is_type_keyword(name)
+    || STORAGE_CLASSES.contains(&name)
+    || ATTRIBUTES.contains(&name)
+}
+
+/// Types from the standard headers, which are not read.
+fn builtin_type(name: &str) -> Option<CType> {
+  let pointer_sized = |signed| CType::Int {
+    size: 8,
+    signed,
+    pointer_sized: true,
+  };
+  Some(match name {
+    "int8_t" | "int_least8_t" => CType::int(1, true),
+    "uint8_t" | "uint_least8_t" => CType::int(1, false),
+    "int16_t" | "int_least16_t" => CType::int(2, true),
+    "uint16_t" | "uint_least16_t" | "char16_t" => CType::int(2, false),
+    "int32_t" | "int_least32_t" => CType::int(4, true),
+    "uint32_t" | "uint_least32_t" | "char32_t" => CType::int(4, false),
+    "int64_t" | "int_least64_t" | "intmax_t" | "off_t" => CType::int(8, true),
+    "uint64_t" | "uint_least64_t" | "uintmax_t" => CType::int(8, false),
+    "size_t" | "uintptr_t" => pointer_sized(false),
+    "ssize_t" | "intptr_t" | "ptrdiff_t" => pointer_sized(true),
+    "wchar_t" if cfg!(windows) => CType::int(2, false),
+    "wchar_t" => CType::int(4, true),
+    _ => return None,
+  })
+}
+
+enum Storage {
+  None,
+  Typedef,
+  Extern,
+  Static,
+}
+
+struct Specifiers {
+  ty: CType,
+  storage: Storage,
+}
+
+enum Derived {
+  Pointer,
+  Array(Option<u64>),
+  Function(Vec<Param>, bool),
+}
+
+struct Parser {
+  tokens: Vec<Token>,
+  pos: usize,
+  header: Header,
+  record_tags: HashMap<String, usize>,
+  enum_tags: HashMap<String, usize>,
+  typedefs: HashMap<String, CType>,
+  enumerators: HashMap<String, i128>,
+  /// Whether `#pragma pack` is in effect.
+  packed: bool,
+  /// Open `extern "C" {` blocks.
+  extern_blocks: usize,
+  definitions: usize,
+}
+
+impl Scope for Parser {
+  fn value(&self, name: &str) -> Option<i128> {
+    self.enumerators.get(name).copied()
+  }
+
+  fn is_type(&self, name: &str) -> bool {
+    self.typedefs.contains_key(name)
+  }
+}
+
+type ParseResult<T> = Result<T, String>;
+
+impl Parser {
+  fn new(tokens: Vec<Token>) -> Self {
+    Parser {
+      tokens,
+      pos: 0,
+      header: Header::default(),
+      record_tags: HashMap::new(),
+      enum_tags: HashMap::new(),
+      typedefs: HashMap::new(),
+      enumerators: HashMap::new(),
+      packed: false,
+      extern_blocks: 0,
+      definitions: 0,
+    }
+  }
+
+  fn parse(&mut self) {
+    while self.pos < self.tokens.len() {
+      let start = self.pos;
+      if let Err(message) = self.external_declaration() {
+        let line = self.line();
+        self
+          .header
+          .warnings
+          .push(format!("line {line}: {message}, skipping declaration"));
+        self.pos = start;
+        self.skip_declaration();
+      }
+    }
+  }
+
+  fn peek(&self) -> Option<&Tok> {
+    self.peek_at(0)
+  }
+
+  fn peek_at(&self, offset: usize) -> Option<&Tok> {
+    self.tokens.get(self.pos + offset).map(|t| &t.tok)
+  }
+
+  fn peek_ident(&self) -> Option<&str> {
+    match self.peek() {
+      Some(Tok::Ident(ident)) => Some(ident),
+      _ => None,
+    }
+  }
+
+  fn line(&self) -> usize {
+    self
+      .tokens
+      .get(self.pos)
+      .or(self.tokens.last())
+      .map(|t| t.line)
+      .unwrap_or_default()
+  }
+
+  fn is_punct(&self, punct: &str) -> bool {
+    self.peek().is_some_and(|t| t.is_punct(punct))
+  }
+
+  fn eat(&mut self, punct: &str) -> bool {
+    let matches = self.is_punct(punct);
+    self.pos += matches as usize;
+    matches
+  }
+
+  fn expect(&mut self, punct: &str) -> ParseResult<()> {
+    if self.eat(punct) {
+      return Ok(());
+    }
+    Err(match self.peek() {
+      Some(tok) => format!("expected `{punct}`, found `{}`", tok.text()),
+      None => format!("expected `{punct}` at end of file"),
+    })
+  }
+
+  /// Skips a balanced group starting at an opening bracket, returning the
+  /// tokens inside it.
+  fn skip_group(&mut self) -> Vec<Tok> {
+    let mut depth = 0;
+    let mut inner = Vec::new();
+    while let Some(tok) = self.peek().cloned() {
+      self.pos += 1;
+      match tok {
+        Tok::Punct("(" | "[" | "{") => depth += 1,
+        Tok::Punct(")" | "]" | "}") => depth -= 1,
+        _ => {}
+      }
+      if depth == 0 {
+        break;
+      }
+      inner.push(tok);
+    }
+    inner
+  }
+
+  /// Skips qualifiers, calling conventions and attributes, returning the
+  /// reason if an attribute changes the layout of a record.
+  fn skip_qualifiers(&mut self) -> Option<&'static str> {
+    let mut unsupported = None;
+    loop {
+      if self.is_punct("[") && self.peek_at(1).is_some_and(|t| t.is_punct("["))
+      {
+        self.skip_group();
+        continue;
+      }
+      let Some(ident) = self.peek_ident() else {
+        return unsupported;
+      };
+      if QUALIFIERS.contains(&ident) {
+        self.pos += 1;
+      } else if ATTRIBUTES.contains(&ident) {
+        let alignas = matches!(ident, "_Alignas" | "alignas");
+        self.pos += 1;
+        if !self.is_punct("(") {
+          continue;
+        }
+        let args = self.skip_group();
+        let has = |names: &[&str]| {
+          args
+            .iter()
+            .any(|t| matches!(t, Tok::Ident(n) if names.contains(&n.as_str())))
+        };
+        if has(&["packed", "__packed__"]) {
+          unsupported = Some("packed structs are not supported");
+        } else if alignas || has(&["aligned", "__aligned__", "align"]) {
+          unsupported = Some("over-aligned structs are not supported");
+        }
+      } else {
+        return unsupported;
+      }
+    }
+  }
+
+  /// Skips to the end of the declaration at the current position.
+  fn skip_declaration(&mut self) {
+    let start = self.pos;
+    let mut depth = 0usize;
+    let mut body = false;
+    while let Some(tok) = self.peek().cloned() {
+      self.pos += 1;
+      match tok {
+        Tok::Punct("(" | "[") => depth += 1,
+        Tok::Punct("{") => {
+          body |= depth == 0
+            && self.pos >= 2
+            && self.tokens[self.pos - 2].tok.is_punct(")");
+          depth += 1;
+        }
+        Tok::Punct(")" | "]") => depth = depth.saturating_sub(1),
+        Tok::Punct("}") if depth == 0 => {
+          // The end of an `extern "C"` block.
+          if self.pos - 1 > start {
+            self.pos -= 1;
+          }
+          return;
+        }
+        Tok::Punct("}") => {
+          depth -= 1;
+          if depth == 0 && body {
+            return;
+          }
+        }
+        Tok::Punct(";") if depth == 0 => return,
+        _ => {}
+      }
+    }
+  }
+
+  fn external_declaration(&mut self) -> ParseResult<()> {
+    match self.peek() {
+      Some(Tok::Pack(packed)) => {
+        self.packed = *packed;
+        self.pos += 1;
+        return Ok(());
+      }
+      Some(Tok::Punct(";")) => {
+        self.pos += 1;
+        return Ok(());
+      }
+      Some(Tok::Punct("}")) if self.extern_blocks > 0 => {
+        self.extern_blocks -= 1;
+        self.pos += 1;
+        return Ok(());
+      }
+      Some(Tok::Ident(ident))
+        if ident == "extern"
+          && matches!(self.peek_at(1), Some(Tok::Str(_))) =>
+      {
+        self.pos += 2;
+        if self.eat("{") {
+          self.extern_blocks += 1;
+        }
+        return Ok(());
+      }
+      Some(Tok::Ident(ident))
+        if ident == "_Static_assert" || ident == "static_assert" =>
+      {
+        self.skip_declaration();
+        return Ok(());
+      }
+      _ => {}
+    }
+
+    let specifiers = self.specifiers()?;
+    if self.eat(";") {
+      return Ok(());
+    }
+    loop {
+      let line = self.line();
+      let (name, ty) = self.declarator(specifiers.ty.clone())?;
+      self.skip_qualifiers();
+      let name = name.ok_or("expected a name in declaration")?;
+      if self.is_punct("{") {
+        // Function definitions in headers are `static inline`, which are not
+        // exported by the library.
+        self.skip_group();
+        return Ok(());
+      }
+      if self.eat("=") {
+        self.skip_initializer();
+      }
+      self.declare(&specifiers.storage, name, ty, line);
+      if !self.eat(",") {
+        return self.expect(";");
+      }
+    }
+  }
+
+  fn skip_initializer(&mut self) {
+    while let Some(tok) = self.peek() {
+      match tok {
+        Tok::Punct("," | ";") => return,
+        Tok::Punct("(" | "[" | "{") => {
+          self.skip_group();
+        }
+        _ => self.pos += 1,
+      }
+    }
+  }
+
+  fn declare(
+    &mut self,
+    storage: &Storage,
+    name: String,
+    ty: CType,
+    line: usize,
+  ) {
+    match storage {
+      Storage::Typedef => {
+        match ty {
+          CType::Record(index) if !self.header.records[index].aliased => {
+            let record = &mut self.header.records[index];
+            record.name = Some(name.clone());
+            record.aliased = true;
+          }
+          CType::Enum(index) if !self.header.enums[index].aliased => {
+            let enum_ = &mut self.header.enums[index];
+            enum_.name = Some(name.clone());
+            enum_.aliased = true;
+          }
+          _ => {}
+        }
+        self.typedefs.insert(name, ty);
+      }
+      Storage::Static => {}
+      Storage::None | Storage::Extern => match ty {
+        CType::Function(ty) => {
+          if !self.header.functions.iter().any(|f| f.name == name) {
+            self.header.functions.push(Function {
+              name,
+              ty: *ty,
+              line,
+            });
+          }
+        }
+        ty => {
+          if !self.header.variables.iter().any(|v| v.name == name) {
+            self.header.variables.push(Variable { name, ty, line });
+          }
+        }
+      },
+    }
+  }
+
+  fn specifiers(&mut self) -> ParseResult<Specifiers> {
+    let mut storage = Storage::None;
+    let mut ty = None;
+    let mut primitive = None;
+    let mut signed = None;
+    let mut short = false;
+    let mut longs = 0;
+    let mut complex = false;
+    while let Some(word) = self.peek_ident() {
+      match word {
+        "typedef" => storage = Storage::Typedef,
+        "extern" => storage = Storage::Extern,
+        "static" => storage = Storage::Static,
+        _ if STORAGE_CLASSES.contains(&word) => {}
+        _ if QUALIFIERS.contains(&word) || ATTRIBUTES.contains(&word) => {
+          if word == "_Atomic"
+            && self.peek_at(1).is_some_and(|t| t.is_punct("("))
+          {
+            return Err("`_Atomic` types are not supported".to_string());
+          }
+          self.skip_qualifiers();
+          continue;
+        }
+        "struct" | "union" | "enum" if ty.is_none() && primitive.is_none() => {
+          ty = Some(if word == "enum" {
+            self.enum_specifier()?
+          } else {
+            self.record_specifier(word == "union")?
+          });
+          continue;
+        }
+        "signed" | "__signed" | "__signed__" => signed = Some(true),
+        "unsigned" => signed = Some(false),
+        "short" => short = true,
+        "long" => longs += 1,
+        "_Complex" => complex = true,
+        "int" if primitive.is_some() => {}
+        _ if PRIMITIVES.contains(&word) => {
+          if ty.is_some() {
+            return Err(format!("unexpected `{word}`"));
+          }
+          primitive = Some(word.to_string());
+        }
+        _ => {
+          if ty.is_some()
+            || primitive.is_some()
+            || signed.is_some()
+            || short
+            || longs > 0
+          {
+            break;
+          }
+          if let Some(builtin) = builtin_type(word) {
+            ty = Some(builtin);
+          } else if let Some(typedef) = self.typedefs.get(word) {
+            ty = Some(typedef.clone());
+          } else if matches!(
+            self.peek_at(1),
+            Some(Tok::Ident(next)) if is_keyword(next)
+              && !QUALIFIERS.contains(&next.as_str())
+          ) {
+            // An annotation macro defined in a header that was not read.
+          } else if matches!(
+            self.peek_at(1),
+            Some(Tok::Ident(_) | Tok::Punct("*"))
+          ) {
+            ty = Some(CType::Unknown(word.to_string()));
+          } else {
+            break;
+          }
+        }
+      }
+      self.pos += 1;
+    }
+
+    let ty = match (ty, primitive.as_deref()) {
+      (Some(ty), _) => ty,
+      _ if complex => CType::Unsupported("_Complex"),
+      (None, Some("void")) => CType::Void,
+      (None, Some("_Bool" | "bool")) => CType::Bool,
+      (None, Some("float")) => CType::Float,
+      (None, Some("double")) if longs > 0 => CType::Unsupported("long double"),
+      (None, Some("double")) => CType::Double,
+      (None, Some("__int128")) => CType::Unsupported("__int128"),
+      (None, Some("char" | "__int8")) => CType::int(1, signed != Some(false)),
+      (None, Some("__int16")) => CType::int(2, signed != Some(false)),
+      (None, Some("__int32")) => CType::int(4, signed != Some(false)),
+      (None, Some("__int64")) => CType::int(8, signed != Some(false)),
+      _ if short => CType::int(2, signed != Some(false)),
+      // `long` is 32-bit on Windows (LLP64) and 64-bit elsewhere (LP64).
+      _ if longs == 1 && cfg!(windows) => CType::int(4, signed != Some(false)),
+      _ if longs > 0 => CType::int(8, signed != Some(false)),
+      (None, Some(_)) => CType::int(4, signed != Some(false)),
+      (None, None) if signed.is_some() => CType::int(4, signed.unwrap()),
+      (None, None) => {
+        return Err(match self.peek() {
+          Some(tok) => format!("expected a type, found `{}`", tok.text()),
+          None => "expected a type at end of file".to_string(),
+        });
+      }
+    };
+    Ok(Specifiers { ty, storage })
+  }
+
+  fn record_specifier(&mut self, is_union: bool) -> ParseResult<CType> {
+    self.pos += 1;
+    let mut unsupported = self.skip_qualifiers();
+    let tag = self.peek_ident().map(str::to_string);
+    if tag.is_some() {
+      self.pos += 1;
+    }
+    unsupported = self.skip_qualifiers().or(unsupported);
+    let existing = tag.as_ref().and_then(|tag| self.record_tags.get(tag));
+    let index = match existing {
+      Some(index) => *index,
+      None => {
+        if tag.is_none() && !self.is_punct("{") {
+          return Err("expected `{` after anonymous struct".to_string());
+        }
+        self.header.records.push(Record {
+          name: tag.clone(),
+          is_union,
+          fields: None,
+          unsupported: None,
+          order: 0,
+          aliased: false,
+        });
+        let index = self.header.records.len() - 1;
+        if let Some(tag) = tag.clone() {
+          self.record_tags.insert(tag, index);
+        }
+        index
+      }
+    };
+    if !self.eat("{") {
+      return Ok(CType::Record(index));
+    }
+    let fields = self.fields()?;
+    self.expect("}")?;
+    unsupported = self.skip_qualifiers().or(unsupported);
+    if self.packed {
+      unsupported = Some("packed structs are not supported");
+    }
+    self.definitions += 1;
+    let record = &mut self.header.records[index];
+    if record.fields.is_some() {
+      let line = self.line();
+      self.header.warnings.push(format!(
+        "line {line}: redefinition of `{}`, keeping the first definition",
+        tag.unwrap_or_default()
+      ));
+    } else {
+      record.fields = Some(fields);
+      record.unsupported = unsupported;
+      record.order = self.definitions;
+    }
+    Ok(CType::Record(index))
+  }
+
+  fn fields(&mut self) -> ParseResult<Vec<Field>> {
+    let mut fields = Vec::new();
+    loop {
+      match self.peek() {
+        None => return Err("unexpected end of file in struct".to_string()),
+        Some(Tok::Punct("}")) => return Ok(fields),
+        Some(Tok::Punct(";")) => {
+          self.pos += 1;
+          continue;
+        }
+        Some(Tok::Ident(ident))
+          if ident == "_Static_assert" || ident == "static_assert" =>
+        {
+          self.skip_declaration();
+          continue;
+        }
+        _ => {}
+      }
+      let specifiers = self.specifiers()?;
+      if self.eat(";") {
+        fields.push(Field {
+          name: None,
+          ty: specifiers.ty,
+          bit_width: None,
+        });
+        continue;
+      }
+      loop {
+        let (name, ty) = if self.is_punct(":") {
+          (None, specifiers.ty.clone())
+        } else {
+          self.declarator(specifiers.ty.clone())?
+        };
+        let bit_width = if self.eat(":") {
+          let tokens = self.tokens_until(&[",", ";"]);
+          Some(
+            self
+              .const_expr(&tokens)?
+              .try_into()
+              .map_err(|_| "invalid bit-field width".to_string())?,
+          )
+        } else {
+          None
+        };
+        self.skip_qualifiers();
+        fields.push(Field {
+          name,
+          ty,
+          bit_width,
+        });
+        if !self.eat(",") {
+          self.expect(";")?;
+          break;
+        }
+      }
+    }
+  }
+
+  fn enum_specifier(&mut self) -> ParseResult<CType> {
+    self.pos += 1;
+    self.skip_qualifiers();
+    let tag = self.peek_ident().map(str::to_string);
+    if tag.is_some() {
+      self.pos += 1;
+    }
+    self.skip_qualifiers();
+    let fixed = if self.eat(":") {
+      Some(self.specifiers()?.ty)
+    } else {
+      None
+    };
+    let existing = tag.as_ref().and_then(|tag| self.enum_tags.get(tag));
+    let index = match existing {
+      Some(index) => *index,
+      None => {
+        if tag.is_none() && !self.is_punct("{") {
+          return Err("expected `{` after anonymous enum".to_string());
+        }
+        self.header.enums.push(Enum {
+          name: tag.clone(),
+          ty: CType::int(4, true),
+          variants: Vec::new(),
+          aliased: false,
+        });
+        let index = self.header.enums.len() - 1;
+        if let Some(tag) = tag {
+          self.enum_tags.insert(tag, index);
+        }
+        index
+      }
+    };
+    if !self.eat("{") {
+      return Ok(CType::Enum(index));
+    }
+    let mut variants = Vec::new();
+    let mut next = 0;
+    while !self.eat("}") {
+      let name = self
+        .peek_ident()
+        .ok_or_else(|| "expected an enumerator name".to_string())?
+        .to_string();
+      self.pos += 1;
+      self.skip_qualifiers();
+      if self.eat("=") {
+        let tokens = self.tokens_until(&[",", "}"]);
+        next = self.const_expr(&tokens)?;
+      }
+      self.enumerators.insert(name.clone(), next);
+      variants.push((name, next));
+      next = next.wrapping_add(1);
+      if !self.eat(",") {
+        self.expect("}")?;
+        break;
+      }
+    }
+    let min = variants.iter().map(|(_, v)| *v).min().unwrap_or_default();
+    let max = variants.iter().map(|(_, v)| *v).max().unwrap_or_default();
+    let ty =
+      fixed.unwrap_or(if min >= i32::MIN as i128 && max <= i32::MAX as i128 {
+        CType::int(4, true)
+      } else if min >= 0 && max <= u32::MAX as i128 {
+        CType::int(4, false)
+      } else if min >= 0 && max > i64::MAX as i128 {
+        CType::int(8, false)
+      } else {
+        CType::int(8, true)
+      });
+    let enum_ = &mut self.header.enums[index];
+    enum_.variants = variants;
+    enum_.ty = ty;
+    Ok(CType::Enum(index))
+  }
+
+  /// Collects the tokens up to one of `ends` outside of any parentheses.
+  fn tokens_until(&mut self, ends: &[&str]) -> Vec<Tok> {
+    let mut tokens = Vec::new();
+    let mut depth = 0usize;
+    while let Some(tok) = self.peek() {
+      match tok {
+        Tok::Punct(punct) if depth == 0 && ends.contains(punct) => break,
+        Tok::Punct("(" | "[") => depth += 1,
+        Tok::Punct(")" | "]") if depth == 0 => break,
+        Tok::Punct(")" | "]") => depth -= 1,
+        _ => {}
+      }
+      tokens.push(tok.clone());
+      self.pos += 1;
+    }
+    tokens
+  }
+
+  fn const_expr(&self, tokens: &[Tok]) -> ParseResult<i128> {
+    if tokens
+      .iter()
+      .any(|t| matches!(t, Tok::Ident(n) if n == "sizeof"))
+    {
+      return Err("`sizeof` in constant expressions is not supported".into());
+    }
+    eval(tokens, self)
+  }
+
+  fn declarator(
+    &mut self,
+    base: CType,
+  ) -> ParseResult<(Option<String>, CType)> {
+    let (name, derived) = self.declarator_parts()?;
+    let ty = derived.into_iter().fold(base, |ty, derived| match derived {
+      Derived::Pointer => CType::Pointer(Box::new(ty)),
+      Derived::Array(len) => CType::Array(Box::new(ty), len),
+      Derived::Function(params, variadic) => {
+        CType::Function(Box::new(FunctionType {
+          result: ty,
+          params,
+          variadic,
+        }))
+      }
+    });
+    Ok((name, ty))
+  }
+
+  /// Parses a possibly abstract declarator into its name and the types it
+  /// derives from the base type, innermost first.
+  fn declarator_parts(
+    &mut self,
+  ) -> ParseResult<(Option<String>, Vec<Derived>)> {
+    self.skip_qualifiers();
+    let mut derived = Vec::new();
+    // `^` declares a block pointer, which is passed like a pointer.
+    while self.eat("*") || self.eat("^") {
+      derived.push(Derived::Pointer);
+      self.skip_qualifiers();
+    }
+    let mut name = None;
+    let mut inner = Vec::new();
+    if self.is_punct("(") && self.is_nested_declarator() {
+      self.pos += 1;
+      (name, inner) = self.declarator_parts()?;
+      self.expect(")")?;
+    } else if let Some(ident) = self.peek_ident()
+      && !is_keyword(ident)
+    {
+      name = Some(ident.to_string());
+      self.pos += 1;
+    }
+    let mut suffixes = Vec::new();
+    loop {
+      if self.eat("[") {
+        let mut tokens = self.tokens_until(&["]"]);
+        self.expect("]")?;
+        tokens.retain(|t| {
+          !matches!(t, Tok::Ident(n) if n == "static" || QUALIFIERS.contains(&n.as_str()))
+        });
+        let len = if tokens.is_empty() {
+          None
+        } else {
+          let len = self.const_expr(&tokens)?;
+          Some(u64::try_from(len).map_err(|_| "negative array size")?)
+        };
+        suffixes.push(Derived::Array(len));
+      } else if self.eat("(") {
+        let (params, variadic) = self.params()?;
+        suffixes.push(Derived::Function(params, variadic));
+      } else {
+        break;
+      }
+    }
+    derived.extend(suffixes.into_iter().rev());
+    derived.extend(inner);
+    Ok((name, derived))
+  }
+
+  /// Whether the parenthesis at the current position opens a nested
+  /// declarator like `(*name)` rather than a parameter list.
+  fn is_nested_declarator(&self) -> bool {
+    match self.peek_at(1) {
+      Some(Tok::Punct("*" | "^" | "(")) => true,
+      Some(Tok::Ident(ident)) => {
+        QUALIFIERS.contains(&ident.as_str())
+          || ATTRIBUTES.contains(&ident.as_str())
+          || !(is_keyword(ident) || self.typedefs.contains_key(ident))
+      }
+      _ => false,
+    }
+  }
+
+  fn params(&mut self) -> ParseResult<(Vec<Param>, bool)> {
+    let mut params = Vec::new();
+    if self.eat(")") {
+      return Ok((params, false));
+    }
+    if self.peek_ident() == Some("void")
+      && self.peek_at(1).is_some_and(|t| t.is_punct(")"))
+    {
+      self.pos += 2;
+      return Ok((params, false));
+    }
+    loop {
+      if self.eat("...") {
+        self.expect(")")?;
+        return Ok((params, true));
+      }
+      let specifiers = self.specifiers()?;
+      let (name, ty) = self.declarator(specifiers.ty)?;
+      self.skip_qualifiers();
+      // Array and function parameters are adjusted to pointers.
+      let ty = match ty {
+        CType::Array(element, _) => CType::Pointer(element),
+        CType::Function(function) => {
+          CType::Pointer(Box::new(CType::Function(function)))
+        }
+        ty => ty,
+      };
+      params.push(Param { name, ty });
+      if !self.eat(",") {
+        self.expect(")")?;
+        return Ok((params, false));
+      }
+    }
+  }
+
+  /// The value of a macro expansion, if it is a constant.
+  fn constant_value(&self, tokens: &[Tok]) -> Option<ConstValue> {
+    if tokens.iter().all(|t| matches!(t, Tok::Str(_))) {
+      let strings = tokens.iter().map(|t| match t {
+        Tok::Str(value) => value.as_str(),
+        _ => unreachable!(),
+      });
+      return Some(ConstValue::Str(strings.collect()));
+    }
+    let mut inner = tokens;
+    while let [Tok::Punct("("), rest @ .., Tok::Punct(")")] = inner {
+      inner = rest;
+    }
+    match inner {
+      [Tok::Number(text)] | [Tok::Punct("+"), Tok::Number(text)]
+        if parse_int(text).is_none() =>
+      {
+        return parse_float(text).map(ConstValue::Float);
+      }
+      [Tok::Punct("-"), Tok::Number(text)] if parse_int(text).is_none() => {
+        return parse_float(text).map(|value| ConstValue::Float(-value));
+      }
+      _ => {}
+    }
+    self.const_expr(tokens).ok().map(ConstValue::Int)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse_ok(source: &str) -> Header {
+    let header = parse(source, &[]);
+    assert_eq!(header.warnings, Vec::<String>::new());
+    header
+  }
+
+  fn constants(header: &Header) -> Vec<(&str, ConstValue)> {
+    header
+      .constants
+      .iter()
+      .map(|c| (c.name.as_str(), c.value.clone()))
+      .collect()
+  }
+
+  #[test]
+  fn macro_constants() {
+    let header = parse_ok(
+      r#"
+#ifndef LIB_H
+#define LIB_H
+#define VERSION_MAJOR 2
+#define VERSION_MINOR 0x10
+#define VERSION ((VERSION_MAJOR << 16) | VERSION_MINOR)
+#define FLAG(n) (1u << (n))
+#define FLAG_B FLAG(3)
+#define NAME "lib" \
+  "name"
+#define RATIO -0.5f
+#define NEWLINE '\n'
+#define EXPORT __attribute__((visibility("default")))
+#define MASK ((uint32_t)0xff)
+#undef VERSION_MINOR
+#define VERSION_MINOR 1 // changed
+#endif
+"#,
+    );
+    assert_eq!(
+      constants(&header),
+      vec![
+        ("VERSION_MAJOR", ConstValue::Int(2)),
+        ("VERSION", ConstValue::Int(0x20001)),
+        ("FLAG_B", ConstValue::Int(8)),
+        ("NAME", ConstValue::Str("libname".to_string())),
+        ("RATIO", ConstValue::Float(-0.5)),
+        ("NEWLINE", ConstValue::Int(10)),
+        ("MASK", ConstValue::Int(0xff)),
+        ("VERSION_MINOR", ConstValue::Int(1)),
+      ]
+    );
+  }
+
+  #[test]
+  fn conditionals() {
+    let header = parse(
+      r#"
+#define FEATURE 2
+#if FEATURE > 1 && defined(FEATURE) && !defined UNDEFINED
+int taken(void);
+#elif 1
+int not_taken_elif(void);
+#else
+int not_taken(void);
+#endif
+#ifdef __cplusplus
+extern "C" {
+class Foo {};
+#endif
+#if UNKNOWN_MACRO || __has_include(<foo.h>)
+int unknown(void);
+#elif CUSTOM == 3
+int custom(void);
+#endif
+#ifdef __cplusplus
+}
+#endif
+"#,
+      &[("CUSTOM".to_string(), "3".to_string())],
+    );
+    assert_eq!(header.warnings, Vec::<String>::new());
+    let names = header.functions.iter().map(|f| f.name.as_str());
+    assert_eq!(names.collect::<Vec<_>>(), vec!["taken", "custom"]);
+  }
+
+  #[test]
+  fn functions() {
+    let header = parse_ok(
+      r#"
+#define API extern __attribute__((visibility("default")))
+#define DECLARE_GETTER(type, name) API type get_##name(void)
+#ifdef __cplusplus
+extern "C" {
+#endif
+typedef struct context context_t;
+typedef void (*callback_t)(void *user_data, int status);
+API context_t *context_new(const char *name, size_t capacity);
+API void context_free(context_t *context);
+API int context_run(context_t *, callback_t callback, void *user_data);
+API const char *version(void);
+API int printf_like(const char *format, ...);
+API void sum(const double values[], unsigned long long count,
+             int (*compare)(const void *, const void *));
+DECLARE_GETTER(uint16_t, port);
+static inline int helper(int x) { return x + 1; }
+API FILE *open_file(void);
+API int counter;
+#ifdef __cplusplus
+}
+#endif
+"#,
+    );
+    let function = |name: &str| {
+      &header.functions.iter().find(|f| f.name == name).unwrap().ty
+    };
+    assert_eq!(
+      header
+        .functions
+        .iter()
+        .map(|f| &f.name[..])
+        .collect::<Vec<_>>(),
+      vec![
+        "context_new",
+        "context_free",
+        "context_run",
+        "version",
+        "printf_like",
+        "sum",
+        "get_port",
+        "open_file",
+      ]
+    );
+    let context_new = function("context_new");
+    assert!(matches!(
+      &context_new.result,
+      CType::Pointer(inner) if matches!(**inner, CType::Record(_))
+    ));
+    assert_eq!(
+      context_new.params[0].ty,
+      CType::Pointer(Box::new(CType::int(1, true)))
+    );
+    assert_eq!(context_new.params[0].name.as_deref(), Some("name"));
+    assert!(matches!(
+      context_new.params[1].ty,
+      CType::Int {
+        size: 8,
+        signed: false,
+        pointer_sized: true
+      }
+    ));
+    let run = function("context_run");
+    assert_eq!(run.params[0].name, None);
+    let CType::Pointer(callback) = &run.params[1].ty else {
+      panic!("expected a function pointer");
+    };
+    let CType::Function(callback) = &**callback else {
+      panic!("expected a function pointer");
+    };
+    assert_eq!(callback.result, CType::Void);
+    assert_eq!(callback.params.len(), 2);
+    assert!(function("printf_like").variadic);
+    let sum = function("sum");
+    assert_eq!(sum.params[0].ty, CType::Pointer(Box::new(CType::Double)));
+    assert_eq!(sum.params[1].ty, CType::int(8, false));
+    assert!(
+      matches!(&sum.params[2].ty, CType::Pointer(f) if matches!(**f, CType::Function(_)))
+    );
+    assert_eq!(function("get_port").result, CType::int(2, false));
+    assert_eq!(
+      function("open_file").result,
+      CType::Pointer(Box::new(CType::Unknown("FILE".to_string())))
+    );
+    assert_eq!(header.variables[0].name, "counter");
+    assert_eq!(header.variables[0].ty, CType::int(4, true));
+  }
+
+  #[test]
+  fn records_and_enums() {
+    let header = parse_ok(
+      r#"
+#define NAME_LEN 16
+typedef enum { RED, GREEN = 5, BLUE } color_t;
+enum flags { FLAG_A = 1 << 0, FLAG_B = 1 << 1, FLAG_ALL = FLAG_A | FLAG_B };
+enum { ANONYMOUS_A = 3 };
+typedef struct point { double x, y; } point_t;
+struct shape {
+  point_t points[4][2];
+  char name[NAME_LEN + 1];
+  color_t color;
+  struct { int a; } nested;
+  union { int i; float f; };
+  unsigned flags : 3;
+  struct shape *next;
+};
+#pragma pack(push, 1)
+struct packed { char a; int b; };
+#pragma pack(pop)
+struct aligned { int a; } __attribute__((aligned(16)));
+"#,
+    );
+    let enums = header
+      .enums
+      .iter()
+      .map(|e| (e.name.as_deref(), e.variants.clone()))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      enums,
+      vec![
+        (
+          Some("color_t"),
+          vec![
+            ("RED".to_string(), 0),
+            ("GREEN".to_string(), 5),
+            ("BLUE".to_string(), 6)
+          ]
+        ),
+        (
+          Some("flags"),
+          vec![
+            ("FLAG_A".to_string(), 1),
+            ("FLAG_B".to_string(), 2),
+            ("FLAG_ALL".to_string(), 3)
+          ]
+        ),
+        (None, vec![("ANONYMOUS_A".to_string(), 3)]),
+      ]
+    );
+
+    let record = |name: &str| {
+      header
+        .records
+        .iter()
+        .find(|r| r.name.as_deref() == Some(name))
+        .unwrap()
+    };
+    let point = record("point_t");
+    assert_eq!(point.fields.as_ref().unwrap().len(), 2);
+    let shape = record("shape");
+    let fields = shape.fields.as_ref().unwrap();
+    assert!(matches!(
+      &fields[0].ty,
+      CType::Array(inner, Some(4)) if matches!(**inner, CType::Array(_, Some(2)))
+    ));
+    assert_eq!(
+      fields[1].ty,
+      CType::Array(Box::new(CType::int(1, true)), Some(17))
+    );
+    assert!(matches!(fields[2].ty, CType::Enum(0)));
+    assert!(matches!(fields[3].ty, CType::Record(_)));
+    assert_eq!(fields[4].name, None);
+    assert_eq!(fields[5].bit_width, Some(3));
+    assert!(shape.order > point.order);
+    assert_eq!(
+      record("packed").unsupported,
+      Some("packed structs are not supported")
+    );
+    assert_eq!(
+      record("aligned").unsupported,
+      Some("over-aligned structs are not supported")
+    );
+  }
+
+  #[test]
+  fn recovers_from_errors() {
+    let header = parse(
+      r#"
+int before(void);
+int broken(int x y);
+struct s { int a b; };
+int after(void);
+"#,
+      &[],
+    );
+    assert_eq!(header.warnings.len(), 2);
+    assert!(header.warnings[0].starts_with("line 3: expected `)`"));
+    let names = header.functions.iter().map(|f| f.name.as_str());
+    assert_eq!(names.collect::<Vec<_>>(), vec!["before", "after"]);
+  }
+}
@@ -0,0 +1,47 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+mod bindgen;
+mod c_header;
+
+use std::path::Path;
+
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+
+use crate::args::FfiBindgenFlags;
+use crate::args::FfiFlags;
+use crate::colors;
+use crate::display;
+
+pub fn ffi(ffi_flags: FfiFlags) -> Result<(), AnyError> {
+  match ffi_flags {
+    FfiFlags::Bindgen(bindgen_flags) => generate_bindings(bindgen_flags),
+  }
+}
+
+fn generate_bindings(flags: FfiBindgenFlags) -> Result<(), AnyError> {
+  let path = Path::new(&flags.header);
+  let source = std::fs::read_to_string(path)
+    .with_context(|| format!("Failed to read C header at {}", flags.header))?;
+  let header = c_header::parse(&source, &flags.defines);
+  let source_name = path
+    .file_name()
+    .map(|name| name.to_string_lossy())
+    .unwrap_or_else(|| flags.header.as_str().into());
+  let bindings = bindgen::generate(&header, &source_name);
+  for warning in header.warnings.iter().chain(&bindings.warnings) {
+    log::warn!("{} {}: {}", colors::yellow("Warning"), source_name, warning);
+  }
+
+  match &flags.output {
+    Some(output) => {
+      std::fs::write(output, bindings.code)
+        .with_context(|| format!("Failed to write bindings to {output}"))?;
+      log::info!("{} {}", colors::green("Wrote"), output);
+    }
+    None => {
+      display::write_to_stdout_ignore_sigpipe(bindings.code.as_bytes())?;
+    }
+  }
+  Ok(())
+}
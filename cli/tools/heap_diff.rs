@@ -0,0 +1,586 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::Path;
+
+use deno_core::anyhow::Context;
+use deno_core::anyhow::bail;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::args::HeapDiffFlags;
+use crate::colors;
+use crate::display;
+
+/// How many retainers are shown for each constructor.
+const MAX_RETAINERS: usize = 5;
+
+#[derive(Deserialize)]
+struct RawSnapshot {
+  snapshot: RawSnapshotInfo,
+  nodes: Vec<u64>,
+  edges: Vec<u64>,
+  strings: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct RawSnapshotInfo {
+  meta: RawSnapshotMeta,
+}
+
+#[derive(Deserialize)]
+struct RawSnapshotMeta {
+  node_fields: Vec<String>,
+  node_types: Vec<serde_json::Value>,
+  edge_fields: Vec<String>,
+  edge_types: Vec<serde_json::Value>,
+}
+
+/// Field offsets and enum values of the flat `nodes` and `edges` arrays, as
+/// described by the snapshot's `meta`.
+struct Layout {
+  node_field_count: usize,
+  node_type: usize,
+  node_name: usize,
+  node_id: usize,
+  node_self_size: usize,
+  node_edge_count: usize,
+  node_types: Vec<String>,
+  edge_field_count: usize,
+  edge_type: usize,
+  edge_name: usize,
+  edge_to_node: usize,
+  edge_types: Vec<String>,
+}
+
+impl Layout {
+  fn new(meta: &RawSnapshotMeta) -> Result<Self, AnyError> {
+    fn field(fields: &[String], name: &str) -> Result<usize, AnyError> {
+      match fields.iter().position(|f| f == name) {
+        Some(index) => Ok(index),
+        None => bail!("Missing \"{}\" field in heap snapshot meta.", name),
+      }
+    }
+    fn types(types: &[serde_json::Value]) -> Vec<String> {
+      types
+        .first()
+        .and_then(|types| types.as_array())
+        .map(|types| {
+          types
+            .iter()
+            .map(|t| t.as_str().unwrap_or_default().to_string())
+            .collect()
+        })
+        .unwrap_or_default()
+    }
+
+    Ok(Self {
+      node_field_count: meta.node_fields.len(),
+      node_type: field(&meta.node_fields, "type")?,
+      node_name: field(&meta.node_fields, "name")?,
+      node_id: field(&meta.node_fields, "id")?,
+      node_self_size: field(&meta.node_fields, "self_size")?,
+      node_edge_count: field(&meta.node_fields, "edge_count")?,
+      node_types: types(&meta.node_types),
+      edge_field_count: meta.edge_fields.len(),
+      edge_type: field(&meta.edge_fields, "type")?,
+      edge_name: field(&meta.edge_fields, "name_or_index")?,
+      edge_to_node: field(&meta.edge_fields, "to_node")?,
+      edge_types: types(&meta.edge_types),
+    })
+  }
+}
+
+struct Node {
+  id: u64,
+  self_size: u64,
+  /// Index into [`HeapSnapshot::groups`].
+  group: usize,
+  first_edge: usize,
+  edge_count: usize,
+}
+
+struct Edge {
+  /// Weak and shortcut edges don't keep their target alive.
+  retains: bool,
+  /// Index into [`HeapSnapshot::strings`], or `None` for element and hidden
+  /// edges, which are named by their index instead.
+  name: Option<usize>,
+  to_node: usize,
+}
+
+/// A parsed `.heapsnapshot` file, with every node assigned to a group named
+/// after its constructor, the same way Chrome DevTools' summary view does.
+struct HeapSnapshot {
+  nodes: Vec<Node>,
+  edges: Vec<Edge>,
+  groups: Vec<String>,
+  strings: Vec<String>,
+}
+
+impl HeapSnapshot {
+  fn parse(bytes: &[u8]) -> Result<Self, AnyError> {
+    let raw: RawSnapshot = serde_json::from_slice(bytes)?;
+    let layout = Layout::new(&raw.snapshot.meta)?;
+    let string = |index: u64| -> &str {
+      raw
+        .strings
+        .get(index as usize)
+        .map(|s| s.as_str())
+        .unwrap_or_default()
+    };
+    let node_type = |index: u64| -> &str {
+      layout
+        .node_types
+        .get(index as usize)
+        .map(|s| s.as_str())
+        .unwrap_or_default()
+    };
+
+    let mut groups = Vec::new();
+    let mut group_index = HashMap::new();
+    let mut nodes =
+      Vec::with_capacity(raw.nodes.len() / layout.node_field_count);
+    let mut first_edge = 0;
+    for fields in raw.nodes.chunks_exact(layout.node_field_count) {
+      let kind = node_type(fields[layout.node_type]);
+      let group = match kind {
+        "object" | "native" => string(fields[layout.node_name]).to_string(),
+        "closure" => "(closure)".to_string(),
+        "code" => "(compiled code)".to_string(),
+        "hidden" => "(system)".to_string(),
+        "concatenated string" | "sliced string" => "(string)".to_string(),
+        kind => format!("({kind})"),
+      };
+      let next_index = groups.len();
+      let group = *group_index.entry(group.clone()).or_insert_with(|| {
+        groups.push(group);
+        next_index
+      });
+      let edge_count = fields[layout.node_edge_count] as usize;
+      nodes.push(Node {
+        id: fields[layout.node_id],
+        self_size: fields[layout.node_self_size],
+        group,
+        first_edge,
+        edge_count,
+      });
+      first_edge += edge_count;
+    }
+
+    let edges = raw
+      .edges
+      .chunks_exact(layout.edge_field_count)
+      .map(|fields| {
+        let kind = layout
+          .edge_types
+          .get(fields[layout.edge_type] as usize)
+          .map(|s| s.as_str())
+          .unwrap_or_default();
+        Edge {
+          retains: kind != "weak" && kind != "shortcut",
+          name: match kind {
+            "element" | "hidden" => None,
+            _ => Some(fields[layout.edge_name] as usize),
+          },
+          to_node: fields[layout.edge_to_node] as usize
+            / layout.node_field_count,
+        }
+      })
+      .collect();
+
+    Ok(Self {
+      nodes,
+      edges,
+      groups,
+      strings: raw.strings,
+    })
+  }
+
+  fn outgoing_edges(&self, node: &Node) -> &[Edge] {
+    let end = (node.first_edge + node.edge_count).min(self.edges.len());
+    &self.edges[node.first_edge.min(end)..end]
+  }
+
+  /// Returns a name for the edge like DevTools shows it in the retainers
+  /// view. The index of element edges is left out so that all the elements
+  /// of an array are reported as one retainer.
+  fn edge_name(&self, edge: &Edge) -> &str {
+    match edge.name {
+      Some(index) => self
+        .strings
+        .get(index)
+        .map(|s| s.as_str())
+        .unwrap_or_default(),
+      None => "[]",
+    }
+  }
+
+  fn stats_by_group(&self) -> HashMap<&str, (i64, i64)> {
+    let mut stats: HashMap<&str, (i64, i64)> = HashMap::new();
+    for node in &self.nodes {
+      let entry = stats.entry(&self.groups[node.group]).or_default();
+      entry.0 += 1;
+      entry.1 += node.self_size as i64;
+    }
+    stats
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Retainer {
+  /// The constructor of the retaining object.
+  constructor: String,
+  edge: String,
+  /// How many new objects are retained through this edge.
+  count: usize,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConstructorDiff {
+  constructor: String,
+  count_delta: i64,
+  size_delta: i64,
+  /// Objects that only exist in the second snapshot.
+  new_count: usize,
+  retainers: Vec<Retainer>,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HeapDiff {
+  size_before: i64,
+  size_after: i64,
+  count_before: i64,
+  count_after: i64,
+  constructors: Vec<ConstructorDiff>,
+}
+
+/// Compares two snapshots and returns the `top` constructors that grew the
+/// most in self size, with the most common retainers of the objects that
+/// were allocated between the two snapshots.
+fn diff_snapshots(
+  before: &HeapSnapshot,
+  after: &HeapSnapshot,
+  top: usize,
+) -> HeapDiff {
+  let before_stats = before.stats_by_group();
+  let after_stats = after.stats_by_group();
+
+  let mut constructors = after_stats
+    .iter()
+    .map(|(name, (count, size))| {
+      let (count_before, size_before) =
+        before_stats.get(name).copied().unwrap_or_default();
+      (*name, count - count_before, size - size_before)
+    })
+    .chain(
+      before_stats
+        .iter()
+        .filter(|(name, _)| !after_stats.contains_key(*name))
+        .map(|(name, (count, size))| (*name, -count, -size)),
+    )
+    .filter(|(_, count_delta, size_delta)| *count_delta > 0 || *size_delta > 0)
+    .collect::<Vec<_>>();
+  constructors
+    .sort_by(|a, b| b.2.cmp(&a.2).then(b.1.cmp(&a.1)).then(a.0.cmp(b.0)));
+  constructors.truncate(top);
+
+  // Node ids are stable across snapshots of the same isolate, so any id
+  // that is not in the first snapshot belongs to an object allocated after
+  // it was taken.
+  let before_ids = before.nodes.iter().map(|n| n.id).collect::<HashSet<_>>();
+  let reported_groups = constructors
+    .iter()
+    .filter_map(|(name, _, _)| {
+      after.groups.iter().position(|group| group == name)
+    })
+    .collect::<HashSet<_>>();
+  let mut new_counts: HashMap<usize, usize> = HashMap::new();
+  for node in &after.nodes {
+    if reported_groups.contains(&node.group) && !before_ids.contains(&node.id) {
+      *new_counts.entry(node.group).or_default() += 1;
+    }
+  }
+  let mut retainers: HashMap<usize, HashMap<(usize, &str), usize>> =
+    HashMap::new();
+  for node in &after.nodes {
+    for edge in after.outgoing_edges(node) {
+      if !edge.retains {
+        continue;
+      }
+      let Some(target) = after.nodes.get(edge.to_node) else {
+        continue;
+      };
+      if !reported_groups.contains(&target.group)
+        || before_ids.contains(&target.id)
+      {
+        continue;
+      }
+      *retainers
+        .entry(target.group)
+        .or_default()
+        .entry((node.group, after.edge_name(edge)))
+        .or_default() += 1;
+    }
+  }
+
+  let constructors = constructors
+    .into_iter()
+    .map(|(name, count_delta, size_delta)| {
+      let group = after.groups.iter().position(|group| group == name);
+      let mut group_retainers = group
+        .and_then(|group| retainers.remove(&group))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|((retainer_group, edge), count)| Retainer {
+          constructor: after.groups[retainer_group].clone(),
+          edge: edge.to_string(),
+          count,
+        })
+        .collect::<Vec<_>>();
+      group_retainers.sort_by(|a, b| {
+        b.count
+          .cmp(&a.count)
+          .then_with(|| a.constructor.cmp(&b.constructor))
+          .then_with(|| a.edge.cmp(&b.edge))
+      });
+      group_retainers.truncate(MAX_RETAINERS);
+      ConstructorDiff {
+        constructor: name.to_string(),
+        count_delta,
+        size_delta,
+        new_count: group
+          .and_then(|group| new_counts.get(&group).copied())
+          .unwrap_or_default(),
+        retainers: group_retainers,
+      }
+    })
+    .collect();
+
+  let total = |stats: &HashMap<&str, (i64, i64)>| {
+    stats
+      .values()
+      .fold((0, 0), |acc, (count, size)| (acc.0 + count, acc.1 + size))
+  };
+  let (count_before, size_before) = total(&before_stats);
+  let (count_after, size_after) = total(&after_stats);
+  HeapDiff {
+    size_before,
+    size_after,
+    count_before,
+    count_after,
+    constructors,
+  }
+}
+
+fn format_size_delta(delta: i64) -> String {
+  let sign = if delta >= 0 { "+" } else { "" };
+  format!("{sign}{}", display::human_size(delta as f64))
+}
+
+fn format_diff(diff: &HeapDiff) -> String {
+  let mut out = String::new();
+  let _ = writeln!(
+    out,
+    "{} {} -> {} ({}, {:+} objects)",
+    colors::bold("Heap size:"),
+    display::human_size(diff.size_before as f64),
+    display::human_size(diff.size_after as f64),
+    format_size_delta(diff.size_after - diff.size_before),
+    diff.count_after - diff.count_before,
+  );
+  if diff.constructors.is_empty() {
+    let _ = writeln!(out, "\nNo constructors grew between the snapshots.");
+    return out;
+  }
+  for constructor in &diff.constructors {
+    let _ = writeln!(
+      out,
+      "\n{} {} ({:+} objects, {} new)",
+      colors::green(&constructor.constructor),
+      format_size_delta(constructor.size_delta),
+      constructor.count_delta,
+      constructor.new_count,
+    );
+    for retainer in &constructor.retainers {
+      let _ = writeln!(
+        out,
+        "  {} {} {}",
+        colors::gray(format!("{:>6}", retainer.count)),
+        retainer.constructor,
+        colors::gray(&retainer.edge),
+      );
+    }
+  }
+  out
+}
+
+fn read_snapshot(path: &str) -> Result<HeapSnapshot, AnyError> {
+  let bytes = std::fs::read(Path::new(path))
+    .with_context(|| format!("Failed to read heap snapshot at {path}"))?;
+  HeapSnapshot::parse(&bytes)
+    .with_context(|| format!("Failed to parse heap snapshot at {path}"))
+}
+
+pub fn heap_diff(heap_diff_flags: HeapDiffFlags) -> Result<(), AnyError> {
+  let before = read_snapshot(&heap_diff_flags.before)?;
+  let after = read_snapshot(&heap_diff_flags.after)?;
+  let diff = diff_snapshots(&before, &after, heap_diff_flags.top);
+  if heap_diff_flags.json {
+    display::write_json_to_stdout(&diff)
+  } else {
+    display::write_to_stdout_ignore_sigpipe(format_diff(&diff).as_bytes())?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use deno_core::serde_json::json;
+
+  use super::*;
+
+  /// Builds a snapshot with the given `(type, name, id, self_size)` nodes and
+  /// `(from, type, name, to)` edges, where nodes are referred to by their
+  /// position in `nodes`.
+  fn snapshot(
+    nodes: &[(&str, &str, u64, u64)],
+    edges: &[(usize, &str, &str, usize)],
+  ) -> HeapSnapshot {
+    let node_types = ["hidden", "array", "string", "object", "closure"];
+    let edge_types = ["context", "element", "property", "weak"];
+    let mut strings: Vec<String> = vec![];
+    let mut string = |s: &str| {
+      let index = strings.iter().position(|x| x == s).unwrap_or_else(|| {
+        strings.push(s.to_string());
+        strings.len() - 1
+      });
+      index as u64
+    };
+    let mut flat_nodes = vec![];
+    for (i, (kind, name, id, size)) in nodes.iter().enumerate() {
+      let edge_count = edges.iter().filter(|e| e.0 == i).count() as u64;
+      flat_nodes.extend([
+        node_types.iter().position(|t| t == kind).unwrap() as u64,
+        string(name),
+        *id,
+        *size,
+        edge_count,
+      ]);
+    }
+    let mut sorted_edges = edges.to_vec();
+    sorted_edges.sort_by_key(|e| e.0);
+    let mut flat_edges = vec![];
+    for (_, kind, name, to) in sorted_edges {
+      let name = if kind == "element" {
+        name.parse().unwrap()
+      } else {
+        string(name)
+      };
+      flat_edges.extend([
+        edge_types.iter().position(|t| *t == kind).unwrap() as u64,
+        name,
+        (to * 5) as u64,
+      ]);
+    }
+    let raw = json!({
+      "snapshot": {
+        "meta": {
+          "node_fields": ["type", "name", "id", "self_size", "edge_count"],
+          "node_types": [node_types, "string", "number", "number", "number"],
+          "edge_fields": ["type", "name_or_index", "to_node"],
+          "edge_types": [edge_types, "string_or_number", "node"],
+        },
+      },
+      "nodes": flat_nodes,
+      "edges": flat_edges,
+      "strings": strings,
+    });
+    HeapSnapshot::parse(raw.to_string().as_bytes()).unwrap()
+  }
+
+  #[test]
+  fn diffs_snapshots() {
+    let before = snapshot(
+      &[
+        ("hidden", "(GC roots)", 1, 0),
+        ("object", "Cache", 3, 32),
+        ("array", "", 5, 16),
+        ("object", "Item", 7, 24),
+        ("object", "Temp", 9, 100),
+      ],
+      &[
+        (0, "property", "cache", 1),
+        (1, "property", "items", 2),
+        (2, "element", "0", 3),
+      ],
+    );
+    let after = snapshot(
+      &[
+        ("hidden", "(GC roots)", 1, 0),
+        ("object", "Cache", 3, 32),
+        ("array", "", 5, 64),
+        ("object", "Item", 7, 24),
+        ("object", "Item", 11, 24),
+        ("object", "Item", 13, 24),
+        ("object", "Listener", 15, 8),
+        ("object", "Item", 17, 24),
+      ],
+      &[
+        (0, "property", "cache", 1),
+        (1, "property", "items", 2),
+        (2, "element", "0", 3),
+        (2, "element", "1", 4),
+        (2, "element", "2", 5),
+        (1, "property", "listener", 6),
+        (6, "property", "item", 7),
+        (1, "weak", "last", 7),
+      ],
+    );
+
+    let diff = diff_snapshots(&before, &after, 2);
+    assert_eq!(
+      diff,
+      HeapDiff {
+        size_before: 172,
+        size_after: 200,
+        count_before: 5,
+        count_after: 8,
+        constructors: vec![
+          ConstructorDiff {
+            constructor: "Item".to_string(),
+            count_delta: 3,
+            size_delta: 72,
+            new_count: 3,
+            retainers: vec![
+              Retainer {
+                constructor: "(array)".to_string(),
+                edge: "[]".to_string(),
+                count: 2,
+              },
+              Retainer {
+                constructor: "Listener".to_string(),
+                edge: "item".to_string(),
+                count: 1,
+              },
+            ],
+          },
+          ConstructorDiff {
+            constructor: "(array)".to_string(),
+            count_delta: 0,
+            size_delta: 48,
+            new_count: 0,
+            retainers: vec![],
+          },
+        ],
+      }
+    );
+  }
+}
@@ -1,11 +1,14 @@
 // Copyright 2018-2026 the Deno authors. MIT license.
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt::Write;
 use std::sync::Arc;
 
 use deno_ast::ModuleSpecifier;
+use deno_config::workspace::Workspace;
+use deno_core::anyhow::Context;
 use deno_core::anyhow::bail;
 use deno_core::error::AnyError;
 use deno_core::serde_json;
@@ -23,8 +26,11 @@ use deno_lib::version::DENO_VERSION_INFO;
 use deno_npm::NpmPackageId;
 use deno_npm::NpmResolutionPackage;
 use deno_npm::npm_rc::ResolvedNpmRc;
+use deno_npm::registry::NpmRegistryApi;
+use deno_npm::resolution::NpmDependencyPathStep;
 use deno_npm::resolution::NpmResolutionSnapshot;
 use deno_npm_installer::graph::NpmCachingStrategy;
+use deno_package_json::PackageJsonDepValue;
 use deno_path_util::resolve_url_or_path;
 use deno_resolver::DenoResolveErrorKind;
 use deno_resolver::display::DisplayTreeNode;
@@ -36,10 +42,17 @@ use crate::args::Flags;
 use crate::args::InfoFlags;
 use crate::display;
 use crate::factory::CliFactory;
+use crate::graph_container::CollectSpecifiersOptions;
 use crate::graph_util::graph_exit_integrity_errors;
+use crate::graph_util::graph_import_paths;
 use crate::npm::CliManagedNpmResolver;
 
+mod graph;
+
 const JSON_SCHEMA_VERSION: u8 = 1;
+/// The maximum number of import chains and npm dependency paths
+/// `--why` will look for from each starting point.
+const MAX_WHY_PATHS: usize = 50;
 
 pub async fn info(
   flags: Arc<Flags>,
@@ -47,107 +60,26 @@ pub async fn info(
 ) -> Result<(), AnyError> {
   let factory = CliFactory::from_flags(flags);
   let cli_options = factory.cli_options()?;
+  if let Some(why) = &info_flags.why {
+    return info_why(
+      &factory,
+      info_flags.file.as_deref(),
+      why,
+      info_flags.json,
+    )
+    .await;
+  }
+  if info_flags.format.is_some() || info_flags.cycles {
+    return info_graph(&factory, &info_flags).await;
+  }
   if let Some(specifier) = info_flags.file {
     let module_graph_builder = factory.module_graph_builder().await?;
     let module_graph_creator = factory.module_graph_creator().await?;
     let npm_resolver = factory.npm_resolver().await?;
     let maybe_lockfile = factory.maybe_lockfile().await?;
-    let resolver = factory.workspace_resolver().await?.clone();
     let npmrc = factory.npmrc()?;
-    let node_resolver = factory.node_resolver().await?;
-
-    let cwd_url =
-      url::Url::from_directory_path(cli_options.initial_cwd()).unwrap();
-
-    let maybe_import_specifier = match resolver.resolve(
-      &specifier,
-      &cwd_url,
-      deno_resolver::workspace::ResolutionKind::Execution,
-    ) {
-      Ok(resolved) => match resolved {
-        deno_resolver::workspace::MappedResolution::Normal {
-          specifier,
-          ..
-        }
-        | deno_resolver::workspace::MappedResolution::WorkspaceJsrPackage {
-          specifier,
-          ..
-        } => Some(specifier),
-        deno_resolver::workspace::MappedResolution::WorkspaceNpmPackage {
-          target_pkg_json,
-          sub_path,
-          ..
-        } => Some(
-          node_resolver
-            .resolve_package_subpath_from_deno_module(
-              target_pkg_json.clone().dir_path(),
-              sub_path.as_deref(),
-              Some(&cwd_url),
-              node_resolver::ResolutionMode::Import,
-              node_resolver::NodeResolutionKind::Execution,
-            )?
-            .into_url()?,
-        ),
-        deno_resolver::workspace::MappedResolution::PackageJson {
-          alias,
-          sub_path,
-          dep_result,
-          ..
-        } => match dep_result.as_ref().map_err(|e| e.clone())? {
-          deno_package_json::PackageJsonDepValue::File(_) => {
-            return Err(
-              DenoResolveErrorKind::UnsupportedPackageJsonFileSpecifier
-                .into_box()
-                .into(),
-            );
-          }
-          deno_package_json::PackageJsonDepValue::Workspace(version_req) => {
-            let pkg_folder = resolver
-              .resolve_workspace_pkg_json_folder_for_pkg_json_dep(
-                alias,
-                version_req,
-              )?;
-            Some(
-              node_resolver
-                .resolve_package_subpath_from_deno_module(
-                  pkg_folder,
-                  sub_path.as_deref(),
-                  Some(&cwd_url),
-                  node_resolver::ResolutionMode::Import,
-                  node_resolver::NodeResolutionKind::Execution,
-                )?
-                .into_url()?,
-            )
-          }
-          deno_package_json::PackageJsonDepValue::Req(req) => {
-            Some(ModuleSpecifier::parse(&format!(
-              "npm:{}{}",
-              req,
-              sub_path.map(|s| format!("/{}", s)).unwrap_or_default()
-            ))?)
-          }
-        },
-        deno_resolver::workspace::MappedResolution::PackageJsonImport {
-          pkg_json,
-        } => Some(
-          node_resolver
-            .resolve_package_import(
-              &specifier,
-              Some(&node_resolver::UrlOrPathRef::from_url(&cwd_url)),
-              Some(pkg_json),
-              node_resolver::ResolutionMode::Import,
-              node_resolver::NodeResolutionKind::Execution,
-            )?
-            .into_url()?,
-        ),
-      },
-      Err(_) => None,
-    };
 
-    let specifier = match maybe_import_specifier {
-      Some(specifier) => specifier,
-      None => resolve_url_or_path(&specifier, cli_options.initial_cwd())?,
-    };
+    let specifier = resolve_info_specifier(&factory, &specifier).await?;
 
     let mut loader =
       module_graph_builder.create_graph_loader_with_root_permissions();
@@ -207,6 +139,494 @@ pub async fn info(
   Ok(())
 }
 
+async fn resolve_info_specifier(
+  factory: &CliFactory,
+  specifier: &str,
+) -> Result<ModuleSpecifier, AnyError> {
+  let cli_options = factory.cli_options()?;
+  let resolver = factory.workspace_resolver().await?;
+  let node_resolver = factory.node_resolver().await?;
+  let cwd_url =
+    url::Url::from_directory_path(cli_options.initial_cwd()).unwrap();
+
+  let maybe_import_specifier = match resolver.resolve(
+    specifier,
+    &cwd_url,
+    deno_resolver::workspace::ResolutionKind::Execution,
+  ) {
+    Ok(resolved) => match resolved {
+      deno_resolver::workspace::MappedResolution::Normal {
+        specifier, ..
+      }
+      | deno_resolver::workspace::MappedResolution::WorkspaceJsrPackage {
+        specifier,
+        ..
+      } => Some(specifier),
+      deno_resolver::workspace::MappedResolution::WorkspaceNpmPackage {
+        target_pkg_json,
+        sub_path,
+        ..
+      } => Some(
+        node_resolver
+          .resolve_package_subpath_from_deno_module(
+            target_pkg_json.clone().dir_path(),
+            sub_path.as_deref(),
+            Some(&cwd_url),
+            node_resolver::ResolutionMode::Import,
+            node_resolver::NodeResolutionKind::Execution,
+          )?
+          .into_url()?,
+      ),
+      deno_resolver::workspace::MappedResolution::PackageJson {
+        alias,
+        sub_path,
+        dep_result,
+        ..
+      } => match dep_result.as_ref().map_err(|e| e.clone())? {
+        deno_package_json::PackageJsonDepValue::File(_) => {
+          return Err(
+            DenoResolveErrorKind::UnsupportedPackageJsonFileSpecifier
+              .into_box()
+              .into(),
+          );
+        }
+        deno_package_json::PackageJsonDepValue::Workspace(version_req) => {
+          let pkg_folder = resolver
+            .resolve_workspace_pkg_json_folder_for_pkg_json_dep(
+              alias,
+              version_req,
+            )?;
+          Some(
+            node_resolver
+              .resolve_package_subpath_from_deno_module(
+                pkg_folder,
+                sub_path.as_deref(),
+                Some(&cwd_url),
+                node_resolver::ResolutionMode::Import,
+                node_resolver::NodeResolutionKind::Execution,
+              )?
+              .into_url()?,
+          )
+        }
+        deno_package_json::PackageJsonDepValue::Req(req) => {
+          Some(ModuleSpecifier::parse(&format!(
+            "npm:{}{}",
+            req,
+            sub_path.map(|s| format!("/{}", s)).unwrap_or_default()
+          ))?)
+        }
+        deno_package_json::PackageJsonDepValue::Catalog(catalog) => {
+          return Err(
+            deno_package_json::PackageJsonDepValueParseErrorKind::CatalogEntryNotFound {
+              catalog: catalog.to_string(),
+              alias: alias.to_string(),
+            }
+            .into_box()
+            .into(),
+          );
+        }
+      },
+      deno_resolver::workspace::MappedResolution::PackageJsonImport {
+        pkg_json,
+      } => Some(
+        node_resolver
+          .resolve_package_import(
+            specifier,
+            Some(&node_resolver::UrlOrPathRef::from_url(&cwd_url)),
+            Some(pkg_json),
+            node_resolver::ResolutionMode::Import,
+            node_resolver::NodeResolutionKind::Execution,
+          )?
+          .into_url()?,
+      ),
+    },
+    Err(_) => None,
+  };
+
+  Ok(match maybe_import_specifier {
+    Some(specifier) => specifier,
+    None => resolve_url_or_path(specifier, cli_options.initial_cwd())?,
+  })
+}
+
+/// A step in a path that explains why an npm package is in the
+/// dependency tree.
+enum WhyPathStep {
+  Config(ModuleSpecifier),
+  Module(ModuleSpecifier),
+  Npm {
+    name: String,
+    version_req: Option<String>,
+    package_id: NpmPackageId,
+  },
+}
+
+impl WhyPathStep {
+  fn to_json(&self) -> serde_json::Value {
+    match self {
+      WhyPathStep::Config(specifier) => {
+        serde_json::json!({ "config": specifier })
+      }
+      WhyPathStep::Module(specifier) => {
+        serde_json::json!({ "specifier": specifier })
+      }
+      WhyPathStep::Npm {
+        name,
+        version_req,
+        package_id,
+      } => serde_json::json!({
+        "name": name,
+        "versionReq": version_req,
+        "npmPackage": package_id.as_serialized(),
+      }),
+    }
+  }
+
+  fn display_text(&self) -> String {
+    match self {
+      WhyPathStep::Config(specifier) | WhyPathStep::Module(specifier) => {
+        specifier.to_string()
+      }
+      WhyPathStep::Npm {
+        name,
+        version_req,
+        package_id,
+      } => format!(
+        "{} {} npm:/{}",
+        match version_req {
+          Some(version_req) => format!("{}@{}", name, version_req),
+          None => name.clone(),
+        },
+        colors::gray("->"),
+        package_id.as_serialized()
+      ),
+    }
+  }
+}
+
+/// Resolves the provided file or, when not provided, the modules of the
+/// workspace.
+async fn resolve_info_roots(
+  factory: &CliFactory,
+  maybe_file: Option<&str>,
+) -> Result<Vec<ModuleSpecifier>, AnyError> {
+  match maybe_file {
+    Some(file) => Ok(vec![resolve_info_specifier(factory, file).await?]),
+    None => Ok(
+      factory
+        .main_module_graph_container()
+        .await?
+        .collect_specifiers(
+          &[".".to_string()],
+          CollectSpecifiersOptions {
+            include_ignored_specified: false,
+          },
+        )?,
+    ),
+  }
+}
+
+/// Outputs the module graph in a graph description format or the import
+/// cycles found in it.
+async fn info_graph(
+  factory: &CliFactory,
+  info_flags: &InfoFlags,
+) -> Result<(), AnyError> {
+  let roots = resolve_info_roots(factory, info_flags.file.as_deref()).await?;
+  let module_graph = factory
+    .module_graph_creator()
+    .await?
+    .create_graph(GraphKind::CodeOnly, roots, NpmCachingStrategy::Lazy)
+    .await?;
+  if let Some(lockfile) = factory.maybe_lockfile().await? {
+    graph_exit_integrity_errors(&module_graph);
+    lockfile.write_if_changed()?;
+  }
+
+  let npm_resolver = factory.npm_resolver().await?;
+  let npm_snapshot =
+    npm_resolver.as_managed().map(|r| r.resolution().snapshot());
+  let info_graph = graph::InfoGraph::build(
+    &module_graph,
+    npm_snapshot.as_ref(),
+    info_flags.collapse,
+  );
+  if info_flags.cycles {
+    let cycles = info_graph.cycles();
+    if info_flags.json {
+      display::write_json_to_stdout(&serde_json::json!({
+        "version": JSON_SCHEMA_VERSION,
+        "cycles": graph::cycles_to_json(&cycles),
+      }))?;
+    } else {
+      let mut output = String::new();
+      graph::write_cycles(&cycles, &mut output)?;
+      display::write_to_stdout_ignore_sigpipe(output.as_bytes())?;
+    }
+  } else if let Some(format) = info_flags.format {
+    let mut output = String::new();
+    info_graph.write(format, &mut output)?;
+    display::write_to_stdout_ignore_sigpipe(output.as_bytes())?;
+  }
+  Ok(())
+}
+
+/// Gets the npm package requirements found in the workspace's deno.json
+/// and package.json files along with the config file they're in.
+fn config_npm_deps(
+  workspace: &Workspace,
+) -> Vec<(ModuleSpecifier, PackageReq)> {
+  let mut deps = Vec::new();
+  for deno_json in workspace.deno_jsons() {
+    let imports = deno_json.json.imports.iter();
+    let scopes = deno_json
+      .json
+      .scopes
+      .as_ref()
+      .and_then(|scopes| scopes.as_object())
+      .into_iter()
+      .flat_map(|scopes| scopes.values());
+    for (key, value) in imports
+      .chain(scopes)
+      .filter_map(|map| map.as_object())
+      .flatten()
+    {
+      let Some(value) = value.as_str() else {
+        continue;
+      };
+      let value = match workspace.resolve_catalog_import_value(key, value) {
+        Some(Ok(value)) => Cow::Owned(value),
+        Some(Err(_)) => continue,
+        None => Cow::Borrowed(value),
+      };
+      if let Ok(req_ref) = NpmPackageReqReference::from_str(&value) {
+        deps.push((deno_json.specifier.clone(), req_ref.req().clone()));
+      }
+    }
+  }
+  for pkg_json in workspace.package_jsons() {
+    let pkg_json_deps = workspace.resolve_pkg_json_deps(pkg_json);
+    let values = pkg_json_deps
+      .dependencies
+      .values()
+      .chain(pkg_json_deps.dev_dependencies.values());
+    for value in values {
+      if let Ok(PackageJsonDepValue::Req(req)) = value {
+        deps.push((pkg_json.specifier(), req.clone()));
+      }
+    }
+  }
+  deps
+}
+
+/// Outputs every path from the workspace's modules and config file
+/// dependencies (or the provided file) to the npm packages matching `why`.
+async fn info_why(
+  factory: &CliFactory,
+  maybe_file: Option<&str>,
+  why: &str,
+  json: bool,
+) -> Result<(), AnyError> {
+  let cli_options = factory.cli_options()?;
+  let target =
+    PackageReq::from_str_loose(why.strip_prefix("npm:").unwrap_or(why))
+      .with_context(|| format!("Failed parsing npm package '{}'", why))?;
+  let any_version = target.version_req.version_text() == "*";
+  let is_target = |id: &NpmPackageId| {
+    id.nv.name == target.name
+      && (any_version || target.version_req.matches(&id.nv.version))
+  };
+
+  let npm_resolver = factory.npm_resolver().await?;
+  let Some(managed_npm_resolver) = npm_resolver.as_managed() else {
+    bail!(
+      "--why is not supported when using a manually managed node_modules directory"
+    );
+  };
+
+  let roots = resolve_info_roots(factory, maybe_file).await?;
+  let config_deps = match maybe_file {
+    Some(_) => Vec::new(),
+    None => config_npm_deps(cli_options.workspace()),
+  };
+  let graph = factory
+    .module_graph_creator()
+    .await?
+    .create_graph(GraphKind::All, roots, NpmCachingStrategy::Lazy)
+    .await?;
+  if let Some(npm_installer) = factory.npm_installer_if_managed().await? {
+    // only resolve these in memory as `--why` should never
+    // modify the lockfile
+    let reqs = config_deps
+      .iter()
+      .map(|(_, req)| req.clone())
+      .collect::<Vec<_>>();
+    npm_installer.add_package_reqs_no_cache(&reqs).await?;
+  }
+  if factory.maybe_lockfile().await?.is_some() {
+    graph_exit_integrity_errors(&graph);
+  }
+
+  // resolve the paths through the npm resolution for each top level req
+  let snapshot = managed_npm_resolver.resolution().snapshot();
+  let mut npm_paths = HashMap::new();
+  let graph_reqs = graph
+    .modules()
+    .filter_map(|m| m.npm())
+    .map(|m| m.pkg_req_ref.req());
+  let config_reqs = config_deps.iter().map(|(_, req)| req);
+  for req in graph_reqs.chain(config_reqs) {
+    if !npm_paths.contains_key(req) {
+      let paths = snapshot.dependency_paths(req, is_target, MAX_WHY_PATHS);
+      npm_paths.insert(req.clone(), paths);
+    }
+  }
+
+  // get the version requirements that selected each package
+  let registry_api =
+    factory.npm_installer_factory()?.registry_info_provider()?;
+  let mut package_infos = HashMap::new();
+  let parents = npm_paths
+    .values()
+    .flatten()
+    .flat_map(|path| &path[..path.len().saturating_sub(1)]);
+  for step in parents {
+    let name = &step.id.nv.name;
+    if !package_infos.contains_key(name)
+      && let Ok(info) = registry_api.package_info(name).await
+    {
+      package_infos.insert(name.clone(), info);
+    }
+  }
+  let npm_steps = |req: &PackageReq, path: &[NpmDependencyPathStep]| {
+    let mut steps = Vec::with_capacity(path.len());
+    let mut parent: Option<&NpmPackageId> = None;
+    for step in path {
+      let version_req = match parent {
+        Some(parent) => package_infos
+          .get(&parent.nv.name)
+          .and_then(|info| info.versions.get(&parent.nv.version))
+          .and_then(|version_info| {
+            version_info
+              .dependencies
+              .get(&step.name)
+              .or_else(|| version_info.optional_dependencies.get(&step.name))
+              .or_else(|| version_info.peer_dependencies.get(&step.name))
+          })
+          .map(|version_req| version_req.to_string()),
+        None => Some(req.version_req.version_text().to_string()),
+      };
+      steps.push(WhyPathStep::Npm {
+        name: step.name.to_string(),
+        version_req,
+        package_id: step.id.clone(),
+      });
+      parent = Some(&step.id);
+    }
+    steps
+  };
+
+  let mut paths = Vec::new();
+  let import_paths = graph_import_paths(
+    &graph,
+    |module| {
+      module
+        .npm()
+        .and_then(|m| npm_paths.get(m.pkg_req_ref.req()))
+        .is_some_and(|paths| !paths.is_empty())
+    },
+    MAX_WHY_PATHS,
+  );
+  for import_path in import_paths {
+    let Some((npm_module, modules)) = import_path.split_last() else {
+      continue;
+    };
+    let req = npm_module.npm().unwrap().pkg_req_ref.req();
+    for npm_path in &npm_paths[req] {
+      let mut path = modules
+        .iter()
+        .map(|m| WhyPathStep::Module(m.specifier().clone()))
+        .collect::<Vec<_>>();
+      path.extend(npm_steps(req, npm_path));
+      paths.push(path);
+    }
+  }
+  for (config_url, req) in &config_deps {
+    for npm_path in &npm_paths[req] {
+      let mut path = vec![WhyPathStep::Config(config_url.clone())];
+      path.extend(npm_steps(req, npm_path));
+      paths.push(path);
+    }
+  }
+
+  let mut package_ids = paths
+    .iter()
+    .filter_map(|path| match path.last() {
+      Some(WhyPathStep::Npm { package_id, .. }) => Some(package_id),
+      _ => None,
+    })
+    .collect::<Vec<_>>();
+  package_ids.sort();
+  package_ids.dedup();
+
+  if json {
+    display::write_json_to_stdout(&serde_json::json!({
+      "version": JSON_SCHEMA_VERSION,
+      "package": why,
+      "npmPackages": package_ids
+        .iter()
+        .map(|id| id.as_serialized())
+        .collect::<Vec<_>>(),
+      "paths": paths
+        .iter()
+        .map(|path| path.iter().map(|step| step.to_json()).collect())
+        .collect::<Vec<serde_json::Value>>(),
+    }))
+  } else {
+    let mut output = String::new();
+    if paths.is_empty() {
+      writeln!(
+        output,
+        "npm package '{}' was not found in the dependency tree",
+        why
+      )?;
+    } else {
+      writeln!(
+        output,
+        "{} {}",
+        colors::bold("why:"),
+        package_ids
+          .iter()
+          .map(|id| format!("npm:/{}", id.as_serialized()))
+          .collect::<Vec<_>>()
+          .join(", ")
+      )?;
+      // merge the paths with a common start into trees
+      let mut trees: Vec<DisplayTreeNode> = Vec::new();
+      for path in &paths {
+        let mut nodes = &mut trees;
+        for step in path {
+          let text = step.display_text();
+          let index = match nodes.iter().position(|n| n.text == text) {
+            Some(index) => index,
+            None => {
+              nodes.push(DisplayTreeNode::from_text(text));
+              nodes.len() - 1
+            }
+          };
+          nodes = &mut nodes[index].children;
+        }
+      }
+      for tree in trees {
+        writeln!(output)?;
+        tree.print(&mut output)?;
+      }
+    }
+    display::write_to_stdout_ignore_sigpipe(output.as_bytes())?;
+    Ok(())
+  }
+}
+
 #[allow(clippy::print_stdout)]
 fn print_cache_info(
   factory: &CliFactory,
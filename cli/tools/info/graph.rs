@@ -0,0 +1,396 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+//! Exports the module graph for `deno info --format` and finds the
+//! import cycles for `deno info --cycles`.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use deno_ast::ModuleSpecifier;
+use deno_core::serde_json;
+use deno_graph::Module;
+use deno_graph::ModuleGraph;
+use deno_graph::Range;
+use deno_graph::source::JsrUrlProvider;
+use deno_npm::resolution::NpmResolutionSnapshot;
+use deno_terminal::colors;
+
+use crate::args::InfoGraphCollapse;
+use crate::args::InfoGraphFormat;
+use crate::graph_util::CliJsrUrlProvider;
+
+/// A runtime import from one module in the graph to another.
+struct ModuleImport<'a> {
+  referrer: &'a ModuleSpecifier,
+  specifier: &'a str,
+  range: &'a Range,
+  resolved: &'a ModuleSpecifier,
+  is_dynamic: bool,
+}
+
+/// The module graph where modules may be collapsed into a single node
+/// per package or per directory.
+pub struct InfoGraph<'a> {
+  nodes: Vec<String>,
+  /// Edges between the nodes. The flag indicates whether every import
+  /// the edge is made of is dynamic.
+  edges: Vec<Vec<(usize, bool)>>,
+  imports: Vec<ModuleImport<'a>>,
+  module_nodes: HashMap<&'a ModuleSpecifier, usize>,
+}
+
+impl<'a> InfoGraph<'a> {
+  pub fn build(
+    graph: &'a ModuleGraph,
+    npm_snapshot: Option<&NpmResolutionSnapshot>,
+    collapse: Option<InfoGraphCollapse>,
+  ) -> Self {
+    let mut info_graph = Self {
+      nodes: Vec::new(),
+      edges: Vec::new(),
+      imports: Vec::new(),
+      module_nodes: HashMap::new(),
+    };
+    let mut node_indexes = HashMap::new();
+    for module in graph.modules() {
+      let name = node_name(module, npm_snapshot, collapse);
+      let index = *node_indexes.entry(name.clone()).or_insert_with(|| {
+        info_graph.nodes.push(name);
+        info_graph.edges.push(Vec::new());
+        info_graph.nodes.len() - 1
+      });
+      info_graph.module_nodes.insert(module.specifier(), index);
+    }
+
+    for module in graph.modules() {
+      let dependencies = match module {
+        Module::Js(module) => &module.dependencies,
+        Module::Wasm(module) => &module.dependencies,
+        Module::Json(_)
+        | Module::Npm(_)
+        | Module::Node(_)
+        | Module::External(_) => continue,
+      };
+      for (specifier, dep) in dependencies {
+        // type only imports don't affect the runtime graph
+        let Some(import) = dep.imports.iter().find(|i| i.kind.is_runtime())
+        else {
+          continue;
+        };
+        let Some(resolved) = dep
+          .get_code()
+          .and_then(|s| graph.get(s))
+          .map(|m| m.specifier())
+        else {
+          continue;
+        };
+        info_graph.imports.push(ModuleImport {
+          referrer: module.specifier(),
+          specifier,
+          range: &import.specifier_range,
+          resolved,
+          is_dynamic: dep.is_dynamic,
+        });
+      }
+    }
+
+    for import in &info_graph.imports {
+      let from = info_graph.module_nodes[import.referrer];
+      let to = info_graph.module_nodes[import.resolved];
+      if from == to && collapse.is_some() {
+        continue; // an import within the same package or directory
+      }
+      let edges = &mut info_graph.edges[from];
+      match edges.iter_mut().find(|(index, _)| *index == to) {
+        Some((_, is_dynamic)) => *is_dynamic = *is_dynamic && import.is_dynamic,
+        None => edges.push((to, import.is_dynamic)),
+      }
+    }
+
+    info_graph
+  }
+
+  pub fn write<TWrite: Write>(
+    &self,
+    format: InfoGraphFormat,
+    writer: &mut TWrite,
+  ) -> std::fmt::Result {
+    match format {
+      InfoGraphFormat::Dot => self.write_dot(writer),
+      InfoGraphFormat::Mermaid => self.write_mermaid(writer),
+    }
+  }
+
+  fn write_dot<TWrite: Write>(&self, writer: &mut TWrite) -> std::fmt::Result {
+    fn quote(text: &str) -> String {
+      format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+
+    writeln!(writer, "digraph {{")?;
+    writeln!(writer, "  node [shape=box];")?;
+    for node in &self.nodes {
+      writeln!(writer, "  {};", quote(node))?;
+    }
+    for (from, edges) in self.edges.iter().enumerate() {
+      for (to, is_dynamic) in edges {
+        writeln!(
+          writer,
+          "  {} -> {}{};",
+          quote(&self.nodes[from]),
+          quote(&self.nodes[*to]),
+          if *is_dynamic { " [style=dashed]" } else { "" }
+        )?;
+      }
+    }
+    writeln!(writer, "}}")
+  }
+
+  fn write_mermaid<TWrite: Write>(
+    &self,
+    writer: &mut TWrite,
+  ) -> std::fmt::Result {
+    writeln!(writer, "flowchart LR")?;
+    for (index, node) in self.nodes.iter().enumerate() {
+      writeln!(writer, "  n{}[\"{}\"]", index, node.replace('"', "#quot;"))?;
+    }
+    for (from, edges) in self.edges.iter().enumerate() {
+      for (to, is_dynamic) in edges {
+        let arrow = if *is_dynamic { "-.->" } else { "-->" };
+        writeln!(writer, "  n{} {} n{}", from, arrow, to)?;
+      }
+    }
+    Ok(())
+  }
+
+  /// Gets the import cycles in the graph, ignoring dynamic imports
+  /// because they don't get evaluated when the module graph is loaded.
+  pub fn cycles(&self) -> Vec<ImportCycle<'_>> {
+    let static_edges = self
+      .edges
+      .iter()
+      .map(|edges| {
+        edges
+          .iter()
+          .filter(|(_, is_dynamic)| !is_dynamic)
+          .map(|(to, _)| *to)
+          .collect::<Vec<_>>()
+      })
+      .collect::<Vec<_>>();
+    cyclic_components(&static_edges)
+      .into_iter()
+      .map(|component| {
+        let imports = self
+          .imports
+          .iter()
+          .filter(|import| {
+            let from = self.module_nodes[import.referrer];
+            let to = self.module_nodes[import.resolved];
+            !import.is_dynamic
+              && component.contains(&from)
+              && component.contains(&to)
+              && static_edges[from].contains(&to)
+          })
+          .collect();
+        ImportCycle {
+          nodes: component.iter().map(|i| self.nodes[*i].as_str()).collect(),
+          imports,
+        }
+      })
+      .collect()
+  }
+}
+
+/// A strongly connected component of the graph.
+pub struct ImportCycle<'a> {
+  nodes: Vec<&'a str>,
+  imports: Vec<&'a ModuleImport<'a>>,
+}
+
+pub fn write_cycles<TWrite: Write>(
+  cycles: &[ImportCycle],
+  writer: &mut TWrite,
+) -> std::fmt::Result {
+  if cycles.is_empty() {
+    return writeln!(writer, "No import cycles found.");
+  }
+  writeln!(
+    writer,
+    "{}",
+    colors::bold(format!(
+      "Found {} import cycle{}.",
+      cycles.len(),
+      if cycles.len() == 1 { "" } else { "s" }
+    ))
+  )?;
+  for (index, cycle) in cycles.iter().enumerate() {
+    writeln!(writer)?;
+    writeln!(
+      writer,
+      "{} {}",
+      colors::bold(format!("cycle {}:", index + 1)),
+      cycle.nodes.join(", ")
+    )?;
+    for import in &cycle.imports {
+      writeln!(
+        writer,
+        "  {} {} {}",
+        colors::gray(import.range),
+        colors::cyan(format!("\"{}\"", import.specifier)),
+        colors::gray(format!("-> {}", import.resolved)),
+      )?;
+    }
+  }
+  Ok(())
+}
+
+pub fn cycles_to_json(cycles: &[ImportCycle]) -> serde_json::Value {
+  serde_json::Value::Array(
+    cycles
+      .iter()
+      .map(|cycle| {
+        serde_json::json!({
+          "nodes": cycle.nodes,
+          "imports": cycle
+            .imports
+            .iter()
+            .map(|import| {
+              serde_json::json!({
+                "referrer": import.referrer,
+                "specifier": import.specifier,
+                "range": import.range,
+                "resolved": import.resolved,
+              })
+            })
+            .collect::<Vec<_>>(),
+        })
+      })
+      .collect(),
+  )
+}
+
+fn node_name(
+  module: &Module,
+  npm_snapshot: Option<&NpmResolutionSnapshot>,
+  collapse: Option<InfoGraphCollapse>,
+) -> String {
+  let specifier = module.specifier();
+  if let Some(module) = module.npm() {
+    let req_ref = &module.pkg_req_ref;
+    return match npm_snapshot
+      .and_then(|s| s.resolve_pkg_from_pkg_req(req_ref.req()).ok())
+    {
+      Some(package) => match (collapse, req_ref.sub_path()) {
+        (None, Some(sub_path)) => {
+          format!("npm:/{}/{}", package.id.nv, sub_path)
+        }
+        _ => format!("npm:/{}", package.id.nv),
+      },
+      None => specifier.to_string(),
+    };
+  }
+  let Some(collapse) = collapse else {
+    return specifier.to_string();
+  };
+  if let Some(nv) = CliJsrUrlProvider.package_url_to_nv(specifier) {
+    return format!("jsr:{}", nv);
+  }
+  let path = match (collapse, specifier.scheme()) {
+    // modules that aren't in a package are collapsed per host when remote
+    // and per directory otherwise
+    (InfoGraphCollapse::Package, "http" | "https") => "/",
+    _ => "./",
+  };
+  specifier
+    .join(path)
+    .map(|url| url.to_string())
+    .unwrap_or_else(|_| specifier.to_string())
+}
+
+/// Gets the strongly connected components of a directed graph that
+/// contain a cycle using Tarjan's algorithm.
+fn cyclic_components(edges: &[Vec<usize>]) -> Vec<Vec<usize>> {
+  const UNVISITED: usize = usize::MAX;
+  let mut indexes = vec![UNVISITED; edges.len()];
+  let mut low_links = vec![0; edges.len()];
+  let mut on_stack = vec![false; edges.len()];
+  let mut stack = Vec::new();
+  let mut next_index = 0;
+  let mut components = Vec::new();
+
+  for start in 0..edges.len() {
+    if indexes[start] != UNVISITED {
+      continue;
+    }
+    // iterative to not overflow the stack on deep graphs
+    let mut call_stack = vec![(start, 0)];
+    indexes[start] = next_index;
+    low_links[start] = next_index;
+    next_index += 1;
+    stack.push(start);
+    on_stack[start] = true;
+    while let Some(frame) = call_stack.last_mut() {
+      let node = frame.0;
+      if let Some(&child) = edges[node].get(frame.1) {
+        frame.1 += 1;
+        if indexes[child] == UNVISITED {
+          indexes[child] = next_index;
+          low_links[child] = next_index;
+          next_index += 1;
+          stack.push(child);
+          on_stack[child] = true;
+          call_stack.push((child, 0));
+        } else if on_stack[child] {
+          low_links[node] = low_links[node].min(indexes[child]);
+        }
+        continue;
+      }
+      call_stack.pop();
+      if let Some((parent, _)) = call_stack.last() {
+        low_links[*parent] = low_links[*parent].min(low_links[node]);
+      }
+      if low_links[node] == indexes[node] {
+        let mut component = Vec::new();
+        loop {
+          let member = stack.pop().unwrap();
+          on_stack[member] = false;
+          component.push(member);
+          if member == node {
+            break;
+          }
+        }
+        if component.len() > 1 || edges[node].contains(&node) {
+          component.sort();
+          components.push(component);
+        }
+      }
+    }
+  }
+
+  components.sort();
+  components
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_cyclic_components() {
+    // 0 -> 1 -> 2 -> 0, 2 -> 3, 4 -> 4, 5 -> 6 -> 5, 7
+    let edges = vec![
+      vec![1],
+      vec![2],
+      vec![0, 3],
+      vec![],
+      vec![4],
+      vec![6],
+      vec![5],
+      vec![],
+    ];
+    assert_eq!(
+      cyclic_components(&edges),
+      vec![vec![0, 1, 2], vec![4], vec![5, 6]]
+    );
+    assert!(cyclic_components(&[vec![1], vec![2], vec![]]).is_empty());
+  }
+}
@@ -515,6 +515,14 @@ fn resolve_shim_data(
     executable_args.push("--location".to_string());
     executable_args.push(url.to_string());
   }
+  if let Some(storage_dir) = &flags.storage_dir {
+    executable_args.push("--storage-dir".to_string());
+    executable_args.push(cwd.join(storage_dir).to_string_lossy().into_owned());
+  }
+  if let Some(storage_quota) = flags.storage_quota {
+    executable_args.push("--storage-quota".to_string());
+    executable_args.push(storage_quota.to_string());
+  }
   if let Some(deno_lib::args::CaData::File(ca_file)) = &flags.ca_data {
     executable_args.push("--cert".to_string());
     executable_args.push(ca_file.to_owned())
@@ -64,8 +64,7 @@ pub fn categorize_installed_npm_deps(
   let mut normal_deps = HashSet::new();
   let mut dev_deps = HashSet::new();
 
-  for package_json in workspace.package_jsons() {
-    let deps = package_json.resolve_local_package_json_deps();
+  for deps in workspace.package_json_deps() {
     for (_k, v) in deps.dependencies.iter() {
       let Ok(s) = v else {
         continue;
@@ -83,6 +82,9 @@ pub fn categorize_installed_npm_deps(
         ) => {
           // ignore workspace deps
         }
+        deno_package_json::PackageJsonDepValue::Catalog(_) => {
+          // resolved by the workspace
+        }
       }
     }
 
@@ -103,6 +105,9 @@ pub fn categorize_installed_npm_deps(
         ) => {
           // ignore workspace deps
         }
+        deno_package_json::PackageJsonDepValue::Catalog(_) => {
+          // resolved by the workspace
+        }
       }
     }
   }
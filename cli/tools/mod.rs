@@ -8,7 +8,9 @@ pub mod compile;
 pub mod coverage;
 pub mod deploy;
 pub mod doc;
+pub mod ffi;
 pub mod fmt;
+pub mod heap_diff;
 pub mod info;
 pub mod init;
 pub mod installer;
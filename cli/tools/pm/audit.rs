@@ -247,8 +247,7 @@ mod npm {
     // there's no way to specify `devDependencies` in `deno.json`, this is only iterating
     // through discovered `package.json` files.
     let mut all_dev_deps = Vec::with_capacity(32);
-    for pkg_json in workspace.package_jsons() {
-      let deps = pkg_json.resolve_local_package_json_deps();
+    for deps in workspace.package_json_deps() {
       for v in deps.dev_dependencies.values() {
         let Ok(PackageJsonDepValue::Req(package_req)) = v else {
           continue;
@@ -23,6 +23,7 @@ use deno_core::url::Url;
 use deno_graph::JsrPackageReqNotFoundError;
 use deno_graph::packages::JsrPackageVersionInfo;
 use deno_npm::resolution::NpmVersionResolver;
+use deno_package_json::DEFAULT_CATALOG_NAME;
 use deno_package_json::PackageJsonDepsMap;
 use deno_package_json::PackageJsonRc;
 use deno_runtime::deno_permissions::PermissionsContainer;
@@ -380,7 +381,8 @@ fn add_deps_from_package_json(
             alias,
           })
         }
-        deno_package_json::PackageJsonDepValue::Workspace(_) => continue,
+        deno_package_json::PackageJsonDepValue::Workspace(_)
+        | deno_package_json::PackageJsonDepValue::Catalog(_) => continue,
       }
     }
   }
@@ -401,6 +403,113 @@ fn add_deps_from_package_json(
   );
 }
 
+fn add_deps_from_catalogs(
+  workspace: &Workspace,
+  mut filter: impl DepFilter,
+  deps: &mut Vec<Dep>,
+) {
+  fn catalog_key_path(catalog: &str, alias: &str) -> KeyPath {
+    if catalog == DEFAULT_CATALOG_NAME {
+      KeyPath::from_parts([
+        KeyPart::String("catalog".into()),
+        KeyPart::String(alias.into()),
+      ])
+    } else {
+      KeyPath::from_parts([
+        KeyPart::String("catalogs".into()),
+        KeyPart::String(catalog.into()),
+        KeyPart::String(alias.into()),
+      ])
+    }
+  }
+
+  if let Some(deno_json) = workspace.root_deno_json() {
+    let catalogs = match deno_json.to_catalogs_config() {
+      Ok(catalogs) => catalogs,
+      Err(e) => {
+        log::warn!(
+          "failed to parse catalogs from {}: {e}",
+          deno_json.specifier
+        );
+        return;
+      }
+    };
+    let entries = catalogs
+      .default
+      .iter()
+      .map(|entry| (DEFAULT_CATALOG_NAME, entry))
+      .chain(catalogs.named.iter().flat_map(|(name, entries)| {
+        entries.iter().map(move |entry| (name.as_str(), entry))
+      }));
+    for (catalog, (alias, value)) in entries {
+      let Some(Ok(value)) =
+        workspace.resolve_catalog_import_value(alias, value)
+      else {
+        continue;
+      };
+      let kind = match value.split_once(':') {
+        Some(("npm", _)) => DepKind::Npm,
+        Some(("jsr", _)) => DepKind::Jsr,
+        _ => continue,
+      };
+      let req = match parse_req_reference(&value, kind) {
+        Ok(req) => req.req,
+        Err(err) => {
+          log::warn!("failed to parse package req \"{}\": {err}", value);
+          continue;
+        }
+      };
+      let alias = (alias.as_str() != req.name).then(|| alias.to_string());
+      if !filter.should_include(alias.as_deref(), &req, kind) {
+        continue;
+      }
+      let id = DepId(deps.len());
+      deps.push(Dep {
+        location: DepLocation::DenoJson(
+          deno_json.clone(),
+          catalog_key_path(catalog, alias.as_deref().unwrap_or(&req.name)),
+          ImportMapKind::Inline,
+        ),
+        kind,
+        req,
+        id,
+        alias,
+      });
+    }
+  }
+
+  if let Some(package_json) = workspace.root_pkg_json() {
+    let catalogs = package_json.resolve_catalogs();
+    for (catalog, entries) in catalogs.iter() {
+      for alias in entries.keys() {
+        let req = match catalogs.resolve_dep(catalog, alias) {
+          Ok(deno_package_json::PackageJsonDepValue::Req(req)) => req,
+          Ok(_) => continue,
+          Err(e) => {
+            log::warn!("bad package json catalog value: {e}");
+            continue;
+          }
+        };
+        let alias = (alias.as_str() != req.name).then(|| alias.to_string());
+        if !filter.should_include(alias.as_deref(), &req, DepKind::Npm) {
+          continue;
+        }
+        let id = DepId(deps.len());
+        deps.push(Dep {
+          location: DepLocation::PackageJson(
+            package_json.clone(),
+            catalog_key_path(catalog, alias.as_deref().unwrap_or(&req.name)),
+          ),
+          kind: DepKind::Npm,
+          req,
+          id,
+          alias,
+        });
+      }
+    }
+  }
+}
+
 fn deps_from_workspace(
   workspace: &Arc<Workspace>,
   dep_filter: impl DepFilter,
@@ -412,6 +521,7 @@ fn deps_from_workspace(
   for package_json in workspace.package_jsons() {
     add_deps_from_package_json(package_json, dep_filter, &mut deps);
   }
+  add_deps_from_catalogs(workspace, dep_filter, &mut deps);
 
   Ok(deps)
 }
@@ -544,6 +654,10 @@ impl DepManager {
     if let Some(package_json) = workspace_dir.member_pkg_json() {
       add_deps_from_package_json(package_json, dep_filter, &mut deps);
     }
+    // the catalogs are defined in the root config files
+    if workspace_dir.dir_url() == workspace_dir.workspace.root_dir_url() {
+      add_deps_from_catalogs(&workspace_dir.workspace, dep_filter, &mut deps);
+    }
 
     Ok(Self::with_deps_args(deps, args))
   }
@@ -909,13 +1023,18 @@ impl DepManager {
               let Some(string_value) = cst_string_literal(&property) else {
                 continue;
               };
-              let mut req_reference = match dep.kind {
-                DepKind::Npm => NpmPackageReqReference::from_str(&string_value)
-                  .unwrap()
-                  .into_inner(),
-                DepKind::Jsr => JsrPackageReqReference::from_str(&string_value)
-                  .unwrap()
-                  .into_inner(),
+              let Ok(mut req_reference) =
+                parse_req_reference(&string_value, dep.kind)
+              else {
+                // a catalog entry that only specifies the version
+                let new_value = match dep.kind {
+                  DepKind::Npm => version_req.to_string(),
+                  DepKind::Jsr => format!("jsr:{version_req}"),
+                };
+                property.set_value(jsonc_parser::cst::CstInputValue::String(
+                  new_value,
+                ));
+                continue;
               };
               req_reference.req.version_req = version_req;
               let mut new_value =
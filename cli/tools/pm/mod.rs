@@ -1,5 +1,6 @@
 // Copyright 2018-2026 the Deno authors. MIT license.
 
+use std::collections::HashSet;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -11,6 +12,8 @@ use deno_core::anyhow::bail;
 use deno_core::error::AnyError;
 use deno_core::futures::FutureExt;
 use deno_core::futures::StreamExt;
+use deno_package_json::DEFAULT_CATALOG_NAME;
+use deno_package_json::PackageJsonDepValue;
 use deno_path_util::url_to_file_path;
 use deno_semver::StackString;
 use deno_semver::Version;
@@ -142,8 +145,11 @@ impl ConfigUpdater {
     match self.kind {
       ConfigKind::DenoJson => {
         let imports = self.root_object.object_value_or_set("imports");
-        let value =
-          format!("{}@{}", selected.package_name, selected.version_req);
+        let value = if selected.version_req.starts_with("catalog:") {
+          selected.version_req
+        } else {
+          format!("{}@{}", selected.package_name, selected.version_req)
+        };
         match imports.get(&selected.import_name) {
           Some(prop) => {
             prop.set_value(json!(value));
@@ -513,6 +519,24 @@ pub async fn add(
     }
   }
 
+  // packages in the workspace's default catalog use the catalog's
+  // version when no version was provided
+  let catalogs = cli_factory.cli_options()?.workspace().catalogs();
+  let mut catalog_aliases = HashSet::new();
+  for package_req in &mut package_reqs {
+    let AddRmPackageReqValue::Npm(req) = &mut package_req.value else {
+      continue;
+    };
+    if req.version_req.version_text() == "*"
+      && let Ok(PackageJsonDepValue::Req(catalog_req)) =
+        catalogs.resolve_dep(DEFAULT_CATALOG_NAME, &package_req.alias)
+      && catalog_req.name == req.name
+    {
+      *req = catalog_req;
+      catalog_aliases.insert(package_req.alias.clone());
+    }
+  }
+
   let package_futures = package_reqs
     .into_iter()
     .map({
@@ -566,7 +590,10 @@ pub async fn add(
         }
         None => bail!("{} was not found.", crate::colors::red(package_name)),
       },
-      PackageAndVersion::Selected(selected) => {
+      PackageAndVersion::Selected(mut selected) => {
+        if catalog_aliases.contains(&selected.import_name) {
+          selected.version_req = "catalog:".to_string();
+        }
         selected_packages.push(selected);
       }
     }
@@ -551,6 +551,8 @@ impl<TSys: SpecifierUnfurlerSys> SpecifierUnfurler<TSys> {
                 ))
                 .ok()
               }
+              // catalog entries are resolved by the workspace
+              PackageJsonDepValue::Catalog(_) => None,
             },
             Err(err) => match err.as_kind() {
               PackageJsonDepValueParseErrorKind::Unsupported { scheme } => {
@@ -564,6 +566,9 @@ impl<TSys: SpecifierUnfurlerSys> SpecifierUnfurler<TSys> {
               PackageJsonDepValueParseErrorKind::VersionReq { .. }
               | PackageJsonDepValueParseErrorKind::JsrRequiresScope {
                 ..
+              }
+              | PackageJsonDepValueParseErrorKind::CatalogEntryNotFound {
+                ..
               } => {
                 log::warn!(
                   "Ignoring failed to resolve package.json dependency. {:#}",
@@ -677,7 +682,10 @@ impl<TSys: SpecifierUnfurlerSys> SpecifierUnfurler<TSys> {
     for pkg_json in referrer_pkg_jsons
       .chain(self.workspace_dir.workspace.package_jsons().cloned())
     {
-      let deps = pkg_json.resolve_local_package_json_deps();
+      let deps = self
+        .workspace_dir
+        .workspace
+        .resolve_pkg_json_deps(&pkg_json);
       if let Some(Ok(PackageJsonDepValue::Req(pkg_req))) =
         deps.get(types_package_name)
       {
@@ -701,8 +709,10 @@ impl<TSys: SpecifierUnfurlerSys> SpecifierUnfurler<TSys> {
       self.workspace_dir.workspace.root_deno_json(),
     ];
     for deno_json in deno_jsons.iter().flatten() {
-      let deps = deno_json
-        .dependencies()
+      let deps = self
+        .workspace_dir
+        .workspace
+        .resolve_deno_json_deps(deno_json)
         .into_iter()
         .collect::<BTreeSet<_>>();
       for dep in deps {
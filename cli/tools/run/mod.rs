@@ -1,7 +1,6 @@
 // Copyright 2018-2026 the Deno authors. MIT license.
 
 use std::io::Read;
-use std::path::PathBuf;
 use std::sync::Arc;
 
 use deno_cache_dir::file_fetcher::File;
@@ -25,6 +24,7 @@ use crate::args::WatchFlagsWithPaths;
 use crate::factory::CliFactory;
 use crate::util;
 use crate::util::env::WatchEnvTracker;
+use crate::util::env::resolve_env_file_paths;
 use crate::util::file_watcher::WatcherRestartMode;
 
 pub mod hmr;
@@ -182,9 +182,10 @@ async fn run_with_watch(
       let env_file_paths: Option<Vec<std::path::PathBuf>> = flags
         .env_file
         .as_ref()
-        .map(|files| files.iter().map(PathBuf::from).collect());
+        .map(|files| resolve_env_file_paths(files, flags.env_mode.as_deref()));
       WatchEnvTracker::snapshot().load_env_variables_from_env_files(
         env_file_paths.as_ref(),
+        flags.env_expand,
         flags.log_level,
       );
       Ok(async move {
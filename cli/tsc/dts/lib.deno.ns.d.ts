@@ -4864,6 +4864,12 @@ declare namespace Deno {
      * The unit is seconds, with a default of 30.
      * Set to `0` to disable timeouts. */
     idleTimeout?: number;
+    /** Accept permessage-deflate compression (RFC 7692) if the client offers
+     * it. Pass `true` to accept the client's parameters, or an object to
+     * require stricter ones.
+     *
+     * @default {false} */
+    perMessageDeflate?: boolean | WebSocketPerMessageDeflateOptions;
   }
 
   /**
@@ -5847,9 +5853,35 @@ declare namespace Deno {
    * @category FFI
    */
   export interface NativeStructType {
-    readonly struct: readonly NativeType[];
+    readonly struct: readonly NativeFieldType[];
+  }
+
+  /** The native union type for interfacing with foreign functions.
+   *
+   * @category FFI
+   */
+  export interface NativeUnionType {
+    readonly union: readonly NativeFieldType[];
   }
 
+  /** The native fixed-size array type. Arrays can only be used as fields of
+   * structs and unions, array parameters are passed as pointers.
+   *
+   * @category FFI
+   */
+  export interface NativeArrayType {
+    readonly array: {
+      readonly type: NativeFieldType;
+      readonly length: number;
+    };
+  }
+
+  /** All supported types for fields of structs and unions.
+   *
+   * @category FFI
+   */
+  export type NativeFieldType = NativeType | NativeArrayType;
+
   /**
    * @category FFI
    */
@@ -5905,7 +5937,8 @@ declare namespace Deno {
     | NativePointerType
     | NativeBufferType
     | NativeFunctionType
-    | NativeStructType;
+    | NativeStructType
+    | NativeUnionType;
 
   /** @category FFI
    */
@@ -5917,7 +5950,7 @@ declare namespace Deno {
    * @category FFI
    */
   export type ToNativeType<T extends NativeType = NativeType> = T extends
-    NativeStructType ? BufferSource
+    NativeStructType | NativeUnionType ? BufferSource
     : T extends NativeNumberType ? T extends NativeU8Enum<infer U> ? U
       : T extends NativeI8Enum<infer U> ? U
       : T extends NativeU16Enum<infer U> ? U
@@ -5942,7 +5975,7 @@ declare namespace Deno {
    */
   export type ToNativeResultType<
     T extends NativeResultType = NativeResultType,
-  > = T extends NativeStructType ? BufferSource
+  > = T extends NativeStructType | NativeUnionType ? BufferSource
     : T extends NativeNumberType ? T extends NativeU8Enum<infer U> ? U
       : T extends NativeI8Enum<infer U> ? U
       : T extends NativeU16Enum<infer U> ? U
@@ -5981,7 +6014,7 @@ declare namespace Deno {
    * @category FFI
    */
   export type FromNativeType<T extends NativeType = NativeType> = T extends
-    NativeStructType ? Uint8Array<ArrayBuffer>
+    NativeStructType | NativeUnionType ? Uint8Array<ArrayBuffer>
     : T extends NativeNumberType ? T extends NativeU8Enum<infer U> ? U
       : T extends NativeI8Enum<infer U> ? U
       : T extends NativeU16Enum<infer U> ? U
@@ -6006,7 +6039,7 @@ declare namespace Deno {
    */
   export type FromNativeResultType<
     T extends NativeResultType = NativeResultType,
-  > = T extends NativeStructType ? Uint8Array<ArrayBuffer>
+  > = T extends NativeStructType | NativeUnionType ? Uint8Array<ArrayBuffer>
     : T extends NativeNumberType ? T extends NativeU8Enum<infer U> ? U
       : T extends NativeI8Enum<infer U> ? U
       : T extends NativeU16Enum<infer U> ? U
@@ -6064,8 +6097,30 @@ declare namespace Deno {
      *
      * @default {false} */
     optional?: boolean;
+    /** When `true`, the function takes variadic arguments after its
+     * `parameters`. Each call passes an array with the types of its variadic
+     * arguments followed by their values:
+     *
+     * ```ts
+     * lib.symbols.printf(format, ["i32", "f64"], 42, 1.5);
+     * ```
+     *
+     * Variadic arguments undergo the C default argument promotions, so
+     * `"f32"` and integer types smaller than `"i32"` cannot be used.
+     *
+     * @default {false} */
+    variadic?: boolean;
   }
 
+  /** The variadic arguments of a call to a variadic foreign function: an
+   * array with their types followed by their values.
+   *
+   * @category FFI
+   */
+  export type ToNativeVariadicArguments =
+    | []
+    | [types: readonly NativeType[], ...values: unknown[]];
+
   /** @category FFI
    */
   export interface ForeignStatic<Type extends NativeType = NativeType> {
@@ -6100,7 +6155,14 @@ declare namespace Deno {
   /**  @category FFI
    */
   export type FromForeignFunction<T extends ForeignFunction> =
-    T["parameters"] extends readonly [] ? () => StaticForeignSymbolReturnType<T>
+    T["variadic"] extends true ? (
+        ...args: [
+          ...ToNativeParameterTypes<T["parameters"]>,
+          ...ToNativeVariadicArguments,
+        ]
+      ) => StaticForeignSymbolReturnType<T>
+      : T["parameters"] extends readonly []
+        ? () => StaticForeignSymbolReturnType<T>
       : (
         ...args: ToNativeParameterTypes<T["parameters"]>
       ) => StaticForeignSymbolReturnType<T>;
@@ -6526,6 +6588,20 @@ declare namespace Deno {
     allowHost?: boolean;
     /** Sets the local address where the socket will connect from. */
     localAddress?: string;
+    /** Enables a private HTTP cache (RFC 9111) for requests made with this
+     * client.
+     *
+     * Responses are stored according to their `Cache-Control`, `Expires` and
+     * `Vary` header fields, and reused while they are fresh. Stale responses
+     * are revalidated with `If-None-Match` or `If-Modified-Since`, and
+     * `stale-while-revalidate` is honoured.
+     *
+     * - `"memory"`: responses are kept in memory until the client is closed.
+     * - `{ name }`: responses are stored in the named cache of
+     *   {@linkcode CacheStorage}, and are shared with other clients using the
+     *   same cache.
+     */
+    cache?: "memory" | { name: string };
   }
 
   /**
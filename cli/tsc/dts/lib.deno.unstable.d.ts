@@ -303,6 +303,52 @@ declare namespace Deno {
     reusePort?: boolean;
   }
 
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Options of {@linkcode Deno.serve} that require the `--unstable-net` flag.
+   *
+   * @category HTTP Server
+   * @experimental
+   */
+  export interface ServeTcpOptions {
+    /** Also serve HTTP/3 over QUIC on the same port number using UDP and
+     * advertise it to clients with the `Alt-Svc` header. Requires `cert` and
+     * `key` to be provided.
+     *
+     * @default {false} */
+    http3?: boolean;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Options of {@linkcode Deno.createHttpClient} that require the
+   * `--unstable-net` flag.
+   *
+   * @category Fetch
+   * @experimental
+   */
+  export interface CreateHttpClientOptions {
+    /** When requests to `https:` URLs are sent over HTTP/3 (QUIC).
+     *
+     * - `"disabled"`: only HTTP/1.1 and HTTP/2 are used.
+     * - `"alt-svc"`: HTTP/3 is used for origins that advertised it in an
+     *   `Alt-Svc` response header. Advertisements are cached for their
+     *   `ma` (max age).
+     * - `"force"`: HTTP/3 is used for every `https:` request.
+     *
+     * Requests that go through a proxy are never sent over HTTP/3.
+     *
+     * @default {"disabled"}
+     */
+    http3?: "disabled" | "alt-svc" | "force";
+    /** Whether requests are sent over HTTP/1.1 or HTTP/2 when an HTTP/3
+     * connection can't be established. If `false`, the request fails instead.
+     *
+     * @default {true}
+     */
+    http3Fallback?: boolean;
+  }
+
   /** **UNSTABLE**: New API, yet to be vetted.
    *
    * Unstable options which can be set when opening a datagram listener via
@@ -372,6 +418,160 @@ declare namespace Deno {
     options: UnixListenOptions & { transport: "unixpacket" },
   ): DatagramConn;
 
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Options of {@linkcode Deno.Command} that require the `--unstable-process`
+   * flag.
+   *
+   * @category Subprocess
+   * @experimental
+   */
+  export interface CommandOptions {
+    /** Runs the spawned process in a newly allocated pseudo-terminal, so that
+     * it sees a TTY on its `stdin`, `stdout` and `stderr`. The master side of
+     * the terminal is available as {@linkcode Deno.ChildProcess.pty}.
+     *
+     * Pass `true` for an 80x24 terminal, or an object to set its size. The
+     * child is made the leader of a new session with the pseudo-terminal as
+     * its controlling terminal. Cannot be combined with `stdin`, `stdout` or
+     * `stderr`, and is only supported in {@linkcode Deno.Command.spawn}.
+     *
+     * Not supported on Windows.
+     *
+     * @default {false} */
+    pty?: boolean | { rows?: number; cols?: number };
+
+    /** Resource limits set on the spawned process with `setrlimit`. Both the
+     * soft and the hard limit are set, so the process can't raise them again.
+     *
+     * Not supported on Windows. */
+    rlimits?: CommandResourceLimits;
+
+    /** The maximum time in milliseconds that the process is allowed to run
+     * for. Once it elapses, the process is sent `killSignal`, and then
+     * `SIGKILL` if it is still running after `killGracePeriod`. */
+    timeout?: number;
+
+    /** The signal sent to the process once `timeout` elapses.
+     *
+     * On Windows, only `"SIGTERM"` and `"SIGKILL"` are supported.
+     *
+     * @default {"SIGTERM"} */
+    killSignal?: Signal | number;
+
+    /** How long in milliseconds to wait after sending `killSignal` on timeout
+     * before sending `SIGKILL`.
+     *
+     * @default {5000} */
+    killGracePeriod?: number;
+
+    /** Spawns the process as the leader of a new process group. Signals sent
+     * with {@linkcode Deno.ChildProcess.kill} and on `timeout` then go to
+     * the whole group, including any processes the child spawned itself.
+     *
+     * Processes spawned with `detached` or `pty` are always in their own
+     * process group, but are only signalled as a group if this is set.
+     *
+     * Not supported on Windows.
+     *
+     * @default {false} */
+    processGroup?: boolean;
+
+    /** Confines the spawned process and all of its descendants to the file
+     * system and network access that this program has been granted, using
+     * Landlock and a seccomp filter.
+     *
+     * Pass `true` to derive the confinement from `--allow-read`,
+     * `--allow-write` and `--allow-net`, or an object to narrow it down. The
+     * process can always read and execute the spawned command and the
+     * system directories (`/usr`, `/lib`, `/etc` and similar) needed to start
+     * it. The process also can't gain privileges, e.g. through setuid
+     * binaries.
+     *
+     * If read or write access has been partially denied with `--deny-read`
+     * or `--deny-write` and no explicit paths are given, spawning fails
+     * rather than allowing more than intended, as the confinement can only
+     * describe what is allowed.
+     *
+     * Only supported on Linux 5.13 and newer with Landlock enabled.
+     *
+     * @default {false} */
+    sandbox?: boolean | CommandSandboxOptions;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Explicit confinement for a child process, set with the `sandbox` option
+   * of {@linkcode Deno.CommandOptions}. Each option that is set must be
+   * allowed by this program's own permissions.
+   *
+   * @category Subprocess
+   * @experimental
+   */
+  export interface CommandSandboxOptions {
+    /** Paths the process may read and execute. Defaults to the paths
+     * granted by `--allow-read`. */
+    read?: (string | URL)[];
+    /** Paths the process may write to. Defaults to the paths granted by
+     * `--allow-write`. */
+    write?: (string | URL)[];
+    /** Whether the process may open network sockets. Defaults to `true`
+     * only if `--allow-net` is granted without restrictions. */
+    net?: boolean;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Resource limits for a child process, set with the `rlimits` option of
+   * {@linkcode Deno.CommandOptions}.
+   *
+   * @category Subprocess
+   * @experimental
+   */
+  export interface CommandResourceLimits {
+    /** CPU time in seconds (`RLIMIT_CPU`). */
+    cpu?: number;
+    /** Size of the virtual address space in bytes (`RLIMIT_AS`). */
+    addressSpace?: number;
+    /** Number of open file descriptors (`RLIMIT_NOFILE`). */
+    openFiles?: number;
+    /** Number of processes that the child's user may have (`RLIMIT_NPROC`).
+     * This counts every process of the user, not just the child's
+     * descendants. */
+    processes?: number;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * @category Subprocess
+   * @experimental
+   */
+  export interface ChildProcess {
+    /** The pseudo-terminal the child was spawned in, if the `pty` option was
+     * set. */
+    readonly pty: ChildPty;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * The master side of a pseudo-terminal allocated for a child process with
+   * the `pty` option of {@linkcode Deno.CommandOptions}.
+   *
+   * @category Subprocess
+   * @experimental
+   */
+  export interface ChildPty {
+    /** Everything the child process writes to its terminal. Ends once the
+     * child and any processes that inherited the terminal have exited. */
+    readonly readable: ReadableStream<Uint8Array<ArrayBuffer>>;
+    /** Input to the child process, as if typed into its terminal. Closing it
+     * does not signal end of input; write `"\x04"` (Ctrl-D) instead. */
+    readonly writable: WritableStream<Uint8Array<ArrayBufferLike>>;
+    /** Changes the size of the terminal, which sends `SIGWINCH` to the
+     * child process. */
+    resize(rows: number, cols: number): void;
+  }
+
   /** **UNSTABLE**: New API, yet to be vetted.
    *
    * Open a new {@linkcode Deno.Kv} connection to persist data.
@@ -4617,6 +4817,7 @@ interface WebSocketStreamOptions {
   protocols?: string[];
   signal?: AbortSignal;
   headers?: HeadersInit;
+  perMessageDeflate?: boolean | WebSocketPerMessageDeflateOptions;
 }
 
 /** **UNSTABLE**: New API, yet to be vetted.
@@ -4687,6 +4888,79 @@ declare var WebSocketError: {
   new (message?: string, init?: WebSocketCloseInfo): WebSocketError;
 };
 
+/**
+ * **UNSTABLE**: New API, yet to be vetted.
+ *
+ * Values that follow the flow of asynchronous execution, such as an id for
+ * the request being handled. Available with `--unstable-async-context`.
+ *
+ * [Specification](https://github.com/tc39/proposal-async-context)
+ *
+ * @category Async Context
+ * @experimental
+ */
+declare namespace AsyncContext {
+  /**
+   * @category Async Context
+   * @experimental
+   */
+  export interface VariableOptions<T> {
+    /** A name for the variable, for debugging. */
+    name?: string;
+    /** The value returned by `get()` outside of any `run()` call. */
+    defaultValue?: T;
+  }
+
+  /**
+   * A value that is set for the duration of a callback and every
+   * asynchronous continuation it schedules, including promise reactions,
+   * timers and `Deno.serve`, `Deno.cron` and `Deno.Kv.listenQueue` handlers.
+   *
+   * ```ts
+   * const requestId = new AsyncContext.Variable<string>();
+   *
+   * Deno.serve((req) =>
+   *   requestId.run(crypto.randomUUID(), async () => {
+   *     await handle(req);
+   *     console.log("handled", requestId.get());
+   *     return new Response("ok");
+   *   })
+   * );
+   * ```
+   *
+   * @category Async Context
+   * @experimental
+   */
+  export class Variable<T> {
+    constructor(options?: VariableOptions<T>);
+    readonly name: string;
+    /** Returns the value in the current context. */
+    get(): T | undefined;
+    /** Calls `fn` with the variable set to `value`. */
+    run<R, A extends unknown[]>(
+      value: T,
+      fn: (...args: A) => R,
+      ...args: A
+    ): R;
+  }
+
+  /**
+   * A capture of the values of all variables at the time it is constructed.
+   *
+   * @category Async Context
+   * @experimental
+   */
+  export class Snapshot {
+    constructor();
+    /** Calls `fn` with the variables restored to the captured values. */
+    run<R, A extends unknown[]>(fn: (...args: A) => R, ...args: A): R;
+    /** Returns a function that always runs `fn` in the current context. */
+    static wrap<T, A extends unknown[], R>(
+      fn: (this: T, ...args: A) => R,
+    ): (this: T, ...args: A) => R;
+  }
+}
+
 // Adapted from `tc39/proposal-temporal`: https://github.com/tc39/proposal-temporal/blob/main/polyfill/index.d.ts
 
 /**
@@ -20,6 +20,7 @@ interface WindowEventMap {
   "error": ErrorEvent;
   "unhandledrejection": PromiseRejectionEvent;
   "rejectionhandled": PromiseRejectionEvent;
+  "storage": StorageEvent;
 }
 
 /**
@@ -49,6 +50,7 @@ interface Window extends EventTarget {
   onload: ((this: Window, ev: Event) => any) | null;
   onbeforeunload: ((this: Window, ev: Event) => any) | null;
   onunload: ((this: Window, ev: Event) => any) | null;
+  onstorage: ((this: Window, ev: StorageEvent) => any) | null;
   onunhandledrejection:
     | ((this: Window, ev: PromiseRejectionEvent) => any)
     | null;
@@ -198,6 +200,14 @@ declare var onbeforeunload: ((this: Window, ev: Event) => any) | null;
  */
 declare var onunload: ((this: Window, ev: Event) => any) | null;
 
+/**
+ * Event handler for changes that other workers of the same process make to
+ * `localStorage`.
+ *
+ * @category Storage
+ */
+declare var onstorage: ((this: Window, ev: StorageEvent) => any) | null;
+
 /**
  * Event handler for unhandled promise rejections.
  * Triggered when a `Promise` is rejected and no rejection handler is attached to it.
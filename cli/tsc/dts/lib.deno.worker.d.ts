@@ -5,6 +5,7 @@
 /// <reference lib="deno.shared_globals" />
 /// <reference lib="esnext" />
 /// <reference lib="deno.cache" />
+/// <reference lib="deno.webstorage" />
 
 /**
  * Event map for WorkerGlobalScope event handlers.
@@ -14,6 +15,7 @@
 declare interface WorkerGlobalScopeEventMap {
   "error": ErrorEvent;
   "unhandledrejection": PromiseRejectionEvent;
+  "storage": StorageEvent;
 }
 
 /**
@@ -49,6 +51,12 @@ declare interface WorkerGlobalScope extends EventTarget {
     | ((this: WorkerGlobalScope, ev: PromiseRejectionEvent) => any)
     | null;
 
+  /**
+   * Event handler for changes that other workers of the same process make to
+   * `localStorage`.
+   */
+  onstorage: ((this: WorkerGlobalScope, ev: StorageEvent) => any) | null;
+
   /** Reference to the worker's global scope, which is the worker itself. */
   readonly self: WorkerGlobalScope & typeof globalThis;
 
@@ -91,6 +99,12 @@ declare interface WorkerGlobalScope extends EventTarget {
 
   /** The cache storage object for the worker. */
   caches: CacheStorage;
+
+  /** The `localStorage` of the worker, shared with the main worker. */
+  localStorage: Storage;
+
+  /** The `sessionStorage` of the worker, not shared with other workers. */
+  sessionStorage: Storage;
 }
 
 /**
@@ -360,6 +374,32 @@ declare var onunhandledrejection:
   | ((this: DedicatedWorkerGlobalScope, ev: PromiseRejectionEvent) => any)
   | null;
 
+/**
+ * Event handler for changes that other workers of the same process make to
+ * `localStorage`.
+ *
+ * @category Storage
+ */
+declare var onstorage:
+  | ((this: DedicatedWorkerGlobalScope, ev: StorageEvent) => any)
+  | null;
+
+/**
+ * The `localStorage` of the worker. It is the same storage area as the
+ * `localStorage` of the main worker that created it.
+ *
+ * @category Storage
+ */
+declare var localStorage: Storage;
+
+/**
+ * The `sessionStorage` of the worker. Every worker has its own, which is
+ * cleared when the worker exits.
+ *
+ * @category Storage
+ */
+declare var sessionStorage: Storage;
+
 /**
  * Reference to the worker's global scope, which is the worker itself.
  *
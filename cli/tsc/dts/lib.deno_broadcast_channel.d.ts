@@ -14,6 +14,11 @@ interface BroadcastChannelEventMap {
 }
 
 /**
+ * Channels are shared by all workers of a program. With
+ * `--unstable-broadcast-channel`, they are also shared by all processes of
+ * the same app, i.e. with the same `--location`, config file or main module,
+ * except on Windows.
+ *
  * @category Messaging
  */
 interface BroadcastChannel extends EventTarget {
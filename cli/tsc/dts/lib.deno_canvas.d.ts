@@ -192,3 +192,480 @@ declare var ImageBitmap: {
   prototype: ImageBitmap;
   new (): ImageBitmap;
 };
+
+/**
+ * The source images that can be drawn with
+ * {@linkcode OffscreenCanvasRenderingContext2D.drawImage}.
+ *
+ * @category Canvas
+ */
+type CanvasImageSource = ImageBitmap | OffscreenCanvas;
+
+/** @category Canvas */
+type OffscreenRenderingContextId =
+  | "2d"
+  | "bitmaprenderer"
+  | "webgl"
+  | "webgl2"
+  | "webgpu";
+
+/** @category Canvas */
+interface ImageEncodeOptions {
+  /** The quality of lossy formats, between 0 and 1. */
+  quality?: number;
+  /**
+   * The format of the image. Either `image/png` (default), `image/jpeg` or
+   * `image/webp`. WebP images are always lossless. Other formats fall back
+   * to PNG.
+   */
+  type?: string;
+}
+
+/**
+ * A canvas that is rendered on the CPU without a document. Only the `"2d"`
+ * context is supported; the other context types return `null`.
+ *
+ * @example
+ * ```ts
+ * const canvas = new OffscreenCanvas(200, 100);
+ * const ctx = canvas.getContext("2d");
+ * ctx.fillStyle = "rebeccapurple";
+ * ctx.fillRect(0, 0, 200, 100);
+ * const blob = await canvas.convertToBlob({ type: "image/png" });
+ * await Deno.writeFile("out.png", await blob.bytes());
+ * ```
+ *
+ * @category Canvas
+ */
+interface OffscreenCanvas {
+  /** The height of the canvas. Setting it clears the canvas. */
+  height: number;
+  /** The width of the canvas. Setting it clears the canvas. */
+  width: number;
+  /** Encodes the canvas as an image. */
+  convertToBlob(options?: ImageEncodeOptions): Promise<Blob>;
+  getContext(
+    contextId: "2d",
+    options?: unknown,
+  ): OffscreenCanvasRenderingContext2D;
+  getContext(
+    contextId: OffscreenRenderingContextId,
+    options?: unknown,
+  ): OffscreenCanvasRenderingContext2D | null;
+  /**
+   * Moves the pixels of the canvas to a new `ImageBitmap` and clears the
+   * canvas.
+   */
+  transferToImageBitmap(): ImageBitmap;
+}
+
+/**
+ * A canvas that is rendered on the CPU without a document.
+ *
+ * @category Canvas
+ */
+declare var OffscreenCanvas: {
+  prototype: OffscreenCanvas;
+  new (width: number, height: number): OffscreenCanvas;
+};
+
+/** @category Canvas */
+type CanvasFillRule = "evenodd" | "nonzero";
+/** @category Canvas */
+type CanvasLineCap = "butt" | "round" | "square";
+/** @category Canvas */
+type CanvasLineJoin = "bevel" | "miter" | "round";
+/** @category Canvas */
+type CanvasTextAlign = "center" | "end" | "left" | "right" | "start";
+/** @category Canvas */
+type CanvasTextBaseline =
+  | "alphabetic"
+  | "bottom"
+  | "hanging"
+  | "ideographic"
+  | "middle"
+  | "top";
+/** @category Canvas */
+type CanvasDirection = "inherit" | "ltr" | "rtl";
+/** @category Canvas */
+type ImageSmoothingQuality = "high" | "low" | "medium";
+/** @category Canvas */
+type GlobalCompositeOperation =
+  | "color"
+  | "color-burn"
+  | "color-dodge"
+  | "copy"
+  | "darken"
+  | "destination-atop"
+  | "destination-in"
+  | "destination-out"
+  | "destination-over"
+  | "difference"
+  | "exclusion"
+  | "hard-light"
+  | "hue"
+  | "lighten"
+  | "lighter"
+  | "luminosity"
+  | "multiply"
+  | "overlay"
+  | "saturation"
+  | "screen"
+  | "soft-light"
+  | "source-atop"
+  | "source-in"
+  | "source-out"
+  | "source-over"
+  | "xor";
+
+/** @category Canvas */
+interface DOMMatrix2DInit {
+  a?: number;
+  b?: number;
+  c?: number;
+  d?: number;
+  e?: number;
+  f?: number;
+  m11?: number;
+  m12?: number;
+  m21?: number;
+  m22?: number;
+  m41?: number;
+  m42?: number;
+}
+
+/** @category Canvas */
+interface DOMPointInit {
+  x?: number;
+  y?: number;
+}
+
+/**
+ * The path methods shared by {@linkcode Path2D} and
+ * {@linkcode OffscreenCanvasRenderingContext2D}.
+ *
+ * @category Canvas
+ */
+interface CanvasPath {
+  arc(
+    x: number,
+    y: number,
+    radius: number,
+    startAngle: number,
+    endAngle: number,
+    counterclockwise?: boolean,
+  ): void;
+  arcTo(x1: number, y1: number, x2: number, y2: number, radius: number): void;
+  bezierCurveTo(
+    cp1x: number,
+    cp1y: number,
+    cp2x: number,
+    cp2y: number,
+    x: number,
+    y: number,
+  ): void;
+  closePath(): void;
+  ellipse(
+    x: number,
+    y: number,
+    radiusX: number,
+    radiusY: number,
+    rotation: number,
+    startAngle: number,
+    endAngle: number,
+    counterclockwise?: boolean,
+  ): void;
+  lineTo(x: number, y: number): void;
+  moveTo(x: number, y: number): void;
+  quadraticCurveTo(cpx: number, cpy: number, x: number, y: number): void;
+  rect(x: number, y: number, w: number, h: number): void;
+  roundRect(
+    x: number,
+    y: number,
+    w: number,
+    h: number,
+    radii?: number | DOMPointInit | (number | DOMPointInit)[],
+  ): void;
+}
+
+/**
+ * A path that can be filled, stroked or used for clipping by a 2D context.
+ *
+ * @category Canvas
+ */
+interface Path2D extends CanvasPath {
+  /** Adds another path, transformed by `transform`, to this path. */
+  addPath(path: Path2D, transform?: DOMMatrix2DInit): void;
+}
+
+/**
+ * A path that can be filled, stroked or used for clipping by a 2D context.
+ *
+ * @category Canvas
+ */
+declare var Path2D: {
+  prototype: Path2D;
+  /** Creates an empty path, a copy of `path` or a path from SVG path data. */
+  new (path?: Path2D | string): Path2D;
+};
+
+/**
+ * A gradient created by
+ * {@linkcode OffscreenCanvasRenderingContext2D.createLinearGradient} or
+ * {@linkcode OffscreenCanvasRenderingContext2D.createRadialGradient}.
+ *
+ * @category Canvas
+ */
+interface CanvasGradient {
+  /** Adds a color stop at `offset`, which is between 0 and 1. */
+  addColorStop(offset: number, color: string): void;
+}
+
+/** @category Canvas */
+declare var CanvasGradient: {
+  prototype: CanvasGradient;
+  new (): never;
+};
+
+/**
+ * The dimensions of a piece of text, as returned by
+ * {@linkcode OffscreenCanvasRenderingContext2D.measureText}.
+ *
+ * @category Canvas
+ */
+interface TextMetrics {
+  readonly actualBoundingBoxAscent: number;
+  readonly actualBoundingBoxDescent: number;
+  readonly actualBoundingBoxLeft: number;
+  readonly actualBoundingBoxRight: number;
+  readonly alphabeticBaseline: number;
+  readonly emHeightAscent: number;
+  readonly emHeightDescent: number;
+  readonly fontBoundingBoxAscent: number;
+  readonly fontBoundingBoxDescent: number;
+  readonly hangingBaseline: number;
+  readonly ideographicBaseline: number;
+  readonly width: number;
+}
+
+/** @category Canvas */
+declare var TextMetrics: {
+  prototype: TextMetrics;
+  new (): never;
+};
+
+/**
+ * The 2D rendering context of an {@linkcode OffscreenCanvas}, rendered on
+ * the CPU.
+ *
+ * Text is only drawn with the fonts added to {@linkcode fonts}; system fonts
+ * are not used. Shadows, filters, patterns, conic gradients and hit testing
+ * with `isPointInPath()` are not supported.
+ *
+ * @category Canvas
+ */
+interface OffscreenCanvasRenderingContext2D extends CanvasPath {
+  readonly canvas: OffscreenCanvas;
+  direction: CanvasDirection;
+  fillStyle: string | CanvasGradient;
+  font: string;
+  globalAlpha: number;
+  globalCompositeOperation: GlobalCompositeOperation;
+  imageSmoothingEnabled: boolean;
+  imageSmoothingQuality: ImageSmoothingQuality;
+  lineCap: CanvasLineCap;
+  lineDashOffset: number;
+  lineJoin: CanvasLineJoin;
+  lineWidth: number;
+  miterLimit: number;
+  strokeStyle: string | CanvasGradient;
+  textAlign: CanvasTextAlign;
+  textBaseline: CanvasTextBaseline;
+  beginPath(): void;
+  clearRect(x: number, y: number, w: number, h: number): void;
+  clip(fillRule?: CanvasFillRule): void;
+  clip(path: Path2D, fillRule?: CanvasFillRule): void;
+  createImageData(
+    sw: number,
+    sh: number,
+    settings?: ImageDataSettings,
+  ): ImageData;
+  createImageData(imageData: ImageData): ImageData;
+  createLinearGradient(
+    x0: number,
+    y0: number,
+    x1: number,
+    y1: number,
+  ): CanvasGradient;
+  createRadialGradient(
+    x0: number,
+    y0: number,
+    r0: number,
+    x1: number,
+    y1: number,
+    r1: number,
+  ): CanvasGradient;
+  drawImage(image: CanvasImageSource, dx: number, dy: number): void;
+  drawImage(
+    image: CanvasImageSource,
+    dx: number,
+    dy: number,
+    dw: number,
+    dh: number,
+  ): void;
+  drawImage(
+    image: CanvasImageSource,
+    sx: number,
+    sy: number,
+    sw: number,
+    sh: number,
+    dx: number,
+    dy: number,
+    dw: number,
+    dh: number,
+  ): void;
+  fill(fillRule?: CanvasFillRule): void;
+  fill(path: Path2D, fillRule?: CanvasFillRule): void;
+  fillRect(x: number, y: number, w: number, h: number): void;
+  fillText(text: string, x: number, y: number, maxWidth?: number): void;
+  getImageData(
+    sx: number,
+    sy: number,
+    sw: number,
+    sh: number,
+    settings?: ImageDataSettings,
+  ): ImageData;
+  getLineDash(): number[];
+  getTransform(): Required<
+    Pick<DOMMatrix2DInit, "a" | "b" | "c" | "d" | "e" | "f">
+  >;
+  isContextLost(): boolean;
+  measureText(text: string): TextMetrics;
+  putImageData(imageData: ImageData, dx: number, dy: number): void;
+  putImageData(
+    imageData: ImageData,
+    dx: number,
+    dy: number,
+    dirtyX: number,
+    dirtyY: number,
+    dirtyWidth: number,
+    dirtyHeight: number,
+  ): void;
+  reset(): void;
+  resetTransform(): void;
+  restore(): void;
+  rotate(angle: number): void;
+  save(): void;
+  scale(x: number, y: number): void;
+  setLineDash(segments: number[]): void;
+  setTransform(
+    a: number,
+    b: number,
+    c: number,
+    d: number,
+    e: number,
+    f: number,
+  ): void;
+  setTransform(transform?: DOMMatrix2DInit): void;
+  stroke(path?: Path2D): void;
+  strokeRect(x: number, y: number, w: number, h: number): void;
+  strokeText(text: string, x: number, y: number, maxWidth?: number): void;
+  transform(
+    a: number,
+    b: number,
+    c: number,
+    d: number,
+    e: number,
+    f: number,
+  ): void;
+  translate(x: number, y: number): void;
+}
+
+/** @category Canvas */
+declare var OffscreenCanvasRenderingContext2D: {
+  prototype: OffscreenCanvasRenderingContext2D;
+  new (): never;
+};
+
+/** @category Canvas */
+type FontFaceLoadStatus = "error" | "loaded" | "loading" | "unloaded";
+/** @category Canvas */
+type FontFaceSetLoadStatus = "loaded" | "loading";
+
+/** @category Canvas */
+interface FontFaceDescriptors {
+  /** `normal`, `italic` or `oblique`. */
+  style?: string;
+  /** `normal`, `bold` or a number between 1 and 1000. */
+  weight?: string;
+}
+
+/**
+ * A font face that can be added to {@linkcode fonts} to render text in a
+ * canvas. The font is loaded from TrueType or OpenType data; loading fonts
+ * from URLs is not supported.
+ *
+ * @example
+ * ```ts
+ * const data = await Deno.readFile("./Inter-Regular.ttf");
+ * fonts.add(new FontFace("Inter", data));
+ * ctx.font = "24px Inter";
+ * ```
+ *
+ * @category Canvas
+ */
+interface FontFace {
+  readonly family: string;
+  readonly loaded: Promise<FontFace>;
+  readonly status: FontFaceLoadStatus;
+  readonly style: string;
+  readonly weight: string;
+  load(): Promise<FontFace>;
+}
+
+/** @category Canvas */
+declare var FontFace: {
+  prototype: FontFace;
+  new (
+    family: string,
+    source: BufferSource | string,
+    descriptors?: FontFaceDescriptors,
+  ): FontFace;
+};
+
+/**
+ * The set of font faces used to render text in canvases.
+ *
+ * @category Canvas
+ */
+interface FontFaceSet {
+  readonly ready: Promise<FontFaceSet>;
+  readonly size: number;
+  readonly status: FontFaceSetLoadStatus;
+  add(font: FontFace): FontFaceSet;
+  clear(): void;
+  delete(font: FontFace): boolean;
+  forEach(
+    callbackfn: (value: FontFace, key: FontFace, parent: FontFaceSet) => void,
+    thisArg?: unknown,
+  ): void;
+  has(font: FontFace): boolean;
+  entries(): IterableIterator<[FontFace, FontFace]>;
+  keys(): IterableIterator<FontFace>;
+  values(): IterableIterator<FontFace>;
+  [Symbol.iterator](): IterableIterator<FontFace>;
+}
+
+/** @category Canvas */
+declare var FontFaceSet: {
+  prototype: FontFaceSet;
+  new (): never;
+};
+
+/**
+ * The font faces used to render text in canvases. When no family of the
+ * `font` of a context matches, the family of the first added face is used.
+ *
+ * @category Canvas
+ */
+declare var fonts: FontFaceSet;
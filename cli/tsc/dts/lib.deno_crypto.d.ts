@@ -26,16 +26,28 @@ type HashAlgorithmIdentifier = AlgorithmIdentifier;
 type KeyType = "private" | "public" | "secret";
 /** @category Crypto */
 type KeyUsage =
+  | "decapsulateBits"
+  | "decapsulateKey"
   | "decrypt"
   | "deriveBits"
   | "deriveKey"
+  | "encapsulateBits"
+  | "encapsulateKey"
   | "encrypt"
   | "sign"
   | "unwrapKey"
   | "verify"
   | "wrapKey";
 /** @category Crypto */
-type KeyFormat = "jwk" | "pkcs8" | "raw" | "spki";
+type KeyFormat =
+  | "jwk"
+  | "pkcs8"
+  | "raw"
+  | "raw-private"
+  | "raw-public"
+  | "raw-secret"
+  | "raw-seed"
+  | "spki";
 /** @category Crypto */
 type NamedCurve = string;
 /** @category Crypto */
@@ -64,6 +76,8 @@ interface JsonWebKey {
   n?: string;
   oth?: RsaOtherPrimesInfo[];
   p?: string;
+  priv?: string;
+  pub?: string;
   q?: string;
   qi?: string;
   use?: string;
@@ -198,6 +212,69 @@ interface AesKeyAlgorithm extends KeyAlgorithm {
   length: number;
 }
 
+/** @category Crypto */
+interface CShakeParams extends Algorithm {
+  length: number;
+  functionName?: BufferSource;
+  customization?: BufferSource;
+}
+
+/** @category Crypto */
+interface KmacKeyGenParams extends Algorithm {
+  length?: number;
+}
+
+/** @category Crypto */
+interface KmacImportParams extends Algorithm {
+  length?: number;
+}
+
+/** @category Crypto */
+interface KmacKeyAlgorithm extends KeyAlgorithm {
+  length: number;
+}
+
+/** @category Crypto */
+interface KmacParams extends Algorithm {
+  length: number;
+  customization?: BufferSource;
+}
+
+/** @category Crypto */
+interface ContextParams extends Algorithm {
+  context?: BufferSource;
+}
+
+/** @category Crypto */
+interface AeadParams extends Algorithm {
+  iv: BufferSource;
+  additionalData?: BufferSource;
+  tagLength?: number;
+}
+
+/** @category Crypto */
+interface Argon2Params extends Algorithm {
+  nonce: BufferSource;
+  parallelism: number;
+  memory: number;
+  passes: number;
+  version?: number;
+  secretValue?: BufferSource;
+  associatedData?: BufferSource;
+}
+
+/** @category Crypto */
+interface EncapsulatedKey {
+  sharedKey: CryptoKey;
+  ciphertext: ArrayBuffer;
+}
+
+/** @category Crypto */
+interface EncapsulatedBits {
+  sharedKey: ArrayBuffer;
+  ciphertext: ArrayBuffer;
+}
+
 /** The CryptoKey dictionary of the Web Crypto API represents a cryptographic
  * key.
  *
@@ -315,7 +392,7 @@ interface SubtleCrypto {
    * @see https://developer.mozilla.org/en-US/docs/Web/API/SubtleCrypto/generateKey
    */
   generateKey(
-    algorithm: AesKeyGenParams | HmacKeyGenParams,
+    algorithm: AesKeyGenParams | HmacKeyGenParams | KmacKeyGenParams,
     extractable: boolean,
     keyUsages: KeyUsage[],
   ): Promise<CryptoKey>;
@@ -362,6 +439,7 @@ interface SubtleCrypto {
     algorithm:
       | AlgorithmIdentifier
       | HmacImportParams
+      | KmacImportParams
       | RsaHashedImportParams
       | EcKeyImportParams,
     extractable: boolean,
@@ -389,6 +467,7 @@ interface SubtleCrypto {
     algorithm:
       | AlgorithmIdentifier
       | HmacImportParams
+      | KmacImportParams
       | RsaHashedImportParams
       | EcKeyImportParams,
     extractable: boolean,
@@ -437,7 +516,12 @@ interface SubtleCrypto {
    * @see https://developer.mozilla.org/en-US/docs/Web/API/SubtleCrypto/sign
    */
   sign(
-    algorithm: AlgorithmIdentifier | RsaPssParams | EcdsaParams,
+    algorithm:
+      | AlgorithmIdentifier
+      | RsaPssParams
+      | EcdsaParams
+      | KmacParams
+      | ContextParams,
     key: CryptoKey,
     data: BufferSource,
   ): Promise<ArrayBuffer>;
@@ -454,7 +538,12 @@ interface SubtleCrypto {
    * @see https://developer.mozilla.org/en-US/docs/Web/API/SubtleCrypto/verify
    */
   verify(
-    algorithm: AlgorithmIdentifier | RsaPssParams | EcdsaParams,
+    algorithm:
+      | AlgorithmIdentifier
+      | RsaPssParams
+      | EcdsaParams
+      | KmacParams
+      | ContextParams,
     key: CryptoKey,
     signature: BufferSource,
     data: BufferSource,
@@ -473,7 +562,7 @@ interface SubtleCrypto {
    * @see https://developer.mozilla.org/en-US/docs/Web/API/SubtleCrypto/digest
    */
   digest(
-    algorithm: AlgorithmIdentifier,
+    algorithm: AlgorithmIdentifier | CShakeParams,
     data: BufferSource,
   ): Promise<ArrayBuffer>;
   /**
@@ -494,7 +583,8 @@ interface SubtleCrypto {
       | RsaOaepParams
       | AesCbcParams
       | AesGcmParams
-      | AesCtrParams,
+      | AesCtrParams
+      | AeadParams,
     key: CryptoKey,
     data: BufferSource,
   ): Promise<ArrayBuffer>;
@@ -514,7 +604,8 @@ interface SubtleCrypto {
       | RsaOaepParams
       | AesCbcParams
       | AesGcmParams
-      | AesCtrParams,
+      | AesCtrParams
+      | AeadParams,
     key: CryptoKey,
     data: BufferSource,
   ): Promise<ArrayBuffer>;
@@ -533,7 +624,8 @@ interface SubtleCrypto {
       | AlgorithmIdentifier
       | HkdfParams
       | Pbkdf2Params
-      | EcdhKeyDeriveParams,
+      | EcdhKeyDeriveParams
+      | Argon2Params,
     baseKey: CryptoKey,
     length: number,
   ): Promise<ArrayBuffer>;
@@ -556,17 +648,98 @@ interface SubtleCrypto {
       | AlgorithmIdentifier
       | HkdfParams
       | Pbkdf2Params
-      | EcdhKeyDeriveParams,
+      | EcdhKeyDeriveParams
+      | Argon2Params,
     baseKey: CryptoKey,
     derivedKeyType:
       | AlgorithmIdentifier
       | AesDerivedKeyParams
       | HmacImportParams
+      | KmacImportParams
       | HkdfParams
       | Pbkdf2Params,
     extractable: boolean,
     keyUsages: KeyUsage[],
   ): Promise<CryptoKey>;
+  /**
+   * Generates a shared secret and its ciphertext for the holder of the
+   * private key, and imports the shared secret as a key.
+   *
+   * @example
+   * ```ts
+   * const { sharedKey, ciphertext } = await crypto.subtle.encapsulateKey(
+   *   "ML-KEM-768",
+   *   publicKey,
+   *   "AES-GCM",
+   *   false,
+   *   ["encrypt", "decrypt"],
+   * );
+   * ```
+   */
+  encapsulateKey(
+    encapsulationAlgorithm: AlgorithmIdentifier,
+    encapsulationKey: CryptoKey,
+    sharedKeyAlgorithm:
+      | AlgorithmIdentifier
+      | HmacImportParams
+      | KmacImportParams,
+    extractable: boolean,
+    keyUsages: KeyUsage[],
+  ): Promise<EncapsulatedKey>;
+  /**
+   * Generates a shared secret and its ciphertext for the holder of the
+   * private key.
+   *
+   * @example
+   * ```ts
+   * await crypto.subtle.encapsulateBits("ML-KEM-768", publicKey);
+   * ```
+   */
+  encapsulateBits(
+    encapsulationAlgorithm: AlgorithmIdentifier,
+    encapsulationKey: CryptoKey,
+  ): Promise<EncapsulatedBits>;
+  /**
+   * Recovers the shared secret from a ciphertext produced by
+   * `encapsulateKey` or `encapsulateBits`, and imports it as a key.
+   *
+   * @example
+   * ```ts
+   * await crypto.subtle.decapsulateKey(
+   *   "ML-KEM-768",
+   *   privateKey,
+   *   ciphertext,
+   *   "AES-GCM",
+   *   false,
+   *   ["decrypt"],
+   * );
+   * ```
+   */
+  decapsulateKey(
+    decapsulationAlgorithm: AlgorithmIdentifier,
+    decapsulationKey: CryptoKey,
+    ciphertext: BufferSource,
+    sharedKeyAlgorithm:
+      | AlgorithmIdentifier
+      | HmacImportParams
+      | KmacImportParams,
+    extractable: boolean,
+    keyUsages: KeyUsage[],
+  ): Promise<CryptoKey>;
+  /**
+   * Recovers the shared secret from a ciphertext produced by
+   * `encapsulateKey` or `encapsulateBits`.
+   *
+   * @example
+   * ```ts
+   * await crypto.subtle.decapsulateBits("ML-KEM-768", privateKey, ciphertext);
+   * ```
+   */
+  decapsulateBits(
+    decapsulationAlgorithm: AlgorithmIdentifier,
+    decapsulationKey: CryptoKey,
+    ciphertext: BufferSource,
+  ): Promise<ArrayBuffer>;
   /**
    * Wraps (encrypts) a cryptographic key for secure storage or transmission
    *
@@ -618,6 +791,7 @@ interface SubtleCrypto {
     unwrappedKeyAlgorithm:
       | AlgorithmIdentifier
       | HmacImportParams
+      | KmacImportParams
       | RsaHashedImportParams
       | EcKeyImportParams,
     extractable: boolean,
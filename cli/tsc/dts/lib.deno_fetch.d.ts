@@ -380,6 +380,12 @@ interface Response extends Body {
   readonly statusText: string;
   readonly type: ResponseType;
   readonly url: string;
+  /** The protocol the response was received over, as an ALPN identifier
+   * (`"http/1.1"`, `"h2"` or `"h3"`). `null` if the response was not
+   * received over the network.
+   *
+   * This is a non-standard extension. */
+  readonly protocol: string | null;
   clone(): Response;
 }
 
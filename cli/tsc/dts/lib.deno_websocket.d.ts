@@ -325,6 +325,40 @@ interface WebSocketOptions {
    * @experimental
    */
   client?: Deno.HttpClient;
+  /**
+   * Offer permessage-deflate compression (RFC 7692) to the server. Pass
+   * `true` to use the default parameters.
+   * This feature is non-standard.
+   *
+   * ```ts
+   * const ws = new WebSocket("ws://localhost:8000/socket", {
+   *   perMessageDeflate: { clientNoContextTakeover: true },
+   * });
+   * ws.onopen = () => console.log(ws.extensions);
+   * ```
+   *
+   * @default {false}
+   */
+  perMessageDeflate?: boolean | WebSocketPerMessageDeflateOptions;
+}
+
+/**
+ * Parameters for permessage-deflate compression (RFC 7692).
+ * This feature is non-standard.
+ *
+ * @category WebSockets
+ */
+interface WebSocketPerMessageDeflateOptions {
+  /** Ask the server to reset its compression context after every message. */
+  serverNoContextTakeover?: boolean;
+  /** Reset the client's compression context after every message. */
+  clientNoContextTakeover?: boolean;
+  /** The largest LZ77 window, as a power of two between 8 and 15, the
+   * server may use to compress messages. */
+  serverMaxWindowBits?: number;
+  /** The largest LZ77 window, as a power of two between 8 and 15, the
+   * client may use to compress messages. */
+  clientMaxWindowBits?: number;
 }
 
 /**
@@ -46,3 +46,51 @@ declare var Storage: {
   readonly prototype: Storage;
   new (): never;
 };
+
+/** Options for constructing a `StorageEvent`.
+ *
+ * @category Storage
+ */
+interface StorageEventInit extends EventInit {
+  key?: string | null;
+  newValue?: string | null;
+  oldValue?: string | null;
+  storageArea?: Storage | null;
+  url?: string;
+}
+
+/** A `StorageEvent` is dispatched on the global scope of a worker when
+ * another worker of the same process changes `localStorage`. Workers share
+ * `localStorage` when they use the same storage directory, set by
+ * `--location`, `--storage-dir`, the config file or the main module, and
+ * web workers use the storage directory of the worker that created them.
+ * `sessionStorage` is never shared.
+ *
+ * @example
+ * ```ts
+ * addEventListener("storage", (event) => {
+ *   console.log(event.key, event.oldValue, event.newValue);
+ * });
+ * ```
+ *
+ * @see https://developer.mozilla.org/en-US/docs/Web/API/StorageEvent
+ * @category Storage
+ */
+interface StorageEvent extends Event {
+  /** The key that changed, or `null` if the storage area was cleared. */
+  readonly key: string | null;
+  /** The new value of the key, or `null` if it was removed. */
+  readonly newValue: string | null;
+  /** The previous value of the key, or `null` if it was just added. */
+  readonly oldValue: string | null;
+  /** The storage area that changed. */
+  readonly storageArea: Storage | null;
+  /** The value of `location.href`, or an empty string if it is not set. */
+  readonly url: string;
+}
+
+/** @category Storage */
+declare var StorageEvent: {
+  readonly prototype: StorageEvent;
+  new (type: string, eventInitDict?: StorageEventInit): StorageEvent;
+};
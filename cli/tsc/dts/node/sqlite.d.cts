@@ -584,6 +584,179 @@ declare module "node:sqlite" {
          */
         readonly sourceSQL: string;
     }
+    /**
+     * This class represents a single connection to a SQLite database whose queries run on a
+     * dedicated thread, so that they do not block the event loop. It accepts the same options as
+     * `DatabaseSync`, and every method that accesses the database returns a promise.
+     *
+     * Custom functions, aggregates and authorizers are not supported, as they would have to be
+     * called from the connection thread.
+     *
+     * ```js
+     * import { Database } from 'node:sqlite';
+     *
+     * await using db = new Database(':memory:');
+     * await db.exec('CREATE TABLE data(key INTEGER PRIMARY KEY, value TEXT)');
+     * const insert = await db.prepare('INSERT INTO data (key, value) VALUES (?, ?)');
+     * await insert.run(1, 'hello');
+     * const query = await db.prepare('SELECT * FROM data ORDER BY key');
+     * console.log(await query.all());
+     * ```
+     */
+    class Database implements AsyncDisposable {
+        /**
+         * Constructs a new `Database` instance and opens it, unless `options.open` is `false`.
+         * @param path The path of the database, or `':memory:'` for an in-memory database.
+         * @param options Configuration options for the database connection.
+         */
+        constructor(path: string | Buffer | URL, options?: DatabaseSyncOptions);
+        /**
+         * Whether the database is currently open.
+         */
+        readonly isOpen: boolean;
+        /**
+         * Opens the database specified in the `path` argument of the `Database` constructor. This
+         * method should only be used when the database is not opened via the constructor.
+         */
+        open(): void;
+        /**
+         * Closes the database connection once all pending queries finished. Prepared statements and
+         * sessions of this database can no longer be used afterwards.
+         */
+        close(): Promise<void>;
+        /**
+         * Executes one or more SQL statements without returning any results.
+         */
+        exec(sql: string): Promise<void>;
+        /**
+         * Compiles a SQL statement into a prepared statement.
+         */
+        prepare(sql: string): Promise<Statement>;
+        /**
+         * Creates and attaches a session to the database.
+         */
+        createSession(options?: CreateSessionOptions): Promise<AsyncSession>;
+        /**
+         * Applies a changeset. Resolves to `false` when applying it was aborted because of a conflict.
+         */
+        applyChangeset(changeset: Uint8Array, options?: DatabaseApplyChangesetOptions): Promise<boolean>;
+        /**
+         * Loads a shared library into the database connection. Requires the `allowExtension` option
+         * and FFI permission.
+         */
+        loadExtension(path: string, entryPoint?: string): Promise<void>;
+        /**
+         * Resolves to the location of the database file, or `null` for an in-memory database.
+         * @param dbName Name of the database. This can be `'main'` (the default primary database) or
+         * any other database that has been added with `ATTACH DATABASE`.
+         */
+        location(dbName?: string): Promise<string | null>;
+        /**
+         * Enables or disables the defensive flag of the connection.
+         */
+        enableDefensive(active: boolean): Promise<void>;
+        /**
+         * Creates a store of prepared statements for tagged template queries.
+         * @param maxSize The number of prepared statements to cache. **Default:** `1000`.
+         */
+        createTagStore(maxSize?: number): AsyncSQLTagStore;
+        /**
+         * Closes the database connection. If the database connection is already closed then this is
+         * a no-op.
+         */
+        [Symbol.asyncDispose](): Promise<void>;
+    }
+    interface DatabaseApplyChangesetOptions {
+        /**
+         * Only changes to the listed tables are applied. By default, all changes are applied.
+         */
+        filter?: string[] | undefined;
+        /**
+         * How conflicts are resolved, one of `SQLITE_CHANGESET_OMIT`, `SQLITE_CHANGESET_REPLACE`
+         * or `SQLITE_CHANGESET_ABORT`.
+         * @default constants.SQLITE_CHANGESET_ABORT
+         */
+        onConflict?: number | undefined;
+    }
+    /**
+     * A session created with `database.createSession()`.
+     */
+    interface AsyncSession extends AsyncDisposable {
+        /**
+         * Resolves to a changeset containing all changes since the session was created.
+         */
+        changeset(): Promise<Uint8Array>;
+        /**
+         * Similar to the method above, but generates a more compact patchset.
+         */
+        patchset(): Promise<Uint8Array>;
+        /**
+         * Closes the session.
+         */
+        close(): Promise<void>;
+    }
+    /**
+     * A prepared statement of a `Database`, created with `database.prepare()`. Parameters are
+     * bound in the same way as for `StatementSync`.
+     */
+    class Statement {
+        private constructor();
+        all(...anonymousParameters: SQLInputValue[]): Promise<Record<string, SQLOutputValue>[]>;
+        all(
+            namedParameters: Record<string, SQLInputValue>,
+            ...anonymousParameters: SQLInputValue[]
+        ): Promise<Record<string, SQLOutputValue>[]>;
+        get(...anonymousParameters: SQLInputValue[]): Promise<Record<string, SQLOutputValue> | undefined>;
+        get(
+            namedParameters: Record<string, SQLInputValue>,
+            ...anonymousParameters: SQLInputValue[]
+        ): Promise<Record<string, SQLOutputValue> | undefined>;
+        /**
+         * Returns an async iterator over the resulting rows, which are read from the connection
+         * thread in batches.
+         */
+        iterate(...anonymousParameters: SQLInputValue[]): AsyncIterableIterator<Record<string, SQLOutputValue>>;
+        iterate(
+            namedParameters: Record<string, SQLInputValue>,
+            ...anonymousParameters: SQLInputValue[]
+        ): AsyncIterableIterator<Record<string, SQLOutputValue>>;
+        run(...anonymousParameters: SQLInputValue[]): Promise<StatementResultingChanges>;
+        run(
+            namedParameters: Record<string, SQLInputValue>,
+            ...anonymousParameters: SQLInputValue[]
+        ): Promise<StatementResultingChanges>;
+        columns(): StatementColumnMetadata[];
+        setAllowBareNamedParameters(enabled: boolean): void;
+        setAllowUnknownNamedParameters(enabled: boolean): void;
+        setReadBigInts(enabled: boolean): void;
+        setReturnArrays(enabled: boolean): void;
+        readonly sourceSQL: string;
+    }
+    /**
+     * A store of prepared statements of a `Database`, created with `database.createTagStore()`.
+     */
+    interface AsyncSQLTagStore {
+        all(strings: TemplateStringsArray, ...values: SQLInputValue[]): Promise<Record<string, SQLOutputValue>[]>;
+        get(
+            strings: TemplateStringsArray,
+            ...values: SQLInputValue[]
+        ): Promise<Record<string, SQLOutputValue> | undefined>;
+        iterate(
+            strings: TemplateStringsArray,
+            ...values: SQLInputValue[]
+        ): AsyncIterableIterator<Record<string, SQLOutputValue>>;
+        run(strings: TemplateStringsArray, ...values: SQLInputValue[]): Promise<StatementResultingChanges>;
+        /**
+         * The number of cached prepared statements.
+         */
+        size(): number;
+        /**
+         * Removes all cached prepared statements.
+         */
+        clear(): void;
+        readonly capacity: number;
+        readonly db: Database;
+    }
     interface BackupOptions {
         /**
          * Name of the source database. This can be `'main'` (the default primary database) or any other
@@ -643,7 +816,11 @@ declare module "node:sqlite" {
      * following properties are supported:
      * @returns A promise that resolves when the backup is completed and rejects if an error occurs.
      */
-    function backup(sourceDb: DatabaseSync, path: string | Buffer | URL, options?: BackupOptions): Promise<void>;
+    function backup(
+        sourceDb: DatabaseSync | Database,
+        path: string | Buffer | URL,
+        options?: BackupOptions,
+    ): Promise<void>;
     /**
      * @since v22.13.0
      */
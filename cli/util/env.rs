@@ -4,6 +4,7 @@ use std::borrow::Cow;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::env;
+use std::ffi::OsStr;
 use std::ffi::OsString;
 use std::path::Path;
 use std::path::PathBuf;
@@ -68,102 +69,6 @@ impl WatchEnvTracker {
     })
   }
 
-  // Internal method that accepts an already-acquired lock to avoid deadlocks
-  fn load_env_file_inner(
-    &self,
-    file_path: PathBuf,
-    log_level: Option<log::Level>,
-    inner: &mut WatchEnvTrackerInner,
-  ) {
-    // Check if file exists
-    if !file_path.exists() {
-      // Only show warning if logging is enabled
-      #[allow(clippy::print_stderr)]
-      if log_level.map(|l| l >= log::Level::Info).unwrap_or(true) {
-        eprintln!(
-          "{} The environment file specified '{}' was not found.",
-          colors::yellow("Warning"),
-          file_path.display()
-        );
-      }
-      return;
-    }
-
-    match deno_dotenv::from_path_sanitized_iter(&file_path) {
-      Ok(iter) => {
-        for item in iter {
-          match item {
-            Ok((key, value)) => {
-              // Convert to OsString for consistency
-              let key_os = OsString::from(key);
-              let value_os = OsString::from(value);
-
-              // Process-level env vars should always take precedence over env files.
-              if inner.original_env.contains_key(&key_os) {
-                #[allow(clippy::print_stderr)]
-                if log_level.map(|l| l >= log::Level::Debug).unwrap_or(false) {
-                  eprintln!(
-                    "{} Variable '{}' already exists in the process environment, skipping value from '{}'",
-                    colors::yellow("Debug"),
-                    key_os.to_string_lossy(),
-                    file_path.display()
-                  );
-                }
-                continue;
-              }
-
-              // Check if this variable is already loaded from a previous file
-              if inner.loaded_variables.contains(&key_os) {
-                // Variable already exists from a previous file, skip it
-                #[allow(clippy::print_stderr)]
-                if log_level.map(|l| l >= log::Level::Debug).unwrap_or(false) {
-                  eprintln!(
-                    "{} Variable '{}' already loaded from '{}', skipping value from '{}'",
-                    colors::yellow("Debug"),
-                    key_os.to_string_lossy(),
-                    inner
-                      .loaded_variables
-                      .get(&key_os)
-                      .map(|k| k.to_string_lossy().to_string())
-                      .unwrap_or_else(|| "unknown".to_string()),
-                    file_path.display()
-                  );
-                }
-                continue;
-              }
-
-              // Set the environment variable
-              // SAFETY: We're setting environment variables with valid UTF-8 strings
-              // from the .env file. Both key and value are guaranteed to be valid strings.
-              unsafe {
-                env::set_var(&key_os, &value_os);
-              }
-
-              // Track this variable
-              inner.loaded_variables.insert(key_os.clone());
-              inner.unused_variables.remove(&key_os);
-            }
-            Err(e) => {
-              handle_dotenv_error(e, &file_path, log_level);
-            }
-          }
-        }
-      }
-      Err(e) =>
-      {
-        #[allow(clippy::print_stderr)]
-        if log_level.map(|l| l >= log::Level::Info).unwrap_or(true) {
-          eprintln!(
-            "{} Failed to read {}: {}",
-            colors::yellow("Warning"),
-            file_path.display(),
-            e
-          );
-        }
-      }
-    }
-  }
-
   /// Clean up variables that are no longer present in any loaded file
   fn _cleanup_removed_variables(
     &self,
@@ -204,10 +109,11 @@ impl WatchEnvTracker {
     }
   }
 
-  // Load multiple env files in reverse order (later files take precedence over earlier ones)
+  // Load multiple env files where later files take precedence over earlier ones
   pub fn load_env_variables_from_env_files(
     &self,
     file_paths: Option<&Vec<PathBuf>>,
+    expand: bool,
     log_level: Option<log::Level>,
   ) {
     let Some(env_file_names) = file_paths else {
@@ -219,20 +125,85 @@ impl WatchEnvTracker {
     inner.unused_variables = std::mem::take(&mut inner.loaded_variables);
     inner.loaded_variables = HashSet::new();
 
-    for env_file_name in env_file_names.iter().rev() {
-      self.load_env_file_inner(
-        env_file_name.to_path_buf(),
-        log_level,
-        &mut inner,
-      );
+    // references resolve with the original environment because the current
+    // one contains the variables loaded before the restart
+    let vars = read_env_files(
+      env_file_names,
+      expand.then_some(|name: &str| {
+        inner
+          .original_env
+          .get(OsStr::new(name))
+          .and_then(|value| value.to_str())
+          .map(|value| value.to_string())
+      }),
+      true,
+      log_level,
+    );
+    for (file_path, var) in vars {
+      let key_os = OsString::from(var.key);
+
+      // Process-level env vars should always take precedence over env files.
+      if inner.original_env.contains_key(&key_os) {
+        #[allow(clippy::print_stderr)]
+        if log_level.map(|l| l >= log::Level::Debug).unwrap_or(false) {
+          eprintln!(
+            "{} Variable '{}' already exists in the process environment, skipping value from '{}'",
+            colors::yellow("Debug"),
+            key_os.to_string_lossy(),
+            file_path.display()
+          );
+        }
+        continue;
+      }
+
+      // SAFETY: We're setting environment variables with sanitized key/value strings from a .env file.
+      unsafe {
+        env::set_var(&key_os, var.value);
+      }
+
+      // Track this variable
+      inner.unused_variables.remove(&key_os);
+      inner.loaded_variables.insert(key_os);
     }
 
     self._cleanup_removed_variables(&mut inner, log_level);
   }
 }
 
+/// Resolves the paths of the environment files to load for the
+/// `--env-file` and `--env-mode` flags, from the lowest to the highest
+/// precedence.
+///
+/// With a mode, the layered files that don't exist are skipped unless
+/// none of the files for an environment file exist.
+pub fn resolve_env_file_paths(
+  file_names: &[String],
+  mode: Option<&str>,
+) -> Vec<PathBuf> {
+  let mut paths = Vec::with_capacity(file_names.len());
+  for file_name in file_names {
+    let path = PathBuf::from(file_name);
+    if mode.is_none() {
+      paths.push(path);
+      continue;
+    }
+    let layered_paths = deno_dotenv::layered_paths(&path, mode)
+      .into_iter()
+      .filter(|path| path.is_file())
+      .collect::<Vec<_>>();
+    if layered_paths.is_empty() {
+      // warned about as not found on load
+      paths.push(path);
+    } else {
+      paths.extend(layered_paths);
+    }
+  }
+  paths
+}
+
 pub fn load_env_variables_from_env_files(
   filename: Option<&Vec<PathBuf>>,
+  expand: bool,
   flags_log_level: Option<log::Level>,
 ) {
   let Some(env_file_names) = filename else {
@@ -241,38 +212,106 @@ pub fn load_env_variables_from_env_files(
 
   let original_env_keys: HashSet<OsString> =
     env::vars_os().map(|(key, _)| key).collect();
-  let mut loaded_keys = HashSet::new();
+  let vars = read_env_files(
+    env_file_names,
+    expand.then_some(|name: &str| env::var(name).ok()),
+    false,
+    flags_log_level,
+  );
+  for (_, var) in vars {
+    let key_os = OsString::from(var.key);
+    if original_env_keys.contains(&key_os) {
+      continue;
+    }
+
+    // SAFETY: We're setting environment variables with sanitized key/value strings from a .env file.
+    unsafe {
+      env::set_var(&key_os, var.value);
+    }
+  }
+}
 
-  for env_file_name in env_file_names.iter().rev() {
-    let iter = match deno_dotenv::from_path_sanitized_iter(env_file_name) {
+/// Reads the variables from the environment files along with the file
+/// each one was loaded from. Variables from later files take precedence
+/// and within a file the first declaration of a variable is used.
+///
+/// When a lookup is provided, the references in the values are expanded.
+/// When watching, the lines after one that fails to parse are still loaded.
+fn read_env_files(
+  file_paths: &[PathBuf],
+  expand_lookup: Option<impl Fn(&str) -> Option<String>>,
+  is_watch: bool,
+  log_level: Option<log::Level>,
+) -> Vec<(&Path, deno_dotenv::EnvVar)> {
+  let mut loaded_from: HashMap<String, &Path> = HashMap::new();
+  let mut vars = Vec::new();
+  let mut var_paths = Vec::new();
+  for env_file_name in file_paths.iter().rev() {
+    if is_watch && !env_file_name.exists() {
+      // Only show warning if logging is enabled
+      #[allow(clippy::print_stderr)]
+      if log_level.map(|l| l >= log::Level::Info).unwrap_or(true) {
+        eprintln!(
+          "{} The environment file specified '{}' was not found.",
+          colors::yellow("Warning"),
+          env_file_name.display()
+        );
+      }
+      continue;
+    }
+    let iter = match deno_dotenv::from_path_sanitized_vars(env_file_name) {
       Ok(iter) => iter,
+      Err(error) if is_watch => {
+        #[allow(clippy::print_stderr)]
+        if log_level.map(|l| l >= log::Level::Info).unwrap_or(true) {
+          eprintln!(
+            "{} Failed to read {}: {}",
+            colors::yellow("Warning"),
+            env_file_name.display(),
+            error
+          );
+        }
+        continue;
+      }
       Err(error) => {
-        handle_dotenv_error(error, env_file_name, flags_log_level);
+        handle_dotenv_error(error, env_file_name, log_level);
         continue;
       }
     };
 
     for item in iter {
-      let (key, value) = match item {
-        Ok(pair) => pair,
+      let var = match item {
+        Ok(var) => var,
         Err(error) => {
-          handle_dotenv_error(error, env_file_name, flags_log_level);
+          handle_dotenv_error(error, env_file_name, log_level);
+          if is_watch {
+            continue;
+          }
           break;
         }
       };
-
-      let key_os = OsString::from(key);
-      if original_env_keys.contains(&key_os) || loaded_keys.contains(&key_os) {
+      if let Some(loaded_path) = loaded_from.get(&var.key) {
+        #[allow(clippy::print_stderr)]
+        if log_level.map(|l| l >= log::Level::Debug).unwrap_or(false) {
+          eprintln!(
+            "{} Variable '{}' already loaded from '{}', skipping value from '{}'",
+            colors::yellow("Debug"),
+            var.key,
+            loaded_path.display(),
+            env_file_name.display()
+          );
+        }
         continue;
       }
-
-      // SAFETY: We're setting environment variables with sanitized key/value strings from a .env file.
-      unsafe {
-        env::set_var(&key_os, value);
-      }
-      loaded_keys.insert(key_os);
+      loaded_from.insert(var.key.clone(), env_file_name.as_path());
+      vars.push(var);
+      var_paths.push(env_file_name.as_path());
     }
   }
+  if let Some(lookup) = expand_lookup {
+    deno_dotenv::expand_vars(&mut vars, lookup);
+  }
+  var_paths.into_iter().zip(vars).collect()
 }
 
 fn handle_dotenv_error(
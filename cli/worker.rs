@@ -20,6 +20,10 @@ use deno_npm_installer::PackageCaching;
 use deno_npm_installer::graph::NpmCachingStrategy;
 use deno_runtime::WorkerExecutionMode;
 use deno_runtime::coverage::CoverageCollector;
+use deno_runtime::cpu_profiler::CpuProfiler;
+use deno_runtime::cpu_profiler::CpuProfilerOptions;
+use deno_runtime::heap_profiler::HeapProfiler;
+use deno_runtime::heap_profiler::HeapProfilerOptions;
 use deno_runtime::deno_permissions::PermissionsContainer;
 use deno_runtime::worker::MainWorker;
 use deno_semver::npm::NpmPackageReqReference;
@@ -40,6 +44,8 @@ pub type CreateHmrRunnerCb = Box<dyn Fn() -> HmrRunnerState + Send + Sync>;
 pub struct CliMainWorkerOptions {
   pub create_hmr_runner: Option<CreateHmrRunnerCb>,
   pub maybe_coverage_dir: Option<PathBuf>,
+  pub maybe_cpu_profiler: Option<CpuProfilerOptions>,
+  pub maybe_heap_profiler: Option<HeapProfilerOptions>,
   pub default_npm_caching_strategy: NpmCachingStrategy,
   pub needs_test_modules: bool,
   pub initial_cwd: Arc<ModuleSpecifier>,
@@ -49,6 +55,8 @@ pub struct CliMainWorkerOptions {
 struct SharedState {
   pub create_hmr_runner: Option<CreateHmrRunnerCb>,
   pub maybe_coverage_dir: Option<PathBuf>,
+  pub maybe_cpu_profiler: Option<CpuProfilerOptions>,
+  pub maybe_heap_profiler: Option<HeapProfilerOptions>,
   pub maybe_file_watcher_communicator: Option<Arc<WatcherCommunicator>>,
   pub initial_cwd: Arc<ModuleSpecifier>,
 }
@@ -71,6 +79,8 @@ impl CliMainWorker {
 
   pub async fn run(&mut self) -> Result<i32, CoreError> {
     let mut maybe_coverage_collector = self.maybe_setup_coverage_collector();
+    let mut maybe_cpu_profiler = self.maybe_setup_cpu_profiler();
+    let mut maybe_heap_profiler = self.maybe_setup_heap_profiler();
     let mut maybe_hmr_runner = self.maybe_setup_hmr_runner();
 
     // WARNING: Remember to update cli/lib/worker.rs to align with
@@ -129,6 +139,12 @@ impl CliMainWorker {
     if let Some(coverage_collector) = maybe_coverage_collector.as_mut() {
       coverage_collector.stop_collecting()?;
     }
+    if let Some(cpu_profiler) = maybe_cpu_profiler.as_mut() {
+      cpu_profiler.stop(self.worker.js_runtime());
+    }
+    if let Some(heap_profiler) = maybe_heap_profiler.as_mut() {
+      heap_profiler.stop(self.worker.js_runtime());
+    }
     if let Some(hmr_runner) = maybe_hmr_runner.as_mut() {
       hmr_runner.stop();
     }
@@ -241,6 +257,16 @@ impl CliMainWorker {
     Some(coverage_collector)
   }
 
+  pub fn maybe_setup_cpu_profiler(&mut self) -> Option<CpuProfiler> {
+    let options = self.shared.maybe_cpu_profiler.clone()?;
+    Some(CpuProfiler::new(self.worker.js_runtime(), options))
+  }
+
+  pub fn maybe_setup_heap_profiler(&mut self) -> Option<HeapProfiler> {
+    let options = self.shared.maybe_heap_profiler.clone()?;
+    Some(HeapProfiler::new(self.worker.js_runtime(), options))
+  }
+
   pub fn execute_script_static(
     &mut self,
     name: &'static str,
@@ -311,6 +337,8 @@ impl CliMainWorkerFactory {
       shared: Arc::new(SharedState {
         create_hmr_runner: options.create_hmr_runner,
         maybe_coverage_dir: options.maybe_coverage_dir,
+        maybe_cpu_profiler: options.maybe_cpu_profiler,
+        maybe_heap_profiler: options.maybe_heap_profiler,
         maybe_file_watcher_communicator,
         initial_cwd: options.initial_cwd,
       }),
@@ -514,6 +542,7 @@ mod tests {
         npm_process_state_provider: Default::default(),
         root_cert_store_provider: Default::default(),
         fetch_dns_resolver: Default::default(),
+        web_storage: Default::default(),
         shared_array_buffer_store: Default::default(),
         compiled_wasm_module_store: Default::default(),
         v8_code_cache: Default::default(),
@@ -27,6 +27,7 @@ import {
 import { toInnerResponse } from "ext:deno_fetch/23_response.js";
 import { URLPrototype } from "ext:deno_web/00_url.js";
 import { getHeader } from "ext:deno_fetch/20_headers.js";
+import { setCacheStorage } from "ext:deno_fetch/24_http_cache.js";
 import {
   getReadableStreamResourceBacking,
   readableStreamForRid,
@@ -300,4 +301,6 @@ function cacheStorage() {
   return cacheStorageStorage;
 }
 
+setCacheStorage(cacheStorage);
+
 export { Cache, CacheStorage, cacheStorage };
@@ -15,10 +15,12 @@ const {
 import {
   op_crypto_base64url_decode,
   op_crypto_base64url_encode,
+  op_crypto_decapsulate_ml_kem,
   op_crypto_decrypt,
   op_crypto_derive_bits,
   op_crypto_derive_bits_x25519,
   op_crypto_derive_bits_x448,
+  op_crypto_encapsulate_ml_kem,
   op_crypto_encrypt,
   op_crypto_export_key,
   op_crypto_export_pkcs8_ed25519,
@@ -71,6 +73,7 @@ const {
   SafeWeakMap,
   StringFromCharCode,
   StringPrototypeCharCodeAt,
+  StringPrototypeSlice,
   StringPrototypeToLowerCase,
   StringPrototypeToUpperCase,
   Symbol,
@@ -102,6 +105,10 @@ const recognisedUsages = [
   "deriveBits",
   "wrapKey",
   "unwrapKey",
+  "encapsulateKey",
+  "encapsulateBits",
+  "decapsulateKey",
+  "decapsulateBits",
 ];
 
 const simpleAlgorithmDictionaries = {
@@ -121,6 +128,17 @@ const simpleAlgorithmDictionaries = {
   RsaOaepParams: { label: "BufferSource" },
   RsaHashedImportParams: { hash: "HashAlgorithmIdentifier" },
   EcKeyImportParams: {},
+  CShakeParams: { functionName: "BufferSource", customization: "BufferSource" },
+  KmacKeyGenParams: {},
+  KmacImportParams: {},
+  KmacParams: { customization: "BufferSource" },
+  ContextParams: { context: "BufferSource" },
+  AeadParams: { iv: "BufferSource", additionalData: "BufferSource" },
+  Argon2Params: {
+    nonce: "BufferSource",
+    secretValue: "BufferSource",
+    associatedData: "BufferSource",
+  },
 };
 
 const supportedAlgorithms = {
@@ -132,6 +150,8 @@ const supportedAlgorithms = {
     "SHA3-256": null,
     "SHA3-384": null,
     "SHA3-512": null,
+    "cSHAKE128": "CShakeParams",
+    "cSHAKE256": "CShakeParams",
   },
   "generateKey": {
     "RSASSA-PKCS1-v1_5": "RsaHashedKeyGenParams",
@@ -148,6 +168,15 @@ const supportedAlgorithms = {
     "X25519": null,
     "X448": null,
     "Ed25519": null,
+    "ChaCha20-Poly1305": null,
+    "KMAC128": "KmacKeyGenParams",
+    "KMAC256": "KmacKeyGenParams",
+    "ML-KEM-512": null,
+    "ML-KEM-768": null,
+    "ML-KEM-1024": null,
+    "ML-DSA-44": null,
+    "ML-DSA-65": null,
+    "ML-DSA-87": null,
   },
   "sign": {
     "RSASSA-PKCS1-v1_5": null,
@@ -155,6 +184,11 @@ const supportedAlgorithms = {
     "ECDSA": "EcdsaParams",
     "HMAC": null,
     "Ed25519": null,
+    "KMAC128": "KmacParams",
+    "KMAC256": "KmacParams",
+    "ML-DSA-44": "ContextParams",
+    "ML-DSA-65": "ContextParams",
+    "ML-DSA-87": "ContextParams",
   },
   "verify": {
     "RSASSA-PKCS1-v1_5": null,
@@ -162,6 +196,11 @@ const supportedAlgorithms = {
     "ECDSA": "EcdsaParams",
     "HMAC": null,
     "Ed25519": null,
+    "KMAC128": "KmacParams",
+    "KMAC256": "KmacParams",
+    "ML-DSA-44": "ContextParams",
+    "ML-DSA-65": "ContextParams",
+    "ML-DSA-87": "ContextParams",
   },
   "importKey": {
     "RSASSA-PKCS1-v1_5": "RsaHashedImportParams",
@@ -180,6 +219,18 @@ const supportedAlgorithms = {
     "Ed25519": null,
     "X25519": null,
     "X448": null,
+    "ChaCha20-Poly1305": null,
+    "KMAC128": "KmacImportParams",
+    "KMAC256": "KmacImportParams",
+    "ML-KEM-512": null,
+    "ML-KEM-768": null,
+    "ML-KEM-1024": null,
+    "ML-DSA-44": null,
+    "ML-DSA-65": null,
+    "ML-DSA-87": null,
+    "Argon2d": null,
+    "Argon2i": null,
+    "Argon2id": null,
   },
   "deriveBits": {
     "HKDF": "HkdfParams",
@@ -187,6 +238,9 @@ const supportedAlgorithms = {
     "ECDH": "EcdhKeyDeriveParams",
     "X25519": "EcdhKeyDeriveParams",
     "X448": "EcdhKeyDeriveParams",
+    "Argon2d": "Argon2Params",
+    "Argon2i": "Argon2Params",
+    "Argon2id": "Argon2Params",
   },
   "encrypt": {
     "RSA-OAEP": "RsaOaepParams",
@@ -194,6 +248,7 @@ const supportedAlgorithms = {
     "AES-GCM": "AesGcmParams",
     "AES-OCB": "AesGcmParams",
     "AES-CTR": "AesCtrParams",
+    "ChaCha20-Poly1305": "AeadParams",
   },
   "decrypt": {
     "RSA-OAEP": "RsaOaepParams",
@@ -201,6 +256,17 @@ const supportedAlgorithms = {
     "AES-GCM": "AesGcmParams",
     "AES-OCB": "AesGcmParams",
     "AES-CTR": "AesCtrParams",
+    "ChaCha20-Poly1305": "AeadParams",
+  },
+  "encapsulate": {
+    "ML-KEM-512": null,
+    "ML-KEM-768": null,
+    "ML-KEM-1024": null,
+  },
+  "decapsulate": {
+    "ML-KEM-512": null,
+    "ML-KEM-768": null,
+    "ML-KEM-1024": null,
   },
   "get key length": {
     "AES-CBC": "AesDerivedKeyParams",
@@ -210,6 +276,12 @@ const supportedAlgorithms = {
     "HMAC": "HmacImportParams",
     "HKDF": null,
     "PBKDF2": null,
+    "ChaCha20-Poly1305": null,
+    "KMAC128": "KmacImportParams",
+    "KMAC256": "KmacImportParams",
+    "Argon2d": null,
+    "Argon2i": null,
+    "Argon2id": null,
   },
   "wrapKey": {
     "AES-KW": null,
@@ -522,11 +594,53 @@ function getKeyLength(algorithm) {
       // 1.
       return null;
     }
+    case "ChaCha20-Poly1305": {
+      return 256;
+    }
+    case "KMAC128":
+    case "KMAC256": {
+      if (algorithm.length !== undefined) {
+        return algorithm.length;
+      }
+      return algorithm.name === "KMAC128" ? 128 : 256;
+    }
+    case "Argon2d":
+    case "Argon2i":
+    case "Argon2id": {
+      return null;
+    }
     default:
       throw new TypeError("Unreachable");
   }
 }
 
+// "raw-secret" and "raw-public" are the same as "raw" for the algorithms
+// that were defined before the more specific formats.
+const RAW_FORMAT_ALIASES = {
+  "raw-secret": [
+    "AES-CTR",
+    "AES-CBC",
+    "AES-GCM",
+    "AES-OCB",
+    "AES-KW",
+    "HMAC",
+    "HKDF",
+    "PBKDF2",
+  ],
+  "raw-public": ["ECDSA", "ECDH", "Ed25519", "X25519", "X448"],
+};
+
+function normalizeKeyFormat(format, algorithmName) {
+  const algorithms = RAW_FORMAT_ALIASES[format];
+  if (
+    algorithms !== undefined &&
+    ArrayPrototypeIncludes(algorithms, algorithmName)
+  ) {
+    return "raw";
+  }
+  return format;
+}
+
 class SubtleCrypto {
   constructor() {
     webidl.illegalConstructor();
@@ -552,8 +666,23 @@ class SubtleCrypto {
 
     algorithm = normalizeAlgorithm(algorithm, "digest");
 
+    if (algorithm.name === "cSHAKE128" || algorithm.name === "cSHAKE256") {
+      if (algorithm.length % 8 !== 0) {
+        throw new DOMException(
+          `Length must be a multiple of 8: received ${algorithm.length}`,
+          "OperationError",
+        );
+      }
+      if (algorithm.functionName === undefined) {
+        algorithm.functionName = new Uint8Array();
+      }
+      if (algorithm.customization === undefined) {
+        algorithm.customization = new Uint8Array();
+      }
+    }
+
     const result = await op_crypto_subtle_digest(
-      algorithm.name,
+      algorithm,
       data,
     );
 
@@ -807,6 +936,45 @@ class SubtleCrypto {
         // 9.
         return TypedArrayPrototypeGetBuffer(plaintext);
       }
+      case "ChaCha20-Poly1305": {
+        // 1.
+        if (TypedArrayPrototypeGetByteLength(normalizedAlgorithm.iv) !== 12) {
+          throw new DOMException(
+            "Initialization vector must be 12 bytes",
+            "OperationError",
+          );
+        }
+
+        // 2.
+        if (
+          normalizedAlgorithm.tagLength !== undefined &&
+          normalizedAlgorithm.tagLength !== 128
+        ) {
+          throw new DOMException(
+            `Invalid tag length: ${normalizedAlgorithm.tagLength}`,
+            "OperationError",
+          );
+        }
+
+        // 3.
+        if (TypedArrayPrototypeGetByteLength(data) < 16) {
+          throw new DOMException(
+            "The provided data is too small",
+            "OperationError",
+          );
+        }
+
+        // 4-6.
+        const plaintext = await op_crypto_decrypt({
+          key: keyData,
+          algorithm: "ChaCha20-Poly1305",
+          iv: normalizedAlgorithm.iv,
+          additionalData: normalizedAlgorithm.additionalData || null,
+        }, data);
+
+        // 7.
+        return TypedArrayPrototypeGetBuffer(plaintext);
+      }
       default:
         throw new DOMException("Not implemented", "NotSupportedError");
     }
@@ -951,6 +1119,49 @@ class SubtleCrypto {
         }
         return TypedArrayPrototypeGetBuffer(signature);
       }
+      case "KMAC128":
+      case "KMAC256": {
+        // 1.
+        if (normalizedAlgorithm.length % 8 !== 0) {
+          throw new DOMException(
+            `Length must be a multiple of 8: received ${normalizedAlgorithm.length}`,
+            "OperationError",
+          );
+        }
+
+        // 2.
+        const signature = await op_crypto_sign_key({
+          key: keyData,
+          algorithm: normalizedAlgorithm.name,
+          length: normalizedAlgorithm.length,
+          customization: normalizedAlgorithm.customization,
+        }, data);
+
+        return TypedArrayPrototypeGetBuffer(signature);
+      }
+      case "ML-DSA-44":
+      case "ML-DSA-65":
+      case "ML-DSA-87": {
+        // 1.
+        if (key[_type] !== "private") {
+          throw new DOMException(
+            "Key type not supported",
+            "InvalidAccessError",
+          );
+        }
+
+        // 2.
+        checkMlDsaContext(normalizedAlgorithm);
+
+        // 3.
+        const signature = await op_crypto_sign_key({
+          key: keyData,
+          algorithm: normalizedAlgorithm.name,
+          context: normalizedAlgorithm.context,
+        }, data);
+
+        return TypedArrayPrototypeGetBuffer(signature);
+      }
     }
 
     throw new TypeError("Unreachable");
@@ -1039,6 +1250,7 @@ class SubtleCrypto {
     const innerKey = WeakMapPrototypeGet(KEY_STORE, handle);
 
     const algorithmName = key[_algorithm].name;
+    format = normalizeKeyFormat(format, algorithmName);
 
     let result;
 
@@ -1078,6 +1290,21 @@ class SubtleCrypto {
         result = exportKeyAES(format, key, innerKey);
         break;
       }
+      case "ChaCha20-Poly1305":
+      case "KMAC128":
+      case "KMAC256": {
+        result = exportKeyRawSecret(format, key, innerKey);
+        break;
+      }
+      case "ML-KEM-512":
+      case "ML-KEM-768":
+      case "ML-KEM-1024":
+      case "ML-DSA-44":
+      case "ML-DSA-65":
+      case "ML-DSA-87": {
+        result = exportKeyModuleLattice(format, key, innerKey);
+        break;
+      }
       default:
         throw new DOMException("Not implemented", "NotSupportedError");
     }
@@ -1214,7 +1441,7 @@ class SubtleCrypto {
 
     // 15.
     const result = await this.importKey(
-      "raw",
+      "raw-secret",
       secret,
       normalizedDerivedKeyAlgorithmImport,
       extractable,
@@ -1350,133 +1577,490 @@ class SubtleCrypto {
 
         return op_crypto_verify_ed25519(keyData, data, signature);
       }
+      case "KMAC128":
+      case "KMAC256": {
+        // 1.
+        if (normalizedAlgorithm.length % 8 !== 0) {
+          throw new DOMException(
+            `Length must be a multiple of 8: received ${normalizedAlgorithm.length}`,
+            "OperationError",
+          );
+        }
+
+        // 2-3.
+        return await op_crypto_verify_key({
+          key: keyData,
+          algorithm: normalizedAlgorithm.name,
+          length: normalizedAlgorithm.length,
+          customization: normalizedAlgorithm.customization,
+          signature,
+        }, data);
+      }
+      case "ML-DSA-44":
+      case "ML-DSA-65":
+      case "ML-DSA-87": {
+        // 1.
+        if (key[_type] !== "public") {
+          throw new DOMException(
+            "Key type not supported",
+            "InvalidAccessError",
+          );
+        }
+
+        // 2.
+        checkMlDsaContext(normalizedAlgorithm);
+
+        // 3.
+        return await op_crypto_verify_key({
+          key: keyData,
+          algorithm: normalizedAlgorithm.name,
+          context: normalizedAlgorithm.context,
+          signature,
+        }, data);
+      }
     }
 
     throw new TypeError("Unreachable");
   }
 
   /**
-   * @param {string} algorithm
+   * @param {AlgorithmIdentifier} encapsulationAlgorithm
+   * @param {CryptoKey} encapsulationKey
+   * @param {AlgorithmIdentifier} sharedKeyAlgorithm
    * @param {boolean} extractable
    * @param {KeyUsage[]} keyUsages
-   * @returns {Promise<any>}
+   * @returns {Promise<EncapsulatedKey>}
    */
-  async wrapKey(format, key, wrappingKey, wrapAlgorithm) {
+  async encapsulateKey(
+    encapsulationAlgorithm,
+    encapsulationKey,
+    sharedKeyAlgorithm,
+    extractable,
+    keyUsages,
+  ) {
     webidl.assertBranded(this, SubtleCryptoPrototype);
-    const prefix = "Failed to execute 'wrapKey' on 'SubtleCrypto'";
-    webidl.requiredArguments(arguments.length, 4, prefix);
-    format = webidl.converters.KeyFormat(format, prefix, "Argument 1");
-    key = webidl.converters.CryptoKey(key, prefix, "Argument 2");
-    wrappingKey = webidl.converters.CryptoKey(
-      wrappingKey,
+    const prefix = "Failed to execute 'encapsulateKey' on 'SubtleCrypto'";
+    webidl.requiredArguments(arguments.length, 5, prefix);
+    encapsulationAlgorithm = webidl.converters.AlgorithmIdentifier(
+      encapsulationAlgorithm,
+      prefix,
+      "Argument 1",
+    );
+    encapsulationKey = webidl.converters.CryptoKey(
+      encapsulationKey,
+      prefix,
+      "Argument 2",
+    );
+    sharedKeyAlgorithm = webidl.converters.AlgorithmIdentifier(
+      sharedKeyAlgorithm,
       prefix,
       "Argument 3",
     );
-    wrapAlgorithm = webidl.converters.AlgorithmIdentifier(
-      wrapAlgorithm,
+    extractable = webidl.converters.boolean(extractable, prefix, "Argument 4");
+    keyUsages = webidl.converters["sequence<KeyUsage>"](
+      keyUsages,
       prefix,
-      "Argument 4",
+      "Argument 5",
     );
 
-    let normalizedAlgorithm;
+    // 2-3.
+    const normalizedEncapsulationAlgorithm = normalizeAlgorithm(
+      encapsulationAlgorithm,
+      "encapsulate",
+    );
 
-    try {
-      // 2.
-      normalizedAlgorithm = normalizeAlgorithm(wrapAlgorithm, "wrapKey");
-    } catch (_) {
-      // 3.
-      normalizedAlgorithm = normalizeAlgorithm(wrapAlgorithm, "encrypt");
-    }
+    // 4-5.
+    const normalizedSharedKeyAlgorithm = normalizeAlgorithm(
+      sharedKeyAlgorithm,
+      "importKey",
+    );
 
-    // 8.
-    if (normalizedAlgorithm.name !== wrappingKey[_algorithm].name) {
+    // 9.
+    if (
+      normalizedEncapsulationAlgorithm.name !==
+        encapsulationKey[_algorithm].name
+    ) {
       throw new DOMException(
-        "Wrapping algorithm does not match key algorithm",
+        "Encapsulation algorithm does not match key algorithm",
         "InvalidAccessError",
       );
     }
 
-    // 9.
-    if (!ArrayPrototypeIncludes(wrappingKey[_usages], "wrapKey")) {
+    // 10.
+    if (!ArrayPrototypeIncludes(encapsulationKey[_usages], "encapsulateKey")) {
       throw new DOMException(
         "The requested operation is not valid for the provided key",
         "InvalidAccessError",
       );
     }
 
-    // 10. NotSupportedError will be thrown in step 12.
     // 11.
-    if (key[_extractable] === false) {
-      throw new DOMException(
-        "Key is not extractable",
-        "InvalidAccessError",
-      );
-    }
+    const { sharedKey, ciphertext } = encapsulate(
+      normalizedEncapsulationAlgorithm,
+      encapsulationKey,
+    );
 
     // 12.
-    const exportedKey = await this.exportKey(format, key);
+    const key = await this.importKey(
+      "raw-secret",
+      sharedKey,
+      normalizedSharedKeyAlgorithm,
+      extractable,
+      keyUsages,
+    );
 
-    let bytes;
     // 13.
-    if (format !== "jwk") {
-      bytes = new Uint8Array(exportedKey);
-    } else {
-      const jwk = JSONStringify(exportedKey);
-      const ret = new Uint8Array(jwk.length);
-      for (let i = 0; i < jwk.length; i++) {
-        ret[i] = StringPrototypeCharCodeAt(jwk, i);
-      }
-      bytes = ret;
-    }
+    return {
+      sharedKey: key,
+      ciphertext: TypedArrayPrototypeGetBuffer(ciphertext),
+    };
+  }
 
-    // 14-15.
-    if (
-      supportedAlgorithms["wrapKey"][normalizedAlgorithm.name] !== undefined
-    ) {
-      const handle = wrappingKey[_handle];
-      const keyData = WeakMapPrototypeGet(KEY_STORE, handle);
+  /**
+   * @param {AlgorithmIdentifier} encapsulationAlgorithm
+   * @param {CryptoKey} encapsulationKey
+   * @returns {Promise<EncapsulatedBits>}
+   */
+  async encapsulateBits(encapsulationAlgorithm, encapsulationKey) {
+    webidl.assertBranded(this, SubtleCryptoPrototype);
+    const prefix = "Failed to execute 'encapsulateBits' on 'SubtleCrypto'";
+    webidl.requiredArguments(arguments.length, 2, prefix);
+    encapsulationAlgorithm = webidl.converters.AlgorithmIdentifier(
+      encapsulationAlgorithm,
+      prefix,
+      "Argument 1",
+    );
+    encapsulationKey = webidl.converters.CryptoKey(
+      encapsulationKey,
+      prefix,
+      "Argument 2",
+    );
 
-      switch (normalizedAlgorithm.name) {
-        case "AES-KW": {
-          const cipherText = await op_crypto_wrap_key({
-            key: keyData,
-            algorithm: normalizedAlgorithm.name,
-          }, bytes);
+    // 2-3.
+    const normalizedEncapsulationAlgorithm = normalizeAlgorithm(
+      encapsulationAlgorithm,
+      "encapsulate",
+    );
 
-          // 4.
-          return TypedArrayPrototypeGetBuffer(cipherText);
-        }
-        default: {
-          throw new DOMException(
-            "Not implemented",
-            "NotSupportedError",
-          );
-        }
-      }
-    } else if (
-      supportedAlgorithms["encrypt"][normalizedAlgorithm.name] !== undefined
+    // 7.
+    if (
+      normalizedEncapsulationAlgorithm.name !==
+        encapsulationKey[_algorithm].name
     ) {
-      // must construct a new key, since keyUsages is ["wrapKey"] and not ["encrypt"]
-      return await encrypt(
-        normalizedAlgorithm,
-        constructKey(
-          wrappingKey[_type],
-          wrappingKey[_extractable],
-          ["encrypt"],
-          wrappingKey[_algorithm],
-          wrappingKey[_handle],
-        ),
-        bytes,
+      throw new DOMException(
+        "Encapsulation algorithm does not match key algorithm",
+        "InvalidAccessError",
       );
-    } else {
+    }
+
+    // 8.
+    if (
+      !ArrayPrototypeIncludes(encapsulationKey[_usages], "encapsulateBits")
+    ) {
       throw new DOMException(
-        "Algorithm not supported",
-        "NotSupportedError",
+        "The requested operation is not valid for the provided key",
+        "InvalidAccessError",
       );
     }
+
+    // 9.
+    const { sharedKey, ciphertext } = encapsulate(
+      normalizedEncapsulationAlgorithm,
+      encapsulationKey,
+    );
+
+    // 10.
+    return {
+      sharedKey: TypedArrayPrototypeGetBuffer(sharedKey),
+      ciphertext: TypedArrayPrototypeGetBuffer(ciphertext),
+    };
   }
+
   /**
-   * @param {string} format
+   * @param {AlgorithmIdentifier} decapsulationAlgorithm
+   * @param {CryptoKey} decapsulationKey
+   * @param {BufferSource} ciphertext
+   * @param {AlgorithmIdentifier} sharedKeyAlgorithm
+   * @param {boolean} extractable
+   * @param {KeyUsage[]} keyUsages
+   * @returns {Promise<CryptoKey>}
+   */
+  async decapsulateKey(
+    decapsulationAlgorithm,
+    decapsulationKey,
+    ciphertext,
+    sharedKeyAlgorithm,
+    extractable,
+    keyUsages,
+  ) {
+    webidl.assertBranded(this, SubtleCryptoPrototype);
+    const prefix = "Failed to execute 'decapsulateKey' on 'SubtleCrypto'";
+    webidl.requiredArguments(arguments.length, 6, prefix);
+    decapsulationAlgorithm = webidl.converters.AlgorithmIdentifier(
+      decapsulationAlgorithm,
+      prefix,
+      "Argument 1",
+    );
+    decapsulationKey = webidl.converters.CryptoKey(
+      decapsulationKey,
+      prefix,
+      "Argument 2",
+    );
+    ciphertext = webidl.converters.BufferSource(
+      ciphertext,
+      prefix,
+      "Argument 3",
+    );
+    sharedKeyAlgorithm = webidl.converters.AlgorithmIdentifier(
+      sharedKeyAlgorithm,
+      prefix,
+      "Argument 4",
+    );
+    extractable = webidl.converters.boolean(extractable, prefix, "Argument 5");
+    keyUsages = webidl.converters["sequence<KeyUsage>"](
+      keyUsages,
+      prefix,
+      "Argument 6",
+    );
+
+    // 2-3.
+    const normalizedDecapsulationAlgorithm = normalizeAlgorithm(
+      decapsulationAlgorithm,
+      "decapsulate",
+    );
+
+    // 4-5.
+    const normalizedSharedKeyAlgorithm = normalizeAlgorithm(
+      sharedKeyAlgorithm,
+      "importKey",
+    );
+
+    // 6.
+    ciphertext = copyBuffer(ciphertext);
+
+    // 10.
+    if (
+      normalizedDecapsulationAlgorithm.name !==
+        decapsulationKey[_algorithm].name
+    ) {
+      throw new DOMException(
+        "Decapsulation algorithm does not match key algorithm",
+        "InvalidAccessError",
+      );
+    }
+
+    // 11.
+    if (!ArrayPrototypeIncludes(decapsulationKey[_usages], "decapsulateKey")) {
+      throw new DOMException(
+        "The requested operation is not valid for the provided key",
+        "InvalidAccessError",
+      );
+    }
+
+    // 12.
+    const sharedKey = decapsulate(
+      normalizedDecapsulationAlgorithm,
+      decapsulationKey,
+      ciphertext,
+    );
+
+    // 13-14.
+    return await this.importKey(
+      "raw-secret",
+      sharedKey,
+      normalizedSharedKeyAlgorithm,
+      extractable,
+      keyUsages,
+    );
+  }
+
+  /**
+   * @param {AlgorithmIdentifier} decapsulationAlgorithm
+   * @param {CryptoKey} decapsulationKey
+   * @param {BufferSource} ciphertext
+   * @returns {Promise<ArrayBuffer>}
+   */
+  async decapsulateBits(decapsulationAlgorithm, decapsulationKey, ciphertext) {
+    webidl.assertBranded(this, SubtleCryptoPrototype);
+    const prefix = "Failed to execute 'decapsulateBits' on 'SubtleCrypto'";
+    webidl.requiredArguments(arguments.length, 3, prefix);
+    decapsulationAlgorithm = webidl.converters.AlgorithmIdentifier(
+      decapsulationAlgorithm,
+      prefix,
+      "Argument 1",
+    );
+    decapsulationKey = webidl.converters.CryptoKey(
+      decapsulationKey,
+      prefix,
+      "Argument 2",
+    );
+    ciphertext = webidl.converters.BufferSource(
+      ciphertext,
+      prefix,
+      "Argument 3",
+    );
+
+    // 2-3.
+    const normalizedDecapsulationAlgorithm = normalizeAlgorithm(
+      decapsulationAlgorithm,
+      "decapsulate",
+    );
+
+    // 4.
+    ciphertext = copyBuffer(ciphertext);
+
+    // 8.
+    if (
+      normalizedDecapsulationAlgorithm.name !==
+        decapsulationKey[_algorithm].name
+    ) {
+      throw new DOMException(
+        "Decapsulation algorithm does not match key algorithm",
+        "InvalidAccessError",
+      );
+    }
+
+    // 9.
+    if (
+      !ArrayPrototypeIncludes(decapsulationKey[_usages], "decapsulateBits")
+    ) {
+      throw new DOMException(
+        "The requested operation is not valid for the provided key",
+        "InvalidAccessError",
+      );
+    }
+
+    // 10-11.
+    const sharedKey = decapsulate(
+      normalizedDecapsulationAlgorithm,
+      decapsulationKey,
+      ciphertext,
+    );
+    return TypedArrayPrototypeGetBuffer(sharedKey);
+  }
+
+  /**
+   * @param {string} algorithm
+   * @param {boolean} extractable
+   * @param {KeyUsage[]} keyUsages
+   * @returns {Promise<any>}
+   */
+  async wrapKey(format, key, wrappingKey, wrapAlgorithm) {
+    webidl.assertBranded(this, SubtleCryptoPrototype);
+    const prefix = "Failed to execute 'wrapKey' on 'SubtleCrypto'";
+    webidl.requiredArguments(arguments.length, 4, prefix);
+    format = webidl.converters.KeyFormat(format, prefix, "Argument 1");
+    key = webidl.converters.CryptoKey(key, prefix, "Argument 2");
+    wrappingKey = webidl.converters.CryptoKey(
+      wrappingKey,
+      prefix,
+      "Argument 3",
+    );
+    wrapAlgorithm = webidl.converters.AlgorithmIdentifier(
+      wrapAlgorithm,
+      prefix,
+      "Argument 4",
+    );
+
+    let normalizedAlgorithm;
+
+    try {
+      // 2.
+      normalizedAlgorithm = normalizeAlgorithm(wrapAlgorithm, "wrapKey");
+    } catch (_) {
+      // 3.
+      normalizedAlgorithm = normalizeAlgorithm(wrapAlgorithm, "encrypt");
+    }
+
+    // 8.
+    if (normalizedAlgorithm.name !== wrappingKey[_algorithm].name) {
+      throw new DOMException(
+        "Wrapping algorithm does not match key algorithm",
+        "InvalidAccessError",
+      );
+    }
+
+    // 9.
+    if (!ArrayPrototypeIncludes(wrappingKey[_usages], "wrapKey")) {
+      throw new DOMException(
+        "The requested operation is not valid for the provided key",
+        "InvalidAccessError",
+      );
+    }
+
+    // 10. NotSupportedError will be thrown in step 12.
+    // 11.
+    if (key[_extractable] === false) {
+      throw new DOMException(
+        "Key is not extractable",
+        "InvalidAccessError",
+      );
+    }
+
+    // 12.
+    const exportedKey = await this.exportKey(format, key);
+
+    let bytes;
+    // 13.
+    if (format !== "jwk") {
+      bytes = new Uint8Array(exportedKey);
+    } else {
+      const jwk = JSONStringify(exportedKey);
+      const ret = new Uint8Array(jwk.length);
+      for (let i = 0; i < jwk.length; i++) {
+        ret[i] = StringPrototypeCharCodeAt(jwk, i);
+      }
+      bytes = ret;
+    }
+
+    // 14-15.
+    if (
+      supportedAlgorithms["wrapKey"][normalizedAlgorithm.name] !== undefined
+    ) {
+      const handle = wrappingKey[_handle];
+      const keyData = WeakMapPrototypeGet(KEY_STORE, handle);
+
+      switch (normalizedAlgorithm.name) {
+        case "AES-KW": {
+          const cipherText = await op_crypto_wrap_key({
+            key: keyData,
+            algorithm: normalizedAlgorithm.name,
+          }, bytes);
+
+          // 4.
+          return TypedArrayPrototypeGetBuffer(cipherText);
+        }
+        default: {
+          throw new DOMException(
+            "Not implemented",
+            "NotSupportedError",
+          );
+        }
+      }
+    } else if (
+      supportedAlgorithms["encrypt"][normalizedAlgorithm.name] !== undefined
+    ) {
+      // must construct a new key, since keyUsages is ["wrapKey"] and not ["encrypt"]
+      return await encrypt(
+        normalizedAlgorithm,
+        constructKey(
+          wrappingKey[_type],
+          wrappingKey[_extractable],
+          ["encrypt"],
+          wrappingKey[_algorithm],
+          wrappingKey[_handle],
+        ),
+        bytes,
+      );
+    } else {
+      throw new DOMException(
+        "Algorithm not supported",
+        "NotSupportedError",
+      );
+    }
+  }
+  /**
+   * @param {string} format
    * @param {BufferSource} wrappedKey
    * @param {CryptoKey} unwrappingKey
    * @param {AlgorithmIdentifier} unwrapAlgorithm
@@ -2159,33 +2743,171 @@ async function generateKey(normalizedAlgorithm, extractable, usages) {
       // 14.
       return key;
     }
-  }
-}
-
-function importKeyX448(
-  format,
-  keyData,
-  extractable,
-  keyUsages,
-) {
-  switch (format) {
-    case "raw": {
+    case "ChaCha20-Poly1305": {
       // 1.
-      if (keyUsages.length > 0) {
+      if (
+        ArrayPrototypeFind(
+          usages,
+          (u) =>
+            !ArrayPrototypeIncludes([
+              "encrypt",
+              "decrypt",
+              "wrapKey",
+              "unwrapKey",
+            ], u),
+        ) !== undefined
+      ) {
         throw new DOMException("Invalid key usage", "SyntaxError");
       }
 
+      // 2.
+      const keyData = await op_crypto_generate_key({
+        algorithm: "ChaCha20-Poly1305",
+      });
       const handle = {};
-      WeakMapPrototypeSet(KEY_STORE, handle, keyData);
-
-      // 2-3.
-      const algorithm = {
-        name: "X448",
-      };
+      WeakMapPrototypeSet(KEY_STORE, handle, {
+        type: "secret",
+        data: keyData,
+      });
 
-      // 4-6.
+      // 3-9.
       return constructKey(
-        "public",
+        "secret",
+        extractable,
+        usageIntersection(usages, recognisedUsages),
+        { name: algorithmName },
+        handle,
+      );
+    }
+    case "KMAC128":
+    case "KMAC256": {
+      // 1.
+      if (
+        ArrayPrototypeFind(
+          usages,
+          (u) => !ArrayPrototypeIncludes(["sign", "verify"], u),
+        ) !== undefined
+      ) {
+        throw new DOMException("Invalid key usage", "SyntaxError");
+      }
+
+      // 2.
+      let length = normalizedAlgorithm.length;
+      if (length === undefined) {
+        length = algorithmName === "KMAC128" ? 128 : 256;
+      } else if (length === 0) {
+        throw new DOMException("Invalid length", "OperationError");
+      }
+
+      // 3.
+      const keyData = await op_crypto_generate_key({
+        algorithm: "KMAC",
+        length,
+      });
+      const handle = {};
+      WeakMapPrototypeSet(KEY_STORE, handle, {
+        type: "secret",
+        data: keyData,
+      });
+
+      // 4-10.
+      return constructKey(
+        "secret",
+        extractable,
+        usageIntersection(usages, recognisedUsages),
+        { name: algorithmName, length },
+        handle,
+      );
+    }
+    case "ML-KEM-512":
+    case "ML-KEM-768":
+    case "ML-KEM-1024":
+    case "ML-DSA-44":
+    case "ML-DSA-65":
+    case "ML-DSA-87": {
+      const supportedUsages = SUPPORTED_KEY_USAGES[algorithmName];
+
+      // 1.
+      if (
+        ArrayPrototypeFind(
+          usages,
+          (u) =>
+            !ArrayPrototypeIncludes(supportedUsages.public, u) &&
+            !ArrayPrototypeIncludes(supportedUsages.private, u),
+        ) !== undefined
+      ) {
+        throw new DOMException("Invalid key usage", "SyntaxError");
+      }
+
+      // 2. The private key is kept as the seed it is expanded from.
+      const family = StringPrototypeSlice(algorithmName, 0, 6);
+      const seed = await op_crypto_generate_key({ algorithm: family });
+      const privateKeyData = { type: "private", data: seed };
+      const publicKeyData = op_crypto_export_key({
+        format: "rawpublic",
+        algorithm: family,
+        parameterSet: algorithmName,
+      }, privateKeyData);
+
+      const privateHandle = {};
+      WeakMapPrototypeSet(KEY_STORE, privateHandle, privateKeyData);
+      const publicHandle = {};
+      WeakMapPrototypeSet(KEY_STORE, publicHandle, {
+        type: "public",
+        data: publicKeyData,
+      });
+
+      // 3-4.
+      const algorithm = { name: algorithmName };
+
+      // 5-9.
+      const publicKey = constructKey(
+        "public",
+        true,
+        usageIntersection(usages, supportedUsages.public),
+        algorithm,
+        publicHandle,
+      );
+
+      // 10-14.
+      const privateKey = constructKey(
+        "private",
+        extractable,
+        usageIntersection(usages, supportedUsages.private),
+        algorithm,
+        privateHandle,
+      );
+
+      // 15-18.
+      return { publicKey, privateKey };
+    }
+  }
+}
+
+function importKeyX448(
+  format,
+  keyData,
+  extractable,
+  keyUsages,
+) {
+  switch (format) {
+    case "raw": {
+      // 1.
+      if (keyUsages.length > 0) {
+        throw new DOMException("Invalid key usage", "SyntaxError");
+      }
+
+      const handle = {};
+      WeakMapPrototypeSet(KEY_STORE, handle, keyData);
+
+      // 2-3.
+      const algorithm = {
+        name: "X448",
+      };
+
+      // 4-6.
+      return constructKey(
+        "public",
         extractable,
         [],
         algorithm,
@@ -3592,6 +4314,7 @@ async function importKeyInner(
   keyUsages,
 ) {
   const algorithmName = normalizedAlgorithm.name;
+  format = normalizeKeyFormat(format, algorithmName);
 
   switch (algorithmName) {
     case "HMAC": {
@@ -3677,6 +4400,42 @@ async function importKeyInner(
         keyUsages,
       );
     }
+    case "ChaCha20-Poly1305":
+    case "KMAC128":
+    case "KMAC256": {
+      return importKeyRawSecret(
+        format,
+        normalizedAlgorithm,
+        keyData,
+        extractable,
+        keyUsages,
+      );
+    }
+    case "Argon2d":
+    case "Argon2i":
+    case "Argon2id": {
+      return importKeyArgon2(
+        format,
+        normalizedAlgorithm,
+        keyData,
+        extractable,
+        keyUsages,
+      );
+    }
+    case "ML-KEM-512":
+    case "ML-KEM-768":
+    case "ML-KEM-1024":
+    case "ML-DSA-44":
+    case "ML-DSA-65":
+    case "ML-DSA-87": {
+      return importKeyModuleLattice(
+        format,
+        normalizedAlgorithm,
+        keyData,
+        extractable,
+        keyUsages,
+      );
+    }
     default:
       throw new DOMException("Not implemented", "NotSupportedError");
   }
@@ -3708,6 +4467,36 @@ const SUPPORTED_KEY_USAGES = {
     private: ["deriveKey", "deriveBits"],
     jwkUse: "enc",
   },
+  "ML-KEM-512": {
+    public: ["encapsulateKey", "encapsulateBits"],
+    private: ["decapsulateKey", "decapsulateBits"],
+    jwkUse: "enc",
+  },
+  "ML-KEM-768": {
+    public: ["encapsulateKey", "encapsulateBits"],
+    private: ["decapsulateKey", "decapsulateBits"],
+    jwkUse: "enc",
+  },
+  "ML-KEM-1024": {
+    public: ["encapsulateKey", "encapsulateBits"],
+    private: ["decapsulateKey", "decapsulateBits"],
+    jwkUse: "enc",
+  },
+  "ML-DSA-44": {
+    public: ["verify"],
+    private: ["sign"],
+    jwkUse: "sig",
+  },
+  "ML-DSA-65": {
+    public: ["verify"],
+    private: ["sign"],
+    jwkUse: "sig",
+  },
+  "ML-DSA-87": {
+    public: ["verify"],
+    private: ["sign"],
+    jwkUse: "sig",
+  },
 };
 
 function importKeyRSA(
@@ -4761,28 +5550,498 @@ function exportKeyEC(format, key, innerKey) {
           kty: "EC",
         };
 
-        // missing step from spec
-        jwk.alg = "ECDH";
+        // missing step from spec
+        jwk.alg = "ECDH";
+
+        // 3.1
+        jwk.crv = key[_algorithm].namedCurve;
+
+        // 3.2 - 3.4
+        const data = op_crypto_export_key({
+          format: key[_type] === "private" ? "jwkprivate" : "jwkpublic",
+          algorithm: key[_algorithm].name,
+          namedCurve: key[_algorithm].namedCurve,
+        }, innerKey);
+        ObjectAssign(jwk, data);
+
+        // 4.
+        jwk.key_ops = key.usages;
+
+        // 5.
+        jwk.ext = key[_extractable];
+
+        return jwk;
+      }
+    }
+    default:
+      throw new DOMException("Not implemented", "NotSupportedError");
+  }
+}
+
+const RAW_SECRET_JWK_ALG = {
+  "ChaCha20-Poly1305": "C20P",
+  "KMAC128": "K128",
+  "KMAC256": "K256",
+};
+
+// ChaCha20-Poly1305 and KMAC keys.
+function importKeyRawSecret(
+  format,
+  normalizedAlgorithm,
+  keyData,
+  extractable,
+  keyUsages,
+) {
+  const algorithmName = normalizedAlgorithm.name;
+  const isKmac = algorithmName !== "ChaCha20-Poly1305";
+  const supportedKeyUsages = isKmac
+    ? ["sign", "verify"]
+    : ["encrypt", "decrypt", "wrapKey", "unwrapKey"];
+
+  // 1.
+  if (
+    ArrayPrototypeFind(
+      keyUsages,
+      (u) => !ArrayPrototypeIncludes(supportedKeyUsages, u),
+    ) !== undefined
+  ) {
+    throw new DOMException("Invalid key usage", "SyntaxError");
+  }
+
+  // 2.
+  let data = keyData;
+
+  switch (format) {
+    case "raw-secret": {
+      break;
+    }
+    case "jwk": {
+      // 1.
+      const jwk = keyData;
+
+      // 2.
+      if (jwk.kty !== "oct") {
+        throw new DOMException(
+          "'kty' property of JsonWebKey must be 'oct'",
+          "DataError",
+        );
+      }
+
+      // Section 6.4.1 of RFC7518
+      if (jwk.k === undefined) {
+        throw new DOMException(
+          "'k' property of JsonWebKey must be present",
+          "DataError",
+        );
+      }
+
+      // 3.
+      const { rawData } = op_crypto_import_key(
+        { algorithm: isKmac ? "KMAC" : "ChaCha20-Poly1305" },
+        { jwkSecret: jwk },
+      );
+      data = rawData.data;
+
+      // 4.
+      if (
+        jwk.alg !== undefined && jwk.alg !== RAW_SECRET_JWK_ALG[algorithmName]
+      ) {
+        throw new DOMException(
+          `Invalid algorithm: ${jwk.alg}`,
+          "DataError",
+        );
+      }
+
+      // 5.
+      const use = isKmac ? "sig" : "enc";
+      if (keyUsages.length > 0 && jwk.use !== undefined && jwk.use !== use) {
+        throw new DOMException("Invalid key usage", "DataError");
+      }
+
+      // 6.
+      // Section 4.3 of RFC7517
+      if (jwk.key_ops !== undefined) {
+        if (
+          ArrayPrototypeFind(
+            jwk.key_ops,
+            (u) => !ArrayPrototypeIncludes(recognisedUsages, u),
+          ) !== undefined
+        ) {
+          throw new DOMException(
+            "'key_ops' property of JsonWebKey is invalid",
+            "DataError",
+          );
+        }
+
+        if (
+          !ArrayPrototypeEvery(
+            keyUsages,
+            (u) => ArrayPrototypeIncludes(jwk.key_ops, u),
+          )
+        ) {
+          throw new DOMException(
+            "'key_ops' property of JsonWebKey is invalid",
+            "DataError",
+          );
+        }
+      }
+
+      // 7.
+      if (jwk.ext === false && extractable === true) {
+        throw new DOMException(
+          "'ext' property of JsonWebKey must not be false if extractable is true",
+          "DataError",
+        );
+      }
+
+      break;
+    }
+    default:
+      throw new DOMException("Not implemented", "NotSupportedError");
+  }
+
+  // 3.
+  const length = TypedArrayPrototypeGetByteLength(data) * 8;
+  if (isKmac) {
+    if (length === 0) {
+      throw new DOMException("Key length is zero", "DataError");
+    }
+    if (
+      normalizedAlgorithm.length !== undefined &&
+      normalizedAlgorithm.length !== length
+    ) {
+      throw new DOMException("Key length mismatch", "DataError");
+    }
+  } else if (length !== 256) {
+    throw new DOMException("Invalid key length", "DataError");
+  }
+
+  const handle = {};
+  WeakMapPrototypeSet(KEY_STORE, handle, {
+    type: "secret",
+    data,
+  });
+
+  // 4-7.
+  const algorithm = isKmac
+    ? { name: algorithmName, length }
+    : { name: algorithmName };
+
+  return constructKey(
+    "secret",
+    extractable,
+    usageIntersection(keyUsages, recognisedUsages),
+    algorithm,
+    handle,
+  );
+}
+
+function exportKeyRawSecret(format, key, innerKey) {
+  const algorithmName = key[_algorithm].name;
+
+  switch (format) {
+    case "raw-secret": {
+      // 1-2.
+      return TypedArrayPrototypeGetBuffer(innerKey.data);
+    }
+    case "jwk": {
+      // 1-2.
+      const jwk = {
+        kty: "oct",
+      };
+
+      // 3.
+      const data = op_crypto_export_key({
+        format: "jwksecret",
+        algorithm: algorithmName === "ChaCha20-Poly1305"
+          ? "ChaCha20-Poly1305"
+          : "KMAC",
+      }, innerKey);
+      ObjectAssign(jwk, data);
+
+      // 4.
+      jwk.alg = RAW_SECRET_JWK_ALG[algorithmName];
+
+      // 5.
+      jwk.key_ops = key.usages;
+
+      // 6.
+      jwk.ext = key[_extractable];
+
+      // 7.
+      return jwk;
+    }
+    default:
+      throw new DOMException("Not implemented", "NotSupportedError");
+  }
+}
+
+function importKeyArgon2(
+  format,
+  normalizedAlgorithm,
+  keyData,
+  extractable,
+  keyUsages,
+) {
+  // 1.
+  if (format !== "raw-secret") {
+    throw new DOMException("Format not supported", "NotSupportedError");
+  }
+
+  // 2.
+  if (
+    ArrayPrototypeFind(
+      keyUsages,
+      (u) => !ArrayPrototypeIncludes(["deriveKey", "deriveBits"], u),
+    ) !== undefined
+  ) {
+    throw new DOMException("Invalid key usage", "SyntaxError");
+  }
+
+  // 3.
+  if (extractable !== false) {
+    throw new DOMException(
+      "Key must not be extractable",
+      "SyntaxError",
+    );
+  }
+
+  // 4.
+  const handle = {};
+  WeakMapPrototypeSet(KEY_STORE, handle, {
+    type: "secret",
+    data: keyData,
+  });
+
+  // 5-9.
+  return constructKey(
+    "secret",
+    false,
+    usageIntersection(keyUsages, recognisedUsages),
+    { name: normalizedAlgorithm.name },
+    handle,
+  );
+}
+
+// ML-KEM and ML-DSA keys. Private keys are stored as their seed, which is
+// the only private key format that can be imported and exported.
+function importKeyModuleLattice(
+  format,
+  normalizedAlgorithm,
+  keyData,
+  extractable,
+  keyUsages,
+) {
+  const algorithmName = normalizedAlgorithm.name;
+  const family = StringPrototypeSlice(algorithmName, 0, 6);
+
+  let isPrivate;
+  switch (format) {
+    case "spki":
+    case "raw-public":
+      isPrivate = false;
+      break;
+    case "pkcs8":
+    case "raw-seed":
+      isPrivate = true;
+      break;
+    case "jwk":
+      if (family !== "ML-DSA") {
+        throw new DOMException("Not implemented", "NotSupportedError");
+      }
+      isPrivate = keyData.priv !== undefined;
+      break;
+    default:
+      throw new DOMException("Not implemented", "NotSupportedError");
+  }
+
+  // 1.
+  const supportedUsages = isPrivate
+    ? SUPPORTED_KEY_USAGES[algorithmName].private
+    : SUPPORTED_KEY_USAGES[algorithmName].public;
+  if (
+    ArrayPrototypeFind(
+      keyUsages,
+      (u) => !ArrayPrototypeIncludes(supportedUsages, u),
+    ) !== undefined
+  ) {
+    throw new DOMException("Invalid key usage", "SyntaxError");
+  }
+
+  let data;
+  switch (format) {
+    case "spki":
+      data = { spki: keyData };
+      break;
+    case "raw-public":
+      data = { rawPublic: keyData };
+      break;
+    case "pkcs8":
+      data = { pkcs8: keyData };
+      break;
+    case "raw-seed":
+      data = { rawSeed: keyData };
+      break;
+    case "jwk": {
+      const jwk = keyData;
+
+      // 2.
+      if (jwk.kty !== "AKP") {
+        throw new DOMException(
+          "'kty' property of JsonWebKey must be 'AKP'",
+          "DataError",
+        );
+      }
+
+      // 3.
+      if (jwk.alg !== algorithmName) {
+        throw new DOMException(
+          `'alg' property of JsonWebKey must be '${algorithmName}'`,
+          "DataError",
+        );
+      }
+
+      // 4.
+      if (keyUsages.length > 0 && jwk.use !== undefined && jwk.use !== "sig") {
+        throw new DOMException(
+          "'use' property of JsonWebKey must be 'sig'",
+          "DataError",
+        );
+      }
+
+      // 5.
+      if (jwk.key_ops !== undefined) {
+        if (
+          ArrayPrototypeFind(
+            jwk.key_ops,
+            (u) => !ArrayPrototypeIncludes(recognisedUsages, u),
+          ) !== undefined
+        ) {
+          throw new DOMException(
+            "'key_ops' property of JsonWebKey is invalid",
+            "DataError",
+          );
+        }
+
+        if (
+          !ArrayPrototypeEvery(
+            keyUsages,
+            (u) => ArrayPrototypeIncludes(jwk.key_ops, u),
+          )
+        ) {
+          throw new DOMException(
+            "'key_ops' property of JsonWebKey is invalid",
+            "DataError",
+          );
+        }
+      }
+
+      // 6.
+      if (jwk.ext === false && extractable === true) {
+        throw new DOMException(
+          "'ext' property of JsonWebKey must not be false if extractable is true",
+          "DataError",
+        );
+      }
+
+      // 7.
+      if (jwk.pub === undefined) {
+        throw new DOMException(
+          "'pub' property of JsonWebKey must be present",
+          "DataError",
+        );
+      }
+      data = isPrivate
+        ? { jwkPrivateAkp: { pub: jwk.pub, priv: jwk.priv } }
+        : { jwkPublicAkp: { pub: jwk.pub } };
+      break;
+    }
+  }
+
+  const { rawData } = op_crypto_import_key({
+    algorithm: family,
+    parameterSet: algorithmName,
+  }, data);
+
+  const handle = {};
+  WeakMapPrototypeSet(KEY_STORE, handle, rawData);
+
+  return constructKey(
+    rawData.type,
+    extractable,
+    usageIntersection(keyUsages, recognisedUsages),
+    { name: algorithmName },
+    handle,
+  );
+}
+
+function exportKeyModuleLattice(format, key, innerKey) {
+  const algorithmName = key[_algorithm].name;
+  const options = {
+    algorithm: StringPrototypeSlice(algorithmName, 0, 6),
+    parameterSet: algorithmName,
+  };
+
+  switch (format) {
+    case "spki":
+    case "raw-public": {
+      // 1.
+      if (key[_type] !== "public") {
+        throw new DOMException(
+          "Key is not a public key",
+          "InvalidAccessError",
+        );
+      }
+
+      // 2-3.
+      const data = op_crypto_export_key({
+        ...options,
+        format: format === "spki" ? "spki" : "rawpublic",
+      }, innerKey);
+      return TypedArrayPrototypeGetBuffer(data);
+    }
+    case "pkcs8":
+    case "raw-seed": {
+      // 1.
+      if (key[_type] !== "private") {
+        throw new DOMException(
+          "Key is not a private key",
+          "InvalidAccessError",
+        );
+      }
+
+      // 2-3.
+      const data = op_crypto_export_key({
+        ...options,
+        format: format === "pkcs8" ? "pkcs8" : "rawseed",
+      }, innerKey);
+      return TypedArrayPrototypeGetBuffer(data);
+    }
+    case "jwk": {
+      if (options.algorithm !== "ML-DSA") {
+        throw new DOMException("Not implemented", "NotSupportedError");
+      }
 
-        // 3.1
-        jwk.crv = key[_algorithm].namedCurve;
+      // 1-3.
+      const jwk = {
+        kty: "AKP",
+        alg: algorithmName,
+      };
 
-        // 3.2 - 3.4
-        const data = op_crypto_export_key({
-          format: key[_type] === "private" ? "jwkprivate" : "jwkpublic",
-          algorithm: key[_algorithm].name,
-          namedCurve: key[_algorithm].namedCurve,
-        }, innerKey);
-        ObjectAssign(jwk, data);
+      // 4-5.
+      const data = op_crypto_export_key({
+        ...options,
+        format: key[_type] === "private" ? "jwkprivate" : "jwkpublic",
+      }, innerKey);
+      ObjectAssign(jwk, data);
 
-        // 4.
-        jwk.key_ops = key.usages;
+      // 6.
+      jwk.key_ops = key.usages;
 
-        // 5.
-        jwk.ext = key[_extractable];
+      // 7.
+      jwk.ext = key[_extractable];
 
-        return jwk;
-      }
+      return jwk;
     }
     default:
       throw new DOMException("Not implemented", "NotSupportedError");
@@ -4860,6 +6119,72 @@ async function deriveBits(normalizedAlgorithm, baseKey, length) {
 
       return TypedArrayPrototypeGetBuffer(buf);
     }
+    case "Argon2d":
+    case "Argon2i":
+    case "Argon2id": {
+      // 1.
+      if (length == null || length < 32 || length % 8 !== 0) {
+        throw new DOMException("Invalid length", "OperationError");
+      }
+
+      // 2.
+      if (
+        normalizedAlgorithm.version !== undefined &&
+        normalizedAlgorithm.version !== 0x13
+      ) {
+        throw new DOMException(
+          `Unsupported version: ${normalizedAlgorithm.version}`,
+          "OperationError",
+        );
+      }
+
+      // 3.
+      if (
+        normalizedAlgorithm.parallelism === 0 ||
+        normalizedAlgorithm.parallelism > 0xFFFFFF
+      ) {
+        throw new DOMException("Invalid parallelism", "OperationError");
+      }
+
+      // 4.
+      if (normalizedAlgorithm.memory < 8 * normalizedAlgorithm.parallelism) {
+        throw new DOMException(
+          "memory must be at least 8 times the parallelism",
+          "OperationError",
+        );
+      }
+
+      // 5.
+      if (normalizedAlgorithm.passes === 0) {
+        throw new DOMException("passes must not be zero", "OperationError");
+      }
+
+      // 6.
+      if (TypedArrayPrototypeGetByteLength(normalizedAlgorithm.nonce) < 8) {
+        throw new DOMException(
+          "nonce must be at least 8 bytes",
+          "OperationError",
+        );
+      }
+
+      const handle = baseKey[_handle];
+      const keyData = WeakMapPrototypeGet(KEY_STORE, handle);
+
+      // 7-8.
+      const buf = await op_crypto_derive_bits({
+        key: keyData,
+        algorithm: normalizedAlgorithm.name,
+        parallelism: normalizedAlgorithm.parallelism,
+        memory: normalizedAlgorithm.memory,
+        passes: normalizedAlgorithm.passes,
+        version: normalizedAlgorithm.version,
+        secretValue: normalizedAlgorithm.secretValue,
+        associatedData: normalizedAlgorithm.associatedData,
+        length,
+      }, normalizedAlgorithm.nonce);
+
+      return TypedArrayPrototypeGetBuffer(buf);
+    }
     case "ECDH": {
       // 1.
       if (baseKey[_type] !== "private") {
@@ -5262,11 +6587,80 @@ async function encrypt(normalizedAlgorithm, key, data) {
       // 7.
       return TypedArrayPrototypeGetBuffer(cipherText);
     }
+    case "ChaCha20-Poly1305": {
+      // 1.
+      if (TypedArrayPrototypeGetByteLength(normalizedAlgorithm.iv) !== 12) {
+        throw new DOMException(
+          "Initialization vector must be 12 bytes",
+          "OperationError",
+        );
+      }
+
+      // 2.
+      if (
+        normalizedAlgorithm.tagLength !== undefined &&
+        normalizedAlgorithm.tagLength !== 128
+      ) {
+        throw new DOMException(
+          `Invalid tag length: ${normalizedAlgorithm.tagLength}`,
+          "OperationError",
+        );
+      }
+
+      // 3-5.
+      const cipherText = await op_crypto_encrypt({
+        key: keyData,
+        algorithm: "ChaCha20-Poly1305",
+        iv: normalizedAlgorithm.iv,
+        additionalData: normalizedAlgorithm.additionalData || null,
+      }, data);
+
+      // 6.
+      return TypedArrayPrototypeGetBuffer(cipherText);
+    }
     default:
       throw new DOMException("Not implemented", "NotSupportedError");
   }
 }
 
+function checkMlDsaContext(normalizedAlgorithm) {
+  if (
+    normalizedAlgorithm.context !== undefined &&
+    TypedArrayPrototypeGetByteLength(normalizedAlgorithm.context) > 255
+  ) {
+    throw new DOMException(
+      "Context must not be longer than 255 bytes",
+      "OperationError",
+    );
+  }
+}
+
+function encapsulate(normalizedAlgorithm, key) {
+  // 1.
+  if (key[_type] !== "public") {
+    throw new DOMException("Key type not supported", "InvalidAccessError");
+  }
+
+  // 2-4.
+  const keyData = WeakMapPrototypeGet(KEY_STORE, key[_handle]);
+  return op_crypto_encapsulate_ml_kem(normalizedAlgorithm.name, keyData.data);
+}
+
+function decapsulate(normalizedAlgorithm, key, ciphertext) {
+  // 1.
+  if (key[_type] !== "private") {
+    throw new DOMException("Key type not supported", "InvalidAccessError");
+  }
+
+  // 2-4.
+  const keyData = WeakMapPrototypeGet(KEY_STORE, key[_handle]);
+  return op_crypto_decapsulate_ml_kem(
+    normalizedAlgorithm.name,
+    keyData.data,
+    ciphertext,
+  );
+}
+
 webidl.configureInterface(SubtleCrypto);
 const subtle = webidl.createBranded(SubtleCrypto);
 
@@ -5370,6 +6764,10 @@ webidl.converters.KeyType = webidl.createEnumConverter("KeyType", [
 ]);
 
 webidl.converters.KeyFormat = webidl.createEnumConverter("KeyFormat", [
+  "raw-public",
+  "raw-private",
+  "raw-seed",
+  "raw-secret",
   "raw",
   "pkcs8",
   "spki",
@@ -5385,6 +6783,10 @@ webidl.converters.KeyUsage = webidl.createEnumConverter("KeyUsage", [
   "deriveBits",
   "wrapKey",
   "unwrapKey",
+  "encapsulateKey",
+  "encapsulateBits",
+  "decapsulateKey",
+  "decapsulateBits",
 ]);
 
 webidl.converters["sequence<KeyUsage>"] = webidl.createSequenceConverter(
@@ -5681,6 +7083,14 @@ const dictJsonWebKey = [
     key: "k",
     converter: webidl.converters["DOMString"],
   },
+  {
+    key: "pub",
+    converter: webidl.converters["DOMString"],
+  },
+  {
+    key: "priv",
+    converter: webidl.converters["DOMString"],
+  },
 ];
 
 webidl.converters.JsonWebKey = webidl.createDictionaryConverter(
@@ -5840,4 +7250,156 @@ const dictEcdhKeyDeriveParams = [
 webidl.converters.EcdhKeyDeriveParams = webidl
   .createDictionaryConverter("EcdhKeyDeriveParams", dictEcdhKeyDeriveParams);
 
+const dictCShakeParams = [
+  ...new SafeArrayIterator(dictAlgorithm),
+  {
+    key: "length",
+    converter: (V, prefix, context, opts) =>
+      webidl.converters["unsigned long"](V, prefix, context, {
+        ...opts,
+        enforceRange: true,
+      }),
+    required: true,
+  },
+  {
+    key: "functionName",
+    converter: webidl.converters["BufferSource"],
+  },
+  {
+    key: "customization",
+    converter: webidl.converters["BufferSource"],
+  },
+];
+
+webidl.converters.CShakeParams = webidl
+  .createDictionaryConverter("CShakeParams", dictCShakeParams);
+
+const dictKmacKeyGenParams = [
+  ...new SafeArrayIterator(dictAlgorithm),
+  {
+    key: "length",
+    converter: (V, prefix, context, opts) =>
+      webidl.converters["unsigned long"](V, prefix, context, {
+        ...opts,
+        enforceRange: true,
+      }),
+  },
+];
+
+webidl.converters.KmacKeyGenParams = webidl
+  .createDictionaryConverter("KmacKeyGenParams", dictKmacKeyGenParams);
+
+webidl.converters.KmacImportParams = webidl
+  .createDictionaryConverter("KmacImportParams", dictKmacKeyGenParams);
+
+const dictKmacParams = [
+  ...new SafeArrayIterator(dictAlgorithm),
+  {
+    key: "length",
+    converter: (V, prefix, context, opts) =>
+      webidl.converters["unsigned long"](V, prefix, context, {
+        ...opts,
+        enforceRange: true,
+      }),
+    required: true,
+  },
+  {
+    key: "customization",
+    converter: webidl.converters["BufferSource"],
+  },
+];
+
+webidl.converters.KmacParams = webidl
+  .createDictionaryConverter("KmacParams", dictKmacParams);
+
+const dictContextParams = [
+  ...new SafeArrayIterator(dictAlgorithm),
+  {
+    key: "context",
+    converter: webidl.converters["BufferSource"],
+  },
+];
+
+webidl.converters.ContextParams = webidl
+  .createDictionaryConverter("ContextParams", dictContextParams);
+
+const dictAeadParams = [
+  ...new SafeArrayIterator(dictAlgorithm),
+  {
+    key: "iv",
+    converter: webidl.converters["BufferSource"],
+    required: true,
+  },
+  {
+    key: "additionalData",
+    converter: webidl.converters["BufferSource"],
+  },
+  {
+    key: "tagLength",
+    converter: (V, prefix, context, opts) =>
+      webidl.converters["octet"](V, prefix, context, {
+        ...opts,
+        enforceRange: true,
+      }),
+  },
+];
+
+webidl.converters.AeadParams = webidl
+  .createDictionaryConverter("AeadParams", dictAeadParams);
+
+const dictArgon2Params = [
+  ...new SafeArrayIterator(dictAlgorithm),
+  {
+    key: "nonce",
+    converter: webidl.converters["BufferSource"],
+    required: true,
+  },
+  {
+    key: "parallelism",
+    converter: (V, prefix, context, opts) =>
+      webidl.converters["unsigned long"](V, prefix, context, {
+        ...opts,
+        enforceRange: true,
+      }),
+    required: true,
+  },
+  {
+    key: "memory",
+    converter: (V, prefix, context, opts) =>
+      webidl.converters["unsigned long"](V, prefix, context, {
+        ...opts,
+        enforceRange: true,
+      }),
+    required: true,
+  },
+  {
+    key: "passes",
+    converter: (V, prefix, context, opts) =>
+      webidl.converters["unsigned long"](V, prefix, context, {
+        ...opts,
+        enforceRange: true,
+      }),
+    required: true,
+  },
+  {
+    key: "version",
+    converter: (V, prefix, context, opts) =>
+      webidl.converters["octet"](V, prefix, context, {
+        ...opts,
+        enforceRange: true,
+      }),
+  },
+  {
+    key: "secretValue",
+    converter: webidl.converters["BufferSource"],
+  },
+  {
+    key: "associatedData",
+    converter: webidl.converters["BufferSource"],
+  },
+];
+
+webidl.converters.Argon2Params = webidl
+  .createDictionaryConverter("Argon2Params", dictArgon2Params);
+
 export { Crypto, crypto, CryptoKey, SubtleCrypto };
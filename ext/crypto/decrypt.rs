@@ -80,6 +80,13 @@ pub enum DecryptAlgorithm {
     length: usize,
     tag_length: usize,
   },
+  #[serde(rename = "ChaCha20-Poly1305", rename_all = "camelCase")]
+  ChaCha20Poly1305 {
+    #[serde(with = "serde_bytes")]
+    iv: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    additional_data: Option<Vec<u8>>,
+  },
 }
 
 #[derive(Debug, thiserror::Error, deno_error::JsError)]
@@ -150,6 +157,10 @@ pub async fn op_crypto_decrypt(
       length,
       tag_length,
     } => decrypt_aes_ocb(key, length, tag_length, iv, additional_data, &data),
+    DecryptAlgorithm::ChaCha20Poly1305 {
+      iv,
+      additional_data,
+    } => decrypt_chacha20_poly1305(key, iv, additional_data, &data),
   };
   let buf = spawn_blocking(fun).await.unwrap()?;
   Ok(buf.into())
@@ -472,3 +483,29 @@ fn decrypt_aes_ocb(
 
   Ok(plaintext)
 }
+
+fn decrypt_chacha20_poly1305(
+  key: V8RawKeyData,
+  iv: Vec<u8>,
+  additional_data: Option<Vec<u8>>,
+  data: &[u8],
+) -> Result<Vec<u8>, DecryptError> {
+  use aws_lc_rs::aead;
+
+  let key = key.as_secret_key()?;
+  let additional_data = additional_data.unwrap_or_default();
+
+  let key = aead::UnboundKey::new(&aead::CHACHA20_POLY1305, key)
+    .map_err(|_| DecryptError::InvalidKeyOrIv)?;
+  let nonce = aead::Nonce::try_assume_unique_for_key(&iv)
+    .map_err(|_| DecryptError::InvalidKeyOrIv)?;
+
+  // The ciphertext with the tag appended, decrypted in place.
+  let mut plaintext = data.to_vec();
+  let len = aead::LessSafeKey::new(key)
+    .open_in_place(nonce, aead::Aad::from(additional_data), &mut plaintext)
+    .map_err(|_| DecryptError::Failed)?
+    .len();
+  plaintext.truncate(len);
+  Ok(plaintext)
+}
@@ -74,6 +74,13 @@ pub enum EncryptAlgorithm {
     length: usize,
     tag_length: usize,
   },
+  #[serde(rename = "ChaCha20-Poly1305", rename_all = "camelCase")]
+  ChaCha20Poly1305 {
+    #[serde(with = "serde_bytes")]
+    iv: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    additional_data: Option<Vec<u8>>,
+  },
   #[serde(rename = "AES-CTR", rename_all = "camelCase")]
   AesCtr {
     #[serde(with = "serde_bytes")]
@@ -137,6 +144,10 @@ pub async fn op_crypto_encrypt(
       length,
       tag_length,
     } => encrypt_aes_ocb(key, length, tag_length, iv, additional_data, &data),
+    EncryptAlgorithm::ChaCha20Poly1305 {
+      iv,
+      additional_data,
+    } => encrypt_chacha20_poly1305(key, iv, additional_data, &data),
     EncryptAlgorithm::AesCtr {
       counter,
       ctr_length,
@@ -425,3 +436,30 @@ fn encrypt_aes_ctr(
     _ => Err(EncryptError::InvalidCounterLength),
   }
 }
+
+fn encrypt_chacha20_poly1305(
+  key: V8RawKeyData,
+  iv: Vec<u8>,
+  additional_data: Option<Vec<u8>>,
+  data: &[u8],
+) -> Result<Vec<u8>, EncryptError> {
+  use aws_lc_rs::aead;
+
+  let key = key.as_secret_key()?;
+  let additional_data = additional_data.unwrap_or_default();
+
+  let key = aead::UnboundKey::new(&aead::CHACHA20_POLY1305, key)
+    .map_err(|_| EncryptError::InvalidKeyOrIv)?;
+  let nonce = aead::Nonce::try_assume_unique_for_key(&iv)
+    .map_err(|_| EncryptError::InvalidKeyOrIv)?;
+
+  let mut ciphertext = data.to_vec();
+  aead::LessSafeKey::new(key)
+    .seal_in_place_append_tag(
+      nonce,
+      aead::Aad::from(additional_data),
+      &mut ciphertext,
+    )
+    .map_err(|_| EncryptError::Failed)?;
+  Ok(ciphertext)
+}
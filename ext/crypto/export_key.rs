@@ -6,6 +6,8 @@ use const_oid::AssociatedOid;
 use const_oid::ObjectIdentifier;
 use deno_core::ToJsBuffer;
 use deno_core::op2;
+use deno_crypto_provider::ml_dsa::MlDsa;
+use deno_crypto_provider::ml_kem::MlKem;
 use elliptic_curve::sec1::ToEncodedPoint;
 use p256::pkcs8::DecodePrivateKey;
 use rsa::pkcs1::der::Decode;
@@ -54,6 +56,8 @@ pub enum ExportKeyFormat {
   JwkPublic,
   JwkPrivate,
   JwkSecret,
+  RawPublic,
+  RawSeed,
 }
 
 #[derive(Deserialize)]
@@ -73,6 +77,14 @@ pub enum ExportKeyAlgorithm {
   Aes {},
   #[serde(rename = "HMAC")]
   Hmac {},
+  #[serde(rename = "ChaCha20-Poly1305")]
+  ChaCha20Poly1305 {},
+  #[serde(rename = "KMAC")]
+  Kmac {},
+  #[serde(rename = "ML-KEM", rename_all = "camelCase")]
+  MlKem { parameter_set: MlKemParameterSet },
+  #[serde(rename = "ML-DSA", rename_all = "camelCase")]
+  MlDsa { parameter_set: MlDsaParameterSet },
 }
 
 #[derive(Serialize)]
@@ -107,6 +119,13 @@ pub enum ExportKeyResult {
     y: String,
     d: String,
   },
+  JwkPublicAkp {
+    r#pub: String,
+  },
+  JwkPrivateAkp {
+    r#pub: String,
+    r#priv: String,
+  },
 }
 
 #[op2]
@@ -123,9 +142,18 @@ pub fn op_crypto_export_key(
     | ExportKeyAlgorithm::Ecdsa { named_curve } => {
       export_key_ec(opts.format, key_data, opts.algorithm, named_curve)
     }
-    ExportKeyAlgorithm::Aes {} | ExportKeyAlgorithm::Hmac {} => {
+    ExportKeyAlgorithm::Aes {}
+    | ExportKeyAlgorithm::Hmac {}
+    | ExportKeyAlgorithm::ChaCha20Poly1305 {}
+    | ExportKeyAlgorithm::Kmac {} => {
       export_key_symmetric(opts.format, key_data)
     }
+    ExportKeyAlgorithm::MlKem { parameter_set } => {
+      export_key_ml_kem(opts.format, key_data, parameter_set)
+    }
+    ExportKeyAlgorithm::MlDsa { parameter_set } => {
+      export_key_ml_dsa(opts.format, key_data, parameter_set)
+    }
   }
 }
 
@@ -429,6 +457,110 @@ fn export_key_ec(
         }
       }
     }
-    ExportKeyFormat::JwkSecret => Err(SharedError::UnsupportedFormat.into()),
+    ExportKeyFormat::JwkSecret
+    | ExportKeyFormat::RawPublic
+    | ExportKeyFormat::RawSeed => Err(SharedError::UnsupportedFormat.into()),
+  }
+}
+
+fn pq_spki(
+  oid: ObjectIdentifier,
+  public_key: &[u8],
+) -> Result<ExportKeyResult, ExportKeyError> {
+  let key_info = spki::SubjectPublicKeyInfo {
+    algorithm: spki::AlgorithmIdentifierRef {
+      oid,
+      // The parameters are absent for ML-KEM and ML-DSA.
+      parameters: None,
+    },
+    subject_public_key: BitString::from_bytes(public_key)?,
+  };
+  Ok(ExportKeyResult::Spki(key_info.to_der()?.into()))
+}
+
+/// Encodes the seed of an ML-KEM or ML-DSA private key as a `PrivateKeyInfo`
+/// using the `seed [0] IMPLICIT OCTET STRING` choice.
+fn pq_pkcs8(
+  oid: ObjectIdentifier,
+  seed: &[u8],
+) -> Result<ExportKeyResult, ExportKeyError> {
+  let mut private_key = vec![0x80, seed.len() as u8];
+  private_key.extend_from_slice(seed);
+  let pk_info = rsa::pkcs8::PrivateKeyInfo {
+    public_key: None,
+    algorithm: rsa::pkcs8::AlgorithmIdentifierRef {
+      oid,
+      parameters: None,
+    },
+    private_key: &private_key,
+  };
+
+  let mut pkcs8_der = Vec::new();
+  pk_info.encode_to_vec(&mut pkcs8_der)?;
+  Ok(ExportKeyResult::Pkcs8(pkcs8_der.into()))
+}
+
+fn export_key_ml_kem(
+  format: ExportKeyFormat,
+  key_data: V8RawKeyData,
+  parameter_set: MlKemParameterSet,
+) -> Result<ExportKeyResult, ExportKeyError> {
+  let ml_kem = MlKem::from(parameter_set);
+  let public_key = || match &key_data {
+    V8RawKeyData::Public(data) => Ok(data.to_vec()),
+    V8RawKeyData::Private(seed) => ml_kem
+      .public_key_from_seed(seed)
+      .map_err(|_| SharedError::ExpectedValidPrivateKey),
+    V8RawKeyData::Secret(_) => Err(SharedError::ExpectedPublicKey),
+  };
+
+  match format {
+    ExportKeyFormat::Spki => pq_spki(parameter_set.oid(), &public_key()?),
+    ExportKeyFormat::RawPublic => {
+      Ok(ExportKeyResult::Raw(public_key()?.into()))
+    }
+    ExportKeyFormat::Pkcs8 => {
+      pq_pkcs8(parameter_set.oid(), key_data.as_private_key()?)
+    }
+    ExportKeyFormat::RawSeed => Ok(ExportKeyResult::Raw(
+      key_data.as_private_key()?.to_vec().into(),
+    )),
+    _ => Err(SharedError::UnsupportedFormat.into()),
+  }
+}
+
+fn export_key_ml_dsa(
+  format: ExportKeyFormat,
+  key_data: V8RawKeyData,
+  parameter_set: MlDsaParameterSet,
+) -> Result<ExportKeyResult, ExportKeyError> {
+  let ml_dsa = MlDsa::from(parameter_set);
+  let public_key = || match &key_data {
+    V8RawKeyData::Public(data) => Ok(data.to_vec()),
+    V8RawKeyData::Private(seed) => ml_dsa
+      .public_key_from_seed(seed)
+      .map_err(|_| SharedError::ExpectedValidPrivateKey),
+    V8RawKeyData::Secret(_) => Err(SharedError::ExpectedPublicKey),
+  };
+
+  match format {
+    ExportKeyFormat::Spki => pq_spki(parameter_set.oid(), &public_key()?),
+    ExportKeyFormat::RawPublic => {
+      Ok(ExportKeyResult::Raw(public_key()?.into()))
+    }
+    ExportKeyFormat::JwkPublic => Ok(ExportKeyResult::JwkPublicAkp {
+      r#pub: bytes_to_b64(&public_key()?),
+    }),
+    ExportKeyFormat::Pkcs8 => {
+      pq_pkcs8(parameter_set.oid(), key_data.as_private_key()?)
+    }
+    ExportKeyFormat::RawSeed => Ok(ExportKeyResult::Raw(
+      key_data.as_private_key()?.to_vec().into(),
+    )),
+    ExportKeyFormat::JwkPrivate => Ok(ExportKeyResult::JwkPrivateAkp {
+      r#pub: bytes_to_b64(&public_key()?),
+      r#priv: bytes_to_b64(key_data.as_private_key()?),
+    }),
+    _ => Err(SharedError::UnsupportedFormat.into()),
   }
 }
@@ -34,6 +34,8 @@ pub enum GenerateKeyError {
   FailedRSAKeySerialization,
   #[error("Invalid AES key length")]
   InvalidAESKeyLength,
+  #[error("Invalid KMAC key length")]
+  InvalidKMACKeyLength,
   #[error("Failed to generate RSA key")]
   FailedRSAKeyGeneration,
   #[error("Failed to generate EC key")]
@@ -68,6 +70,14 @@ pub enum GenerateKeyOptions {
     hash: ShaHash,
     length: Option<usize>,
   },
+  #[serde(rename = "ChaCha20-Poly1305")]
+  ChaCha20Poly1305 {},
+  #[serde(rename = "KMAC")]
+  Kmac { length: usize },
+  #[serde(rename = "ML-KEM")]
+  MlKem {},
+  #[serde(rename = "ML-DSA")]
+  MlDsa {},
 }
 
 #[op2]
@@ -84,6 +94,16 @@ pub async fn op_crypto_generate_key(
     GenerateKeyOptions::Hmac { hash, length } => {
       generate_key_hmac(hash, length)
     }
+    GenerateKeyOptions::ChaCha20Poly1305 {} => generate_random(32),
+    GenerateKeyOptions::Kmac { length } => generate_key_kmac(length),
+    // Private keys of the post-quantum algorithms are stored as the seed
+    // they are expanded from.
+    GenerateKeyOptions::MlKem {} => {
+      generate_random(deno_crypto_provider::ml_kem::SEED_LEN)
+    }
+    GenerateKeyOptions::MlDsa {} => {
+      generate_random(deno_crypto_provider::ml_dsa::SEED_LEN)
+    }
   };
   let buf = spawn_blocking(fun).await.unwrap()?;
   Ok(buf.into())
@@ -193,3 +213,20 @@ fn generate_key_hmac(
 
   Ok(key)
 }
+
+fn generate_key_kmac(length: usize) -> Result<Vec<u8>, GenerateKeyError> {
+  if length == 0 || !length.is_multiple_of(8) {
+    return Err(GenerateKeyError::InvalidKMACKeyLength);
+  }
+  generate_random(length / 8)
+}
+
+fn generate_random(len: usize) -> Result<Vec<u8>, GenerateKeyError> {
+  let rng = aws_lc_rs::rand::SystemRandom::new();
+  let mut key = vec![0u8; len];
+  rng
+    .fill(&mut key)
+    .map_err(|_| GenerateKeyError::FailedKeyGeneration)?;
+
+  Ok(key)
+}
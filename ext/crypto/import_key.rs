@@ -4,6 +4,10 @@ use base64::Engine;
 use deno_core::JsBuffer;
 use deno_core::ToJsBuffer;
 use deno_core::op2;
+use deno_crypto_provider::ml_dsa;
+use deno_crypto_provider::ml_dsa::MlDsa;
+use deno_crypto_provider::ml_kem;
+use deno_crypto_provider::ml_kem::MlKem;
 use elliptic_curve::pkcs8::PrivateKeyInfo;
 use p256::pkcs8::EncodePrivateKey;
 use rsa::pkcs1::UintRef;
@@ -72,6 +76,12 @@ pub enum ImportKeyError {
   InvalidKeyData,
   #[error("invalid JWK private key")]
   InvalidJWKPrivateKey,
+  #[error("invalid ML-KEM public key")]
+  InvalidMlKemPublicKey,
+  #[error("invalid ML-DSA public key")]
+  InvalidMlDsaPublicKey,
+  #[error("only PKCS#8 private keys in the seed format are supported")]
+  ExpectedSeedPrivateKey,
   #[error(transparent)]
   EllipticCurve(#[from] elliptic_curve::Error),
   #[error("expected valid PKCS#8 data")]
@@ -118,6 +128,15 @@ pub enum KeyData {
     y: String,
     d: String,
   },
+  RawPublic(JsBuffer),
+  RawSeed(JsBuffer),
+  JwkPublicAkp {
+    r#pub: String,
+  },
+  JwkPrivateAkp {
+    r#pub: String,
+    r#priv: String,
+  },
 }
 
 #[derive(Deserialize)]
@@ -137,6 +156,14 @@ pub enum ImportKeyOptions {
   Aes {},
   #[serde(rename = "HMAC", rename_all = "camelCase")]
   Hmac {},
+  #[serde(rename = "ChaCha20-Poly1305")]
+  ChaCha20Poly1305 {},
+  #[serde(rename = "KMAC")]
+  Kmac {},
+  #[serde(rename = "ML-KEM", rename_all = "camelCase")]
+  MlKem { parameter_set: MlKemParameterSet },
+  #[serde(rename = "ML-DSA", rename_all = "camelCase")]
+  MlDsa { parameter_set: MlDsaParameterSet },
 }
 
 #[derive(Serialize)]
@@ -155,6 +182,10 @@ pub enum ImportKeyResult {
   Aes { raw_data: RustRawKeyData },
   #[serde(rename_all = "camelCase")]
   Hmac { raw_data: RustRawKeyData },
+  #[serde(rename_all = "camelCase")]
+  MlKem { raw_data: RustRawKeyData },
+  #[serde(rename_all = "camelCase")]
+  MlDsa { raw_data: RustRawKeyData },
 }
 
 #[op2]
@@ -171,8 +202,18 @@ pub fn op_crypto_import_key(
     | ImportKeyOptions::Ecdh { named_curve } => {
       import_key_ec(key_data, named_curve)
     }
-    ImportKeyOptions::Aes {} => import_key_aes(key_data),
-    ImportKeyOptions::Hmac {} => import_key_hmac(key_data),
+    ImportKeyOptions::Aes {} | ImportKeyOptions::ChaCha20Poly1305 {} => {
+      import_key_aes(key_data)
+    }
+    ImportKeyOptions::Hmac {} | ImportKeyOptions::Kmac {} => {
+      import_key_hmac(key_data)
+    }
+    ImportKeyOptions::MlKem { parameter_set } => {
+      import_key_ml_kem(key_data, parameter_set)
+    }
+    ImportKeyOptions::MlDsa { parameter_set } => {
+      import_key_ml_dsa(key_data, parameter_set)
+    }
   }
 }
 
@@ -832,3 +873,137 @@ fn import_key_hmac(
     _ => return Err(SharedError::UnsupportedFormat.into()),
   })
 }
+
+/// Returns the seed of an ML-KEM or ML-DSA `PrivateKeyInfo`, whose private
+/// key is the `seed [0] IMPLICIT OCTET STRING` choice of the key formats in
+/// draft-ietf-lamps-kyber-certificates and draft-ietf-lamps-dilithium-certificates.
+fn pkcs8_seed(
+  data: &[u8],
+  oid: const_oid::ObjectIdentifier,
+  seed_len: usize,
+) -> Result<Vec<u8>, ImportKeyError> {
+  let pk_info = PrivateKeyInfo::from_der(data)
+    .map_err(|_| ImportKeyError::ExpectedValidPkcs8Data)?;
+  if pk_info.algorithm.oid != oid {
+    return Err(ImportKeyError::UnsupportedAlgorithm);
+  }
+  if pk_info.algorithm.parameters.is_some() {
+    return Err(ImportKeyError::MalformedParameters);
+  }
+  match pk_info.private_key {
+    [0x80, len, seed @ ..]
+      if *len as usize == seed_len && seed.len() == seed_len =>
+    {
+      Ok(seed.to_vec())
+    }
+    _ => Err(ImportKeyError::ExpectedSeedPrivateKey),
+  }
+}
+
+fn spki_public_key(
+  data: &[u8],
+  oid: const_oid::ObjectIdentifier,
+) -> Result<Vec<u8>, ImportKeyError> {
+  let pk_info = spki::SubjectPublicKeyInfoRef::try_from(data)?;
+  if pk_info.algorithm.oid != oid {
+    return Err(ImportKeyError::UnsupportedAlgorithm);
+  }
+  if pk_info.algorithm.parameters.is_some() {
+    return Err(ImportKeyError::MalformedParameters);
+  }
+  pk_info
+    .subject_public_key
+    .as_bytes()
+    .map(|bytes| bytes.to_vec())
+    .ok_or(ImportKeyError::InvalidKeyData)
+}
+
+fn import_key_ml_kem(
+  key_data: KeyData,
+  parameter_set: MlKemParameterSet,
+) -> Result<ImportKeyResult, ImportKeyError> {
+  let ml_kem = MlKem::from(parameter_set);
+  let raw_data = match key_data {
+    KeyData::Spki(data) => {
+      let public_key = spki_public_key(&data, parameter_set.oid())?;
+      ml_kem
+        .check_public_key(&public_key)
+        .map_err(|_| ImportKeyError::InvalidMlKemPublicKey)?;
+      RustRawKeyData::Public(public_key.into())
+    }
+    KeyData::RawPublic(data) => {
+      ml_kem
+        .check_public_key(&data)
+        .map_err(|_| ImportKeyError::InvalidMlKemPublicKey)?;
+      RustRawKeyData::Public(data.to_vec().into())
+    }
+    KeyData::Pkcs8(data) => {
+      let seed = pkcs8_seed(&data, parameter_set.oid(), ml_kem::SEED_LEN)?;
+      RustRawKeyData::Private(seed.into())
+    }
+    KeyData::RawSeed(data) => {
+      if data.len() != ml_kem::SEED_LEN {
+        return Err(ImportKeyError::InvalidKeyData);
+      }
+      RustRawKeyData::Private(data.to_vec().into())
+    }
+    _ => return Err(SharedError::UnsupportedFormat.into()),
+  };
+  Ok(ImportKeyResult::MlKem { raw_data })
+}
+
+fn import_key_ml_dsa(
+  key_data: KeyData,
+  parameter_set: MlDsaParameterSet,
+) -> Result<ImportKeyResult, ImportKeyError> {
+  let ml_dsa = MlDsa::from(parameter_set);
+  let raw_data = match key_data {
+    KeyData::Spki(data) => {
+      let public_key = spki_public_key(&data, parameter_set.oid())?;
+      ml_dsa
+        .check_public_key(&public_key)
+        .map_err(|_| ImportKeyError::InvalidMlDsaPublicKey)?;
+      RustRawKeyData::Public(public_key.into())
+    }
+    KeyData::RawPublic(data) => {
+      ml_dsa
+        .check_public_key(&data)
+        .map_err(|_| ImportKeyError::InvalidMlDsaPublicKey)?;
+      RustRawKeyData::Public(data.to_vec().into())
+    }
+    KeyData::JwkPublicAkp { r#pub } => {
+      let public_key = BASE64_URL_SAFE_FORGIVING
+        .decode(r#pub)
+        .map_err(|_| ImportKeyError::InvalidKeyData)?;
+      ml_dsa
+        .check_public_key(&public_key)
+        .map_err(|_| ImportKeyError::InvalidMlDsaPublicKey)?;
+      RustRawKeyData::Public(public_key.into())
+    }
+    KeyData::Pkcs8(data) => {
+      let seed = pkcs8_seed(&data, parameter_set.oid(), ml_dsa::SEED_LEN)?;
+      RustRawKeyData::Private(seed.into())
+    }
+    KeyData::RawSeed(data) => {
+      if data.len() != ml_dsa::SEED_LEN {
+        return Err(ImportKeyError::InvalidKeyData);
+      }
+      RustRawKeyData::Private(data.to_vec().into())
+    }
+    KeyData::JwkPrivateAkp { r#pub, r#priv } => {
+      let seed = BASE64_URL_SAFE_FORGIVING
+        .decode(r#priv)
+        .map_err(|_| ImportKeyError::InvalidJWKPrivateKey)?;
+      let public_key = BASE64_URL_SAFE_FORGIVING
+        .decode(r#pub)
+        .map_err(|_| ImportKeyError::InvalidJWKPrivateKey)?;
+      // The public key must be the one derived from the seed.
+      if ml_dsa.public_key_from_seed(&seed).ok() != Some(public_key) {
+        return Err(ImportKeyError::InvalidJWKPrivateKey);
+      }
+      RustRawKeyData::Private(seed.into())
+    }
+    _ => return Err(SharedError::UnsupportedFormat.into()),
+  };
+  Ok(ImportKeyResult::MlDsa { raw_data })
+}
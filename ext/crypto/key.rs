@@ -82,6 +82,42 @@ impl From<CryptoHash> for &'static digest::Algorithm {
   }
 }
 
+/// The algorithms accepted by `SubtleCrypto.digest`.
+#[derive(Deserialize)]
+#[serde(tag = "name")]
+pub enum DigestAlgorithm {
+  #[serde(rename = "SHA-1")]
+  Sha1,
+  #[serde(rename = "SHA-256")]
+  Sha256,
+  #[serde(rename = "SHA-384")]
+  Sha384,
+  #[serde(rename = "SHA-512")]
+  Sha512,
+  #[serde(rename = "SHA3-256")]
+  Sha3_256,
+  #[serde(rename = "SHA3-384")]
+  Sha3_384,
+  #[serde(rename = "SHA3-512")]
+  Sha3_512,
+  #[serde(rename = "cSHAKE128", rename_all = "camelCase")]
+  CShake128 {
+    length: usize,
+    #[serde(with = "serde_bytes")]
+    function_name: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    customization: Vec<u8>,
+  },
+  #[serde(rename = "cSHAKE256", rename_all = "camelCase")]
+  CShake256 {
+    length: usize,
+    #[serde(with = "serde_bytes")]
+    function_name: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    customization: Vec<u8>,
+  },
+}
+
 pub struct HkdfOutput<T>(pub T);
 
 impl hkdf::KeyType for HkdfOutput<usize> {
@@ -116,4 +152,20 @@ pub enum Algorithm {
   Pbkdf2,
   #[serde(rename = "HKDF")]
   Hkdf,
+  #[serde(rename = "KMAC128")]
+  Kmac128,
+  #[serde(rename = "KMAC256")]
+  Kmac256,
+  #[serde(rename = "ML-DSA-44")]
+  MlDsa44,
+  #[serde(rename = "ML-DSA-65")]
+  MlDsa65,
+  #[serde(rename = "ML-DSA-87")]
+  MlDsa87,
+  #[serde(rename = "Argon2d")]
+  Argon2d,
+  #[serde(rename = "Argon2i")]
+  Argon2i,
+  #[serde(rename = "Argon2id")]
+  Argon2id,
 }
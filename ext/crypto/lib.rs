@@ -17,6 +17,8 @@ use deno_core::OpState;
 use deno_core::convert::Uint8Array;
 use deno_core::op2;
 use deno_core::unsync::spawn_blocking;
+use deno_crypto_provider::ml_dsa::MU_LEN;
+use deno_crypto_provider::ml_dsa::MlDsa;
 use deno_error::JsErrorBox;
 use p256::ecdsa::Signature as P256Signature;
 use p256::ecdsa::SigningKey as P256SigningKey;
@@ -57,6 +59,8 @@ mod export_key;
 mod generate_key;
 mod import_key;
 mod key;
+mod ml_kem;
+mod shake;
 mod shared;
 mod x25519;
 mod x448;
@@ -75,7 +79,10 @@ pub use crate::import_key::op_crypto_import_key;
 use crate::key::Algorithm;
 use crate::key::CryptoHash;
 use crate::key::CryptoNamedCurve;
+use crate::key::DigestAlgorithm;
 use crate::key::HkdfOutput;
+pub use crate::ml_kem::MlKemError;
+use crate::shake::CShakeVariant;
 pub use crate::shared::SharedError;
 use crate::shared::V8RawKeyData;
 pub use crate::x448::X448Error;
@@ -120,6 +127,8 @@ deno_core::extension!(deno_crypto,
     ed25519::op_crypto_export_spki_ed25519,
     ed25519::op_crypto_export_pkcs8_ed25519,
     ed25519::op_crypto_jwk_x_ed25519,
+    ml_kem::op_crypto_encapsulate_ml_kem,
+    ml_kem::op_crypto_decapsulate_ml_kem,
   ],
   esm = [ "00_crypto.js" ],
   options = {
@@ -190,6 +199,18 @@ pub enum CryptoError {
   #[class(type)]
   #[error("Missing argument info")]
   MissingArgumentInfo,
+  #[class(type)]
+  #[error("Missing argument length")]
+  MissingArgumentLength,
+  #[class("DOMExceptionOperationError")]
+  #[error("The ML-DSA context must not be longer than 255 bytes")]
+  MlDsaContextTooLong,
+  #[class("DOMExceptionOperationError")]
+  #[error(transparent)]
+  Argon2(#[from] argon2::Error),
+  #[class("DOMExceptionOperationError")]
+  #[error("{0}")]
+  Provider(&'static str),
   #[class("DOMExceptionOperationError")]
   #[error("The length provided for HKDF is too large")]
   HKDFLengthTooLarge,
@@ -291,6 +312,13 @@ pub struct SignArg {
   hash: Option<CryptoHash>,
   #[from_v8(serde)]
   named_curve: Option<CryptoNamedCurve>,
+  // KMAC
+  length: Option<usize>,
+  #[from_v8(serde)]
+  customization: Option<JsBuffer>,
+  // ML-DSA
+  #[from_v8(serde)]
+  context: Option<JsBuffer>,
 }
 
 #[op2]
@@ -405,6 +433,22 @@ pub async fn op_crypto_sign_key(
         let signature = aws_lc_rs::hmac::sign(&key, data);
         signature.as_ref().to_vec()
       }
+      Algorithm::Kmac128 | Algorithm::Kmac256 => kmac(
+        algorithm,
+        &args.key.data,
+        args.length,
+        args.customization.as_deref(),
+        data,
+      )?,
+      Algorithm::MlDsa44 | Algorithm::MlDsa65 | Algorithm::MlDsa87 => {
+        let ml_dsa = ml_dsa_parameter_set(algorithm);
+        let seed = &*args.key.data;
+        let public_key = ml_dsa
+          .public_key_from_seed(seed)
+          .map_err(CryptoError::Provider)?;
+        let mu = ml_dsa_mu(&public_key, args.context.as_deref(), data)?;
+        ml_dsa.sign_mu(seed, &mu).map_err(CryptoError::Provider)?
+      }
       _ => return Err(CryptoError::UnsupportedAlgorithm),
     };
 
@@ -425,6 +469,13 @@ pub struct VerifyArg {
   signature: Uint8Array,
   #[from_v8(serde)]
   named_curve: Option<CryptoNamedCurve>,
+  // KMAC
+  length: Option<usize>,
+  #[from_v8(serde)]
+  customization: Option<JsBuffer>,
+  // ML-DSA
+  #[from_v8(serde)]
+  context: Option<JsBuffer>,
 }
 
 #[op2]
@@ -499,6 +550,28 @@ pub async fn op_crypto_verify_key(
         let key = HmacKey::new(hash, &args.key.data);
         aws_lc_rs::hmac::verify(&key, data, &args.signature).is_ok()
       }
+      Algorithm::Kmac128 | Algorithm::Kmac256 => {
+        let expected = kmac(
+          algorithm,
+          &args.key.data,
+          args.length,
+          args.customization.as_deref(),
+          data,
+        )?;
+        aws_lc_rs::constant_time::verify_slices_are_equal(
+          &expected,
+          &args.signature,
+        )
+        .is_ok()
+      }
+      Algorithm::MlDsa44 | Algorithm::MlDsa65 | Algorithm::MlDsa87 => {
+        let ml_dsa = ml_dsa_parameter_set(algorithm);
+        let public_key = &*args.key.data;
+        let mu = ml_dsa_mu(public_key, args.context.as_deref(), data)?;
+        ml_dsa
+          .verify_mu(public_key, &mu, &args.signature)
+          .map_err(CryptoError::Provider)?
+      }
       Algorithm::Ecdsa => {
         let hash = args.hash.ok_or_else(|| CryptoError::MissingArgumentHash)?;
         let named_curve =
@@ -588,6 +661,15 @@ pub struct DeriveKeyArg {
   // HKDF
   #[from_v8(serde)]
   info: Option<JsBuffer>,
+  // Argon2
+  parallelism: Option<u32>,
+  memory: Option<u32>,
+  passes: Option<u32>,
+  version: Option<u32>,
+  #[from_v8(serde)]
+  secret_value: Option<JsBuffer>,
+  #[from_v8(serde)]
+  associated_data: Option<JsBuffer>,
 }
 
 #[op2]
@@ -726,12 +808,82 @@ pub async fn op_crypto_derive_bits(
         okm.fill(&mut r)?;
         Ok(r.into())
       }
+      Algorithm::Argon2d | Algorithm::Argon2i | Algorithm::Argon2id => {
+        let zero_copy = zero_copy.ok_or_else(JsErrorBox::not_supported)?;
+        let nonce = &*zero_copy;
+        let algorithm = match algorithm {
+          Algorithm::Argon2d => argon2::Algorithm::Argon2d,
+          Algorithm::Argon2i => argon2::Algorithm::Argon2i,
+          _ => argon2::Algorithm::Argon2id,
+        };
+        // The caller must validate that the version is 0x13.
+        let version = argon2::Version::try_from(args.version.unwrap_or(0x13))?;
+        let associated_data = argon2::AssociatedData::new(
+          args.associated_data.as_deref().unwrap_or_default(),
+        )?;
+
+        let params = argon2::ParamsBuilder::new()
+          .m_cost(args.memory.ok_or_else(JsErrorBox::not_supported)?)
+          .t_cost(args.passes.ok_or_else(JsErrorBox::not_supported)?)
+          .p_cost(args.parallelism.ok_or_else(JsErrorBox::not_supported)?)
+          .data(associated_data)
+          .output_len(args.length / 8)
+          .build()?;
+        let secret = args.secret_value.as_deref().unwrap_or_default();
+        let argon2 =
+          argon2::Argon2::new_with_secret(secret, algorithm, version, params)?;
+
+        let mut out = vec![0; args.length / 8];
+        argon2.hash_password_into(&args.key.data, nonce, &mut out)?;
+        Ok(out.into())
+      }
       _ => Err(CryptoError::UnsupportedAlgorithm),
     }
   })
   .await?
 }
 
+fn kmac(
+  algorithm: Algorithm,
+  key: &[u8],
+  length: Option<usize>,
+  customization: Option<&[u8]>,
+  data: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+  let variant = match algorithm {
+    Algorithm::Kmac128 => CShakeVariant::CShake128,
+    _ => CShakeVariant::CShake256,
+  };
+  let length = length.ok_or(CryptoError::MissingArgumentLength)?;
+  Ok(shake::kmac(
+    variant,
+    key,
+    customization.unwrap_or_default(),
+    data,
+    length / 8,
+  ))
+}
+
+fn ml_dsa_parameter_set(algorithm: Algorithm) -> MlDsa {
+  match algorithm {
+    Algorithm::MlDsa44 => MlDsa::MlDsa44,
+    Algorithm::MlDsa65 => MlDsa::MlDsa65,
+    _ => MlDsa::MlDsa87,
+  }
+}
+
+fn ml_dsa_mu(
+  public_key: &[u8],
+  context: Option<&[u8]>,
+  data: &[u8],
+) -> Result<[u8; MU_LEN], CryptoError> {
+  let context = context.unwrap_or_default();
+  if context.len() > 255 {
+    return Err(CryptoError::MlDsaContextTooLong);
+  }
+  Ok(shake::ml_dsa_mu(public_key, context, data))
+}
+
 fn read_rsa_public_key(key_data: KeyData) -> Result<RsaPublicKey, CryptoError> {
   let public_key = match key_data.r#type {
     KeyType::Private => {
@@ -765,14 +917,43 @@ pub fn op_crypto_random_uuid(
 
 #[op2]
 pub async fn op_crypto_subtle_digest(
-  #[serde] algorithm: CryptoHash,
+  #[serde] algorithm: DigestAlgorithm,
   #[buffer] data: JsBuffer,
 ) -> Result<Uint8Array, CryptoError> {
   let output = spawn_blocking(move || {
-    digest::digest(algorithm.into(), &data)
-      .as_ref()
-      .to_vec()
-      .into()
+    let hash = |algorithm| digest::digest(algorithm, &data).as_ref().to_vec();
+    match algorithm {
+      DigestAlgorithm::Sha1 => hash(&digest::SHA1_FOR_LEGACY_USE_ONLY),
+      DigestAlgorithm::Sha256 => hash(&digest::SHA256),
+      DigestAlgorithm::Sha384 => hash(&digest::SHA384),
+      DigestAlgorithm::Sha512 => hash(&digest::SHA512),
+      DigestAlgorithm::Sha3_256 => hash(&digest::SHA3_256),
+      DigestAlgorithm::Sha3_384 => hash(&digest::SHA3_384),
+      DigestAlgorithm::Sha3_512 => hash(&digest::SHA3_512),
+      DigestAlgorithm::CShake128 {
+        length,
+        function_name,
+        customization,
+      } => shake::cshake(
+        CShakeVariant::CShake128,
+        &function_name,
+        &customization,
+        &[&data],
+        length / 8,
+      ),
+      DigestAlgorithm::CShake256 {
+        length,
+        function_name,
+        customization,
+      } => shake::cshake(
+        CShakeVariant::CShake256,
+        &function_name,
+        &customization,
+        &[&data],
+        length / 8,
+      ),
+    }
+    .into()
   })
   .await?;
 
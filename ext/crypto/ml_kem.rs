@@ -0,0 +1,50 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+use deno_core::ToJsBuffer;
+use deno_core::convert::Uint8Array;
+use deno_core::op2;
+use deno_crypto_provider::ml_kem::MlKem;
+use serde::Serialize;
+
+use crate::shared::MlKemParameterSet;
+
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+pub enum MlKemError {
+  #[class("DOMExceptionOperationError")]
+  #[error("{0}")]
+  Failed(&'static str),
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncapsulatedBits {
+  ciphertext: ToJsBuffer,
+  shared_key: ToJsBuffer,
+}
+
+#[op2]
+#[serde]
+pub fn op_crypto_encapsulate_ml_kem(
+  #[serde] parameter_set: MlKemParameterSet,
+  #[buffer] public_key: &[u8],
+) -> Result<EncapsulatedBits, MlKemError> {
+  let (ciphertext, shared_key) = MlKem::from(parameter_set)
+    .encapsulate(public_key)
+    .map_err(MlKemError::Failed)?;
+  Ok(EncapsulatedBits {
+    ciphertext: ciphertext.into(),
+    shared_key: shared_key.into(),
+  })
+}
+
+#[op2]
+pub fn op_crypto_decapsulate_ml_kem(
+  #[serde] parameter_set: MlKemParameterSet,
+  #[buffer] seed: &[u8],
+  #[buffer] ciphertext: &[u8],
+) -> Result<Uint8Array, MlKemError> {
+  let shared_key = MlKem::from(parameter_set)
+    .decapsulate(seed, ciphertext)
+    .map_err(MlKemError::Failed)?;
+  Ok(shared_key.into())
+}
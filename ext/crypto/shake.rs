@@ -0,0 +1,153 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+//! Keccak based primitives from NIST SP 800-185 (cSHAKE and KMAC), and the
+//! ML-DSA message representative built on SHAKE256.
+
+use deno_crypto_provider::ml_dsa::MU_LEN;
+use sha3::CShake128;
+use sha3::CShake128Core;
+use sha3::CShake256;
+use sha3::CShake256Core;
+use sha3::Shake256;
+use sha3::digest::ExtendableOutput;
+use sha3::digest::Update;
+use sha3::digest::XofReader;
+
+#[derive(Clone, Copy)]
+pub enum CShakeVariant {
+  CShake128,
+  CShake256,
+}
+
+impl CShakeVariant {
+  /// The rate of the underlying Keccak sponge in bytes.
+  fn rate(self) -> usize {
+    match self {
+      CShakeVariant::CShake128 => 168,
+      CShakeVariant::CShake256 => 136,
+    }
+  }
+}
+
+pub fn cshake(
+  variant: CShakeVariant,
+  function_name: &[u8],
+  customization: &[u8],
+  data: &[&[u8]],
+  output_len: usize,
+) -> Vec<u8> {
+  let mut out = vec![0; output_len];
+  match variant {
+    CShakeVariant::CShake128 => {
+      let mut hasher = CShake128::from_core(
+        CShake128Core::new_with_function_name(function_name, customization),
+      );
+      for chunk in data {
+        hasher.update(chunk);
+      }
+      hasher.finalize_xof().read(&mut out);
+    }
+    CShakeVariant::CShake256 => {
+      let mut hasher = CShake256::from_core(
+        CShake256Core::new_with_function_name(function_name, customization),
+      );
+      for chunk in data {
+        hasher.update(chunk);
+      }
+      hasher.finalize_xof().read(&mut out);
+    }
+  }
+  out
+}
+
+/// KMAC128 or KMAC256 (SP 800-185 section 4) of `data` with an output of
+/// `output_len` bytes.
+pub fn kmac(
+  variant: CShakeVariant,
+  key: &[u8],
+  customization: &[u8],
+  data: &[u8],
+  output_len: usize,
+) -> Vec<u8> {
+  let mut padded_key = left_encode(variant.rate() as u64);
+  padded_key.extend(left_encode(key.len() as u64 * 8));
+  padded_key.extend_from_slice(key);
+  let padding = padded_key.len().next_multiple_of(variant.rate());
+  padded_key.resize(padding, 0);
+
+  cshake(
+    variant,
+    b"KMAC",
+    customization,
+    &[&padded_key, data, &right_encode(output_len as u64 * 8)],
+    output_len,
+  )
+}
+
+/// The message representative `mu` of FIPS 204 for the pure ML-DSA variant.
+pub fn ml_dsa_mu(
+  public_key: &[u8],
+  context: &[u8],
+  message: &[u8],
+) -> [u8; MU_LEN] {
+  let mut tr = [0; 64];
+  let mut hasher = Shake256::default();
+  hasher.update(public_key);
+  hasher.finalize_xof().read(&mut tr);
+
+  let mut mu = [0; MU_LEN];
+  let mut hasher = Shake256::default();
+  hasher.update(&tr);
+  hasher.update(&[0, context.len() as u8]);
+  hasher.update(context);
+  hasher.update(message);
+  hasher.finalize_xof().read(&mut mu);
+  mu
+}
+
+fn left_encode(value: u64) -> Vec<u8> {
+  let bytes = value.to_be_bytes();
+  let skip = bytes.iter().take_while(|b| **b == 0).count().min(7);
+  let mut out = vec![(8 - skip) as u8];
+  out.extend_from_slice(&bytes[skip..]);
+  out
+}
+
+fn right_encode(value: u64) -> Vec<u8> {
+  let bytes = value.to_be_bytes();
+  let skip = bytes.iter().take_while(|b| **b == 0).count().min(7);
+  let mut out = bytes[skip..].to_vec();
+  out.push((8 - skip) as u8);
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn hex(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{b:02x}")).collect()
+  }
+
+  #[test]
+  fn kmac_sp800_185_samples() {
+    let key = (0x40..0x60).collect::<Vec<u8>>();
+    let data = [0, 1, 2, 3];
+    // KMAC_samples.pdf, sample #1 and #4.
+    assert_eq!(
+      hex(&kmac(CShakeVariant::CShake128, &key, b"", &data, 32)),
+      "e5780b0d3ea6f7d3a429c5706aa43a00fadbd7d49628839e3187243f456ee14e"
+    );
+    assert_eq!(
+      hex(&kmac(
+        CShakeVariant::CShake256,
+        &key,
+        b"My Tagged Application",
+        &data,
+        64
+      )),
+      "20c570c31346f703c9ac36c61c03cb64c3970d0cfc787e9b79599d273a68d2f7\
+       f69d4cc3de9d104a351689f27cf6f5951f0103f33f4f24871024d9c27773a8dd"
+    );
+  }
+}
@@ -4,6 +4,8 @@ use std::borrow::Cow;
 
 use deno_core::JsBuffer;
 use deno_core::ToJsBuffer;
+use deno_crypto_provider::ml_dsa::MlDsa;
+use deno_crypto_provider::ml_kem::MlKem;
 use elliptic_curve::sec1::ToEncodedPoint;
 use p256::pkcs8::DecodePrivateKey;
 use rsa::RsaPrivateKey;
@@ -22,6 +24,20 @@ pub const ID_SECP384R1_OID: const_oid::ObjectIdentifier =
 pub const ID_SECP521R1_OID: const_oid::ObjectIdentifier =
   const_oid::ObjectIdentifier::new_unwrap("1.3.132.0.35");
 
+pub const ID_ALG_ML_KEM_512_OID: const_oid::ObjectIdentifier =
+  const_oid::ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.4.1");
+pub const ID_ALG_ML_KEM_768_OID: const_oid::ObjectIdentifier =
+  const_oid::ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.4.2");
+pub const ID_ALG_ML_KEM_1024_OID: const_oid::ObjectIdentifier =
+  const_oid::ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.4.3");
+
+pub const ID_ML_DSA_44_OID: const_oid::ObjectIdentifier =
+  const_oid::ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.17");
+pub const ID_ML_DSA_65_OID: const_oid::ObjectIdentifier =
+  const_oid::ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.18");
+pub const ID_ML_DSA_87_OID: const_oid::ObjectIdentifier =
+  const_oid::ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.19");
+
 #[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq)]
 pub enum ShaHash {
   #[serde(rename = "SHA-1")]
@@ -50,6 +66,66 @@ pub enum EcNamedCurve {
   P521,
 }
 
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq)]
+pub enum MlKemParameterSet {
+  #[serde(rename = "ML-KEM-512")]
+  MlKem512,
+  #[serde(rename = "ML-KEM-768")]
+  MlKem768,
+  #[serde(rename = "ML-KEM-1024")]
+  MlKem1024,
+}
+
+impl MlKemParameterSet {
+  pub fn oid(self) -> const_oid::ObjectIdentifier {
+    match self {
+      MlKemParameterSet::MlKem512 => ID_ALG_ML_KEM_512_OID,
+      MlKemParameterSet::MlKem768 => ID_ALG_ML_KEM_768_OID,
+      MlKemParameterSet::MlKem1024 => ID_ALG_ML_KEM_1024_OID,
+    }
+  }
+}
+
+impl From<MlKemParameterSet> for MlKem {
+  fn from(parameter_set: MlKemParameterSet) -> MlKem {
+    match parameter_set {
+      MlKemParameterSet::MlKem512 => MlKem::MlKem512,
+      MlKemParameterSet::MlKem768 => MlKem::MlKem768,
+      MlKemParameterSet::MlKem1024 => MlKem::MlKem1024,
+    }
+  }
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq)]
+pub enum MlDsaParameterSet {
+  #[serde(rename = "ML-DSA-44")]
+  MlDsa44,
+  #[serde(rename = "ML-DSA-65")]
+  MlDsa65,
+  #[serde(rename = "ML-DSA-87")]
+  MlDsa87,
+}
+
+impl MlDsaParameterSet {
+  pub fn oid(self) -> const_oid::ObjectIdentifier {
+    match self {
+      MlDsaParameterSet::MlDsa44 => ID_ML_DSA_44_OID,
+      MlDsaParameterSet::MlDsa65 => ID_ML_DSA_65_OID,
+      MlDsaParameterSet::MlDsa87 => ID_ML_DSA_87_OID,
+    }
+  }
+}
+
+impl From<MlDsaParameterSet> for MlDsa {
+  fn from(parameter_set: MlDsaParameterSet) -> MlDsa {
+    match parameter_set {
+      MlDsaParameterSet::MlDsa44 => MlDsa::MlDsa44,
+      MlDsaParameterSet::MlDsa65 => MlDsa::MlDsa65,
+      MlDsaParameterSet::MlDsa87 => MlDsa::MlDsa87,
+    }
+  }
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "lowercase", tag = "type", content = "data")]
 pub enum V8RawKeyData {
@@ -186,6 +262,13 @@ impl V8RawKeyData {
     }
   }
 
+  pub fn as_private_key(&self) -> Result<&[u8], SharedError> {
+    match self {
+      V8RawKeyData::Private(data) => Ok(data),
+      _ => Err(SharedError::ExpectedPrivateKey),
+    }
+  }
+
   pub fn as_ec_private_key(&self) -> Result<&[u8], SharedError> {
     match self {
       V8RawKeyData::Private(data) => Ok(data),
@@ -19,11 +19,26 @@ const {
   JSONStringify,
   ObjectDefineProperty,
   ObjectHasOwn,
+  SafeMap,
   StringPrototypeStartsWith,
   SymbolDispose,
   TypeError,
 } = primordials;
 
+/**
+ * The HTTP caches of clients created with the `cache` option, by resource id.
+ * The store is created on first use by `ext:deno_fetch/24_http_cache.js`.
+ * @type {Map<number, { options: "memory" | { name: string }, store: object | null }>}
+ */
+const httpCaches = new SafeMap();
+
+/**
+ * @param {number} rid
+ */
+function getHttpCache(rid) {
+  return httpCaches.get(rid);
+}
+
 /**
  * @param {Deno.CreateHttpClientOptions} options
  * @returns {HttpClient}
@@ -91,13 +106,31 @@ function createHttpClient(options) {
       options.proxy.transport = "http";
     }
   }
+  if (options.cache !== undefined) {
+    if (
+      options.cache !== "memory" &&
+      (typeof options.cache !== "object" || options.cache === null ||
+        typeof options.cache.name !== "string")
+    ) {
+      throw new TypeError(
+        `Invalid value for 'cache' option: expected "memory" or { name: string }`,
+      );
+    }
+  }
   const keyPair = loadTlsKeyPair("Deno.createHttpClient", options);
-  return new HttpClient(
+  const client = new HttpClient(
     op_fetch_custom_client(
       options,
       keyPair,
     ),
   );
+  if (options.cache !== undefined) {
+    httpCaches.set(client[internalRidSymbol], {
+      options: options.cache,
+      store: null,
+    });
+  }
+  return client;
 }
 
 class HttpClient {
@@ -116,13 +149,15 @@ class HttpClient {
   }
 
   close() {
+    httpCaches.delete(this.#rid);
     core.close(this.#rid);
   }
 
   [SymbolDispose]() {
+    httpCaches.delete(this.#rid);
     core.tryClose(this.#rid);
   }
 }
 const HttpClientPrototype = HttpClient.prototype;
 
-export { createHttpClient, HttpClient, HttpClientPrototype };
+export { createHttpClient, getHttpCache, HttpClient, HttpClientPrototype };
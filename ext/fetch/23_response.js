@@ -80,6 +80,7 @@ const webidlConvertersHeadersInit = webidl.converters["HeadersInit"];
  * @property {[string, string][]} headerList
  * @property {null | typeof __window.bootstrap.fetchBody.InnerBody} body
  * @property {boolean} aborted
+ * @property {string | null} [protocol]
  * @property {string} [error]
  */
 
@@ -124,6 +125,7 @@ function cloneInnerResponse(response) {
     urlList,
     status: response.status,
     statusMessage: response.statusMessage,
+    protocol: response.protocol,
     aborted: response.aborted,
     url() {
       if (this.urlList.length == 0) return null;
@@ -392,6 +394,14 @@ class Response {
     return this[_response].statusMessage;
   }
 
+  /**
+   * @returns {string | null}
+   */
+  get protocol() {
+    webidl.assertBranded(this, ResponsePrototype);
+    return this[_response].protocol ?? null;
+  }
+
   /**
    * @returns {Headers}
    */
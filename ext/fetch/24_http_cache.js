@@ -0,0 +1,820 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+// @ts-check
+/// <reference path="../../core/lib.deno_core.d.ts" />
+/// <reference path="../web/internal.d.ts" />
+/// <reference path="./internal.d.ts" />
+/// <reference lib="esnext" />
+
+// An HTTP cache for `fetch` (RFC 9111), enabled per client with
+// `Deno.createHttpClient({ cache })`. The cache is private: responses are
+// only reused by requests made with the same client.
+
+import { primordials } from "ext:core/mod.js";
+const {
+  ArrayPrototypeFilter,
+  ArrayPrototypeIncludes,
+  ArrayPrototypePush,
+  ArrayPrototypeSplice,
+  DateNow,
+  DateParse,
+  MathFloor,
+  MathMax,
+  NumberIsNaN,
+  NumberParseInt,
+  PromisePrototypeCatch,
+  PromisePrototypeThen,
+  RegExpPrototypeTest,
+  SafeArrayIterator,
+  SafeMap,
+  SafeMapIterator,
+  String,
+  StringPrototypeIndexOf,
+  StringPrototypeSlice,
+  StringPrototypeSplit,
+  StringPrototypeTrim,
+  TypedArrayPrototypeGetByteLength,
+  TypedArrayPrototypeSet,
+  TypedArrayPrototypeSlice,
+  TypeError,
+  Uint8Array,
+} = primordials;
+
+import { byteLowerCase } from "ext:deno_web/00_infra.js";
+import { getHeader } from "ext:deno_fetch/20_headers.js";
+import { InnerBody } from "ext:deno_fetch/22_body.js";
+import { getHttpCache } from "ext:deno_fetch/22_http_client.js";
+import { processUrlList, Request } from "ext:deno_fetch/23_request.js";
+import { Response, toInnerResponse } from "ext:deno_fetch/23_response.js";
+
+/**
+ * @typedef CacheEntry
+ * @property {number} status
+ * @property {string} statusMessage
+ * @property {[string, string][]} headerList
+ * @property {Uint8Array | null} body
+ * @property {number} requestTime
+ * @property {number} responseTime
+ */
+
+// https://www.rfc-editor.org/rfc/rfc9110#section-15.1
+const HEURISTICALLY_CACHEABLE_STATUSES = [
+  200,
+  203,
+  204,
+  300,
+  301,
+  308,
+  404,
+  405,
+  410,
+  414,
+  501,
+];
+
+const CONDITIONAL_HEADER_NAMES = [
+  "if-match",
+  "if-none-match",
+  "if-modified-since",
+  "if-unmodified-since",
+  "if-range",
+];
+
+// Header fields that are not updated from a 304 response.
+// https://www.rfc-editor.org/rfc/rfc9111#section-3.2
+const NOT_FRESHENED_HEADER_NAMES = [
+  "content-length",
+  "content-encoding",
+  "transfer-encoding",
+];
+
+// Stores when a response stored in Cache Storage was requested and received,
+// which is needed to calculate its age.
+const TIMING_HEADER_NAME = "x-deno-http-cache-timing";
+
+// Responses larger than this are not stored in memory.
+const MEMORY_STORE_MAX_ENTRY_SIZE = 8 * 1024 * 1024;
+// When the responses stored in memory are larger than this in total, the
+// least recently used ones are evicted.
+const MEMORY_STORE_MAX_SIZE = 64 * 1024 * 1024;
+
+/** @type {(() => CacheStorage) | null} */
+let getCacheStorage = null;
+
+/**
+ * Called by `ext:deno_cache` to make Cache Storage available as a backend.
+ * @param {() => CacheStorage} cacheStorage
+ */
+function setCacheStorage(cacheStorage) {
+  getCacheStorage = cacheStorage;
+}
+
+/**
+ * @param {[string, string][]} responseHeaderList
+ * @param {[string, string][]} storedRequestHeaderList
+ * @param {[string, string][]} requestHeaderList
+ * @returns {boolean}
+ */
+function varyHeaderMatches(
+  responseHeaderList,
+  storedRequestHeaderList,
+  requestHeaderList,
+) {
+  const vary = getHeader(responseHeaderList, "vary");
+  if (vary === null) return true;
+  const names = StringPrototypeSplit(vary, ",");
+  for (let i = 0; i < names.length; i++) {
+    const name = byteLowerCase(StringPrototypeTrim(names[i]));
+    if (name === "*") return false;
+    if (
+      getHeader(storedRequestHeaderList, name) !==
+        getHeader(requestHeaderList, name)
+    ) {
+      return false;
+    }
+  }
+  return true;
+}
+
+/**
+ * Approximates the memory used by a stored response.
+ * @param {[string, string][]} requestHeaderList
+ * @param {CacheEntry} entry
+ * @returns {number}
+ */
+function entrySize(requestHeaderList, entry) {
+  let size = entry.body === null
+    ? 0
+    : TypedArrayPrototypeGetByteLength(entry.body);
+  for (const { 0: name, 1: value } of new SafeArrayIterator(entry.headerList)) {
+    size += name.length + value.length;
+  }
+  for (
+    const { 0: name, 1: value } of new SafeArrayIterator(requestHeaderList)
+  ) {
+    size += name.length + value.length;
+  }
+  return size;
+}
+
+class MemoryStore {
+  /**
+   * Response bodies larger than this are not buffered to be stored.
+   * @type {number}
+   */
+  maxBodySize = MEMORY_STORE_MAX_ENTRY_SIZE;
+
+  /**
+   * Ordered from the least to the most recently used URL.
+   * @type {Map<string, { requestHeaderList: [string, string][], entry: CacheEntry, size: number }[]>}
+   */
+  #variants = new SafeMap();
+  /** @type {number} */
+  #size = 0;
+
+  /**
+   * @param {string} url
+   * @param {[string, string][]} requestHeaderList
+   * @returns {Promise<CacheEntry | null>}
+   */
+  // deno-lint-ignore require-await
+  async match(url, requestHeaderList) {
+    const variants = this.#variants.get(url);
+    if (variants === undefined) return null;
+    for (const variant of new SafeArrayIterator(variants)) {
+      if (
+        varyHeaderMatches(
+          variant.entry.headerList,
+          variant.requestHeaderList,
+          requestHeaderList,
+        )
+      ) {
+        // mark the URL as the most recently used one
+        this.#variants.delete(url);
+        this.#variants.set(url, variants);
+        return variant.entry;
+      }
+    }
+    return null;
+  }
+
+  /**
+   * @param {string} url
+   * @param {[string, string][]} requestHeaderList
+   * @param {CacheEntry} entry
+   */
+  // deno-lint-ignore require-await
+  async put(url, requestHeaderList, entry) {
+    const variants = this.#variants.get(url) ?? [];
+    this.#variants.delete(url);
+    for (let i = 0; i < variants.length; i++) {
+      if (
+        varyHeaderMatches(
+          variants[i].entry.headerList,
+          variants[i].requestHeaderList,
+          requestHeaderList,
+        )
+      ) {
+        this.#size -= variants[i].size;
+        ArrayPrototypeSplice(variants, i, 1);
+        i--;
+      }
+    }
+    const size = entrySize(requestHeaderList, entry);
+    if (size <= MEMORY_STORE_MAX_ENTRY_SIZE) {
+      ArrayPrototypePush(variants, { requestHeaderList, entry, size });
+      this.#size += size;
+    }
+    if (variants.length > 0) {
+      this.#variants.set(url, variants);
+    }
+    this.#evict();
+  }
+
+  /**
+   * @param {string} url
+   */
+  // deno-lint-ignore require-await
+  async delete(url) {
+    const variants = this.#variants.get(url);
+    if (variants === undefined) return;
+    this.#variants.delete(url);
+    for (const variant of new SafeArrayIterator(variants)) {
+      this.#size -= variant.size;
+    }
+  }
+
+  /**
+   * Evicts the least recently used URLs until the store fits in
+   * `MEMORY_STORE_MAX_SIZE`.
+   */
+  #evict() {
+    for (
+      const { 0: url, 1: variants } of new SafeMapIterator(this.#variants)
+    ) {
+      if (this.#size <= MEMORY_STORE_MAX_SIZE) return;
+      this.#variants.delete(url);
+      for (const variant of new SafeArrayIterator(variants)) {
+        this.#size -= variant.size;
+      }
+    }
+  }
+}
+
+class CacheStorageStore {
+  /**
+   * Response bodies larger than this are not buffered to be stored.
+   * @type {number}
+   */
+  maxBodySize = Infinity;
+
+  /** @type {string} */
+  #name;
+  /** @type {Cache | null} */
+  #cache = null;
+
+  /**
+   * @param {string} name
+   */
+  constructor(name) {
+    this.#name = name;
+  }
+
+  /**
+   * @returns {Promise<Cache>}
+   */
+  async #open() {
+    if (this.#cache === null) {
+      if (getCacheStorage === null) {
+        throw new TypeError("Cache Storage is not available");
+      }
+      this.#cache = await getCacheStorage().open(this.#name);
+    }
+    return this.#cache;
+  }
+
+  /**
+   * @param {string} url
+   * @param {[string, string][]} requestHeaderList
+   * @returns {Promise<CacheEntry | null>}
+   */
+  async match(url, requestHeaderList) {
+    const cache = await this.#open();
+    const response = await cache.match(
+      new Request(url, { headers: requestHeaderList }),
+    );
+    if (response === undefined) return null;
+    const inner = toInnerResponse(response);
+    const timing = getHeader(inner.headerList, TIMING_HEADER_NAME);
+    if (timing === null) return null;
+    const { 0: requestTime, 1: responseTime } = StringPrototypeSplit(
+      timing,
+      ",",
+    );
+    return {
+      status: inner.status,
+      statusMessage: inner.statusMessage,
+      headerList: ArrayPrototypeFilter(
+        inner.headerList,
+        (header) => byteLowerCase(header[0]) !== TIMING_HEADER_NAME,
+      ),
+      body: inner.body === null ? null : await inner.body.consume(),
+      requestTime: NumberParseInt(requestTime, 10),
+      responseTime: NumberParseInt(responseTime, 10),
+    };
+  }
+
+  /**
+   * @param {string} url
+   * @param {[string, string][]} requestHeaderList
+   * @param {CacheEntry} entry
+   */
+  async put(url, requestHeaderList, entry) {
+    const cache = await this.#open();
+    const headerList = [...new SafeArrayIterator(entry.headerList)];
+    ArrayPrototypePush(headerList, [
+      TIMING_HEADER_NAME,
+      `${entry.requestTime},${entry.responseTime}`,
+    ]);
+    await cache.put(
+      new Request(url, { headers: requestHeaderList }),
+      new Response(entry.body, {
+        status: entry.status,
+        statusText: entry.statusMessage,
+        headers: headerList,
+      }),
+    );
+  }
+
+  /**
+   * @param {string} url
+   */
+  async delete(url) {
+    const cache = await this.#open();
+    await cache.delete(url);
+  }
+}
+
+/**
+ * Returns the HTTP cache of the client, or `null` if it has none.
+ * @param {number | null} clientRid
+ * @returns {MemoryStore | CacheStorageStore | null}
+ */
+function getHttpCacheStore(clientRid) {
+  if (clientRid === null) return null;
+  const httpCache = getHttpCache(clientRid);
+  if (httpCache === undefined) return null;
+  if (httpCache.store === null) {
+    httpCache.store = httpCache.options === "memory"
+      ? new MemoryStore()
+      : new CacheStorageStore(httpCache.options.name);
+  }
+  return httpCache.store;
+}
+
+/**
+ * Parses the Cache-Control header fields into a map of lowercased directive
+ * names to their unquoted arguments (an empty string if there is none).
+ * @param {[string, string][]} headerList
+ * @returns {Map<string, string>}
+ */
+function parseCacheControl(headerList) {
+  const directives = new SafeMap();
+  const value = getHeader(headerList, "cache-control");
+  if (value === null) return directives;
+  const parts = StringPrototypeSplit(value, ",");
+  for (let i = 0; i < parts.length; i++) {
+    const part = parts[i];
+    const eq = StringPrototypeIndexOf(part, "=");
+    let name = part;
+    let argument = "";
+    if (eq !== -1) {
+      name = StringPrototypeSlice(part, 0, eq);
+      argument = StringPrototypeTrim(StringPrototypeSlice(part, eq + 1));
+      if (argument.length >= 2 && argument[0] === '"') {
+        argument = StringPrototypeSlice(argument, 1, -1);
+      }
+    }
+    name = byteLowerCase(StringPrototypeTrim(name));
+    // the first occurrence of a directive wins
+    if (name !== "" && !directives.has(name)) {
+      directives.set(name, argument);
+    }
+  }
+  return directives;
+}
+
+/**
+ * https://www.rfc-editor.org/rfc/rfc9111#section-1.2.2
+ * @param {string | null | undefined} value
+ * @returns {number | null} the delta in milliseconds
+ */
+function deltaSeconds(value) {
+  if (
+    value === null || value === undefined ||
+    !RegExpPrototypeTest(/^\d+$/, value)
+  ) {
+    return null;
+  }
+  return NumberParseInt(value, 10) * 1000;
+}
+
+/**
+ * @param {CacheEntry} entry
+ * @returns {number}
+ */
+function dateValue(entry) {
+  const date = getHeader(entry.headerList, "date");
+  const value = date === null ? NaN : DateParse(date);
+  return NumberIsNaN(value) ? entry.responseTime : value;
+}
+
+/**
+ * https://www.rfc-editor.org/rfc/rfc9111#section-4.2.1
+ * @param {CacheEntry} entry
+ * @param {Map<string, string>} responseDirectives
+ * @returns {number}
+ */
+function freshnessLifetime(entry, responseDirectives) {
+  const maxAge = deltaSeconds(responseDirectives.get("max-age"));
+  if (maxAge !== null) return maxAge;
+
+  const expires = getHeader(entry.headerList, "expires");
+  if (expires !== null) {
+    const expiresTime = DateParse(expires);
+    // an invalid date represents a time in the past
+    if (NumberIsNaN(expiresTime)) return 0;
+    return MathMax(0, expiresTime - dateValue(entry));
+  }
+
+  // https://www.rfc-editor.org/rfc/rfc9111#section-4.2.2
+  const lastModified = getHeader(entry.headerList, "last-modified");
+  if (
+    lastModified !== null &&
+    ArrayPrototypeIncludes(HEURISTICALLY_CACHEABLE_STATUSES, entry.status)
+  ) {
+    const lastModifiedTime = DateParse(lastModified);
+    if (!NumberIsNaN(lastModifiedTime)) {
+      return MathMax(0, (dateValue(entry) - lastModifiedTime) / 10);
+    }
+  }
+  return 0;
+}
+
+/**
+ * https://www.rfc-editor.org/rfc/rfc9111#section-4.2.3
+ * @param {CacheEntry} entry
+ * @param {number} now
+ * @returns {number}
+ */
+function currentAge(entry, now) {
+  const ageValue = deltaSeconds(getHeader(entry.headerList, "age")) ?? 0;
+  const apparentAge = MathMax(0, entry.responseTime - dateValue(entry));
+  const responseDelay = entry.responseTime - entry.requestTime;
+  const correctedInitialAge = MathMax(apparentAge, ageValue + responseDelay);
+  return correctedInitialAge + (now - entry.responseTime);
+}
+
+/**
+ * Whether the stored response can be used without validation, taking the
+ * request's `max-age`, `min-fresh` and `max-stale` directives into account.
+ * @param {number} age
+ * @param {number} lifetime
+ * @param {Map<string, string>} requestDirectives
+ * @param {Map<string, string>} responseDirectives
+ * @returns {boolean}
+ */
+function isFresh(age, lifetime, requestDirectives, responseDirectives) {
+  const maxAge = deltaSeconds(requestDirectives.get("max-age"));
+  if (maxAge !== null && age > maxAge) return false;
+  const minFresh = deltaSeconds(requestDirectives.get("min-fresh"));
+  if (minFresh !== null && lifetime - age < minFresh) return false;
+  if (age < lifetime) return true;
+
+  if (
+    requestDirectives.has("max-stale") &&
+    !responseDirectives.has("must-revalidate")
+  ) {
+    const maxStale = requestDirectives.get("max-stale");
+    if (maxStale === "") return true;
+    const maxStaleness = deltaSeconds(maxStale);
+    return maxStaleness !== null && age - lifetime <= maxStaleness;
+  }
+  return false;
+}
+
+/**
+ * https://www.rfc-editor.org/rfc/rfc9111#section-3
+ * @param {InnerRequest} req
+ * @param {InnerResponse} response
+ * @param {Map<string, string>} requestDirectives
+ * @returns {boolean}
+ */
+function isStorable(req, response, requestDirectives) {
+  if (req.method !== "GET" || response.type === "error") return false;
+  if (response.status === 206 || response.status === 304) return false;
+  if (requestDirectives.has("no-store")) return false;
+  const responseDirectives = parseCacheControl(response.headerList);
+  if (responseDirectives.has("no-store")) return false;
+  if (getHeader(response.headerList, "vary") === "*") return false;
+  return responseDirectives.has("max-age") ||
+    responseDirectives.has("public") ||
+    getHeader(response.headerList, "expires") !== null ||
+    ArrayPrototypeIncludes(HEURISTICALLY_CACHEABLE_STATUSES, response.status);
+}
+
+/**
+ * @param {InnerRequest} req
+ * @param {number} status
+ * @param {string} statusMessage
+ * @param {[string, string][]} headerList
+ * @param {Uint8Array | null} body
+ * @returns {InnerResponse}
+ */
+function newCacheResponse(req, status, statusMessage, headerList, body) {
+  processUrlList(req.urlList, req.urlListProcessed);
+  return {
+    headerList,
+    status,
+    body: body === null || req.method === "HEAD"
+      ? null
+      // copied so that consumers can't modify the stored body
+      : new InnerBody({
+        body: TypedArrayPrototypeSlice(body),
+        consumed: false,
+      }),
+    statusMessage,
+    protocol: null,
+    type: "basic",
+    aborted: false,
+    url() {
+      if (this.urlList.length == 0) return null;
+      return this.urlList[this.urlList.length - 1];
+    },
+    urlList: req.urlListProcessed,
+  };
+}
+
+/**
+ * @param {InnerRequest} req
+ * @param {CacheEntry} entry
+ * @param {number} age
+ * @returns {InnerResponse}
+ */
+function responseFromEntry(req, entry, age) {
+  const headerList = ArrayPrototypeFilter(
+    entry.headerList,
+    (header) => byteLowerCase(header[0]) !== "age",
+  );
+  ArrayPrototypePush(headerList, ["age", String(MathFloor(age / 1000))]);
+  return newCacheResponse(
+    req,
+    entry.status,
+    entry.statusMessage,
+    headerList,
+    entry.body,
+  );
+}
+
+/**
+ * Reads the body into a buffer. Once it is larger than `maxSize` bytes, the
+ * rest of the body is not read and `null` is returned.
+ * @param {InnerBody} body
+ * @param {number} maxSize
+ * @returns {Promise<Uint8Array | null>}
+ */
+async function readBodyUpTo(body, maxSize) {
+  const reader = body.stream.getReader();
+  /** @type {Uint8Array[]} */
+  const chunks = [];
+  let totalLength = 0;
+  while (true) {
+    const { value: chunk, done } = await reader.read();
+    if (done) break;
+    totalLength += TypedArrayPrototypeGetByteLength(chunk);
+    if (totalLength > maxSize) {
+      await reader.cancel();
+      return null;
+    }
+    ArrayPrototypePush(chunks, chunk);
+  }
+  const buffer = new Uint8Array(totalLength);
+  let offset = 0;
+  for (let i = 0; i < chunks.length; i++) {
+    TypedArrayPrototypeSet(buffer, chunks[i], offset);
+    offset += TypedArrayPrototypeGetByteLength(chunks[i]);
+  }
+  return buffer;
+}
+
+/**
+ * Stores the response if it is storable. The body is teed so that it can be
+ * buffered into the cache while it is read by the caller. Bodies larger than
+ * the store accepts are not buffered.
+ * @param {MemoryStore | CacheStorageStore} store
+ * @param {InnerRequest} req
+ * @param {InnerResponse} response
+ * @param {Map<string, string>} requestDirectives
+ * @param {number} requestTime
+ * @returns {InnerResponse}
+ */
+function storeResponse(store, req, response, requestDirectives, requestTime) {
+  if (!isStorable(req, response, requestDirectives)) return response;
+  const url = req.currentUrl();
+  const requestHeaderList = req.headerList;
+  const responseTime = DateNow();
+  /** @param {Uint8Array | null} body */
+  const put = (body) =>
+    store.put(url, requestHeaderList, {
+      status: response.status,
+      statusMessage: response.statusMessage,
+      headerList: response.headerList,
+      body,
+      requestTime,
+      responseTime,
+    });
+  const contentLength = getHeader(response.headerList, "content-length");
+  if (
+    contentLength !== null &&
+    NumberParseInt(contentLength, 10) > store.maxBodySize
+  ) {
+    return response;
+  }
+  const body = response.body === null ? null : response.body.clone();
+  // failing to store a response must not fail the request
+  PromisePrototypeCatch(
+    body === null ? put(null) : PromisePrototypeThen(
+      readBodyUpTo(body, store.maxBodySize),
+      (buffer) => buffer === null ? undefined : put(buffer),
+    ),
+    () => {},
+  );
+  return response;
+}
+
+/**
+ * Updates the stored response with the header fields of a 304 response.
+ * https://www.rfc-editor.org/rfc/rfc9111#section-3.2
+ * @param {CacheEntry} entry
+ * @param {[string, string][]} headerList
+ * @param {number} requestTime
+ * @returns {CacheEntry}
+ */
+function freshenEntry(entry, headerList, requestTime) {
+  const updatedNames = [];
+  for (const { 0: name } of new SafeArrayIterator(headerList)) {
+    const lowerName = byteLowerCase(name);
+    if (!ArrayPrototypeIncludes(NOT_FRESHENED_HEADER_NAMES, lowerName)) {
+      ArrayPrototypePush(updatedNames, lowerName);
+    }
+  }
+  const freshened = ArrayPrototypeFilter(
+    entry.headerList,
+    (header) => !ArrayPrototypeIncludes(updatedNames, byteLowerCase(header[0])),
+  );
+  for (const header of new SafeArrayIterator(headerList)) {
+    if (ArrayPrototypeIncludes(updatedNames, byteLowerCase(header[0]))) {
+      ArrayPrototypePush(freshened, header);
+    }
+  }
+  return {
+    status: entry.status,
+    statusMessage: entry.statusMessage,
+    headerList: freshened,
+    body: entry.body,
+    requestTime,
+    responseTime: DateNow(),
+  };
+}
+
+/**
+ * Sends a conditional request for the stored response, and returns either
+ * the freshened stored response or the new response.
+ * https://www.rfc-editor.org/rfc/rfc9111#section-4.3
+ * @param {MemoryStore | CacheStorageStore} store
+ * @param {InnerRequest} req
+ * @param {CacheEntry} entry
+ * @param {Map<string, string>} requestDirectives
+ * @param {(req: InnerRequest) => Promise<InnerResponse>} networkFetch
+ * @returns {Promise<InnerResponse>}
+ */
+async function revalidate(store, req, entry, requestDirectives, networkFetch) {
+  const etag = getHeader(entry.headerList, "etag");
+  const lastModified = getHeader(entry.headerList, "last-modified");
+  const requestTime = DateNow();
+  if (etag === null && lastModified === null) {
+    const response = await networkFetch(req);
+    return storeResponse(store, req, response, requestDirectives, requestTime);
+  }
+
+  const headerList = [...new SafeArrayIterator(req.headerList)];
+  if (etag !== null) {
+    ArrayPrototypePush(headerList, ["if-none-match", etag]);
+  }
+  if (lastModified !== null) {
+    ArrayPrototypePush(headerList, ["if-modified-since", lastModified]);
+  }
+  const response = await networkFetch({ ...req, headerList });
+  if (response.type === "error" || response.status !== 304) {
+    return storeResponse(store, req, response, requestDirectives, requestTime);
+  }
+
+  const freshened = freshenEntry(entry, response.headerList, requestTime);
+  await store.put(req.currentUrl(), req.headerList, freshened);
+  return responseFromEntry(req, freshened, currentAge(freshened, DateNow()));
+}
+
+/**
+ * Fetches the request through the client's HTTP cache.
+ * @param {MemoryStore | CacheStorageStore} store
+ * @param {InnerRequest} req
+ * @param {(req: InnerRequest) => Promise<InnerResponse>} networkFetch
+ * @returns {Promise<InnerResponse>}
+ */
+async function httpCacheFetch(store, req, networkFetch) {
+  const method = req.method;
+  if (method !== "GET" && method !== "HEAD") {
+    const response = await networkFetch(req);
+    // https://www.rfc-editor.org/rfc/rfc9111#section-4.4
+    if (
+      response.type !== "error" && method !== "OPTIONS" &&
+      method !== "TRACE" && response.status >= 200 && response.status < 400
+    ) {
+      await store.delete(req.currentUrl());
+    }
+    return response;
+  }
+
+  const requestDirectives = parseCacheControl(req.headerList);
+  for (const { 0: name } of new SafeArrayIterator(req.headerList)) {
+    const lowerName = byteLowerCase(name);
+    // requests with their own preconditions or ranges are passed through
+    if (
+      lowerName === "range" ||
+      ArrayPrototypeIncludes(CONDITIONAL_HEADER_NAMES, lowerName)
+    ) {
+      return networkFetch(req);
+    }
+  }
+  if (requestDirectives.has("no-store")) {
+    return networkFetch(req);
+  }
+
+  const entry = await store.match(req.currentUrl(), req.headerList);
+  if (entry !== null) {
+    const responseDirectives = parseCacheControl(entry.headerList);
+    const age = currentAge(entry, DateNow());
+    const lifetime = freshnessLifetime(entry, responseDirectives);
+    const noCache = requestDirectives.has("no-cache") ||
+      responseDirectives.has("no-cache") ||
+      (getHeader(req.headerList, "cache-control") === null &&
+        getHeader(req.headerList, "pragma") === "no-cache");
+
+    if (!noCache) {
+      if (isFresh(age, lifetime, requestDirectives, responseDirectives)) {
+        return responseFromEntry(req, entry, age);
+      }
+
+      // https://www.rfc-editor.org/rfc/rfc5861#section-3
+      const staleWhileRevalidate = deltaSeconds(
+        responseDirectives.get("stale-while-revalidate"),
+      );
+      if (
+        staleWhileRevalidate !== null &&
+        age < lifetime + staleWhileRevalidate &&
+        !requestDirectives.has("max-age") &&
+        !requestDirectives.has("min-fresh")
+      ) {
+        const revalidation = revalidate(
+          store,
+          { ...req, method: "GET" },
+          entry,
+          requestDirectives,
+          networkFetch,
+        );
+        PromisePrototypeCatch(
+          PromisePrototypeThen(revalidation, (response) => {
+            // the revalidated response is only used to update the cache
+            response.body?.cancel();
+          }),
+          () => {},
+        );
+        return responseFromEntry(req, entry, age);
+      }
+    }
+
+    if (requestDirectives.has("only-if-cached")) {
+      return newCacheResponse(req, 504, "Gateway Timeout", [], null);
+    }
+    return revalidate(store, req, entry, requestDirectives, networkFetch);
+  }
+
+  if (requestDirectives.has("only-if-cached")) {
+    return newCacheResponse(req, 504, "Gateway Timeout", [], null);
+  }
+  const requestTime = DateNow();
+  const response = await networkFetch(req);
+  return storeResponse(store, req, response, requestDirectives, requestTime);
+}
+
+export { getHttpCacheStore, httpCacheFetch, setCacheStorage };
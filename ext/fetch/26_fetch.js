@@ -49,6 +49,10 @@ import {
 } from "ext:deno_web/06_streams.js";
 import { extractBody, InnerBody } from "ext:deno_fetch/22_body.js";
 import { processUrlList, toInnerRequest } from "ext:deno_fetch/23_request.js";
+import {
+  getHttpCacheStore,
+  httpCacheFetch,
+} from "ext:deno_fetch/24_http_cache.js";
 import {
   abortedNetworkError,
   fromInnerResponse,
@@ -87,7 +91,7 @@ const REDIRECT_SENSITIVE_HEADER_NAMES = [
 
 /**
  * @param {number} rid
- * @returns {Promise<{ status: number, statusText: string, headers: [string, string][], url: string, responseRid: number, error: [string, string]? }>}
+ * @returns {Promise<{ status: number, statusText: string, headers: [string, string][], url: string, responseRid: number, protocol: string | null, error: [string, string]? }>}
  */
 function opFetchSend(rid) {
   return op_fetch_send(rid);
@@ -147,6 +151,47 @@ async function mainFetch(req, recursive, terminator) {
     };
   }
 
+  const httpCacheStore = getHttpCacheStore(req.clientRid);
+  const response = httpCacheStore !== null
+    ? await httpCacheFetch(
+      httpCacheStore,
+      req,
+      (req) => httpNetworkFetch(req, terminator),
+    )
+    : await httpNetworkFetch(req, terminator);
+  if (response.type === "error") return response;
+
+  if (redirectStatus(response.status)) {
+    switch (req.redirectMode) {
+      case "error":
+        response.body?.cancel();
+        return networkError(
+          "Encountered redirect while redirect mode is set to 'error'",
+        );
+      case "follow":
+        response.body?.cancel();
+        return httpRedirectFetch(req, response, terminator);
+      case "manual":
+        break;
+    }
+  }
+
+  if (recursive) return response;
+
+  if (response.urlList.length === 0) {
+    processUrlList(req.urlList, req.urlListProcessed);
+    response.urlList = [...new SafeArrayIterator(req.urlListProcessed)];
+  }
+
+  return response;
+}
+
+/**
+ * @param {InnerRequest} req
+ * @param {AbortSignal} terminator
+ * @returns {Promise<InnerResponse>}
+ */
+async function httpNetworkFetch(req, terminator) {
   /** @type {ReadableStream<Uint8Array> | Uint8Array | null} */
   let reqBody = null;
   let reqRid = null;
@@ -213,6 +258,7 @@ async function mainFetch(req, recursive, terminator) {
     status: resp.status,
     body: null,
     statusMessage: resp.statusText,
+    protocol: resp.protocol,
     type: "basic",
     url() {
       if (this.urlList.length == 0) return null;
@@ -220,22 +266,11 @@ async function mainFetch(req, recursive, terminator) {
     },
     urlList: req.urlListProcessed,
   };
-  if (redirectStatus(resp.status)) {
-    switch (req.redirectMode) {
-      case "error":
-        core.close(resp.responseRid);
-        return networkError(
-          "Encountered redirect while redirect mode is set to 'error'",
-        );
-      case "follow":
-        core.close(resp.responseRid);
-        return httpRedirectFetch(req, response, terminator);
-      case "manual":
-        break;
-    }
-  }
 
-  if (nullBodyStatus(response.status)) {
+  if (
+    nullBodyStatus(response.status) ||
+    (redirectStatus(response.status) && req.redirectMode !== "manual")
+  ) {
     core.close(resp.responseRid);
   } else {
     if (req.method === "HEAD" || req.method === "CONNECT") {
@@ -248,13 +283,6 @@ async function mainFetch(req, recursive, terminator) {
     }
   }
 
-  if (recursive) return response;
-
-  if (response.urlList.length === 0) {
-    processUrlList(req.urlList, req.urlListProcessed);
-    response.urlList = [...new SafeArrayIterator(req.urlListProcessed)];
-  }
-
   return response;
 }
 
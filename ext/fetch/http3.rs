@@ -0,0 +1,487 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+//! HTTP/3 support for fetch clients. Requests are sent over QUIC when the
+//! client forces HTTP/3, or when the origin advertised an `h3` alternative
+//! service in an earlier response.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::future::poll_fn;
+use std::net::IpAddr;
+use std::net::Ipv4Addr;
+use std::net::Ipv6Addr;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::task::Context;
+use std::task::Poll;
+use std::task::ready;
+use std::time::Duration;
+use std::time::Instant;
+
+use bytes::Buf;
+use bytes::Bytes;
+use deno_error::JsErrorBox;
+use deno_tls::rustls;
+use h3::client::RequestStream;
+use h3::client::SendRequest;
+use h3::error::Code;
+use http::HeaderMap;
+use http::Uri;
+use http::header::ALT_SVC;
+use http::header::CONNECTION;
+use http::header::TRANSFER_ENCODING;
+use http::header::UPGRADE;
+use hyper::body::Body;
+use hyper::body::Frame;
+use hyper_util::client::legacy::connect::dns::Name;
+use quinn::crypto::rustls::QuicClientConfig;
+use serde::Deserialize;
+use tower::Service;
+
+use crate::HttpClientCreateError;
+use crate::ReqBody;
+use crate::dns;
+
+/// When a fetch client sends requests over HTTP/3.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Http3Mode {
+  /// Only use HTTP/1.1 and HTTP/2.
+  #[default]
+  Disabled,
+  /// Use HTTP/3 for origins that advertised it with an `Alt-Svc` header.
+  AltSvc,
+  /// Use HTTP/3 for every `https:` request.
+  Force,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Http3Error {
+  #[error("failed to resolve {0}: {1}")]
+  Resolve(String, std::io::Error),
+  #[error(transparent)]
+  Endpoint(std::io::Error),
+  #[error(transparent)]
+  Connect(#[from] quinn::ConnectError),
+  #[error(transparent)]
+  Connection(#[from] quinn::ConnectionError),
+  #[error(transparent)]
+  H3Connection(#[from] h3::error::ConnectionError),
+  #[error(transparent)]
+  Stream(#[from] h3::error::StreamError),
+}
+
+struct AltSvcEntry {
+  port: u16,
+  expires: Instant,
+}
+
+/// An alternative service advertised in an `Alt-Svc` header.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum AltSvc {
+  /// `Alt-Svc: clear`, which invalidates all alternatives for the origin.
+  Clear,
+  /// An `h3` alternative on the same host.
+  H3 { port: u16, max_age: Duration },
+}
+
+/// Finds the first `h3` alternative on the same host in an `Alt-Svc`
+/// header value (RFC 7838).
+pub(crate) fn parse_alt_svc(value: &str) -> Option<AltSvc> {
+  for entry in value.split(',') {
+    let entry = entry.trim();
+    if entry == "clear" {
+      return Some(AltSvc::Clear);
+    }
+    let mut params = entry.split(';').map(str::trim);
+    let Some((protocol, authority)) =
+      params.next().and_then(|alt| alt.split_once('='))
+    else {
+      continue;
+    };
+    if protocol != "h3" {
+      continue;
+    }
+    // alternatives on a different host are ignored as the connection would
+    // have to be authenticated for the origin host
+    let Some(port) = authority
+      .trim_matches('"')
+      .strip_prefix(':')
+      .and_then(|port| port.parse::<u16>().ok())
+    else {
+      continue;
+    };
+    let max_age = params
+      .filter_map(|param| param.split_once('='))
+      .find(|(name, _)| name.trim() == "ma")
+      .and_then(|(_, value)| value.trim().parse::<u64>().ok())
+      .unwrap_or(86400);
+    return Some(AltSvc::H3 {
+      port,
+      max_age: Duration::from_secs(max_age),
+    });
+  }
+  None
+}
+
+/// Sends requests over HTTP/3, keeping one QUIC connection per origin.
+pub struct Http3Client {
+  mode: Http3Mode,
+  fallback: bool,
+  client_config: quinn::ClientConfig,
+  dns_resolver: dns::Resolver,
+  local_address: Option<IpAddr>,
+  /// Client endpoints for IPv4 and IPv6 peers, created on first use.
+  endpoints: Mutex<[Option<quinn::Endpoint>; 2]>,
+  connections: Mutex<HashMap<String, Http3Connection>>,
+  alt_svc: Mutex<HashMap<String, AltSvcEntry>>,
+}
+
+impl std::fmt::Debug for Http3Client {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("Http3Client")
+      .field("mode", &self.mode)
+      .field("fallback", &self.fallback)
+      .finish_non_exhaustive()
+  }
+}
+
+impl Http3Client {
+  pub fn new(
+    mode: Http3Mode,
+    fallback: bool,
+    mut tls_config: rustls::ClientConfig,
+    dns_resolver: dns::Resolver,
+    local_address: Option<IpAddr>,
+  ) -> Result<Self, HttpClientCreateError> {
+    tls_config.alpn_protocols = vec![b"h3".to_vec()];
+    let client_config = QuicClientConfig::try_from(tls_config)
+      .map_err(|_| HttpClientCreateError::Http3TlsVersion)?;
+    Ok(Self {
+      mode,
+      fallback,
+      client_config: quinn::ClientConfig::new(Arc::new(client_config)),
+      dns_resolver,
+      local_address,
+      endpoints: Mutex::new([None, None]),
+      connections: Mutex::new(HashMap::new()),
+      alt_svc: Mutex::new(HashMap::new()),
+    })
+  }
+
+  /// Whether requests may be sent over TCP when the QUIC connection could
+  /// not be established.
+  pub fn fallback(&self) -> bool {
+    self.fallback
+  }
+
+  /// Returns the host and UDP port to send the request to over HTTP/3, if
+  /// HTTP/3 should be used for this request.
+  fn target(&self, uri: &Uri) -> Option<(String, u16)> {
+    if uri.scheme_str() != Some("https") {
+      return None;
+    }
+    let host = uri.host()?.to_string();
+    match self.mode {
+      Http3Mode::Disabled => None,
+      Http3Mode::Force => Some((host, uri.port_u16().unwrap_or(443))),
+      Http3Mode::AltSvc => {
+        let origin = origin_key(uri)?;
+        let mut alt_svc = self.alt_svc.lock().unwrap();
+        let entry = alt_svc.get(&origin)?;
+        if entry.expires <= Instant::now() {
+          alt_svc.remove(&origin);
+          return None;
+        }
+        Some((host, entry.port))
+      }
+    }
+  }
+
+  /// Remembers the `h3` alternative advertised by a response received over
+  /// TCP so that later requests to the origin are upgraded. Alternatives
+  /// that `check_net` rejects are ignored.
+  pub fn record_alt_svc(
+    &self,
+    uri: &Uri,
+    headers: &HeaderMap,
+    mut check_net: impl FnMut(&str, u16) -> bool,
+  ) {
+    if self.mode != Http3Mode::AltSvc || uri.scheme_str() != Some("https") {
+      return;
+    }
+    let (Some(origin), Some(host)) = (origin_key(uri), uri.host()) else {
+      return;
+    };
+    let alt_svc = headers
+      .get_all(ALT_SVC)
+      .iter()
+      .filter_map(|value| value.to_str().ok())
+      .find_map(parse_alt_svc);
+    match alt_svc {
+      Some(AltSvc::H3 { port, max_age }) => {
+        if !check_net(host, port) {
+          return;
+        }
+        self.alt_svc.lock().unwrap().insert(
+          origin,
+          AltSvcEntry {
+            port,
+            expires: Instant::now() + max_age,
+          },
+        );
+      }
+      Some(AltSvc::Clear) => {
+        self.alt_svc.lock().unwrap().remove(&origin);
+      }
+      None => {}
+    }
+  }
+
+  /// Returns a connection to send the request on, or `None` if the request
+  /// should not be sent over HTTP/3.
+  pub async fn connect(
+    &self,
+    uri: &Uri,
+  ) -> Result<Option<Http3Connection>, Http3Error> {
+    let Some((host, port)) = self.target(uri) else {
+      return Ok(None);
+    };
+    let key = format!("{host}:{port}");
+    if let Some(connection) = self.connections.lock().unwrap().get(&key)
+      && connection.quic.close_reason().is_none()
+    {
+      return Ok(Some(connection.clone()));
+    }
+
+    let result = self.connect_inner(&host, port).await;
+    match result {
+      Ok(connection) => {
+        self
+          .connections
+          .lock()
+          .unwrap()
+          .insert(key, connection.clone());
+        Ok(Some(connection))
+      }
+      Err(err) => {
+        // don't keep trying an advertised alternative that is unreachable
+        if let Some(origin) = origin_key(uri) {
+          self.alt_svc.lock().unwrap().remove(&origin);
+        }
+        Err(err)
+      }
+    }
+  }
+
+  async fn connect_inner(
+    &self,
+    host: &str,
+    port: u16,
+  ) -> Result<Http3Connection, Http3Error> {
+    let server_name = host.trim_start_matches('[').trim_end_matches(']');
+    let addrs = match server_name.parse::<IpAddr>() {
+      Ok(ip) => vec![SocketAddr::new(ip, port)],
+      Err(_) => {
+        let name = Name::from_str(server_name).map_err(|err| {
+          Http3Error::Resolve(
+            server_name.to_string(),
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, err),
+          )
+        })?;
+        let mut resolver = self.dns_resolver.clone();
+        resolver
+          .call(name)
+          .await
+          .map_err(|err| Http3Error::Resolve(server_name.to_string(), err))?
+          .map(|addr| SocketAddr::new(addr.ip(), port))
+          .collect()
+      }
+    };
+
+    let mut last_err = Http3Error::Resolve(
+      server_name.to_string(),
+      std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        "no addresses were resolved",
+      ),
+    );
+    for addr in addrs {
+      match self.connect_addr(addr, server_name).await {
+        Ok(connection) => return Ok(connection),
+        Err(err) => last_err = err,
+      }
+    }
+    Err(last_err)
+  }
+
+  async fn connect_addr(
+    &self,
+    addr: SocketAddr,
+    server_name: &str,
+  ) -> Result<Http3Connection, Http3Error> {
+    let endpoint = self.endpoint(&addr)?;
+    let quic = endpoint
+      .connect_with(self.client_config.clone(), addr, server_name)?
+      .await?;
+    let (mut driver, send_request) =
+      h3::client::new(h3_quinn::Connection::new(quic.clone())).await?;
+    tokio::spawn(async move {
+      poll_fn(|cx| driver.poll_close(cx)).await;
+    });
+    Ok(Http3Connection { quic, send_request })
+  }
+
+  fn endpoint(&self, addr: &SocketAddr) -> Result<quinn::Endpoint, Http3Error> {
+    let index = addr.is_ipv6() as usize;
+    let mut endpoints = self.endpoints.lock().unwrap();
+    if let Some(endpoint) = &endpoints[index] {
+      return Ok(endpoint.clone());
+    }
+    let local_ip = match self.local_address {
+      Some(ip) => ip,
+      None if addr.is_ipv6() => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+      None => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+    };
+    let endpoint = quinn::Endpoint::client(SocketAddr::new(local_ip, 0))
+      .map_err(Http3Error::Endpoint)?;
+    endpoints[index] = Some(endpoint.clone());
+    Ok(endpoint)
+  }
+}
+
+/// The key the `Alt-Svc` cache is indexed by.
+fn origin_key(uri: &Uri) -> Option<String> {
+  let host = uri.host()?;
+  Some(format!("{host}:{}", uri.port_u16().unwrap_or(443)))
+}
+
+/// An established HTTP/3 connection to an origin.
+#[derive(Clone)]
+pub struct Http3Connection {
+  quic: quinn::Connection,
+  send_request: SendRequest<h3_quinn::OpenStreams, Bytes>,
+}
+
+impl Http3Connection {
+  async fn send(
+    mut self,
+    req: http::Request<ReqBody>,
+  ) -> Result<http::Response<Http3ResponseBody>, Http3Error> {
+    let (mut parts, mut body) = req.into_parts();
+    // connection-specific header fields are malformed in HTTP/3
+    for name in [CONNECTION, TRANSFER_ENCODING, UPGRADE] {
+      parts.headers.remove(name);
+    }
+    parts.headers.remove("keep-alive");
+    parts.headers.remove("proxy-connection");
+
+    let stream = self
+      .send_request
+      .send_request(http::Request::from_parts(parts, ()))
+      .await?;
+    let (mut send, mut recv) = stream.split();
+
+    // The body is sent concurrently with receiving the response, as servers
+    // may respond before the whole request body was read.
+    tokio::spawn(async move {
+      let _ = send_body(&mut send, &mut body).await;
+    });
+
+    let response = recv.recv_response().await?;
+    Ok(response.map(|()| Http3ResponseBody {
+      stream: recv,
+      data_done: false,
+      trailers_done: false,
+    }))
+  }
+}
+
+async fn send_body(
+  send: &mut RequestStream<h3_quinn::SendStream<Bytes>, Bytes>,
+  body: &mut ReqBody,
+) -> Result<(), Http3Error> {
+  while let Some(frame) =
+    poll_fn(|cx| Pin::new(&mut *body).poll_frame(cx)).await
+  {
+    let Ok(frame) = frame else {
+      send.stop_stream(Code::H3_REQUEST_CANCELLED);
+      return Ok(());
+    };
+    match frame.into_data() {
+      Ok(mut data) => {
+        send.send_data(data.copy_to_bytes(data.remaining())).await?;
+      }
+      Err(frame) => {
+        if let Ok(trailers) = frame.into_trailers() {
+          send.send_trailers(trailers).await?;
+        }
+      }
+    }
+  }
+  send.finish().await?;
+  Ok(())
+}
+
+impl Service<http::Request<ReqBody>> for Http3Connection {
+  type Response = http::Response<Http3ResponseBody>;
+  type Error = Http3Error;
+  type Future = Pin<
+    Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + Sync>,
+  >;
+
+  fn poll_ready(
+    &mut self,
+    _cx: &mut Context<'_>,
+  ) -> Poll<Result<(), Self::Error>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, req: http::Request<ReqBody>) -> Self::Future {
+    Box::pin(self.clone().send(req))
+  }
+}
+
+/// The body of a response received over HTTP/3.
+pub struct Http3ResponseBody {
+  stream: RequestStream<h3_quinn::RecvStream, Bytes>,
+  data_done: bool,
+  trailers_done: bool,
+}
+
+impl Body for Http3ResponseBody {
+  type Data = Bytes;
+  type Error = JsErrorBox;
+
+  fn poll_frame(
+    self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+  ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+    let this = self.get_mut();
+    if this.trailers_done {
+      return Poll::Ready(None);
+    }
+    if !this.data_done {
+      match ready!(this.stream.poll_recv_data(cx)) {
+        Ok(Some(mut data)) => {
+          let data = data.copy_to_bytes(data.remaining());
+          return Poll::Ready(Some(Ok(Frame::data(data))));
+        }
+        Ok(None) => this.data_done = true,
+        Err(err) => {
+          return Poll::Ready(Some(Err(JsErrorBox::generic(err.to_string()))));
+        }
+      }
+    }
+    let trailers = ready!(this.stream.poll_recv_trailers(cx));
+    this.trailers_done = true;
+    match trailers {
+      Ok(Some(trailers)) => Poll::Ready(Some(Ok(Frame::trailers(trailers)))),
+      Ok(None) => Poll::Ready(None),
+      Err(err) => Poll::Ready(Some(Err(JsErrorBox::generic(err.to_string())))),
+    }
+  }
+}
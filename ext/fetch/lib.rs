@@ -2,6 +2,7 @@
 
 pub mod dns;
 mod fs_fetch_handler;
+mod http3;
 mod proxy;
 #[cfg(test)]
 mod tests;
@@ -44,13 +45,13 @@ use deno_core::convert::Uint8Array;
 use deno_core::futures::FutureExt;
 use deno_core::futures::Stream;
 use deno_core::futures::StreamExt;
-use deno_core::futures::TryFutureExt;
 use deno_core::futures::stream::Peekable;
 use deno_core::op2;
 use deno_core::url;
 use deno_core::url::Url;
 use deno_core::v8;
 use deno_error::JsErrorBox;
+use deno_features::FeatureChecker;
 pub use deno_fs::FsError;
 use deno_path_util::PathToUrlError;
 use deno_permissions::OpenAccessKind;
@@ -80,6 +81,8 @@ use http::header::RANGE;
 use http::header::USER_AGENT;
 use http_body_util::BodyExt;
 use http_body_util::combinators::BoxBody;
+pub use http3::Http3Error;
+pub use http3::Http3Mode;
 use hyper::body::Frame;
 use hyper_util::client::legacy::Builder as HyperClientBuilder;
 use hyper_util::client::legacy::connect::Connection;
@@ -160,6 +163,7 @@ deno_core::extension!(deno_fetch,
     "22_http_client.js",
     "23_request.js",
     "23_response.js",
+    "24_http_cache.js",
     "26_fetch.js",
     "27_eventsource.js"
   ],
@@ -227,6 +231,9 @@ pub enum FetchError {
   #[class(inherit)]
   #[error(transparent)]
   ClientSend(#[from] ClientSendError),
+  #[class(type)]
+  #[error("error sending request for url ({0}) over HTTP/3: {1}")]
+  Http3Send(Uri, #[source] Http3Error),
   #[class(inherit)]
   #[error(transparent)]
   RequestBuilderHook(JsErrorBox),
@@ -328,6 +335,8 @@ pub fn create_client_from_options(
       pool_idle_timeout: None,
       http1: true,
       http2: true,
+      http3: Http3Mode::Disabled,
+      http3_fallback: true,
       local_address: None,
       client_builder_hook: options.client_builder_hook,
     },
@@ -450,6 +459,7 @@ pub fn op_fetch(
     "http" | "https" => {
       let permissions = state.borrow_mut::<PermissionsContainer>();
       permissions.check_net_url(&url, "fetch()")?;
+      let mut permissions = permissions.clone();
 
       let maybe_authority = extract_authority(&mut url);
       let uri = url
@@ -529,8 +539,11 @@ pub fn op_fetch(
 
       let fut = async move {
         client
-          .send(request)
-          .map_err(Into::into)
+          .send_with_http3(request, move |host, port| {
+            permissions
+              .check_net(&(host, Some(port)), "fetch()")
+              .is_ok()
+          })
           .or_cancel(cancel_handle_)
           .await
       };
@@ -591,6 +604,8 @@ pub struct FetchResponse {
   pub response_rid: ResourceId,
   #[to_v8(serde)]
   pub content_length: Option<u64>,
+  /// The HTTP version the response was received over, e.g. `"h2"`.
+  pub protocol: Option<String>,
   /// This field is populated if some error occurred which needs to be
   /// reconstructed in the JS side to set the error _cause_.
   /// In the tuple, the first element is an error message and the second one is
@@ -637,6 +652,10 @@ pub async fn op_fetch_send(
   };
 
   let status = res.status();
+  let protocol = match request.url.scheme() {
+    "http" | "https" => Some(protocol_name(res.version()).to_string()),
+    _ => None,
+  };
   let url = request.url.into();
   let mut res_headers = Vec::new();
   for (key, val) in res.headers().iter() {
@@ -657,10 +676,22 @@ pub async fn op_fetch_send(
     url,
     response_rid,
     content_length,
+    protocol,
     error: None,
   })
 }
 
+/// Returns the ALPN identifier of the HTTP version.
+fn protocol_name(version: http::Version) -> &'static str {
+  match version {
+    http::Version::HTTP_09 => "http/0.9",
+    http::Version::HTTP_10 => "http/1.0",
+    http::Version::HTTP_2 => "h2",
+    http::Version::HTTP_3 => "h3",
+    _ => "http/1.1",
+  }
+}
+
 type CancelableResponseResult =
   Result<Result<http::Response<ResBody>, FetchError>, Canceled>;
 
@@ -825,6 +856,9 @@ pub struct CreateHttpClientArgs {
   http1: bool,
   #[from_v8(default = true)]
   http2: bool,
+  #[from_v8(serde)]
+  http3: Option<Http3Mode>,
+  http3_fallback: Option<bool>,
   #[from_v8(default)]
   allow_host: bool,
   local_address: Option<String>,
@@ -838,6 +872,17 @@ pub fn op_fetch_custom_client(
   #[scoped] mut args: CreateHttpClientArgs,
   #[cppgc] tls_keys: &TlsKeysHolder,
 ) -> Result<ResourceId, FetchError> {
+  // HTTP/3 is served over QUIC, which is behind `--unstable-net`
+  if args.http3.is_some() {
+    state
+      .borrow::<Arc<FeatureChecker>>()
+      .check_or_exit("net", "Deno.CreateHttpClientOptions.http3");
+  }
+  if args.http3_fallback.is_some() {
+    state
+      .borrow::<Arc<FeatureChecker>>()
+      .check_or_exit("net", "Deno.CreateHttpClientOptions.http3Fallback");
+  }
   if let Some(proxy) = &mut args.proxy {
     let permissions = state.borrow_mut::<PermissionsContainer>();
     match proxy {
@@ -904,6 +949,8 @@ pub fn op_fetch_custom_client(
       ),
       http1: args.http1,
       http2: args.http2,
+      http3: args.http3.unwrap_or_default(),
+      http3_fallback: args.http3_fallback.unwrap_or(true),
       local_address: args.local_address,
       client_builder_hook: options.client_builder_hook,
     },
@@ -927,6 +974,10 @@ pub struct CreateHttpClientOptions {
   pub pool_idle_timeout: Option<Option<u64>>,
   pub http1: bool,
   pub http2: bool,
+  pub http3: Http3Mode,
+  /// Whether requests are sent over TCP if an HTTP/3 connection can't be
+  /// established.
+  pub http3_fallback: bool,
   pub local_address: Option<String>,
   pub client_builder_hook: Option<fn(HyperClientBuilder) -> HyperClientBuilder>,
 }
@@ -944,6 +995,8 @@ impl Default for CreateHttpClientOptions {
       pool_idle_timeout: None,
       http1: true,
       http2: true,
+      http3: Http3Mode::Disabled,
+      http3_fallback: true,
       local_address: None,
       client_builder_hook: None,
     }
@@ -972,6 +1025,8 @@ pub enum HttpClientCreateError {
   UnixProxyNotSupportedOnWindows,
   #[error("Vsock proxy is not supported on this platform")]
   VsockProxyNotSupported,
+  #[error("Cannot create Http Client: HTTP/3 requires TLS 1.3")]
+  Http3TlsVersion,
 }
 
 /// Create new instance of async Client. This client supports
@@ -995,6 +1050,8 @@ pub fn create_http_client(
   // Proxy TLS should not send ALPN
   tls_config.alpn_protocols.clear();
   let proxy_tls_config = Arc::from(tls_config.clone());
+  let http3_tls_config =
+    (options.http3 != Http3Mode::Disabled).then(|| tls_config.clone());
 
   let mut alpn_protocols = vec![];
   if options.http2 {
@@ -1009,12 +1066,28 @@ pub fn create_http_client(
   let mut http_connector =
     HttpConnector::new_with_resolver(options.dns_resolver.clone());
   http_connector.enforce_http(false);
-  if let Some(local_address) = options.local_address {
-    let local_addr = local_address
-      .parse::<IpAddr>()
-      .map_err(|_| HttpClientCreateError::InvalidAddress(local_address))?;
-    http_connector.set_local_address(Some(local_addr));
-  }
+  let local_addr = match options.local_address {
+    Some(local_address) => Some(
+      local_address
+        .parse::<IpAddr>()
+        .map_err(|_| HttpClientCreateError::InvalidAddress(local_address))?,
+    ),
+    None => None,
+  };
+  http_connector.set_local_address(local_addr);
+
+  let http3 = http3_tls_config
+    .map(|tls_config| {
+      http3::Http3Client::new(
+        options.http3,
+        options.http3_fallback,
+        tls_config,
+        options.dns_resolver.clone(),
+        local_addr,
+      )
+      .map(Arc::new)
+    })
+    .transpose()?;
 
   let user_agent = user_agent.parse::<HeaderValue>().map_err(|_| {
     HttpClientCreateError::InvalidUserAgent(user_agent.to_string())
@@ -1113,6 +1186,7 @@ pub fn create_http_client(
   Ok(Client {
     inner: decompress,
     connector,
+    http3,
     user_agent,
   })
 }
@@ -1131,6 +1205,7 @@ pub struct Client {
     >,
   >,
   connector: Connector,
+  http3: Option<Arc<http3::Http3Client>>,
   user_agent: HeaderValue,
 }
 
@@ -1322,6 +1397,60 @@ impl Client {
       .map_err(|e| ClientSendError { uri, source: e })?;
     Ok(resp.map(|b| b.map_err(|e| JsErrorBox::generic(e.to_string())).boxed()))
   }
+
+  /// Sends a request over HTTP/3 if the client is configured to use it for
+  /// the request's origin, and over TCP otherwise.
+  ///
+  /// `check_net` is called with the host and UDP port of `h3` alternatives
+  /// advertised in `Alt-Svc` response headers. Alternatives it rejects are
+  /// not used for later requests.
+  pub async fn send_with_http3(
+    self,
+    mut req: http::Request<ReqBody>,
+    check_net: impl FnMut(&str, u16) -> bool,
+  ) -> Result<http::Response<ResBody>, FetchError> {
+    let Some(http3) = self.http3.clone() else {
+      return Ok(self.send(req).await?);
+    };
+
+    let uri = req.uri().clone();
+    if let Some(connection) = self.connect_http3(&http3, &uri).await? {
+      self.inject_common_headers(&mut req);
+      req.headers_mut().entry(ACCEPT).or_insert(STAR_STAR);
+      let resp = Decompression::new(connection)
+        .gzip(true)
+        .br(true)
+        .oneshot(req)
+        .await
+        .map_err(|e| FetchError::Http3Send(uri, e))?;
+      return Ok(
+        resp.map(|b| b.map_err(|e| JsErrorBox::generic(e.to_string())).boxed()),
+      );
+    }
+
+    let resp = self.send(req).await?;
+    http3.record_alt_svc(&uri, resp.headers(), check_net);
+    Ok(resp)
+  }
+
+  /// Returns the HTTP/3 connection to send the request on, if the request
+  /// should be sent over HTTP/3. Connection failures are only surfaced when
+  /// the client doesn't allow falling back to TCP.
+  async fn connect_http3(
+    &self,
+    http3: &http3::Http3Client,
+    uri: &Uri,
+  ) -> Result<Option<http3::Http3Connection>, FetchError> {
+    // proxies are only supported over TCP
+    if self.connector.proxies.is_proxied(uri) {
+      return Ok(None);
+    }
+    match http3.connect(uri).await {
+      Ok(connection) => Ok(connection),
+      Err(_) if http3.fallback() => Ok(None),
+      Err(err) => Err(FetchError::Http3Send(uri.clone(), err)),
+    }
+  }
 }
 
 // This is a custom enum to allow the retry policy to clone the variants that could be retried.
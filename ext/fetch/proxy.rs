@@ -516,6 +516,10 @@ impl Proxies {
     }
   }
 
+  pub(crate) fn is_proxied(&self, dst: &Uri) -> bool {
+    self.intercept(dst).is_some()
+  }
+
   fn intercept(&self, dst: &Uri) -> Option<&Intercept> {
     if let Some(no_proxy) = self.no.as_ref()
       && no_proxy.contains(dst.host()?)
@@ -7,6 +7,7 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering::SeqCst;
+use std::time::Duration;
 
 use bytes::Bytes;
 use fast_socks5::server::Config as Socks5Config;
@@ -16,8 +17,11 @@ use tokio::io::AsyncReadExt;
 use tokio::io::AsyncWriteExt;
 
 use super::CreateHttpClientOptions;
+use super::Http3Mode;
 use super::create_http_client;
 use crate::dns;
+use crate::http3::AltSvc;
+use crate::http3::parse_alt_svc;
 
 static EXAMPLE_CRT: &[u8] = include_bytes!("../tls/testdata/example1_cert.der");
 static EXAMPLE_KEY: &[u8] =
@@ -67,6 +71,28 @@ fn test_userspace_resolver() {
   });
 }
 
+#[test]
+fn test_parse_alt_svc() {
+  assert_eq!(
+    parse_alt_svc(r#"h3=":443"; ma=3600"#),
+    Some(AltSvc::H3 {
+      port: 443,
+      max_age: Duration::from_secs(3600),
+    })
+  );
+  // the first same-host h3 alternative is used, `ma` defaults to 24 hours
+  assert_eq!(
+    parse_alt_svc(r#"h2=":443", h3="other.example:443", h3=":8443""#),
+    Some(AltSvc::H3 {
+      port: 8443,
+      max_age: Duration::from_secs(86400),
+    })
+  );
+  assert_eq!(parse_alt_svc("clear"), Some(AltSvc::Clear));
+  assert_eq!(parse_alt_svc(r#"h3-29=":443""#), None);
+  assert_eq!(parse_alt_svc(r#"h3=":invalid""#), None);
+}
+
 #[tokio::test]
 async fn test_http_proxy_http11_ipv4() {
   let src_addr = create_https_server(false, Ipv4Addr::LOCALHOST.into()).await;
@@ -146,6 +172,8 @@ async fn run_test_client_with_resolver(
       dns_resolver: resolver,
       http1: true,
       http2: true,
+      http3: Http3Mode::Disabled,
+      http3_fallback: true,
       local_address: None,
       client_builder_hook: None,
     },
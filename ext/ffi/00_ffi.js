@@ -43,6 +43,7 @@ const {
   ArrayBufferPrototypeGetByteLength,
   ArrayPrototypeMap,
   ArrayPrototypeJoin,
+  ArrayPrototypePush,
   BigInt,
   DataViewPrototypeGetByteLength,
   ObjectDefineProperty,
@@ -280,7 +281,7 @@ class UnsafeFnPointer {
   constructor(pointer, definition) {
     this.pointer = pointer;
     this.definition = definition;
-    this.#structSize = isStruct(definition.result)
+    this.#structSize = isAggregate(definition.result)
       ? getTypeSizeAndAlignment(definition.result)[0]
       : null;
   }
@@ -331,13 +332,30 @@ function isStruct(type) {
     typeof type.struct === "object";
 }
 
+function isUnion(type) {
+  return typeof type === "object" && type !== null &&
+    typeof type.union === "object";
+}
+
+function isArrayType(type) {
+  return typeof type === "object" && type !== null &&
+    typeof type.array === "object";
+}
+
+// Structs and unions are passed as buffers and returned through an out buffer.
+function isAggregate(type) {
+  return isStruct(type) || isUnion(type);
+}
+
 function getTypeSizeAndAlignment(type, cache = new SafeMap()) {
-  if (isStruct(type)) {
+  if (isStruct(type) || isUnion(type)) {
     const cached = cache.get(type);
     if (cached !== undefined) {
       if (cached === null) {
         throw new TypeError(
-          "Cannot get pointer size: found recursive struct",
+          `Cannot get pointer size: found recursive ${
+            isStruct(type) ? "struct" : "union"
+          }`,
         );
       }
       return cached;
@@ -345,14 +363,25 @@ function getTypeSizeAndAlignment(type, cache = new SafeMap()) {
     cache.set(type, null);
     let size = 0;
     let alignment = 1;
-    for (const field of new SafeArrayIterator(type.struct)) {
-      const { 0: fieldSize, 1: fieldAlign } = getTypeSizeAndAlignment(
-        field,
-        cache,
-      );
-      alignment = MathMax(alignment, fieldAlign);
-      size = MathCeil(size / fieldAlign) * fieldAlign;
-      size += fieldSize;
+    if (isStruct(type)) {
+      for (const field of new SafeArrayIterator(type.struct)) {
+        const { 0: fieldSize, 1: fieldAlign } = getTypeSizeAndAlignment(
+          field,
+          cache,
+        );
+        alignment = MathMax(alignment, fieldAlign);
+        size = MathCeil(size / fieldAlign) * fieldAlign;
+        size += fieldSize;
+      }
+    } else {
+      for (const member of new SafeArrayIterator(type.union)) {
+        const { 0: memberSize, 1: memberAlign } = getTypeSizeAndAlignment(
+          member,
+          cache,
+        );
+        alignment = MathMax(alignment, memberAlign);
+        size = MathMax(size, memberSize);
+      }
     }
     size = MathCeil(size / alignment) * alignment;
     const result = [size, alignment];
@@ -360,6 +389,14 @@ function getTypeSizeAndAlignment(type, cache = new SafeMap()) {
     return result;
   }
 
+  if (isArrayType(type)) {
+    const { 0: elementSize, 1: elementAlign } = getTypeSizeAndAlignment(
+      type.array.type,
+      cache,
+    );
+    return [elementSize * type.array.length, elementAlign];
+  }
+
   switch (type) {
     case "bool":
     case "u8":
@@ -401,6 +438,11 @@ class UnsafeCallback {
         "Cannot construct UnsafeCallback: cannot be nonblocking",
       );
     }
+    if (definition.variadic) {
+      throw new TypeError(
+        "Cannot construct UnsafeCallback: cannot be variadic",
+      );
+    }
     const { 0: rid, 1: pointer } = op_ffi_unsafe_callback_create(
       definition,
       callback,
@@ -495,7 +537,7 @@ class DynamicLibrary {
         continue;
       }
       const resultType = symbols[symbol].result;
-      const isStructResult = isStruct(resultType);
+      const isStructResult = isAggregate(resultType);
       const structSize = isStructResult
         ? getTypeSizeAndAlignment(resultType)[0]
         : 0;
@@ -538,15 +580,21 @@ class DynamicLibrary {
       if (isStructResult && !isNonBlocking) {
         const call = this.symbols[symbol];
         const parameters = symbols[symbol].parameters;
-        const params = ArrayPrototypeJoin(
-          ArrayPrototypeMap(parameters, (_, index) => `p${index}`),
-          ", ",
+        const params = ArrayPrototypeMap(
+          parameters,
+          (_, index) => `p${index}`,
         );
+        // Variadic arguments are passed after the declared parameters and
+        // the out buffer has to come last.
+        if (symbols[symbol].variadic) {
+          ArrayPrototypePush(params, "...variadic");
+        }
+        const args = ArrayPrototypeJoin(params, ", ");
         this.symbols[symbol] = new Function(
           "call",
-          `return function (${params}) {
+          `return function (${args}) {
             const buffer = new Uint8Array(${structSize});
-            call(${params}${parameters.length > 0 ? ", " : ""}buffer);
+            call(${args}${params.length > 0 ? ", " : ""}buffer);
             return buffer;
           }`,
         )(call);
@@ -13,6 +13,7 @@ use deno_core::serde_v8::BigInt as V8BigInt;
 use deno_core::serde_v8::ExternalPointer;
 use deno_core::unsync::spawn_blocking;
 use deno_core::v8;
+use deno_error::JsErrorBox;
 use deno_permissions::PermissionsContainer;
 use libffi::middle::Arg;
 use num_bigint::BigInt;
@@ -24,6 +25,7 @@ use crate::dlfcn::DynamicLibraryResource;
 use crate::ir::*;
 use crate::symbol::NativeType;
 use crate::symbol::Symbol;
+use crate::symbol::make_cif;
 
 #[derive(Debug, thiserror::Error, deno_error::JsError)]
 pub enum CallError {
@@ -45,6 +47,9 @@ pub enum CallError {
   #[class(inherit)]
   #[error(transparent)]
   Callback(#[from] super::CallbackError),
+  #[class(inherit)]
+  #[error(transparent)]
+  Other(#[from] JsErrorBox),
 }
 
 // SAFETY: Makes an FFI call
@@ -68,7 +73,7 @@ unsafe fn ffi_call_rtype_struct(
 // A one-off synchronous FFI call.
 pub(crate) fn ffi_call_sync<'scope>(
   scope: &mut v8::PinScope<'scope, '_>,
-  args: v8::FunctionCallbackArguments,
+  args: v8::FunctionCallbackArguments<'scope>,
   symbol: &Symbol,
   out_buffer: Option<OutBuffer>,
 ) -> Result<NativeValue, CallError>
@@ -80,70 +85,29 @@ where
     result_type,
     cif,
     ptr: fun_ptr,
+    variadic,
     ..
   } = symbol;
-  let mut ffi_args: Vec<NativeValue> =
-    Vec::with_capacity(parameter_types.len());
-
-  for (index, native_type) in parameter_types.iter().enumerate() {
-    let value = args.get(index as i32);
-    match native_type {
-      NativeType::Bool => {
-        ffi_args.push(ffi_parse_bool_arg(value)?);
-      }
-      NativeType::U8 => {
-        ffi_args.push(ffi_parse_u8_arg(value)?);
-      }
-      NativeType::I8 => {
-        ffi_args.push(ffi_parse_i8_arg(value)?);
-      }
-      NativeType::U16 => {
-        ffi_args.push(ffi_parse_u16_arg(value)?);
-      }
-      NativeType::I16 => {
-        ffi_args.push(ffi_parse_i16_arg(value)?);
-      }
-      NativeType::U32 => {
-        ffi_args.push(ffi_parse_u32_arg(value)?);
-      }
-      NativeType::I32 => {
-        ffi_args.push(ffi_parse_i32_arg(value)?);
-      }
-      NativeType::U64 => {
-        ffi_args.push(ffi_parse_u64_arg(scope, value)?);
-      }
-      NativeType::I64 => {
-        ffi_args.push(ffi_parse_i64_arg(scope, value)?);
-      }
-      NativeType::USize => {
-        ffi_args.push(ffi_parse_usize_arg(scope, value)?);
-      }
-      NativeType::ISize => {
-        ffi_args.push(ffi_parse_isize_arg(scope, value)?);
-      }
-      NativeType::F32 => {
-        ffi_args.push(ffi_parse_f32_arg(value)?);
-      }
-      NativeType::F64 => {
-        ffi_args.push(ffi_parse_f64_arg(value)?);
-      }
-      NativeType::Buffer => {
-        ffi_args.push(ffi_parse_buffer_arg(value)?);
-      }
-      NativeType::Struct(_) => {
-        ffi_args.push(ffi_parse_struct_arg(scope, value)?);
-      }
-      NativeType::Pointer => {
-        ffi_args.push(ffi_parse_pointer_arg(scope, value)?);
-      }
-      NativeType::Function => {
-        ffi_args.push(ffi_parse_function_arg(scope, value)?);
-      }
-      NativeType::Void => {
-        unreachable!();
-      }
+  let variadic_cif;
+  let variadic_types;
+  let (cif, parameter_types, ffi_args) = if *variadic {
+    // The out buffer of aggregate results is passed last.
+    let argc = args.length() - result_type.is_aggregate() as i32;
+    let values = (0..argc).map(|index| args.get(index)).collect::<Vec<_>>();
+    let call =
+      ffi_parse_variadic_args(scope, &values, parameter_types, result_type)?;
+    variadic_cif = call.cif;
+    variadic_types = call.parameter_types;
+    (&variadic_cif, &variadic_types[..], call.args)
+  } else {
+    let mut ffi_args: Vec<NativeValue> =
+      Vec::with_capacity(parameter_types.len());
+    for (index, native_type) in parameter_types.iter().enumerate() {
+      let value = args.get(index as i32);
+      ffi_args.push(ffi_parse_arg(scope, value, native_type)?);
     }
-  }
+    (cif, &parameter_types[..], ffi_args)
+  };
   let call_args: Vec<Arg> = ffi_args
     .iter()
     .enumerate()
@@ -201,18 +165,79 @@ where
           pointer: cif.call::<*mut c_void>(*fun_ptr, &call_args),
         }
       }
-      NativeType::Struct(_) => NativeValue {
+      NativeType::Struct(_) | NativeType::Union(_) => NativeValue {
         void_value: ffi_call_rtype_struct(
-          &symbol.cif,
-          &symbol.ptr,
+          cif,
+          fun_ptr,
           call_args,
           out_buffer.unwrap().0,
         ),
       },
+      NativeType::Array { .. } => unreachable!(),
     })
   }
 }
 
+/// The call interface and arguments of one call to a variadic function.
+struct VariadicCall {
+  cif: libffi::middle::Cif,
+  parameter_types: Vec<NativeType>,
+  args: Vec<NativeValue>,
+}
+
+/// Parses the arguments of a call to a variadic function. The variadic
+/// arguments follow the declared parameters, led by an array holding their
+/// types, e.g. `printf(format, ["i32", "f64"], 42, 1.5)`.
+fn ffi_parse_variadic_args<'scope>(
+  scope: &mut v8::PinScope<'scope, '_>,
+  args: &[v8::Local<'scope, v8::Value>],
+  parameter_types: &[NativeType],
+  result_type: &NativeType,
+) -> Result<VariadicCall, CallError> {
+  let fixed_parameters = parameter_types.len();
+  let mut parameter_types = parameter_types.to_vec();
+  let values = match args.get(fixed_parameters..) {
+    None | Some([]) => &[][..],
+    Some([types, values @ ..]) => {
+      let types: Vec<NativeType> = deno_core::serde_v8::from_v8(scope, *types)
+        .map_err(|_| IRError::InvalidVariadicArguments)?;
+      if types.len() != values.len() {
+        return Err(IRError::InvalidVariadicArguments.into());
+      }
+      parameter_types.extend(types);
+      values
+    }
+  };
+  let cif = make_cif(&parameter_types, result_type, Some(fixed_parameters))?;
+
+  let mut ffi_args = Vec::with_capacity(parameter_types.len());
+  for (index, native_type) in parameter_types.iter().enumerate() {
+    let value = match index.checked_sub(fixed_parameters) {
+      Some(index) => values[index],
+      None => match args.get(index) {
+        Some(value) => *value,
+        None => v8::undefined(scope).into(),
+      },
+    };
+    ffi_args.push(ffi_parse_arg(scope, value, native_type)?);
+  }
+
+  Ok(VariadicCall {
+    cif,
+    parameter_types,
+    args: ffi_args,
+  })
+}
+
+fn array_values<'scope>(
+  scope: &mut v8::PinScope<'scope, '_>,
+  array: v8::Local<v8::Array>,
+) -> Vec<v8::Local<'scope, v8::Value>> {
+  (0..array.length())
+    .map(|index| array.get_index(scope, index).unwrap())
+    .collect()
+}
+
 #[derive(Serialize)]
 #[serde(untagged)]
 pub enum FfiValue {
@@ -290,14 +315,37 @@ fn ffi_call(
           cif.call::<*mut c_void>(fun_ptr, &call_args),
         ))
       }
-      NativeType::Struct(_) => {
+      NativeType::Struct(_) | NativeType::Union(_) => {
         ffi_call_rtype_struct(cif, &fun_ptr, call_args, out_buffer.unwrap().0);
         FfiValue::Value(Value::Null)
       }
+      NativeType::Array { .. } => unreachable!(),
     }
   }
 }
 
+fn ffi_parse_ptr_args<'scope>(
+  scope: &mut v8::PinScope<'scope, '_>,
+  pointer: *mut c_void,
+  def: &ForeignFunction,
+  parameters: v8::Local<v8::Array>,
+) -> Result<(PtrSymbol, Vec<NativeType>, Vec<NativeValue>), CallError> {
+  if def.variadic {
+    let values = array_values(scope, parameters);
+    let call =
+      ffi_parse_variadic_args(scope, &values, &def.parameters, &def.result)?;
+    let symbol = PtrSymbol {
+      cif: call.cif,
+      ptr: libffi::middle::CodePtr::from_ptr(pointer as _),
+    };
+    Ok((symbol, call.parameter_types, call.args))
+  } else {
+    let symbol = PtrSymbol::new(pointer, def)?;
+    let call_args = ffi_parse_args(scope, parameters, &def.parameters)?;
+    Ok((symbol, def.parameters.clone(), call_args))
+  }
+}
+
 #[op2(stack_trace)]
 #[serde]
 pub fn op_ffi_call_ptr_nonblocking(
@@ -316,8 +364,8 @@ where
     permissions.check_ffi_partial_no_path()?;
   };
 
-  let symbol = PtrSymbol::new(pointer, &def)?;
-  let call_args = ffi_parse_args(scope, parameters, &def.parameters)?;
+  let (symbol, parameter_types, call_args) =
+    ffi_parse_ptr_args(scope, pointer, &def, parameters)?;
   let out_buffer_ptr = out_buffer_as_ptr(scope, out_buffer);
 
   let join_handle = spawn_blocking(move || {
@@ -326,7 +374,7 @@ where
       call_args,
       &cif,
       ptr,
-      &def.parameters,
+      &parameter_types,
       def.result,
       out_buffer_ptr,
     )
@@ -353,7 +401,7 @@ pub fn op_ffi_call_nonblocking(
   out_buffer: Option<v8::Local<v8::TypedArray>>,
 ) -> Result<impl Future<Output = Result<FfiValue, CallError>> + use<>, CallError>
 {
-  let symbol = {
+  let mut symbol = {
     let state = state.borrow();
     let resource = state.resource_table.get::<DynamicLibraryResource>(rid)?;
     let symbols = &resource.symbols;
@@ -363,7 +411,20 @@ pub fn op_ffi_call_nonblocking(
       .clone()
   };
 
-  let call_args = ffi_parse_args(scope, parameters, &symbol.parameter_types)?;
+  let call_args = if symbol.variadic {
+    let values = array_values(scope, parameters);
+    let call = ffi_parse_variadic_args(
+      scope,
+      &values,
+      &symbol.parameter_types,
+      &symbol.result_type,
+    )?;
+    symbol.cif = call.cif;
+    symbol.parameter_types = call.parameter_types;
+    call.args
+  } else {
+    ffi_parse_args(scope, parameters, &symbol.parameter_types)?
+  };
   let out_buffer_ptr = out_buffer_as_ptr(scope, out_buffer);
 
   let join_handle = spawn_blocking(move || {
@@ -409,8 +470,8 @@ pub fn op_ffi_call_ptr(
     permissions.check_ffi_partial_no_path()?;
   };
 
-  let symbol = PtrSymbol::new(pointer, &def)?;
-  let call_args = ffi_parse_args(scope, parameters, &def.parameters)?;
+  let (symbol, parameter_types, call_args) =
+    ffi_parse_ptr_args(scope, pointer, &def, parameters)?;
 
   let out_buffer_ptr = out_buffer_as_ptr(scope, out_buffer);
 
@@ -418,7 +479,7 @@ pub fn op_ffi_call_ptr(
     call_args,
     &symbol.cif,
     symbol.ptr,
-    &def.parameters,
+    &parameter_types,
     def.result.clone(),
     out_buffer_ptr,
   );
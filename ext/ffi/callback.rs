@@ -27,6 +27,7 @@ use serde::Deserialize;
 
 use crate::ForeignFunction;
 use crate::symbol::NativeType;
+use crate::symbol::make_cif;
 
 static THREAD_ID_COUNTER: AtomicU32 = AtomicU32::new(1);
 
@@ -59,15 +60,11 @@ impl PtrSymbol {
     def: &ForeignFunction,
   ) -> Result<Self, CallbackError> {
     let ptr = libffi::middle::CodePtr::from_ptr(fn_ptr as _);
-    let cif = libffi::middle::Cif::new(
-      def
-        .parameters
-        .clone()
-        .into_iter()
-        .map(libffi::middle::Type::try_from)
-        .collect::<Result<Vec<_>, _>>()?,
-      def.result.clone().try_into()?,
-    );
+    let cif = make_cif(
+      &def.parameters,
+      &def.result,
+      def.variadic.then_some(def.parameters.len()),
+    )?;
 
     Ok(Self { cif, ptr })
   }
@@ -273,7 +270,7 @@ unsafe fn do_ffi_callback(
             v8::External::new(scope, result).into()
           }
         }
-        NativeType::Struct(_) => {
+        NativeType::Struct(_) | NativeType::Union(_) => {
           let size = arg_types[index].as_ref().unwrap().size;
           let ptr = (*val) as *const u8;
           let slice = std::slice::from_raw_parts(ptr, size);
@@ -288,7 +285,7 @@ unsafe fn do_ffi_callback(
               .into();
           local_value
         }
-        NativeType::Void => unreachable!(),
+        NativeType::Void | NativeType::Array { .. } => unreachable!(),
       };
       params.push(value);
     }
@@ -510,7 +507,7 @@ unsafe fn do_ffi_callback(
             as u64;
         }
       }
-      NativeType::Struct(_) => {
+      NativeType::Struct(_) | NativeType::Union(_) => {
         let size;
         let pointer = if let Ok(value) =
           v8::Local::<v8::ArrayBufferView>::try_from(value)
@@ -543,6 +540,7 @@ unsafe fn do_ffi_callback(
       NativeType::Void => {
         // nop
       }
+      NativeType::Array { .. } => unreachable!(),
     };
   }
 }
@@ -23,6 +23,7 @@ use serde_value::ValueDeserializer;
 use crate::ir::out_buffer_as_ptr;
 use crate::symbol::NativeType;
 use crate::symbol::Symbol;
+use crate::symbol::make_cif;
 use crate::turbocall;
 use crate::turbocall::Turbocall;
 
@@ -99,6 +100,8 @@ pub struct ForeignFunction {
   #[serde(rename = "optional")]
   #[serde(default = "default_optional")]
   optional: bool,
+  #[serde(default)]
+  pub variadic: bool,
 }
 
 fn default_optional() -> bool {
@@ -205,15 +208,11 @@ pub fn op_ffi_load<'scope>(
           }?;
 
         let ptr = libffi::middle::CodePtr::from_ptr(fn_ptr as _);
-        let cif = libffi::middle::Cif::new(
-          foreign_fn
-            .parameters
-            .clone()
-            .into_iter()
-            .map(libffi::middle::Type::try_from)
-            .collect::<Result<Vec<_>, _>>()?,
-          foreign_fn.result.clone().try_into()?,
-        );
+        let cif = make_cif(
+          &foreign_fn.parameters,
+          &foreign_fn.result,
+          foreign_fn.variadic.then_some(foreign_fn.parameters.len()),
+        )?;
 
         let func_key = v8::String::new(scope, &symbol_key).unwrap();
         let sym = Box::new(Symbol {
@@ -222,6 +221,7 @@ pub fn op_ffi_load<'scope>(
           ptr,
           parameter_types: foreign_fn.parameters,
           result_type: foreign_fn.result,
+          variadic: foreign_fn.variadic,
         });
 
         resource.symbols.insert(symbol_key, sym.clone());
@@ -317,7 +317,7 @@ fn sync_fn_impl<'s>(
   )
   .unwrap();
   let out_buffer = match data.symbol.result_type {
-    NativeType::Struct(_) => {
+    NativeType::Struct(_) | NativeType::Union(_) => {
       let argc = args.length();
       out_buffer_as_ptr(
         scope,
@@ -474,6 +474,45 @@ mod tests {
     }
   }
 
+  #[test]
+  fn test_serialize_variadic_foreign_symbol() {
+    let symbol: ForeignSymbol = serde_json::from_value(json! {{
+      "parameters": ["buffer", { "union": ["i64", "f64"] }],
+      "result": { "struct": ["u8", { "array": { "type": "u8", "length": 3 } }] },
+      "variadic": true
+    }})
+    .expect("Failed to parse");
+    if let ForeignSymbol::ForeignFunction(ForeignFunction {
+      parameters,
+      result,
+      variadic,
+      ..
+    }) = symbol
+    {
+      assert!(variadic);
+      assert_eq!(
+        parameters,
+        vec![
+          NativeType::Buffer,
+          NativeType::Union(Box::new([NativeType::I64, NativeType::F64])),
+        ]
+      );
+      assert_eq!(
+        result,
+        NativeType::Struct(Box::new([
+          NativeType::U8,
+          NativeType::Array {
+            element: Box::new(NativeType::U8),
+            length: 3,
+          },
+        ]))
+      );
+      assert_eq!(result.size_and_alignment(), (4, 1));
+    } else {
+      panic!("Failed to parse ForeignFunction as expected");
+    }
+  }
+
   #[test]
   fn test_serialize_foreign_symbol_failures() {
     let error = serde_json::from_value::<ForeignSymbol>(json! {{
@@ -51,6 +51,12 @@ pub enum IRError {
   InvalidStructType,
   #[error("Invalid FFI function type, expected null, or External")]
   InvalidFunctionType,
+  #[error(
+    "Invalid FFI variadic arguments, expected an array of types followed by the values"
+  )]
+  InvalidVariadicArguments,
+  #[error("Invalid FFI variadic argument type '{0}'")]
+  InvalidVariadicArgumentType(&'static str),
 }
 
 pub struct OutBuffer(pub *mut u8);
@@ -101,7 +107,7 @@ impl NativeValue {
     #[allow(clippy::undocumented_unsafe_blocks)]
     unsafe {
       match native_type {
-        NativeType::Void => unreachable!(),
+        NativeType::Void | NativeType::Array { .. } => unreachable!(),
         NativeType::Bool => Arg::new(&self.bool_value),
         NativeType::U8 => Arg::new(&self.u8_value),
         NativeType::I8 => Arg::new(&self.i8_value),
@@ -118,7 +124,9 @@ impl NativeValue {
         NativeType::Pointer | NativeType::Buffer | NativeType::Function => {
           Arg::new(&self.pointer)
         }
-        NativeType::Struct(_) => Arg::new(&*self.pointer),
+        NativeType::Struct(_) | NativeType::Union(_) => {
+          Arg::new(&*self.pointer)
+        }
       }
     }
   }
@@ -171,7 +179,9 @@ impl NativeValue {
           };
           local_value
         }
-        NativeType::Struct(_) => v8::null(scope).into(),
+        NativeType::Struct(_)
+        | NativeType::Union(_)
+        | NativeType::Array { .. } => v8::null(scope).into(),
       }
     }
   }
@@ -455,6 +465,38 @@ pub fn ffi_parse_function_arg(
   Ok(NativeValue { pointer })
 }
 
+#[inline]
+pub fn ffi_parse_arg(
+  scope: &mut v8::PinScope<'_, '_>,
+  value: v8::Local<v8::Value>,
+  native_type: &NativeType,
+) -> Result<NativeValue, IRError> {
+  match native_type {
+    NativeType::Bool => ffi_parse_bool_arg(value),
+    NativeType::U8 => ffi_parse_u8_arg(value),
+    NativeType::I8 => ffi_parse_i8_arg(value),
+    NativeType::U16 => ffi_parse_u16_arg(value),
+    NativeType::I16 => ffi_parse_i16_arg(value),
+    NativeType::U32 => ffi_parse_u32_arg(value),
+    NativeType::I32 => ffi_parse_i32_arg(value),
+    NativeType::U64 => ffi_parse_u64_arg(scope, value),
+    NativeType::I64 => ffi_parse_i64_arg(scope, value),
+    NativeType::USize => ffi_parse_usize_arg(scope, value),
+    NativeType::ISize => ffi_parse_isize_arg(scope, value),
+    NativeType::F32 => ffi_parse_f32_arg(value),
+    NativeType::F64 => ffi_parse_f64_arg(value),
+    NativeType::Buffer => ffi_parse_buffer_arg(value),
+    NativeType::Struct(_) | NativeType::Union(_) => {
+      ffi_parse_struct_arg(scope, value)
+    }
+    NativeType::Pointer => ffi_parse_pointer_arg(scope, value),
+    NativeType::Function => ffi_parse_function_arg(scope, value),
+    NativeType::Void | NativeType::Array { .. } => {
+      unreachable!();
+    }
+  }
+}
+
 pub fn ffi_parse_args<'scope>(
   scope: &mut v8::PinScope<'scope, '_>,
   args: v8::Local<v8::Array>,
@@ -472,62 +514,7 @@ where
 
   for (index, native_type) in parameter_types.iter().enumerate() {
     let value = args.get_index(scope, index as u32).unwrap();
-    match native_type {
-      NativeType::Bool => {
-        ffi_args.push(ffi_parse_bool_arg(value)?);
-      }
-      NativeType::U8 => {
-        ffi_args.push(ffi_parse_u8_arg(value)?);
-      }
-      NativeType::I8 => {
-        ffi_args.push(ffi_parse_i8_arg(value)?);
-      }
-      NativeType::U16 => {
-        ffi_args.push(ffi_parse_u16_arg(value)?);
-      }
-      NativeType::I16 => {
-        ffi_args.push(ffi_parse_i16_arg(value)?);
-      }
-      NativeType::U32 => {
-        ffi_args.push(ffi_parse_u32_arg(value)?);
-      }
-      NativeType::I32 => {
-        ffi_args.push(ffi_parse_i32_arg(value)?);
-      }
-      NativeType::U64 => {
-        ffi_args.push(ffi_parse_u64_arg(scope, value)?);
-      }
-      NativeType::I64 => {
-        ffi_args.push(ffi_parse_i64_arg(scope, value)?);
-      }
-      NativeType::USize => {
-        ffi_args.push(ffi_parse_usize_arg(scope, value)?);
-      }
-      NativeType::ISize => {
-        ffi_args.push(ffi_parse_isize_arg(scope, value)?);
-      }
-      NativeType::F32 => {
-        ffi_args.push(ffi_parse_f32_arg(value)?);
-      }
-      NativeType::F64 => {
-        ffi_args.push(ffi_parse_f64_arg(value)?);
-      }
-      NativeType::Buffer => {
-        ffi_args.push(ffi_parse_buffer_arg(value)?);
-      }
-      NativeType::Struct(_) => {
-        ffi_args.push(ffi_parse_struct_arg(scope, value)?);
-      }
-      NativeType::Pointer => {
-        ffi_args.push(ffi_parse_pointer_arg(scope, value)?);
-      }
-      NativeType::Function => {
-        ffi_args.push(ffi_parse_function_arg(scope, value)?);
-      }
-      NativeType::Void => {
-        unreachable!();
-      }
-    }
+    ffi_args.push(ffi_parse_arg(scope, value, native_type)?);
   }
 
   Ok(ffi_args)
@@ -21,6 +21,12 @@ pub enum StaticError {
   #[class(type)]
   #[error("Invalid FFI static type 'struct'")]
   InvalidTypeStruct,
+  #[class(type)]
+  #[error("Invalid FFI static type 'union'")]
+  InvalidTypeUnion,
+  #[class(type)]
+  #[error("Invalid FFI static type 'array'")]
+  InvalidTypeArray,
   #[class(inherit)]
   #[error(transparent)]
   Resource(#[from] deno_core::error::ResourceError),
@@ -150,5 +156,11 @@ pub fn op_ffi_get_static<'scope>(
     NativeType::Struct(_) => {
       return Err(StaticError::InvalidTypeStruct);
     }
+    NativeType::Union(_) => {
+      return Err(StaticError::InvalidTypeUnion);
+    }
+    NativeType::Array { .. } => {
+      return Err(StaticError::InvalidTypeArray);
+    }
   })
 }
@@ -2,6 +2,8 @@
 
 use deno_error::JsErrorBox;
 
+use crate::ir::IRError;
+
 /// Defines the accepted types that can be used as
 /// parameters and return values in FFI.
 #[derive(Clone, Debug, serde::Deserialize, Eq, PartialEq)]
@@ -25,44 +27,236 @@ pub enum NativeType {
   Buffer,
   Function,
   Struct(Box<[NativeType]>),
+  Union(Box<[NativeType]>),
+  /// A fixed-size array, only valid as a struct or union field.
+  Array {
+    #[serde(rename = "type")]
+    element: Box<NativeType>,
+    length: usize,
+  },
+}
+
+impl NativeType {
+  /// Whether values of this type are passed as buffers and returned through
+  /// an out buffer.
+  pub fn is_aggregate(&self) -> bool {
+    matches!(self, NativeType::Struct(_) | NativeType::Union(_))
+  }
+
+  /// Size and alignment of the type following the C layout rules of the
+  /// current platform.
+  pub fn size_and_alignment(&self) -> (usize, usize) {
+    use std::mem::align_of;
+    use std::mem::size_of;
+
+    match self {
+      NativeType::Void => (0, 1),
+      NativeType::Bool | NativeType::U8 | NativeType::I8 => (1, 1),
+      NativeType::U16 | NativeType::I16 => (2, 2),
+      NativeType::U32 | NativeType::I32 => (4, 4),
+      NativeType::F32 => (4, align_of::<f32>()),
+      NativeType::U64 | NativeType::I64 => (8, align_of::<u64>()),
+      NativeType::F64 => (8, align_of::<f64>()),
+      NativeType::USize
+      | NativeType::ISize
+      | NativeType::Pointer
+      | NativeType::Buffer
+      | NativeType::Function => (size_of::<usize>(), align_of::<usize>()),
+      NativeType::Struct(fields) => {
+        let mut size: usize = 0;
+        let mut alignment = 1;
+        for field in fields {
+          let (field_size, field_alignment) = field.size_and_alignment();
+          alignment = alignment.max(field_alignment);
+          size = size.next_multiple_of(field_alignment) + field_size;
+        }
+        (size.next_multiple_of(alignment), alignment)
+      }
+      NativeType::Union(members) => {
+        let (size, alignment) =
+          members.iter().map(NativeType::size_and_alignment).fold(
+            (0, 1),
+            |(size, alignment), (member_size, member_alignment)| {
+              (size.max(member_size), alignment.max(member_alignment))
+            },
+          );
+        (size.next_multiple_of(alignment), alignment)
+      }
+      NativeType::Array { element, length } => {
+        let (size, alignment) = element.size_and_alignment();
+        (size * length, alignment)
+      }
+    }
+  }
+
+  fn is_floating_point(&self) -> bool {
+    match self {
+      NativeType::F32 | NativeType::F64 => true,
+      NativeType::Struct(fields) | NativeType::Union(fields) => {
+        fields.iter().all(NativeType::is_floating_point)
+      }
+      NativeType::Array { element, .. } => element.is_floating_point(),
+      _ => false,
+    }
+  }
 }
 
 impl TryFrom<NativeType> for libffi::middle::Type {
   type Error = JsErrorBox;
 
   fn try_from(native_type: NativeType) -> Result<Self, Self::Error> {
-    Ok(match native_type {
-      NativeType::Void => libffi::middle::Type::void(),
-      NativeType::U8 | NativeType::Bool => libffi::middle::Type::u8(),
-      NativeType::I8 => libffi::middle::Type::i8(),
-      NativeType::U16 => libffi::middle::Type::u16(),
-      NativeType::I16 => libffi::middle::Type::i16(),
-      NativeType::U32 => libffi::middle::Type::u32(),
-      NativeType::I32 => libffi::middle::Type::i32(),
-      NativeType::U64 => libffi::middle::Type::u64(),
-      NativeType::I64 => libffi::middle::Type::i64(),
-      NativeType::USize => libffi::middle::Type::usize(),
-      NativeType::ISize => libffi::middle::Type::isize(),
-      NativeType::F32 => libffi::middle::Type::f32(),
-      NativeType::F64 => libffi::middle::Type::f64(),
-      NativeType::Pointer | NativeType::Buffer | NativeType::Function => {
-        libffi::middle::Type::pointer()
+    if let NativeType::Array { .. } = native_type {
+      return Err(JsErrorBox::type_error(
+        "Arrays can only be used as struct or union fields",
+      ));
+    }
+    field_type(&native_type)
+  }
+}
+
+fn field_type(
+  native_type: &NativeType,
+) -> Result<libffi::middle::Type, JsErrorBox> {
+  Ok(match native_type {
+    NativeType::Void => libffi::middle::Type::void(),
+    NativeType::U8 | NativeType::Bool => libffi::middle::Type::u8(),
+    NativeType::I8 => libffi::middle::Type::i8(),
+    NativeType::U16 => libffi::middle::Type::u16(),
+    NativeType::I16 => libffi::middle::Type::i16(),
+    NativeType::U32 => libffi::middle::Type::u32(),
+    NativeType::I32 => libffi::middle::Type::i32(),
+    NativeType::U64 => libffi::middle::Type::u64(),
+    NativeType::I64 => libffi::middle::Type::i64(),
+    NativeType::USize => libffi::middle::Type::usize(),
+    NativeType::ISize => libffi::middle::Type::isize(),
+    NativeType::F32 => libffi::middle::Type::f32(),
+    NativeType::F64 => libffi::middle::Type::f64(),
+    NativeType::Pointer | NativeType::Buffer | NativeType::Function => {
+      libffi::middle::Type::pointer()
+    }
+    NativeType::Struct(fields) => {
+      if fields.is_empty() {
+        return Err(JsErrorBox::type_error(
+          "Struct must have at least one field",
+        ));
       }
-      NativeType::Struct(fields) => {
-        libffi::middle::Type::structure(match !fields.is_empty() {
-          true => fields
-            .iter()
-            .map(|field| field.clone().try_into())
-            .collect::<Result<Vec<_>, _>>()?,
-          false => {
-            return Err(JsErrorBox::type_error(
-              "Struct must have at least one field",
-            ));
-          }
-        })
+      struct_type(fields)?
+    }
+    NativeType::Union(members) => {
+      if members.is_empty() {
+        return Err(JsErrorBox::type_error(
+          "Union must have at least one member",
+        ));
+      }
+      let (size, alignment) = native_type.size_and_alignment();
+      // libffi has no union type either. A union made up only of floating
+      // point members is classified like its largest member, anything
+      // else is passed like a struct of integers of the same size and
+      // alignment.
+      let largest = members
+        .iter()
+        .max_by_key(|member| member.size_and_alignment().0)
+        .unwrap();
+      if native_type.is_floating_point()
+        && largest.size_and_alignment().0 == size
+      {
+        struct_type(std::slice::from_ref(largest))?
+      } else {
+        let word = match alignment {
+          1 => libffi::middle::Type::u8(),
+          2 => libffi::middle::Type::u16(),
+          4 => libffi::middle::Type::u32(),
+          _ => libffi::middle::Type::u64(),
+        };
+        libffi::middle::Type::structure(
+          std::iter::repeat_n(word, size / alignment).collect::<Vec<_>>(),
+        )
+      }
+    }
+    NativeType::Array { .. } => unreachable!(),
+  })
+}
+
+fn struct_type(
+  fields: &[NativeType],
+) -> Result<libffi::middle::Type, JsErrorBox> {
+  let mut elements = Vec::with_capacity(fields.len());
+  for field in fields {
+    push_struct_field(&mut elements, field)?;
+  }
+  Ok(libffi::middle::Type::structure(elements))
+}
+
+fn push_struct_field(
+  elements: &mut Vec<libffi::middle::Type>,
+  field: &NativeType,
+) -> Result<(), JsErrorBox> {
+  match field {
+    // libffi has no array type, the elements are laid out as consecutive
+    // fields instead.
+    NativeType::Array { element, length } => {
+      if *length == 0 {
+        return Err(JsErrorBox::type_error(
+          "Array must have at least one element",
+        ));
+      }
+      for _ in 0..*length {
+        push_struct_field(elements, element)?;
       }
-    })
+    }
+    field => elements.push(field_type(field)?),
   }
+  Ok(())
+}
+
+/// Prepares the call interface of a function. For variadic functions
+/// `fixed_parameters` is the number of declared parameters, the remaining
+/// ones are the variadic arguments of a particular call.
+pub fn make_cif(
+  parameters: &[NativeType],
+  result: &NativeType,
+  fixed_parameters: Option<usize>,
+) -> Result<libffi::middle::Cif, JsErrorBox> {
+  if let Some(fixed_parameters) = fixed_parameters {
+    // Variadic arguments undergo the default argument promotions, libffi
+    // refuses to prepare a call interface that skips them.
+    for parameter in &parameters[fixed_parameters..] {
+      let (name, promoted) = match parameter {
+        NativeType::Bool => ("bool", "i32"),
+        NativeType::U8 => ("u8", "i32"),
+        NativeType::I8 => ("i8", "i32"),
+        NativeType::U16 => ("u16", "i32"),
+        NativeType::I16 => ("i16", "i32"),
+        NativeType::F32 => ("f32", "f64"),
+        NativeType::Void => {
+          return Err(JsErrorBox::from_err(
+            IRError::InvalidVariadicArgumentType("void"),
+          ));
+        }
+        NativeType::Array { .. } => {
+          return Err(JsErrorBox::from_err(
+            IRError::InvalidVariadicArgumentType("array"),
+          ));
+        }
+        _ => continue,
+      };
+      return Err(JsErrorBox::type_error(format!(
+        "Invalid variadic argument type '{name}', variadic arguments are promoted to '{promoted}'"
+      )));
+    }
+  }
+  let parameters = parameters
+    .iter()
+    .cloned()
+    .map(libffi::middle::Type::try_from)
+    .collect::<Result<Vec<_>, _>>()?;
+  let result = result.clone().try_into()?;
+  Ok(match fixed_parameters {
+    Some(fixed_parameters) => {
+      libffi::middle::Cif::new_variadic(parameters, fixed_parameters, result)
+    }
+    None => libffi::middle::Cif::new(parameters, result),
+  })
 }
 
 #[derive(Clone)]
@@ -72,6 +266,7 @@ pub struct Symbol {
   pub ptr: libffi::middle::CodePtr,
   pub parameter_types: Vec<NativeType>,
   pub result_type: NativeType,
+  pub variadic: bool,
 }
 
 #[allow(clippy::non_send_fields_in_send_ty)]
@@ -79,3 +274,32 @@ pub struct Symbol {
 unsafe impl Send for Symbol {}
 // SAFETY: unsafe trait must have unsafe implementation
 unsafe impl Sync for Symbol {}
+
+#[cfg(test)]
+mod tests {
+  use super::NativeType;
+  use super::make_cif;
+
+  #[test]
+  fn make_cif_rejects_invalid_variadic_types() {
+    let parameters = [NativeType::Buffer, NativeType::I32];
+    assert!(make_cif(&parameters, &NativeType::I32, Some(1)).is_ok());
+
+    for (variadic, expected) in [
+      (NativeType::Void, "'void'"),
+      (
+        NativeType::Array {
+          element: Box::new(NativeType::U8),
+          length: 4,
+        },
+        "'array'",
+      ),
+      (NativeType::F32, "promoted to 'f64'"),
+    ] {
+      let parameters = [NativeType::Buffer, variadic];
+      let err = make_cif(&parameters, &NativeType::I32, Some(1)).unwrap_err();
+      assert_eq!(deno_error::JsErrorClass::get_class(&err), "TypeError");
+      assert!(err.to_string().contains(expected), "{err}");
+    }
+  }
+}
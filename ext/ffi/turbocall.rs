@@ -40,11 +40,11 @@ pub enum TurbocallError {
 }
 
 pub(crate) fn is_compatible(sym: &Symbol) -> bool {
-  !matches!(sym.result_type, NativeType::Struct(_))
-    && !sym
-      .parameter_types
-      .iter()
-      .any(|t| matches!(t, NativeType::Struct(_)))
+  // Variadic calls need a call interface per argument list, which the fast
+  // path can't provide.
+  !sym.variadic
+    && !sym.result_type.is_aggregate()
+    && !sym.parameter_types.iter().any(NativeType::is_aggregate)
 }
 
 /// Trampoline for fast-call FFI functions
@@ -133,7 +133,9 @@ pub(crate) fn compile_trampoline(
       NativeType::Pointer => AbiParam::new(ISIZE),
       NativeType::Buffer => AbiParam::new(ISIZE),
       NativeType::Function => AbiParam::new(ISIZE),
-      NativeType::Struct(_) => AbiParam::new(types::INVALID),
+      NativeType::Struct(_)
+      | NativeType::Union(_)
+      | NativeType::Array { .. } => AbiParam::new(types::INVALID),
       NativeType::Void => AbiParam::new(types::INVALID),
     }
   }
@@ -152,7 +154,7 @@ pub(crate) fn compile_trampoline(
   // const FastApiCallbackOptions& options
   wrapper_sig.params.push(AbiParam::new(ISIZE));
 
-  if !matches!(sym.result_type, NativeType::Struct(_) | NativeType::Void) {
+  if !sym.result_type.is_aggregate() && sym.result_type != NativeType::Void {
     target_sig.returns.push(convert(&sym.result_type, false));
     wrapper_sig.returns.push(convert(&sym.result_type, true));
   }
@@ -397,7 +399,9 @@ impl From<&NativeType> for fast_api::CTypeInfo {
         fast_api::Type::Pointer.as_info()
       }
       NativeType::Buffer => fast_api::Type::V8Value.as_info(),
-      NativeType::Struct(_) => fast_api::Type::V8Value.as_info(),
+      NativeType::Struct(_)
+      | NativeType::Union(_)
+      | NativeType::Array { .. } => fast_api::Type::V8Value.as_info(),
     }
   }
 }
@@ -84,6 +84,7 @@ const {
   ObjectDefineProperty,
   ObjectPrototypeIsPrototypeOf,
   ObjectValues,
+  String,
   StringPrototypeSlice,
   StringPrototypeStartsWith,
   SymbolAsyncIterator,
@@ -102,6 +103,25 @@ import {
   writableStreamForRid,
 } from "ext:deno_web/06_streams.js";
 import { pathFromURL } from "ext:deno_web/00_infra.js";
+import {
+  FS_TRACING_ENABLED,
+  FS_TRACING_THRESHOLD_MS,
+} from "ext:deno_telemetry/telemetry.ts";
+import { traceAsyncAboveThreshold } from "ext:deno_telemetry/util.ts";
+
+// Asynchronous file system calls that take at least `OTEL_DENO_FS_THRESHOLD_MS`
+// milliseconds (1 by default) are traced as internal spans named after the
+// `Deno` namespace function, with the path in the `file.path` attribute.
+function traceFs(operation, path, fn) {
+  if (!FS_TRACING_ENABLED) return fn();
+  return traceAsyncAboveThreshold(
+    `deno.fs.${operation}`,
+    0,
+    { "file.path": String(path) },
+    FS_TRACING_THRESHOLD_MS,
+    fn,
+  );
+}
 
 function chmodSync(path, mode) {
   op_fs_chmod_sync(pathFromURL(path), mode);
@@ -145,9 +165,11 @@ async function copyFile(
   fromPath,
   toPath,
 ) {
-  await op_fs_copy_file_async(
-    pathFromURL(fromPath),
-    pathFromURL(toPath),
+  fromPath = pathFromURL(fromPath);
+  await traceFs(
+    "copyFile",
+    fromPath,
+    () => op_fs_copy_file_async(fromPath, pathFromURL(toPath)),
   );
 }
 
@@ -200,10 +222,16 @@ function mkdirSync(path, options) {
 }
 
 async function mkdir(path, options) {
-  await op_fs_mkdir_async(
-    pathFromURL(path),
-    options?.recursive ?? false,
-    options?.mode,
+  path = pathFromURL(path);
+  await traceFs(
+    "mkdir",
+    path,
+    () =>
+      op_fs_mkdir_async(
+        path,
+        options?.recursive ?? false,
+        options?.mode,
+      ),
   );
 }
 
@@ -214,9 +242,8 @@ function readDirSync(path) {
 }
 
 function readDir(path) {
-  const array = op_fs_read_dir_async(
-    pathFromURL(path),
-  );
+  path = pathFromURL(path);
+  const array = traceFs("readDir", path, () => op_fs_read_dir_async(path));
   return {
     async *[SymbolAsyncIterator]() {
       const dir = await array;
@@ -257,9 +284,11 @@ async function remove(
   path,
   options = { __proto__: null },
 ) {
-  await op_fs_remove_async(
-    pathFromURL(path),
-    !!options.recursive,
+  path = pathFromURL(path);
+  await traceFs(
+    "remove",
+    path,
+    () => op_fs_remove_async(path, !!options.recursive),
   );
 }
 
@@ -271,9 +300,11 @@ function renameSync(oldpath, newpath) {
 }
 
 async function rename(oldpath, newpath) {
-  await op_fs_rename_async(
-    pathFromURL(oldpath),
-    pathFromURL(newpath),
+  oldpath = pathFromURL(oldpath);
+  await traceFs(
+    "rename",
+    oldpath,
+    () => op_fs_rename_async(oldpath, pathFromURL(newpath)),
   );
 }
 
@@ -414,7 +445,8 @@ function parseFileInfo(response) {
 }
 
 async function lstat(path) {
-  const res = await op_fs_lstat_async(pathFromURL(path));
+  path = pathFromURL(path);
+  const res = await traceFs("lstat", path, () => op_fs_lstat_async(path));
   return parseFileInfo(res);
 }
 
@@ -424,7 +456,8 @@ function lstatSync(path) {
 }
 
 async function stat(path) {
-  const res = await op_fs_stat_async(pathFromURL(path));
+  path = pathFromURL(path);
+  const res = await traceFs("stat", path, () => op_fs_stat_async(path));
   return parseFileInfo(res);
 }
 
@@ -555,9 +588,11 @@ async function open(
   options,
 ) {
   if (options) checkOpenOptions(options);
-  const rid = await op_fs_open_async(
-    pathFromURL(path),
-    options,
+  path = pathFromURL(path);
+  const rid = await traceFs(
+    "open",
+    path,
+    () => op_fs_open_async(path, options),
   );
 
   return new FsFile(rid, SymbolFor("Deno.internal.FsFile"));
@@ -776,9 +811,11 @@ async function readFile(path, options) {
   }
 
   try {
-    const read = await op_fs_read_file_async(
-      pathFromURL(path),
-      cancelRid,
+    path = pathFromURL(path);
+    const read = await traceFs(
+      "readFile",
+      path,
+      () => op_fs_read_file_async(path, cancelRid),
     );
     return read;
   } finally {
@@ -806,9 +843,11 @@ async function readTextFile(path, options) {
   }
 
   try {
-    const read = await op_fs_read_file_text_async(
-      pathFromURL(path),
-      cancelRid,
+    path = pathFromURL(path);
+    const read = await traceFs(
+      "readTextFile",
+      path,
+      () => op_fs_read_file_text_async(path, cancelRid),
     );
     return read;
   } finally {
@@ -864,14 +903,20 @@ async function writeFile(
         signal: options.signal,
       });
     } else {
-      await op_fs_write_file_async(
-        pathFromURL(path),
-        options.mode,
-        options.append ?? false,
-        options.create ?? true,
-        options.createNew ?? false,
-        data,
-        cancelRid,
+      path = pathFromURL(path);
+      await traceFs(
+        "writeFile",
+        path,
+        () =>
+          op_fs_write_file_async(
+            path,
+            options.mode,
+            options.append ?? false,
+            options.create ?? true,
+            options.createNew ?? false,
+            data,
+            cancelRid,
+          ),
       );
     }
   } finally {
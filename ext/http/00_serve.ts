@@ -20,6 +20,7 @@ import {
   op_http_serve,
   op_http_serve_address_override,
   op_http_serve_on,
+  op_http_serve_with_http3,
   op_http_set_promise_complete,
   op_http_set_response_body_bytes,
   op_http_set_response_body_resource,
@@ -105,6 +106,8 @@ import {
   updateSpanFromResponse,
 } from "ext:deno_telemetry/util.ts";
 
+const loadQuic = core.createLazyLoader("ext:deno_net/03_quic.js");
+
 const _upgraded = Symbol("_upgraded");
 
 function internalServerError() {
@@ -198,7 +201,7 @@ class InnerRequest {
     return this.#upgraded;
   }
 
-  _wantsUpgrade(upgradeType) {
+  _wantsUpgrade(upgradeType, wsExtensions) {
     if (this.#upgraded) {
       throw new Deno.errors.Http("Already upgraded");
     }
@@ -235,7 +238,7 @@ class InnerRequest {
 
       this.#upgraded = true;
 
-      return op_http_upgrade_websocket_next(external);
+      return op_http_upgrade_websocket_next(external, wsExtensions);
     }
   }
 
@@ -400,10 +403,11 @@ class CallbackContext {
   /** @type {Promise<void> | undefined} */
   closing;
   listener;
+  quicEndpoint;
   asyncContextSnapshot;
   legacyAbort;
 
-  constructor(signal, args, listener) {
+  constructor(signal, args, listener, quicEndpoint) {
     this.asyncContextSnapshot = currentSnapshot();
     // The abort signal triggers a non-graceful shutdown
     signal?.addEventListener(
@@ -420,12 +424,14 @@ class CallbackContext {
     this.legacyAbort = args[3] == false;
     this.closed = false;
     this.listener = listener;
+    this.quicEndpoint = quicEndpoint;
   }
 
   close() {
     try {
       this.closed = true;
       core.tryClose(this.serverRid);
+      this.quicEndpoint?.close();
     } catch {
       // Pass
     }
@@ -608,7 +614,7 @@ function mapToCallback(context, callback, onError) {
     const origMapped = mapped;
     mapped = function (req, _span) {
       const snapshot = currentSnapshot();
-      restoreSnapshot(context.asyncContext);
+      restoreSnapshot(context.asyncContextSnapshot);
 
       const reqHeaders = op_http_get_request_headers(req);
       const headers: [key: string, value: string][] = [];
@@ -652,7 +658,7 @@ function mapToCallback(context, callback, onError) {
     const origMapped = mapped;
     mapped = function (req, span) {
       const snapshot = currentSnapshot();
-      restoreSnapshot(context.asyncContext);
+      restoreSnapshot(context.asyncContextSnapshot);
       try {
         return origMapped(req, span);
       } finally {
@@ -676,6 +682,7 @@ type RawServeOptions = {
   reusePort?: boolean;
   key?: string;
   cert?: string;
+  http3?: boolean;
   onError?: (error: unknown) => Response | Promise<Response>;
   onListen?: (params: { hostname: string; port: number }) => void;
   handler?: RawHandler;
@@ -916,7 +923,14 @@ function serveInner(options, handler) {
     );
   }
 
+  if (options.http3 && !wantsHttps) {
+    throw new TypeError(
+      "Both 'cert' and 'key' must be provided to enable HTTP/3",
+    );
+  }
+
   let listener;
+  let quicListener;
   if (wantsHttps) {
     if (!options.cert || !options.key) {
       throw new TypeError(
@@ -928,6 +942,26 @@ function serveInner(options, handler) {
     listenOpts.alpnProtocols = ["h2", "http/1.1"];
     listener = listenTls(listenOpts);
     listenOpts.port = listener.addr.port;
+    if (options.http3) {
+      // HTTP/3 is served over QUIC on the same port, but over UDP
+      let endpoint;
+      try {
+        const { QuicEndpoint } = loadQuic();
+        endpoint = new QuicEndpoint({
+          hostname: listenOpts.hostname,
+          port: listenOpts.port,
+        });
+        quicListener = endpoint.listen({
+          cert: options.cert,
+          key: options.key,
+          alpnProtocols: ["h3"],
+        });
+      } catch (error) {
+        endpoint?.close();
+        listener.close();
+        throw error;
+      }
+    }
   } else {
     listener = listen(listenOpts);
     listenOpts.port = listener.addr.port;
@@ -951,17 +985,43 @@ function serveInner(options, handler) {
     }
   };
 
-  return serveHttpOnListener(listener, signal, handler, onError, onListen);
+  return serveHttpOnListener(
+    listener,
+    signal,
+    handler,
+    onError,
+    onListen,
+    quicListener,
+  );
 }
 
 /**
- * Serve HTTP/1.1 and/or HTTP/2 on an arbitrary listener.
+ * Serve HTTP/1.1 and/or HTTP/2 on an arbitrary listener, along with HTTP/3
+ * when a QUIC listener is provided.
  */
-function serveHttpOnListener(listener, signal, handler, onError, onListen) {
+function serveHttpOnListener(
+  listener,
+  signal,
+  handler,
+  onError,
+  onListen,
+  quicListener,
+) {
+  let args;
+  if (quicListener) {
+    const { getListenerResource } = loadQuic();
+    args = op_http_serve_with_http3(
+      listener[internalRidSymbol],
+      getListenerResource(quicListener),
+    );
+  } else {
+    args = op_http_serve(listener[internalRidSymbol]);
+  }
   const context = new CallbackContext(
     signal,
-    op_http_serve(listener[internalRidSymbol]),
+    args,
     listener,
+    quicListener?.endpoint,
   );
   const callback = mapToCallback(context, handler, onError);
 
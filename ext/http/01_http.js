@@ -45,6 +45,7 @@ import {
 } from "ext:deno_fetch/23_request.js";
 import {
   _eventLoop,
+  _extensions,
   _idleTimeoutDuration,
   _idleTimeoutTimeout,
   _protocol,
@@ -360,6 +361,7 @@ function createRespondWith(
       if (ws) {
         const wsRid = await op_http_upgrade_websocket(
           readStreamRid,
+          ws[_extensions],
         );
         ws[_rid] = wsRid;
         ws[_protocol] = resp.headers.get("sec-websocket-protocol");
@@ -1,6 +1,9 @@
 // Copyright 2018-2026 the Deno authors. MIT license.
 import { internals, primordials } from "ext:core/mod.js";
-import { op_http_websocket_accept_header } from "ext:core/ops";
+import {
+  op_http_websocket_accept_header,
+  op_ws_negotiate_per_message_deflate,
+} from "ext:core/ops";
 const {
   ArrayPrototypeIncludes,
   ArrayPrototypeMap,
@@ -20,6 +23,7 @@ import {
 import { setEventTargetData } from "ext:deno_web/02_event.js";
 import {
   _eventLoop,
+  _extensions,
   _idleTimeoutDuration,
   _idleTimeoutTimeout,
   _protocol,
@@ -29,6 +33,7 @@ import {
   _serverHandleIdleTimeout,
   createWebSocketBranded,
   SERVER,
+  toPerMessageDeflateOptions,
   WebSocket,
 } from "ext:deno_websocket/01_websocket.js";
 
@@ -88,15 +93,33 @@ function upgradeWebSocket(request, options = { __proto__: null }) {
     }
   }
 
+  const perMessageDeflate = toPerMessageDeflateOptions(
+    options.perMessageDeflate,
+    "Failed to execute 'Deno.upgradeWebSocket'",
+    "Argument 2",
+  );
+  const extensionsOffer = request.headers.get("sec-websocket-extensions");
+  let extensions = "";
+  if (perMessageDeflate !== null && extensionsOffer !== null) {
+    extensions = op_ws_negotiate_per_message_deflate(
+      extensionsOffer,
+      perMessageDeflate,
+    ) ?? "";
+  }
+  if (extensions !== "") {
+    ArrayPrototypePush(r.headerList, ["sec-websocket-extensions", extensions]);
+  }
+
   const socket = createWebSocketBranded(WebSocket);
   setEventTargetData(socket);
   socket[_role] = SERVER;
+  socket[_extensions] = extensions;
   // Nginx timeout is 60s, so default to a lower number: https://github.com/denoland/deno/pull/23985
   socket[_idleTimeoutDuration] = options.idleTimeout ?? 30;
   socket[_idleTimeoutTimeout] = null;
 
   if (inner._wantsUpgrade) {
-    const wsPromise = inner._wantsUpgrade("upgradeWebSocket");
+    const wsPromise = inner._wantsUpgrade("upgradeWebSocket", extensions);
 
     // Start the upgrade in the background.
     (async () => {
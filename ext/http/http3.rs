@@ -0,0 +1,193 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+//! Serves HTTP/3 over QUIC for `Deno.serve`. Requests are handed to the
+//! same [`handle_request`] pipeline as HTTP/1.1 and HTTP/2.
+
+use std::future::poll_fn;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::Context;
+use std::task::Poll;
+use std::task::ready;
+
+use bytes::Buf;
+use bytes::Bytes;
+use deno_core::CancelFuture;
+use deno_core::CancelHandle;
+use deno_core::CancelTryFuture;
+use deno_core::unsync::spawn;
+use deno_error::JsErrorBox;
+use deno_net::raw::NetworkStreamType;
+use h3::error::StreamError;
+use h3::server::RequestResolver;
+use h3::server::RequestStream;
+use hyper::body::Body;
+use hyper::body::Frame;
+use scopeguard::guard;
+
+use crate::http_next::HttpNextError;
+use crate::request_body::RequestBody;
+use crate::request_properties::HttpConnectionProperties;
+use crate::service::HttpRecord;
+use crate::service::HttpServerState;
+use crate::service::SignallingRc;
+use crate::service::handle_request;
+
+type H3Connection = h3_quinn::Connection;
+
+/// The body of a request received over HTTP/3.
+pub struct Http3RequestBody(RequestStream<h3_quinn::RecvStream, Bytes>);
+
+impl Body for Http3RequestBody {
+  type Data = Bytes;
+  type Error = StreamError;
+
+  fn poll_frame(
+    self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+  ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+    match ready!(self.get_mut().0.poll_recv_data(cx)) {
+      Ok(Some(mut data)) => {
+        let data = data.copy_to_bytes(data.remaining());
+        Poll::Ready(Some(Ok(Frame::data(data))))
+      }
+      Ok(None) => Poll::Ready(None),
+      Err(err) => Poll::Ready(Some(Err(err))),
+    }
+  }
+}
+
+/// Accepts QUIC connections on the endpoint and serves HTTP/3 on them until
+/// the listen cancel handle is triggered.
+pub(crate) async fn serve_http3(
+  endpoint: quinn::Endpoint,
+  server_state: SignallingRc<HttpServerState>,
+  connection_cancel_handle: Rc<CancelHandle>,
+  listen_cancel_handle: Rc<CancelHandle>,
+  tx: tokio::sync::mpsc::Sender<Rc<HttpRecord>>,
+  legacy_abort: bool,
+) -> Result<(), HttpNextError> {
+  let local_port = endpoint.local_addr()?.port();
+  server_state.set_http3_port(local_port);
+  loop {
+    let Some(incoming) = endpoint
+      .accept()
+      .or_cancel(listen_cancel_handle.clone())
+      .await?
+    else {
+      // the endpoint was closed
+      return Ok(());
+    };
+    let remote_address = incoming.remote_address();
+    let request_info = HttpConnectionProperties {
+      peer_address: remote_address.ip().to_string().into(),
+      peer_port: Some(remote_address.port() as _),
+      local_port: Some(local_port as _),
+      stream_type: NetworkStreamType::Tls,
+    };
+    spawn(
+      serve_http3_connection(
+        incoming,
+        request_info,
+        server_state.clone(),
+        listen_cancel_handle.clone(),
+        tx.clone(),
+        legacy_abort,
+      )
+      .try_or_cancel(connection_cancel_handle.clone()),
+    );
+  }
+}
+
+async fn serve_http3_connection(
+  incoming: quinn::Incoming,
+  request_info: HttpConnectionProperties,
+  server_state: SignallingRc<HttpServerState>,
+  listen_cancel_handle: Rc<CancelHandle>,
+  tx: tokio::sync::mpsc::Sender<Rc<HttpRecord>>,
+  legacy_abort: bool,
+) -> Result<(), HttpNextError> {
+  let connection = incoming.await?;
+  let mut connection = h3::server::Connection::<H3Connection, Bytes>::new(
+    h3_quinn::Connection::new(connection),
+  )
+  .await?;
+
+  // Requests are cancelled when the connection goes away, the same as when
+  // hyper drops the service future for a closed connection.
+  let requests_cancel_handle = guard(CancelHandle::new_rc(), |handle| {
+    handle.cancel();
+  });
+  let mut shutting_down = false;
+  loop {
+    let accepted = if shutting_down {
+      connection.accept().await
+    } else {
+      match connection
+        .accept()
+        .or_cancel(listen_cancel_handle.clone())
+        .await
+      {
+        Ok(accepted) => accepted,
+        Err(_) => {
+          // stop accepting new requests and let the in-flight ones finish
+          shutting_down = true;
+          connection.shutdown(0).await?;
+          continue;
+        }
+      }
+    };
+    match accepted {
+      Ok(Some(resolver)) => {
+        spawn(
+          serve_http3_request(
+            resolver,
+            request_info.clone(),
+            server_state.clone(),
+            tx.clone(),
+            legacy_abort,
+          )
+          .try_or_cancel(requests_cancel_handle.clone()),
+        );
+      }
+      Ok(None) => return Ok(()),
+      Err(err) if err.is_h3_no_error() => return Ok(()),
+      Err(err) => return Err(err.into()),
+    }
+  }
+}
+
+async fn serve_http3_request(
+  resolver: RequestResolver<H3Connection, Bytes>,
+  request_info: HttpConnectionProperties,
+  server_state: SignallingRc<HttpServerState>,
+  tx: tokio::sync::mpsc::Sender<Rc<HttpRecord>>,
+  legacy_abort: bool,
+) -> Result<(), HttpNextError> {
+  let (request, stream) = resolver.resolve_request().await?;
+  let (mut send, recv) = stream.split();
+  let request = request.map(|()| RequestBody::Http3(Http3RequestBody(recv)));
+  let response =
+    handle_request(request, request_info, server_state, tx, legacy_abort)
+      .await
+      .map_err(|err| JsErrorBox::generic(err.to_string()))?;
+  let (parts, mut body) = response.into_parts();
+  send
+    .send_response(http::Response::from_parts(parts, ()))
+    .await?;
+  while let Some(frame) = poll_fn(|cx| Pin::new(&mut body).poll_frame(cx)).await
+  {
+    let frame = match frame.map_err(HttpNextError::Other)?.into_data() {
+      Ok(mut data) => {
+        send.send_data(data.copy_to_bytes(data.remaining())).await?;
+        continue;
+      }
+      Err(frame) => frame,
+    };
+    if let Ok(trailers) = frame.into_trailers() {
+      send.send_trailers(trailers).await?;
+    }
+  }
+  send.finish().await?;
+  Ok(())
+}
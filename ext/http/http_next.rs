@@ -8,6 +8,7 @@ use std::io;
 use std::pin::Pin;
 use std::ptr::null;
 use std::rc::Rc;
+use std::sync::Arc;
 
 use bytes::Bytes;
 use bytes::BytesMut;
@@ -29,10 +30,13 @@ use deno_core::ResourceId;
 use deno_core::convert::ByteString;
 use deno_core::external;
 use deno_core::futures::TryFutureExt;
+use deno_core::futures::future::try_join;
 use deno_core::op2;
 use deno_core::unsync::JoinHandle;
 use deno_core::unsync::spawn;
 use deno_core::v8;
+use deno_features::FeatureChecker;
+use deno_net::QuicListenerResource;
 use deno_net::ops_tls::TlsStream;
 use deno_net::raw::NetworkStream;
 use deno_net::raw::NetworkStreamReadHalf;
@@ -68,6 +72,7 @@ use crate::LocalExecutor;
 use crate::Options;
 use crate::compressible::is_content_compressible;
 use crate::extract_network_stream;
+use crate::http3::serve_http3;
 use crate::network_buffered_stream::NetworkStreamPrefixCheck;
 use crate::request_body::HttpRequestBody;
 use crate::request_properties::HttpConnectionProperties;
@@ -192,6 +197,15 @@ pub enum HttpNextError {
   #[class("Http")]
   #[error("raw upgrade failed")]
   RawUpgradeFailed,
+  #[class("Http")]
+  #[error("{0}")]
+  QuicConnection(#[from] quinn::ConnectionError),
+  #[class("Http")]
+  #[error("{0}")]
+  Http3Connection(#[from] h3::error::ConnectionError),
+  #[class("Http")]
+  #[error("{0}")]
+  Http3Stream(#[from] h3::error::StreamError),
 }
 
 #[op2(fast)]
@@ -223,6 +237,7 @@ pub fn op_http_upgrade_raw(
 pub async fn op_http_upgrade_websocket_next(
   state: Rc<RefCell<OpState>>,
   external: *const c_void,
+  #[string] extensions: String,
 ) -> Result<ResourceId, HttpNextError> {
   let upgrade = {
     // SAFETY: op is called with external.
@@ -238,6 +253,7 @@ pub async fn op_http_upgrade_websocket_next(
     &mut state.borrow_mut(),
     stream,
     bytes,
+    &extensions,
   ))
 }
 
@@ -1082,6 +1098,42 @@ pub fn op_http_serve<HTTP>(
   state: Rc<RefCell<OpState>>,
   #[smi] listener_rid: ResourceId,
 ) -> Result<(ResourceId, &'static str, String, bool), HttpNextError>
+where
+  HTTP: HttpPropertyExtractor,
+{
+  serve_listener::<HTTP>(state, listener_rid, None)
+}
+
+/// Serves HTTP/1.1 and HTTP/2 on the listener along with HTTP/3 on the QUIC
+/// listener, which is advertised to clients with the `Alt-Svc` header.
+#[op2]
+pub fn op_http_serve_with_http3<HTTP>(
+  state: Rc<RefCell<OpState>>,
+  #[smi] listener_rid: ResourceId,
+  #[cppgc] quic_listener: &QuicListenerResource,
+) -> Result<(ResourceId, &'static str, String, bool), HttpNextError>
+where
+  HTTP: HttpPropertyExtractor,
+{
+  state
+    .borrow()
+    .borrow::<Arc<FeatureChecker>>()
+    .check_or_exit(
+      deno_net::UNSTABLE_FEATURE_NAME,
+      "Deno.serve({ http3: true })",
+    );
+  serve_listener::<HTTP>(
+    state,
+    listener_rid,
+    Some(quic_listener.endpoint().clone()),
+  )
+}
+
+fn serve_listener<HTTP>(
+  state: Rc<RefCell<OpState>>,
+  listener_rid: ResourceId,
+  quic_endpoint: Option<quinn::Endpoint>,
+) -> Result<(ResourceId, &'static str, String, bool), HttpNextError>
 where
   HTTP: HttpPropertyExtractor,
 {
@@ -1101,22 +1153,39 @@ where
     *state.borrow::<Options>()
   };
 
+  let http3 = quic_endpoint.map(|endpoint| {
+    serve_http3(
+      endpoint,
+      lifetime.server_state.clone(),
+      lifetime.connection_cancel_handle.clone(),
+      lifetime.listen_cancel_handle.clone(),
+      tx.clone(),
+      !options.no_legacy_abort,
+    )
+  });
+
   let listen_properties_clone: HttpListenProperties = listen_properties.clone();
   let handle = spawn(async move {
-    loop {
-      let conn = HTTP::accept_connection_from_listener(&listener)
-        .try_or_cancel(listen_cancel_clone.clone())
-        .await?;
-      serve_http_on::<HTTP>(
-        conn,
-        &listen_properties_clone,
-        lifetime.clone(),
-        tx.clone(),
-        options,
-      );
+    let http = async {
+      loop {
+        let conn = HTTP::accept_connection_from_listener(&listener)
+          .try_or_cancel(listen_cancel_clone.clone())
+          .await?;
+        serve_http_on::<HTTP>(
+          conn,
+          &listen_properties_clone,
+          lifetime.clone(),
+          tx.clone(),
+          options,
+        );
+      }
+      #[allow(unreachable_code)]
+      Ok::<_, HttpNextError>(())
+    };
+    match http3 {
+      Some(http3) => try_join(http, http3).await.map(|_| ()),
+      None => http.await,
     }
-    #[allow(unreachable_code)]
-    Ok::<_, HttpNextError>(())
   });
 
   // Set the handle after we start the future
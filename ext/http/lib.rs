@@ -90,6 +90,7 @@ use crate::reader_stream::ShutdownHandle;
 
 pub mod compressible;
 mod fly_accept_encoding;
+mod http3;
 mod http_next;
 mod network_buffered_stream;
 mod reader_stream;
@@ -162,6 +163,7 @@ deno_core::extension!(
     http_next::op_http_read_request_body,
     http_next::op_http_serve_on<HTTP>,
     http_next::op_http_serve<HTTP>,
+    http_next::op_http_serve_with_http3<HTTP>,
     http_next::op_http_set_promise_complete,
     http_next::op_http_set_response_body_bytes,
     http_next::op_http_set_response_body_resource,
@@ -212,6 +214,7 @@ deno_core::extension!(
     http_next::op_http_read_request_body,
     http_next::op_http_serve_on<DefaultHttpPropertyExtractor>,
     http_next::op_http_serve<DefaultHttpPropertyExtractor>,
+    http_next::op_http_serve_with_http3<DefaultHttpPropertyExtractor>,
     http_next::op_http_set_promise_complete,
     http_next::op_http_set_response_body_bytes,
     http_next::op_http_set_response_body_resource,
@@ -1518,6 +1521,7 @@ fn op_http_websocket_accept_header(#[string] key: String) -> String {
 async fn op_http_upgrade_websocket(
   state: Rc<RefCell<OpState>>,
   #[smi] rid: ResourceId,
+  #[string] extensions: String,
 ) -> Result<ResourceId, HttpError> {
   let stream = state
     .borrow_mut()
@@ -1543,6 +1547,7 @@ async fn op_http_upgrade_websocket(
     &mut state.borrow_mut(),
     transport,
     bytes,
+    &extensions,
   ))
 }
 
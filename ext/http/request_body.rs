@@ -17,14 +17,65 @@ use deno_core::futures::TryFutureExt;
 use deno_core::futures::stream::Peekable;
 use deno_error::JsErrorBox;
 use hyper::body::Body;
+use hyper::body::Frame;
 use hyper::body::Incoming;
 use hyper::body::SizeHint;
 
-/// Converts a hyper incoming body stream into a stream of [`Bytes`] that we can use to read in V8.
-struct ReadFuture(Incoming);
+use crate::http3::Http3RequestBody;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// The body of a request received over HTTP/1.1 and HTTP/2 by hyper or over
+/// HTTP/3.
+pub enum RequestBody {
+  Incoming(Incoming),
+  Http3(Http3RequestBody),
+}
+
+impl From<Incoming> for RequestBody {
+  fn from(value: Incoming) -> Self {
+    RequestBody::Incoming(value)
+  }
+}
+
+impl Body for RequestBody {
+  type Data = Bytes;
+  type Error = BoxError;
+
+  fn poll_frame(
+    self: Pin<&mut Self>,
+    cx: &mut std::task::Context<'_>,
+  ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+    match self.get_mut() {
+      RequestBody::Incoming(body) => {
+        Pin::new(body).poll_frame(cx).map_err(Into::into)
+      }
+      RequestBody::Http3(body) => {
+        Pin::new(body).poll_frame(cx).map_err(Into::into)
+      }
+    }
+  }
+
+  fn is_end_stream(&self) -> bool {
+    match self {
+      RequestBody::Incoming(body) => body.is_end_stream(),
+      RequestBody::Http3(body) => body.is_end_stream(),
+    }
+  }
+
+  fn size_hint(&self) -> SizeHint {
+    match self {
+      RequestBody::Incoming(body) => body.size_hint(),
+      RequestBody::Http3(body) => body.size_hint(),
+    }
+  }
+}
+
+/// Converts an incoming body stream into a stream of [`Bytes`] that we can use to read in V8.
+struct ReadFuture(RequestBody);
 
 impl Stream for ReadFuture {
-  type Item = Result<Bytes, hyper::Error>;
+  type Item = Result<Bytes, BoxError>;
 
   fn poll_next(
     self: Pin<&mut Self>,
@@ -55,12 +106,12 @@ impl Stream for ReadFuture {
 pub struct HttpRequestBody(AsyncRefCell<Peekable<ReadFuture>>, SizeHint);
 
 impl HttpRequestBody {
-  pub fn new(body: Incoming) -> Self {
+  pub fn new(body: RequestBody) -> Self {
     let size_hint = body.size_hint();
     Self(AsyncRefCell::new(ReadFuture(body).peekable()), size_hint)
   }
 
-  async fn read(self: Rc<Self>, limit: usize) -> Result<BufView, hyper::Error> {
+  async fn read(self: Rc<Self>, limit: usize) -> Result<BufView, BoxError> {
     let peekable = RcRef::map(self, |this| &this.0);
     let mut peekable = peekable.borrow_mut().await;
     match Pin::new(&mut *peekable).peek_mut().await {
@@ -22,9 +22,10 @@ use deno_error::JsErrorBox;
 use http::request::Parts;
 use hyper::body::Body;
 use hyper::body::Frame;
-use hyper::body::Incoming;
 use hyper::body::SizeHint;
+use hyper::header::ALT_SVC;
 use hyper::header::HeaderMap;
+use hyper::header::HeaderValue;
 use hyper::upgrade::OnUpgrade;
 use scopeguard::ScopeGuard;
 use scopeguard::guard;
@@ -32,11 +33,12 @@ use tokio::sync::oneshot;
 
 use crate::OtelInfo;
 use crate::OtelInfoAttributes;
+use crate::request_body::RequestBody;
 use crate::request_properties::HttpConnectionProperties;
 use crate::response_body::ResponseBytesInner;
 use crate::response_body::ResponseStreamResult;
 
-pub type Request = hyper::Request<Incoming>;
+pub type Request = hyper::Request<RequestBody>;
 pub type Response = hyper::Response<HttpRecordResponse>;
 
 #[cfg(feature = "__http_tracing")]
@@ -80,6 +82,9 @@ pub(crate) use http_trace;
 
 pub(crate) struct HttpServerStateInner {
   pool: Vec<(Rc<HttpRecord>, HeaderMap)>,
+  /// The `Alt-Svc` header advertising HTTP/3 for responses sent over
+  /// HTTP/1.1 and HTTP/2.
+  alt_svc: Option<HeaderValue>,
 }
 
 /// A signalling version of `Rc` that allows one to poll for when all other references
@@ -143,8 +148,15 @@ impl HttpServerState {
   pub fn new() -> SignallingRc<Self> {
     SignallingRc::new(Self(RefCell::new(HttpServerStateInner {
       pool: Vec::new(),
+      alt_svc: None,
     })))
   }
+
+  /// Advertises that HTTP/3 is served on the provided UDP port.
+  pub fn set_http3_port(&self, port: u16) {
+    self.borrow_mut().alt_svc =
+      HeaderValue::try_from(format!("h3=\":{port}\"; ma=86400")).ok();
+  }
 }
 
 impl std::ops::Deref for HttpServerState {
@@ -156,12 +168,12 @@ impl std::ops::Deref for HttpServerState {
 }
 
 enum RequestBodyState {
-  Incoming(Incoming),
+  Incoming(RequestBody),
   Resource(#[allow(dead_code)] HttpRequestBodyAutocloser),
 }
 
-impl From<Incoming> for RequestBodyState {
-  fn from(value: Incoming) -> Self {
+impl From<RequestBody> for RequestBodyState {
+  fn from(value: RequestBody) -> Self {
     RequestBodyState::Incoming(value)
   }
 }
@@ -203,12 +215,13 @@ fn validate_request(req: &Request) -> bool {
 }
 
 pub(crate) async fn handle_request(
-  request: Request,
+  request: hyper::Request<impl Into<RequestBody>>,
   request_info: HttpConnectionProperties,
   server_state: SignallingRc<HttpServerState>, // Keep server alive for duration of this future.
   tx: tokio::sync::mpsc::Sender<Rc<HttpRecord>>,
   legacy_abort: bool,
 ) -> Result<Response, hyper_v014::Error> {
+  let request = request.map(Into::into);
   if !validate_request(&request) {
     let mut response = Response::new(HttpRecordResponse(None));
     *response.version_mut() = request.version();
@@ -467,8 +480,8 @@ impl HttpRecord {
       .ok_or(UpgradeUnavailableError)
   }
 
-  /// Take the Hyper body from this record.
-  pub fn take_request_body(&self) -> Option<Incoming> {
+  /// Take the request body from this record.
+  pub fn take_request_body(&self) -> Option<RequestBody> {
     let body_holder = &mut self.self_mut().request_body;
     let body = body_holder.take();
     match body {
@@ -563,7 +576,15 @@ impl HttpRecord {
 
   /// Take the response.
   fn into_response(self: Rc<Self>) -> Response {
-    let parts = self.self_mut().response_parts.take().unwrap();
+    let mut inner = self.self_mut();
+    let mut parts = inner.response_parts.take().unwrap();
+    if inner.request_parts.version != http::Version::HTTP_3
+      && let Some(alt_svc) = &inner.server_state.borrow().alt_svc
+      && !parts.headers.contains_key(ALT_SVC)
+    {
+      parts.headers.insert(ALT_SVC, alt_svc.clone());
+    }
+    drop(inner);
     let body = HttpRecordResponse(Some(ManuallyDrop::new(self)));
     Response::from_parts(parts, body)
   }
@@ -761,6 +782,7 @@ mod tests {
   use bytes::Buf;
   use deno_net::raw::NetworkStreamType;
   use hyper::body::Body;
+  use hyper::body::Incoming;
   use hyper::service::HttpService;
   use hyper::service::service_fn;
   use hyper_util::rt::TokioIo;
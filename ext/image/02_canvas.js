@@ -0,0 +1,716 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+import { primordials } from "ext:core/mod.js";
+import {
+  CanvasGradient,
+  OffscreenCanvas,
+  OffscreenCanvasRenderingContext2D,
+  op_image_encode_canvas,
+  op_image_font_face_parse,
+  op_image_fonts_add,
+  op_image_fonts_delete,
+  Path2D,
+  TextMetrics,
+} from "ext:core/ops";
+import * as webidl from "ext:deno_webidl/00_webidl.js";
+import { DOMException } from "ext:deno_web/01_dom_exception.js";
+import { createFilteredInspectProxy } from "ext:deno_web/01_console.js";
+import { Blob } from "ext:deno_web/09_file.js";
+import {
+  _data,
+  _height,
+  _width,
+  ImageData,
+  ImageDataPrototype,
+} from "ext:deno_web/16_image_data.js";
+const {
+  ArrayPrototypeIndexOf,
+  ArrayPrototypePush,
+  ArrayPrototypeSlice,
+  ArrayPrototypeSplice,
+  FunctionPrototypeCall,
+  MathAbs,
+  MathRound,
+  NumberIsInteger,
+  NumberParseFloat,
+  ObjectDefineProperties,
+  ObjectDefineProperty,
+  ObjectPrototypeIsPrototypeOf,
+  PromisePrototypeCatch,
+  PromiseReject,
+  PromiseResolve,
+  PromiseWithResolvers,
+  RangeError,
+  RegExpPrototypeExec,
+  SafeMap,
+  StringPrototypeToLowerCase,
+  StringPrototypeTrim,
+  Symbol,
+  SymbolFor,
+  SymbolIterator,
+  TypeError,
+  TypedArrayPrototypeGetBuffer,
+  TypedArrayPrototypeGetByteLength,
+  TypedArrayPrototypeGetByteOffset,
+  TypedArrayPrototypeGetLength,
+  TypedArrayPrototypeGetSymbolToStringTag,
+  Uint8Array,
+  Uint8ClampedArray,
+} = primordials;
+
+const OffscreenCanvasPrototype = OffscreenCanvas.prototype;
+const OffscreenCanvasRenderingContext2DPrototype =
+  OffscreenCanvasRenderingContext2D.prototype;
+
+webidl.converters["ImageEncodeOptions"] = webidl.createDictionaryConverter(
+  "ImageEncodeOptions",
+  [
+    {
+      key: "type",
+      converter: webidl.converters["DOMString"],
+      defaultValue: "image/png",
+    },
+    {
+      key: "quality",
+      converter: webidl.converters["unrestricted double"],
+    },
+  ],
+);
+
+const method = (value) => ({
+  __proto__: null,
+  value,
+  enumerable: false,
+  configurable: true,
+  writable: true,
+});
+
+function convertToBlob(options = { __proto__: null }) {
+  try {
+    if (!ObjectPrototypeIsPrototypeOf(OffscreenCanvasPrototype, this)) {
+      throw new TypeError("Illegal invocation");
+    }
+    const prefix = "Failed to execute 'convertToBlob' on 'OffscreenCanvas'";
+    options = webidl.converters["ImageEncodeOptions"](
+      options,
+      prefix,
+      "Argument 1",
+    );
+    if (this.width === 0 || this.height === 0) {
+      throw new DOMException(
+        `${prefix}: The canvas has a width or height of 0`,
+        "IndexSizeError",
+      );
+    }
+
+    // Unsupported types fall back to PNG, as in browsers.
+    let type = StringPrototypeToLowerCase(options.type);
+    if (type !== "image/jpeg" && type !== "image/webp") {
+      type = "image/png";
+    }
+    let quality = options.quality;
+    if (quality === undefined || !(quality >= 0 && quality <= 1)) {
+      quality = 0.92;
+    }
+
+    return (async () => {
+      const data = await op_image_encode_canvas(this, type, quality);
+      return new Blob([data], { type });
+    })();
+  } catch (err) {
+    return PromiseReject(err);
+  }
+}
+
+ObjectDefineProperties(OffscreenCanvasPrototype, {
+  convertToBlob: method(convertToBlob),
+});
+
+function float16ToUnorm8(data) {
+  const length = TypedArrayPrototypeGetLength(data);
+  const result = new Uint8ClampedArray(length);
+  for (let i = 0; i < length; i++) {
+    result[i] = data[i] * 255;
+  }
+  return result;
+}
+
+function assertContext(self) {
+  if (
+    !ObjectPrototypeIsPrototypeOf(
+      OffscreenCanvasRenderingContext2DPrototype,
+      self,
+    )
+  ) {
+    throw new TypeError("Illegal invocation");
+  }
+}
+
+function assertImageData(value, prefix, context) {
+  if (!ObjectPrototypeIsPrototypeOf(ImageDataPrototype, value)) {
+    throw new TypeError(`${prefix}: ${context} is not of type 'ImageData'`);
+  }
+}
+
+// Matches `MAX_CANVAS_AREA` in context2d.rs.
+const MAX_CANVAS_AREA = 2 ** 28;
+
+const _getImageData = SymbolFor("Deno_getImageData");
+const _putImageData = SymbolFor("Deno_putImageData");
+
+function createImageData(sw, sh = undefined, settings = undefined) {
+  assertContext(this);
+  const prefix =
+    "Failed to execute 'createImageData' on 'OffscreenCanvasRenderingContext2D'";
+  webidl.requiredArguments(arguments.length, 1, prefix);
+  if (arguments.length === 1) {
+    assertImageData(sw, prefix, "Argument 1");
+    return new ImageData(sw[_width], sw[_height], {
+      colorSpace: sw.colorSpace,
+    });
+  }
+  sw = webidl.converters["long"](sw, prefix, "Argument 1");
+  sh = webidl.converters["long"](sh, prefix, "Argument 2");
+  settings = webidl.converters["ImageDataSettings"](
+    settings,
+    prefix,
+    "Argument 3",
+  );
+  if (sw === 0 || sh === 0) {
+    throw new DOMException(
+      `${prefix}: The source width and height must not be 0`,
+      "IndexSizeError",
+    );
+  }
+  return new ImageData(MathAbs(sw), MathAbs(sh), settings);
+}
+
+function getImageData(sx, sy, sw, sh, settings = undefined) {
+  assertContext(this);
+  const prefix =
+    "Failed to execute 'getImageData' on 'OffscreenCanvasRenderingContext2D'";
+  webidl.requiredArguments(arguments.length, 4, prefix);
+  sx = webidl.converters["long"](sx, prefix, "Argument 1");
+  sy = webidl.converters["long"](sy, prefix, "Argument 2");
+  sw = webidl.converters["long"](sw, prefix, "Argument 3");
+  sh = webidl.converters["long"](sh, prefix, "Argument 4");
+  settings = webidl.converters["ImageDataSettings"](
+    settings,
+    prefix,
+    "Argument 5",
+  );
+  if (sw === 0 || sh === 0) {
+    throw new DOMException(
+      `${prefix}: The source width and height must not be 0`,
+      "IndexSizeError",
+    );
+  }
+  if (sw < 0) {
+    sx += sw;
+    sw = -sw;
+  }
+  if (sh < 0) {
+    sy += sh;
+    sh = -sh;
+  }
+  if (sw * sh > MAX_CANVAS_AREA) {
+    throw new RangeError(`${prefix}: Out of memory at ImageData creation`);
+  }
+
+  const data = this[_getImageData](sx, sy, sw, sh);
+  if (settings.pixelFormat === "rgba-float16") {
+    const imageData = new ImageData(sw, sh, settings);
+    const target = imageData[_data];
+    const length = TypedArrayPrototypeGetLength(data);
+    for (let i = 0; i < length; i++) {
+      target[i] = data[i] / 255;
+    }
+    return imageData;
+  }
+  return new ImageData(
+    new Uint8ClampedArray(TypedArrayPrototypeGetBuffer(data)),
+    sw,
+    sh,
+    settings,
+  );
+}
+
+function putImageData(
+  imageData,
+  dx,
+  dy,
+  dirtyX = undefined,
+  dirtyY = undefined,
+  dirtyWidth = undefined,
+  dirtyHeight = undefined,
+) {
+  assertContext(this);
+  const prefix =
+    "Failed to execute 'putImageData' on 'OffscreenCanvasRenderingContext2D'";
+  webidl.requiredArguments(arguments.length, 3, prefix);
+  assertImageData(imageData, prefix, "Argument 1");
+  dx = webidl.converters["long"](dx, prefix, "Argument 2");
+  dy = webidl.converters["long"](dy, prefix, "Argument 3");
+  const width = imageData[_width];
+  const height = imageData[_height];
+  if (arguments.length > 3) {
+    webidl.requiredArguments(arguments.length, 7, prefix);
+    dirtyX = webidl.converters["long"](dirtyX, prefix, "Argument 4");
+    dirtyY = webidl.converters["long"](dirtyY, prefix, "Argument 5");
+    dirtyWidth = webidl.converters["long"](dirtyWidth, prefix, "Argument 6");
+    dirtyHeight = webidl.converters["long"](dirtyHeight, prefix, "Argument 7");
+  } else {
+    dirtyX = 0;
+    dirtyY = 0;
+    dirtyWidth = width;
+    dirtyHeight = height;
+  }
+
+  if (dirtyWidth < 0) {
+    dirtyX += dirtyWidth;
+    dirtyWidth = -dirtyWidth;
+  }
+  if (dirtyHeight < 0) {
+    dirtyY += dirtyHeight;
+    dirtyHeight = -dirtyHeight;
+  }
+  if (dirtyX < 0) {
+    dirtyWidth += dirtyX;
+    dirtyX = 0;
+  }
+  if (dirtyY < 0) {
+    dirtyHeight += dirtyY;
+    dirtyY = 0;
+  }
+  if (dirtyX + dirtyWidth > width) {
+    dirtyWidth = width - dirtyX;
+  }
+  if (dirtyY + dirtyHeight > height) {
+    dirtyHeight = height - dirtyY;
+  }
+  if (dirtyWidth <= 0 || dirtyHeight <= 0) {
+    return;
+  }
+
+  let data = imageData[_data];
+  if (TypedArrayPrototypeGetSymbolToStringTag(data) === "Float16Array") {
+    data = float16ToUnorm8(data);
+  }
+  this[_putImageData](
+    new Uint8Array(
+      TypedArrayPrototypeGetBuffer(data),
+      TypedArrayPrototypeGetByteOffset(data),
+      TypedArrayPrototypeGetByteLength(data),
+    ),
+    width,
+    dx,
+    dy,
+    dirtyX,
+    dirtyY,
+    dirtyWidth,
+    dirtyHeight,
+  );
+}
+
+ObjectDefineProperties(OffscreenCanvasRenderingContext2DPrototype, {
+  createImageData: method(createImageData),
+  getImageData: method(getImageData),
+  putImageData: method(putImageData),
+});
+
+const privateCustomInspect = SymbolFor("Deno.privateCustomInspect");
+
+function defineInspect(prototype, keys) {
+  ObjectDefineProperty(prototype, privateCustomInspect, {
+    __proto__: null,
+    value(inspect, inspectOptions) {
+      return inspect(
+        createFilteredInspectProxy({
+          object: this,
+          evaluate: ObjectPrototypeIsPrototypeOf(prototype, this),
+          keys,
+        }),
+        inspectOptions,
+      );
+    },
+  });
+}
+
+defineInspect(OffscreenCanvasPrototype, ["width", "height"]);
+defineInspect(OffscreenCanvasRenderingContext2DPrototype, [
+  "canvas",
+  "globalAlpha",
+  "globalCompositeOperation",
+  "fillStyle",
+  "strokeStyle",
+  "lineWidth",
+  "font",
+  "textAlign",
+  "textBaseline",
+]);
+defineInspect(CanvasGradient.prototype, []);
+defineInspect(Path2D.prototype, []);
+defineInspect(TextMetrics.prototype, [
+  "width",
+  "actualBoundingBoxLeft",
+  "actualBoundingBoxRight",
+  "fontBoundingBoxAscent",
+  "fontBoundingBoxDescent",
+  "actualBoundingBoxAscent",
+  "actualBoundingBoxDescent",
+  "emHeightAscent",
+  "emHeightDescent",
+  "hangingBaseline",
+  "alphabeticBaseline",
+  "ideographicBaseline",
+]);
+
+webidl.converters["FontFaceDescriptors"] = webidl.createDictionaryConverter(
+  "FontFaceDescriptors",
+  [
+    {
+      key: "style",
+      converter: webidl.converters["DOMString"],
+      defaultValue: "normal",
+    },
+    {
+      key: "weight",
+      converter: webidl.converters["DOMString"],
+      defaultValue: "normal",
+    },
+  ],
+);
+
+/**
+ * Parses the `font-style` descriptor. Returns whether the face is italic,
+ * or `null` if the value is invalid.
+ * @param {string} value
+ * @returns {boolean | null}
+ */
+function parseFontStyle(value) {
+  const style = StringPrototypeToLowerCase(StringPrototypeTrim(value));
+  if (style === "normal") return false;
+  if (
+    style === "italic" ||
+    RegExpPrototypeExec(/^oblique(\s|$)/, style) !== null
+  ) {
+    return true;
+  }
+  return null;
+}
+
+/**
+ * Parses the `font-weight` descriptor. Ranges are not supported. Returns
+ * `null` if the value is invalid.
+ * @param {string} value
+ * @returns {number | null}
+ */
+function parseFontWeight(value) {
+  const weight = StringPrototypeToLowerCase(StringPrototypeTrim(value));
+  if (weight === "normal") return 400;
+  if (weight === "bold") return 700;
+  if (RegExpPrototypeExec(/^\d+(\.\d+)?$/, weight) === null) return null;
+  const number = NumberParseFloat(weight);
+  if (number < 1 || number > 1000) return null;
+  return NumberIsInteger(number) ? number : MathRound(number);
+}
+
+const _faceData = Symbol("[[faceData]]");
+const _italic = Symbol("[[italic]]");
+const _weight = Symbol("[[weight]]");
+
+class FontFace {
+  [_faceData] = null;
+  [_italic] = false;
+  [_weight] = 400;
+  #family;
+  #style;
+  #weight;
+  #status = "loading";
+  #loaded;
+
+  /**
+   * @param {string} family
+   * @param {BufferSource | string} source
+   * @param {FontFaceDescriptors} descriptors
+   */
+  constructor(family, source, descriptors = { __proto__: null }) {
+    const prefix = "Failed to construct 'FontFace'";
+    webidl.requiredArguments(arguments.length, 2, prefix);
+    family = webidl.converters["DOMString"](family, prefix, "Argument 1");
+    if (typeof source !== "string") {
+      source = webidl.converters["BufferSource"](source, prefix, "Argument 2");
+    }
+    descriptors = webidl.converters["FontFaceDescriptors"](
+      descriptors,
+      prefix,
+      "Argument 3",
+    );
+    this[webidl.brand] = webidl.brand;
+    this.#family = family;
+    this.#style = descriptors.style;
+    this.#weight = descriptors.weight;
+    this.#loaded = PromiseWithResolvers();
+    // The promise is often never awaited when the face is loaded
+    // successfully, so its rejection must not be reported as unhandled.
+    PromisePrototypeCatch(this.#loaded.promise, () => {});
+
+    const italic = parseFontStyle(descriptors.style);
+    const weight = parseFontWeight(descriptors.weight);
+    if (italic === null || weight === null) {
+      this.#fail(
+        new DOMException(
+          `${prefix}: Invalid font descriptor`,
+          "SyntaxError",
+        ),
+      );
+      return;
+    }
+    this[_italic] = italic;
+    this[_weight] = weight;
+
+    if (typeof source === "string") {
+      this.#fail(
+        new DOMException(
+          `${prefix}: Loading fonts from URLs is not supported, pass the font data instead`,
+          "NotSupportedError",
+        ),
+      );
+      return;
+    }
+
+    try {
+      this[_faceData] = op_image_font_face_parse(source);
+    } catch (err) {
+      this.#fail(err);
+      return;
+    }
+    this.#status = "loaded";
+    this.#loaded.resolve(this);
+  }
+
+  #fail(err) {
+    this.#status = "error";
+    this.#loaded.reject(err);
+  }
+
+  /** @returns {string} */
+  get family() {
+    webidl.assertBranded(this, FontFacePrototype);
+    return this.#family;
+  }
+
+  /** @returns {string} */
+  get style() {
+    webidl.assertBranded(this, FontFacePrototype);
+    return this.#style;
+  }
+
+  /** @returns {string} */
+  get weight() {
+    webidl.assertBranded(this, FontFacePrototype);
+    return this.#weight;
+  }
+
+  /** @returns {FontFaceLoadStatus} */
+  get status() {
+    webidl.assertBranded(this, FontFacePrototype);
+    return this.#status;
+  }
+
+  /** @returns {Promise<FontFace>} */
+  get loaded() {
+    webidl.assertBranded(this, FontFacePrototype);
+    return this.#loaded.promise;
+  }
+
+  /** @returns {Promise<FontFace>} */
+  load() {
+    webidl.assertBranded(this, FontFacePrototype);
+    return this.#loaded.promise;
+  }
+
+  [SymbolFor("Deno.privateCustomInspect")](inspect, inspectOptions) {
+    return inspect(
+      createFilteredInspectProxy({
+        object: this,
+        evaluate: ObjectPrototypeIsPrototypeOf(FontFacePrototype, this),
+        keys: [
+          "family",
+          "style",
+          "weight",
+          "status",
+        ],
+      }),
+      inspectOptions,
+    );
+  }
+}
+
+webidl.configureInterface(FontFace);
+const FontFacePrototype = FontFace.prototype;
+
+const illegalConstructorKey = Symbol("illegalConstructorKey");
+
+class FontFaceSet {
+  /** @type {FontFace[]} */
+  #faces = [];
+  /** Maps the faces to their id in the font registry. */
+  #ids = new SafeMap();
+
+  constructor(key = undefined) {
+    if (key !== illegalConstructorKey) {
+      webidl.illegalConstructor();
+    }
+    this[webidl.brand] = webidl.brand;
+  }
+
+  /**
+   * @param {FontFace} font
+   * @returns {FontFaceSet}
+   */
+  add(font) {
+    webidl.assertBranded(this, FontFaceSetPrototype);
+    const prefix = "Failed to execute 'add' on 'FontFaceSet'";
+    webidl.requiredArguments(arguments.length, 1, prefix);
+    webidl.assertBranded(font, FontFacePrototype);
+    if (this.#ids.has(font)) {
+      return this;
+    }
+    // Faces that failed to load are kept in the set but never used.
+    let id = null;
+    if (font[_faceData] !== null) {
+      id = op_image_fonts_add(
+        font[_faceData],
+        font.family,
+        font[_italic],
+        font[_weight],
+      );
+    }
+    ArrayPrototypePush(this.#faces, font);
+    this.#ids.set(font, id);
+    return this;
+  }
+
+  /**
+   * @param {FontFace} font
+   * @returns {boolean}
+   */
+  delete(font) {
+    webidl.assertBranded(this, FontFaceSetPrototype);
+    const prefix = "Failed to execute 'delete' on 'FontFaceSet'";
+    webidl.requiredArguments(arguments.length, 1, prefix);
+    if (!this.#ids.has(font)) {
+      return false;
+    }
+    const id = this.#ids.get(font);
+    if (id !== null) {
+      op_image_fonts_delete(id);
+    }
+    this.#ids.delete(font);
+    ArrayPrototypeSplice(
+      this.#faces,
+      ArrayPrototypeIndexOf(this.#faces, font),
+      1,
+    );
+    return true;
+  }
+
+  clear() {
+    webidl.assertBranded(this, FontFaceSetPrototype);
+    const faces = ArrayPrototypeSlice(this.#faces);
+    for (let i = 0; i < faces.length; i++) {
+      this.delete(faces[i]);
+    }
+  }
+
+  /**
+   * @param {FontFace} font
+   * @returns {boolean}
+   */
+  has(font) {
+    webidl.assertBranded(this, FontFaceSetPrototype);
+    return this.#ids.has(font);
+  }
+
+  /** @returns {number} */
+  get size() {
+    webidl.assertBranded(this, FontFaceSetPrototype);
+    return this.#faces.length;
+  }
+
+  /** @returns {FontFaceSetLoadStatus} */
+  get status() {
+    webidl.assertBranded(this, FontFaceSetPrototype);
+    return "loaded";
+  }
+
+  /** @returns {Promise<FontFaceSet>} */
+  get ready() {
+    webidl.assertBranded(this, FontFaceSetPrototype);
+    return PromiseResolve(this);
+  }
+
+  forEach(callbackfn, thisArg = undefined) {
+    webidl.assertBranded(this, FontFaceSetPrototype);
+    const faces = ArrayPrototypeSlice(this.#faces);
+    for (let i = 0; i < faces.length; i++) {
+      FunctionPrototypeCall(callbackfn, thisArg, faces[i], faces[i], this);
+    }
+  }
+
+  *values() {
+    webidl.assertBranded(this, FontFaceSetPrototype);
+    const faces = ArrayPrototypeSlice(this.#faces);
+    for (let i = 0; i < faces.length; i++) {
+      yield faces[i];
+    }
+  }
+
+  keys() {
+    return this.values();
+  }
+
+  *entries() {
+    webidl.assertBranded(this, FontFaceSetPrototype);
+    const faces = ArrayPrototypeSlice(this.#faces);
+    for (let i = 0; i < faces.length; i++) {
+      yield [faces[i], faces[i]];
+    }
+  }
+
+  [SymbolIterator]() {
+    return this.values();
+  }
+
+  [SymbolFor("Deno.privateCustomInspect")](inspect, inspectOptions) {
+    return inspect(
+      createFilteredInspectProxy({
+        object: this,
+        evaluate: ObjectPrototypeIsPrototypeOf(FontFaceSetPrototype, this),
+        keys: [
+          "size",
+          "status",
+        ],
+      }),
+      inspectOptions,
+    );
+  }
+}
+
+webidl.configureInterface(FontFaceSet);
+const FontFaceSetPrototype = FontFaceSet.prototype;
+
+/** The fonts available to the canvas 2D contexts of this worker. */
+const fonts = new FontFaceSet(illegalConstructorKey);
+
+export {
+  CanvasGradient,
+  FontFace,
+  FontFaceSet,
+  fonts,
+  OffscreenCanvas,
+  OffscreenCanvasRenderingContext2D,
+  Path2D,
+  TextMetrics,
+};
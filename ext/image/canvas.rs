@@ -0,0 +1,253 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+use std::cell::Cell;
+use std::cell::OnceCell;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use deno_core::GarbageCollected;
+use deno_core::cppgc;
+use deno_core::op2;
+use deno_core::unsync::spawn_blocking;
+use deno_core::v8;
+use deno_core::webidl::WebIdlInterfaceConverter;
+use image::DynamicImage;
+use image::ExtendedColorType;
+use image::ImageEncoder;
+use image::RgbaImage;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::codecs::webp::WebPEncoder;
+use tiny_skia::Pixmap;
+use tiny_skia::PremultipliedColorU8;
+
+use crate::ImageError;
+use crate::bitmap::ImageBitmap;
+use crate::context2d::OffscreenCanvasRenderingContext2D;
+use crate::context2d::Renderer;
+
+/// Converts a premultiplied pixmap to a non-premultiplied RGBA image.
+pub(crate) fn pixmap_to_rgba(pixmap: &Pixmap) -> RgbaImage {
+  let mut data = Vec::with_capacity(pixmap.data().len());
+  for pixel in pixmap.pixels() {
+    let pixel = pixel.demultiply();
+    data.extend_from_slice(&[
+      pixel.red(),
+      pixel.green(),
+      pixel.blue(),
+      pixel.alpha(),
+    ]);
+  }
+  RgbaImage::from_raw(pixmap.width(), pixmap.height(), data).unwrap()
+}
+
+/// Converts a non-premultiplied RGBA image to a premultiplied pixmap.
+/// Returns `None` if the image is empty.
+pub(crate) fn rgba_to_pixmap(image: &RgbaImage) -> Option<Pixmap> {
+  let mut pixmap = Pixmap::new(image.width(), image.height())?;
+  for (target, source) in pixmap.pixels_mut().iter_mut().zip(image.pixels()) {
+    let [r, g, b, a] = source.0;
+    let premultiply = |c: u8| ((c as u16 * a as u16 + 127) / 255) as u8;
+    *target = PremultipliedColorU8::from_rgba(
+      premultiply(r),
+      premultiply(g),
+      premultiply(b),
+      a,
+    )
+    .unwrap();
+  }
+  Some(pixmap)
+}
+
+pub struct OffscreenCanvas {
+  width: Cell<u64>,
+  height: Cell<u64>,
+  renderer: Rc<RefCell<Renderer>>,
+  context: OnceCell<v8::TracedReference<v8::Object>>,
+}
+
+// SAFETY: we're sure this can be GCed
+unsafe impl GarbageCollected for OffscreenCanvas {
+  fn trace(&self, visitor: &mut deno_core::v8::cppgc::Visitor) {
+    if let Some(context) = self.context.get() {
+      visitor.trace(context);
+    }
+  }
+
+  fn get_name(&self) -> &'static std::ffi::CStr {
+    c"OffscreenCanvas"
+  }
+}
+
+impl WebIdlInterfaceConverter for OffscreenCanvas {
+  const NAME: &'static str = "OffscreenCanvas";
+}
+
+impl OffscreenCanvas {
+  pub(crate) fn is_empty(&self) -> bool {
+    self.width.get() == 0 || self.height.get() == 0
+  }
+
+  /// Returns a copy of the bitmap, or `None` if it has no pixels.
+  pub(crate) fn snapshot(&self) -> Option<Pixmap> {
+    self.renderer.borrow().pixmap().cloned()
+  }
+
+  /// Replaces the bitmap with a new transparent one of the current size
+  /// and resets the context state, as when the size is set.
+  fn reset(&self) {
+    *self.renderer.borrow_mut() =
+      Renderer::new(self.width.get(), self.height.get());
+  }
+}
+
+#[op2]
+impl OffscreenCanvas {
+  #[constructor]
+  #[required(2)]
+  #[cppgc]
+  fn new(
+    #[webidl(options(enforce_range = true))] width: u64,
+    #[webidl(options(enforce_range = true))] height: u64,
+  ) -> OffscreenCanvas {
+    OffscreenCanvas {
+      width: Cell::new(width),
+      height: Cell::new(height),
+      renderer: Rc::new(RefCell::new(Renderer::new(width, height))),
+      context: OnceCell::new(),
+    }
+  }
+
+  #[getter]
+  #[number]
+  fn width(&self) -> u64 {
+    self.width.get()
+  }
+
+  #[setter]
+  fn width(&self, #[webidl(options(enforce_range = true))] value: u64) {
+    self.width.set(value);
+    self.reset();
+  }
+
+  #[getter]
+  #[number]
+  fn height(&self) -> u64 {
+    self.height.get()
+  }
+
+  #[setter]
+  fn height(&self, #[webidl(options(enforce_range = true))] value: u64) {
+    self.height.set(value);
+    self.reset();
+  }
+
+  // Returns the 2D context, creating it on the first call. The other
+  // context types are not supported and return `null`.
+  #[required(1)]
+  fn get_context(
+    &self,
+    #[this] this: v8::Global<v8::Object>,
+    scope: &mut v8::PinScope<'_, '_>,
+    #[webidl] context_id: String,
+  ) -> Result<Option<v8::Global<v8::Object>>, ImageError> {
+    match context_id.as_str() {
+      "2d" => {}
+      "bitmaprenderer" | "webgl" | "webgl2" | "webgpu" => return Ok(None),
+      _ => return Err(ImageError::InvalidContextId(context_id)),
+    }
+
+    if self.context.get().is_none() {
+      let canvas = v8::Local::new(scope, this);
+      let context = OffscreenCanvasRenderingContext2D::create(
+        scope,
+        canvas,
+        self.renderer.clone(),
+      );
+      let object = cppgc::make_cppgc_object(scope, context);
+      let _ = self.context.set(v8::TracedReference::new(scope, object));
+    }
+    let context = self.context.get().unwrap().get(scope).unwrap();
+    Ok(Some(v8::Global::new(scope, context)))
+  }
+
+  // Moves the bitmap to a new `ImageBitmap` and clears the canvas.
+  #[cppgc]
+  fn transfer_to_image_bitmap(&self) -> Result<ImageBitmap, ImageError> {
+    if self.context.get().is_none() {
+      return Err(ImageError::NoRenderingContext);
+    }
+    let mut renderer = self.renderer.borrow_mut();
+    let image = match renderer.pixmap() {
+      Some(pixmap) => pixmap_to_rgba(pixmap),
+      None => RgbaImage::new(0, 0),
+    };
+    renderer.clear();
+    Ok(ImageBitmap {
+      detached: Default::default(),
+      data: RefCell::new(DynamicImage::ImageRgba8(image)),
+    })
+  }
+}
+
+/// Encodes the bitmap of `canvas` as `mime_type`, which is one of
+/// `image/png`, `image/jpeg` and `image/webp`. The bitmap is copied
+/// synchronously so later drawing does not affect the result.
+#[op2]
+#[buffer]
+pub fn op_image_encode_canvas(
+  #[cppgc] canvas: &OffscreenCanvas,
+  #[string] mime_type: String,
+  quality: f64,
+) -> impl Future<Output = Result<Vec<u8>, ImageError>> + use<> {
+  let pixmap = canvas.snapshot();
+  async move {
+    let Some(pixmap) = pixmap else {
+      return Err(ImageError::Encoding);
+    };
+    spawn_blocking(move || encode(&pixmap, &mime_type, quality))
+      .await
+      .unwrap()
+  }
+}
+
+fn encode(
+  pixmap: &Pixmap,
+  mime_type: &str,
+  quality: f64,
+) -> Result<Vec<u8>, ImageError> {
+  let (width, height) = (pixmap.width(), pixmap.height());
+  let mut out = Vec::new();
+  let result = match mime_type {
+    "image/jpeg" => {
+      // JPEG has no alpha channel, so the image is composited onto
+      // opaque black, which is what the premultiplied colors are.
+      let data = pixmap
+        .data()
+        .chunks_exact(4)
+        .flat_map(|pixel| [pixel[0], pixel[1], pixel[2]])
+        .collect::<Vec<_>>();
+      let quality = (quality.clamp(0.0, 1.0) * 100.0).round().max(1.0) as u8;
+      JpegEncoder::new_with_quality(&mut out, quality).write_image(
+        &data,
+        width,
+        height,
+        ExtendedColorType::Rgb8,
+      )
+    }
+    "image/webp" => WebPEncoder::new_lossless(&mut out).write_image(
+      &pixmap_to_rgba(pixmap),
+      width,
+      height,
+      ExtendedColorType::Rgba8,
+    ),
+    _ => PngEncoder::new(&mut out).write_image(
+      &pixmap_to_rgba(pixmap),
+      width,
+      height,
+      ExtendedColorType::Rgba8,
+    ),
+  };
+  result.map_err(|_| ImageError::Encoding)?;
+  Ok(out)
+}
@@ -0,0 +1,1920 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use deno_core::GarbageCollected;
+use deno_core::OpState;
+use deno_core::cppgc;
+use deno_core::op2;
+use deno_core::v8;
+use deno_core::webidl::UnrestrictedDouble;
+use deno_core::webidl::WebIdlInterfaceConverter;
+use tiny_skia::BlendMode;
+use tiny_skia::FillRule;
+use tiny_skia::FilterQuality;
+use tiny_skia::GradientStop;
+use tiny_skia::LinearGradient;
+use tiny_skia::Mask;
+use tiny_skia::Paint;
+use tiny_skia::Pattern;
+use tiny_skia::Pixmap;
+use tiny_skia::PixmapPaint;
+use tiny_skia::Point;
+use tiny_skia::PremultipliedColorU8;
+use tiny_skia::RadialGradient;
+use tiny_skia::Shader;
+use tiny_skia::SpreadMode;
+use tiny_skia::Stroke;
+use tiny_skia::StrokeDash;
+use tiny_skia::Transform;
+
+use crate::ImageError;
+use crate::bitmap::ImageBitmap;
+use crate::canvas::OffscreenCanvas;
+use crate::canvas::rgba_to_pixmap;
+use crate::css;
+use crate::css::Rgba;
+use crate::font::FontRegistry;
+use crate::path::Path2D;
+use crate::path::PathData;
+use crate::path::radii_from_v8;
+use crate::path::transform_from_v8;
+
+/// Defines an enum for the string values of a context attribute. Invalid
+/// values are ignored by the setters.
+macro_rules! string_enum {
+  ($name:ident { $($variant:ident = $value:literal),* $(,)? }) => {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum $name {
+      $($variant),*
+    }
+
+    impl $name {
+      fn parse(value: &str) -> Option<Self> {
+        match value {
+          $($value => Some(Self::$variant),)*
+          _ => None,
+        }
+      }
+
+      fn as_str(self) -> &'static str {
+        match self {
+          $(Self::$variant => $value),*
+        }
+      }
+    }
+  };
+}
+
+string_enum!(LineCap {
+  Butt = "butt",
+  Round = "round",
+  Square = "square",
+});
+
+string_enum!(LineJoin {
+  Miter = "miter",
+  Round = "round",
+  Bevel = "bevel",
+});
+
+string_enum!(TextAlign {
+  Start = "start",
+  End = "end",
+  Left = "left",
+  Right = "right",
+  Center = "center",
+});
+
+string_enum!(TextBaseline {
+  Top = "top",
+  Hanging = "hanging",
+  Middle = "middle",
+  Alphabetic = "alphabetic",
+  Ideographic = "ideographic",
+  Bottom = "bottom",
+});
+
+string_enum!(Direction {
+  Ltr = "ltr",
+  Rtl = "rtl",
+  Inherit = "inherit",
+});
+
+string_enum!(ImageSmoothingQuality {
+  Low = "low",
+  Medium = "medium",
+  High = "high",
+});
+
+string_enum!(CompositeOperation {
+  SourceOver = "source-over",
+  SourceIn = "source-in",
+  SourceOut = "source-out",
+  SourceAtop = "source-atop",
+  DestinationOver = "destination-over",
+  DestinationIn = "destination-in",
+  DestinationOut = "destination-out",
+  DestinationAtop = "destination-atop",
+  Lighter = "lighter",
+  Copy = "copy",
+  Xor = "xor",
+  Multiply = "multiply",
+  Screen = "screen",
+  Overlay = "overlay",
+  Darken = "darken",
+  Lighten = "lighten",
+  ColorDodge = "color-dodge",
+  ColorBurn = "color-burn",
+  HardLight = "hard-light",
+  SoftLight = "soft-light",
+  Difference = "difference",
+  Exclusion = "exclusion",
+  Hue = "hue",
+  Saturation = "saturation",
+  Color = "color",
+  Luminosity = "luminosity",
+});
+
+impl CompositeOperation {
+  fn blend_mode(self) -> BlendMode {
+    match self {
+      CompositeOperation::SourceOver => BlendMode::SourceOver,
+      CompositeOperation::SourceIn => BlendMode::SourceIn,
+      CompositeOperation::SourceOut => BlendMode::SourceOut,
+      CompositeOperation::SourceAtop => BlendMode::SourceAtop,
+      CompositeOperation::DestinationOver => BlendMode::DestinationOver,
+      CompositeOperation::DestinationIn => BlendMode::DestinationIn,
+      CompositeOperation::DestinationOut => BlendMode::DestinationOut,
+      CompositeOperation::DestinationAtop => BlendMode::DestinationAtop,
+      CompositeOperation::Lighter => BlendMode::Plus,
+      CompositeOperation::Copy => BlendMode::Source,
+      CompositeOperation::Xor => BlendMode::Xor,
+      CompositeOperation::Multiply => BlendMode::Multiply,
+      CompositeOperation::Screen => BlendMode::Screen,
+      CompositeOperation::Overlay => BlendMode::Overlay,
+      CompositeOperation::Darken => BlendMode::Darken,
+      CompositeOperation::Lighten => BlendMode::Lighten,
+      CompositeOperation::ColorDodge => BlendMode::ColorDodge,
+      CompositeOperation::ColorBurn => BlendMode::ColorBurn,
+      CompositeOperation::HardLight => BlendMode::HardLight,
+      CompositeOperation::SoftLight => BlendMode::SoftLight,
+      CompositeOperation::Difference => BlendMode::Difference,
+      CompositeOperation::Exclusion => BlendMode::Exclusion,
+      CompositeOperation::Hue => BlendMode::Hue,
+      CompositeOperation::Saturation => BlendMode::Saturation,
+      CompositeOperation::Color => BlendMode::Color,
+      CompositeOperation::Luminosity => BlendMode::Luminosity,
+    }
+  }
+
+  /// Whether the operation also affects the pixels outside of the drawn
+  /// shape, which then have to be composited as a full layer.
+  fn is_unbounded(self) -> bool {
+    matches!(
+      self,
+      CompositeOperation::SourceIn
+        | CompositeOperation::SourceOut
+        | CompositeOperation::DestinationIn
+        | CompositeOperation::DestinationAtop
+        | CompositeOperation::Copy
+    )
+  }
+}
+
+fn fill_rule_from_v8(
+  scope: &mut v8::PinScope<'_, '_>,
+  value: v8::Local<v8::Value>,
+) -> Result<FillRule, ImageError> {
+  if value.is_undefined() {
+    return Ok(FillRule::Winding);
+  }
+  match value.to_rust_string_lossy(scope).as_str() {
+    "nonzero" => Ok(FillRule::Winding),
+    "evenodd" => Ok(FillRule::EvenOdd),
+    value => Err(ImageError::InvalidFillRule(value.to_string())),
+  }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum GradientKind {
+  Linear {
+    x0: f32,
+    y0: f32,
+    x1: f32,
+    y1: f32,
+  },
+  Radial {
+    x0: f32,
+    y0: f32,
+    r0: f32,
+    x1: f32,
+    y1: f32,
+    r1: f32,
+  },
+}
+
+pub struct Gradient {
+  kind: GradientKind,
+  stops: Vec<(f32, Rgba)>,
+}
+
+impl Gradient {
+  fn shader(&self, alpha: f32) -> Option<Shader<'static>> {
+    let stops = |map: &dyn Fn(f32) -> f32| {
+      self
+        .stops
+        .iter()
+        .map(|(offset, color)| {
+          GradientStop::new(map(*offset), color.to_color(alpha))
+        })
+        .collect::<Vec<_>>()
+    };
+    if self.stops.is_empty() {
+      return None;
+    }
+
+    match self.kind {
+      GradientKind::Linear { x0, y0, x1, y1 } => {
+        if x0 == x1 && y0 == y1 {
+          return None;
+        }
+        LinearGradient::new(
+          Point::from_xy(x0, y0),
+          Point::from_xy(x1, y1),
+          stops(&|offset| offset),
+          SpreadMode::Pad,
+          Transform::identity(),
+        )
+      }
+      GradientKind::Radial {
+        x0,
+        y0,
+        r0,
+        x1,
+        y1,
+        r1,
+      } => {
+        if x0 == x1 && y0 == y1 && r0 == r1 {
+          return None;
+        }
+        // tiny-skia only supports a start circle with a zero radius. The
+        // start radius is approximated by moving the stops outwards,
+        // which is exact for concentric circles.
+        let (inner, outer) = (r0.min(r1), r0.max(r1));
+        if outer == 0.0 {
+          return None;
+        }
+        let start = inner / outer;
+        let map = |offset: f32| {
+          if r0 <= r1 {
+            start + offset * (1.0 - start)
+          } else {
+            1.0 - offset * (1.0 - start)
+          }
+        };
+        let (focal, center) = if r0 <= r1 {
+          (Point::from_xy(x0, y0), Point::from_xy(x1, y1))
+        } else {
+          (Point::from_xy(x1, y1), Point::from_xy(x0, y0))
+        };
+        let mut stops = stops(&map);
+        if r0 > r1 {
+          stops.reverse();
+        }
+        RadialGradient::new(
+          focal,
+          center,
+          outer,
+          stops,
+          SpreadMode::Pad,
+          Transform::identity(),
+        )
+      }
+    }
+  }
+}
+
+pub struct CanvasGradient {
+  gradient: Rc<RefCell<Gradient>>,
+}
+
+// SAFETY: we're sure this can be GCed
+unsafe impl GarbageCollected for CanvasGradient {
+  fn trace(&self, _visitor: &mut deno_core::v8::cppgc::Visitor) {}
+
+  fn get_name(&self) -> &'static std::ffi::CStr {
+    c"CanvasGradient"
+  }
+}
+
+#[op2]
+impl CanvasGradient {
+  #[constructor]
+  #[cppgc]
+  fn new(_: bool) -> Result<CanvasGradient, ImageError> {
+    Err(ImageError::InvalidConstructor)
+  }
+
+  #[required(2)]
+  #[undefined]
+  fn add_color_stop(
+    &self,
+    #[webidl] offset: f64,
+    #[webidl] color: String,
+  ) -> Result<(), ImageError> {
+    if !(0.0..=1.0).contains(&offset) {
+      return Err(ImageError::InvalidColorStopOffset(offset));
+    }
+    let color =
+      css::parse_color(&color).ok_or(ImageError::InvalidColor(color))?;
+    let offset = offset as f32;
+    let mut gradient = self.gradient.borrow_mut();
+    // Stops with the same offset are kept in insertion order.
+    let index = gradient.stops.partition_point(|(o, _)| *o <= offset);
+    gradient.stops.insert(index, (offset, color));
+    Ok(())
+  }
+}
+
+#[derive(Clone)]
+enum Style {
+  Color(Rgba),
+  // The object is kept so the getters return the same `CanvasGradient`.
+  Gradient(Rc<RefCell<Gradient>>, v8::Global<v8::Object>),
+}
+
+impl Style {
+  fn from_v8(
+    scope: &mut v8::PinScope<'_, '_>,
+    value: v8::Local<v8::Value>,
+  ) -> Option<Style> {
+    if let Some(gradient) =
+      cppgc::try_unwrap_cppgc_object::<CanvasGradient>(scope, value)
+    {
+      let object = v8::Local::<v8::Object>::try_from(value).ok()?;
+      Some(Style::Gradient(
+        gradient.gradient.clone(),
+        v8::Global::new(scope, object),
+      ))
+    } else if value.is_string() {
+      css::parse_color(&value.to_rust_string_lossy(scope)).map(Style::Color)
+    } else {
+      None
+    }
+  }
+
+  fn to_v8<'a>(
+    &self,
+    scope: &mut v8::PinScope<'a, '_>,
+  ) -> v8::Local<'a, v8::Value> {
+    match self {
+      Style::Color(color) => {
+        v8::String::new(scope, &color.serialize()).unwrap().into()
+      }
+      Style::Gradient(_, object) => v8::Local::new(scope, object).into(),
+    }
+  }
+
+  fn paint(&self, alpha: f32) -> Option<Paint<'static>> {
+    let shader = match self {
+      Style::Color(color) => Shader::SolidColor(color.to_color(alpha)),
+      Style::Gradient(gradient, _) => gradient.borrow().shader(alpha)?,
+    };
+    Some(Paint {
+      shader,
+      ..Paint::default()
+    })
+  }
+}
+
+#[derive(Clone)]
+struct DrawingState {
+  transform: Transform,
+  clip: Option<Rc<Mask>>,
+  fill_style: Style,
+  stroke_style: Style,
+  global_alpha: f32,
+  composite: CompositeOperation,
+  image_smoothing_enabled: bool,
+  image_smoothing_quality: ImageSmoothingQuality,
+  line_width: f32,
+  line_cap: LineCap,
+  line_join: LineJoin,
+  miter_limit: f32,
+  line_dash: Vec<f32>,
+  line_dash_offset: f32,
+  font: css::Font,
+  text_align: TextAlign,
+  text_baseline: TextBaseline,
+  direction: Direction,
+}
+
+impl Default for DrawingState {
+  fn default() -> Self {
+    DrawingState {
+      transform: Transform::identity(),
+      clip: None,
+      fill_style: Style::Color(Rgba::BLACK),
+      stroke_style: Style::Color(Rgba::BLACK),
+      global_alpha: 1.0,
+      composite: CompositeOperation::SourceOver,
+      image_smoothing_enabled: true,
+      image_smoothing_quality: ImageSmoothingQuality::Low,
+      line_width: 1.0,
+      line_cap: LineCap::Butt,
+      line_join: LineJoin::Miter,
+      miter_limit: 10.0,
+      line_dash: Vec::new(),
+      line_dash_offset: 0.0,
+      font: css::Font::default(),
+      text_align: TextAlign::Start,
+      text_baseline: TextBaseline::Alphabetic,
+      direction: Direction::Inherit,
+    }
+  }
+}
+
+impl DrawingState {
+  fn stroke(&self) -> Stroke {
+    Stroke {
+      width: self.line_width,
+      miter_limit: self.miter_limit,
+      line_cap: match self.line_cap {
+        LineCap::Butt => tiny_skia::LineCap::Butt,
+        LineCap::Round => tiny_skia::LineCap::Round,
+        LineCap::Square => tiny_skia::LineCap::Square,
+      },
+      line_join: match self.line_join {
+        LineJoin::Miter => tiny_skia::LineJoin::Miter,
+        LineJoin::Round => tiny_skia::LineJoin::Round,
+        LineJoin::Bevel => tiny_skia::LineJoin::Bevel,
+      },
+      dash: StrokeDash::new(self.line_dash.clone(), self.line_dash_offset),
+    }
+  }
+
+  fn filter_quality(&self) -> FilterQuality {
+    if !self.image_smoothing_enabled {
+      return FilterQuality::Nearest;
+    }
+    match self.image_smoothing_quality {
+      ImageSmoothingQuality::Low => FilterQuality::Bilinear,
+      ImageSmoothingQuality::Medium | ImageSmoothingQuality::High => {
+        FilterQuality::Bicubic
+      }
+    }
+  }
+}
+
+/// Canvases larger than this are not allocated and stay blank, like in
+/// browsers.
+const MAX_CANVAS_AREA: u64 = 1 << 28;
+
+/// The bitmap of an `OffscreenCanvas` together with the state of its 2D
+/// context.
+pub struct Renderer {
+  pixmap: Option<Pixmap>,
+  state: DrawingState,
+  stack: Vec<DrawingState>,
+  path: PathData,
+}
+
+impl Renderer {
+  pub(crate) fn new(width: u64, height: u64) -> Self {
+    let pixmap = match width.checked_mul(height) {
+      Some(area) if area <= MAX_CANVAS_AREA => {
+        Pixmap::new(width as u32, height as u32)
+      }
+      _ => None,
+    };
+    Renderer {
+      pixmap,
+      state: DrawingState::default(),
+      stack: Vec::new(),
+      path: PathData::default(),
+    }
+  }
+
+  /// Returns the bitmap, or `None` if the canvas has no pixels.
+  pub(crate) fn pixmap(&self) -> Option<&Pixmap> {
+    self.pixmap.as_ref()
+  }
+
+  /// Clears the bitmap to transparent black.
+  pub(crate) fn clear(&mut self) {
+    if let Some(pixmap) = &mut self.pixmap {
+      pixmap.fill(tiny_skia::Color::TRANSPARENT);
+    }
+  }
+
+  /// Converts a path in device space, like the current default path, to
+  /// the current user space.
+  fn user_path(&self, path: &PathData) -> Option<tiny_skia::Path> {
+    let inverse = self.state.transform.invert()?;
+    path.to_path()?.transform(inverse)
+  }
+
+  /// Draws with `paint`, applying the clipping region and the global
+  /// composite operation.
+  fn composite<'a>(
+    &mut self,
+    mut paint: Paint<'a>,
+    draw: impl FnOnce(&mut Pixmap, &Paint<'a>, Option<&Mask>),
+  ) {
+    let Renderer { pixmap, state, .. } = self;
+    let Some(pixmap) = pixmap else {
+      return;
+    };
+    let mask = state.clip.as_deref();
+    if state.composite.is_unbounded() {
+      let Some(mut layer) = Pixmap::new(pixmap.width(), pixmap.height()) else {
+        return;
+      };
+      paint.blend_mode = BlendMode::SourceOver;
+      draw(&mut layer, &paint, None);
+      pixmap.draw_pixmap(
+        0,
+        0,
+        layer.as_ref(),
+        &PixmapPaint {
+          opacity: 1.0,
+          blend_mode: state.composite.blend_mode(),
+          quality: FilterQuality::Nearest,
+        },
+        Transform::identity(),
+        mask,
+      );
+    } else {
+      paint.blend_mode = state.composite.blend_mode();
+      draw(pixmap, &paint, mask);
+    }
+  }
+
+  /// Fills a path given in user space.
+  fn fill_path(&mut self, path: &tiny_skia::Path, rule: FillRule) {
+    let Some(paint) = self.state.fill_style.paint(self.state.global_alpha)
+    else {
+      return;
+    };
+    let transform = self.state.transform;
+    self.composite(paint, |pixmap, paint, mask| {
+      pixmap.fill_path(path, paint, rule, transform, mask)
+    });
+  }
+
+  /// Strokes a path given in user space.
+  fn stroke_path(&mut self, path: &tiny_skia::Path) {
+    let Some(paint) = self.state.stroke_style.paint(self.state.global_alpha)
+    else {
+      return;
+    };
+    let transform = self.state.transform;
+    let stroke = self.state.stroke();
+    self.composite(paint, |pixmap, paint, mask| {
+      pixmap.stroke_path(path, paint, &stroke, transform, mask)
+    });
+  }
+
+  fn clip(&mut self, path: &PathData, rule: FillRule) {
+    let Some(pixmap) = &self.pixmap else {
+      return;
+    };
+    let mut mask = match &self.state.clip {
+      Some(clip) => (**clip).clone(),
+      None => {
+        let Some(mut mask) = Mask::new(pixmap.width(), pixmap.height()) else {
+          return;
+        };
+        mask.invert();
+        mask
+      }
+    };
+    match path.to_path() {
+      Some(path) => {
+        mask.intersect_path(&path, rule, true, Transform::identity())
+      }
+      None => mask.clear(),
+    }
+    self.state.clip = Some(Rc::new(mask));
+  }
+
+  fn clear_rect(&mut self, x: f64, y: f64, w: f64, h: f64) {
+    let mut rect = PathData::default();
+    rect.rect(Transform::identity(), x, y, w, h);
+    let (Some(pixmap), Some(rect)) = (&mut self.pixmap, rect.to_path()) else {
+      return;
+    };
+    let paint = Paint {
+      shader: Shader::SolidColor(tiny_skia::Color::TRANSPARENT),
+      blend_mode: BlendMode::Clear,
+      ..Paint::default()
+    };
+    pixmap.fill_path(
+      &rect,
+      &paint,
+      FillRule::Winding,
+      self.state.transform,
+      self.state.clip.as_deref(),
+    );
+  }
+
+  /// Lays out `text` and positions it according to the text attributes.
+  /// Returns the layout and the offset of the text from `(x, y)`.
+  fn layout_text(
+    &self,
+    registry: &FontRegistry,
+    text: &str,
+  ) -> (crate::font::TextLayout, f32, f32) {
+    let layout = registry.layout(&self.state.font, text);
+    let rtl = self.state.direction == Direction::Rtl;
+    let x = match self.state.text_align {
+      TextAlign::Left => 0.0,
+      TextAlign::Start if !rtl => 0.0,
+      TextAlign::End if rtl => 0.0,
+      TextAlign::Right | TextAlign::Start | TextAlign::End => -layout.width,
+      TextAlign::Center => -layout.width / 2.0,
+    };
+    let y = match self.state.text_baseline {
+      TextBaseline::Alphabetic => 0.0,
+      TextBaseline::Top => layout.ascent,
+      TextBaseline::Hanging => layout.ascent * 0.8,
+      TextBaseline::Middle => (layout.ascent - layout.descent) / 2.0,
+      TextBaseline::Ideographic | TextBaseline::Bottom => -layout.descent,
+    };
+    (layout, x, y)
+  }
+
+  fn draw_text(
+    &mut self,
+    registry: &FontRegistry,
+    text: &str,
+    x: f64,
+    y: f64,
+    max_width: Option<f64>,
+    stroke: bool,
+  ) {
+    if !x.is_finite() || !y.is_finite() {
+      return;
+    }
+    let (layout, dx, dy) = self.layout_text(registry, text);
+    let mut scale = 1.0;
+    if let Some(max_width) = max_width {
+      if !max_width.is_finite() || max_width <= 0.0 {
+        return;
+      }
+      if layout.width > max_width as f32 {
+        scale = max_width as f32 / layout.width;
+      }
+    }
+    let Some(path) = layout.path else {
+      return;
+    };
+    let transform = Transform::from_translate(x as f32, y as f32)
+      .pre_scale(scale, 1.0)
+      .pre_translate(dx, dy);
+    let Some(path) = path.transform(transform) else {
+      return;
+    };
+    if stroke {
+      self.stroke_path(&path);
+    } else {
+      self.fill_path(&path, FillRule::Winding);
+    }
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  fn draw_image(
+    &mut self,
+    image: &Pixmap,
+    sx: f64,
+    sy: f64,
+    sw: f64,
+    sh: f64,
+    dx: f64,
+    dy: f64,
+    dw: f64,
+    dh: f64,
+  ) {
+    if ![sx, sy, sw, sh, dx, dy, dw, dh]
+      .iter()
+      .all(|v| v.is_finite())
+    {
+      return;
+    }
+    let normalize = |x: f64, w: f64| if w < 0.0 { (x + w, -w) } else { (x, w) };
+    let (sx, sw) = normalize(sx, sw);
+    let (sy, sh) = normalize(sy, sh);
+    let (dx, dw) = normalize(dx, dw);
+    let (dy, dh) = normalize(dy, dh);
+    if sw == 0.0 || sh == 0.0 || dw == 0.0 || dh == 0.0 {
+      return;
+    }
+
+    // Clip the source rectangle to the image and shrink the destination
+    // rectangle accordingly.
+    let (scale_x, scale_y) = (dw / sw, dh / sh);
+    let left = sx.max(0.0);
+    let top = sy.max(0.0);
+    let right = (sx + sw).min(image.width() as f64);
+    let bottom = (sy + sh).min(image.height() as f64);
+    if right <= left || bottom <= top {
+      return;
+    }
+    let dx = dx + (left - sx) * scale_x;
+    let dy = dy + (top - sy) * scale_y;
+    let dw = (right - left) * scale_x;
+    let dh = (bottom - top) * scale_y;
+
+    let mut rect = PathData::default();
+    rect.rect(Transform::identity(), dx, dy, dw, dh);
+    let Some(rect) = rect.to_path() else {
+      return;
+    };
+    let pattern_transform = Transform::from_translate(dx as f32, dy as f32)
+      .pre_scale(scale_x as f32, scale_y as f32)
+      .pre_translate(-left as f32, -top as f32);
+    let paint = Paint {
+      shader: Pattern::new(
+        image.as_ref(),
+        SpreadMode::Pad,
+        self.state.filter_quality(),
+        self.state.global_alpha,
+        pattern_transform,
+      ),
+      ..Paint::default()
+    };
+    let transform = self.state.transform;
+    self.composite(paint, |pixmap, paint, mask| {
+      pixmap.fill_path(&rect, paint, FillRule::Winding, transform, mask)
+    });
+  }
+
+  /// Returns the pixels of a rectangle of the bitmap as non-premultiplied
+  /// RGBA. Pixels outside of the bitmap are transparent black.
+  fn get_image_data(&self, sx: i32, sy: i32, sw: u32, sh: u32) -> Vec<u8> {
+    let mut data = vec![0; sw as usize * sh as usize * 4];
+    let Some(pixmap) = &self.pixmap else {
+      return data;
+    };
+    let pixels = pixmap.pixels();
+    for y in 0..sh as i64 {
+      let source_y = sy as i64 + y;
+      if source_y < 0 || source_y >= pixmap.height() as i64 {
+        continue;
+      }
+      for x in 0..sw as i64 {
+        let source_x = sx as i64 + x;
+        if source_x < 0 || source_x >= pixmap.width() as i64 {
+          continue;
+        }
+        let pixel = pixels
+          [(source_y * pixmap.width() as i64 + source_x) as usize]
+          .demultiply();
+        let offset = ((y * sw as i64 + x) * 4) as usize;
+        data[offset..offset + 4].copy_from_slice(&[
+          pixel.red(),
+          pixel.green(),
+          pixel.blue(),
+          pixel.alpha(),
+        ]);
+      }
+    }
+    data
+  }
+
+  /// Writes the dirty rectangle of non-premultiplied RGBA `data` to the
+  /// bitmap at `(dx, dy)`, ignoring the drawing state.
+  #[allow(clippy::too_many_arguments)]
+  fn put_image_data(
+    &mut self,
+    data: &[u8],
+    width: u32,
+    dx: i32,
+    dy: i32,
+    dirty_x: u32,
+    dirty_y: u32,
+    dirty_width: u32,
+    dirty_height: u32,
+  ) {
+    let Some(pixmap) = &mut self.pixmap else {
+      return;
+    };
+    let (canvas_width, canvas_height) =
+      (pixmap.width() as i64, pixmap.height() as i64);
+    let pixels = pixmap.pixels_mut();
+    for y in dirty_y..dirty_y + dirty_height {
+      let target_y = dy as i64 + y as i64;
+      if target_y < 0 || target_y >= canvas_height {
+        continue;
+      }
+      for x in dirty_x..dirty_x + dirty_width {
+        let target_x = dx as i64 + x as i64;
+        if target_x < 0 || target_x >= canvas_width {
+          continue;
+        }
+        let offset = (y as usize * width as usize + x as usize) * 4;
+        let Some(&[r, g, b, a]) = data.get(offset..offset + 4) else {
+          continue;
+        };
+        let premultiply = |c: u8| ((c as u16 * a as u16 + 127) / 255) as u8;
+        pixels[(target_y * canvas_width + target_x) as usize] =
+          PremultipliedColorU8::from_rgba(
+            premultiply(r),
+            premultiply(g),
+            premultiply(b),
+            a,
+          )
+          .unwrap();
+      }
+    }
+  }
+}
+
+pub struct TextMetrics {
+  width: f64,
+  actual_bounding_box_left: f64,
+  actual_bounding_box_right: f64,
+  font_bounding_box_ascent: f64,
+  font_bounding_box_descent: f64,
+  actual_bounding_box_ascent: f64,
+  actual_bounding_box_descent: f64,
+  hanging_baseline: f64,
+  alphabetic_baseline: f64,
+  ideographic_baseline: f64,
+}
+
+// SAFETY: we're sure this can be GCed
+unsafe impl GarbageCollected for TextMetrics {
+  fn trace(&self, _visitor: &mut deno_core::v8::cppgc::Visitor) {}
+
+  fn get_name(&self) -> &'static std::ffi::CStr {
+    c"TextMetrics"
+  }
+}
+
+#[op2]
+impl TextMetrics {
+  #[constructor]
+  #[cppgc]
+  fn new(_: bool) -> Result<TextMetrics, ImageError> {
+    Err(ImageError::InvalidConstructor)
+  }
+
+  #[getter]
+  fn width(&self) -> f64 {
+    self.width
+  }
+
+  #[getter]
+  fn actual_bounding_box_left(&self) -> f64 {
+    self.actual_bounding_box_left
+  }
+
+  #[getter]
+  fn actual_bounding_box_right(&self) -> f64 {
+    self.actual_bounding_box_right
+  }
+
+  #[getter]
+  fn font_bounding_box_ascent(&self) -> f64 {
+    self.font_bounding_box_ascent
+  }
+
+  #[getter]
+  fn font_bounding_box_descent(&self) -> f64 {
+    self.font_bounding_box_descent
+  }
+
+  #[getter]
+  fn actual_bounding_box_ascent(&self) -> f64 {
+    self.actual_bounding_box_ascent
+  }
+
+  #[getter]
+  fn actual_bounding_box_descent(&self) -> f64 {
+    self.actual_bounding_box_descent
+  }
+
+  #[getter]
+  fn em_height_ascent(&self) -> f64 {
+    self.font_bounding_box_ascent
+  }
+
+  #[getter]
+  fn em_height_descent(&self) -> f64 {
+    self.font_bounding_box_descent
+  }
+
+  #[getter]
+  fn hanging_baseline(&self) -> f64 {
+    self.hanging_baseline
+  }
+
+  #[getter]
+  fn alphabetic_baseline(&self) -> f64 {
+    self.alphabetic_baseline
+  }
+
+  #[getter]
+  fn ideographic_baseline(&self) -> f64 {
+    self.ideographic_baseline
+  }
+}
+
+pub struct OffscreenCanvasRenderingContext2D {
+  canvas: v8::TracedReference<v8::Object>,
+  pub(crate) renderer: Rc<RefCell<Renderer>>,
+}
+
+// SAFETY: we're sure this can be GCed
+unsafe impl GarbageCollected for OffscreenCanvasRenderingContext2D {
+  fn trace(&self, visitor: &mut deno_core::v8::cppgc::Visitor) {
+    visitor.trace(&self.canvas);
+  }
+
+  fn get_name(&self) -> &'static std::ffi::CStr {
+    c"OffscreenCanvasRenderingContext2D"
+  }
+}
+
+impl WebIdlInterfaceConverter for OffscreenCanvasRenderingContext2D {
+  const NAME: &'static str = "OffscreenCanvasRenderingContext2D";
+}
+
+impl OffscreenCanvasRenderingContext2D {
+  pub(crate) fn create(
+    scope: &mut v8::PinScope<'_, '_>,
+    canvas: v8::Local<v8::Object>,
+    renderer: Rc<RefCell<Renderer>>,
+  ) -> Self {
+    OffscreenCanvasRenderingContext2D {
+      canvas: v8::TracedReference::new(scope, canvas),
+      renderer,
+    }
+  }
+
+  fn path_and_fill_rule(
+    scope: &mut v8::PinScope<'_, '_>,
+    path_or_fill_rule: v8::Local<v8::Value>,
+    fill_rule: v8::Local<v8::Value>,
+  ) -> Result<(Option<PathData>, FillRule), ImageError> {
+    match Path2D::try_unwrap(scope, path_or_fill_rule) {
+      Some(path) => Ok((
+        Some(path.path.borrow().clone()),
+        fill_rule_from_v8(scope, fill_rule)?,
+      )),
+      None => Ok((None, fill_rule_from_v8(scope, path_or_fill_rule)?)),
+    }
+  }
+
+  /// Returns the source image of `drawImage()` as a premultiplied pixmap,
+  /// or `None` if it has no pixels.
+  fn image_source(
+    scope: &mut v8::PinScope<'_, '_>,
+    value: v8::Local<v8::Value>,
+  ) -> Result<Option<Pixmap>, ImageError> {
+    if let Some(bitmap) =
+      cppgc::try_unwrap_cppgc_object::<ImageBitmap>(scope, value)
+    {
+      if bitmap.detached.get().is_some() {
+        return Err(ImageError::DetachedImageBitmap);
+      }
+      return Ok(rgba_to_pixmap(&bitmap.data.borrow().to_rgba8()));
+    }
+    if let Some(canvas) =
+      cppgc::try_unwrap_cppgc_object::<OffscreenCanvas>(scope, value)
+    {
+      if canvas.is_empty() {
+        return Err(ImageError::EmptyImageSource);
+      }
+      return Ok(canvas.snapshot());
+    }
+    Err(ImageError::InvalidImageSource)
+  }
+}
+
+#[op2]
+impl OffscreenCanvasRenderingContext2D {
+  #[constructor]
+  #[cppgc]
+  fn new(_: bool) -> Result<OffscreenCanvasRenderingContext2D, ImageError> {
+    Err(ImageError::InvalidConstructor)
+  }
+
+  #[getter]
+  fn canvas<'a>(
+    &self,
+    scope: &mut v8::PinScope<'a, '_>,
+  ) -> v8::Local<'a, v8::Object> {
+    self.canvas.get(scope).unwrap()
+  }
+
+  #[fast]
+  fn is_context_lost(&self) -> bool {
+    false
+  }
+
+  // state
+
+  #[fast]
+  #[undefined]
+  fn save(&self) {
+    let mut renderer = self.renderer.borrow_mut();
+    let state = renderer.state.clone();
+    renderer.stack.push(state);
+  }
+
+  #[fast]
+  #[undefined]
+  fn restore(&self) {
+    let mut renderer = self.renderer.borrow_mut();
+    if let Some(state) = renderer.stack.pop() {
+      renderer.state = state;
+    }
+  }
+
+  // Resets the bitmap, the drawing state, the state stack and the
+  // current path.
+  #[fast]
+  #[undefined]
+  fn reset(&self) {
+    let mut renderer = self.renderer.borrow_mut();
+    renderer.clear();
+    renderer.state = DrawingState::default();
+    renderer.stack.clear();
+    renderer.path.clear();
+  }
+
+  // transformations
+
+  #[required(2)]
+  #[undefined]
+  fn scale(
+    &self,
+    #[webidl] x: UnrestrictedDouble,
+    #[webidl] y: UnrestrictedDouble,
+  ) {
+    if x.is_finite() && y.is_finite() {
+      let mut renderer = self.renderer.borrow_mut();
+      renderer.state.transform =
+        renderer.state.transform.pre_scale(*x as f32, *y as f32);
+    }
+  }
+
+  #[required(1)]
+  #[undefined]
+  fn rotate(&self, #[webidl] angle: UnrestrictedDouble) {
+    if angle.is_finite() {
+      let (sin, cos) = (*angle as f32).sin_cos();
+      let mut renderer = self.renderer.borrow_mut();
+      renderer.state.transform = renderer
+        .state
+        .transform
+        .pre_concat(Transform::from_row(cos, sin, -sin, cos, 0.0, 0.0));
+    }
+  }
+
+  #[required(2)]
+  #[undefined]
+  fn translate(
+    &self,
+    #[webidl] x: UnrestrictedDouble,
+    #[webidl] y: UnrestrictedDouble,
+  ) {
+    if x.is_finite() && y.is_finite() {
+      let mut renderer = self.renderer.borrow_mut();
+      renderer.state.transform =
+        renderer.state.transform.pre_translate(*x as f32, *y as f32);
+    }
+  }
+
+  #[required(6)]
+  #[undefined]
+  fn transform(
+    &self,
+    #[webidl] a: UnrestrictedDouble,
+    #[webidl] b: UnrestrictedDouble,
+    #[webidl] c: UnrestrictedDouble,
+    #[webidl] d: UnrestrictedDouble,
+    #[webidl] e: UnrestrictedDouble,
+    #[webidl] f: UnrestrictedDouble,
+  ) {
+    let matrix = Transform::from_row(
+      *a as f32, *b as f32, *c as f32, *d as f32, *e as f32, *f as f32,
+    );
+    if matrix.is_finite() {
+      let mut renderer = self.renderer.borrow_mut();
+      renderer.state.transform = renderer.state.transform.pre_concat(matrix);
+    }
+  }
+
+  // Returns the current transformation matrix as a `DOMMatrix2DInit`
+  // like object with the `a` to `f` members.
+  fn get_transform<'a>(
+    &self,
+    scope: &mut v8::PinScope<'a, '_>,
+  ) -> v8::Local<'a, v8::Object> {
+    let ts = self.renderer.borrow().state.transform;
+    let object = v8::Object::new(scope);
+    for (key, value) in [
+      ("a", ts.sx),
+      ("b", ts.ky),
+      ("c", ts.kx),
+      ("d", ts.sy),
+      ("e", ts.tx),
+      ("f", ts.ty),
+    ] {
+      let key = v8::String::new(scope, key).unwrap();
+      let value = v8::Number::new(scope, value as f64);
+      object.set(scope, key.into(), value.into());
+    }
+    object
+  }
+
+  // Accepts either six numbers or a `DOMMatrix2DInit`.
+  #[fast]
+  #[undefined]
+  fn set_transform(
+    &self,
+    scope: &mut v8::PinScope<'_, '_>,
+    #[varargs] args: Option<&v8::FunctionCallbackArguments>,
+  ) -> Result<(), ImageError> {
+    let length = args.map(|args| args.length()).unwrap_or(0);
+    let matrix = match args {
+      Some(args) if length >= 6 => {
+        let mut values = [0.0f32; 6];
+        for (i, value) in values.iter_mut().enumerate() {
+          *value =
+            args.get(i as i32).number_value(scope).unwrap_or(f64::NAN) as f32;
+        }
+        let [a, b, c, d, e, f] = values;
+        Transform::from_row(a, b, c, d, e, f)
+      }
+      Some(args) if length == 1 => transform_from_v8(scope, args.get(0))?,
+      _ if length == 0 => Transform::identity(),
+      _ => return Err(ImageError::InvalidArity("[0, 1, 6]", length as usize)),
+    };
+    if matrix.is_finite() {
+      self.renderer.borrow_mut().state.transform = matrix;
+    }
+    Ok(())
+  }
+
+  #[fast]
+  #[undefined]
+  fn reset_transform(&self) {
+    self.renderer.borrow_mut().state.transform = Transform::identity();
+  }
+
+  // compositing
+
+  #[getter]
+  fn global_alpha(&self) -> f64 {
+    self.renderer.borrow().state.global_alpha as f64
+  }
+
+  #[setter]
+  fn global_alpha(&self, #[webidl] value: UnrestrictedDouble) {
+    if (0.0..=1.0).contains(&*value) {
+      self.renderer.borrow_mut().state.global_alpha = *value as f32;
+    }
+  }
+
+  #[getter]
+  #[string]
+  fn global_composite_operation(&self) -> &'static str {
+    self.renderer.borrow().state.composite.as_str()
+  }
+
+  #[setter]
+  fn global_composite_operation(&self, #[webidl] value: String) {
+    if let Some(op) = CompositeOperation::parse(&value) {
+      self.renderer.borrow_mut().state.composite = op;
+    }
+  }
+
+  // image smoothing
+
+  #[getter]
+  fn image_smoothing_enabled(&self) -> bool {
+    self.renderer.borrow().state.image_smoothing_enabled
+  }
+
+  #[setter]
+  fn image_smoothing_enabled(&self, #[webidl] value: bool) {
+    self.renderer.borrow_mut().state.image_smoothing_enabled = value;
+  }
+
+  #[getter]
+  #[string]
+  fn image_smoothing_quality(&self) -> &'static str {
+    self
+      .renderer
+      .borrow()
+      .state
+      .image_smoothing_quality
+      .as_str()
+  }
+
+  #[setter]
+  fn image_smoothing_quality(&self, #[webidl] value: String) {
+    if let Some(quality) = ImageSmoothingQuality::parse(&value) {
+      self.renderer.borrow_mut().state.image_smoothing_quality = quality;
+    }
+  }
+
+  // fill and stroke styles
+
+  #[getter]
+  fn fill_style<'a>(
+    &self,
+    scope: &mut v8::PinScope<'a, '_>,
+  ) -> v8::Local<'a, v8::Value> {
+    self.renderer.borrow().state.fill_style.to_v8(scope)
+  }
+
+  #[setter]
+  fn fill_style(
+    &self,
+    scope: &mut v8::PinScope<'_, '_>,
+    value: v8::Local<v8::Value>,
+  ) {
+    if let Some(style) = Style::from_v8(scope, value) {
+      self.renderer.borrow_mut().state.fill_style = style;
+    }
+  }
+
+  #[getter]
+  fn stroke_style<'a>(
+    &self,
+    scope: &mut v8::PinScope<'a, '_>,
+  ) -> v8::Local<'a, v8::Value> {
+    self.renderer.borrow().state.stroke_style.to_v8(scope)
+  }
+
+  #[setter]
+  fn stroke_style(
+    &self,
+    scope: &mut v8::PinScope<'_, '_>,
+    value: v8::Local<v8::Value>,
+  ) {
+    if let Some(style) = Style::from_v8(scope, value) {
+      self.renderer.borrow_mut().state.stroke_style = style;
+    }
+  }
+
+  #[required(4)]
+  #[cppgc]
+  fn create_linear_gradient(
+    &self,
+    #[webidl] x0: f64,
+    #[webidl] y0: f64,
+    #[webidl] x1: f64,
+    #[webidl] y1: f64,
+  ) -> CanvasGradient {
+    CanvasGradient {
+      gradient: Rc::new(RefCell::new(Gradient {
+        kind: GradientKind::Linear {
+          x0: x0 as f32,
+          y0: y0 as f32,
+          x1: x1 as f32,
+          y1: y1 as f32,
+        },
+        stops: Vec::new(),
+      })),
+    }
+  }
+
+  #[required(6)]
+  #[cppgc]
+  fn create_radial_gradient(
+    &self,
+    #[webidl] x0: f64,
+    #[webidl] y0: f64,
+    #[webidl] r0: f64,
+    #[webidl] x1: f64,
+    #[webidl] y1: f64,
+    #[webidl] r1: f64,
+  ) -> Result<CanvasGradient, ImageError> {
+    for radius in [r0, r1] {
+      if radius < 0.0 {
+        return Err(ImageError::NegativeRadius(radius));
+      }
+    }
+    Ok(CanvasGradient {
+      gradient: Rc::new(RefCell::new(Gradient {
+        kind: GradientKind::Radial {
+          x0: x0 as f32,
+          y0: y0 as f32,
+          r0: r0 as f32,
+          x1: x1 as f32,
+          y1: y1 as f32,
+          r1: r1 as f32,
+        },
+        stops: Vec::new(),
+      })),
+    })
+  }
+
+  // rects
+
+  #[required(4)]
+  #[undefined]
+  fn clear_rect(
+    &self,
+    #[webidl] x: UnrestrictedDouble,
+    #[webidl] y: UnrestrictedDouble,
+    #[webidl] w: UnrestrictedDouble,
+    #[webidl] h: UnrestrictedDouble,
+  ) {
+    self.renderer.borrow_mut().clear_rect(*x, *y, *w, *h);
+  }
+
+  #[required(4)]
+  #[undefined]
+  fn fill_rect(
+    &self,
+    #[webidl] x: UnrestrictedDouble,
+    #[webidl] y: UnrestrictedDouble,
+    #[webidl] w: UnrestrictedDouble,
+    #[webidl] h: UnrestrictedDouble,
+  ) {
+    let mut rect = PathData::default();
+    rect.rect(Transform::identity(), *x, *y, *w, *h);
+    if let Some(rect) = rect.to_path() {
+      self
+        .renderer
+        .borrow_mut()
+        .fill_path(&rect, FillRule::Winding);
+    }
+  }
+
+  #[required(4)]
+  #[undefined]
+  fn stroke_rect(
+    &self,
+    #[webidl] x: UnrestrictedDouble,
+    #[webidl] y: UnrestrictedDouble,
+    #[webidl] w: UnrestrictedDouble,
+    #[webidl] h: UnrestrictedDouble,
+  ) {
+    let mut rect = PathData::default();
+    rect.rect(Transform::identity(), *x, *y, *w, *h);
+    if let Some(rect) = rect.to_path() {
+      self.renderer.borrow_mut().stroke_path(&rect);
+    }
+  }
+
+  // path drawing
+
+  #[fast]
+  #[undefined]
+  fn begin_path(&self) {
+    self.renderer.borrow_mut().path.clear();
+  }
+
+  // Fills the current path or the given `Path2D`.
+  #[fast]
+  #[undefined]
+  fn fill(
+    &self,
+    scope: &mut v8::PinScope<'_, '_>,
+    path_or_fill_rule: v8::Local<v8::Value>,
+    fill_rule: v8::Local<v8::Value>,
+  ) -> Result<(), ImageError> {
+    let (path, fill_rule) =
+      Self::path_and_fill_rule(scope, path_or_fill_rule, fill_rule)?;
+    let mut renderer = self.renderer.borrow_mut();
+    let path = match path {
+      Some(path) => path.to_path(),
+      None => renderer.user_path(&renderer.path),
+    };
+    if let Some(path) = path {
+      renderer.fill_path(&path, fill_rule);
+    }
+    Ok(())
+  }
+
+  // Strokes the current path or the given `Path2D`.
+  #[fast]
+  #[undefined]
+  fn stroke(
+    &self,
+    scope: &mut v8::PinScope<'_, '_>,
+    path: v8::Local<v8::Value>,
+  ) -> Result<(), ImageError> {
+    let path = if path.is_undefined() {
+      None
+    } else {
+      let path =
+        Path2D::try_unwrap(scope, path).ok_or(ImageError::InvalidPath)?;
+      Some(path.path.borrow().clone())
+    };
+    let mut renderer = self.renderer.borrow_mut();
+    let path = match path {
+      Some(path) => path.to_path(),
+      None => renderer.user_path(&renderer.path),
+    };
+    if let Some(path) = path {
+      renderer.stroke_path(&path);
+    }
+    Ok(())
+  }
+
+  // Intersects the clipping region with the current path or the given
+  // `Path2D`.
+  #[fast]
+  #[undefined]
+  fn clip(
+    &self,
+    scope: &mut v8::PinScope<'_, '_>,
+    path_or_fill_rule: v8::Local<v8::Value>,
+    fill_rule: v8::Local<v8::Value>,
+  ) -> Result<(), ImageError> {
+    let (path, fill_rule) =
+      Self::path_and_fill_rule(scope, path_or_fill_rule, fill_rule)?;
+    let mut renderer = self.renderer.borrow_mut();
+    let path = match path {
+      Some(path) => {
+        let mut device = PathData::default();
+        device.add_path(&path, renderer.state.transform);
+        device
+      }
+      None => renderer.path.clone(),
+    };
+    renderer.clip(&path, fill_rule);
+    Ok(())
+  }
+
+  // text
+
+  #[required(3)]
+  #[undefined]
+  fn fill_text(
+    &self,
+    state: &OpState,
+    #[webidl] text: String,
+    #[webidl] x: UnrestrictedDouble,
+    #[webidl] y: UnrestrictedDouble,
+    #[webidl] max_width: Option<UnrestrictedDouble>,
+  ) {
+    self.renderer.borrow_mut().draw_text(
+      state.borrow::<FontRegistry>(),
+      &text,
+      *x,
+      *y,
+      max_width.map(|w| *w),
+      false,
+    );
+  }
+
+  #[required(3)]
+  #[undefined]
+  fn stroke_text(
+    &self,
+    state: &OpState,
+    #[webidl] text: String,
+    #[webidl] x: UnrestrictedDouble,
+    #[webidl] y: UnrestrictedDouble,
+    #[webidl] max_width: Option<UnrestrictedDouble>,
+  ) {
+    self.renderer.borrow_mut().draw_text(
+      state.borrow::<FontRegistry>(),
+      &text,
+      *x,
+      *y,
+      max_width.map(|w| *w),
+      true,
+    );
+  }
+
+  #[required(1)]
+  #[cppgc]
+  fn measure_text(
+    &self,
+    state: &OpState,
+    #[webidl] text: String,
+  ) -> TextMetrics {
+    let renderer = self.renderer.borrow();
+    let (layout, dx, dy) =
+      renderer.layout_text(state.borrow::<FontRegistry>(), &text);
+    let (left, top, right, bottom) = match &layout.path {
+      Some(path) => {
+        let bounds = path.bounds();
+        (
+          bounds.left() + dx,
+          bounds.top() + dy,
+          bounds.right() + dx,
+          bounds.bottom() + dy,
+        )
+      }
+      None => (dx, dy, dx, dy),
+    };
+    TextMetrics {
+      width: layout.width as f64,
+      actual_bounding_box_left: -left as f64,
+      actual_bounding_box_right: right as f64,
+      font_bounding_box_ascent: (layout.ascent - dy) as f64,
+      font_bounding_box_descent: (layout.descent + dy) as f64,
+      actual_bounding_box_ascent: -top as f64,
+      actual_bounding_box_descent: bottom as f64,
+      hanging_baseline: (layout.ascent * 0.8 - dy) as f64,
+      alphabetic_baseline: -dy as f64,
+      ideographic_baseline: (-layout.descent - dy) as f64,
+    }
+  }
+
+  // drawing images
+
+  // Draws an `ImageBitmap` or `OffscreenCanvas` with the
+  // `(image, dx, dy)`, `(image, dx, dy, dw, dh)` or
+  // `(image, sx, sy, sw, sh, dx, dy, dw, dh)` arguments.
+  #[fast]
+  #[undefined]
+  fn draw_image(
+    &self,
+    scope: &mut v8::PinScope<'_, '_>,
+    #[varargs] args: Option<&v8::FunctionCallbackArguments>,
+  ) -> Result<(), ImageError> {
+    let Some(args) = args else {
+      return Err(ImageError::InvalidArity("[3, 5, 9]", 0));
+    };
+    let length = args.length() as usize;
+    if !matches!(length, 3 | 5 | 9..) {
+      return Err(ImageError::InvalidArity("[3, 5, 9]", length));
+    }
+    let Some(image) = Self::image_source(scope, args.get(0))? else {
+      return Ok(());
+    };
+    let mut numbers = [0.0; 8];
+    for (i, number) in numbers.iter_mut().take(length - 1).enumerate() {
+      *number = args
+        .get(i as i32 + 1)
+        .number_value(scope)
+        .unwrap_or(f64::NAN);
+    }
+    let (width, height) = (image.width() as f64, image.height() as f64);
+    let [sx, sy, sw, sh, dx, dy, dw, dh] = match length {
+      3 => [
+        0.0, 0.0, width, height, numbers[0], numbers[1], width, height,
+      ],
+      5 => [
+        0.0, 0.0, width, height, numbers[0], numbers[1], numbers[2], numbers[3],
+      ],
+      _ => numbers,
+    };
+    self
+      .renderer
+      .borrow_mut()
+      .draw_image(&image, sx, sy, sw, sh, dx, dy, dw, dh);
+    Ok(())
+  }
+
+  // pixel manipulation
+
+  #[buffer]
+  #[symbol("Deno_getImageData")]
+  fn get_image_data(&self, sx: i32, sy: i32, sw: u32, sh: u32) -> Vec<u8> {
+    self.renderer.borrow().get_image_data(sx, sy, sw, sh)
+  }
+
+  #[fast]
+  #[undefined]
+  #[symbol("Deno_putImageData")]
+  fn put_image_data(
+    &self,
+    #[buffer] data: &[u8],
+    width: u32,
+    dx: i32,
+    dy: i32,
+    dirty_x: u32,
+    dirty_y: u32,
+    dirty_width: u32,
+    dirty_height: u32,
+  ) {
+    self.renderer.borrow_mut().put_image_data(
+      data,
+      width,
+      dx,
+      dy,
+      dirty_x,
+      dirty_y,
+      dirty_width,
+      dirty_height,
+    );
+  }
+
+  // line styles
+
+  #[getter]
+  fn line_width(&self) -> f64 {
+    self.renderer.borrow().state.line_width as f64
+  }
+
+  #[setter]
+  fn line_width(&self, #[webidl] value: UnrestrictedDouble) {
+    if value.is_finite() && *value > 0.0 {
+      self.renderer.borrow_mut().state.line_width = *value as f32;
+    }
+  }
+
+  #[getter]
+  #[string]
+  fn line_cap(&self) -> &'static str {
+    self.renderer.borrow().state.line_cap.as_str()
+  }
+
+  #[setter]
+  fn line_cap(&self, #[webidl] value: String) {
+    if let Some(cap) = LineCap::parse(&value) {
+      self.renderer.borrow_mut().state.line_cap = cap;
+    }
+  }
+
+  #[getter]
+  #[string]
+  fn line_join(&self) -> &'static str {
+    self.renderer.borrow().state.line_join.as_str()
+  }
+
+  #[setter]
+  fn line_join(&self, #[webidl] value: String) {
+    if let Some(join) = LineJoin::parse(&value) {
+      self.renderer.borrow_mut().state.line_join = join;
+    }
+  }
+
+  #[getter]
+  fn miter_limit(&self) -> f64 {
+    self.renderer.borrow().state.miter_limit as f64
+  }
+
+  #[setter]
+  fn miter_limit(&self, #[webidl] value: UnrestrictedDouble) {
+    if value.is_finite() && *value > 0.0 {
+      self.renderer.borrow_mut().state.miter_limit = *value as f32;
+    }
+  }
+
+  #[required(1)]
+  #[undefined]
+  fn set_line_dash(&self, #[webidl] segments: Vec<UnrestrictedDouble>) {
+    if segments.iter().any(|s| !s.is_finite() || **s < 0.0) {
+      return;
+    }
+    let mut dash: Vec<f32> = segments.iter().map(|s| **s as f32).collect();
+    if dash.len() % 2 == 1 {
+      dash.extend_from_within(..);
+    }
+    self.renderer.borrow_mut().state.line_dash = dash;
+  }
+
+  fn get_line_dash<'a>(
+    &self,
+    scope: &mut v8::PinScope<'a, '_>,
+  ) -> v8::Local<'a, v8::Array> {
+    let renderer = self.renderer.borrow();
+    let segments = renderer
+      .state
+      .line_dash
+      .iter()
+      .map(|s| v8::Number::new(scope, *s as f64).into())
+      .collect::<Vec<_>>();
+    v8::Array::new_with_elements(scope, &segments)
+  }
+
+  #[getter]
+  fn line_dash_offset(&self) -> f64 {
+    self.renderer.borrow().state.line_dash_offset as f64
+  }
+
+  #[setter]
+  fn line_dash_offset(&self, #[webidl] value: UnrestrictedDouble) {
+    if value.is_finite() {
+      self.renderer.borrow_mut().state.line_dash_offset = *value as f32;
+    }
+  }
+
+  // text styles
+
+  #[getter]
+  #[string]
+  fn font(&self) -> String {
+    self.renderer.borrow().state.font.serialize()
+  }
+
+  #[setter]
+  fn font(&self, #[webidl] value: String) {
+    if let Some(font) = css::parse_font(&value) {
+      self.renderer.borrow_mut().state.font = font;
+    }
+  }
+
+  #[getter]
+  #[string]
+  fn text_align(&self) -> &'static str {
+    self.renderer.borrow().state.text_align.as_str()
+  }
+
+  #[setter]
+  fn text_align(&self, #[webidl] value: String) {
+    if let Some(align) = TextAlign::parse(&value) {
+      self.renderer.borrow_mut().state.text_align = align;
+    }
+  }
+
+  #[getter]
+  #[string]
+  fn text_baseline(&self) -> &'static str {
+    self.renderer.borrow().state.text_baseline.as_str()
+  }
+
+  #[setter]
+  fn text_baseline(&self, #[webidl] value: String) {
+    if let Some(baseline) = TextBaseline::parse(&value) {
+      self.renderer.borrow_mut().state.text_baseline = baseline;
+    }
+  }
+
+  #[getter]
+  #[string]
+  fn direction(&self) -> &'static str {
+    self.renderer.borrow().state.direction.as_str()
+  }
+
+  #[setter]
+  fn direction(&self, #[webidl] value: String) {
+    if let Some(direction) = Direction::parse(&value) {
+      self.renderer.borrow_mut().state.direction = direction;
+    }
+  }
+
+  // paths
+
+  #[fast]
+  #[undefined]
+  fn close_path(&self) {
+    self.renderer.borrow_mut().path.close();
+  }
+
+  #[required(2)]
+  #[undefined]
+  fn move_to(
+    &self,
+    #[webidl] x: UnrestrictedDouble,
+    #[webidl] y: UnrestrictedDouble,
+  ) {
+    let renderer = &mut *self.renderer.borrow_mut();
+    renderer.path.move_to(renderer.state.transform, *x, *y);
+  }
+
+  #[required(2)]
+  #[undefined]
+  fn line_to(
+    &self,
+    #[webidl] x: UnrestrictedDouble,
+    #[webidl] y: UnrestrictedDouble,
+  ) {
+    let renderer = &mut *self.renderer.borrow_mut();
+    renderer.path.line_to(renderer.state.transform, *x, *y);
+  }
+
+  #[required(4)]
+  #[undefined]
+  fn quadratic_curve_to(
+    &self,
+    #[webidl] cpx: UnrestrictedDouble,
+    #[webidl] cpy: UnrestrictedDouble,
+    #[webidl] x: UnrestrictedDouble,
+    #[webidl] y: UnrestrictedDouble,
+  ) {
+    let renderer = &mut *self.renderer.borrow_mut();
+    renderer
+      .path
+      .quad_to(renderer.state.transform, *cpx, *cpy, *x, *y);
+  }
+
+  #[required(6)]
+  #[undefined]
+  fn bezier_curve_to(
+    &self,
+    #[webidl] cp1x: UnrestrictedDouble,
+    #[webidl] cp1y: UnrestrictedDouble,
+    #[webidl] cp2x: UnrestrictedDouble,
+    #[webidl] cp2y: UnrestrictedDouble,
+    #[webidl] x: UnrestrictedDouble,
+    #[webidl] y: UnrestrictedDouble,
+  ) {
+    let renderer = &mut *self.renderer.borrow_mut();
+    renderer.path.bezier_to(
+      renderer.state.transform,
+      *cp1x,
+      *cp1y,
+      *cp2x,
+      *cp2y,
+      *x,
+      *y,
+    );
+  }
+
+  #[required(5)]
+  #[undefined]
+  fn arc_to(
+    &self,
+    #[webidl] x1: UnrestrictedDouble,
+    #[webidl] y1: UnrestrictedDouble,
+    #[webidl] x2: UnrestrictedDouble,
+    #[webidl] y2: UnrestrictedDouble,
+    #[webidl] radius: UnrestrictedDouble,
+  ) -> Result<(), ImageError> {
+    let renderer = &mut *self.renderer.borrow_mut();
+    renderer
+      .path
+      .arc_to(renderer.state.transform, *x1, *y1, *x2, *y2, *radius)
+  }
+
+  #[required(4)]
+  #[undefined]
+  fn rect(
+    &self,
+    #[webidl] x: UnrestrictedDouble,
+    #[webidl] y: UnrestrictedDouble,
+    #[webidl] w: UnrestrictedDouble,
+    #[webidl] h: UnrestrictedDouble,
+  ) {
+    let renderer = &mut *self.renderer.borrow_mut();
+    renderer.path.rect(renderer.state.transform, *x, *y, *w, *h);
+  }
+
+  #[required(4)]
+  #[undefined]
+  fn round_rect(
+    &self,
+    scope: &mut v8::PinScope<'_, '_>,
+    #[webidl] x: UnrestrictedDouble,
+    #[webidl] y: UnrestrictedDouble,
+    #[webidl] w: UnrestrictedDouble,
+    #[webidl] h: UnrestrictedDouble,
+    radii: v8::Local<v8::Value>,
+  ) -> Result<(), ImageError> {
+    let radii = radii_from_v8(scope, radii);
+    let renderer = &mut *self.renderer.borrow_mut();
+    renderer
+      .path
+      .round_rect(renderer.state.transform, *x, *y, *w, *h, &radii)
+  }
+
+  #[required(5)]
+  #[undefined]
+  fn arc(
+    &self,
+    #[webidl] x: UnrestrictedDouble,
+    #[webidl] y: UnrestrictedDouble,
+    #[webidl] radius: UnrestrictedDouble,
+    #[webidl] start_angle: UnrestrictedDouble,
+    #[webidl] end_angle: UnrestrictedDouble,
+    #[webidl] counterclockwise: Option<bool>,
+  ) -> Result<(), ImageError> {
+    let renderer = &mut *self.renderer.borrow_mut();
+    renderer.path.ellipse(
+      renderer.state.transform,
+      *x,
+      *y,
+      *radius,
+      *radius,
+      0.0,
+      *start_angle,
+      *end_angle,
+      counterclockwise.unwrap_or(false),
+    )
+  }
+
+  #[required(7)]
+  #[undefined]
+  fn ellipse(
+    &self,
+    #[webidl] x: UnrestrictedDouble,
+    #[webidl] y: UnrestrictedDouble,
+    #[webidl] radius_x: UnrestrictedDouble,
+    #[webidl] radius_y: UnrestrictedDouble,
+    #[webidl] rotation: UnrestrictedDouble,
+    #[webidl] start_angle: UnrestrictedDouble,
+    #[webidl] end_angle: UnrestrictedDouble,
+    #[webidl] counterclockwise: Option<bool>,
+  ) -> Result<(), ImageError> {
+    let renderer = &mut *self.renderer.borrow_mut();
+    renderer.path.ellipse(
+      renderer.state.transform,
+      *x,
+      *y,
+      *radius_x,
+      *radius_y,
+      *rotation,
+      *start_angle,
+      *end_angle,
+      counterclockwise.unwrap_or(false),
+    )
+  }
+}
@@ -0,0 +1,582 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+//! Parsing and serialization of the CSS values accepted by the 2D
+//! context: colors (`fillStyle`, `strokeStyle`, `addColorStop()`) and the
+//! `font` shorthand.
+
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rgba {
+  pub r: u8,
+  pub g: u8,
+  pub b: u8,
+  /// Alpha in the range `0.0..=1.0`.
+  pub a: f32,
+}
+
+impl Rgba {
+  pub const BLACK: Rgba = Rgba {
+    r: 0,
+    g: 0,
+    b: 0,
+    a: 1.0,
+  };
+
+  pub const TRANSPARENT: Rgba = Rgba {
+    r: 0,
+    g: 0,
+    b: 0,
+    a: 0.0,
+  };
+
+  pub fn to_color(self, alpha: f32) -> tiny_skia::Color {
+    tiny_skia::Color::from_rgba8(
+      self.r,
+      self.g,
+      self.b,
+      (self.a * alpha * 255.0).round().clamp(0.0, 255.0) as u8,
+    )
+  }
+
+  /// Serializes the color the way the `fillStyle` and `strokeStyle`
+  /// getters do: `#rrggbb` for opaque colors, `rgba(r, g, b, a)`
+  /// otherwise.
+  pub fn serialize(&self) -> String {
+    if self.a >= 1.0 {
+      format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    } else {
+      // Alpha is stored with 8 bits of precision, so use the shortest
+      // decimal that maps back to the same byte.
+      let byte = (self.a.clamp(0.0, 1.0) * 255.0).round();
+      let mut alpha = format!("{:.2}", byte / 255.0);
+      if (alpha.parse::<f32>().unwrap() * 255.0).round() != byte {
+        alpha = format!("{:.3}", byte / 255.0);
+      }
+      let alpha = alpha.trim_end_matches('0').trim_end_matches('.');
+      format!("rgba({}, {}, {}, {alpha})", self.r, self.g, self.b)
+    }
+  }
+}
+
+/// Parses a CSS `<color>`. Returns `None` for invalid values, which the
+/// context ignores.
+pub fn parse_color(input: &str) -> Option<Rgba> {
+  let input = input.trim().to_ascii_lowercase();
+  if let Some(hex) = input.strip_prefix('#') {
+    return parse_hex(hex);
+  }
+  if let Some(open) = input.find('(') {
+    let name = input[..open].trim_end();
+    let args = input[open + 1..].strip_suffix(')')?;
+    return match name {
+      "rgb" | "rgba" => parse_rgb(args),
+      "hsl" | "hsla" => parse_hsl(args),
+      _ => None,
+    };
+  }
+  match input.as_str() {
+    "transparent" => Some(Rgba::TRANSPARENT),
+    // There is no element to inherit from in an `OffscreenCanvas`.
+    "currentcolor" => Some(Rgba::BLACK),
+    name => {
+      let index = NAMED_COLORS
+        .binary_search_by(|(color, _)| (*color).cmp(name))
+        .ok()?;
+      let rgb = NAMED_COLORS[index].1;
+      Some(Rgba {
+        r: (rgb >> 16) as u8,
+        g: (rgb >> 8) as u8,
+        b: rgb as u8,
+        a: 1.0,
+      })
+    }
+  }
+}
+
+fn parse_hex(hex: &str) -> Option<Rgba> {
+  if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+    return None;
+  }
+  let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).unwrap();
+  let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap();
+  let (r, g, b, a) = match hex.len() {
+    3 => (digit(0) * 17, digit(1) * 17, digit(2) * 17, 255),
+    4 => (digit(0) * 17, digit(1) * 17, digit(2) * 17, digit(3) * 17),
+    6 => (byte(0), byte(2), byte(4), 255),
+    8 => (byte(0), byte(2), byte(4), byte(6)),
+    _ => return None,
+  };
+  Some(Rgba {
+    r,
+    g,
+    b,
+    a: a as f32 / 255.0,
+  })
+}
+
+/// Splits the arguments of a color function, accepting both the legacy
+/// comma separated syntax and the space separated one with `/ alpha`.
+fn split_args(args: &str) -> Option<Vec<&str>> {
+  let parts: Vec<&str> = if args.contains(',') {
+    args.split(',').map(str::trim).collect()
+  } else {
+    let (color, alpha) = match args.split_once('/') {
+      Some((color, alpha)) => (color, Some(alpha.trim())),
+      None => (args, None),
+    };
+    let mut parts: Vec<&str> = color.split_whitespace().collect();
+    if parts.len() != 3 {
+      return None;
+    }
+    parts.extend(alpha);
+    parts
+  };
+  if parts.len() < 3 || parts.len() > 4 || parts.iter().any(|p| p.is_empty()) {
+    return None;
+  }
+  Some(parts)
+}
+
+fn parse_number(value: &str) -> Option<f32> {
+  let n = value.parse::<f32>().ok()?;
+  n.is_finite().then_some(n)
+}
+
+fn parse_alpha(value: Option<&&str>) -> Option<f32> {
+  let Some(value) = value else {
+    return Some(1.0);
+  };
+  let alpha = match value.strip_suffix('%') {
+    Some(percent) => parse_number(percent)? / 100.0,
+    None => parse_number(value)?,
+  };
+  Some(alpha.clamp(0.0, 1.0))
+}
+
+fn parse_rgb(args: &str) -> Option<Rgba> {
+  let parts = split_args(args)?;
+  let channel = |value: &str| -> Option<u8> {
+    let n = match value.strip_suffix('%') {
+      Some(percent) => parse_number(percent)? * 2.55,
+      None => parse_number(value)?,
+    };
+    Some(n.round().clamp(0.0, 255.0) as u8)
+  };
+  Some(Rgba {
+    r: channel(parts[0])?,
+    g: channel(parts[1])?,
+    b: channel(parts[2])?,
+    a: parse_alpha(parts.get(3))?,
+  })
+}
+
+fn parse_hsl(args: &str) -> Option<Rgba> {
+  let parts = split_args(args)?;
+  let hue = parts[0];
+  let hue = if let Some(deg) = hue.strip_suffix("deg") {
+    parse_number(deg)?
+  } else if let Some(turn) = hue.strip_suffix("turn") {
+    parse_number(turn)? * 360.0
+  } else if let Some(rad) = hue.strip_suffix("rad") {
+    parse_number(rad)?.to_degrees()
+  } else {
+    parse_number(hue)?
+  };
+  let percent = |value: &str| -> Option<f32> {
+    Some((parse_number(value.strip_suffix('%')?)? / 100.0).clamp(0.0, 1.0))
+  };
+  let saturation = percent(parts[1])?;
+  let lightness = percent(parts[2])?;
+
+  // https://drafts.csswg.org/css-color-4/#hsl-to-rgb
+  let hue = hue.rem_euclid(360.0);
+  let channel = |n: f32| {
+    let k = (n + hue / 30.0) % 12.0;
+    let a = saturation * lightness.min(1.0 - lightness);
+    let value = lightness - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0);
+    (value * 255.0).round() as u8
+  };
+  Some(Rgba {
+    r: channel(0.0),
+    g: channel(8.0),
+    b: channel(4.0),
+    a: parse_alpha(parts.get(3))?,
+  })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FontStyle {
+  Normal,
+  Italic,
+  Oblique,
+}
+
+/// A parsed `font` shorthand.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Font {
+  pub style: FontStyle,
+  pub weight: u16,
+  /// Font size in CSS pixels.
+  pub size: f32,
+  pub families: Vec<String>,
+}
+
+impl Default for Font {
+  fn default() -> Self {
+    Font {
+      style: FontStyle::Normal,
+      weight: 400,
+      size: 10.0,
+      families: vec!["sans-serif".to_string()],
+    }
+  }
+}
+
+impl Font {
+  /// Serializes the font the way the `font` getter does.
+  pub fn serialize(&self) -> String {
+    let mut s = String::new();
+    match self.style {
+      FontStyle::Normal => {}
+      FontStyle::Italic => s.push_str("italic "),
+      FontStyle::Oblique => s.push_str("oblique "),
+    }
+    match self.weight {
+      400 => {}
+      700 => s.push_str("bold "),
+      weight => {
+        let _ = write!(s, "{weight} ");
+      }
+    }
+    let _ = write!(s, "{}px ", self.size);
+    for (i, family) in self.families.iter().enumerate() {
+      if i > 0 {
+        s.push_str(", ");
+      }
+      if is_generic_family(family) || !family.contains(' ') {
+        s.push_str(family);
+      } else {
+        let _ = write!(s, "\"{family}\"");
+      }
+    }
+    s
+  }
+}
+
+fn is_generic_family(family: &str) -> bool {
+  matches!(
+    family,
+    "serif"
+      | "sans-serif"
+      | "monospace"
+      | "cursive"
+      | "fantasy"
+      | "system-ui"
+      | "ui-serif"
+      | "ui-sans-serif"
+      | "ui-monospace"
+  )
+}
+
+/// Parses a CSS `font` shorthand, e.g. `italic bold 16px "Inter", serif`.
+/// The `line-height` part is accepted and ignored. `em` and `%` sizes are
+/// relative to the default size of 10px.
+pub fn parse_font(input: &str) -> Option<Font> {
+  let input = input.trim();
+  let mut font = Font {
+    families: Vec::new(),
+    ..Font::default()
+  };
+
+  // Style, variant, weight and stretch keywords come in any order before
+  // the size.
+  let mut rest = input;
+  let size = loop {
+    let (token, tail) = rest.split_once(char::is_whitespace)?;
+    rest = tail.trim_start();
+    let lower = token.to_ascii_lowercase();
+    match lower.as_str() {
+      "normal" | "small-caps" => {}
+      "italic" => font.style = FontStyle::Italic,
+      "oblique" => font.style = FontStyle::Oblique,
+      "ultra-condensed" | "extra-condensed" | "condensed"
+      | "semi-condensed" | "semi-expanded" | "expanded" | "extra-expanded"
+      | "ultra-expanded" => {}
+      "bold" | "bolder" => font.weight = 700,
+      "lighter" => font.weight = 100,
+      _ => match lower.parse::<f32>() {
+        Ok(weight) if (1.0..=1000.0).contains(&weight) => {
+          font.weight = weight.round() as u16;
+        }
+        _ => break lower,
+      },
+    }
+  };
+
+  let size = size.split('/').next()?;
+  font.size = if let Some(px) = size.strip_suffix("px") {
+    parse_number(px)?
+  } else if let Some(pt) = size.strip_suffix("pt") {
+    parse_number(pt)? * 4.0 / 3.0
+  } else if let Some(em) = size.strip_suffix("rem") {
+    parse_number(em)? * 10.0
+  } else if let Some(em) = size.strip_suffix("em") {
+    parse_number(em)? * 10.0
+  } else if let Some(percent) = size.strip_suffix('%') {
+    parse_number(percent)? / 10.0
+  } else {
+    return None;
+  };
+  if font.size < 0.0 {
+    return None;
+  }
+
+  // A line height may follow the size after a slash, separated by spaces.
+  if let Some(tail) = rest.strip_prefix('/') {
+    let tail = tail.trim_start();
+    rest = tail
+      .split_once(char::is_whitespace)
+      .map(|(_, tail)| tail.trim_start())?;
+  }
+
+  for family in rest.split(',') {
+    let family = family.trim();
+    let family = if let Some(quoted) = family
+      .strip_prefix('"')
+      .and_then(|f| f.strip_suffix('"'))
+      .or_else(|| family.strip_prefix('\'').and_then(|f| f.strip_suffix('\'')))
+    {
+      quoted.to_string()
+    } else {
+      let family = family.split_whitespace().collect::<Vec<_>>().join(" ");
+      let lower = family.to_ascii_lowercase();
+      if is_generic_family(&lower) {
+        lower
+      } else {
+        family
+      }
+    };
+    if family.is_empty() {
+      return None;
+    }
+    font.families.push(family);
+  }
+  if font.families.is_empty() {
+    return None;
+  }
+
+  Some(font)
+}
+
+// https://drafts.csswg.org/css-color-4/#named-colors
+// Sorted by name for binary search.
+const NAMED_COLORS: &[(&str, u32)] = &[
+  ("aliceblue", 0xf0f8ff),
+  ("antiquewhite", 0xfaebd7),
+  ("aqua", 0x00ffff),
+  ("aquamarine", 0x7fffd4),
+  ("azure", 0xf0ffff),
+  ("beige", 0xf5f5dc),
+  ("bisque", 0xffe4c4),
+  ("black", 0x000000),
+  ("blanchedalmond", 0xffebcd),
+  ("blue", 0x0000ff),
+  ("blueviolet", 0x8a2be2),
+  ("brown", 0xa52a2a),
+  ("burlywood", 0xdeb887),
+  ("cadetblue", 0x5f9ea0),
+  ("chartreuse", 0x7fff00),
+  ("chocolate", 0xd2691e),
+  ("coral", 0xff7f50),
+  ("cornflowerblue", 0x6495ed),
+  ("cornsilk", 0xfff8dc),
+  ("crimson", 0xdc143c),
+  ("cyan", 0x00ffff),
+  ("darkblue", 0x00008b),
+  ("darkcyan", 0x008b8b),
+  ("darkgoldenrod", 0xb8860b),
+  ("darkgray", 0xa9a9a9),
+  ("darkgreen", 0x006400),
+  ("darkgrey", 0xa9a9a9),
+  ("darkkhaki", 0xbdb76b),
+  ("darkmagenta", 0x8b008b),
+  ("darkolivegreen", 0x556b2f),
+  ("darkorange", 0xff8c00),
+  ("darkorchid", 0x9932cc),
+  ("darkred", 0x8b0000),
+  ("darksalmon", 0xe9967a),
+  ("darkseagreen", 0x8fbc8f),
+  ("darkslateblue", 0x483d8b),
+  ("darkslategray", 0x2f4f4f),
+  ("darkslategrey", 0x2f4f4f),
+  ("darkturquoise", 0x00ced1),
+  ("darkviolet", 0x9400d3),
+  ("deeppink", 0xff1493),
+  ("deepskyblue", 0x00bfff),
+  ("dimgray", 0x696969),
+  ("dimgrey", 0x696969),
+  ("dodgerblue", 0x1e90ff),
+  ("firebrick", 0xb22222),
+  ("floralwhite", 0xfffaf0),
+  ("forestgreen", 0x228b22),
+  ("fuchsia", 0xff00ff),
+  ("gainsboro", 0xdcdcdc),
+  ("ghostwhite", 0xf8f8ff),
+  ("gold", 0xffd700),
+  ("goldenrod", 0xdaa520),
+  ("gray", 0x808080),
+  ("green", 0x008000),
+  ("greenyellow", 0xadff2f),
+  ("grey", 0x808080),
+  ("honeydew", 0xf0fff0),
+  ("hotpink", 0xff69b4),
+  ("indianred", 0xcd5c5c),
+  ("indigo", 0x4b0082),
+  ("ivory", 0xfffff0),
+  ("khaki", 0xf0e68c),
+  ("lavender", 0xe6e6fa),
+  ("lavenderblush", 0xfff0f5),
+  ("lawngreen", 0x7cfc00),
+  ("lemonchiffon", 0xfffacd),
+  ("lightblue", 0xadd8e6),
+  ("lightcoral", 0xf08080),
+  ("lightcyan", 0xe0ffff),
+  ("lightgoldenrodyellow", 0xfafad2),
+  ("lightgray", 0xd3d3d3),
+  ("lightgreen", 0x90ee90),
+  ("lightgrey", 0xd3d3d3),
+  ("lightpink", 0xffb6c1),
+  ("lightsalmon", 0xffa07a),
+  ("lightseagreen", 0x20b2aa),
+  ("lightskyblue", 0x87cefa),
+  ("lightslategray", 0x778899),
+  ("lightslategrey", 0x778899),
+  ("lightsteelblue", 0xb0c4de),
+  ("lightyellow", 0xffffe0),
+  ("lime", 0x00ff00),
+  ("limegreen", 0x32cd32),
+  ("linen", 0xfaf0e6),
+  ("magenta", 0xff00ff),
+  ("maroon", 0x800000),
+  ("mediumaquamarine", 0x66cdaa),
+  ("mediumblue", 0x0000cd),
+  ("mediumorchid", 0xba55d3),
+  ("mediumpurple", 0x9370db),
+  ("mediumseagreen", 0x3cb371),
+  ("mediumslateblue", 0x7b68ee),
+  ("mediumspringgreen", 0x00fa9a),
+  ("mediumturquoise", 0x48d1cc),
+  ("mediumvioletred", 0xc71585),
+  ("midnightblue", 0x191970),
+  ("mintcream", 0xf5fffa),
+  ("mistyrose", 0xffe4e1),
+  ("moccasin", 0xffe4b5),
+  ("navajowhite", 0xffdead),
+  ("navy", 0x000080),
+  ("oldlace", 0xfdf5e6),
+  ("olive", 0x808000),
+  ("olivedrab", 0x6b8e23),
+  ("orange", 0xffa500),
+  ("orangered", 0xff4500),
+  ("orchid", 0xda70d6),
+  ("palegoldenrod", 0xeee8aa),
+  ("palegreen", 0x98fb98),
+  ("paleturquoise", 0xafeeee),
+  ("palevioletred", 0xdb7093),
+  ("papayawhip", 0xffefd5),
+  ("peachpuff", 0xffdab9),
+  ("peru", 0xcd853f),
+  ("pink", 0xffc0cb),
+  ("plum", 0xdda0dd),
+  ("powderblue", 0xb0e0e6),
+  ("purple", 0x800080),
+  ("rebeccapurple", 0x663399),
+  ("red", 0xff0000),
+  ("rosybrown", 0xbc8f8f),
+  ("royalblue", 0x4169e1),
+  ("saddlebrown", 0x8b4513),
+  ("salmon", 0xfa8072),
+  ("sandybrown", 0xf4a460),
+  ("seagreen", 0x2e8b57),
+  ("seashell", 0xfff5ee),
+  ("sienna", 0xa0522d),
+  ("silver", 0xc0c0c0),
+  ("skyblue", 0x87ceeb),
+  ("slateblue", 0x6a5acd),
+  ("slategray", 0x708090),
+  ("slategrey", 0x708090),
+  ("snow", 0xfffafa),
+  ("springgreen", 0x00ff7f),
+  ("steelblue", 0x4682b4),
+  ("tan", 0xd2b48c),
+  ("teal", 0x008080),
+  ("thistle", 0xd8bfd8),
+  ("tomato", 0xff6347),
+  ("turquoise", 0x40e0d0),
+  ("violet", 0xee82ee),
+  ("wheat", 0xf5deb3),
+  ("white", 0xffffff),
+  ("whitesmoke", 0xf5f5f5),
+  ("yellow", 0xffff00),
+  ("yellowgreen", 0x9acd32),
+];
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn named_colors_are_sorted() {
+    assert!(NAMED_COLORS.windows(2).all(|w| w[0].0 < w[1].0));
+  }
+
+  #[test]
+  fn test_parse_color() {
+    let rgba = |r, g, b, a| Some(Rgba { r, g, b, a });
+    assert_eq!(parse_color("red"), rgba(255, 0, 0, 1.0));
+    assert_eq!(parse_color(" RebeccaPurple "), rgba(102, 51, 153, 1.0));
+    assert_eq!(parse_color("#0f08"), rgba(0, 255, 0, 136.0 / 255.0));
+    assert_eq!(parse_color("#123456"), rgba(0x12, 0x34, 0x56, 1.0));
+    assert_eq!(parse_color("rgb(1, 2, 3)"), rgba(1, 2, 3, 1.0));
+    assert_eq!(parse_color("rgba(1, 2, 3, 0.5)"), rgba(1, 2, 3, 0.5));
+    assert_eq!(parse_color("rgb(100% 0% 0% / 50%)"), rgba(255, 0, 0, 0.5));
+    assert_eq!(parse_color("hsl(120, 100%, 50%)"), rgba(0, 255, 0, 1.0));
+    assert_eq!(parse_color("hsl(0.5turn 100% 25%)"), rgba(0, 128, 128, 1.0));
+    assert_eq!(parse_color("transparent"), rgba(0, 0, 0, 0.0));
+    assert_eq!(parse_color("#12345"), None);
+    assert_eq!(parse_color("rgb(1, 2)"), None);
+    assert_eq!(parse_color("notacolor"), None);
+  }
+
+  #[test]
+  fn test_serialize_color() {
+    assert_eq!(parse_color("red").unwrap().serialize(), "#ff0000");
+    assert_eq!(
+      parse_color("rgba(1, 2, 3, 0.5)").unwrap().serialize(),
+      "rgba(1, 2, 3, 0.5)"
+    );
+    assert_eq!(
+      parse_color("transparent").unwrap().serialize(),
+      "rgba(0, 0, 0, 0)"
+    );
+  }
+
+  #[test]
+  fn test_parse_font() {
+    assert_eq!(parse_font("10px sans-serif"), Some(Font::default()));
+    let font = parse_font("italic bold 12pt/1.5 \"Open Sans\", Serif").unwrap();
+    assert_eq!(font.style, FontStyle::Italic);
+    assert_eq!(font.weight, 700);
+    assert_eq!(font.size, 16.0);
+    assert_eq!(font.families, vec!["Open Sans", "serif"]);
+    assert_eq!(font.serialize(), "italic bold 16px \"Open Sans\", serif");
+    let font = parse_font("300 2em Inter").unwrap();
+    assert_eq!(font.weight, 300);
+    assert_eq!(font.size, 20.0);
+    assert_eq!(font.serialize(), "300 20px Inter");
+    assert_eq!(parse_font("bold"), None);
+    assert_eq!(parse_font("12px"), None);
+    assert_eq!(parse_font("12 Inter"), None);
+  }
+}
@@ -0,0 +1,232 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+use std::rc::Rc;
+
+use deno_core::GarbageCollected;
+use deno_core::OpState;
+use deno_core::op2;
+use tiny_skia::PathBuilder;
+use ttf_parser::Face;
+use ttf_parser::GlyphId;
+
+use crate::ImageError;
+use crate::css;
+
+/// The font data backing a `FontFace`. The data is validated when the
+/// `FontFace` is constructed and parsed again whenever text is laid out,
+/// which is cheap as `ttf_parser` parses lazily.
+pub struct FontFaceData {
+  data: Rc<Vec<u8>>,
+}
+
+// SAFETY: we're sure this can be GCed
+unsafe impl GarbageCollected for FontFaceData {
+  fn trace(&self, _visitor: &mut deno_core::v8::cppgc::Visitor) {}
+
+  fn get_name(&self) -> &'static std::ffi::CStr {
+    c"FontFaceData"
+  }
+}
+
+struct RegisteredFace {
+  id: u32,
+  family: String,
+  italic: bool,
+  weight: u16,
+  data: Rc<Vec<u8>>,
+}
+
+/// The font faces added to the `fonts` set of this worker. Only these
+/// faces are used to render text; system fonts are never loaded.
+#[derive(Default)]
+pub struct FontRegistry {
+  faces: Vec<RegisteredFace>,
+  next_id: u32,
+}
+
+/// Text laid out on a single line, in CSS pixels. The origin is the
+/// start of the alphabetic baseline and the y axis points down.
+pub struct TextLayout {
+  pub path: Option<tiny_skia::Path>,
+  pub width: f32,
+  /// Distance from the baseline to the top of the em box.
+  pub ascent: f32,
+  /// Distance from the baseline to the bottom of the em box.
+  pub descent: f32,
+}
+
+struct GlyphOutline<'a> {
+  builder: &'a mut PathBuilder,
+  scale: f32,
+  x: f32,
+}
+
+impl ttf_parser::OutlineBuilder for GlyphOutline<'_> {
+  fn move_to(&mut self, x: f32, y: f32) {
+    self
+      .builder
+      .move_to(self.x + x * self.scale, -y * self.scale);
+  }
+
+  fn line_to(&mut self, x: f32, y: f32) {
+    self
+      .builder
+      .line_to(self.x + x * self.scale, -y * self.scale);
+  }
+
+  fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+    self.builder.quad_to(
+      self.x + x1 * self.scale,
+      -y1 * self.scale,
+      self.x + x * self.scale,
+      -y * self.scale,
+    );
+  }
+
+  fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+    self.builder.cubic_to(
+      self.x + x1 * self.scale,
+      -y1 * self.scale,
+      self.x + x2 * self.scale,
+      -y2 * self.scale,
+      self.x + x * self.scale,
+      -y * self.scale,
+    );
+  }
+
+  fn close(&mut self) {
+    self.builder.close();
+  }
+}
+
+fn kerning(face: &Face, left: GlyphId, right: GlyphId) -> i16 {
+  let Some(kern) = face.tables().kern else {
+    return 0;
+  };
+  kern
+    .subtables
+    .into_iter()
+    .filter(|subtable| subtable.horizontal && !subtable.variable)
+    .find_map(|subtable| subtable.glyphs_kerning(left, right))
+    .unwrap_or(0)
+}
+
+impl FontRegistry {
+  /// Finds the face to render `font` with, following a simplified
+  /// version of the CSS font matching algorithm. Falls back to the
+  /// family of the first added face when no family matches, including
+  /// for generic families.
+  fn find(&self, font: &css::Font) -> Option<&RegisteredFace> {
+    let italic = font.style != css::FontStyle::Normal;
+    let family = font
+      .families
+      .iter()
+      .find(|family| {
+        self
+          .faces
+          .iter()
+          .any(|face| face.family.eq_ignore_ascii_case(family))
+      })
+      .map(String::as_str)
+      .or_else(|| self.faces.first().map(|face| face.family.as_str()))?;
+
+    self
+      .faces
+      .iter()
+      .filter(|face| face.family.eq_ignore_ascii_case(family))
+      .min_by_key(|face| {
+        let distance = (face.weight as i32 - font.weight as i32).abs();
+        // Prefer lighter faces for normal weights and heavier ones for
+        // bold weights when the distance is the same.
+        let lighter = face.weight < font.weight;
+        (
+          face.italic != italic,
+          distance,
+          lighter == (font.weight > 500),
+        )
+      })
+  }
+
+  pub fn layout(&self, font: &css::Font, text: &str) -> TextLayout {
+    let empty = TextLayout {
+      path: None,
+      width: 0.0,
+      ascent: font.size * 0.8,
+      descent: font.size * 0.2,
+    };
+    let Some(face) = self.find(font) else {
+      return empty;
+    };
+    let Ok(face) = Face::parse(&face.data, 0) else {
+      return empty;
+    };
+
+    let scale = font.size / face.units_per_em() as f32;
+    let mut builder = PathBuilder::new();
+    let mut x = 0.0;
+    let mut previous = None;
+    for c in text.chars() {
+      let c = if c.is_ascii_whitespace() { ' ' } else { c };
+      let glyph = face.glyph_index(c).unwrap_or(GlyphId(0));
+      if let Some(previous) = previous {
+        x += kerning(&face, previous, glyph) as f32 * scale;
+      }
+      face.outline_glyph(
+        glyph,
+        &mut GlyphOutline {
+          builder: &mut builder,
+          scale,
+          x,
+        },
+      );
+      x += face.glyph_hor_advance(glyph).unwrap_or(0) as f32 * scale;
+      previous = Some(glyph);
+    }
+
+    TextLayout {
+      path: builder.finish(),
+      width: x,
+      ascent: face.ascender() as f32 * scale,
+      descent: -face.descender() as f32 * scale,
+    }
+  }
+}
+
+#[op2]
+#[cppgc]
+pub fn op_image_font_face_parse(
+  #[buffer(copy)] data: Vec<u8>,
+) -> Result<FontFaceData, ImageError> {
+  Face::parse(&data, 0).map_err(ImageError::InvalidFontData)?;
+  Ok(FontFaceData {
+    data: Rc::new(data),
+  })
+}
+
+#[op2(fast)]
+#[smi]
+pub fn op_image_fonts_add(
+  state: &mut OpState,
+  #[cppgc] face: &FontFaceData,
+  #[string] family: String,
+  italic: bool,
+  #[smi] weight: u16,
+) -> u32 {
+  let registry = state.borrow_mut::<FontRegistry>();
+  registry.next_id += 1;
+  let id = registry.next_id;
+  registry.faces.push(RegisteredFace {
+    id,
+    family,
+    italic,
+    weight,
+    data: face.data.clone(),
+  });
+  id
+}
+
+#[op2(fast)]
+pub fn op_image_fonts_delete(state: &mut OpState, #[smi] id: u32) {
+  let registry = state.borrow_mut::<FontRegistry>();
+  registry.faces.retain(|face| face.id != id);
+}
@@ -1,7 +1,12 @@
 // Copyright 2018-2026 the Deno authors. MIT license.
 
 mod bitmap;
+mod canvas;
+mod context2d;
+mod css;
+mod font;
 mod image_ops;
+mod path;
 pub use image;
 use image::ColorType;
 
@@ -32,6 +37,58 @@ pub enum ImageError {
   #[class(generic)]
   #[error(transparent)]
   Image(#[from] image::ImageError),
+  #[class(type)]
+  #[error("Illegal constructor")]
+  InvalidConstructor,
+  #[class(type)]
+  #[error(
+    "The provided value '{0}' is not a valid enum value of type OffscreenRenderingContextId"
+  )]
+  InvalidContextId(String),
+  #[class("DOMExceptionInvalidStateError")]
+  #[error("The canvas has no rendering context")]
+  NoRenderingContext,
+  #[class("DOMExceptionEncodingError")]
+  #[error("Failed to encode the canvas")]
+  Encoding,
+  #[class("DOMExceptionIndexSizeError")]
+  #[error("The radius provided ({0}) is negative")]
+  NegativeRadius(f64),
+  #[class(range)]
+  #[error("{0}")]
+  InvalidRadii(&'static str),
+  #[class(type)]
+  #[error("Failed to convert value to 'DOMMatrix2DInit'")]
+  InvalidMatrix,
+  #[class(type)]
+  #[error("Failed to convert value to 'Path2D'")]
+  InvalidPath,
+  #[class(type)]
+  #[error(
+    "The provided value '{0}' is not a valid enum value of type CanvasFillRule"
+  )]
+  InvalidFillRule(String),
+  #[class("DOMExceptionIndexSizeError")]
+  #[error("The provided offset ({0}) is outside the range [0, 1]")]
+  InvalidColorStopOffset(f64),
+  #[class("DOMExceptionSyntaxError")]
+  #[error("The provided value '{0}' could not be parsed as a color")]
+  InvalidColor(String),
+  #[class(type)]
+  #[error("Failed to convert value to '(ImageBitmap or OffscreenCanvas)'")]
+  InvalidImageSource,
+  #[class("DOMExceptionInvalidStateError")]
+  #[error("The image source has been detached")]
+  DetachedImageBitmap,
+  #[class("DOMExceptionInvalidStateError")]
+  #[error("The image source has a width or height of 0")]
+  EmptyImageSource,
+  #[class(type)]
+  #[error("Valid arities are: {0}, but {1} arguments provided")]
+  InvalidArity(&'static str, usize),
+  #[class("DOMExceptionSyntaxError")]
+  #[error("The font data could not be parsed: {0}")]
+  InvalidFontData(ttf_parser::FaceParsingError),
 }
 
 impl ImageError {
@@ -44,7 +101,23 @@ impl ImageError {
 deno_core::extension!(
   deno_image,
   deps = [deno_webidl, deno_web, deno_webgpu],
-  ops = [bitmap::op_create_image_bitmap],
-  objects = [bitmap::ImageBitmap],
-  lazy_loaded_esm = ["01_image.js"],
+  ops = [
+    bitmap::op_create_image_bitmap,
+    canvas::op_image_encode_canvas,
+    font::op_image_font_face_parse,
+    font::op_image_fonts_add,
+    font::op_image_fonts_delete,
+  ],
+  objects = [
+    bitmap::ImageBitmap,
+    canvas::OffscreenCanvas,
+    context2d::CanvasGradient,
+    context2d::OffscreenCanvasRenderingContext2D,
+    context2d::TextMetrics,
+    path::Path2D,
+  ],
+  lazy_loaded_esm = ["01_image.js", "02_canvas.js"],
+  state = |state| {
+    state.put(font::FontRegistry::default());
+  },
 );
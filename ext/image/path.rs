@@ -0,0 +1,1102 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+use std::cell::RefCell;
+use std::f64::consts::FRAC_PI_2;
+use std::f64::consts::PI;
+use std::f64::consts::TAU;
+
+use deno_core::GarbageCollected;
+use deno_core::cppgc;
+use deno_core::op2;
+use deno_core::v8;
+use deno_core::webidl::UnrestrictedDouble;
+use deno_core::webidl::WebIdlInterfaceConverter;
+use tiny_skia::PathBuilder;
+use tiny_skia::Point;
+use tiny_skia::Transform;
+
+use crate::ImageError;
+
+#[derive(Debug, Clone, Copy)]
+enum Segment {
+  MoveTo(Point),
+  LineTo(Point),
+  QuadTo(Point, Point),
+  CubicTo(Point, Point, Point),
+  Close,
+}
+
+/// The list of subpaths shared by `Path2D` and the current default path
+/// of a 2D context.
+///
+/// Points are transformed by the given transform when they are added,
+/// as the context's current path is affected by the transformation
+/// matrix at the time each segment is added. `Path2D` always uses the
+/// identity transform and is transformed when it is drawn.
+#[derive(Debug, Clone, Default)]
+pub struct PathData {
+  segments: Vec<Segment>,
+  /// The first point of the last subpath.
+  start: Option<Point>,
+  /// The last point of the last subpath.
+  current: Option<Point>,
+}
+
+fn finite(values: &[f64]) -> bool {
+  values.iter().all(|v| v.is_finite())
+}
+
+fn map(ts: Transform, x: f64, y: f64) -> Point {
+  let mut point = Point::from_xy(x as f32, y as f32);
+  ts.map_point(&mut point);
+  point
+}
+
+impl PathData {
+  pub fn clear(&mut self) {
+    *self = PathData::default();
+  }
+
+  fn push_move(&mut self, point: Point) {
+    if let Some(Segment::MoveTo(last)) = self.segments.last_mut() {
+      *last = point;
+    } else {
+      self.segments.push(Segment::MoveTo(point));
+    }
+    self.start = Some(point);
+    self.current = Some(point);
+  }
+
+  /// Ensures there is a subpath, starting it at `point` otherwise.
+  fn ensure_subpath(&mut self, point: Point) {
+    if self.current.is_none() {
+      self.push_move(point);
+    }
+  }
+
+  fn push_line(&mut self, point: Point) {
+    if self.current.is_none() {
+      self.push_move(point);
+    } else {
+      self.segments.push(Segment::LineTo(point));
+      self.current = Some(point);
+    }
+  }
+
+  pub fn move_to(&mut self, ts: Transform, x: f64, y: f64) {
+    if finite(&[x, y]) {
+      self.push_move(map(ts, x, y));
+    }
+  }
+
+  pub fn line_to(&mut self, ts: Transform, x: f64, y: f64) {
+    if finite(&[x, y]) {
+      self.push_line(map(ts, x, y));
+    }
+  }
+
+  pub fn quad_to(&mut self, ts: Transform, cpx: f64, cpy: f64, x: f64, y: f64) {
+    if !finite(&[cpx, cpy, x, y]) {
+      return;
+    }
+    let control = map(ts, cpx, cpy);
+    let point = map(ts, x, y);
+    self.ensure_subpath(control);
+    self.segments.push(Segment::QuadTo(control, point));
+    self.current = Some(point);
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  pub fn bezier_to(
+    &mut self,
+    ts: Transform,
+    cp1x: f64,
+    cp1y: f64,
+    cp2x: f64,
+    cp2y: f64,
+    x: f64,
+    y: f64,
+  ) {
+    if !finite(&[cp1x, cp1y, cp2x, cp2y, x, y]) {
+      return;
+    }
+    let control1 = map(ts, cp1x, cp1y);
+    let control2 = map(ts, cp2x, cp2y);
+    let point = map(ts, x, y);
+    self.ensure_subpath(control1);
+    self
+      .segments
+      .push(Segment::CubicTo(control1, control2, point));
+    self.current = Some(point);
+  }
+
+  pub fn close(&mut self) {
+    if self.current.is_some()
+      && !matches!(self.segments.last(), Some(Segment::Close) | None)
+    {
+      self.segments.push(Segment::Close);
+      self.current = self.start;
+    }
+  }
+
+  /// Appends an elliptical arc from `start` sweeping by `sweep` radians,
+  /// connecting it to the current subpath with a straight line.
+  #[allow(clippy::too_many_arguments)]
+  fn push_arc(
+    &mut self,
+    ts: Transform,
+    cx: f64,
+    cy: f64,
+    rx: f64,
+    ry: f64,
+    rotation: f64,
+    start: f64,
+    sweep: f64,
+  ) {
+    let (sin_r, cos_r) = rotation.sin_cos();
+    let point_at = |angle: f64| {
+      let (sin, cos) = angle.sin_cos();
+      (
+        cx + rx * cos * cos_r - ry * sin * sin_r,
+        cy + rx * cos * sin_r + ry * sin * cos_r,
+      )
+    };
+    let tangent_at = |angle: f64| {
+      let (sin, cos) = angle.sin_cos();
+      (
+        -rx * sin * cos_r - ry * cos * sin_r,
+        -rx * sin * sin_r + ry * cos * cos_r,
+      )
+    };
+
+    let (x, y) = point_at(start);
+    let first = map(ts, x, y);
+    match self.current {
+      Some(current)
+        if (current.x - first.x).abs() < 1e-4
+          && (current.y - first.y).abs() < 1e-4 => {}
+      _ => self.push_line(first),
+    }
+
+    if sweep == 0.0 || rx == 0.0 || ry == 0.0 {
+      return;
+    }
+
+    // Each segment spans at most a quarter turn, which keeps the cubic
+    // approximation error well below a pixel.
+    let count = (sweep.abs() / FRAC_PI_2).ceil().max(1.0);
+    let delta = sweep / count;
+    let k = 4.0 / 3.0 * (delta / 4.0).tan();
+    let mut angle = start;
+    for _ in 0..count as usize {
+      let next = angle + delta;
+      let (x0, y0) = point_at(angle);
+      let (dx0, dy0) = tangent_at(angle);
+      let (x1, y1) = point_at(next);
+      let (dx1, dy1) = tangent_at(next);
+      let control1 = map(ts, x0 + k * dx0, y0 + k * dy0);
+      let control2 = map(ts, x1 - k * dx1, y1 - k * dy1);
+      let point = map(ts, x1, y1);
+      self
+        .segments
+        .push(Segment::CubicTo(control1, control2, point));
+      self.current = Some(point);
+      angle = next;
+    }
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  pub fn ellipse(
+    &mut self,
+    ts: Transform,
+    x: f64,
+    y: f64,
+    rx: f64,
+    ry: f64,
+    rotation: f64,
+    start: f64,
+    end: f64,
+    counterclockwise: bool,
+  ) -> Result<(), ImageError> {
+    if !finite(&[x, y, rx, ry, rotation, start, end]) {
+      return Ok(());
+    }
+    if rx < 0.0 {
+      return Err(ImageError::NegativeRadius(rx));
+    }
+    if ry < 0.0 {
+      return Err(ImageError::NegativeRadius(ry));
+    }
+
+    let sweep = if !counterclockwise && end - start >= TAU {
+      TAU
+    } else if counterclockwise && start - end >= TAU {
+      -TAU
+    } else if counterclockwise {
+      -(start - end).rem_euclid(TAU)
+    } else {
+      (end - start).rem_euclid(TAU)
+    };
+    self.push_arc(ts, x, y, rx, ry, rotation, start, sweep);
+    Ok(())
+  }
+
+  /// Appends an arc tangent to the lines from the current point to
+  /// `(x1, y1)` and from there to `(x2, y2)`.
+  pub fn arc_to(
+    &mut self,
+    ts: Transform,
+    x1: f64,
+    y1: f64,
+    x2: f64,
+    y2: f64,
+    radius: f64,
+  ) -> Result<(), ImageError> {
+    if !finite(&[x1, y1, x2, y2, radius]) {
+      return Ok(());
+    }
+    self.ensure_subpath(map(ts, x1, y1));
+    if radius < 0.0 {
+      return Err(ImageError::NegativeRadius(radius));
+    }
+
+    // The current point is in device space, while the arc is defined in
+    // the user space of `ts`.
+    let Some(inverse) = ts.invert() else {
+      return Ok(());
+    };
+    let mut current = self.current.unwrap();
+    inverse.map_point(&mut current);
+    let (x0, y0) = (current.x as f64, current.y as f64);
+
+    let (v1x, v1y) = (x0 - x1, y0 - y1);
+    let (v2x, v2y) = (x2 - x1, y2 - y1);
+    let len1 = v1x.hypot(v1y);
+    let len2 = v2x.hypot(v2y);
+    let cross = v1x * v2y - v1y * v2x;
+    if radius == 0.0 || len1 == 0.0 || len2 == 0.0 || cross.abs() < 1e-9 {
+      self.line_to(ts, x1, y1);
+      return Ok(());
+    }
+
+    let (u1x, u1y) = (v1x / len1, v1y / len1);
+    let (u2x, u2y) = (v2x / len2, v2y / len2);
+    let angle = (u1x * u2x + u1y * u2y).clamp(-1.0, 1.0).acos();
+    let tangent = radius / (angle / 2.0).tan();
+    let (t1x, t1y) = (x1 + u1x * tangent, y1 + u1y * tangent);
+    let (t2x, t2y) = (x1 + u2x * tangent, y1 + u2y * tangent);
+    let (bx, by) = (u1x + u2x, u1y + u2y);
+    let bisector = bx.hypot(by);
+    let center = radius / (angle / 2.0).sin();
+    let (cx, cy) = (x1 + bx / bisector * center, y1 + by / bisector * center);
+
+    let start = (t1y - cy).atan2(t1x - cx);
+    let end = (t2y - cy).atan2(t2x - cx);
+    let mut sweep = end - start;
+    if sweep > PI {
+      sweep -= TAU;
+    } else if sweep < -PI {
+      sweep += TAU;
+    }
+    self.push_arc(ts, cx, cy, radius, radius, 0.0, start, sweep);
+    Ok(())
+  }
+
+  pub fn rect(&mut self, ts: Transform, x: f64, y: f64, w: f64, h: f64) {
+    if !finite(&[x, y, w, h]) {
+      return;
+    }
+    self.push_move(map(ts, x, y));
+    self.push_line(map(ts, x + w, y));
+    self.push_line(map(ts, x + w, y + h));
+    self.push_line(map(ts, x, y + h));
+    self.close();
+    self.push_move(map(ts, x, y));
+  }
+
+  /// Appends a rounded rectangle. `radii` are the normalized
+  /// upper-left, upper-right, lower-right and lower-left radii.
+  pub fn round_rect(
+    &mut self,
+    ts: Transform,
+    mut x: f64,
+    mut y: f64,
+    mut w: f64,
+    mut h: f64,
+    radii: &[(f64, f64)],
+  ) -> Result<(), ImageError> {
+    if !finite(&[x, y, w, h]) {
+      return Ok(());
+    }
+    if radii
+      .iter()
+      .any(|(rx, ry)| !rx.is_finite() || !ry.is_finite())
+    {
+      return Ok(());
+    }
+    if radii.is_empty() || radii.len() > 4 {
+      return Err(ImageError::InvalidRadii(
+        "The number of radii must be between 1 and 4",
+      ));
+    }
+    if radii.iter().any(|(rx, ry)| *rx < 0.0 || *ry < 0.0) {
+      return Err(ImageError::InvalidRadii("The radii must not be negative"));
+    }
+
+    let (mut ul, mut ur, mut lr, mut ll) = match *radii {
+      [r] => (r, r, r, r),
+      [a, b] => (a, b, a, b),
+      [a, b, c] => (a, b, c, b),
+      [a, b, c, d] => (a, b, c, d),
+      _ => unreachable!(),
+    };
+
+    // Normalize the rectangle so that corners keep their meaning.
+    if w < 0.0 {
+      x += w;
+      w = -w;
+      std::mem::swap(&mut ul, &mut ur);
+      std::mem::swap(&mut ll, &mut lr);
+    }
+    if h < 0.0 {
+      y += h;
+      h = -h;
+      std::mem::swap(&mut ul, &mut ll);
+      std::mem::swap(&mut ur, &mut lr);
+    }
+
+    // Scale down the radii if adjacent corners overlap.
+    let scale = [
+      w / (ul.0 + ur.0),
+      w / (ll.0 + lr.0),
+      h / (ul.1 + ll.1),
+      h / (ur.1 + lr.1),
+    ]
+    .into_iter()
+    .filter(|s| s.is_finite())
+    .fold(1.0f64, f64::min);
+    for corner in [&mut ul, &mut ur, &mut lr, &mut ll] {
+      corner.0 *= scale;
+      corner.1 *= scale;
+    }
+
+    self.push_move(map(ts, x + ul.0, y));
+    self.push_line(map(ts, x + w - ur.0, y));
+    self.push_arc(
+      ts,
+      x + w - ur.0,
+      y + ur.1,
+      ur.0,
+      ur.1,
+      0.0,
+      -FRAC_PI_2,
+      FRAC_PI_2,
+    );
+    self.push_line(map(ts, x + w, y + h - lr.1));
+    self.push_arc(
+      ts,
+      x + w - lr.0,
+      y + h - lr.1,
+      lr.0,
+      lr.1,
+      0.0,
+      0.0,
+      FRAC_PI_2,
+    );
+    self.push_line(map(ts, x + ll.0, y + h));
+    self.push_arc(
+      ts,
+      x + ll.0,
+      y + h - ll.1,
+      ll.0,
+      ll.1,
+      0.0,
+      FRAC_PI_2,
+      FRAC_PI_2,
+    );
+    self.push_line(map(ts, x, y + ul.1));
+    self.push_arc(ts, x + ul.0, y + ul.1, ul.0, ul.1, 0.0, PI, FRAC_PI_2);
+    self.close();
+    self.push_move(map(ts, x, y));
+    Ok(())
+  }
+
+  /// Appends the subpaths of `other`, transformed by `ts`.
+  pub fn add_path(&mut self, other: &PathData, ts: Transform) {
+    let map = |mut point: Point| {
+      ts.map_point(&mut point);
+      point
+    };
+    for segment in &other.segments {
+      let segment = match *segment {
+        Segment::MoveTo(p) => Segment::MoveTo(map(p)),
+        Segment::LineTo(p) => Segment::LineTo(map(p)),
+        Segment::QuadTo(c, p) => Segment::QuadTo(map(c), map(p)),
+        Segment::CubicTo(c1, c2, p) => {
+          Segment::CubicTo(map(c1), map(c2), map(p))
+        }
+        Segment::Close => Segment::Close,
+      };
+      self.segments.push(segment);
+    }
+    if other.current.is_some() {
+      self.start = other.start.map(map);
+      self.current = other.current.map(map);
+    }
+  }
+
+  /// Converts the path to a `tiny_skia::Path`. Returns `None` for empty
+  /// paths.
+  pub fn to_path(&self) -> Option<tiny_skia::Path> {
+    let mut builder = PathBuilder::new();
+    for segment in &self.segments {
+      match *segment {
+        Segment::MoveTo(p) => builder.move_to(p.x, p.y),
+        Segment::LineTo(p) => builder.line_to(p.x, p.y),
+        Segment::QuadTo(c, p) => builder.quad_to(c.x, c.y, p.x, p.y),
+        Segment::CubicTo(c1, c2, p) => {
+          builder.cubic_to(c1.x, c1.y, c2.x, c2.y, p.x, p.y)
+        }
+        Segment::Close => builder.close(),
+      }
+    }
+    builder.finish()
+  }
+
+  /// Parses SVG path data. Like in SVG, the path is built up to the first
+  /// error.
+  pub fn parse_svg(data: &str) -> PathData {
+    let mut path = PathData::default();
+    let _ = SvgPathParser {
+      input: data.as_bytes(),
+      pos: 0,
+    }
+    .parse(&mut path);
+    path
+  }
+}
+
+struct SvgPathParser<'a> {
+  input: &'a [u8],
+  pos: usize,
+}
+
+impl SvgPathParser<'_> {
+  fn skip_separators(&mut self) {
+    while let Some(c) = self.input.get(self.pos) {
+      if c.is_ascii_whitespace() || *c == b',' {
+        self.pos += 1;
+      } else {
+        break;
+      }
+    }
+  }
+
+  fn at_number(&mut self) -> bool {
+    self.skip_separators();
+    matches!(
+      self.input.get(self.pos),
+      Some(b'0'..=b'9' | b'-' | b'+' | b'.')
+    )
+  }
+
+  fn number(&mut self) -> Option<f64> {
+    self.skip_separators();
+    let start = self.pos;
+    let digits = |parser: &mut Self| {
+      let start = parser.pos;
+      while parser.input.get(parser.pos).is_some_and(u8::is_ascii_digit) {
+        parser.pos += 1;
+      }
+      parser.pos > start
+    };
+    if matches!(self.input.get(self.pos), Some(b'-' | b'+')) {
+      self.pos += 1;
+    }
+    let mut has_digits = digits(self);
+    if self.input.get(self.pos) == Some(&b'.') {
+      self.pos += 1;
+      has_digits |= digits(self);
+    }
+    if !has_digits {
+      return None;
+    }
+    if matches!(self.input.get(self.pos), Some(b'e' | b'E')) {
+      let before_exponent = self.pos;
+      self.pos += 1;
+      if matches!(self.input.get(self.pos), Some(b'-' | b'+')) {
+        self.pos += 1;
+      }
+      if !digits(self) {
+        self.pos = before_exponent;
+      }
+    }
+    std::str::from_utf8(&self.input[start..self.pos])
+      .ok()?
+      .parse()
+      .ok()
+  }
+
+  fn flag(&mut self) -> Option<bool> {
+    self.skip_separators();
+    let flag = match self.input.get(self.pos)? {
+      b'0' => false,
+      b'1' => true,
+      _ => return None,
+    };
+    self.pos += 1;
+    Some(flag)
+  }
+
+  fn parse(&mut self, path: &mut PathData) -> Option<()> {
+    let ts = Transform::identity();
+    let mut command = None;
+    // The second control point of the previous cubic or quadratic
+    // segment, for the smooth variants.
+    let mut last_cubic: Option<(f64, f64)> = None;
+    let mut last_quad: Option<(f64, f64)> = None;
+
+    loop {
+      self.skip_separators();
+      let Some(&c) = self.input.get(self.pos) else {
+        return Some(());
+      };
+      if c.is_ascii_alphabetic() {
+        self.pos += 1;
+        command = Some(c);
+      } else if command.is_none() || !self.at_number() {
+        return None;
+      }
+      let c = command?;
+      let (x, y) = path
+        .current
+        .map(|p| (p.x as f64, p.y as f64))
+        .unwrap_or((0.0, 0.0));
+      let relative = c.is_ascii_lowercase();
+      let (ox, oy) = if relative { (x, y) } else { (0.0, 0.0) };
+
+      let mut next_cubic = None;
+      let mut next_quad = None;
+      match c.to_ascii_uppercase() {
+        b'M' => {
+          let (nx, ny) = (self.number()? + ox, self.number()? + oy);
+          path.move_to(ts, nx, ny);
+          // Subsequent coordinate pairs are implicit line commands.
+          command = Some(if relative { b'l' } else { b'L' });
+        }
+        b'L' => {
+          let (nx, ny) = (self.number()? + ox, self.number()? + oy);
+          path.line_to(ts, nx, ny);
+        }
+        b'H' => {
+          let nx = self.number()? + ox;
+          path.line_to(ts, nx, y);
+        }
+        b'V' => {
+          let ny = self.number()? + oy;
+          path.line_to(ts, x, ny);
+        }
+        b'C' => {
+          let (x1, y1) = (self.number()? + ox, self.number()? + oy);
+          let (x2, y2) = (self.number()? + ox, self.number()? + oy);
+          let (nx, ny) = (self.number()? + ox, self.number()? + oy);
+          path.bezier_to(ts, x1, y1, x2, y2, nx, ny);
+          next_cubic = Some((x2, y2));
+        }
+        b'S' => {
+          let (x1, y1) = last_cubic
+            .map(|(cx, cy)| (2.0 * x - cx, 2.0 * y - cy))
+            .unwrap_or((x, y));
+          let (x2, y2) = (self.number()? + ox, self.number()? + oy);
+          let (nx, ny) = (self.number()? + ox, self.number()? + oy);
+          path.bezier_to(ts, x1, y1, x2, y2, nx, ny);
+          next_cubic = Some((x2, y2));
+        }
+        b'Q' => {
+          let (x1, y1) = (self.number()? + ox, self.number()? + oy);
+          let (nx, ny) = (self.number()? + ox, self.number()? + oy);
+          path.quad_to(ts, x1, y1, nx, ny);
+          next_quad = Some((x1, y1));
+        }
+        b'T' => {
+          let (x1, y1) = last_quad
+            .map(|(cx, cy)| (2.0 * x - cx, 2.0 * y - cy))
+            .unwrap_or((x, y));
+          let (nx, ny) = (self.number()? + ox, self.number()? + oy);
+          path.quad_to(ts, x1, y1, nx, ny);
+          next_quad = Some((x1, y1));
+        }
+        b'A' => {
+          let (rx, ry) = (self.number()?, self.number()?);
+          let rotation = self.number()?;
+          let large_arc = self.flag()?;
+          let sweep = self.flag()?;
+          let (nx, ny) = (self.number()? + ox, self.number()? + oy);
+          svg_arc(path, (x, y), rx, ry, rotation, large_arc, sweep, (nx, ny));
+        }
+        b'Z' => {
+          path.close();
+          command = None;
+        }
+        _ => return None,
+      }
+      last_cubic = next_cubic;
+      last_quad = next_quad;
+    }
+  }
+}
+
+/// Converts an SVG endpoint arc to a center parameterized one.
+/// https://www.w3.org/TR/SVG/implnote.html#ArcConversionEndpointToCenter
+#[allow(clippy::too_many_arguments)]
+fn svg_arc(
+  path: &mut PathData,
+  (x1, y1): (f64, f64),
+  rx: f64,
+  ry: f64,
+  rotation: f64,
+  large_arc: bool,
+  sweep: bool,
+  (x2, y2): (f64, f64),
+) {
+  let ts = Transform::identity();
+  if x1 == x2 && y1 == y2 {
+    return;
+  }
+  let (mut rx, mut ry) = (rx.abs(), ry.abs());
+  if rx == 0.0 || ry == 0.0 {
+    path.line_to(ts, x2, y2);
+    return;
+  }
+
+  let phi = rotation.to_radians();
+  let (sin_phi, cos_phi) = phi.sin_cos();
+  let (dx, dy) = ((x1 - x2) / 2.0, (y1 - y2) / 2.0);
+  let x1p = cos_phi * dx + sin_phi * dy;
+  let y1p = -sin_phi * dx + cos_phi * dy;
+
+  let lambda = (x1p * x1p) / (rx * rx) + (y1p * y1p) / (ry * ry);
+  if lambda > 1.0 {
+    rx *= lambda.sqrt();
+    ry *= lambda.sqrt();
+  }
+
+  let numerator = rx * rx * ry * ry - rx * rx * y1p * y1p - ry * ry * x1p * x1p;
+  let denominator = rx * rx * y1p * y1p + ry * ry * x1p * x1p;
+  let mut coef = (numerator / denominator).max(0.0).sqrt();
+  if large_arc == sweep {
+    coef = -coef;
+  }
+  let cxp = coef * rx * y1p / ry;
+  let cyp = -coef * ry * x1p / rx;
+  let cx = cos_phi * cxp - sin_phi * cyp + (x1 + x2) / 2.0;
+  let cy = sin_phi * cxp + cos_phi * cyp + (y1 + y2) / 2.0;
+
+  let angle = |ux: f64, uy: f64, vx: f64, vy: f64| {
+    (ux * vy - uy * vx).atan2(ux * vx + uy * vy)
+  };
+  let (ux, uy) = ((x1p - cxp) / rx, (y1p - cyp) / ry);
+  let (vx, vy) = ((-x1p - cxp) / rx, (-y1p - cyp) / ry);
+  let start = angle(1.0, 0.0, ux, uy);
+  let mut delta = angle(ux, uy, vx, vy);
+  if !sweep && delta > 0.0 {
+    delta -= TAU;
+  } else if sweep && delta < 0.0 {
+    delta += TAU;
+  }
+  path.push_arc(ts, cx, cy, rx, ry, phi, start, delta);
+}
+
+fn get_number(
+  scope: &mut v8::PinScope<'_, '_>,
+  object: v8::Local<v8::Object>,
+  key: &str,
+) -> Option<f64> {
+  let key = v8::String::new(scope, key).unwrap();
+  let value = object.get(scope, key.into())?;
+  if value.is_undefined() {
+    None
+  } else {
+    value.number_value(scope)
+  }
+}
+
+/// Converts a `DOMMatrix2DInit` dictionary to a transform. Both the
+/// `a`..`f` and the `m11`..`m42` members are accepted.
+pub fn transform_from_v8(
+  scope: &mut v8::PinScope<'_, '_>,
+  value: v8::Local<v8::Value>,
+) -> Result<Transform, ImageError> {
+  if value.is_null_or_undefined() {
+    return Ok(Transform::identity());
+  }
+  let object = v8::Local::<v8::Object>::try_from(value)
+    .map_err(|_| ImageError::InvalidMatrix)?;
+  let mut component = |short: &str, long: &str, default: f64| {
+    get_number(scope, object, short)
+      .or_else(|| get_number(scope, object, long))
+      .unwrap_or(default)
+  };
+  let a = component("a", "m11", 1.0);
+  let b = component("b", "m12", 0.0);
+  let c = component("c", "m21", 0.0);
+  let d = component("d", "m22", 1.0);
+  let e = component("e", "m41", 0.0);
+  let f = component("f", "m42", 0.0);
+  Ok(Transform::from_row(
+    a as f32, b as f32, c as f32, d as f32, e as f32, f as f32,
+  ))
+}
+
+/// Converts the `radii` argument of `roundRect()`, which is a number, a
+/// `DOMPointInit` or a list of them, to `(x, y)` pairs.
+pub fn radii_from_v8(
+  scope: &mut v8::PinScope<'_, '_>,
+  value: v8::Local<v8::Value>,
+) -> Vec<(f64, f64)> {
+  fn radius(
+    scope: &mut v8::PinScope<'_, '_>,
+    value: v8::Local<v8::Value>,
+  ) -> (f64, f64) {
+    if let Ok(object) = v8::Local::<v8::Object>::try_from(value) {
+      let x = get_number(scope, object, "x").unwrap_or(0.0);
+      let y = get_number(scope, object, "y").unwrap_or(0.0);
+      (x, y)
+    } else {
+      let r = value.number_value(scope).unwrap_or(f64::NAN);
+      (r, r)
+    }
+  }
+
+  if value.is_undefined() {
+    return vec![(0.0, 0.0)];
+  }
+  if let Ok(array) = v8::Local::<v8::Array>::try_from(value) {
+    let mut radii = Vec::with_capacity(array.length() as usize);
+    for i in 0..array.length() {
+      let value = array.get_index(scope, i).unwrap();
+      radii.push(radius(scope, value));
+    }
+    return radii;
+  }
+  vec![radius(scope, value)]
+}
+
+pub struct Path2D {
+  pub(crate) path: RefCell<PathData>,
+}
+
+// SAFETY: we're sure this can be GCed
+unsafe impl GarbageCollected for Path2D {
+  fn trace(&self, _visitor: &mut deno_core::v8::cppgc::Visitor) {}
+
+  fn get_name(&self) -> &'static std::ffi::CStr {
+    c"Path2D"
+  }
+}
+
+impl WebIdlInterfaceConverter for Path2D {
+  const NAME: &'static str = "Path2D";
+}
+
+impl Path2D {
+  pub(crate) fn try_unwrap<'a>(
+    scope: &mut v8::PinScope<'_, '_>,
+    value: v8::Local<'a, v8::Value>,
+  ) -> Option<cppgc::UnsafePtr<Path2D>> {
+    cppgc::try_unwrap_cppgc_object::<Path2D>(scope, value)
+  }
+}
+
+#[op2]
+impl Path2D {
+  // Creates an empty path, a copy of another `Path2D` or a path from SVG
+  // path data.
+  #[constructor]
+  #[cppgc]
+  fn new(
+    scope: &mut v8::PinScope<'_, '_>,
+    path: v8::Local<v8::Value>,
+  ) -> Path2D {
+    let path = if path.is_undefined() {
+      PathData::default()
+    } else if let Some(other) = Path2D::try_unwrap(scope, path) {
+      other.path.borrow().clone()
+    } else {
+      PathData::parse_svg(&path.to_rust_string_lossy(scope))
+    };
+    Path2D {
+      path: RefCell::new(path),
+    }
+  }
+
+  #[fast]
+  #[required(1)]
+  #[undefined]
+  fn add_path(
+    &self,
+    scope: &mut v8::PinScope<'_, '_>,
+    path: v8::Local<v8::Value>,
+    transform: v8::Local<v8::Value>,
+  ) -> Result<(), ImageError> {
+    let other =
+      Path2D::try_unwrap(scope, path).ok_or(ImageError::InvalidPath)?;
+    let transform = transform_from_v8(scope, transform)?;
+    if !transform.is_finite() {
+      return Ok(());
+    }
+    let other = other.path.borrow().clone();
+    self.path.borrow_mut().add_path(&other, transform);
+    Ok(())
+  }
+
+  #[fast]
+  #[undefined]
+  fn close_path(&self) {
+    self.path.borrow_mut().close();
+  }
+
+  #[required(2)]
+  #[undefined]
+  fn move_to(
+    &self,
+    #[webidl] x: UnrestrictedDouble,
+    #[webidl] y: UnrestrictedDouble,
+  ) {
+    self
+      .path
+      .borrow_mut()
+      .move_to(Transform::identity(), *x, *y);
+  }
+
+  #[required(2)]
+  #[undefined]
+  fn line_to(
+    &self,
+    #[webidl] x: UnrestrictedDouble,
+    #[webidl] y: UnrestrictedDouble,
+  ) {
+    self
+      .path
+      .borrow_mut()
+      .line_to(Transform::identity(), *x, *y);
+  }
+
+  #[required(4)]
+  #[undefined]
+  fn quadratic_curve_to(
+    &self,
+    #[webidl] cpx: UnrestrictedDouble,
+    #[webidl] cpy: UnrestrictedDouble,
+    #[webidl] x: UnrestrictedDouble,
+    #[webidl] y: UnrestrictedDouble,
+  ) {
+    self
+      .path
+      .borrow_mut()
+      .quad_to(Transform::identity(), *cpx, *cpy, *x, *y);
+  }
+
+  #[required(6)]
+  #[undefined]
+  fn bezier_curve_to(
+    &self,
+    #[webidl] cp1x: UnrestrictedDouble,
+    #[webidl] cp1y: UnrestrictedDouble,
+    #[webidl] cp2x: UnrestrictedDouble,
+    #[webidl] cp2y: UnrestrictedDouble,
+    #[webidl] x: UnrestrictedDouble,
+    #[webidl] y: UnrestrictedDouble,
+  ) {
+    self.path.borrow_mut().bezier_to(
+      Transform::identity(),
+      *cp1x,
+      *cp1y,
+      *cp2x,
+      *cp2y,
+      *x,
+      *y,
+    );
+  }
+
+  #[required(5)]
+  #[undefined]
+  fn arc_to(
+    &self,
+    #[webidl] x1: UnrestrictedDouble,
+    #[webidl] y1: UnrestrictedDouble,
+    #[webidl] x2: UnrestrictedDouble,
+    #[webidl] y2: UnrestrictedDouble,
+    #[webidl] radius: UnrestrictedDouble,
+  ) -> Result<(), ImageError> {
+    self.path.borrow_mut().arc_to(
+      Transform::identity(),
+      *x1,
+      *y1,
+      *x2,
+      *y2,
+      *radius,
+    )
+  }
+
+  #[required(4)]
+  #[undefined]
+  fn rect(
+    &self,
+    #[webidl] x: UnrestrictedDouble,
+    #[webidl] y: UnrestrictedDouble,
+    #[webidl] w: UnrestrictedDouble,
+    #[webidl] h: UnrestrictedDouble,
+  ) {
+    self
+      .path
+      .borrow_mut()
+      .rect(Transform::identity(), *x, *y, *w, *h);
+  }
+
+  #[required(4)]
+  #[undefined]
+  fn round_rect(
+    &self,
+    scope: &mut v8::PinScope<'_, '_>,
+    #[webidl] x: UnrestrictedDouble,
+    #[webidl] y: UnrestrictedDouble,
+    #[webidl] w: UnrestrictedDouble,
+    #[webidl] h: UnrestrictedDouble,
+    radii: v8::Local<v8::Value>,
+  ) -> Result<(), ImageError> {
+    let radii = radii_from_v8(scope, radii);
+    self.path.borrow_mut().round_rect(
+      Transform::identity(),
+      *x,
+      *y,
+      *w,
+      *h,
+      &radii,
+    )
+  }
+
+  #[required(5)]
+  #[undefined]
+  fn arc(
+    &self,
+    #[webidl] x: UnrestrictedDouble,
+    #[webidl] y: UnrestrictedDouble,
+    #[webidl] radius: UnrestrictedDouble,
+    #[webidl] start_angle: UnrestrictedDouble,
+    #[webidl] end_angle: UnrestrictedDouble,
+    #[webidl] counterclockwise: Option<bool>,
+  ) -> Result<(), ImageError> {
+    self.path.borrow_mut().ellipse(
+      Transform::identity(),
+      *x,
+      *y,
+      *radius,
+      *radius,
+      0.0,
+      *start_angle,
+      *end_angle,
+      counterclockwise.unwrap_or(false),
+    )
+  }
+
+  #[required(7)]
+  #[undefined]
+  fn ellipse(
+    &self,
+    #[webidl] x: UnrestrictedDouble,
+    #[webidl] y: UnrestrictedDouble,
+    #[webidl] radius_x: UnrestrictedDouble,
+    #[webidl] radius_y: UnrestrictedDouble,
+    #[webidl] rotation: UnrestrictedDouble,
+    #[webidl] start_angle: UnrestrictedDouble,
+    #[webidl] end_angle: UnrestrictedDouble,
+    #[webidl] counterclockwise: Option<bool>,
+  ) -> Result<(), ImageError> {
+    self.path.borrow_mut().ellipse(
+      Transform::identity(),
+      *x,
+      *y,
+      *radius_x,
+      *radius_y,
+      *rotation,
+      *start_angle,
+      *end_angle,
+      counterclockwise.unwrap_or(false),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn bounds(path: &PathData) -> (f32, f32, f32, f32) {
+    let rect = path.to_path().unwrap().bounds();
+    (rect.left(), rect.top(), rect.right(), rect.bottom())
+  }
+
+  #[test]
+  fn test_parse_svg() {
+    let path = PathData::parse_svg("M10 10 h 20 v20 H10 z");
+    assert_eq!(bounds(&path), (10.0, 10.0, 30.0, 30.0));
+
+    let path = PathData::parse_svg("M0,0L10-10.5.5e1,5");
+    assert_eq!(bounds(&path), (0.0, -10.5, 10.0, 5.0));
+
+    // A full circle drawn with two arcs.
+    let path =
+      PathData::parse_svg("M0 10 A10 10 0 0 1 20 10 a10 10 0 1 1 -20 0");
+    let (left, top, right, bottom) = bounds(&path);
+    assert!((left - 0.0).abs() < 0.01 && (right - 20.0).abs() < 0.01);
+    assert!((top - 0.0).abs() < 0.01 && (bottom - 20.0).abs() < 0.01);
+
+    // Everything up to the error is kept.
+    let path = PathData::parse_svg("M0 0 L10 10 L20 X 30 30");
+    assert_eq!(bounds(&path), (0.0, 0.0, 10.0, 10.0));
+  }
+
+  #[test]
+  fn test_arc() {
+    let mut path = PathData::default();
+    path
+      .ellipse(
+        Transform::identity(),
+        50.0,
+        50.0,
+        10.0,
+        10.0,
+        0.0,
+        0.0,
+        TAU,
+        false,
+      )
+      .unwrap();
+    let (left, top, right, bottom) = bounds(&path);
+    assert!((left - 40.0).abs() < 0.01 && (right - 60.0).abs() < 0.01);
+    assert!((top - 40.0).abs() < 0.01 && (bottom - 60.0).abs() < 0.01);
+
+    assert!(matches!(
+      path.ellipse(
+        Transform::identity(),
+        0.0,
+        0.0,
+        -1.0,
+        1.0,
+        0.0,
+        0.0,
+        1.0,
+        false
+      ),
+      Err(ImageError::NegativeRadius(_))
+    ));
+  }
+
+  #[test]
+  fn test_transformed_points() {
+    let mut path = PathData::default();
+    let ts = Transform::from_translate(5.0, 5.0).pre_scale(2.0, 2.0);
+    path.rect(ts, 0.0, 0.0, 10.0, 10.0);
+    assert_eq!(bounds(&path), (5.0, 5.0, 25.0, 25.0));
+  }
+}
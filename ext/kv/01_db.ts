@@ -45,6 +45,8 @@ const {
 } = primordials;
 
 import { ReadableStream } from "ext:deno_web/06_streams.js";
+import { KV_TRACING_ENABLED } from "ext:deno_telemetry/telemetry.ts";
+import { traceAsync } from "ext:deno_telemetry/util.ts";
 
 const encodeCursor: (
   selector: [Deno.KvKey | null, Deno.KvKey | null, Deno.KvKey | null],
@@ -101,6 +103,15 @@ interface RawKvEntry {
   versionstamp: string;
 }
 
+type RawSelector = [
+  Deno.KvKey | null,
+  Deno.KvKey | null,
+  Deno.KvKey | null,
+  number,
+  boolean,
+  string | null | undefined,
+];
+
 type RawValue = {
   kind: "v8";
   value: Uint8Array;
@@ -138,7 +149,8 @@ class Kv {
   }
 
   async get(key: Deno.KvKey, opts?: { consistency?: Deno.KvConsistencyLevel }) {
-    const { 0: entries }: [RawKvEntry[]] = await op_kv_snapshot_read(
+    const { 0: entries }: [RawKvEntry[]] = await snapshotRead(
+      "get",
       this.#rid,
       [[
         null,
@@ -164,7 +176,8 @@ class Kv {
     keys: Deno.KvKey[],
     opts?: { consistency?: Deno.KvConsistencyLevel },
   ): Promise<Deno.KvEntry<unknown>[]> {
-    const ranges: RawKvEntry[][] = await op_kv_snapshot_read(
+    const ranges: RawKvEntry[][] = await snapshotRead(
+      "getMany",
       this.#rid,
       ArrayPrototypeMap(keys, (key: Deno.KvKey) => [
         null,
@@ -190,6 +203,7 @@ class Kv {
 
   async set(key: Deno.KvKey, value: unknown, options?: { expireIn?: number }) {
     const versionstamp = await doAtomicWriteInPlace(
+      "set",
       this.#rid,
       [],
       [[key, "set", serializeValue(value), options?.expireIn]],
@@ -201,6 +215,7 @@ class Kv {
 
   async delete(key: Deno.KvKey) {
     const result = await doAtomicWriteInPlace(
+      "delete",
       this.#rid,
       [],
       [[key, "delete", null, undefined]],
@@ -245,7 +260,8 @@ class Kv {
     consistency: Deno.KvConsistencyLevel,
   ) => Promise<Deno.KvEntry<unknown>[]> {
     return async (selector, cursor, reverse, consistency) => {
-      const { 0: entries }: [RawKvEntry[]] = await op_kv_snapshot_read(
+      const { 0: entries }: [RawKvEntry[]] = await snapshotRead(
+        "list",
         this.#rid,
         [[
           ObjectHasOwn(selector, "prefix") ? selector.prefix : null,
@@ -278,6 +294,7 @@ class Kv {
     }
 
     const versionstamp = await doAtomicWriteInPlace(
+      "enqueue",
       this.#rid,
       [],
       [],
@@ -321,8 +338,21 @@ class Kv {
       (async () => {
         let success = false;
         try {
-          const result = handler(deserializedPayload);
-          const _res = isPromise(result) ? (await result) : result;
+          if (KV_TRACING_ENABLED) {
+            await traceAsync(
+              "deno.kv.listenQueue",
+              4,
+              {
+                "db.system.name": "deno_kv",
+                "messaging.system": "deno_kv",
+                "messaging.operation.type": "process",
+              },
+              () => handler(deserializedPayload),
+            );
+          } else {
+            const result = handler(deserializedPayload);
+            const _res = isPromise(result) ? (await result) : result;
+          }
           success = true;
         } catch (error) {
           import.meta.log("error", "Exception in queue handler", error);
@@ -554,6 +584,7 @@ class AtomicOperation {
 
   async commit(): Promise<Deno.KvCommitResult | Deno.KvCommitError> {
     const versionstamp = await doAtomicWriteInPlace(
+      "atomic",
       this.#rid,
       this.#checks,
       this.#mutations,
@@ -924,7 +955,39 @@ class KvListIterator extends AsyncIterator
   }
 }
 
+// Reads and writes are traced as client spans named after the public
+// `Deno.Kv` method, following the database semantic conventions.
+function traceKv<T>(
+  operation: string,
+  batchSize: number,
+  fn: () => Promise<T>,
+): Promise<T> {
+  if (!KV_TRACING_ENABLED) return fn();
+  const attributes: Record<string, string | number> = {
+    "db.system.name": "deno_kv",
+    "db.operation.name": operation,
+  };
+  if (batchSize > 1) {
+    attributes["db.operation.batch.size"] = batchSize;
+  }
+  return traceAsync(`deno.kv.${operation}`, 2, attributes, fn);
+}
+
+function snapshotRead(
+  operation: string,
+  rid: number,
+  ranges: RawSelector[],
+  consistency: Deno.KvConsistencyLevel,
+): Promise<RawKvEntry[][]> {
+  return traceKv(
+    operation,
+    ranges.length,
+    () => op_kv_snapshot_read(rid, ranges, consistency),
+  );
+}
+
 async function doAtomicWriteInPlace(
+  operation: string,
   rid: number,
   checks: [Deno.KvKey, string | null][],
   mutations: [Deno.KvKey, string, RawValue | null, number | undefined][],
@@ -942,11 +1005,10 @@ async function doAtomicWriteInPlace(
     }
   }
 
-  return await op_kv_atomic_write(
-    rid,
-    checks,
-    mutations,
-    enqueues,
+  return await traceKv(
+    operation,
+    checks.length + mutations.length + enqueues.length,
+    () => op_kv_atomic_write(rid, checks, mutations, enqueues),
   );
 }
 
@@ -11,6 +11,7 @@ use deno_core::OpState;
 use deno_core::futures::Stream;
 use deno_error::JsErrorBox;
 use deno_fetch::CreateHttpClientOptions;
+use deno_fetch::Http3Mode;
 use deno_fetch::create_http_client;
 use deno_permissions::PermissionsContainer;
 use deno_tls::Proxy;
@@ -191,6 +192,8 @@ impl DatabaseHandler for RemoteDbHandler {
         pool_idle_timeout: None,
         http1: false,
         http2: true,
+        http3: Http3Mode::Disabled,
+        http3_fallback: true,
         local_address: None,
         client_builder_hook: None,
       },
@@ -58,6 +58,7 @@ const {
 } = primordials;
 
 let getEndpointResource;
+let getListenerResource;
 
 function promiseFinallyWithoutUnhandled(p, f) {
   return PromisePrototypeThen(p, f, f);
@@ -164,6 +165,10 @@ class QuicListener {
   stop() {
     op_quic_listener_stop(this.#listener);
   }
+
+  static {
+    getListenerResource = (l) => l.#listener;
+  }
 }
 
 class QuicIncoming {
@@ -496,6 +501,7 @@ function connectQuic(options) {
 
 export {
   connectQuic,
+  getListenerResource,
   QuicBidirectionalStream,
   QuicConn,
   QuicEndpoint,
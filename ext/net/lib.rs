@@ -21,6 +21,7 @@ use deno_core::OpState;
 use deno_features::FeatureChecker;
 use deno_tls::RootCertStoreProvider;
 use deno_tls::rustls::RootCertStore;
+pub use quic::ListenerResource as QuicListenerResource;
 pub use quic::QuicError;
 
 pub const UNSTABLE_FEATURE_NAME: &str = "net";
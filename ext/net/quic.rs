@@ -296,7 +296,14 @@ pub(crate) fn op_quic_endpoint_close(
   Ok(())
 }
 
-struct ListenerResource(quinn::Endpoint, Arc<QuicServerConfig>);
+pub struct ListenerResource(quinn::Endpoint, Arc<QuicServerConfig>);
+
+impl ListenerResource {
+  /// The endpoint that incoming connections are accepted on.
+  pub fn endpoint(&self) -> &quinn::Endpoint {
+    &self.0
+  }
+}
 
 impl Drop for ListenerResource {
   fn drop(&mut self) {
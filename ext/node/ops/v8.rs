@@ -39,6 +39,18 @@ pub fn op_v8_get_heap_statistics(
   buffer[13] = stats.external_memory() as f64;
 }
 
+#[op2]
+pub fn op_v8_take_heap_snapshot(
+  scope: &mut v8::PinScope<'_, '_>,
+) -> Uint8Array {
+  let mut snapshot = Vec::new();
+  scope.take_heap_snapshot(|chunk| {
+    snapshot.extend_from_slice(chunk);
+    true
+  });
+  snapshot.into()
+}
+
 pub struct Serializer<'a> {
   inner: v8::ValueSerializer<'a>,
 }
@@ -2,9 +2,26 @@
 
 import { primordials } from "ext:core/mod.js";
 import {
+  AsyncSession,
+  AsyncSQLTagStore,
+  Database as DatabaseOp,
   DatabaseSync as DatabaseSyncOp,
   op_node_database_backup,
+  op_node_database_backup_finish,
+  op_node_database_backup_init,
+  op_node_database_backup_step,
+  op_node_sqlite_statement_all,
+  op_node_sqlite_statement_get,
+  op_node_sqlite_statement_iterate,
+  op_node_sqlite_statement_next,
+  op_node_sqlite_statement_reset,
+  op_node_sqlite_statement_run,
+  op_node_sqlite_tag_store_all,
+  op_node_sqlite_tag_store_get,
+  op_node_sqlite_tag_store_iterate,
+  op_node_sqlite_tag_store_run,
   Session,
+  Statement,
   StatementSync,
 } from "ext:core/ops";
 import type { Buffer } from "node:buffer";
@@ -19,6 +36,7 @@ const {
   ObjectSetPrototypeOf,
   ReflectConstruct,
   StringPrototypeIncludes,
+  SymbolAsyncDispose,
   SymbolDispose,
   SymbolFor,
   TypeError,
@@ -93,6 +111,93 @@ function DatabaseSync(
 ObjectSetPrototypeOf(DatabaseSync.prototype, DatabaseSyncOp.prototype);
 ObjectSetPrototypeOf(DatabaseSync, DatabaseSyncOp);
 
+// Like `DatabaseSync`, but queries run on a dedicated thread and every
+// method that touches the database returns a promise.
+function Database(
+  path: string | URL | Buffer,
+  options?: unknown,
+): DatabaseOp {
+  if (new.target === undefined) {
+    throw new ConstructCallRequiredError();
+  }
+  return ReflectConstruct(
+    DatabaseOp,
+    [parsePath(path), options],
+    new.target,
+  );
+}
+ObjectSetPrototypeOf(Database.prototype, DatabaseOp.prototype);
+ObjectSetPrototypeOf(Database, DatabaseOp);
+
+// Number of rows transferred from the connection thread at once when
+// iterating over the results of a statement.
+const ITERATE_BATCH_SIZE = 100;
+
+async function* iterateRows(stmt: Statement) {
+  let done = false;
+  try {
+    while (!done) {
+      const rows = await op_node_sqlite_statement_next(
+        stmt,
+        ITERATE_BATCH_SIZE,
+      );
+      done = rows.length < ITERATE_BATCH_SIZE;
+      for (let i = 0; i < rows.length; i++) {
+        yield rows[i];
+      }
+    }
+  } finally {
+    // The statement is reset by the last batch, unless the iteration was
+    // stopped early.
+    if (!done) {
+      op_node_sqlite_statement_reset(stmt);
+    }
+  }
+}
+
+const method = (value: unknown) => ({
+  __proto__: null,
+  value,
+  enumerable: false,
+  configurable: true,
+  writable: true,
+});
+
+ObjectDefineProperties(Statement.prototype, {
+  run: method(function run(this: Statement, ...params: unknown[]) {
+    return op_node_sqlite_statement_run(this, params);
+  }),
+  get: method(function get(this: Statement, ...params: unknown[]) {
+    return op_node_sqlite_statement_get(this, params);
+  }),
+  all: method(function all(this: Statement, ...params: unknown[]) {
+    return op_node_sqlite_statement_all(this, params);
+  }),
+  iterate: method(
+    async function* iterate(this: Statement, ...params: unknown[]) {
+      await op_node_sqlite_statement_iterate(this, params);
+      yield* iterateRows(this);
+    },
+  ),
+});
+
+ObjectDefineProperties(AsyncSQLTagStore.prototype, {
+  run: method(function run(this: AsyncSQLTagStore, ...args: unknown[]) {
+    return op_node_sqlite_tag_store_run(this, args);
+  }),
+  get: method(function get(this: AsyncSQLTagStore, ...args: unknown[]) {
+    return op_node_sqlite_tag_store_get(this, args);
+  }),
+  all: method(function all(this: AsyncSQLTagStore, ...args: unknown[]) {
+    return op_node_sqlite_tag_store_all(this, args);
+  }),
+  iterate: method(
+    async function* iterate(this: AsyncSQLTagStore, ...args: unknown[]) {
+      yield* iterateRows(await op_node_sqlite_tag_store_iterate(this, args));
+    },
+  ),
+});
+
 interface BackupOptions {
   /**
    * Name of the source database. This can be `'main'` (the default primary database) or any other
@@ -155,10 +260,13 @@ interface BackupProgressInfo {
  */
 // deno-lint-ignore require-await
 async function backup(
-  sourceDb: DatabaseSync,
+  sourceDb: DatabaseSync | Database,
   path: string | Buffer | URL,
   options?: BackupOptions,
 ): Promise<number> {
+  if (ObjectPrototypeIsPrototypeOf(Database.prototype, sourceDb)) {
+    return backupDatabase(sourceDb, parsePath(path), options);
+  }
   if (!ObjectPrototypeIsPrototypeOf(DatabaseSync.prototype, sourceDb)) {
     throw new InvalidArgTypeError(
       'The "sourceDb" argument must be an object.',
@@ -172,6 +280,37 @@ async function backup(
     options,
   );
 }
+// Copies the pages of an asynchronous `Database` in steps of
+// `options.rate` pages, each step running on the connection thread.
+async function backupDatabase(
+  sourceDb: Database,
+  path: string,
+  options?: BackupOptions,
+): Promise<number> {
+  const id = await op_node_database_backup_init(sourceDb, path, options);
+  const progress = options?.progress;
+  let done = false;
+  try {
+    while (true) {
+      const step = await op_node_database_backup_step(sourceDb, id);
+      done = step.done;
+      if (progress !== undefined && step.remainingPages !== 0) {
+        progress({
+          totalPages: step.totalPages,
+          remainingPages: step.remainingPages,
+        });
+      }
+      if (done) {
+        return step.totalPages;
+      }
+    }
+  } finally {
+    if (!done) {
+      op_node_database_backup_finish(sourceDb, id);
+    }
+  }
+}
+
 ObjectDefineProperty(backup, "length", {
   __proto__: null,
   value: 2,
@@ -253,6 +392,44 @@ ObjectDefineProperties(DatabaseSync.prototype, {
   },
 });
 
+ObjectDefineProperties(Database.prototype, {
+  [sqliteTypeSymbol]: {
+    __proto__: null,
+    value: "node:sqlite",
+    enumerable: false,
+    configurable: true,
+  },
+  [SymbolAsyncDispose]: {
+    __proto__: null,
+    value: async function () {
+      try {
+        await this.close();
+      } catch {
+        // Ignore errors.
+      }
+    },
+    enumerable: true,
+    configurable: true,
+    writable: true,
+  },
+});
+
+ObjectDefineProperties(AsyncSession.prototype, {
+  [SymbolAsyncDispose]: {
+    __proto__: null,
+    value: async function () {
+      try {
+        await this.close();
+      } catch {
+        // Ignore errors.
+      }
+    },
+    enumerable: true,
+    configurable: true,
+    writable: true,
+  },
+});
+
 ObjectDefineProperties(Session.prototype, {
   [SymbolDispose]: {
     __proto__: null,
@@ -269,11 +446,13 @@ ObjectDefineProperties(Session.prototype, {
   },
 });
 
-export { backup, DatabaseSync, StatementSync };
+export { backup, Database, DatabaseSync, Statement, StatementSync };
 
 export default {
   backup,
   constants,
+  Database,
   DatabaseSync,
+  Statement,
   StatementSync,
 };
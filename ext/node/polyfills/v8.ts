@@ -22,6 +22,7 @@ import {
   op_v8_read_value,
   op_v8_release_buffer,
   op_v8_set_treat_array_buffer_views_as_host_objects,
+  op_v8_take_heap_snapshot,
   op_v8_transfer_array_buffer,
   op_v8_transfer_array_buffer_de,
   op_v8_write_double,
@@ -33,6 +34,10 @@ import {
 } from "ext:core/ops";
 
 import { Buffer } from "node:buffer";
+import { writeFileSync } from "node:fs";
+import process from "node:process";
+import { Readable } from "node:stream";
+import { threadId } from "node:worker_threads";
 
 import { notImplemented } from "ext:deno_node/_utils.ts";
 import { isArrayBufferView } from "ext:deno_node/internal/util/types.ts";
@@ -43,8 +48,9 @@ export function cachedDataVersionTag() {
 export function getHeapCodeStatistics() {
   notImplemented("v8.getHeapCodeStatistics");
 }
-export function getHeapSnapshot() {
-  notImplemented("v8.getHeapSnapshot");
+export function getHeapSnapshot(_options?: unknown) {
+  const snapshot = op_v8_take_heap_snapshot();
+  return Readable.from([Buffer.from(snapshot.buffer)], { objectMode: false });
 }
 export function getHeapSpaceStatistics() {
   notImplemented("v8.getHeapSpaceStatistics");
@@ -89,8 +95,31 @@ export function stopCoverage() {
 export function takeCoverage() {
   notImplemented("v8.takeCoverage");
 }
-export function writeHeapSnapshot() {
-  notImplemented("v8.writeHeapSnapshot");
+let heapSnapshotSeq = 0;
+
+function pad(value: number, length = 2) {
+  return String(value).padStart(length, "0");
+}
+
+// Matches Node's `Heap.${yyyymmdd}.${hhmmss}.${pid}.${tid}.${seq}.heapsnapshot`.
+function defaultHeapSnapshotFilename() {
+  const now = new Date();
+  const date = `${now.getFullYear()}${pad(now.getMonth() + 1)}${
+    pad(now.getDate())
+  }`;
+  const time = `${pad(now.getHours())}${pad(now.getMinutes())}${
+    pad(now.getSeconds())
+  }`;
+  const seq = pad(++heapSnapshotSeq, 3);
+  return `Heap.${date}.${time}.${process.pid}.${threadId}.${seq}.heapsnapshot`;
+}
+
+export function writeHeapSnapshot(filename?: string, _options?: unknown) {
+  if (filename === undefined) {
+    filename = defaultHeapSnapshotFilename();
+  }
+  writeFileSync(filename, op_v8_take_heap_snapshot());
+  return filename;
 }
 // deno-lint-ignore no-explicit-any
 export function serialize(value: any) {
@@ -0,0 +1,402 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::ffi::CStr;
+use std::ffi::CString;
+use std::ffi::c_char;
+use std::rc::Rc;
+
+use deno_core::FromV8;
+use deno_core::GarbageCollected;
+use deno_core::OpState;
+use deno_core::convert::OptionUndefined;
+use deno_core::op2;
+use deno_core::v8;
+use deno_core::v8_static_strings;
+use deno_permissions::PermissionsContainer;
+use rusqlite::ffi;
+use rusqlite::ffi::SQLITE_DBCONFIG_DEFENSIVE;
+
+use super::AsyncSQLTagStore;
+use super::AsyncSession;
+use super::SqliteError;
+use super::Statement;
+use super::connection::ConnectionThread;
+use super::database::DatabaseOptions;
+use super::database::open_connection;
+use super::database::set_db_config;
+use super::session::SessionOptions;
+use super::validators;
+
+// Policy for conflicts found while applying a changeset. Unlike
+// `DatabaseSync#applyChangeset()`, callbacks cannot be used because the
+// changeset is applied on the connection thread.
+struct ApplyChangesetOptions {
+  filter: Option<Vec<String>>,
+  on_conflict: i32,
+}
+
+impl<'a> FromV8<'a> for ApplyChangesetOptions {
+  type Error = validators::Error;
+
+  fn from_v8(
+    scope: &mut v8::PinScope<'a, '_>,
+    value: v8::Local<'a, v8::Value>,
+  ) -> Result<Self, validators::Error> {
+    use validators::Error;
+
+    let mut options = ApplyChangesetOptions {
+      filter: None,
+      on_conflict: ffi::SQLITE_CHANGESET_ABORT,
+    };
+
+    if value.is_undefined() {
+      return Ok(options);
+    }
+
+    let obj = v8::Local::<v8::Object>::try_from(value).map_err(|_| {
+      Error::InvalidArgType("The \"options\" argument must be an object.")
+    })?;
+
+    v8_static_strings! {
+      FILTER_STRING = "filter",
+      ON_CONFLICT_STRING = "onConflict",
+    }
+
+    let filter_string = FILTER_STRING.v8_string(scope).unwrap();
+    if let Some(filter) = obj.get(scope, filter_string.into())
+      && !filter.is_undefined()
+    {
+      let filter = v8::Local::<v8::Array>::try_from(filter).map_err(|_| {
+        Error::InvalidArgType(
+          "The \"options.filter\" argument must be an array of strings.",
+        )
+      })?;
+
+      let mut tables = Vec::with_capacity(filter.length() as usize);
+      for i in 0..filter.length() {
+        let table = filter.get_index(scope, i).unwrap();
+        if !table.is_string() {
+          return Err(Error::InvalidArgType(
+            "The \"options.filter\" argument must be an array of strings.",
+          ));
+        }
+        tables.push(table.to_rust_string_lossy(scope));
+      }
+      options.filter = Some(tables);
+    }
+
+    let on_conflict_string = ON_CONFLICT_STRING.v8_string(scope).unwrap();
+    if let Some(on_conflict) = obj.get(scope, on_conflict_string.into())
+      && !on_conflict.is_undefined()
+    {
+      let on_conflict =
+        v8::Local::<v8::Int32>::try_from(on_conflict).map_err(|_| {
+          Error::InvalidArgType(
+            "The \"options.onConflict\" argument must be a number.",
+          )
+        })?;
+      options.on_conflict = on_conflict.value();
+    }
+
+    Ok(options)
+  }
+}
+
+pub struct Database {
+  thread: RefCell<Option<ConnectionThread>>,
+  options: DatabaseOptions,
+  location: String,
+  next_tag_store_id: Cell<u32>,
+}
+
+// SAFETY: we're sure this can be GCed
+unsafe impl GarbageCollected for Database {
+  fn trace(&self, _visitor: &mut deno_core::v8::cppgc::Visitor) {}
+
+  fn get_name(&self) -> &'static std::ffi::CStr {
+    c"Database"
+  }
+}
+
+impl Database {
+  pub(crate) fn thread(&self) -> Result<ConnectionThread, SqliteError> {
+    self
+      .thread
+      .borrow()
+      .clone()
+      .ok_or(SqliteError::AlreadyClosed)
+  }
+}
+
+// Represents a single connection to a SQLite database whose queries run
+// on a dedicated thread. Every method that touches the database returns
+// a promise, so long running queries do not block the event loop.
+#[op2]
+impl Database {
+  // Constructs a new `Database` instance. Accepts the same `location`
+  // and options as `DatabaseSync`.
+  #[constructor]
+  #[cppgc]
+  fn new(
+    state: &mut OpState,
+    #[string] location: String,
+    #[scoped] options: DatabaseOptions,
+  ) -> Result<Database, SqliteError> {
+    let thread = if options.open {
+      let conn = open_connection(state, &location, &options)?;
+      Some(ConnectionThread::spawn(conn))
+    } else {
+      None
+    };
+
+    Ok(Database {
+      thread: RefCell::new(thread),
+      options,
+      location,
+      next_tag_store_id: Cell::new(0),
+    })
+  }
+
+  // Opens the database specified by `location` of this instance.
+  //
+  // Opening is synchronous, like in `DatabaseSync`, so that the
+  // permission checks and errors surface right away.
+  #[fast]
+  #[undefined]
+  fn open(&self, state: &mut OpState) -> Result<(), SqliteError> {
+    if self.thread.borrow().is_some() {
+      return Err(SqliteError::AlreadyOpen);
+    }
+
+    let conn = open_connection(state, &self.location, &self.options)?;
+    *self.thread.borrow_mut() = Some(ConnectionThread::spawn(conn));
+
+    Ok(())
+  }
+
+  // Closes the database connection once all queued queries finished.
+  // Statements and sessions of this database can no longer be used.
+  async fn close(&self) -> Result<(), SqliteError> {
+    let thread = self
+      .thread
+      .borrow_mut()
+      .take()
+      .ok_or(SqliteError::AlreadyClosed)?;
+
+    thread
+      .run(|state| {
+        state.close();
+        Ok(())
+      })
+      .await
+  }
+
+  // Executes one or more SQL statements without returning any results.
+  async fn exec(&self, #[string] sql: String) -> Result<(), SqliteError> {
+    let thread = self.thread()?;
+    thread
+      .run(move |state| Ok(state.connection()?.execute_batch(&sql)?))
+      .await
+  }
+
+  // Compiles an SQL statement into a prepared statement.
+  #[cppgc]
+  async fn prepare(
+    &self,
+    #[string] sql: String,
+  ) -> Result<Statement, SqliteError> {
+    let thread = self.thread()?;
+    let info = thread.run(move |state| state.prepare(&sql)).await?;
+
+    Ok(Statement::create(
+      thread,
+      info,
+      self.options.return_arrays,
+      self.options.use_big_int_arguments,
+      self.options.allow_bare_named_params,
+      self.options.allow_unknown_named_params,
+    ))
+  }
+
+  // Creates and attaches a session to the database.
+  #[cppgc]
+  async fn create_session(
+    &self,
+    #[scoped] options: OptionUndefined<SessionOptions>,
+  ) -> Result<AsyncSession, SqliteError> {
+    let thread = self.thread()?;
+    let SessionOptions { table, db } = options.0.unwrap_or_default();
+    let id = thread
+      .run(move |state| state.create_session(db, table))
+      .await?;
+
+    Ok(AsyncSession::new(thread, id))
+  }
+
+  // Applies a changeset to the database. Resolves to `false` when
+  // applying it was aborted because of a conflict.
+  async fn apply_changeset(
+    &self,
+    #[buffer(copy)] changeset: Vec<u8>,
+    #[scoped] options: ApplyChangesetOptions,
+  ) -> Result<bool, SqliteError> {
+    let thread = self.thread()?;
+    thread
+      .run(move |state| {
+        state.apply_changeset(
+          &changeset,
+          options.filter.as_deref(),
+          options.on_conflict,
+        )
+      })
+      .await
+  }
+
+  // Loads a SQLite extension. Requires FFI permission and
+  // `allowExtension` to be set when opening the database.
+  async fn load_extension(
+    &self,
+    state: Rc<RefCell<OpState>>,
+    #[string] path: String,
+    #[string] entry_point: Option<String>,
+  ) -> Result<(), SqliteError> {
+    let thread = self.thread()?;
+    if !self.options.allow_extension {
+      return Err(SqliteError::LoadExensionFailed(
+        "Cannot load SQLite extensions when allowExtension is not enabled"
+          .to_string(),
+      ));
+    }
+
+    state
+      .borrow()
+      .borrow::<PermissionsContainer>()
+      .check_ffi_all()?;
+
+    let path = CString::new(path)?;
+    let entry_point = entry_point.map(CString::new).transpose()?;
+
+    thread
+      .run(move |state| {
+        // SAFETY: the handle is used on the connection thread while the
+        // connection is open.
+        let raw_handle = unsafe { state.connection()?.handle() };
+        let entry_point_ptr = entry_point
+          .as_ref()
+          .map(|ep| ep.as_ptr())
+          .unwrap_or(std::ptr::null());
+        let mut err_msg: *mut c_char = std::ptr::null_mut();
+
+        // SAFETY: `path` and `entry_point` are valid c-strings and the
+        // error message is freed below.
+        unsafe {
+          let res = ffi::sqlite3_load_extension(
+            raw_handle,
+            path.as_ptr(),
+            entry_point_ptr,
+            &mut err_msg,
+          );
+          if res == ffi::SQLITE_OK {
+            return Ok(());
+          }
+
+          let message = if !err_msg.is_null() {
+            let message =
+              CStr::from_ptr(err_msg).to_string_lossy().into_owned();
+            ffi::sqlite3_free(err_msg as *mut _);
+            message
+          } else {
+            format!("Failed to load extension with error code: {}", res)
+          };
+          Err(SqliteError::LoadExensionFailed(message))
+        }
+      })
+      .await
+  }
+
+  // Resolves to the file name of the database `dbName`, or `null` for
+  // in-memory databases.
+  #[string]
+  async fn location(
+    &self,
+    #[string] db_name: Option<String>,
+  ) -> Result<Option<String>, SqliteError> {
+    let thread = self.thread()?;
+    let name = CString::new(db_name.unwrap_or_else(|| "main".to_string()))?;
+    thread
+      .run(move |state| {
+        let conn = state.connection()?;
+        // SAFETY: the handle is used on the connection thread and `name`
+        // is a valid c-string.
+        let filename =
+          unsafe { ffi::sqlite3_db_filename(conn.handle(), name.as_ptr()) };
+        if filename.is_null() {
+          return Ok(None);
+        }
+
+        // SAFETY: `filename` is a valid c-string owned by SQLite.
+        let filename = unsafe { CStr::from_ptr(filename) };
+        if filename.is_empty() {
+          return Ok(None);
+        }
+        Ok(Some(filename.to_string_lossy().into_owned()))
+      })
+      .await
+  }
+
+  async fn enable_defensive(
+    &self,
+    #[validate(validators::active_bool)] active: bool,
+  ) -> Result<(), SqliteError> {
+    let thread = self.thread()?;
+    thread
+      .run(move |state| {
+        assert!(set_db_config(
+          state.connection()?,
+          SQLITE_DBCONFIG_DEFENSIVE,
+          active
+        ));
+        Ok(())
+      })
+      .await
+  }
+
+  #[getter]
+  fn is_open(&self) -> bool {
+    self.thread.borrow().is_some()
+  }
+
+  // Creates a store of prepared statements for tagged template queries.
+  #[cppgc]
+  fn create_tag_store(
+    &self,
+    scope: &mut v8::PinScope<'_, '_>,
+    #[varargs] args: Option<&v8::FunctionCallbackArguments>,
+  ) -> Result<AsyncSQLTagStore, SqliteError> {
+    let thread = self.thread()?;
+    let capacity = if let Some(args) = args
+      && args.length() > 0
+      && args.get(0).is_number()
+    {
+      args.get(0).uint32_value(scope).unwrap_or(1000)
+    } else {
+      1000
+    };
+
+    let db_object = args.map(|a| a.this()).ok_or(SqliteError::AlreadyClosed)?;
+
+    let id = self.next_tag_store_id.get() + 1;
+    self.next_tag_store_id.set(id);
+
+    Ok(AsyncSQLTagStore::create(
+      thread,
+      id,
+      capacity,
+      self.options.return_arrays,
+      self.options.use_big_int_arguments,
+      v8::Global::new(scope, db_object),
+    ))
+  }
+}
@@ -0,0 +1,253 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+use std::cell::Cell;
+
+use deno_core::GarbageCollected;
+use deno_core::op2;
+use deno_core::v8;
+use deno_core::v8_static_strings;
+
+use super::SqliteError;
+use super::connection::ColumnInfo;
+use super::connection::ConnectionThread;
+use super::connection::Row;
+use super::connection::Rows;
+use super::connection::StatementInfo;
+use super::connection::StatementOptions;
+use super::connection::StatementParams;
+use super::statement::RunStatementResult;
+use super::validators;
+
+// A prepared statement of an asynchronous `Database`. The statement
+// itself lives on the connection thread and is finalized when this
+// object is collected or the database is closed.
+pub struct Statement {
+  thread: ConnectionThread,
+  id: u32,
+  source_sql: String,
+  columns: Vec<ColumnInfo>,
+  return_arrays: Cell<bool>,
+  use_big_ints: Cell<bool>,
+  allow_bare_named_params: Cell<bool>,
+  allow_unknown_named_params: Cell<bool>,
+}
+
+// SAFETY: we're sure this can be GCed
+unsafe impl GarbageCollected for Statement {
+  fn trace(&self, _visitor: &mut deno_core::v8::cppgc::Visitor) {}
+
+  fn get_name(&self) -> &'static std::ffi::CStr {
+    c"Statement"
+  }
+}
+
+impl Drop for Statement {
+  fn drop(&mut self) {
+    let id = self.id;
+    self.thread.post(move |state| state.finalize(id));
+  }
+}
+
+impl Statement {
+  pub(crate) fn create(
+    thread: ConnectionThread,
+    info: StatementInfo,
+    return_arrays: bool,
+    use_big_ints: bool,
+    allow_bare_named_params: bool,
+    allow_unknown_named_params: bool,
+  ) -> Self {
+    Statement {
+      thread,
+      id: info.id,
+      source_sql: info.source_sql,
+      columns: info.columns,
+      return_arrays: Cell::new(return_arrays),
+      use_big_ints: Cell::new(use_big_ints),
+      allow_bare_named_params: Cell::new(allow_bare_named_params),
+      allow_unknown_named_params: Cell::new(allow_unknown_named_params),
+    }
+  }
+
+  fn options(&self) -> StatementOptions {
+    StatementOptions {
+      return_arrays: self.return_arrays.get(),
+      use_big_ints: self.use_big_ints.get(),
+      allow_bare_named_params: self.allow_bare_named_params.get(),
+      allow_unknown_named_params: self.allow_unknown_named_params.get(),
+    }
+  }
+}
+
+// Query methods (`run()`, `get()`, `all()` and `iterate()`) are
+// defined in JavaScript on top of the ops below, which receive the
+// parameters as an array.
+#[op2]
+impl Statement {
+  #[constructor]
+  #[cppgc]
+  fn new(_: bool) -> Result<Statement, SqliteError> {
+    Err(SqliteError::InvalidConstructor)
+  }
+
+  #[fast]
+  #[undefined]
+  fn set_allow_bare_named_parameters(
+    &self,
+    #[validate(validators::allow_bare_named_params_bool)] enabled: bool,
+  ) {
+    self.allow_bare_named_params.set(enabled);
+  }
+
+  #[fast]
+  #[undefined]
+  fn set_allow_unknown_named_parameters(
+    &self,
+    #[validate(validators::allow_unknown_named_params_bool)] enabled: bool,
+  ) {
+    self.allow_unknown_named_params.set(enabled);
+  }
+
+  #[fast]
+  #[undefined]
+  fn set_read_big_ints(
+    &self,
+    #[validate(validators::read_big_ints_bool)] enabled: bool,
+  ) {
+    self.use_big_ints.set(enabled);
+  }
+
+  #[fast]
+  #[undefined]
+  fn set_return_arrays(
+    &self,
+    #[validate(validators::return_arrays_bool)] enabled: bool,
+  ) {
+    self.return_arrays.set(enabled);
+  }
+
+  #[getter]
+  #[rename("sourceSQL")]
+  #[string]
+  fn source_sql(&self) -> String {
+    self.source_sql.clone()
+  }
+
+  fn columns<'a>(
+    &self,
+    scope: &mut v8::PinScope<'a, '_>,
+  ) -> v8::Local<'a, v8::Array> {
+    v8_static_strings! {
+      NAME = "name",
+      COLUMN = "column",
+      TABLE = "table",
+      DATABASE = "database",
+      TYPE = "type",
+    }
+
+    let keys = &[
+      NAME.v8_string(scope).unwrap().into(),
+      COLUMN.v8_string(scope).unwrap().into(),
+      TABLE.v8_string(scope).unwrap().into(),
+      DATABASE.v8_string(scope).unwrap().into(),
+      TYPE.v8_string(scope).unwrap().into(),
+    ];
+
+    let mut columns = Vec::with_capacity(self.columns.len());
+    for column in &self.columns {
+      let values = [
+        &column.name,
+        &column.column,
+        &column.table,
+        &column.database,
+        &column.decl_type,
+      ]
+      .map(|value| match value {
+        Some(value) => v8::String::new(scope, value).unwrap().into(),
+        None => v8::null(scope).into(),
+      });
+      let null = v8::null(scope).into();
+      let obj =
+        v8::Object::with_prototype_and_properties(scope, null, keys, &values);
+      columns.push(obj.into());
+    }
+
+    v8::Array::new_with_elements(scope, &columns)
+  }
+}
+
+#[op2]
+pub async fn op_node_sqlite_statement_run(
+  #[cppgc] stmt: &Statement,
+  #[scoped] params: StatementParams,
+) -> Result<RunStatementResult, SqliteError> {
+  let (id, options) = (stmt.id, stmt.options());
+  stmt
+    .thread
+    .run(move |state| state.query(id, |query| query.run(&params, options)))
+    .await
+}
+
+#[op2]
+pub async fn op_node_sqlite_statement_get(
+  #[cppgc] stmt: &Statement,
+  #[scoped] params: StatementParams,
+) -> Result<Row, SqliteError> {
+  let (id, options) = (stmt.id, stmt.options());
+  stmt
+    .thread
+    .run(move |state| state.query(id, |query| query.get(&params, options)))
+    .await
+    .map(Row)
+}
+
+#[op2]
+pub async fn op_node_sqlite_statement_all(
+  #[cppgc] stmt: &Statement,
+  #[scoped] params: StatementParams,
+) -> Result<Rows, SqliteError> {
+  let (id, options) = (stmt.id, stmt.options());
+  stmt
+    .thread
+    .run(move |state| state.query(id, |query| query.all(&params, options)))
+    .await
+}
+
+// Binds the parameters of an iteration. Rows are then read in batches
+// with `op_node_sqlite_statement_next`.
+#[op2]
+pub async fn op_node_sqlite_statement_iterate(
+  #[cppgc] stmt: &Statement,
+  #[scoped] params: StatementParams,
+) -> Result<(), SqliteError> {
+  let (id, options) = (stmt.id, stmt.options());
+  stmt
+    .thread
+    .run(move |state| state.query(id, |query| query.iterate(&params, options)))
+    .await
+}
+
+#[op2]
+pub async fn op_node_sqlite_statement_next(
+  #[cppgc] stmt: &Statement,
+  #[smi] count: u32,
+) -> Result<Rows, SqliteError> {
+  let (id, options) = (stmt.id, stmt.options());
+  stmt
+    .thread
+    .run(move |state| {
+      state.query(id, |query| query.next(count as usize, options))
+    })
+    .await
+}
+
+#[op2(fast)]
+pub fn op_node_sqlite_statement_reset(#[cppgc] stmt: &Statement) {
+  let id = stmt.id;
+  stmt.thread.post(move |state| {
+    let _ = state.query(id, |query| {
+      query.reset();
+      Ok(())
+    });
+  });
+}
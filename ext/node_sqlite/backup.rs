@@ -3,8 +3,11 @@
 use std::borrow::Cow;
 use std::cell::RefCell;
 use std::ffi::c_int;
+use std::path::Path;
+use std::path::PathBuf;
 use std::rc::Rc;
 
+use deno_core::FromV8;
 use deno_core::OpState;
 use deno_core::op2;
 use deno_core::v8;
@@ -14,8 +17,10 @@ use deno_permissions::PermissionsContainer;
 use rusqlite::Connection;
 use rusqlite::backup;
 
+use super::Database;
 use super::DatabaseSync;
 use super::SqliteError;
+use super::connection::BackupProgress;
 use super::validators;
 
 const DEFAULT_BACKUP_RATE: c_int = 100;
@@ -151,21 +156,7 @@ pub fn op_node_database_backup<'a>(
     )?
   };
 
-  let mut dst_conn = Connection::open(checked_path).map_err(|e| match e {
-    rusqlite::Error::SqliteFailure(err, Some(msg)) => {
-      let message = if err.extended_code == rusqlite::ffi::SQLITE_CANTOPEN {
-        "unable to open database file".to_string()
-      } else {
-        msg
-      };
-      SqliteError::SqliteSysError {
-        message: message.clone(),
-        errstr: message,
-        errcode: err.extended_code as _,
-      }
-    }
-    other_err => SqliteError::from(other_err),
-  })?;
+  let mut dst_conn = open_backup_target(checked_path)?;
   let backup = backup::Backup::new_with_names(
     src_conn,
     options.source.as_str(),
@@ -215,3 +206,98 @@ pub fn op_node_database_backup<'a>(
     }
   }
 }
+
+// Opens the database that a backup is written to.
+pub(crate) fn open_backup_target(
+  path: impl AsRef<Path>,
+) -> Result<Connection, SqliteError> {
+  Connection::open(path).map_err(|e| match e {
+    rusqlite::Error::SqliteFailure(err, Some(msg)) => {
+      let message = if err.extended_code == rusqlite::ffi::SQLITE_CANTOPEN {
+        "unable to open database file".to_string()
+      } else {
+        msg
+      };
+      SqliteError::SqliteSysError {
+        message: message.clone(),
+        errstr: message,
+        errcode: err.extended_code as _,
+      }
+    }
+    other_err => SqliteError::from(other_err),
+  })
+}
+
+// Options of a backup of an asynchronous `Database`. The progress
+// callback is invoked from JavaScript between steps.
+struct DatabaseBackupOptions {
+  source: String,
+  target: String,
+  rate: i32,
+}
+
+impl<'a> FromV8<'a> for DatabaseBackupOptions {
+  type Error = validators::Error;
+
+  fn from_v8(
+    scope: &mut v8::PinScope<'a, '_>,
+    value: v8::Local<'a, v8::Value>,
+  ) -> Result<Self, validators::Error> {
+    let options = BackupOptions::from_value(scope, value)?;
+    Ok(DatabaseBackupOptions {
+      source: options.source,
+      target: options.target,
+      rate: options.rate,
+    })
+  }
+}
+
+// Starts a backup of an asynchronous `Database`. Pages are copied with
+// `op_node_database_backup_step` so that the event loop is not blocked.
+#[op2(stack_trace)]
+pub async fn op_node_database_backup_init(
+  state: Rc<RefCell<OpState>>,
+  #[cppgc] source_db: &Database,
+  #[string] path: String,
+  #[scoped] options: DatabaseBackupOptions,
+) -> Result<u32, SqliteError> {
+  let thread = source_db.thread()?;
+  let checked_path = {
+    let mut state = state.borrow_mut();
+    let permissions = state.borrow_mut::<PermissionsContainer>();
+    permissions
+      .check_open(
+        Cow::Owned(PathBuf::from(path)),
+        OpenAccessKind::Write,
+        Some("node:sqlite.backup"),
+      )?
+      .into_owned_path()
+  };
+
+  thread
+    .run(move |state| {
+      state.start_backup(
+        checked_path,
+        &options.source,
+        &options.target,
+        options.rate,
+      )
+    })
+    .await
+}
+
+#[op2]
+pub async fn op_node_database_backup_step(
+  #[cppgc] source_db: &Database,
+  id: u32,
+) -> Result<BackupProgress, SqliteError> {
+  let thread = source_db.thread()?;
+  thread.run(move |state| state.step_backup(id)).await
+}
+
+#[op2(fast)]
+pub fn op_node_database_backup_finish(#[cppgc] source_db: &Database, id: u32) {
+  if let Ok(thread) = source_db.thread() {
+    thread.post(move |state| state.finish_backup(id));
+  }
+}
@@ -0,0 +1,1083 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::ffi::CString;
+use std::ffi::c_char;
+use std::ffi::c_void;
+use std::future::Future;
+use std::path::PathBuf;
+use std::ptr::null;
+use std::sync::mpsc;
+
+use deno_core::FromV8;
+use deno_core::ToV8;
+use deno_core::futures::channel::oneshot;
+use deno_core::v8;
+use deno_core::v8::GetPropertyNamesArgs;
+use rusqlite::ffi;
+use rusqlite::types::Value;
+
+use super::SqliteError;
+use super::backup::open_backup_target;
+use super::lru_cache::LRUCache;
+use super::session::session_buffer_op;
+use super::statement::MAX_SAFE_JS_INTEGER;
+use super::statement::RunStatementResult;
+use super::statement::check_error_code;
+use super::statement::check_error_code2;
+
+type Job = Box<dyn FnOnce(&mut ConnectionState) + Send>;
+
+// Handle to the thread that owns the connection of an asynchronous
+// `Database`. Jobs are executed one at a time, in the order they were
+// submitted, so a query never blocks the event loop.
+//
+// The thread exits once the connection is closed or every handle to it
+// has been dropped.
+#[derive(Clone)]
+pub struct ConnectionThread {
+  sender: mpsc::Sender<Job>,
+}
+
+impl ConnectionThread {
+  pub fn spawn(conn: rusqlite::Connection) -> ConnectionThread {
+    let (sender, receiver) = mpsc::channel::<Job>();
+    std::thread::Builder::new()
+      .name("node:sqlite".to_string())
+      .spawn(move || {
+        let mut state = ConnectionState::new(conn);
+        while let Ok(job) = receiver.recv() {
+          job(&mut state);
+          if state.conn.is_none() {
+            break;
+          }
+        }
+      })
+      .expect("Failed to spawn node:sqlite connection thread");
+
+    ConnectionThread { sender }
+  }
+
+  // Runs `f` on the connection thread and resolves with its result.
+  pub fn run<T, F>(
+    &self,
+    f: F,
+  ) -> impl Future<Output = Result<T, SqliteError>> + use<T, F>
+  where
+    T: Send + 'static,
+    F: FnOnce(&mut ConnectionState) -> Result<T, SqliteError> + Send + 'static,
+  {
+    let (tx, rx) = oneshot::channel();
+    let sent = self
+      .sender
+      .send(Box::new(move |state| {
+        let _ = tx.send(f(state));
+      }))
+      .is_ok();
+
+    async move {
+      if !sent {
+        return Err(SqliteError::AlreadyClosed);
+      }
+      rx.await.unwrap_or(Err(SqliteError::AlreadyClosed))
+    }
+  }
+
+  // Runs `f` on the connection thread without waiting for it. Used to
+  // release handles when their JavaScript objects are collected.
+  pub fn post(&self, f: impl FnOnce(&mut ConnectionState) + Send + 'static) {
+    let _ = self.sender.send(Box::new(f));
+  }
+}
+
+struct RawStatement(*mut ffi::sqlite3_stmt);
+
+impl Drop for RawStatement {
+  fn drop(&mut self) {
+    // SAFETY: the statement is owned by this value and finalized once.
+    unsafe {
+      ffi::sqlite3_finalize(self.0);
+    }
+  }
+}
+
+struct RawSession(*mut ffi::sqlite3_session);
+
+impl Drop for RawSession {
+  fn drop(&mut self) {
+    // SAFETY: the session is owned by this value and deleted once.
+    unsafe {
+      ffi::sqlite3session_delete(self.0);
+    }
+  }
+}
+
+struct RawBackup {
+  inner: *mut ffi::sqlite3_backup,
+  rate: i32,
+  // Dropped after `inner` is finished.
+  target: rusqlite::Connection,
+}
+
+impl Drop for RawBackup {
+  fn drop(&mut self) {
+    // SAFETY: the backup is owned by this value and finished once.
+    unsafe {
+      ffi::sqlite3_backup_finish(self.inner);
+    }
+  }
+}
+
+// State owned by the connection thread. Raw SQLite handles never leave
+// this thread.
+pub struct ConnectionState {
+  // Declared before `conn` so that all handles are released before the
+  // connection is closed.
+  statements: HashMap<u32, RawStatement>,
+  sessions: HashMap<u32, RawSession>,
+  backups: HashMap<u32, RawBackup>,
+  tag_stores: HashMap<u32, LRUCache<String, RawStatement>>,
+  conn: Option<rusqlite::Connection>,
+  next_id: u32,
+}
+
+impl ConnectionState {
+  fn new(conn: rusqlite::Connection) -> Self {
+    ConnectionState {
+      statements: HashMap::new(),
+      sessions: HashMap::new(),
+      backups: HashMap::new(),
+      tag_stores: HashMap::new(),
+      conn: Some(conn),
+      next_id: 0,
+    }
+  }
+
+  fn next_id(&mut self) -> u32 {
+    self.next_id += 1;
+    self.next_id
+  }
+
+  pub fn connection(&self) -> Result<&rusqlite::Connection, SqliteError> {
+    self.conn.as_ref().ok_or(SqliteError::AlreadyClosed)
+  }
+
+  fn handle(&self) -> Result<*mut ffi::sqlite3, SqliteError> {
+    // SAFETY: the handle is only used on this thread while the
+    // connection is open.
+    Ok(unsafe { self.connection()?.handle() })
+  }
+
+  // Finalizes all statements, sessions and backups and closes the
+  // connection. The thread exits after the current job.
+  pub fn close(&mut self) {
+    self.tag_stores.clear();
+    self.statements.clear();
+    self.sessions.clear();
+    self.backups.clear();
+    self.conn.take();
+  }
+
+  // Compiles `sql` into a prepared statement owned by this thread.
+  pub fn prepare(&mut self, sql: &str) -> Result<StatementInfo, SqliteError> {
+    let db = self.handle()?;
+    let stmt = prepare_raw(db, sql)?;
+    let info = StatementInfo {
+      id: self.next_id(),
+      source_sql: sql.to_string(),
+      columns: column_info(stmt.0),
+    };
+    self.statements.insert(info.id, stmt);
+    Ok(info)
+  }
+
+  pub fn finalize(&mut self, id: u32) {
+    self.statements.remove(&id);
+  }
+
+  // Runs `f` with the prepared statement `id`.
+  pub fn query<T>(
+    &self,
+    id: u32,
+    f: impl FnOnce(&Query) -> Result<T, SqliteError>,
+  ) -> Result<T, SqliteError> {
+    let db = self.handle()?;
+    let stmt = self
+      .statements
+      .get(&id)
+      .ok_or(SqliteError::StatementFinalized)?;
+    f(&Query { db, stmt: stmt.0 })
+  }
+
+  // Runs `f` with the statement cached for `sql` in the tag store `id`,
+  // preparing it on a cache miss. Returns the result together with the
+  // number of cached statements.
+  pub fn tag_store_query<T>(
+    &mut self,
+    id: u32,
+    capacity: usize,
+    sql: String,
+    f: impl FnOnce(&Query) -> Result<T, SqliteError>,
+  ) -> Result<(T, usize), SqliteError> {
+    let db = self.handle()?;
+    let cache = self
+      .tag_stores
+      .entry(id)
+      .or_insert_with(|| LRUCache::new(capacity));
+
+    let stmt = match cache.remove(&sql) {
+      Some(stmt) => stmt,
+      None => prepare_raw(db, &sql)?,
+    };
+    let result = f(&Query { db, stmt: stmt.0 });
+    // Put the statement back even on failure. With a capacity of zero it
+    // is evicted, and finalized, right away.
+    cache.put(sql, stmt);
+
+    Ok((result?, cache.size()))
+  }
+
+  pub fn clear_tag_store(&mut self, id: u32) {
+    self.tag_stores.remove(&id);
+  }
+
+  pub fn create_session(
+    &mut self,
+    db_name: Option<String>,
+    table: Option<String>,
+  ) -> Result<u32, SqliteError> {
+    let db = self.handle()?;
+
+    let z_db = CString::new(db_name.unwrap_or_else(|| "main".to_string()))?;
+    let mut raw_session = std::ptr::null_mut();
+    // SAFETY: `db` is a valid connection handle and `z_db` points to a
+    // valid c-string.
+    let r = unsafe {
+      ffi::sqlite3session_create(db, z_db.as_ptr(), &mut raw_session)
+    };
+    if r != ffi::SQLITE_OK {
+      return Err(SqliteError::SessionCreateFailed);
+    }
+    let session = RawSession(raw_session);
+
+    let table = table.map(CString::new).transpose()?;
+    let z_table = table.as_ref().map(|table| table.as_ptr()).unwrap_or(null());
+    // SAFETY: `z_table` is null or points to a valid c-string and the
+    // session handle is valid.
+    let r = unsafe { ffi::sqlite3session_attach(session.0, z_table) };
+    if r != ffi::SQLITE_OK {
+      return Err(SqliteError::SessionCreateFailed);
+    }
+
+    let id = self.next_id();
+    self.sessions.insert(id, session);
+    Ok(id)
+  }
+
+  // Retrieves the changeset, or the patchset, of the session `id`.
+  pub fn session_changes(
+    &self,
+    id: u32,
+    patchset: bool,
+  ) -> Result<Box<[u8]>, SqliteError> {
+    self.connection()?;
+    let session = self.sessions.get(&id).ok_or(SqliteError::SessionClosed)?;
+    if patchset {
+      session_buffer_op(session.0, ffi::sqlite3session_patchset)
+    } else {
+      session_buffer_op(session.0, ffi::sqlite3session_changeset)
+    }
+  }
+
+  pub fn close_session(&mut self, id: u32) -> Result<(), SqliteError> {
+    self.connection()?;
+    self
+      .sessions
+      .remove(&id)
+      .map(drop)
+      .ok_or(SqliteError::SessionClosed)
+  }
+
+  // Applies a changeset. Conflicts are resolved with `on_conflict` and
+  // only changes to the `tables` are applied when it is given.
+  pub fn apply_changeset(
+    &self,
+    changeset: &[u8],
+    tables: Option<&[String]>,
+    on_conflict: i32,
+  ) -> Result<bool, SqliteError> {
+    struct HandlerCtx<'a> {
+      tables: Option<&'a [String]>,
+      on_conflict: i32,
+    }
+
+    unsafe extern "C" fn filter_handler(
+      p_ctx: *mut c_void,
+      z_tab: *const c_char,
+    ) -> i32 {
+      // SAFETY: `p_ctx` points to the `HandlerCtx` below and `z_tab` is a
+      // valid c-string.
+      let (ctx, table) = unsafe {
+        (
+          &*(p_ctx as *const HandlerCtx),
+          CStr::from_ptr(z_tab).to_bytes(),
+        )
+      };
+      ctx
+        .tables
+        .is_none_or(|tables| tables.iter().any(|t| t.as_bytes() == table))
+        as i32
+    }
+
+    unsafe extern "C" fn conflict_handler(
+      p_ctx: *mut c_void,
+      _: i32,
+      _: *mut ffi::sqlite3_changeset_iter,
+    ) -> i32 {
+      // SAFETY: `p_ctx` points to the `HandlerCtx` below.
+      unsafe { (*(p_ctx as *const HandlerCtx)).on_conflict }
+    }
+
+    let db = self.handle()?;
+    let mut ctx = HandlerCtx {
+      tables,
+      on_conflict,
+    };
+
+    // SAFETY: `changeset` points to a valid memory location and its
+    // length is correct. `ctx` outlives the call to
+    // `sqlite3changeset_apply()`.
+    let r = unsafe {
+      ffi::sqlite3changeset_apply(
+        db,
+        changeset.len() as i32,
+        changeset.as_ptr() as *mut _,
+        Some(filter_handler),
+        Some(conflict_handler),
+        &mut ctx as *mut _ as *mut c_void,
+      )
+    };
+
+    match r {
+      ffi::SQLITE_OK => Ok(true),
+      ffi::SQLITE_ABORT => Ok(false),
+      _ => check_error_code2(r).map(|_| false),
+    }
+  }
+
+  pub fn start_backup(
+    &mut self,
+    path: PathBuf,
+    source: &str,
+    target: &str,
+    rate: i32,
+  ) -> Result<u32, SqliteError> {
+    let db = self.handle()?;
+    let target_conn = open_backup_target(path)?;
+    let source = CString::new(source)?;
+    let target = CString::new(target)?;
+
+    // SAFETY: lifetime of the target connection is tied to the backup.
+    let target_handle = unsafe { target_conn.handle() };
+    // SAFETY: both handles are valid and the names are valid c-strings.
+    let raw = unsafe {
+      ffi::sqlite3_backup_init(
+        target_handle,
+        target.as_ptr(),
+        db,
+        source.as_ptr(),
+      )
+    };
+    if raw.is_null() {
+      return Err(last_error(target_handle));
+    }
+
+    let id = self.next_id();
+    self.backups.insert(
+      id,
+      RawBackup {
+        inner: raw,
+        rate,
+        target: target_conn,
+      },
+    );
+    Ok(id)
+  }
+
+  // Copies the next batch of pages of the backup `id`. The backup is
+  // finished once it is done or fails.
+  pub fn step_backup(
+    &mut self,
+    id: u32,
+  ) -> Result<BackupProgress, SqliteError> {
+    self.connection()?;
+    let backup = self.backups.get(&id).ok_or(SqliteError::AlreadyClosed)?;
+
+    // SAFETY: `backup.inner` is a valid backup handle.
+    let (r, progress) = unsafe {
+      let r = ffi::sqlite3_backup_step(backup.inner, backup.rate);
+      let progress = BackupProgress {
+        done: r == ffi::SQLITE_DONE,
+        total_pages: ffi::sqlite3_backup_pagecount(backup.inner),
+        remaining_pages: ffi::sqlite3_backup_remaining(backup.inner),
+      };
+      (r, progress)
+    };
+
+    match r {
+      ffi::SQLITE_OK | ffi::SQLITE_BUSY | ffi::SQLITE_LOCKED => Ok(progress),
+      ffi::SQLITE_DONE => {
+        self.backups.remove(&id);
+        Ok(progress)
+      }
+      _ => {
+        // SAFETY: the target connection is alive until the backup is
+        // removed below.
+        let err = last_error(unsafe { backup.target.handle() });
+        self.backups.remove(&id);
+        Err(err)
+      }
+    }
+  }
+
+  pub fn finish_backup(&mut self, id: u32) {
+    self.backups.remove(&id);
+  }
+}
+
+fn prepare_raw(
+  db: *mut ffi::sqlite3,
+  sql: &str,
+) -> Result<RawStatement, SqliteError> {
+  let mut raw_stmt = std::ptr::null_mut();
+  // SAFETY: `sql` points to a valid memory location and its length is
+  // correct.
+  let r = unsafe {
+    ffi::sqlite3_prepare_v2(
+      db,
+      sql.as_ptr() as *const _,
+      sql.len() as i32,
+      &mut raw_stmt,
+      std::ptr::null_mut(),
+    )
+  };
+  check_error_code(r, db)?;
+  Ok(RawStatement(raw_stmt))
+}
+
+// Builds the error for the most recent failed call on `db`.
+fn last_error(db: *mut ffi::sqlite3) -> SqliteError {
+  // SAFETY: `db` is a valid connection handle.
+  let code = unsafe { ffi::sqlite3_errcode(db) };
+  check_error_code(code, db)
+    .and_then(|_| check_error_code2(code))
+    .unwrap_err()
+}
+
+fn nullable_string(ptr: *const c_char) -> Option<String> {
+  if ptr.is_null() {
+    return None;
+  }
+  // SAFETY: `ptr` is a valid c-string owned by SQLite.
+  Some(
+    unsafe { CStr::from_ptr(ptr) }
+      .to_string_lossy()
+      .into_owned(),
+  )
+}
+
+fn column_info(stmt: *mut ffi::sqlite3_stmt) -> Vec<ColumnInfo> {
+  // SAFETY: `stmt` is a valid statement handle.
+  let count = unsafe { ffi::sqlite3_column_count(stmt) };
+  (0..count)
+    // SAFETY: `i` is a valid column index of `stmt`.
+    .map(|i| unsafe {
+      ColumnInfo {
+        name: nullable_string(ffi::sqlite3_column_name(stmt, i)),
+        column: nullable_string(ffi::sqlite3_column_origin_name(stmt, i)),
+        table: nullable_string(ffi::sqlite3_column_table_name(stmt, i)),
+        database: nullable_string(ffi::sqlite3_column_database_name(stmt, i)),
+        decl_type: nullable_string(ffi::sqlite3_column_decltype(stmt, i)),
+      }
+    })
+    .collect()
+}
+
+pub struct ColumnInfo {
+  pub name: Option<String>,
+  pub column: Option<String>,
+  pub table: Option<String>,
+  pub database: Option<String>,
+  pub decl_type: Option<String>,
+}
+
+pub struct StatementInfo {
+  pub id: u32,
+  pub source_sql: String,
+  pub columns: Vec<ColumnInfo>,
+}
+
+#[derive(Clone, Copy)]
+pub struct StatementOptions {
+  pub return_arrays: bool,
+  pub use_big_ints: bool,
+  pub allow_bare_named_params: bool,
+  pub allow_unknown_named_params: bool,
+}
+
+// A prepared statement borrowed from the connection thread for the
+// duration of a job.
+pub struct Query {
+  db: *mut ffi::sqlite3,
+  stmt: *mut ffi::sqlite3_stmt,
+}
+
+impl Query {
+  // Executes the statement and returns the first row.
+  pub fn get(
+    &self,
+    params: &StatementParams,
+    options: StatementOptions,
+  ) -> Result<Rows, SqliteError> {
+    self.reset();
+    let result = self
+      .bind(params, options)
+      .and_then(|_| self.read(1, options));
+    self.reset();
+    result.map(|(rows, _)| rows)
+  }
+
+  // Executes the statement and returns all rows.
+  pub fn all(
+    &self,
+    params: &StatementParams,
+    options: StatementOptions,
+  ) -> Result<Rows, SqliteError> {
+    self.reset();
+    let result = self
+      .bind(params, options)
+      .and_then(|_| self.read(usize::MAX, options));
+    self.reset();
+    result.map(|(rows, _)| rows)
+  }
+
+  // Executes the statement and summarizes the resulting changes.
+  pub fn run(
+    &self,
+    params: &StatementParams,
+    options: StatementOptions,
+  ) -> Result<RunStatementResult, SqliteError> {
+    self.reset();
+    let result = self.bind(params, options).and_then(|_| self.step());
+    // Reset before reading the changes so that they are up to date.
+    self.reset();
+    result?;
+
+    // SAFETY: `self.db` is a valid connection handle.
+    unsafe {
+      Ok(RunStatementResult {
+        last_insert_rowid: ffi::sqlite3_last_insert_rowid(self.db),
+        changes: ffi::sqlite3_changes64(self.db) as u64,
+        use_big_ints: options.use_big_ints,
+      })
+    }
+  }
+
+  // Binds `params` so that rows can be read with `next`.
+  pub fn iterate(
+    &self,
+    params: &StatementParams,
+    options: StatementOptions,
+  ) -> Result<(), SqliteError> {
+    self.reset();
+    self.bind(params, options)
+  }
+
+  // Reads up to `limit` rows. The statement is reset once all rows have
+  // been read.
+  pub fn next(
+    &self,
+    limit: usize,
+    options: StatementOptions,
+  ) -> Result<Rows, SqliteError> {
+    let result = self.read(limit, options);
+    if !matches!(result, Ok((_, false))) {
+      self.reset();
+    }
+    result.map(|(rows, _)| rows)
+  }
+
+  pub fn reset(&self) {
+    // SAFETY: `self.stmt` is a valid statement handle.
+    unsafe {
+      ffi::sqlite3_reset(self.stmt);
+    }
+  }
+
+  // Steps the statement, returning `true` once it is done.
+  fn step(&self) -> Result<bool, SqliteError> {
+    // SAFETY: `self.stmt` is a valid statement handle.
+    let r = unsafe { ffi::sqlite3_step(self.stmt) };
+    match r {
+      ffi::SQLITE_ROW => Ok(false),
+      ffi::SQLITE_DONE => Ok(true),
+      _ => check_error_code(r, self.db).map(|_| true),
+    }
+  }
+
+  // Reads up to `limit` rows, returning whether all rows have been read.
+  fn read(
+    &self,
+    limit: usize,
+    options: StatementOptions,
+  ) -> Result<(Rows, bool), SqliteError> {
+    let mut values = Vec::new();
+    let mut done = false;
+    while values.len() < limit {
+      if self.step()? {
+        done = true;
+        break;
+      }
+      values.push(self.row());
+    }
+
+    let rows = Rows {
+      columns: self.column_names(),
+      values,
+      return_arrays: options.return_arrays,
+      use_big_ints: options.use_big_ints,
+    };
+    Ok((rows, done))
+  }
+
+  fn column_names(&self) -> Vec<Vec<u8>> {
+    // SAFETY: `self.stmt` is a valid statement handle.
+    let count = unsafe { ffi::sqlite3_column_count(self.stmt) };
+    (0..count)
+      // SAFETY: `i` is a valid column index.
+      .map(|i| unsafe {
+        CStr::from_ptr(ffi::sqlite3_column_name(self.stmt, i))
+          .to_bytes()
+          .to_vec()
+      })
+      .collect()
+  }
+
+  fn row(&self) -> Vec<Value> {
+    // SAFETY: `self.stmt` is a valid statement handle positioned on a row.
+    let count = unsafe { ffi::sqlite3_column_count(self.stmt) };
+    (0..count)
+      // SAFETY: `i` is a valid column index.
+      .map(|i| unsafe { self.column_value(i) })
+      .collect()
+  }
+
+  unsafe fn column_value(&self, index: i32) -> Value {
+    let raw = self.stmt;
+    // SAFETY: `raw` is a valid statement handle positioned on a row and
+    // `index` is a valid column index.
+    unsafe {
+      match ffi::sqlite3_column_type(raw, index) {
+        ffi::SQLITE_INTEGER => {
+          Value::Integer(ffi::sqlite3_column_int64(raw, index))
+        }
+        ffi::SQLITE_FLOAT => {
+          Value::Real(ffi::sqlite3_column_double(raw, index))
+        }
+        ffi::SQLITE_TEXT => {
+          let value = ffi::sqlite3_column_text(raw, index);
+          let size = ffi::sqlite3_column_bytes(raw, index) as usize;
+          let value = if size == 0 {
+            &[]
+          } else {
+            std::slice::from_raw_parts(value, size)
+          };
+          Value::Text(String::from_utf8_lossy(value).into_owned())
+        }
+        ffi::SQLITE_BLOB => {
+          let value = ffi::sqlite3_column_blob(raw, index);
+          let size = ffi::sqlite3_column_bytes(raw, index) as usize;
+          if size == 0 {
+            Value::Blob(Vec::new())
+          } else {
+            Value::Blob(
+              std::slice::from_raw_parts(value as *const u8, size).to_vec(),
+            )
+          }
+        }
+        _ => Value::Null,
+      }
+    }
+  }
+
+  fn check(&self, r: i32) -> Result<(), SqliteError> {
+    check_error_code(r, self.db)
+  }
+
+  // Binds the parameters to the prepared statement, following the same
+  // rules as `StatementSync`.
+  fn bind(
+    &self,
+    params: &StatementParams,
+    options: StatementOptions,
+  ) -> Result<(), SqliteError> {
+    let raw = self.stmt;
+    // SAFETY: `raw` is a valid statement handle.
+    self.check(unsafe { ffi::sqlite3_clear_bindings(raw) })?;
+
+    if let Some(named) = &params.named {
+      // Allow specifying named parameters without the SQLite prefix
+      // character.
+      let mut bare_named_params = HashMap::new();
+      if options.allow_bare_named_params {
+        // SAFETY: `raw` is a valid statement handle.
+        let param_count = unsafe { ffi::sqlite3_bind_parameter_count(raw) };
+        for i in 1..=param_count {
+          // SAFETY: `raw` is a valid statement handle.
+          let Some(full_name) = nullable_string(unsafe {
+            ffi::sqlite3_bind_parameter_name(raw, i)
+          }) else {
+            continue;
+          };
+          let bare_name = full_name[1..].to_string();
+
+          if let Some(existing_index) =
+            bare_named_params.insert(bare_name.clone(), i)
+          {
+            let existing_full_name = nullable_string(
+              // SAFETY: `raw` is a valid statement handle.
+              unsafe { ffi::sqlite3_bind_parameter_name(raw, existing_index) },
+            )
+            .unwrap_or_default();
+            return Err(SqliteError::DuplicateNamedParameter(
+              bare_name,
+              existing_full_name,
+              full_name,
+            ));
+          }
+        }
+      }
+
+      for (key, value) in named {
+        let key_c = CString::new(key.as_str())?;
+        // SAFETY: `raw` is a valid statement handle.
+        let mut index =
+          unsafe { ffi::sqlite3_bind_parameter_index(raw, key_c.as_ptr()) };
+        if index == 0 {
+          index = bare_named_params.get(key).copied().unwrap_or(0);
+        }
+        if index == 0 {
+          if options.allow_unknown_named_params {
+            continue;
+          }
+          return Err(SqliteError::UnknownNamedParameter(key.clone()));
+        }
+
+        self.bind_value(value.as_ref(), index)?;
+      }
+    }
+
+    // SAFETY: `raw` is a valid statement handle.
+    let sql_param_count = unsafe { ffi::sqlite3_bind_parameter_count(raw) };
+    let mut positional_idx = 1;
+    for value in &params.positional {
+      // Skip named parameters (:name, $name, @name) but include anonymous
+      // (?) and numbered (?NNN) parameters.
+      while positional_idx <= sql_param_count {
+        // SAFETY: `raw` is a valid statement handle.
+        let name_ptr =
+          unsafe { ffi::sqlite3_bind_parameter_name(raw, positional_idx) };
+        if name_ptr.is_null()
+          // SAFETY: short-circuiting guarantees name_ptr is non-null here
+          || unsafe { *name_ptr as u8 == b'?' }
+        {
+          break;
+        }
+        positional_idx += 1;
+      }
+
+      self.bind_value(value.as_ref(), positional_idx)?;
+      positional_idx += 1;
+    }
+
+    Ok(())
+  }
+
+  fn bind_value(
+    &self,
+    value: Option<&Value>,
+    index: i32,
+  ) -> Result<(), SqliteError> {
+    let raw = self.stmt;
+    // SAFETY: `raw` is a valid statement handle. SQLITE_TRANSIENT makes
+    // SQLite copy text and blob values.
+    let r = unsafe {
+      match value {
+        None => return Err(SqliteError::InvalidBindType(index)),
+        Some(Value::Null) => ffi::sqlite3_bind_null(raw, index),
+        Some(Value::Integer(value)) => {
+          ffi::sqlite3_bind_int64(raw, index, *value)
+        }
+        Some(Value::Real(value)) => {
+          ffi::sqlite3_bind_double(raw, index, *value)
+        }
+        Some(Value::Text(value)) => ffi::sqlite3_bind_text(
+          raw,
+          index,
+          value.as_ptr() as *const _,
+          value.len() as i32,
+          ffi::SQLITE_TRANSIENT(),
+        ),
+        Some(Value::Blob(value)) => ffi::sqlite3_bind_blob(
+          raw,
+          index,
+          value.as_ptr() as *const _,
+          value.len() as i32,
+          ffi::SQLITE_TRANSIENT(),
+        ),
+      }
+    };
+
+    self.check(r)
+  }
+}
+
+// Parameters of a statement, converted from the array of call arguments
+// on the main thread so that they can be bound on the connection thread.
+//
+// Values that cannot be bound are kept as `None`, binding them fails with
+// the index of the parameter.
+#[derive(Default)]
+pub struct StatementParams {
+  named: Option<Vec<(String, Option<Value>)>>,
+  positional: Vec<Option<Value>>,
+}
+
+impl StatementParams {
+  pub fn positional(positional: Vec<Option<Value>>) -> Self {
+    StatementParams {
+      named: None,
+      positional,
+    }
+  }
+}
+
+impl<'a> FromV8<'a> for StatementParams {
+  type Error = SqliteError;
+
+  fn from_v8(
+    scope: &mut v8::PinScope<'a, '_>,
+    value: v8::Local<'a, v8::Value>,
+  ) -> Result<Self, SqliteError> {
+    let Ok(args) = v8::Local::<v8::Array>::try_from(value) else {
+      return Ok(Self::default());
+    };
+
+    let mut params = Self::default();
+    let mut anon_start = 0;
+
+    if args.length() > 0 {
+      let param0 = args.get_index(scope, 0).unwrap();
+      if param0.is_object() && !param0.is_array_buffer_view() {
+        let obj = v8::Local::<v8::Object>::try_from(param0).unwrap();
+        let keys = obj
+          .get_property_names(scope, GetPropertyNamesArgs::default())
+          .unwrap();
+
+        let mut named = Vec::with_capacity(keys.length() as usize);
+        for j in 0..keys.length() {
+          let key = keys.get_index(scope, j).unwrap();
+          let value = obj.get(scope, key).unwrap();
+          named.push((
+            key.to_rust_string_lossy(scope),
+            value_from_v8(scope, value)?,
+          ));
+        }
+
+        params.named = Some(named);
+        anon_start = 1;
+      }
+    }
+
+    for i in anon_start..args.length() {
+      let value = args.get_index(scope, i).unwrap();
+      params.positional.push(value_from_v8(scope, value)?);
+    }
+
+    Ok(params)
+  }
+}
+
+// Converts a JavaScript value to the SQLite value it is bound as.
+pub fn value_from_v8(
+  scope: &mut v8::PinScope<'_, '_>,
+  value: v8::Local<v8::Value>,
+) -> Result<Option<Value>, SqliteError> {
+  let value = if value.is_number() {
+    Value::Real(value.number_value(scope).unwrap())
+  } else if value.is_string() {
+    Value::Text(value.to_rust_string_lossy(scope))
+  } else if value.is_null() {
+    Value::Null
+  } else if value.is_array_buffer_view() {
+    let view = v8::Local::<v8::ArrayBufferView>::try_from(value).unwrap();
+    let mut data = vec![0; view.byte_length()];
+    view.copy_contents(&mut data);
+    Value::Blob(data)
+  } else if value.is_big_int() {
+    let value = v8::Local::<v8::BigInt>::try_from(value).unwrap();
+    let (as_int, lossless) = value.i64_value();
+    if !lossless {
+      return Err(SqliteError::InvalidBindValue(
+        "BigInt value is too large to bind",
+      ));
+    }
+    Value::Integer(as_int)
+  } else {
+    return Ok(None);
+  };
+
+  Ok(Some(value))
+}
+
+fn value_to_v8<'a>(
+  scope: &mut v8::PinScope<'a, '_>,
+  value: Value,
+  use_big_ints: bool,
+) -> Result<v8::Local<'a, v8::Value>, SqliteError> {
+  Ok(match value {
+    Value::Null => v8::null(scope).into(),
+    Value::Integer(value) => {
+      if use_big_ints {
+        v8::BigInt::new_from_i64(scope, value).into()
+      } else if (-MAX_SAFE_JS_INTEGER..=MAX_SAFE_JS_INTEGER).contains(&value) {
+        v8::Number::new(scope, value as f64).into()
+      } else {
+        return Err(SqliteError::NumberTooLarge(value));
+      }
+    }
+    Value::Real(value) => v8::Number::new(scope, value).into(),
+    Value::Text(value) => v8::String::new(scope, &value).unwrap().into(),
+    Value::Blob(value) => {
+      let size = value.len();
+      let ab = if size == 0 {
+        v8::ArrayBuffer::new(scope, 0)
+      } else {
+        let bs =
+          v8::ArrayBuffer::new_backing_store_from_vec(value).make_shared();
+        v8::ArrayBuffer::with_backing_store(scope, &bs)
+      };
+      v8::Uint8Array::new(scope, ab, 0, size).unwrap().into()
+    }
+  })
+}
+
+// Rows read on the connection thread. Converted to an array of objects,
+// or of arrays when `return_arrays` is set.
+pub struct Rows {
+  columns: Vec<Vec<u8>>,
+  values: Vec<Vec<Value>>,
+  return_arrays: bool,
+  use_big_ints: bool,
+}
+
+impl Rows {
+  fn into_v8_rows<'a>(
+    self,
+    scope: &mut v8::PinScope<'a, '_>,
+  ) -> Result<Vec<v8::Local<'a, v8::Value>>, SqliteError> {
+    let names = self
+      .columns
+      .iter()
+      .map(|name| {
+        v8::String::new_from_utf8(scope, name, v8::NewStringType::Normal)
+          .unwrap()
+          .into()
+      })
+      .collect::<Vec<v8::Local<v8::Name>>>();
+
+    let mut rows = Vec::with_capacity(self.values.len());
+    for row in self.values {
+      let mut values = Vec::with_capacity(row.len());
+      for value in row {
+        values.push(value_to_v8(scope, value, self.use_big_ints)?);
+      }
+
+      let row = if self.return_arrays {
+        v8::Array::new_with_elements(scope, &values).into()
+      } else {
+        let null = v8::null(scope).into();
+        v8::Object::with_prototype_and_properties(scope, null, &names, &values)
+          .into()
+      };
+      rows.push(row);
+    }
+
+    Ok(rows)
+  }
+}
+
+impl<'a> ToV8<'a> for Rows {
+  type Error = SqliteError;
+
+  fn to_v8(
+    self,
+    scope: &mut v8::PinScope<'a, '_>,
+  ) -> Result<v8::Local<'a, v8::Value>, SqliteError> {
+    let rows = self.into_v8_rows(scope)?;
+    Ok(v8::Array::new_with_elements(scope, &rows).into())
+  }
+}
+
+// The first row of a result, `undefined` when there is none.
+pub struct Row(pub Rows);
+
+impl<'a> ToV8<'a> for Row {
+  type Error = SqliteError;
+
+  fn to_v8(
+    self,
+    scope: &mut v8::PinScope<'a, '_>,
+  ) -> Result<v8::Local<'a, v8::Value>, SqliteError> {
+    let row = self.0.into_v8_rows(scope)?.into_iter().next();
+    Ok(row.unwrap_or_else(|| v8::undefined(scope).into()))
+  }
+}
+
+pub struct BackupProgress {
+  done: bool,
+  total_pages: i32,
+  remaining_pages: i32,
+}
+
+impl<'a> ToV8<'a> for BackupProgress {
+  type Error = SqliteError;
+
+  fn to_v8(
+    self,
+    scope: &mut v8::PinScope<'a, '_>,
+  ) -> Result<v8::Local<'a, v8::Value>, SqliteError> {
+    deno_core::v8_static_strings! {
+      DONE = "done",
+      TOTAL_PAGES = "totalPages",
+      REMAINING_PAGES = "remainingPages",
+    }
+
+    let names = &[
+      DONE.v8_string(scope).unwrap().into(),
+      TOTAL_PAGES.v8_string(scope).unwrap().into(),
+      REMAINING_PAGES.v8_string(scope).unwrap().into(),
+    ];
+    let values = &[
+      v8::Boolean::new(scope, self.done).into(),
+      v8::Integer::new(scope, self.total_pages).into(),
+      v8::Integer::new(scope, self.remaining_pages).into(),
+    ];
+    let null = v8::null(scope).into();
+    Ok(
+      v8::Object::with_prototype_and_properties(scope, null, names, values)
+        .into(),
+    )
+  }
+}
@@ -44,21 +44,21 @@ const SQLITE_DBCONFIG_ENABLE_LOAD_EXTENSION: i32 = 1005;
 const SQLITE_DBCONFIG_ENABLE_ATTACH_WRITE: i32 = 1021;
 const MAX_SAFE_JS_INTEGER: i64 = 9_007_199_254_740_991;
 
-struct DatabaseSyncOptions {
-  open: bool,
+pub(crate) struct DatabaseOptions {
+  pub(crate) open: bool,
   enable_foreign_key_constraints: bool,
   read_only: bool,
-  allow_extension: bool,
+  pub(crate) allow_extension: bool,
   enable_double_quoted_string_literals: bool,
-  use_big_int_arguments: bool,
-  allow_bare_named_params: bool,
-  return_arrays: bool,
-  allow_unknown_named_params: bool,
+  pub(crate) use_big_int_arguments: bool,
+  pub(crate) allow_bare_named_params: bool,
+  pub(crate) return_arrays: bool,
+  pub(crate) allow_unknown_named_params: bool,
   is_defensive_mode: bool,
   timeout: u64,
 }
 
-impl<'a> FromV8<'a> for DatabaseSyncOptions {
+impl<'a> FromV8<'a> for DatabaseOptions {
   type Error = validators::Error;
 
   fn from_v8(
@@ -264,9 +264,9 @@ impl<'a> FromV8<'a> for DatabaseSyncOptions {
   }
 }
 
-impl Default for DatabaseSyncOptions {
+impl Default for DatabaseOptions {
   fn default() -> Self {
-    DatabaseSyncOptions {
+    DatabaseOptions {
       open: true,
       enable_foreign_key_constraints: true,
       read_only: false,
@@ -513,7 +513,7 @@ impl<'a> ApplyChangesetOptions<'a> {
 pub struct DatabaseSync {
   pub conn: Rc<RefCell<Option<rusqlite::Connection>>>,
   statements: Rc<RefCell<Vec<InnerStatementPtr>>>,
-  options: DatabaseSyncOptions,
+  options: DatabaseOptions,
   location: String,
   ignore_next_sqlite_error: Rc<Cell<bool>>,
   authorizer_data: Rc<RefCell<Option<*mut AuthorizerData>>>,
@@ -528,7 +528,7 @@ unsafe impl GarbageCollected for DatabaseSync {
   }
 }
 
-fn set_db_config(
+pub(crate) fn set_db_config(
   conn: &rusqlite::Connection,
   config: i32,
   value: bool,
@@ -555,7 +555,7 @@ fn set_db_config(
 fn open_db(
   state: &mut OpState,
   location: &str,
-  options: &DatabaseSyncOptions,
+  options: &DatabaseOptions,
 ) -> Result<rusqlite::Connection, SqliteError> {
   let perms = state.borrow::<PermissionsContainer>();
   let disable_attach = perms
@@ -662,6 +662,35 @@ fn open_db(
   Ok(conn)
 }
 
+// Opens the database at `location` and applies the connection level
+// options. Shared by `DatabaseSync` and the asynchronous `Database`.
+pub(crate) fn open_connection(
+  state: &mut OpState,
+  location: &str,
+  options: &DatabaseOptions,
+) -> Result<rusqlite::Connection, SqliteError> {
+  let db = open_db(state, location, options)?;
+
+  if options.enable_foreign_key_constraints {
+    db.execute("PRAGMA foreign_keys = ON", [])?;
+  } else {
+    db.execute("PRAGMA foreign_keys = OFF", [])?;
+  }
+
+  set_db_config(
+    &db,
+    SQLITE_DBCONFIG_DQS_DDL,
+    options.enable_double_quoted_string_literals,
+  );
+  set_db_config(
+    &db,
+    SQLITE_DBCONFIG_DQS_DML,
+    options.enable_double_quoted_string_literals,
+  );
+
+  Ok(db)
+}
+
 fn is_open(
   scope: &mut v8::PinScope<'_, '_>,
   args: &v8::FunctionCallbackArguments,
@@ -692,28 +721,10 @@ impl DatabaseSync {
   fn new(
     state: &mut OpState,
     #[string] location: String,
-    #[scoped] options: DatabaseSyncOptions,
+    #[scoped] options: DatabaseOptions,
   ) -> Result<DatabaseSync, SqliteError> {
     let db = if options.open {
-      let db = open_db(state, &location, &options)?;
-
-      if options.enable_foreign_key_constraints {
-        db.execute("PRAGMA foreign_keys = ON", [])?;
-      } else {
-        db.execute("PRAGMA foreign_keys = OFF", [])?;
-      }
-
-      set_db_config(
-        &db,
-        SQLITE_DBCONFIG_DQS_DDL,
-        options.enable_double_quoted_string_literals,
-      );
-      set_db_config(
-        &db,
-        SQLITE_DBCONFIG_DQS_DML,
-        options.enable_double_quoted_string_literals,
-      );
-      Some(db)
+      Some(open_connection(state, &location, &options)?)
     } else {
       None
     };
@@ -740,24 +751,7 @@ impl DatabaseSync {
       return Err(SqliteError::AlreadyOpen);
     }
 
-    let db = open_db(state, &self.location, &self.options)?;
-    if self.options.enable_foreign_key_constraints {
-      db.execute("PRAGMA foreign_keys = ON", [])?;
-    } else {
-      db.execute("PRAGMA foreign_keys = OFF", [])?;
-    }
-
-    set_db_config(
-      &db,
-      SQLITE_DBCONFIG_DQS_DDL,
-      self.options.enable_double_quoted_string_literals,
-    );
-    set_db_config(
-      &db,
-      SQLITE_DBCONFIG_DQS_DML,
-      self.options.enable_double_quoted_string_literals,
-    );
-
+    let db = open_connection(state, &self.location, &self.options)?;
     *self.conn.borrow_mut() = Some(db);
 
     Ok(())
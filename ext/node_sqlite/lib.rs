@@ -1,6 +1,9 @@
 // Copyright 2018-2026 the Deno authors. MIT license.
 
+mod async_database;
+mod async_statement;
 mod backup;
+mod connection;
 mod database;
 mod lru_cache;
 mod session;
@@ -8,16 +11,57 @@ mod sql_tag_store;
 mod statement;
 mod validators;
 
+pub use async_database::Database;
+pub use async_statement::Statement;
+pub use async_statement::op_node_sqlite_statement_all;
+pub use async_statement::op_node_sqlite_statement_get;
+pub use async_statement::op_node_sqlite_statement_iterate;
+pub use async_statement::op_node_sqlite_statement_next;
+pub use async_statement::op_node_sqlite_statement_reset;
+pub use async_statement::op_node_sqlite_statement_run;
 pub use backup::op_node_database_backup;
+pub use backup::op_node_database_backup_finish;
+pub use backup::op_node_database_backup_init;
+pub use backup::op_node_database_backup_step;
 pub use database::DatabaseSync;
+pub use session::AsyncSession;
 pub use session::Session;
+pub use sql_tag_store::AsyncSQLTagStore;
 pub use sql_tag_store::SQLTagStore;
+pub use sql_tag_store::op_node_sqlite_tag_store_all;
+pub use sql_tag_store::op_node_sqlite_tag_store_get;
+pub use sql_tag_store::op_node_sqlite_tag_store_iterate;
+pub use sql_tag_store::op_node_sqlite_tag_store_run;
 pub use statement::StatementSync;
 
 deno_core::extension!(
   deno_node_sqlite,
-  ops = [op_node_database_backup,],
-  objects = [DatabaseSync, Session, SQLTagStore, StatementSync,],
+  ops = [
+    op_node_database_backup,
+    op_node_database_backup_init,
+    op_node_database_backup_step,
+    op_node_database_backup_finish,
+    op_node_sqlite_statement_run,
+    op_node_sqlite_statement_get,
+    op_node_sqlite_statement_all,
+    op_node_sqlite_statement_iterate,
+    op_node_sqlite_statement_next,
+    op_node_sqlite_statement_reset,
+    op_node_sqlite_tag_store_run,
+    op_node_sqlite_tag_store_get,
+    op_node_sqlite_tag_store_all,
+    op_node_sqlite_tag_store_iterate,
+  ],
+  objects = [
+    AsyncSession,
+    AsyncSQLTagStore,
+    Database,
+    DatabaseSync,
+    Session,
+    SQLTagStore,
+    Statement,
+    StatementSync,
+  ],
 );
 
 #[derive(Debug, thiserror::Error, deno_error::JsError)]
@@ -53,6 +53,12 @@ where
     }
   }
 
+  pub fn remove(&mut self, key: &K) -> Option<V> {
+    let value = self.map.remove(key)?;
+    self.order.retain(|k| k != key);
+    Some(value)
+  }
+
   pub fn erase(&mut self, key: &K) {
     if self.map.remove(key).is_some() {
       self.order.retain(|k| k != key);
@@ -166,6 +172,23 @@ mod tests {
     assert_eq!(cache.size(), 1);
   }
 
+  // Test that remove() returns the value and forgets the key
+  #[test]
+  fn remove() {
+    let mut cache = LRUCache::new(2);
+    cache.put(1, "one");
+    cache.put(2, "two");
+
+    assert_eq!(cache.remove(&1), Some("one"));
+    assert_eq!(cache.remove(&1), None);
+    assert_eq!(cache.size(), 1);
+
+    // The removed key no longer takes part in eviction
+    cache.put(3, "three");
+    assert!(cache.exists(&2));
+    assert!(cache.exists(&3));
+  }
+
   // Test the exists() method
   #[test]
   fn exists() {
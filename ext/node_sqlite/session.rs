@@ -13,6 +13,7 @@ use deno_core::v8_static_strings;
 use rusqlite::ffi;
 
 use super::SqliteError;
+use super::connection::ConnectionThread;
 use super::validators;
 
 #[derive(Default)]
@@ -176,7 +177,90 @@ impl Session {
   }
 }
 
-fn session_buffer_op(
+// A session of an asynchronous `Database`. The session itself lives on
+// the connection thread.
+pub struct AsyncSession {
+  thread: ConnectionThread,
+  id: u32,
+  closed: Cell<bool>,
+}
+
+// SAFETY: we're sure this can be GCed
+unsafe impl GarbageCollected for AsyncSession {
+  fn trace(&self, _visitor: &mut deno_core::v8::cppgc::Visitor) {}
+
+  fn get_name(&self) -> &'static std::ffi::CStr {
+    c"AsyncSession"
+  }
+}
+
+impl Drop for AsyncSession {
+  fn drop(&mut self) {
+    if !self.closed.get() {
+      let id = self.id;
+      self.thread.post(move |state| {
+        let _ = state.close_session(id);
+      });
+    }
+  }
+}
+
+impl AsyncSession {
+  pub(crate) fn new(thread: ConnectionThread, id: u32) -> Self {
+    AsyncSession {
+      thread,
+      id,
+      closed: Cell::new(false),
+    }
+  }
+
+  async fn buffer(&self, patchset: bool) -> Result<Box<[u8]>, SqliteError> {
+    if self.closed.get() {
+      return Err(SqliteError::SessionClosed);
+    }
+
+    let id = self.id;
+    self
+      .thread
+      .run(move |state| state.session_changes(id, patchset))
+      .await
+  }
+}
+
+#[op2]
+impl AsyncSession {
+  #[constructor]
+  #[cppgc]
+  fn create(_: bool) -> AsyncSession {
+    unreachable!()
+  }
+
+  // Closes the session.
+  async fn close(&self) -> Result<(), SqliteError> {
+    if self.closed.get() {
+      return Err(SqliteError::SessionClosed);
+    }
+
+    self.closed.set(true);
+    let id = self.id;
+    self.thread.run(move |state| state.close_session(id)).await
+  }
+
+  // Resolves to a changeset containing all changes since the session
+  // was created.
+  #[buffer]
+  async fn changeset(&self) -> Result<Box<[u8]>, SqliteError> {
+    self.buffer(false).await
+  }
+
+  // Similar to the method above, but generates a more compact patchset.
+  #[buffer]
+  async fn patchset(&self) -> Result<Box<[u8]>, SqliteError> {
+    self.buffer(true).await
+  }
+}
+
+pub(crate) fn session_buffer_op(
   s: *mut ffi::sqlite3_session,
   f: unsafe extern "C" fn(
     *mut ffi::sqlite3_session,
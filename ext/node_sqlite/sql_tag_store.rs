@@ -4,15 +4,26 @@ use std::cell::Cell;
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use deno_core::FromV8;
 use deno_core::GarbageCollected;
 use deno_core::op2;
 use deno_core::v8;
 use deno_core::v8_static_strings;
 use rusqlite::ffi as libsqlite3_sys;
+use rusqlite::types::Value;
 
 use super::SqliteError;
+use super::Statement;
+use super::connection::ConnectionThread;
+use super::connection::Query;
+use super::connection::Row;
+use super::connection::Rows;
+use super::connection::StatementOptions;
+use super::connection::StatementParams;
+use super::connection::value_from_v8;
 use super::lru_cache::LRUCache;
 use super::statement::InnerStatementPtr;
+use super::statement::RunStatementResult;
 use super::statement::StatementExecution;
 use super::statement::check_error_code;
 
@@ -617,3 +628,239 @@ impl SQLTagStore {
     self.cache.borrow_mut().clear();
   }
 }
+
+// A tagged template query: the SQL built from the template strings with
+// `?` placeholders for the interpolated values.
+pub struct TaggedQuery {
+  sql: String,
+  values: Vec<Option<Value>>,
+}
+
+impl<'a> FromV8<'a> for TaggedQuery {
+  type Error = SqliteError;
+
+  fn from_v8(
+    scope: &mut v8::PinScope<'a, '_>,
+    value: v8::Local<'a, v8::Value>,
+  ) -> Result<Self, SqliteError> {
+    let invalid_template = || {
+      SqliteError::Validation(super::validators::Error::InvalidArgType(
+        "First argument must be an array of strings (template literal).",
+      ))
+    };
+
+    let args = v8::Local::<v8::Array>::try_from(value)
+      .map_err(|_| invalid_template())?;
+    let strings = args
+      .get_index(scope, 0)
+      .and_then(|strings| v8::Local::<v8::Array>::try_from(strings).ok())
+      .ok_or_else(invalid_template)?;
+    let n_params = args.length() - 1;
+
+    let mut sql = String::new();
+    for i in 0..strings.length() {
+      let str_val = strings.get_index(scope, i).unwrap();
+      if !str_val.is_string() {
+        return Err(SqliteError::Validation(
+          super::validators::Error::InvalidArgType(
+            "Template literal parts must be strings.",
+          ),
+        ));
+      }
+      sql.push_str(&str_val.to_rust_string_lossy(scope));
+      if i < n_params {
+        sql.push('?');
+      }
+    }
+
+    let mut values = Vec::with_capacity(n_params as usize);
+    for i in 1..args.length() {
+      let value = args.get_index(scope, i).unwrap();
+      values.push(value_from_v8(scope, value)?);
+    }
+
+    Ok(TaggedQuery { sql, values })
+  }
+}
+
+// A store of prepared statements for tagged template queries of an
+// asynchronous `Database`. The cached statements live on the connection
+// thread, `size` mirrors the size of that cache.
+pub struct AsyncSQLTagStore {
+  thread: ConnectionThread,
+  id: u32,
+  capacity: u32,
+  size: Cell<usize>,
+  return_arrays: bool,
+  use_big_ints: bool,
+  db_object: v8::Global<v8::Object>,
+}
+
+// SAFETY: we're sure this can be GCed
+unsafe impl GarbageCollected for AsyncSQLTagStore {
+  fn trace(&self, _visitor: &mut deno_core::v8::cppgc::Visitor) {}
+
+  fn get_name(&self) -> &'static std::ffi::CStr {
+    c"AsyncSQLTagStore"
+  }
+}
+
+impl Drop for AsyncSQLTagStore {
+  fn drop(&mut self) {
+    let id = self.id;
+    self.thread.post(move |state| state.clear_tag_store(id));
+  }
+}
+
+impl AsyncSQLTagStore {
+  pub fn create(
+    thread: ConnectionThread,
+    id: u32,
+    capacity: u32,
+    return_arrays: bool,
+    use_big_ints: bool,
+    db_object: v8::Global<v8::Object>,
+  ) -> Self {
+    AsyncSQLTagStore {
+      thread,
+      id,
+      capacity,
+      size: Cell::new(0),
+      return_arrays,
+      use_big_ints,
+      db_object,
+    }
+  }
+
+  fn options(&self) -> StatementOptions {
+    StatementOptions {
+      return_arrays: self.return_arrays,
+      use_big_ints: self.use_big_ints,
+      allow_bare_named_params: false,
+      allow_unknown_named_params: false,
+    }
+  }
+
+  // Runs `f` with the cached statement of `query` on the connection
+  // thread.
+  async fn query<T: Send + 'static>(
+    &self,
+    query: TaggedQuery,
+    f: impl FnOnce(
+      &Query,
+      &StatementParams,
+      StatementOptions,
+    ) -> Result<T, SqliteError>
+    + Send
+    + 'static,
+  ) -> Result<T, SqliteError> {
+    let (id, capacity, options) =
+      (self.id, self.capacity as usize, self.options());
+    let (result, size) = self
+      .thread
+      .run(move |state| {
+        let params = StatementParams::positional(query.values);
+        state.tag_store_query(id, capacity, query.sql, |stmt| {
+          f(stmt, &params, options)
+        })
+      })
+      .await?;
+    self.size.set(size);
+    Ok(result)
+  }
+}
+
+#[op2]
+impl AsyncSQLTagStore {
+  #[constructor]
+  #[cppgc]
+  fn new(_: bool) -> Result<AsyncSQLTagStore, SqliteError> {
+    Err(SqliteError::InvalidConstructor)
+  }
+
+  #[fast]
+  #[number]
+  fn size(&self) -> u64 {
+    self.size.get() as u64
+  }
+
+  #[getter]
+  #[number]
+  fn capacity(&self) -> u64 {
+    self.capacity as u64
+  }
+
+  #[getter]
+  fn db(&self) -> v8::Global<v8::Object> {
+    self.db_object.clone()
+  }
+
+  #[fast]
+  #[undefined]
+  fn clear(&self) {
+    let id = self.id;
+    self.thread.post(move |state| state.clear_tag_store(id));
+    self.size.set(0);
+  }
+}
+
+#[op2]
+pub async fn op_node_sqlite_tag_store_run(
+  #[cppgc] store: &AsyncSQLTagStore,
+  #[scoped] query: TaggedQuery,
+) -> Result<RunStatementResult, SqliteError> {
+  store
+    .query(query, |stmt, params, options| stmt.run(params, options))
+    .await
+}
+
+#[op2]
+pub async fn op_node_sqlite_tag_store_get(
+  #[cppgc] store: &AsyncSQLTagStore,
+  #[scoped] query: TaggedQuery,
+) -> Result<Row, SqliteError> {
+  store
+    .query(query, |stmt, params, options| stmt.get(params, options))
+    .await
+    .map(Row)
+}
+
+#[op2]
+pub async fn op_node_sqlite_tag_store_all(
+  #[cppgc] store: &AsyncSQLTagStore,
+  #[scoped] query: TaggedQuery,
+) -> Result<Rows, SqliteError> {
+  store
+    .query(query, |stmt, params, options| stmt.all(params, options))
+    .await
+}
+
+// Prepares a statement for iterating over the rows of a tagged template
+// query and binds its values. It is not cached, so that concurrent
+// iterations of the same query do not share a cursor.
+#[op2]
+#[cppgc]
+pub async fn op_node_sqlite_tag_store_iterate(
+  #[cppgc] store: &AsyncSQLTagStore,
+  #[scoped] query: TaggedQuery,
+) -> Result<Statement, SqliteError> {
+  let options = store.options();
+  let info = store
+    .thread
+    .run(move |state| {
+      let info = state.prepare(&query.sql)?;
+      let params = StatementParams::positional(query.values);
+      state.query(info.id, |stmt| stmt.iterate(&params, options))?;
+      Ok(info)
+    })
+    .await?;
+
+  Ok(Statement::create(
+    store.thread.clone(),
+    info,
+    options.return_arrays,
+    options.use_big_ints,
+    options.allow_bare_named_params,
+    options.allow_unknown_named_params,
+  ))
+}
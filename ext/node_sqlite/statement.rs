@@ -16,12 +16,12 @@ use super::SqliteError;
 use super::validators;
 
 // ECMA-262, 15th edition, 21.1.2.6. Number.MAX_SAFE_INTEGER (2^53-1)
-const MAX_SAFE_JS_INTEGER: i64 = 9007199254740991;
+pub(crate) const MAX_SAFE_JS_INTEGER: i64 = 9007199254740991;
 
 pub struct RunStatementResult {
-  last_insert_rowid: i64,
-  changes: u64,
-  use_big_ints: bool,
+  pub(crate) last_insert_rowid: i64,
+  pub(crate) changes: u64,
+  pub(crate) use_big_ints: bool,
 }
 
 impl<'a> ToV8<'a> for RunStatementResult {
@@ -3,12 +3,13 @@
 import { core, primordials } from "ext:core/mod.js";
 import { op_signal_bind, op_signal_poll, op_signal_unbind } from "ext:core/ops";
 const {
-  SafeSet,
-  SafeSetIterator,
-  SetPrototypeAdd,
-  SetPrototypeDelete,
+  MapPrototypeDelete,
+  MapPrototypeSet,
+  SafeMap,
+  SafeMapIterator,
   TypeError,
 } = primordials;
+const { getAsyncContext, setAsyncContext } = core;
 
 function bindSignal(signo) {
   return op_signal_bind(signo);
@@ -25,13 +26,14 @@ function unbindSignal(rid) {
 }
 
 // Stores signal listeners and resource data. This has type of
-// `Record<string, { rid: number | undefined, listeners: Set<() => void> }`
+// `Record<string, { rid: number | undefined, listeners: Map<() => void, unknown> }`,
+// where each listener maps to the async context it was added in.
 const signalData = { __proto__: null };
 
 /** Gets the signal handlers and resource data of the given signal */
 function getSignalData(signo) {
   return signalData[signo] ??
-    (signalData[signo] = { rid: undefined, listeners: new SafeSet() });
+    (signalData[signo] = { rid: undefined, listeners: new SafeMap() });
 }
 
 function checkSignalListenerType(listener) {
@@ -46,7 +48,7 @@ function addSignalListener(signo, listener) {
   checkSignalListenerType(listener);
 
   const sigData = getSignalData(signo);
-  SetPrototypeAdd(sigData.listeners, listener);
+  MapPrototypeSet(sigData.listeners, listener, getAsyncContext());
 
   if (!sigData.rid) {
     // If signal resource doesn't exist, create it.
@@ -60,7 +62,7 @@ function removeSignalListener(signo, listener) {
   checkSignalListenerType(listener);
 
   const sigData = getSignalData(signo);
-  SetPrototypeDelete(sigData.listeners, listener);
+  MapPrototypeDelete(sigData.listeners, listener);
 
   if (sigData.listeners.size === 0 && sigData.rid) {
     unbindSignal(sigData.rid);
@@ -73,8 +75,18 @@ async function loop(sigData) {
     if (await pollSignal(sigData.rid)) {
       return;
     }
-    for (const listener of new SafeSetIterator(sigData.listeners)) {
-      listener();
+    for (
+      const { 0: listener, 1: asyncContext } of new SafeMapIterator(
+        sigData.listeners,
+      )
+    ) {
+      const oldContext = getAsyncContext();
+      try {
+        setAsyncContext(asyncContext);
+        listener();
+      } finally {
+        setAsyncContext(oldContext);
+      }
     }
   }
 }
@@ -7,6 +7,7 @@ import {
   op_run_status,
   op_spawn_child,
   op_spawn_kill,
+  op_spawn_pty_resize,
   op_spawn_sync,
   op_spawn_wait,
 } from "ext:core/ops";
@@ -40,6 +41,15 @@ import {
   ReadableStreamPrototype,
   writableStreamForRid,
 } from "ext:deno_web/06_streams.js";
+import {
+  builtinTracer,
+  COMMAND_TRACING_ENABLED,
+} from "ext:deno_telemetry/telemetry.ts";
+import {
+  traceAsync,
+  traceSync,
+  updateSpanFromError,
+} from "ext:deno_telemetry/util.ts";
 
 // The key for private `input` option for `Deno.Command`
 const kInputOption = Symbol("kInputOption");
@@ -180,6 +190,13 @@ function spawnChildInner(command, apiName, {
   stderr = "piped",
   windowsRawArguments = false,
   detached = false,
+  pty = undefined,
+  rlimits = undefined,
+  timeout = undefined,
+  killSignal = undefined,
+  killGracePeriod = undefined,
+  processGroup = false,
+  sandbox = undefined,
   [kSerialization]: serialization = "json",
   [kExtraStdio]: extraStdio = [],
   [kIpc]: ipc = -1,
@@ -202,6 +219,13 @@ function spawnChildInner(command, apiName, {
     extraStdio,
     detached,
     needsNpmProcessState,
+    pty: normalizePtyOptions(pty),
+    rlimits,
+    timeout,
+    killSignal,
+    killGracePeriod,
+    processGroup,
+    sandbox: normalizeSandboxOptions(sandbox),
   }, apiName);
   return new ChildProcess(illegalConstructorKey, {
     ...child,
@@ -209,6 +233,74 @@ function spawnChildInner(command, apiName, {
   });
 }
 
+function normalizeSandboxOptions(sandbox) {
+  if (!sandbox) {
+    return undefined;
+  }
+  if (sandbox === true) {
+    return {};
+  }
+  return {
+    read: sandbox.read && ArrayPrototypeMap(sandbox.read, pathFromURL),
+    write: sandbox.write && ArrayPrototypeMap(sandbox.write, pathFromURL),
+    net: sandbox.net,
+  };
+}
+
+const DEFAULT_PTY_ROWS = 24;
+const DEFAULT_PTY_COLS = 80;
+
+function normalizePtyOptions(pty) {
+  if (!pty) {
+    return undefined;
+  }
+  if (pty === true) {
+    return { rows: DEFAULT_PTY_ROWS, cols: DEFAULT_PTY_COLS };
+  }
+  return {
+    rows: pty.rows ?? DEFAULT_PTY_ROWS,
+    cols: pty.cols ?? DEFAULT_PTY_COLS,
+  };
+}
+
+class ChildPty {
+  #rid;
+  #readable;
+  #writable;
+
+  constructor(key = null, rid) {
+    if (key !== illegalConstructorKey) {
+      throw new TypeError("Illegal constructor");
+    }
+    this.#rid = rid;
+    this.#readable = readableStreamForRidUnrefable(rid);
+    // Both streams share the master side of the pty, which is closed once
+    // the readable side reaches EOF. Closing the writable side must not take
+    // the readable side down with it.
+    this.#writable = writableStreamForRid(rid, false);
+  }
+
+  get readable() {
+    return this.#readable;
+  }
+
+  get writable() {
+    return this.#writable;
+  }
+
+  resize(rows, cols) {
+    op_spawn_pty_resize(this.#rid, rows, cols);
+  }
+
+  ref() {
+    readableStreamForRidUnrefableRef(this.#readable);
+  }
+
+  unref() {
+    readableStreamForRidUnrefableUnref(this.#readable);
+  }
+}
+
 function spawnChild(command, options = { __proto__: null }) {
   return spawnChildInner(
     command,
@@ -282,6 +374,14 @@ class ChildProcess {
     return this.#stderr;
   }
 
+  #pty = null;
+  get pty() {
+    if (this.#pty == null) {
+      throw new TypeError("Cannot get 'pty': 'pty' is not enabled");
+    }
+    return this.#pty;
+  }
+
   constructor(key = null, {
     signal,
     rid,
@@ -291,6 +391,7 @@ class ChildProcess {
     stderrRid,
     ipcPipeRid, // internal
     extraPipeRids,
+    ptyRid,
   } = null) {
     if (key !== illegalConstructorKey) {
       throw new TypeError("Illegal constructor");
@@ -322,6 +423,10 @@ class ChildProcess {
       );
     }
 
+    if (ptyRid !== null) {
+      this.#pty = new ChildPty(illegalConstructorKey, ptyRid);
+    }
+
     const onAbort = () => {
       try {
         this.kill("SIGTERM");
@@ -401,12 +506,14 @@ class ChildProcess {
     core.refOpPromise(this.#waitPromise);
     if (this.#stdout) readableStreamForRidUnrefableRef(this.#stdout);
     if (this.#stderr) readableStreamForRidUnrefableRef(this.#stderr);
+    this.#pty?.ref();
   }
 
   unref() {
     core.unrefOpPromise(this.#waitPromise);
     if (this.#stdout) readableStreamForRidUnrefableUnref(this.#stdout);
     if (this.#stderr) readableStreamForRidUnrefableUnref(this.#stderr);
+    this.#pty?.unref();
   }
 }
 
@@ -461,6 +568,12 @@ function spawnSyncInner(command, {
   stdout = "piped",
   stderr = "piped",
   windowsRawArguments = false,
+  rlimits = undefined,
+  timeout = undefined,
+  killSignal = undefined,
+  killGracePeriod = undefined,
+  processGroup = false,
+  sandbox = undefined,
   [kInputOption]: input,
   [kNeedsNpmProcessState]: needsNpmProcessState = false,
 } = { __proto__: null }) {
@@ -485,6 +598,12 @@ function spawnSyncInner(command, {
     detached: false,
     needsNpmProcessState,
     input,
+    rlimits,
+    timeout,
+    killSignal,
+    killGracePeriod,
+    processGroup,
+    sandbox: normalizeSandboxOptions(sandbox),
   });
   return {
     success: result.status.success,
@@ -505,6 +624,25 @@ function spawnSyncInner(command, {
   };
 }
 
+const COMMAND_SPAN_NAME = "deno.command";
+
+function commandSpanAttributes(command) {
+  return { "process.executable.name": String(pathFromURL(command)) };
+}
+
+function updateSpanFromCommandStatus(span, status) {
+  span.setAttribute("process.exit.code", status.code);
+  if (!status.success) {
+    span.setAttribute("error.type", status.signal ?? String(status.code));
+    span.setStatus({
+      code: 2,
+      message: status.signal
+        ? `Process exited with signal ${status.signal}`
+        : `Process exited with code ${status.code}`,
+    });
+  }
+}
+
 class Command {
   #command;
   #options;
@@ -515,24 +653,71 @@ class Command {
   }
 
   output() {
+    if (this.#options?.pty) {
+      throw new TypeError(
+        "'pty' is not supported for this function, use 'Deno.Command.spawn()' instead",
+      );
+    }
     if (this.#options?.stdin === "piped") {
       throw new TypeError(
         "Piped stdin is not supported for this function, use 'Deno.Command.spawn()' instead",
       );
     }
+    if (COMMAND_TRACING_ENABLED) {
+      return traceAsync(
+        COMMAND_SPAN_NAME,
+        2,
+        commandSpanAttributes(this.#command),
+        (span) =>
+          PromisePrototypeThen(
+            spawnInner(this.#command, this.#options),
+            (output) => {
+              updateSpanFromCommandStatus(span, output);
+              return output;
+            },
+          ),
+      );
+    }
     return spawnInner(this.#command, this.#options);
   }
 
   outputSync() {
+    if (this.#options?.pty) {
+      throw new TypeError(
+        "'pty' is not supported for this function, use 'Deno.Command.spawn()' instead",
+      );
+    }
     if (this.#options?.stdin === "piped") {
       throw new TypeError(
         "Piped stdin is not supported for this function, use 'Deno.Command.spawn()' instead",
       );
     }
+    if (COMMAND_TRACING_ENABLED) {
+      return traceSync(
+        COMMAND_SPAN_NAME,
+        2,
+        commandSpanAttributes(this.#command),
+        (span) => {
+          const output = spawnSyncInner(this.#command, this.#options);
+          updateSpanFromCommandStatus(span, output);
+          return output;
+        },
+      );
+    }
     return spawnSyncInner(this.#command, this.#options);
   }
 
   spawn() {
+    if (
+      this.#options?.pty &&
+      (this.#options.stdin !== undefined ||
+        this.#options.stdout !== undefined ||
+        this.#options.stderr !== undefined)
+    ) {
+      throw new TypeError(
+        "Cannot set 'stdin', 'stdout' or 'stderr' when 'pty' is enabled",
+      );
+    }
     const options = {
       __proto__: null,
       ...(this.#options ?? {}),
@@ -540,7 +725,33 @@ class Command {
       stderr: this.#options?.stderr ?? "inherit",
       stdin: this.#options?.stdin ?? "inherit",
     };
-    return spawnChild(this.#command, options);
+    if (!COMMAND_TRACING_ENABLED) {
+      return spawnChild(this.#command, options);
+    }
+
+    // The span covers the lifetime of the subprocess, so it ends once the
+    // child exits rather than when `spawn()` returns.
+    const span = builtinTracer().startSpan(COMMAND_SPAN_NAME, {
+      kind: 2,
+      attributes: commandSpanAttributes(this.#command),
+    });
+    let child;
+    try {
+      child = spawnChild(this.#command, options);
+    } catch (error) {
+      updateSpanFromError(span, error);
+      span.end();
+      throw error;
+    }
+    span.setAttribute("process.pid", child.pid);
+    PromisePrototypeThen(child.status, (status) => {
+      updateSpanFromCommandStatus(span, status);
+      span.end();
+    }, (error) => {
+      updateSpanFromError(span, error);
+      span.end();
+    });
+    return child;
   }
 }
 
@@ -17,6 +17,7 @@ use std::process::ExitStatus;
 #[cfg(unix)]
 use std::process::Stdio as StdStdio;
 use std::rc::Rc;
+use std::sync::Arc;
 
 use deno_core::AsyncMutFuture;
 use deno_core::AsyncRefCell;
@@ -30,6 +31,7 @@ use deno_core::convert::Uint8Array;
 use deno_core::op2;
 use deno_core::serde_json;
 use deno_error::JsErrorBox;
+use deno_features::FeatureChecker;
 use deno_io::ChildStderrResource;
 use deno_io::ChildStdinResource;
 use deno_io::ChildStdoutResource;
@@ -54,8 +56,27 @@ use ipc::IpcAdvancedStreamResource;
 use ipc::IpcJsonStreamResource;
 use ipc::IpcRefTracker;
 
+mod limits;
+use limits::KillTimer;
+use limits::ResourceLimits;
+use limits::TimeoutOptions;
+
+pub mod pty;
+use pty::PtyOptions;
+use pty::PtyResource;
+
+mod sandbox;
+use sandbox::SandboxArgs;
+
 pub const UNSTABLE_FEATURE_NAME: &str = "process";
 
+/// Helper for checking unstable features.
+fn check_unstable(state: &OpState, api_name: &str) {
+  state
+    .borrow::<Arc<FeatureChecker>>()
+    .check_or_exit(UNSTABLE_FEATURE_NAME, api_name);
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Stdio {
@@ -165,6 +186,7 @@ deno_core::extension!(
     op_spawn_wait,
     op_spawn_sync,
     op_spawn_kill,
+    op_spawn_pty_resize,
     deprecated::op_run,
     deprecated::op_run_status,
     deprecated::op_kill,
@@ -176,9 +198,31 @@ deno_core::extension!(
   },
 );
 
-/// Second member stores the pid separately from the RefCell. It's needed for
-/// `op_spawn_kill`, where the RefCell is borrowed mutably by `op_spawn_wait`.
-struct ChildResource(RefCell<AsyncChild>, u32);
+struct ChildResource {
+  child: RefCell<AsyncChild>,
+  /// Stored separately from the RefCell. It's needed for `op_spawn_kill`,
+  /// where the RefCell is borrowed mutably by `op_spawn_wait`.
+  pid: u32,
+  /// Whether signals are sent to the child's whole process group.
+  process_group: bool,
+  _kill_timer: Option<KillTimer>,
+}
+
+impl ChildResource {
+  /// The pid to pass to `kill(2)`, which is negated to signal the process
+  /// group.
+  fn kill_pid(&self) -> i32 {
+    kill_pid(self.pid, self.process_group)
+  }
+}
+
+fn kill_pid(pid: u32, process_group: bool) -> i32 {
+  if process_group {
+    -(pid as i32)
+  } else {
+    pid as i32
+  }
+}
 
 impl Resource for ChildResource {
   fn name(&self) -> Cow<'_, str> {
@@ -212,6 +256,29 @@ pub struct SpawnArgs {
   extra_stdio: Vec<Stdio>,
   detached: bool,
   needs_npm_process_state: bool,
+  pty: Option<PtyOptions>,
+  rlimits: Option<ResourceLimits>,
+  timeout: Option<u64>,
+  kill_signal: Option<SignalArg>,
+  kill_grace_period: Option<u64>,
+  #[serde(default)]
+  process_group: bool,
+  sandbox: Option<SandboxArgs>,
+}
+
+impl SpawnArgs {
+  fn timeout_options(&self) -> Result<Option<TimeoutOptions>, ProcessError> {
+    self
+      .timeout
+      .map(|timeout| {
+        TimeoutOptions::new(
+          timeout,
+          self.kill_signal.clone(),
+          self.kill_grace_period,
+        )
+      })
+      .transpose()
+  }
 }
 
 #[derive(Deserialize)]
@@ -278,6 +345,9 @@ pub enum ProcessError {
   Permission(#[from] deno_permissions::PermissionCheckError),
   #[class(inherit)]
   #[error(transparent)]
+  PartiallyDenied(#[from] deno_permissions::PartiallyDeniedError),
+  #[class(inherit)]
+  #[error(transparent)]
   RunPermission(#[from] CheckRunPermissionError),
   #[class(inherit)]
   #[error(transparent)]
@@ -303,6 +373,9 @@ pub enum ProcessError {
   #[class(type)]
   #[error("Missing cmd")]
   MissingCmd, // only for Deno.run
+  #[class("NotSupported")]
+  #[error("{0} is not supported on this platform")]
+  NotSupported(&'static str),
 }
 
 #[derive(Deserialize)]
@@ -365,6 +438,7 @@ type CreateCommand = (
   Option<ResourceId>,
   Vec<Option<ResourceId>>,
   Vec<deno_io::RawBiPipeHandle>,
+  Option<ResourceId>,
 );
 
 pub fn npm_process_state_tempfile(
@@ -412,6 +486,28 @@ fn create_command(
   mut args: SpawnArgs,
   api_name: &str,
 ) -> Result<CreateCommand, ProcessError> {
+  if args.pty.is_some() {
+    check_unstable(state, "Deno.CommandOptions.pty");
+  }
+  if args.rlimits.is_some() {
+    check_unstable(state, "Deno.CommandOptions.rlimits");
+  }
+  if args.timeout.is_some() {
+    check_unstable(state, "Deno.CommandOptions.timeout");
+  }
+  if args.kill_signal.is_some() {
+    check_unstable(state, "Deno.CommandOptions.killSignal");
+  }
+  if args.kill_grace_period.is_some() {
+    check_unstable(state, "Deno.CommandOptions.killGracePeriod");
+  }
+  if args.process_group {
+    check_unstable(state, "Deno.CommandOptions.processGroup");
+  }
+  if args.sandbox.is_some() {
+    check_unstable(state, "Deno.CommandOptions.sandbox");
+  }
+
   let maybe_npm_process_state = if args.needs_npm_process_state {
     let provider = state.borrow::<NpmProcessStateProviderRc>();
     let process_state = provider.get_npm_process_state();
@@ -433,6 +529,26 @@ fn create_command(
     state,
     api_name,
   )?;
+
+  #[cfg(target_os = "linux")]
+  let sandbox = match args.sandbox.take() {
+    Some(sandbox_args) => Some(
+      sandbox::Sandbox::resolve(
+        sandbox_args,
+        state.borrow::<PermissionsContainer>(),
+        &cmd,
+        &run_env.cwd,
+        api_name,
+      )?
+      .prepare()?,
+    ),
+    None => None,
+  };
+  #[cfg(not(target_os = "linux"))]
+  if args.sandbox.is_some() {
+    return Err(ProcessError::NotSupported("Sandboxing subprocesses"));
+  }
+
   let mut command = Command::new(cmd);
 
   #[cfg(windows)]
@@ -463,22 +579,43 @@ fn create_command(
     command.uid(uid);
   }
 
-  if args.stdio.stdin.is_ipc() {
-    args.ipc = Some(0);
-  } else if args.input.is_some() {
-    command.stdin(StdStdio::piped());
-  } else {
-    command.stdin(args.stdio.stdin.as_stdio(state)?);
+  let rlimits = args.rlimits.unwrap_or_default();
+  #[cfg(windows)]
+  if !rlimits.is_empty() {
+    return Err(ProcessError::NotSupported("Setting resource limits"));
+  }
+  #[cfg(windows)]
+  if args.process_group {
+    return Err(ProcessError::NotSupported("Spawning in a process group"));
   }
 
-  command.stdout(match args.stdio.stdout {
-    StdioOrRid::Stdio(Stdio::Inherit) => StdioOrRid::Rid(1).as_stdio(state)?,
-    value => value.as_stdio(state)?,
-  });
-  command.stderr(match args.stdio.stderr {
-    StdioOrRid::Stdio(Stdio::Inherit) => StdioOrRid::Rid(2).as_stdio(state)?,
-    value => value.as_stdio(state)?,
-  });
+  let pty_rid = match args.pty {
+    Some(options) => Some(open_pty(state, &mut command, options)?),
+    None => None,
+  };
+
+  if pty_rid.is_none() {
+    if args.stdio.stdin.is_ipc() {
+      args.ipc = Some(0);
+    } else if args.input.is_some() {
+      command.stdin(StdStdio::piped());
+    } else {
+      command.stdin(args.stdio.stdin.as_stdio(state)?);
+    }
+
+    command.stdout(match args.stdio.stdout {
+      StdioOrRid::Stdio(Stdio::Inherit) => {
+        StdioOrRid::Rid(1).as_stdio(state)?
+      }
+      value => value.as_stdio(state)?,
+    });
+    command.stderr(match args.stdio.stderr {
+      StdioOrRid::Stdio(Stdio::Inherit) => {
+        StdioOrRid::Rid(2).as_stdio(state)?
+      }
+      value => value.as_stdio(state)?,
+    });
+  }
 
   #[cfg(unix)]
   // TODO(bartlomieju):
@@ -553,11 +690,32 @@ fn create_command(
     }
 
     let detached = args.detached;
-    if detached || !fds_to_dup.is_empty() || args.gid.is_some() {
+    let has_pty = pty_rid.is_some();
+    #[cfg(target_os = "linux")]
+    let has_sandbox = sandbox.is_some();
+    #[cfg(not(target_os = "linux"))]
+    let has_sandbox = false;
+    // A new session is also a new process group, and setsid(2) fails for a
+    // process that's already a group leader.
+    if args.process_group && !detached && !has_pty {
+      command.process_group(0);
+    }
+    if detached
+      || has_pty
+      || has_sandbox
+      || !rlimits.is_empty()
+      || !fds_to_dup.is_empty()
+      || args.gid.is_some()
+    {
       command.pre_exec(move || {
-        if detached {
+        if detached || has_pty {
           libc::setsid();
         }
+        if has_pty {
+          // stdin is the slave side of the pty at this point, make it the
+          // controlling terminal of the new session.
+          libc::ioctl(0, libc::TIOCSCTTY as _, 0);
+        }
         for &(src, dst) in &fds_to_dup {
           if src >= 0 && dst >= 0 {
             let _fd = libc::dup2(src, dst);
@@ -565,11 +723,19 @@ fn create_command(
           }
         }
         libc::setgroups(0, std::ptr::null());
+        // Limits such as `RLIMIT_NOFILE` must not get in the way of setting up
+        // the child's fds above.
+        rlimits.apply()?;
+        // Confine the child last, right before it execs.
+        #[cfg(target_os = "linux")]
+        if let Some(sandbox) = &sandbox {
+          sandbox.apply()?;
+        }
         Ok(())
       });
     }
 
-    Ok((command, ipc_rid, extra_pipe_rids, fds_to_close))
+    Ok((command, ipc_rid, extra_pipe_rids, fds_to_close, pty_rid))
   }
 
   #[cfg(windows)]
@@ -646,10 +812,34 @@ fn create_command(
       }
     }
 
-    Ok((command, ipc_rid, extra_pipe_rids, handles_to_close))
+    Ok((command, ipc_rid, extra_pipe_rids, handles_to_close, pty_rid))
   }
 }
 
+/// Allocates a pseudo-terminal and makes its slave side the child's stdin,
+/// stdout and stderr, returning the resource id of the master side.
+#[cfg(unix)]
+fn open_pty(
+  state: &mut OpState,
+  command: &mut Command,
+  options: PtyOptions,
+) -> Result<ResourceId, ProcessError> {
+  let (pty, slave) = PtyResource::open(options)?;
+  command.stdin(slave.try_clone()?);
+  command.stdout(slave.try_clone()?);
+  command.stderr(slave);
+  Ok(state.resource_table.add(pty))
+}
+
+#[cfg(windows)]
+fn open_pty(
+  _state: &mut OpState,
+  _command: &mut Command,
+  _options: PtyOptions,
+) -> Result<ResourceId, ProcessError> {
+  Err(ProcessError::NotSupported("Spawning in a pseudo-terminal"))
+}
+
 #[derive(ToV8)]
 struct Child {
   rid: ResourceId,
@@ -659,14 +849,19 @@ struct Child {
   stderr_rid: Option<ResourceId>,
   ipc_pipe_rid: Option<ResourceId>,
   extra_pipe_rids: Vec<Option<ResourceId>>,
+  pty_rid: Option<ResourceId>,
 }
 
+#[allow(clippy::too_many_arguments)]
 fn spawn_child(
   state: &mut OpState,
   command: Command,
   ipc_pipe_rid: Option<ResourceId>,
   extra_pipe_rids: Vec<Option<ResourceId>>,
+  pty_rid: Option<ResourceId>,
   detached: bool,
+  process_group: bool,
+  timeout: Option<TimeoutOptions>,
 ) -> Result<Child, ProcessError> {
   #[cfg(windows)]
   let mut command = command;
@@ -771,9 +966,14 @@ fn spawn_child(
     .transpose()?
     .map(|stderr| state.resource_table.add(ChildStderrResource::from(stderr)));
 
-  let child_rid = state
-    .resource_table
-    .add(ChildResource(RefCell::new(child), pid));
+  let kill_timer = timeout
+    .map(|timeout| KillTimer::start(kill_pid(pid, process_group), timeout));
+  let child_rid = state.resource_table.add(ChildResource {
+    child: RefCell::new(child),
+    pid,
+    process_group,
+    _kill_timer: kill_timer,
+  });
 
   Ok(Child {
     rid: child_rid,
@@ -783,6 +983,7 @@ fn spawn_child(
     stderr_rid,
     ipc_pipe_rid,
     extra_pipe_rids,
+    pty_rid,
   })
 }
 
@@ -1021,9 +1222,20 @@ fn op_spawn_child(
   #[string] api_name: String,
 ) -> Result<Child, ProcessError> {
   let detached = args.detached;
-  let (command, pipe_rid, extra_pipe_rids, handles_to_close) =
+  let process_group = args.process_group;
+  let timeout = args.timeout_options()?;
+  let (command, pipe_rid, extra_pipe_rids, handles_to_close, pty_rid) =
     create_command(state, args, &api_name)?;
-  let child = spawn_child(state, command, pipe_rid, extra_pipe_rids, detached);
+  let child = spawn_child(
+    state,
+    command,
+    pipe_rid,
+    extra_pipe_rids,
+    pty_rid,
+    detached,
+    process_group,
+    timeout,
+  );
   for handle in handles_to_close {
     deno_io::close_raw_handle(handle);
   }
@@ -1042,7 +1254,7 @@ async fn op_spawn_wait(
     .get::<ChildResource>(rid)
     .map_err(ProcessError::Resource)?;
   let result = resource
-    .0
+    .child
     .try_borrow_mut()
     .map_err(ProcessError::BorrowMut)?
     .wait()
@@ -1062,13 +1274,22 @@ fn op_spawn_sync(
   let stdout = matches!(args.stdio.stdout, StdioOrRid::Stdio(Stdio::Piped));
   let stderr = matches!(args.stdio.stderr, StdioOrRid::Stdio(Stdio::Piped));
   let input = args.input.clone();
-  let (mut command, _, _, _) =
+  let process_group = args.process_group;
+  let timeout = args.timeout_options()?;
+  let (mut command, _, _, _, _) =
     create_command(state, args, "Deno.Command().outputSync()")?;
 
   let mut child = command.spawn().map_err(|e| ProcessError::SpawnFailed {
     command: command.get_program().to_string_lossy().into_owned(),
     error: Box::new(e.into()),
   })?;
+  #[cfg(unix)]
+  let pid = child.id();
+  #[cfg(windows)]
+  let pid = child.id().expect("Process ID should be set.");
+  // Dropped once `wait_with_output` returns, which cancels the timer.
+  let _kill_timer = timeout
+    .map(|timeout| KillTimer::start(kill_pid(pid, process_group), timeout));
   if let Some(input) = input {
     let mut stdin = child.stdin.take().ok_or_else(|| {
       ProcessError::Io(std::io::Error::other("stdin is not available"))
@@ -1098,7 +1319,7 @@ fn op_spawn_sync(
   })
 }
 
-#[derive(serde::Deserialize)]
+#[derive(Clone, serde::Deserialize)]
 #[serde(untagged)]
 enum SignalArg {
   String(String),
@@ -1112,12 +1333,27 @@ fn op_spawn_kill(
   #[serde] signal: SignalArg,
 ) -> Result<(), ProcessError> {
   if let Ok(child_resource) = state.resource_table.get::<ChildResource>(rid) {
-    deprecated::kill(child_resource.1 as i32, &signal)?;
+    deprecated::kill(child_resource.kill_pid(), &signal)?;
     return Ok(());
   }
   Err(ProcessError::ChildProcessAlreadyTerminated)
 }
 
+#[op2(fast)]
+fn op_spawn_pty_resize(
+  state: &mut OpState,
+  #[smi] rid: ResourceId,
+  #[smi] rows: u16,
+  #[smi] cols: u16,
+) -> Result<(), ProcessError> {
+  let pty = state
+    .resource_table
+    .get::<PtyResource>(rid)
+    .map_err(ProcessError::Resource)?;
+  pty.resize(PtyOptions { rows, cols })?;
+  Ok(())
+}
+
 mod deprecated {
   use deno_core::FromV8;
   #[cfg(windows)]
@@ -0,0 +1,164 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+use std::sync::mpsc;
+use std::time::Duration;
+
+use deno_os::SignalError;
+use serde::Deserialize;
+
+use crate::ProcessError;
+use crate::SignalArg;
+
+/// Used when a timeout is set without a `killGracePeriod`.
+const DEFAULT_KILL_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+/// Limits applied to a child process with `setrlimit(2)` before it execs.
+///
+/// Both the soft and the hard limit are set, so the child can't raise them
+/// again.
+#[derive(Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(not(unix), allow(dead_code))]
+pub struct ResourceLimits {
+  /// CPU time in seconds (`RLIMIT_CPU`).
+  cpu: Option<u64>,
+  /// Size of the virtual address space in bytes (`RLIMIT_AS`).
+  address_space: Option<u64>,
+  /// Number of open file descriptors (`RLIMIT_NOFILE`).
+  open_files: Option<u64>,
+  /// Number of processes for the child's user (`RLIMIT_NPROC`).
+  processes: Option<u64>,
+}
+
+impl ResourceLimits {
+  pub fn is_empty(&self) -> bool {
+    self.cpu.is_none()
+      && self.address_space.is_none()
+      && self.open_files.is_none()
+      && self.processes.is_none()
+  }
+
+  /// Applies the limits to the current process. Only async-signal-safe
+  /// functions are called, so this can be used in a `pre_exec` closure.
+  #[cfg(unix)]
+  pub fn apply(&self) -> Result<(), std::io::Error> {
+    let limits = [
+      (libc::RLIMIT_CPU, self.cpu),
+      (libc::RLIMIT_AS, self.address_space),
+      (libc::RLIMIT_NOFILE, self.open_files),
+      (libc::RLIMIT_NPROC, self.processes),
+    ];
+    for (resource, value) in limits {
+      let Some(value) = value else {
+        continue;
+      };
+      let rlimit = libc::rlimit {
+        rlim_cur: value as libc::rlim_t,
+        rlim_max: value as libc::rlim_t,
+      };
+      // SAFETY: libc call, rlimit outlives the call
+      if unsafe { libc::setrlimit(resource, &rlimit) } == -1 {
+        return Err(std::io::Error::last_os_error());
+      }
+    }
+    Ok(())
+  }
+}
+
+/// How to stop a child process that runs for longer than allowed.
+pub struct TimeoutOptions {
+  pub timeout: Duration,
+  /// Sent once the timeout elapses.
+  pub signal: SignalArg,
+  /// How long to wait after sending `signal` before sending `SIGKILL`.
+  pub grace_period: Duration,
+}
+
+impl TimeoutOptions {
+  pub fn new(
+    timeout_ms: u64,
+    signal: Option<SignalArg>,
+    grace_period_ms: Option<u64>,
+  ) -> Result<Self, ProcessError> {
+    let signal = signal.unwrap_or(SignalArg::String("SIGTERM".to_string()));
+    validate_signal(&signal)?;
+    Ok(Self {
+      timeout: Duration::from_millis(timeout_ms),
+      signal,
+      grace_period: grace_period_ms
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_KILL_GRACE_PERIOD),
+    })
+  }
+}
+
+/// Checks the signal up front, so that a typo is reported when spawning
+/// rather than silently ignored when the timeout fires.
+fn validate_signal(signal: &SignalArg) -> Result<(), ProcessError> {
+  #[cfg(unix)]
+  if let SignalArg::String(signal) = signal {
+    deno_signals::signal_str_to_int(signal)
+      .map_err(SignalError::InvalidSignalStr)?;
+  }
+  #[cfg(windows)]
+  if !matches!(signal, SignalArg::String(s) if s == "SIGKILL" || s == "SIGTERM")
+  {
+    let signal = match signal {
+      SignalArg::Int(n) => n.to_string(),
+      SignalArg::String(s) => s.clone(),
+    };
+    return Err(
+      SignalError::InvalidSignalStr(deno_signals::InvalidSignalStrError(
+        signal,
+      ))
+      .into(),
+    );
+  }
+  Ok(())
+}
+
+/// Kills a child process once its timeout elapses, escalating to `SIGKILL`
+/// if it is still running after the grace period.
+///
+/// The timer runs on its own thread so that it also works for
+/// `Deno.Command().outputSync()`, which blocks the JS thread. Dropping the
+/// `KillTimer` cancels it, which must happen as soon as the child has been
+/// waited on so the timer never signals a recycled pid.
+pub struct KillTimer {
+  _cancel_tx: mpsc::Sender<()>,
+}
+
+impl KillTimer {
+  /// `pid` is negative to signal a whole process group.
+  pub fn start(pid: i32, options: TimeoutOptions) -> Self {
+    let (cancel_tx, cancel_rx) = mpsc::channel::<()>();
+    let result = std::thread::Builder::new()
+      .name("subprocess-timeout".to_string())
+      .spawn(move || {
+        if cancel_rx.recv_timeout(options.timeout)
+          != Err(mpsc::RecvTimeoutError::Timeout)
+        {
+          return;
+        }
+        if let Err(err) = crate::deprecated::kill(pid, &options.signal) {
+          log::debug!("Failed to kill timed out subprocess: {err}");
+          return;
+        }
+        if cancel_rx.recv_timeout(options.grace_period)
+          != Err(mpsc::RecvTimeoutError::Timeout)
+        {
+          return;
+        }
+        let _ = crate::deprecated::kill(
+          pid,
+          &SignalArg::String("SIGKILL".to_string()),
+        );
+      });
+    if let Err(err) = result {
+      log::warn!("Failed to start subprocess timeout thread: {err}");
+    }
+    Self {
+      _cancel_tx: cancel_tx,
+    }
+  }
+}
@@ -0,0 +1,202 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+use std::borrow::Cow;
+#[cfg(unix)]
+use std::os::fd::AsRawFd;
+#[cfg(unix)]
+use std::os::fd::OwnedFd;
+use std::rc::Rc;
+
+use deno_core::AsyncResult;
+use deno_core::CancelHandle;
+#[cfg(unix)]
+use deno_core::CancelTryFuture;
+#[cfg(unix)]
+use deno_core::RcRef;
+use deno_core::Resource;
+use serde::Deserialize;
+#[cfg(unix)]
+use tokio::io::unix::AsyncFd;
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(not(unix), allow(dead_code))]
+pub struct PtyOptions {
+  pub rows: u16,
+  pub cols: u16,
+}
+
+/// The master side of a pseudo-terminal allocated for a child process.
+///
+/// Reading yields everything the child writes to its terminal, and writing
+/// is equivalent to typing into it. Once the child and all of its
+/// descendants have closed the slave side, reads return EOF.
+pub struct PtyResource {
+  #[cfg(unix)]
+  master: AsyncFd<OwnedFd>,
+  cancel: CancelHandle,
+}
+
+impl Resource for PtyResource {
+  fn name(&self) -> Cow<'_, str> {
+    "pty".into()
+  }
+
+  fn close(self: Rc<Self>) {
+    self.cancel.cancel();
+  }
+
+  deno_core::impl_readable_byob!();
+  deno_core::impl_writable!();
+}
+
+#[cfg(unix)]
+fn set_window_size(
+  fd: std::os::fd::RawFd,
+  options: PtyOptions,
+) -> Result<(), std::io::Error> {
+  let winsize = libc::winsize {
+    ws_row: options.rows,
+    ws_col: options.cols,
+    ws_xpixel: 0,
+    ws_ypixel: 0,
+  };
+  // SAFETY: libc call, fd is valid and winsize outlives the call
+  if unsafe { libc::ioctl(fd, libc::TIOCSWINSZ as _, &winsize) } == -1 {
+    return Err(std::io::Error::last_os_error());
+  }
+  Ok(())
+}
+
+#[cfg(unix)]
+fn set_cloexec(fd: &OwnedFd) -> Result<(), std::io::Error> {
+  // SAFETY: libc call, fd is valid
+  if unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_SETFD, libc::FD_CLOEXEC) }
+    == -1
+  {
+    return Err(std::io::Error::last_os_error());
+  }
+  Ok(())
+}
+
+impl PtyResource {
+  /// Allocates a pseudo-terminal with the given window size, returning the
+  /// resource for the master side and the slave side to hand to the child.
+  #[cfg(unix)]
+  pub fn open(options: PtyOptions) -> Result<(Self, OwnedFd), std::io::Error> {
+    let pty = nix::pty::openpty(None, None).map_err(std::io::Error::from)?;
+    // openpty(3) doesn't set close-on-exec, so without this every other
+    // child spawned while the pty is open would inherit both ends of it.
+    set_cloexec(&pty.master)?;
+    set_cloexec(&pty.slave)?;
+    set_window_size(pty.master.as_raw_fd(), options)?;
+    // SAFETY: libc call, fd is valid
+    unsafe {
+      let flags = libc::fcntl(pty.master.as_raw_fd(), libc::F_GETFL);
+      libc::fcntl(
+        pty.master.as_raw_fd(),
+        libc::F_SETFL,
+        flags | libc::O_NONBLOCK,
+      );
+    }
+    Ok((
+      Self {
+        master: AsyncFd::new(pty.master)?,
+        cancel: Default::default(),
+      },
+      pty.slave,
+    ))
+  }
+
+  #[cfg(unix)]
+  pub fn resize(&self, options: PtyOptions) -> Result<(), std::io::Error> {
+    set_window_size(self.master.get_ref().as_raw_fd(), options)
+  }
+
+  #[cfg(not(unix))]
+  pub fn resize(&self, _options: PtyOptions) -> Result<(), std::io::Error> {
+    Err(std::io::Error::from(std::io::ErrorKind::Unsupported))
+  }
+
+  #[cfg(unix)]
+  pub async fn read(
+    self: Rc<Self>,
+    data: &mut [u8],
+  ) -> Result<usize, std::io::Error> {
+    let cancel_handle = RcRef::map(&self, |r| &r.cancel);
+    async {
+      loop {
+        let mut guard = self.master.readable().await?;
+        let result = guard.try_io(|fd| {
+          // SAFETY: libc call, fd is valid and data is a valid buffer
+          let n = unsafe {
+            libc::read(fd.as_raw_fd(), data.as_mut_ptr() as _, data.len())
+          };
+          if n == -1 {
+            Err(std::io::Error::last_os_error())
+          } else {
+            Ok(n as usize)
+          }
+        });
+        match result {
+          Ok(Ok(n)) => return Ok(n),
+          // Linux reports EIO rather than EOF on the master once the last
+          // file descriptor for the slave side has been closed.
+          Ok(Err(err)) if err.raw_os_error() == Some(libc::EIO) => {
+            return Ok(0);
+          }
+          Ok(Err(err)) => return Err(err),
+          Err(_would_block) => continue,
+        }
+      }
+    }
+    .try_or_cancel(cancel_handle)
+    .await
+  }
+
+  #[cfg(not(unix))]
+  pub async fn read(
+    self: Rc<Self>,
+    _data: &mut [u8],
+  ) -> Result<usize, std::io::Error> {
+    Err(std::io::Error::from(std::io::ErrorKind::Unsupported))
+  }
+
+  #[cfg(unix)]
+  pub async fn write(
+    self: Rc<Self>,
+    data: &[u8],
+  ) -> Result<usize, std::io::Error> {
+    let cancel_handle = RcRef::map(&self, |r| &r.cancel);
+    async {
+      loop {
+        let mut guard = self.master.writable().await?;
+        let result = guard.try_io(|fd| {
+          // SAFETY: libc call, fd is valid and data is a valid buffer
+          let n = unsafe {
+            libc::write(fd.as_raw_fd(), data.as_ptr() as _, data.len())
+          };
+          if n == -1 {
+            Err(std::io::Error::last_os_error())
+          } else {
+            Ok(n as usize)
+          }
+        });
+        match result {
+          Ok(result) => return result,
+          Err(_would_block) => continue,
+        }
+      }
+    }
+    .try_or_cancel(cancel_handle)
+    .await
+  }
+
+  #[cfg(not(unix))]
+  pub async fn write(
+    self: Rc<Self>,
+    _data: &[u8],
+  ) -> Result<usize, std::io::Error> {
+    Err(std::io::Error::from(std::io::ErrorKind::Unsupported))
+  }
+}
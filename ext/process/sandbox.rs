@@ -0,0 +1,450 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+//! Confines subprocesses on Linux, with Landlock for file system access and
+//! a seccomp filter for network access.
+//!
+//! Both mechanisms can only express what is allowed, so the confinement is
+//! built from the paths that the parent's permissions grant (or that are
+//! given explicitly) and is fixed for the lifetime of the child and all of
+//! its descendants.
+
+use std::borrow::Cow;
+use std::path::Path;
+use std::path::PathBuf;
+
+use deno_permissions::OpenAccessKind;
+use deno_permissions::PermissionsContainer;
+use serde::Deserialize;
+
+use crate::ProcessError;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SandboxArgs {
+  /// Paths the child may read and execute, or `None` to use the paths that
+  /// the parent is allowed to read.
+  read: Option<Vec<String>>,
+  /// Paths the child may write to, or `None` to use the paths that the
+  /// parent is allowed to write to.
+  write: Option<Vec<String>>,
+  /// Whether the child may use the network, or `None` if it may only when
+  /// the parent has unrestricted network access.
+  net: Option<bool>,
+}
+
+/// The confinement of a child process, resolved against the parent's
+/// permissions.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub struct Sandbox {
+  /// `None` if reading is unrestricted.
+  read: Option<Vec<PathBuf>>,
+  /// `None` if writing is unrestricted.
+  write: Option<Vec<PathBuf>>,
+  net: bool,
+  /// Always readable and executable, as the child can't start otherwise.
+  cmd: PathBuf,
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+impl Sandbox {
+  pub fn resolve(
+    args: SandboxArgs,
+    permissions: &PermissionsContainer,
+    cmd: &Path,
+    cwd: &Path,
+    api_name: &str,
+  ) -> Result<Self, ProcessError> {
+    let check_paths = |paths: Vec<String>, access_kind: OpenAccessKind| {
+      paths
+        .into_iter()
+        .map(|path| {
+          let path = deno_path_util::normalize_path(Cow::Owned(cwd.join(path)));
+          permissions
+            .check_open(path, access_kind, Some(api_name))
+            .map(|path| path.into_owned_path())
+        })
+        .collect::<Result<Vec<_>, _>>()
+    };
+    let read = match args.read {
+      Some(paths) => Some(check_paths(paths, OpenAccessKind::Read)?),
+      None => permissions.granted_read_paths()?,
+    };
+    let write = match args.write {
+      Some(paths) => Some(check_paths(paths, OpenAccessKind::Write)?),
+      None => permissions.granted_write_paths()?,
+    };
+    let net = match args.net {
+      Some(true) => {
+        permissions.check_net_all()?;
+        true
+      }
+      Some(false) => false,
+      None => permissions.query_net_all(),
+    };
+    Ok(Self {
+      read,
+      write,
+      net,
+      cmd: cmd.to_path_buf(),
+    })
+  }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+  use std::ffi::CString;
+  use std::os::fd::AsRawFd;
+  use std::os::fd::FromRawFd;
+  use std::os::fd::OwnedFd;
+  use std::os::unix::ffi::OsStrExt;
+  use std::path::Path;
+
+  use super::Sandbox;
+  use crate::ProcessError;
+
+  // From linux/landlock.h, which the libc crate doesn't have bindings for.
+  const LANDLOCK_CREATE_RULESET_VERSION: u32 = 1 << 0;
+  const LANDLOCK_RULE_PATH_BENEATH: libc::c_int = 1;
+
+  const ACCESS_FS_EXECUTE: u64 = 1 << 0;
+  const ACCESS_FS_WRITE_FILE: u64 = 1 << 1;
+  const ACCESS_FS_READ_FILE: u64 = 1 << 2;
+  const ACCESS_FS_READ_DIR: u64 = 1 << 3;
+  const ACCESS_FS_REMOVE_DIR: u64 = 1 << 4;
+  const ACCESS_FS_REMOVE_FILE: u64 = 1 << 5;
+  const ACCESS_FS_MAKE_CHAR: u64 = 1 << 6;
+  const ACCESS_FS_MAKE_DIR: u64 = 1 << 7;
+  const ACCESS_FS_MAKE_REG: u64 = 1 << 8;
+  const ACCESS_FS_MAKE_SOCK: u64 = 1 << 9;
+  const ACCESS_FS_MAKE_FIFO: u64 = 1 << 10;
+  const ACCESS_FS_MAKE_BLOCK: u64 = 1 << 11;
+  const ACCESS_FS_MAKE_SYM: u64 = 1 << 12;
+  /// Landlock ABI 2.
+  const ACCESS_FS_REFER: u64 = 1 << 13;
+  /// Landlock ABI 3.
+  const ACCESS_FS_TRUNCATE: u64 = 1 << 14;
+
+  const ACCESS_READ: u64 =
+    ACCESS_FS_EXECUTE | ACCESS_FS_READ_FILE | ACCESS_FS_READ_DIR;
+  const ACCESS_WRITE: u64 = ACCESS_FS_WRITE_FILE
+    | ACCESS_FS_REMOVE_DIR
+    | ACCESS_FS_REMOVE_FILE
+    | ACCESS_FS_MAKE_CHAR
+    | ACCESS_FS_MAKE_DIR
+    | ACCESS_FS_MAKE_REG
+    | ACCESS_FS_MAKE_SOCK
+    | ACCESS_FS_MAKE_FIFO
+    | ACCESS_FS_MAKE_BLOCK
+    | ACCESS_FS_MAKE_SYM
+    | ACCESS_FS_REFER
+    | ACCESS_FS_TRUNCATE;
+  /// The rights that may be granted on a path that isn't a directory.
+  const ACCESS_FILE: u64 = ACCESS_FS_EXECUTE
+    | ACCESS_FS_WRITE_FILE
+    | ACCESS_FS_READ_FILE
+    | ACCESS_FS_TRUNCATE;
+
+  /// Paths that programs generally can't start without, like the dynamic
+  /// loader and shared libraries.
+  const SYSTEM_READ_PATHS: &[&str] =
+    &["/bin", "/sbin", "/usr", "/lib", "/lib32", "/lib64", "/etc"];
+  /// Devices that are commonly opened for writing, like when discarding
+  /// output.
+  const SYSTEM_WRITE_PATHS: &[&str] = &["/dev/null", "/dev/tty"];
+  const SYSTEM_DEVICE_PATHS: &[&str] = &[
+    "/dev/null",
+    "/dev/zero",
+    "/dev/random",
+    "/dev/urandom",
+    "/dev/tty",
+  ];
+
+  #[repr(C)]
+  struct LandlockRulesetAttr {
+    handled_access_fs: u64,
+  }
+
+  #[repr(C, packed)]
+  struct LandlockPathBeneathAttr {
+    allowed_access: u64,
+    parent_fd: libc::c_int,
+  }
+
+  #[cfg(target_arch = "x86_64")]
+  const AUDIT_ARCH: u32 = 0xC000_003E;
+  #[cfg(target_arch = "aarch64")]
+  const AUDIT_ARCH: u32 = 0xC000_00B7;
+  /// Set on x32 syscall numbers, which share the x86-64 audit arch.
+  const X32_SYSCALL_BIT: u32 = 0x4000_0000;
+
+  // Offsets into `struct seccomp_data`.
+  const SECCOMP_DATA_NR: u32 = 0;
+  const SECCOMP_DATA_ARCH: u32 = 4;
+  const SECCOMP_DATA_ARG0: u32 = 16;
+
+  fn last_os_error_unsupported(what: &'static str) -> ProcessError {
+    let err = std::io::Error::last_os_error();
+    match err.raw_os_error() {
+      Some(libc::ENOSYS) | Some(libc::EOPNOTSUPP) => {
+        ProcessError::NotSupported(what)
+      }
+      _ => err.into(),
+    }
+  }
+
+  /// A sandbox whose Landlock ruleset and seccomp filter have been built in
+  /// the parent, so that applying it between fork and exec doesn't allocate.
+  pub struct PreparedSandbox {
+    ruleset: Option<OwnedFd>,
+    seccomp_filter: Option<Vec<libc::sock_filter>>,
+  }
+
+  impl Sandbox {
+    pub fn prepare(self) -> Result<PreparedSandbox, ProcessError> {
+      let ruleset = if self.read.is_some() || self.write.is_some() {
+        Some(self.create_ruleset()?)
+      } else {
+        None
+      };
+      let seccomp_filter = if self.net {
+        None
+      } else {
+        Some(deny_network_filter()?)
+      };
+      Ok(PreparedSandbox {
+        ruleset,
+        seccomp_filter,
+      })
+    }
+
+    fn create_ruleset(&self) -> Result<OwnedFd, ProcessError> {
+      // SAFETY: syscall, querying the ABI version takes no attributes
+      let abi = unsafe {
+        libc::syscall(
+          libc::SYS_landlock_create_ruleset,
+          std::ptr::null::<LandlockRulesetAttr>(),
+          0,
+          LANDLOCK_CREATE_RULESET_VERSION,
+        )
+      };
+      if abi < 0 {
+        return Err(last_os_error_unsupported("Landlock"));
+      }
+      let mut supported = ACCESS_READ | ACCESS_WRITE;
+      if abi < 2 {
+        supported &= !ACCESS_FS_REFER;
+      }
+      if abi < 3 {
+        supported &= !ACCESS_FS_TRUNCATE;
+      }
+
+      let mut handled = 0;
+      if self.read.is_some() {
+        handled |= ACCESS_READ;
+      }
+      if self.write.is_some() {
+        handled |= ACCESS_WRITE;
+      }
+      handled &= supported;
+
+      let attr = LandlockRulesetAttr {
+        handled_access_fs: handled,
+      };
+      // SAFETY: syscall, attr outlives the call
+      let fd = unsafe {
+        libc::syscall(
+          libc::SYS_landlock_create_ruleset,
+          &attr as *const LandlockRulesetAttr,
+          std::mem::size_of::<LandlockRulesetAttr>(),
+          0,
+        )
+      };
+      if fd < 0 {
+        return Err(last_os_error_unsupported("Landlock"));
+      }
+      // SAFETY: the fd was just created and is owned by nothing else
+      let ruleset = unsafe { OwnedFd::from_raw_fd(fd as libc::c_int) };
+
+      let mut rules: Vec<(&Path, u64)> = vec![(&self.cmd, ACCESS_READ)];
+      if let Some(paths) = &self.read {
+        rules.extend(paths.iter().map(|p| (p.as_path(), ACCESS_READ)));
+        rules.extend(
+          SYSTEM_READ_PATHS
+            .iter()
+            .chain(SYSTEM_DEVICE_PATHS)
+            .map(|p| (Path::new(p), ACCESS_READ)),
+        );
+      }
+      if let Some(paths) = &self.write {
+        rules.extend(paths.iter().map(|p| (p.as_path(), ACCESS_WRITE)));
+        rules.extend(
+          SYSTEM_WRITE_PATHS
+            .iter()
+            .map(|p| (Path::new(p), ACCESS_WRITE)),
+        );
+      }
+      for (path, access) in rules {
+        add_path_rule(&ruleset, path, access & handled)?;
+      }
+      Ok(ruleset)
+    }
+  }
+
+  /// Allows `access` beneath `path`. Paths that don't exist are skipped, as
+  /// there's nothing to grant access to.
+  fn add_path_rule(
+    ruleset: &OwnedFd,
+    path: &Path,
+    mut access: u64,
+  ) -> Result<(), ProcessError> {
+    if access == 0 {
+      return Ok(());
+    }
+    let Ok(c_path) = CString::new(path.as_os_str().as_bytes()) else {
+      return Ok(());
+    };
+    // SAFETY: libc call, c_path is a valid C string
+    let fd =
+      unsafe { libc::open(c_path.as_ptr(), libc::O_PATH | libc::O_CLOEXEC) };
+    if fd < 0 {
+      return Ok(());
+    }
+    // SAFETY: the fd was just opened and is owned by nothing else
+    let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+    #[allow(clippy::disallowed_methods)]
+    let is_dir = std::fs::metadata(path).map(|m| m.is_dir()).unwrap_or(false);
+    if !is_dir {
+      access &= ACCESS_FILE;
+    }
+    let attr = LandlockPathBeneathAttr {
+      allowed_access: access,
+      parent_fd: fd.as_raw_fd(),
+    };
+    // SAFETY: syscall, the fds are valid and attr outlives the call
+    let result = unsafe {
+      libc::syscall(
+        libc::SYS_landlock_add_rule,
+        ruleset.as_raw_fd(),
+        LANDLOCK_RULE_PATH_BENEATH,
+        &attr as *const LandlockPathBeneathAttr,
+        0,
+      )
+    };
+    if result < 0 {
+      return Err(std::io::Error::last_os_error().into());
+    }
+    Ok(())
+  }
+
+  fn bpf_stmt(code: u32, k: u32) -> libc::sock_filter {
+    libc::sock_filter {
+      code: code as u16,
+      jt: 0,
+      jf: 0,
+      k,
+    }
+  }
+
+  fn bpf_jump(code: u32, k: u32, jt: u8, jf: u8) -> libc::sock_filter {
+    libc::sock_filter {
+      code: code as u16,
+      jt,
+      jf,
+      k,
+    }
+  }
+
+  /// A seccomp filter that fails creating IPv4 and IPv6 sockets with
+  /// `EACCES`. Unix domain sockets keep working. io_uring is denied as well,
+  /// since sockets created through it aren't seen by the filter.
+  #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+  fn deny_network_filter() -> Result<Vec<libc::sock_filter>, ProcessError> {
+    use libc::BPF_ABS;
+    use libc::BPF_JEQ;
+    use libc::BPF_JGE;
+    use libc::BPF_JMP;
+    use libc::BPF_K;
+    use libc::BPF_LD;
+    use libc::BPF_RET;
+    use libc::BPF_W;
+
+    let deny = libc::SECCOMP_RET_ERRNO | (libc::EACCES as u32);
+    let allow = libc::SECCOMP_RET_ALLOW;
+    Ok(vec![
+      /* 0 */ bpf_stmt(BPF_LD | BPF_W | BPF_ABS, SECCOMP_DATA_ARCH),
+      /* 1 */ bpf_jump(BPF_JMP | BPF_JEQ | BPF_K, AUDIT_ARCH, 1, 0),
+      /* 2 */ bpf_stmt(BPF_RET | BPF_K, libc::SECCOMP_RET_KILL_PROCESS),
+      /* 3 */ bpf_stmt(BPF_LD | BPF_W | BPF_ABS, SECCOMP_DATA_NR),
+      /* 4 */ bpf_jump(BPF_JMP | BPF_JGE | BPF_K, X32_SYSCALL_BIT, 6, 0),
+      /* 5 */
+      bpf_jump(BPF_JMP | BPF_JEQ | BPF_K, libc::SYS_socket as u32, 0, 4),
+      /* 6 */ bpf_stmt(BPF_LD | BPF_W | BPF_ABS, SECCOMP_DATA_ARG0),
+      /* 7 */
+      bpf_jump(BPF_JMP | BPF_JEQ | BPF_K, libc::AF_INET as u32, 3, 0),
+      /* 8 */
+      bpf_jump(BPF_JMP | BPF_JEQ | BPF_K, libc::AF_INET6 as u32, 2, 0),
+      /* 9 */ bpf_stmt(BPF_RET | BPF_K, allow),
+      /* 10 */
+      bpf_jump(
+        BPF_JMP | BPF_JEQ | BPF_K,
+        libc::SYS_io_uring_setup as u32,
+        0,
+        1,
+      ),
+      /* 11 */ bpf_stmt(BPF_RET | BPF_K, deny),
+      /* 12 */ bpf_stmt(BPF_RET | BPF_K, allow),
+    ])
+  }
+
+  #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+  fn deny_network_filter() -> Result<Vec<libc::sock_filter>, ProcessError> {
+    Err(ProcessError::NotSupported(
+      "Restricting network access of subprocesses",
+    ))
+  }
+
+  impl PreparedSandbox {
+    /// Confines the current process. Only async-signal-safe functions are
+    /// called, so this can be used in a `pre_exec` closure.
+    pub fn apply(&self) -> Result<(), std::io::Error> {
+      // Required by both Landlock and seccomp for unprivileged processes. It
+      // also means that setuid binaries don't gain privileges in the child.
+      // SAFETY: libc call
+      if unsafe { libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) } != 0 {
+        return Err(std::io::Error::last_os_error());
+      }
+      if let Some(ruleset) = &self.ruleset {
+        // SAFETY: syscall, the fd is valid
+        let result = unsafe {
+          libc::syscall(
+            libc::SYS_landlock_restrict_self,
+            ruleset.as_raw_fd(),
+            0,
+          )
+        };
+        if result < 0 {
+          return Err(std::io::Error::last_os_error());
+        }
+      }
+      if let Some(filter) = &self.seccomp_filter {
+        let prog = libc::sock_fprog {
+          len: filter.len() as u16,
+          filter: filter.as_ptr() as *mut libc::sock_filter,
+        };
+        // SAFETY: libc call, prog and the filter it points to outlive the
+        // call
+        if unsafe {
+          libc::prctl(
+            libc::PR_SET_SECCOMP,
+            libc::SECCOMP_MODE_FILTER,
+            &prog as *const libc::sock_fprog,
+          )
+        } != 0
+        {
+          return Err(std::io::Error::last_os_error());
+        }
+      }
+      Ok(())
+    }
+  }
+}
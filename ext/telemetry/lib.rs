@@ -9,6 +9,8 @@ use std::collections::HashMap;
 use std::env;
 use std::ffi::c_void;
 use std::fmt::Debug;
+use std::io::Write;
+use std::net::ToSocketAddrs;
 use std::pin::Pin;
 use std::rc::Rc;
 use std::sync::Arc;
@@ -56,12 +58,14 @@ use opentelemetry::otel_error;
 use opentelemetry::trace::Event;
 use opentelemetry::trace::Link;
 use opentelemetry::trace::SpanContext;
-use opentelemetry::trace::SpanId;
+pub use opentelemetry::trace::SpanId;
 use opentelemetry::trace::SpanKind;
 use opentelemetry::trace::Status as SpanStatus;
 use opentelemetry::trace::TraceFlags;
-use opentelemetry::trace::TraceId;
+pub use opentelemetry::trace::TraceId;
 use opentelemetry::trace::TraceState;
+use opentelemetry_http::HttpClient;
+use opentelemetry_http::HttpError;
 use opentelemetry_otlp::HttpExporterBuilder;
 use opentelemetry_otlp::Protocol;
 use opentelemetry_otlp::WithExportConfig;
@@ -94,6 +98,8 @@ use thiserror::Error;
 use tokio::sync::oneshot;
 use tokio::task::JoinSet;
 
+mod prometheus;
+
 deno_core::extension!(
   deno_telemetry,
   ops = [
@@ -101,6 +107,8 @@ deno_core::extension!(
     op_otel_enable_isolate_metrics,
     op_otel_log,
     op_otel_log_foreign,
+    op_otel_span_mark,
+    op_otel_span_mark_foreign,
     op_otel_span_attribute1,
     op_otel_span_attribute2,
     op_otel_span_attribute3,
@@ -135,6 +143,11 @@ pub struct OtelConfig {
   pub console: OtelConsoleConfig,
   pub deterministic_prefix: Option<u8>,
   pub propagators: std::collections::HashSet<OtelPropagators>,
+  pub sampler: OtelSampler,
+  pub instrumentations: OtelInstrumentations,
+  /// Record which span is current whenever JS switches contexts, so that
+  /// the CPU profiler can label its samples with trace and span ids.
+  pub span_marks: bool,
 }
 
 impl OtelConfig {
@@ -143,8 +156,12 @@ impl OtelConfig {
       self.tracing_enabled as u8,
       self.metrics_enabled as u8,
       self.console as u8,
+      self.instrumentations.as_u8(),
+      self.span_marks as u8,
     ];
 
+    data.extend(self.instrumentations.fs_threshold_ms.to_le_bytes());
+
     data.extend(self.propagators.iter().map(|propagator| *propagator as u8));
 
     data.into_boxed_slice()
@@ -160,6 +177,95 @@ pub enum OtelPropagators {
   Baggage = 1,
   #[default]
   None = 2,
+  B3 = 3,
+  B3Multi = 4,
+  Jaeger = 5,
+}
+
+/// Opt-in automatic instrumentations, as configured by
+/// `OTEL_DENO_INSTRUMENTATIONS`. These only take effect when tracing is
+/// enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OtelInstrumentations {
+  /// Spans for `Deno.Kv` reads, writes and queue deliveries.
+  pub kv: bool,
+  /// Spans for `Deno.Command` subprocesses.
+  pub command: bool,
+  /// Spans for asynchronous file system calls.
+  pub fs: bool,
+  /// File system calls that take less than this many milliseconds are not
+  /// traced, as configured by `OTEL_DENO_FS_THRESHOLD_MS`.
+  pub fs_threshold_ms: u32,
+}
+
+impl Default for OtelInstrumentations {
+  fn default() -> Self {
+    Self {
+      kv: false,
+      command: false,
+      fs: false,
+      fs_threshold_ms: 1,
+    }
+  }
+}
+
+impl OtelInstrumentations {
+  fn as_u8(&self) -> u8 {
+    (self.kv as u8) | (self.command as u8) << 1 | (self.fs as u8) << 2
+  }
+}
+
+/// The sampler used to decide whether a newly started span is recorded and
+/// exported, as configured by `OTEL_TRACES_SAMPLER` and
+/// `OTEL_TRACES_SAMPLER_ARG`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum OtelSampler {
+  AlwaysOn,
+  AlwaysOff,
+  TraceIdRatio(f64),
+  #[default]
+  ParentBasedAlwaysOn,
+  ParentBasedAlwaysOff,
+  ParentBasedTraceIdRatio(f64),
+}
+
+impl OtelSampler {
+  /// Returns whether a span in the given trace should be sampled. `parent` is
+  /// the sampled flag of the parent span, if there is one.
+  pub fn should_sample(&self, parent: Option<bool>, trace_id: TraceId) -> bool {
+    match (self, parent) {
+      (Self::AlwaysOn, _) => true,
+      (Self::AlwaysOff, _) => false,
+      (Self::TraceIdRatio(ratio), _) => trace_id_ratio(*ratio, trace_id),
+      (
+        Self::ParentBasedAlwaysOn
+        | Self::ParentBasedAlwaysOff
+        | Self::ParentBasedTraceIdRatio(_),
+        Some(sampled),
+      ) => sampled,
+      (Self::ParentBasedAlwaysOn, None) => true,
+      (Self::ParentBasedAlwaysOff, None) => false,
+      (Self::ParentBasedTraceIdRatio(ratio), None) => {
+        trace_id_ratio(*ratio, trace_id)
+      }
+    }
+  }
+}
+
+/// Samples deterministically based on the lower 64 bits of the trace id, so
+/// that all participants using the same ratio agree on the decision.
+fn trace_id_ratio(ratio: f64, trace_id: TraceId) -> bool {
+  if ratio >= 1.0 {
+    return true;
+  }
+  if ratio <= 0.0 {
+    return false;
+  }
+  let bytes = trace_id.to_bytes();
+  let (_, low) = bytes.split_at(8);
+  let low = u64::from_be_bytes(low.try_into().unwrap()) >> 1;
+  let upper_bound = (ratio * (1u64 << 63) as f64) as u64;
+  low < upper_bound
 }
 
 #[derive(
@@ -177,9 +283,14 @@ static OTEL_SHARED_RUNTIME_SPAWN_TASK_TX: Lazy<
   UnboundedSender<BoxFuture<'static, ()>>,
 > = Lazy::new(otel_create_shared_runtime);
 
-static OTEL_PRE_COLLECT_CALLBACKS: Lazy<
-  Mutex<Vec<oneshot::Sender<oneshot::Sender<()>>>>,
-> = Lazy::new(Default::default);
+/// Asks an isolate with observable instruments to record its observations.
+/// Each isolate registers one callback, which is called for every collection
+/// until the isolate goes away.
+type PreCollectCallback =
+  tokio::sync::mpsc::UnboundedSender<oneshot::Sender<()>>;
+
+static OTEL_PRE_COLLECT_CALLBACKS: Lazy<Mutex<Vec<PreCollectCallback>>> =
+  Lazy::new(Default::default);
 
 fn otel_create_shared_runtime() -> UnboundedSender<BoxFuture<'static, ()>> {
   let (spawn_task_tx, mut spawn_task_rx) =
@@ -317,6 +428,27 @@ impl<T> Stream for BatchMessageChannelReceiver<T> {
   }
 }
 
+/// Asks every isolate with observable instruments to record its observations,
+/// and waits until they are done. Collections may run concurrently (e.g. a
+/// Prometheus scrape during a periodic export), so the callbacks stay
+/// registered and each isolate handles the requests one after the other.
+async fn run_pre_collect_callbacks() {
+  let mut futures = JoinSet::new();
+  OTEL_PRE_COLLECT_CALLBACKS
+    .lock()
+    .unwrap()
+    .retain(|callback| {
+      let (tx, rx) = oneshot::channel();
+      // the isolate went away if the request can't be sent
+      let sent = callback.send(tx).is_ok();
+      if sent {
+        futures.spawn(rx);
+      }
+      sent
+    });
+  while futures.join_next().await.is_some() {}
+}
+
 enum DenoPeriodicReaderMessage {
   Register(std::sync::Weak<opentelemetry_sdk::metrics::Pipeline>),
   Export,
@@ -398,18 +530,7 @@ impl DenoPeriodicReader {
               scope_metrics: Default::default(),
             };
           if collect_observed {
-            let callbacks = {
-              let mut callbacks = OTEL_PRE_COLLECT_CALLBACKS.lock().unwrap();
-              std::mem::take(&mut *callbacks)
-            };
-            let mut futures = JoinSet::new();
-            for callback in callbacks {
-              let (tx, rx) = oneshot::channel();
-              if let Ok(()) = callback.send(tx) {
-                futures.spawn(rx);
-              }
-            }
-            while futures.join_next().await.is_some() {}
+            run_pre_collect_callbacks().await;
           }
           inner.collect(&mut resource_metrics)?;
           if resource_metrics.scope_metrics.is_empty() {
@@ -832,6 +953,65 @@ mod hyper_client {
   }
 }
 
+/// An OTLP "HTTP client" that appends every export request to a local file
+/// instead of sending it to a collector. Each request body is written as a
+/// single line of JSON, following the OTLP file exporter format.
+#[derive(Debug, Clone)]
+struct FileClient(Arc<Mutex<std::fs::File>>);
+
+impl FileClient {
+  fn new(path: &str) -> std::io::Result<Self> {
+    let file = std::fs::OpenOptions::new()
+      .create(true)
+      .append(true)
+      .open(path)?;
+    Ok(Self(Arc::new(Mutex::new(file))))
+  }
+}
+
+#[async_trait::async_trait]
+impl HttpClient for FileClient {
+  async fn send(
+    &self,
+    request: opentelemetry_http::Request<Vec<u8>>,
+  ) -> Result<opentelemetry_http::Response<opentelemetry_http::Bytes>, HttpError>
+  {
+    // The exporter pretty-prints JSON bodies, so re-encode them compactly to
+    // keep one request per line.
+    let body: deno_core::serde_json::Value =
+      deno_core::serde_json::from_slice(request.body())?;
+    let mut line = deno_core::serde_json::to_vec(&body)?;
+    line.push(b'\n');
+    self.0.lock().unwrap().write_all(&line)?;
+    Ok(
+      opentelemetry_http::Response::builder()
+        .status(200)
+        .body(opentelemetry_http::Bytes::new())?,
+    )
+  }
+}
+
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+enum OtlpClient {
+  Hyper(hyper_client::HyperClient),
+  File(FileClient),
+}
+
+#[async_trait::async_trait]
+impl HttpClient for OtlpClient {
+  async fn send(
+    &self,
+    request: opentelemetry_http::Request<Vec<u8>>,
+  ) -> Result<opentelemetry_http::Response<opentelemetry_http::Bytes>, HttpError>
+  {
+    match self {
+      Self::Hyper(client) => client.send(request).await,
+      Self::File(client) => client.send(request).await,
+    }
+  }
+}
+
 #[derive(Debug)]
 pub struct OtelGlobals {
   pub span_processor: BatchSpanProcessor<OtelSharedRuntime>,
@@ -865,10 +1045,26 @@ pub fn init(
     return Ok(());
   }
 
+  // When `OTEL_DENO_EXPORTER_FILE` is set, telemetry is written to that file
+  // as OTLP JSON lines instead of being sent to a collector.
+  let file_client = match env::var("OTEL_DENO_EXPORTER_FILE") {
+    Ok(path) if !path.is_empty() => {
+      Some(FileClient::new(&path).map_err(|err| {
+        deno_core::anyhow::anyhow!(
+          "Failed to open OTEL_DENO_EXPORTER_FILE '{}': {}",
+          path,
+          err
+        )
+      })?)
+    }
+    _ => None,
+  };
+
   // Parse the `OTEL_EXPORTER_OTLP_PROTOCOL` variable. The opentelemetry_*
   // crates don't do this automatically.
   // TODO(piscisaureus): enable GRPC support.
   let protocol = match env::var("OTEL_EXPORTER_OTLP_PROTOCOL").as_deref() {
+    _ if file_client.is_some() => Protocol::HttpJson,
     Ok("http/protobuf") => Protocol::HttpBinary,
     Ok("http/json") => Protocol::HttpJson,
     Ok("") | Err(env::VarError::NotPresent) => Protocol::HttpBinary,
@@ -927,7 +1123,10 @@ pub fn init(
   // `OTEL_EXPORTER_OTLP_ENDPOINT` environment variable. Additional headers can
   // be specified using `OTEL_EXPORTER_OTLP_HEADERS`.
 
-  let client = hyper_client::HyperClient::new()?;
+  let client = match file_client {
+    Some(file_client) => OtlpClient::File(file_client),
+    None => OtlpClient::Hyper(hyper_client::HyperClient::new()?),
+  };
 
   let span_exporter = HttpExporterBuilder::default()
     .with_http_client(client.clone())
@@ -952,15 +1151,71 @@ pub fn init(
       ));
     }
   };
-  let metric_exporter = HttpExporterBuilder::default()
-    .with_http_client(client.clone())
-    .with_protocol(protocol)
-    .build_metrics_exporter(temporality)?;
-  let metric_reader = DenoPeriodicReader::new(metric_exporter);
-  let meter_provider = SdkMeterProvider::builder()
-    .with_reader(metric_reader)
-    .with_resource(resource.clone())
-    .build();
+  // Parse the `OTEL_METRICS_EXPORTER` variable, which selects between pushing
+  // metrics over OTLP and serving them on a Prometheus scrape endpoint.
+  let metrics_exporters = env::var("OTEL_METRICS_EXPORTER")
+    .unwrap_or_else(|_| "otlp".to_string())
+    .to_lowercase();
+  let mut otlp_metrics = false;
+  let mut prometheus_metrics = false;
+  for exporter in metrics_exporters.split(',').map(str::trim) {
+    match exporter {
+      "otlp" | "" => otlp_metrics = true,
+      "prometheus" => prometheus_metrics = true,
+      "none" => {}
+      other => {
+        return Err(deno_core::anyhow::anyhow!(
+          "Env var OTEL_METRICS_EXPORTER specifies an unsupported exporter: {}",
+          other
+        ));
+      }
+    }
+  }
+
+  let mut meter_provider_builder =
+    SdkMeterProvider::builder().with_resource(resource.clone());
+  if otlp_metrics {
+    let metric_exporter = HttpExporterBuilder::default()
+      .with_http_client(client.clone())
+      .with_protocol(protocol)
+      .build_metrics_exporter(temporality)?;
+    meter_provider_builder = meter_provider_builder
+      .with_reader(DenoPeriodicReader::new(metric_exporter));
+  }
+  if prometheus_metrics && config.metrics_enabled {
+    let host = env::var("OTEL_EXPORTER_PROMETHEUS_HOST")
+      .unwrap_or_else(|_| "localhost".to_string());
+    let port = match env::var("OTEL_EXPORTER_PROMETHEUS_PORT") {
+      Ok(port) => port.parse::<u16>().map_err(|_| {
+        deno_core::anyhow::anyhow!(
+          "Invalid value for OTEL_EXPORTER_PROMETHEUS_PORT: {}",
+          port
+        )
+      })?,
+      Err(_) => 9464,
+    };
+    let addr =
+      (host.as_str(), port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| {
+          deno_core::anyhow::anyhow!(
+            "Failed to resolve OTEL_EXPORTER_PROMETHEUS_HOST: {}",
+            host
+          )
+        })?;
+    let prometheus_reader = prometheus::PrometheusReader::new();
+    prometheus_reader.serve(addr).map_err(|err| {
+      deno_core::anyhow::anyhow!(
+        "Failed to start Prometheus metrics endpoint on {}: {}",
+        addr,
+        err
+      )
+    })?;
+    meter_provider_builder =
+      meter_provider_builder.with_reader(prometheus_reader);
+  }
+  let meter_provider = meter_provider_builder.build();
 
   let log_exporter = HttpExporterBuilder::default()
     .with_http_client(client)
@@ -1366,6 +1621,73 @@ fn op_otel_log_foreign(
   log_processor.emit(&mut log_record, builtin_instrumentation_scope);
 }
 
+/// The span that was current on an isolate from `time` onwards, until the
+/// next mark. Both ids are invalid when no span was current.
+#[derive(Debug, Clone, Copy)]
+pub struct SpanMark {
+  pub time: Instant,
+  pub trace_id: TraceId,
+  pub span_id: SpanId,
+}
+
+/// Span marks recorded by an isolate. Put into the `OpState` by the CPU
+/// profiler, which drains it every time it exports a profile.
+#[derive(Debug, Clone, Default)]
+pub struct SpanMarks(Arc<Mutex<Vec<SpanMark>>>);
+
+impl SpanMarks {
+  pub fn take(&self) -> Vec<SpanMark> {
+    std::mem::take(&mut *self.0.lock().unwrap())
+  }
+
+  fn push(&self, trace_id: TraceId, span_id: SpanId) {
+    self.0.lock().unwrap().push(SpanMark {
+      time: Instant::now(),
+      trace_id,
+      span_id,
+    });
+  }
+}
+
+#[op2(fast)]
+fn op_otel_span_mark<'s>(
+  state: &mut OpState,
+  scope: &mut v8::PinScope<'s, '_>,
+  span: v8::Local<'s, v8::Value>,
+) {
+  let Some(marks) = state.try_borrow::<SpanMarks>() else {
+    return;
+  };
+  let Some(span) =
+    deno_core::_ops::try_unwrap_cppgc_object::<OtelSpan>(scope, span)
+  else {
+    marks.push(TraceId::INVALID, SpanId::INVALID);
+    return;
+  };
+  let state = span.0.borrow();
+  let span_context = match &**state {
+    OtelSpanState::Recording(span) => &span.span_context,
+    OtelSpanState::Done(span_context) => span_context,
+  };
+  marks.push(span_context.trace_id(), span_context.span_id());
+}
+
+#[op2(fast)]
+fn op_otel_span_mark_foreign(
+  state: &mut OpState,
+  scope: &mut v8::PinScope<'_, '_>,
+  trace_id: v8::Local<'_, v8::Value>,
+  span_id: v8::Local<'_, v8::Value>,
+) {
+  let Some(marks) = state.try_borrow::<SpanMarks>() else {
+    return;
+  };
+  marks.push(
+    parse_trace_id(scope, trace_id),
+    parse_span_id(scope, span_id),
+  );
+}
+
 pub fn report_event(name: &'static str, data: impl std::fmt::Display) {
   let Some(OtelGlobals {
     log_processor,
@@ -1451,7 +1773,11 @@ impl OtelTracer {
     start_time: Option<f64>,
     #[smi] attribute_count: usize,
   ) -> Result<OtelSpan, JsErrorBox> {
-    let OtelGlobals { id_generator, .. } = OTEL_GLOBALS.get().unwrap();
+    let OtelGlobals {
+      id_generator,
+      config,
+      ..
+    } = OTEL_GLOBALS.get().unwrap();
     let span_context;
     let parent_span_id;
     match parent {
@@ -1461,26 +1787,37 @@ impl OtelTracer {
           OtelSpanState::Recording(span) => &span.span_context,
           OtelSpanState::Done(span_context) => span_context,
         };
+        let sampled = config.sampler.should_sample(
+          Some(parent_span_context.is_sampled()),
+          parent_span_context.trace_id(),
+        );
         span_context = SpanContext::new(
           parent_span_context.trace_id(),
           id_generator.new_span_id(),
-          TraceFlags::SAMPLED,
+          sampled_flags(sampled),
           false,
           parent_span_context.trace_state().clone(),
         );
         parent_span_id = parent_span_context.span_id();
       }
       None => {
+        let trace_id = id_generator.new_trace_id();
+        let sampled = config.sampler.should_sample(None, trace_id);
         span_context = SpanContext::new(
-          id_generator.new_trace_id(),
+          trace_id,
           id_generator.new_span_id(),
-          TraceFlags::SAMPLED,
+          sampled_flags(sampled),
           false,
           TraceState::NONE,
         );
         parent_span_id = SpanId::INVALID;
       }
     }
+    if !span_context.is_sampled() {
+      return Ok(OtelSpan(RefCell::new(Box::new(OtelSpanState::Done(
+        span_context,
+      )))));
+    }
     let name = owned_string(
       scope,
       name
@@ -1527,6 +1864,7 @@ impl OtelTracer {
     scope: &mut v8::PinScope<'s, '_>,
     parent_trace_id: v8::Local<'s, v8::Value>,
     parent_span_id: v8::Local<'s, v8::Value>,
+    #[smi] parent_trace_flags: u8,
     name: v8::Local<'s, v8::Value>,
     #[smi] span_kind: u8,
     start_time: Option<f64>,
@@ -1540,14 +1878,27 @@ impl OtelTracer {
     if parent_span_id == SpanId::INVALID {
       return Err(JsErrorBox::generic("invalid span id"));
     };
-    let OtelGlobals { id_generator, .. } = OTEL_GLOBALS.get().unwrap();
+    let OtelGlobals {
+      id_generator,
+      config,
+      ..
+    } = OTEL_GLOBALS.get().unwrap();
+    let sampled = config.sampler.should_sample(
+      Some(TraceFlags::new(parent_trace_flags).is_sampled()),
+      parent_trace_id,
+    );
     let span_context = SpanContext::new(
       parent_trace_id,
       id_generator.new_span_id(),
-      TraceFlags::SAMPLED,
+      sampled_flags(sampled),
       false,
       TraceState::NONE,
     );
+    if !sampled {
+      return Ok(OtelSpan(RefCell::new(Box::new(OtelSpanState::Done(
+        span_context,
+      )))));
+    }
     let name = owned_string(
       scope,
       name
@@ -1589,6 +1940,14 @@ impl OtelTracer {
   }
 }
 
+fn sampled_flags(sampled: bool) -> TraceFlags {
+  if sampled {
+    TraceFlags::SAMPLED
+  } else {
+    TraceFlags::default()
+  }
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 struct JsSpanContext {
@@ -1649,6 +2008,11 @@ impl OtelSpan {
     }
   }
 
+  #[fast]
+  fn is_recording(&self) -> bool {
+    matches!(&**self.0.borrow(), OtelSpanState::Recording(_))
+  }
+
   #[fast]
   fn set_status<'s>(
     &self,
@@ -2514,21 +2878,30 @@ fn op_otel_metric_attribute3<'s>(
 
 struct ObservationDone(oneshot::Sender<()>);
 
+struct ObservationRequests(
+  tokio::sync::mpsc::UnboundedReceiver<oneshot::Sender<()>>,
+);
+
 #[op2]
 async fn op_otel_metric_wait_to_observe(state: Rc<RefCell<OpState>>) -> bool {
-  let (tx, rx) = oneshot::channel();
-  {
+  let requests = state.borrow_mut().try_take::<ObservationRequests>();
+  let mut requests = requests.unwrap_or_else(|| {
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
     OTEL_PRE_COLLECT_CALLBACKS
       .lock()
       .expect("mutex poisoned")
       .push(tx);
-  }
-  match rx.await {
-    Ok(done) => {
-      state.borrow_mut().put(ObservationDone(done));
+    ObservationRequests(rx)
+  });
+  let done = requests.0.recv().await;
+  let mut state = state.borrow_mut();
+  state.put(requests);
+  match done {
+    Some(done) => {
+      state.put(ObservationDone(done));
       true
     }
-    _ => false,
+    None => false,
   }
 }
 
@@ -0,0 +1,455 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+//! A pull based metrics exporter that serves the contents of the meter
+//! provider in the Prometheus text exposition format.
+
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::fmt::Write;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::Weak;
+
+use deno_core::futures::FutureExt;
+use http_body_util::Full;
+use hyper::Method;
+use hyper::Request;
+use hyper::Response;
+use hyper::StatusCode;
+use hyper::body::Bytes;
+use hyper::body::Incoming;
+use hyper::header::CONTENT_TYPE;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper_util::rt::TokioIo;
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::metrics::InstrumentKind;
+use opentelemetry_sdk::metrics::ManualReader;
+use opentelemetry_sdk::metrics::MetricResult;
+use opentelemetry_sdk::metrics::Pipeline;
+use opentelemetry_sdk::metrics::Temporality;
+use opentelemetry_sdk::metrics::data;
+use opentelemetry_sdk::metrics::reader::MetricReader;
+
+use crate::OTEL_SHARED_RUNTIME_SPAWN_TASK_TX;
+use crate::run_pre_collect_callbacks;
+
+const CONTENT_TYPE_TEXT: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// A metric reader that is collected on demand whenever the Prometheus
+/// endpoint is scraped. Prometheus expects cumulative values, so the reader
+/// ignores the configured OTLP temporality preference.
+#[derive(Debug, Clone)]
+pub struct PrometheusReader(Arc<ManualReader>);
+
+impl PrometheusReader {
+  pub fn new() -> Self {
+    Self(Arc::new(
+      ManualReader::builder()
+        .with_temporality(Temporality::Cumulative)
+        .build(),
+    ))
+  }
+
+  /// Starts serving `/metrics` on the given address. The listener is bound
+  /// synchronously so that an unavailable port is reported at startup.
+  pub fn serve(&self, addr: SocketAddr) -> std::io::Result<()> {
+    let listener = std::net::TcpListener::bind(addr)?;
+    listener.set_nonblocking(true)?;
+
+    let reader = self.clone();
+    let worker = async move {
+      let listener = match tokio::net::TcpListener::from_std(listener) {
+        Ok(listener) => listener,
+        Err(err) => {
+          log::error!("Failed to start Prometheus metrics endpoint: {err}");
+          return;
+        }
+      };
+      loop {
+        let Ok((stream, _)) = listener.accept().await else {
+          continue;
+        };
+        let reader = reader.clone();
+        tokio::spawn(async move {
+          let service = service_fn(move |req| {
+            let reader = reader.clone();
+            async move { reader.handle(req).await }
+          });
+          let _ = http1::Builder::new()
+            .serve_connection(TokioIo::new(stream), service)
+            .await;
+        });
+      }
+    };
+
+    (*OTEL_SHARED_RUNTIME_SPAWN_TASK_TX)
+      .unbounded_send(worker.boxed())
+      .expect("failed to send task to shared OpenTelemetry runtime");
+
+    Ok(())
+  }
+
+  async fn handle(
+    &self,
+    req: Request<Incoming>,
+  ) -> Result<Response<Full<Bytes>>, Infallible> {
+    if req.uri().path() != "/metrics" {
+      return Ok(status_response(StatusCode::NOT_FOUND));
+    }
+    if req.method() != Method::GET && req.method() != Method::HEAD {
+      return Ok(status_response(StatusCode::METHOD_NOT_ALLOWED));
+    }
+
+    run_pre_collect_callbacks().await;
+
+    let mut resource_metrics = data::ResourceMetrics {
+      resource: Default::default(),
+      scope_metrics: Default::default(),
+    };
+    if let Err(err) = self.0.collect(&mut resource_metrics) {
+      log::error!("Failed to collect metrics for Prometheus: {err}");
+      return Ok(status_response(StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    let body = if req.method() == Method::HEAD {
+      Bytes::new()
+    } else {
+      Bytes::from(encode(&resource_metrics))
+    };
+    Ok(
+      Response::builder()
+        .header(CONTENT_TYPE, CONTENT_TYPE_TEXT)
+        .body(Full::new(body))
+        .unwrap(),
+    )
+  }
+}
+
+impl MetricReader for PrometheusReader {
+  fn register_pipeline(&self, pipeline: Weak<Pipeline>) {
+    self.0.register_pipeline(pipeline);
+  }
+
+  fn collect(&self, rm: &mut data::ResourceMetrics) -> MetricResult<()> {
+    self.0.collect(rm)
+  }
+
+  fn force_flush(&self) -> MetricResult<()> {
+    self.0.force_flush()
+  }
+
+  fn shutdown(&self) -> MetricResult<()> {
+    self.0.shutdown()
+  }
+
+  fn temporality(&self, kind: InstrumentKind) -> Temporality {
+    self.0.temporality(kind)
+  }
+}
+
+fn status_response(status: StatusCode) -> Response<Full<Bytes>> {
+  Response::builder()
+    .status(status)
+    .body(Full::new(Bytes::new()))
+    .unwrap()
+}
+
+trait SampleValue: Copy + 'static {
+  fn to_f64(self) -> f64;
+}
+
+impl SampleValue for f64 {
+  fn to_f64(self) -> f64 {
+    self
+  }
+}
+
+impl SampleValue for u64 {
+  fn to_f64(self) -> f64 {
+    self as f64
+  }
+}
+
+impl SampleValue for i64 {
+  fn to_f64(self) -> f64 {
+    self as f64
+  }
+}
+
+#[derive(Default)]
+struct MetricFamily {
+  kind: &'static str,
+  help: String,
+  samples: Vec<String>,
+}
+
+/// Encodes collected metrics in the Prometheus text exposition format.
+///
+/// Metric and label names are sanitized, units are appended as suffixes and
+/// monotonic sums become `_total` counters. Each sample carries the
+/// `otel_scope_name` and `otel_scope_version` labels, and the resource is
+/// exposed as the `target_info` metric.
+pub fn encode(resource_metrics: &data::ResourceMetrics) -> String {
+  let mut families = BTreeMap::<String, MetricFamily>::new();
+
+  for scope_metrics in &resource_metrics.scope_metrics {
+    let scope = &scope_metrics.scope;
+    let mut scope_labels =
+      vec![KeyValue::new("otel_scope_name", scope.name().to_owned())];
+    if let Some(version) = scope.version() {
+      scope_labels
+        .push(KeyValue::new("otel_scope_version", version.to_owned()));
+    }
+
+    for metric in &scope_metrics.metrics {
+      let data = metric.data.as_any();
+      let name = metric_name(&metric.name, &metric.unit);
+      if let Some(family) = encode_sums::<f64>(data, &name, &scope_labels)
+        .or_else(|| encode_sums::<u64>(data, &name, &scope_labels))
+        .or_else(|| encode_sums::<i64>(data, &name, &scope_labels))
+        .or_else(|| encode_gauges::<f64>(data, &name, &scope_labels))
+        .or_else(|| encode_gauges::<u64>(data, &name, &scope_labels))
+        .or_else(|| encode_gauges::<i64>(data, &name, &scope_labels))
+        .or_else(|| encode_histograms::<f64>(data, &name, &scope_labels))
+        .or_else(|| encode_histograms::<u64>(data, &name, &scope_labels))
+        .or_else(|| encode_histograms::<i64>(data, &name, &scope_labels))
+      {
+        let (name, kind, samples) = family;
+        let entry = families.entry(name).or_insert_with(|| MetricFamily {
+          kind,
+          help: metric.description.to_string(),
+          ..Default::default()
+        });
+        // Instruments with the same name but a different type can't be
+        // represented in a single family, so only the first one is kept.
+        if entry.kind == kind {
+          entry.samples.extend(samples);
+        }
+      }
+    }
+  }
+
+  let mut out = String::new();
+
+  let resource_labels = resource_metrics
+    .resource
+    .iter()
+    .map(|(key, value)| KeyValue::new(key.clone(), value.clone()))
+    .collect::<Vec<_>>();
+  if !resource_labels.is_empty() {
+    out.push_str("# HELP target_info Target metadata\n");
+    out.push_str("# TYPE target_info gauge\n");
+    let _ = writeln!(out, "target_info{} 1", labels(&resource_labels, &[]));
+  }
+
+  for (name, family) in families {
+    if !family.help.is_empty() {
+      let _ = writeln!(out, "# HELP {name} {}", escape_help(&family.help));
+    }
+    let _ = writeln!(out, "# TYPE {name} {}", family.kind);
+    for sample in family.samples {
+      out.push_str(&sample);
+    }
+  }
+
+  out
+}
+
+type EncodedFamily = (String, &'static str, Vec<String>);
+
+fn encode_sums<T: SampleValue>(
+  data: &dyn std::any::Any,
+  name: &str,
+  scope_labels: &[KeyValue],
+) -> Option<EncodedFamily> {
+  let sum = data.downcast_ref::<data::Sum<T>>()?;
+  let (name, kind) = if sum.is_monotonic {
+    (format!("{name}_total"), "counter")
+  } else {
+    (name.to_owned(), "gauge")
+  };
+  let samples = sum
+    .data_points
+    .iter()
+    .map(|point| {
+      format!(
+        "{name}{} {}\n",
+        labels(&point.attributes, scope_labels),
+        format_value(point.value.to_f64())
+      )
+    })
+    .collect();
+  Some((name, kind, samples))
+}
+
+fn encode_gauges<T: SampleValue>(
+  data: &dyn std::any::Any,
+  name: &str,
+  scope_labels: &[KeyValue],
+) -> Option<EncodedFamily> {
+  let gauge = data.downcast_ref::<data::Gauge<T>>()?;
+  let samples = gauge
+    .data_points
+    .iter()
+    .map(|point| {
+      format!(
+        "{name}{} {}\n",
+        labels(&point.attributes, scope_labels),
+        format_value(point.value.to_f64())
+      )
+    })
+    .collect();
+  Some((name.to_owned(), "gauge", samples))
+}
+
+fn encode_histograms<T: SampleValue>(
+  data: &dyn std::any::Any,
+  name: &str,
+  scope_labels: &[KeyValue],
+) -> Option<EncodedFamily> {
+  let histogram = data.downcast_ref::<data::Histogram<T>>()?;
+  let mut samples = Vec::with_capacity(histogram.data_points.len());
+  for point in &histogram.data_points {
+    let mut sample = String::new();
+    let mut cumulative = 0;
+    for (i, count) in point.bucket_counts.iter().enumerate() {
+      cumulative += count;
+      let le = point
+        .bounds
+        .get(i)
+        .map(|bound| format_value(*bound))
+        .unwrap_or_else(|| "+Inf".to_owned());
+      let mut bucket_labels = scope_labels.to_vec();
+      bucket_labels.push(KeyValue::new("le", le));
+      let _ = writeln!(
+        sample,
+        "{name}_bucket{} {cumulative}",
+        labels(&point.attributes, &bucket_labels)
+      );
+    }
+    let point_labels = labels(&point.attributes, scope_labels);
+    let _ = writeln!(
+      sample,
+      "{name}_sum{point_labels} {}",
+      format_value(point.sum.to_f64())
+    );
+    let _ = writeln!(sample, "{name}_count{point_labels} {}", point.count);
+    samples.push(sample);
+  }
+  Some((name.to_owned(), "histogram", samples))
+}
+
+/// Builds the Prometheus metric name from an OpenTelemetry instrument name
+/// and unit, e.g. `http.server.request.duration` with unit `s` becomes
+/// `http_server_request_duration_seconds`.
+fn metric_name(name: &str, unit: &str) -> String {
+  let mut name = sanitize_name(name);
+  if let Some(suffix) = unit_suffix(unit)
+    && !name.ends_with(&format!("_{suffix}"))
+  {
+    name.push('_');
+    name.push_str(&suffix);
+  }
+  name
+}
+
+fn unit_suffix(unit: &str) -> Option<String> {
+  // Annotations such as `{request}` carry no unit information.
+  let unit = match unit.find('{') {
+    Some(start) => &unit[..start],
+    None => unit,
+  };
+  let suffix = match unit {
+    "" | "1" => return None,
+    "d" => "days",
+    "h" => "hours",
+    "min" => "minutes",
+    "s" => "seconds",
+    "ms" => "milliseconds",
+    "us" => "microseconds",
+    "ns" => "nanoseconds",
+    "By" => "bytes",
+    "KiBy" => "kibibytes",
+    "MiBy" => "mebibytes",
+    "GiBy" => "gibibytes",
+    "KBy" => "kilobytes",
+    "MBy" => "megabytes",
+    "GBy" => "gigabytes",
+    "%" => "percent",
+    "Hz" => "hertz",
+    "V" => "volts",
+    "W" => "watts",
+    unit => {
+      let unit = sanitize_name(unit);
+      let unit = unit.trim_matches('_');
+      if unit.is_empty() {
+        return None;
+      }
+      return Some(unit.to_owned());
+    }
+  };
+  Some(suffix.to_owned())
+}
+
+fn sanitize_name(name: &str) -> String {
+  let mut out = String::with_capacity(name.len());
+  for (i, c) in name.chars().enumerate() {
+    if i == 0 && c.is_ascii_digit() {
+      out.push('_');
+    }
+    if c.is_ascii_alphanumeric() || c == '_' || c == ':' {
+      out.push(c);
+    } else {
+      out.push('_');
+    }
+  }
+  out
+}
+
+fn sanitize_label_name(name: &str) -> String {
+  sanitize_name(name).replace(':', "_")
+}
+
+fn labels(attributes: &[KeyValue], extra: &[KeyValue]) -> String {
+  if attributes.is_empty() && extra.is_empty() {
+    return String::new();
+  }
+  let mut out = String::from("{");
+  for (i, kv) in attributes.iter().chain(extra).enumerate() {
+    if i > 0 {
+      out.push(',');
+    }
+    let _ = write!(
+      out,
+      "{}=\"{}\"",
+      sanitize_label_name(kv.key.as_str()),
+      escape_label_value(&kv.value.as_str())
+    );
+  }
+  out.push('}');
+  out
+}
+
+fn escape_label_value(value: &str) -> String {
+  value
+    .replace('\\', "\\\\")
+    .replace('"', "\\\"")
+    .replace('\n', "\\n")
+}
+
+fn escape_help(help: &str) -> String {
+  help.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn format_value(value: f64) -> String {
+  if value.is_nan() {
+    "NaN".to_owned()
+  } else if value == f64::INFINITY {
+    "+Inf".to_owned()
+  } else if value == f64::NEG_INFINITY {
+    "-Inf".to_owned()
+  } else {
+    value.to_string()
+  }
+}
@@ -21,6 +21,8 @@ import {
   op_otel_span_attribute1,
   op_otel_span_attribute2,
   op_otel_span_attribute3,
+  op_otel_span_mark,
+  op_otel_span_mark_foreign,
   op_otel_span_update_name,
   OtelMeter,
   OtelSpan,
@@ -66,18 +68,30 @@ const {
   SafeSet,
   SafeWeakSet,
   StringPrototypeIndexOf,
+  StringPrototypePadStart,
   StringPrototypeSlice,
   StringPrototypeSplit,
+  StringPrototypeStartsWith,
   StringPrototypeSubstring,
+  StringPrototypeToLowerCase,
   StringPrototypeTrim,
   SymbolFor,
   TypeError,
 } = primordials;
-const { AsyncVariable, getAsyncContext, setAsyncContext } = core;
+const {
+  AsyncContext,
+  enterAsyncContextVariable,
+  getAsyncContext,
+  setAsyncContext,
+} = core;
 
 export let TRACING_ENABLED = false;
 export let METRICS_ENABLED = false;
 export let PROPAGATORS: TextMapPropagator[] = [];
+export let KV_TRACING_ENABLED = false;
+export let COMMAND_TRACING_ENABLED = false;
+export let FS_TRACING_ENABLED = false;
+export let FS_TRACING_THRESHOLD_MS = 0;
 let ISOLATE_METRICS = false;
 
 // Note: These start at 0 in the JS library,
@@ -207,15 +221,48 @@ export function enterSpan(
   span: Span,
   context?: Context,
 ): AsyncContextSnapshot | undefined {
-  if (!span.isRecording()) return undefined;
+  // Spans that are not sampled are still entered, so that their trace id and
+  // sampling decision propagate to child spans.
+  if (!getOtelSpan(span)) return undefined;
   context = (context ?? CURRENT.get() ?? ROOT_CONTEXT)
     .setValue(SPAN_KEY, span);
-  return CURRENT.enter(context);
+  return enterContext(context);
 }
 
 export const currentSnapshot = getAsyncContext;
 export const restoreSnapshot = setAsyncContext;
 
+function enterContext(context: Context): AsyncContextSnapshot {
+  return enterAsyncContextVariable(CURRENT, context);
+}
+
+type MarkedSpan = Span | { spanContext(): SpanContext } | undefined;
+
+let markedSpan: MarkedSpan = undefined;
+
+// Tells the CPU profiler which span is current from now on, so that it can
+// label the samples it takes with its trace and span id.
+function markSpan(span: MarkedSpan) {
+  if (span === markedSpan) return;
+  markedSpan = span;
+  const otelSpan = span !== undefined ? getOtelSpan(span) : undefined;
+  if (otelSpan || span === undefined) {
+    op_otel_span_mark(otelSpan);
+  } else {
+    const spanContext = span.spanContext();
+    op_otel_span_mark_foreign(spanContext.traceId, spanContext.spanId);
+  }
+}
+
+function markCurrentSpan() {
+  markSpan(CURRENT.get()?.getValue(SPAN_KEY) as MarkedSpan);
+}
+
+// V8 clears the async context once a promise reaction has run.
+function markNoSpan() {
+  markSpan(undefined);
+}
+
 function isDate(value: unknown): value is Date {
   return ObjectPrototypeIsPrototypeOf(DatePrototype, value);
 }
@@ -237,6 +284,7 @@ interface OtelTracer {
   startSpanForeign(
     parentTraceId: string,
     parentSpanId: string,
+    parentTraceFlags: number,
     name: string,
     spanKind: SpanKind,
     startTime: number | undefined,
@@ -248,6 +296,7 @@ interface OtelSpan {
   __key: "span";
 
   spanContext(): SpanContext;
+  isRecording(): boolean;
   setStatus(status: SpanStatusCode, errorDescription: string): void;
   addEvent(
     name: string,
@@ -374,11 +423,11 @@ class Tracer {
       context = context ?? CURRENT.get() ?? ROOT_CONTEXT;
     }
     const span = this.startSpan(name, options, context);
-    const ctx = CURRENT.enter(context.setValue(SPAN_KEY, span));
+    const ctx = enterContext(context.setValue(SPAN_KEY, span));
     try {
       return ReflectApply(fn, undefined, [span]);
     } finally {
-      setAsyncContext(ctx);
+      restoreSnapshot(ctx);
     }
   }
 
@@ -413,6 +462,7 @@ class Tracer {
       otelSpan = this.#tracer.startSpanForeign(
         spanContext.traceId,
         spanContext.spanId,
+        spanContext.traceFlags ?? 0,
         name,
         options?.kind ?? 0,
         startTime,
@@ -519,7 +569,7 @@ class Span {
   }
 
   isRecording(): boolean {
-    return this.#otelSpan !== undefined;
+    return this.#otelSpan?.isRecording() ?? false;
   }
 
   recordException(exception: string | Exception, time?: TimeInput): void {
@@ -580,7 +630,10 @@ class Span {
   }
 }
 
-const CURRENT = new AsyncVariable();
+// The active context is an `AsyncContext.Variable`, so it is captured by
+// `AsyncContext.Snapshot` and flows through the same callbacks as user
+// variables do.
+const CURRENT = new AsyncContext.Variable({ name: "otel.context" });
 
 class Context {
   // @ts-ignore __proto__ is not supported in TypeScript
@@ -627,11 +680,11 @@ export class ContextManager {
     thisArg?: ThisParameterType<F>,
     ...args: A
   ): ReturnType<F> {
-    const ctx = CURRENT.enter(context);
+    const ctx = enterContext(context);
     try {
       return ReflectApply(fn, thisArg, args);
     } finally {
-      setAsyncContext(ctx);
+      restoreSnapshot(ctx);
     }
   }
 
@@ -641,11 +694,11 @@ export class ContextManager {
     target: T,
   ): T {
     return ((...args) => {
-      const ctx = CURRENT.enter(context);
+      const ctx = enterContext(context);
       try {
         return ReflectApply(target, this, args);
       } finally {
-        setAsyncContext(ctx);
+        restoreSnapshot(ctx);
       }
     }) as T;
   }
@@ -1243,6 +1296,15 @@ const VALID_KEY_REGEX = new SafeRegExp(
 const VALID_VALUE_BASE_REGEX = new SafeRegExp("^[ -~]{0,255}[!-~]$");
 const INVALID_VALUE_COMMA_EQUAL_REGEX = new SafeRegExp(",|=");
 
+const B3_CONTEXT_HEADER = "b3";
+const X_B3_TRACE_ID = "x-b3-traceid";
+const X_B3_SPAN_ID = "x-b3-spanid";
+const X_B3_SAMPLED = "x-b3-sampled";
+const X_B3_PARENT_SPAN_ID = "x-b3-parentspanid";
+const X_B3_FLAGS = "x-b3-flags";
+const B3_TRACE_ID_REGEX = new SafeRegExp("^([0-9a-f]{16}){1,2}$", "i");
+const UBER_TRACE_ID_HEADER = "uber-trace-id";
+const UBER_BAGGAGE_HEADER_PREFIX = "uberctx-";
 const TRACE_PARENT_HEADER = "traceparent";
 const TRACE_STATE_HEADER = "tracestate";
 const INVALID_TRACEID = "00000000000000000000000000000000";
@@ -1311,6 +1373,15 @@ const otelPropagators = {
   traceContext: 0,
   baggage: 1,
   none: 2,
+  b3: 3,
+  b3Multi: 4,
+  jaeger: 5,
+};
+
+const otelInstrumentations = {
+  kv: 1 << 0,
+  command: 1 << 1,
+  fs: 1 << 2,
 };
 
 function parseTraceParent(traceParent: string): SpanContext | null {
@@ -1741,6 +1812,199 @@ export class W3CBaggagePropagator implements TextMapPropagator {
   }
 }
 
+function getSingleHeader(
+  carrier: unknown,
+  getter: TextMapGetter,
+  key: string,
+): string | undefined {
+  const value = getter.get(carrier, key);
+  const header = ArrayIsArray(value) ? value[0] : value;
+  return typeof header === "string" ? StringPrototypeTrim(header) : undefined;
+}
+
+function isSampledFlag(value: string | undefined): boolean | undefined {
+  if (value === undefined) return undefined;
+  if (value === "1" || value === "d" || value === "true") return true;
+  if (value === "0" || value === "false") return false;
+  return undefined;
+}
+
+/**
+ * Propagates trace context using the Zipkin B3 headers. Both the single `b3`
+ * header and the multi `X-B3-*` headers are accepted when extracting, while
+ * `singleHeader` controls which encoding is used when injecting.
+ */
+class B3Propagator implements TextMapPropagator {
+  #singleHeader: boolean;
+
+  constructor(singleHeader: boolean) {
+    this.#singleHeader = singleHeader;
+  }
+
+  inject(context: Context, carrier: unknown, setter: TextMapSetter): void {
+    const spanContext = (context.getValue(SPAN_KEY) as Span | undefined)
+      ?.spanContext();
+    if (
+      !spanContext ||
+      isTracingSuppressed(context) ||
+      !isSpanContextValid(spanContext)
+    ) {
+      return;
+    }
+
+    const sampled = (spanContext.traceFlags & 1) === 1 ? "1" : "0";
+    if (this.#singleHeader) {
+      setter.set(
+        carrier,
+        B3_CONTEXT_HEADER,
+        `${spanContext.traceId}-${spanContext.spanId}-${sampled}`,
+      );
+    } else {
+      setter.set(carrier, X_B3_TRACE_ID, spanContext.traceId);
+      setter.set(carrier, X_B3_SPAN_ID, spanContext.spanId);
+      setter.set(carrier, X_B3_SAMPLED, sampled);
+    }
+  }
+
+  extract(context: Context, carrier: unknown, getter: TextMapGetter): Context {
+    let traceId: string | undefined;
+    let spanId: string | undefined;
+    let sampled: boolean | undefined;
+
+    const single = getSingleHeader(carrier, getter, B3_CONTEXT_HEADER);
+    if (single) {
+      const parts = StringPrototypeSplit(single, "-");
+      if (parts.length === 1) {
+        // A lone sampling decision, e.g. `b3: 0`, carries no trace context.
+        return context;
+      }
+      traceId = parts[0];
+      spanId = parts[1];
+      sampled = isSampledFlag(parts[2]);
+    } else {
+      traceId = getSingleHeader(carrier, getter, X_B3_TRACE_ID);
+      spanId = getSingleHeader(carrier, getter, X_B3_SPAN_ID);
+      sampled = getSingleHeader(carrier, getter, X_B3_FLAGS) === "1"
+        ? true
+        : isSampledFlag(getSingleHeader(carrier, getter, X_B3_SAMPLED));
+    }
+
+    if (!traceId || !spanId || !B3_TRACE_ID_REGEX.test(traceId)) {
+      return context;
+    }
+    const spanContext: SpanContext = {
+      traceId: StringPrototypePadStart(
+        StringPrototypeToLowerCase(traceId),
+        32,
+        "0",
+      ),
+      spanId: StringPrototypeToLowerCase(spanId),
+      traceFlags: sampled ? 1 : 0,
+      isRemote: true,
+    };
+    if (!isSpanContextValid(spanContext)) return context;
+    return context.setValue(SPAN_KEY, new NonRecordingSpan(spanContext));
+  }
+
+  fields(): string[] {
+    if (this.#singleHeader) return [B3_CONTEXT_HEADER];
+    return [
+      X_B3_TRACE_ID,
+      X_B3_SPAN_ID,
+      X_B3_FLAGS,
+      X_B3_SAMPLED,
+      X_B3_PARENT_SPAN_ID,
+    ];
+  }
+}
+
+/**
+ * Propagates trace context using the Jaeger `uber-trace-id` header, and
+ * baggage using `uberctx-` prefixed headers.
+ */
+class JaegerPropagator implements TextMapPropagator {
+  inject(context: Context, carrier: unknown, setter: TextMapSetter): void {
+    if (isTracingSuppressed(context)) return;
+
+    const spanContext = (context.getValue(SPAN_KEY) as Span | undefined)
+      ?.spanContext();
+    if (spanContext && isSpanContextValid(spanContext)) {
+      const traceFlags = StringPrototypePadStart(
+        NumberPrototypeToString(Number(spanContext.traceFlags || 0), 16),
+        2,
+        "0",
+      );
+      setter.set(
+        carrier,
+        UBER_TRACE_ID_HEADER,
+        `${spanContext.traceId}:${spanContext.spanId}:0:${traceFlags}`,
+      );
+    }
+
+    const baggage = context.getValue(BAGGAGE_KEY) as Baggage | undefined;
+    if (baggage) {
+      ArrayPrototypeForEach(baggage.getAllEntries(), (entry) => {
+        setter.set(
+          carrier,
+          `${UBER_BAGGAGE_HEADER_PREFIX}${entry[0]}`,
+          encodeURIComponent(entry[1].value),
+        );
+      });
+    }
+  }
+
+  extract(context: Context, carrier: unknown, getter: TextMapGetter): Context {
+    const header = getSingleHeader(carrier, getter, UBER_TRACE_ID_HEADER);
+    if (header) {
+      const parts = StringPrototypeSplit(decodeURIComponent(header), ":");
+      if (parts.length === 4) {
+        const spanContext: SpanContext = {
+          traceId: StringPrototypePadStart(
+            StringPrototypeToLowerCase(parts[0]),
+            32,
+            "0",
+          ),
+          spanId: StringPrototypePadStart(
+            StringPrototypeToLowerCase(parts[1]),
+            16,
+            "0",
+          ),
+          traceFlags: NumberParseInt(parts[3], 16) & 1,
+          isRemote: true,
+        };
+        if (isSpanContextValid(spanContext)) {
+          context = context.setValue(
+            SPAN_KEY,
+            new NonRecordingSpan(spanContext),
+          );
+        }
+      }
+    }
+
+    let baggage = context.getValue(BAGGAGE_KEY) as Baggage | undefined;
+    ArrayPrototypeForEach(getter.keys(carrier), (key) => {
+      const lowerKey = StringPrototypeToLowerCase(key);
+      if (!StringPrototypeStartsWith(lowerKey, UBER_BAGGAGE_HEADER_PREFIX)) {
+        return;
+      }
+      const value = getSingleHeader(carrier, getter, key);
+      if (value === undefined) return;
+      baggage = (baggage ?? new BaggageImpl()).setEntry(
+        StringPrototypeSlice(lowerKey, UBER_BAGGAGE_HEADER_PREFIX.length),
+        { value: decodeURIComponent(value) },
+      );
+    });
+    if (baggage) {
+      context = context.setValue(BAGGAGE_KEY, baggage);
+    }
+    return context;
+  }
+
+  fields(): string[] {
+    return [UBER_TRACE_ID_HEADER];
+  }
+}
+
 export class CompositePropagator implements TextMapPropagator {
   #propagators: TextMapPropagator[];
   #fields: string[];
@@ -1820,6 +2084,12 @@ export function bootstrap(
     0 | 1,
     0 | 1,
     (typeof otelConsoleConfig)[keyof typeof otelConsoleConfig],
+    number,
+    0 | 1,
+    number,
+    number,
+    number,
+    number,
     ...Array<(typeof otelPropagators)[keyof typeof otelPropagators]>,
   ],
 ): void {
@@ -1827,11 +2097,33 @@ export function bootstrap(
     0: tracingEnabled,
     1: metricsEnabled,
     2: consoleConfig,
+    3: instrumentations,
+    4: spanMarks,
+    5: fsThreshold0,
+    6: fsThreshold1,
+    7: fsThreshold2,
+    8: fsThreshold3,
     ...propagators
   } = config;
 
   TRACING_ENABLED = tracingEnabled === 1;
   METRICS_ENABLED = metricsEnabled === 1;
+  KV_TRACING_ENABLED = TRACING_ENABLED &&
+    (instrumentations & otelInstrumentations.kv) !== 0;
+  COMMAND_TRACING_ENABLED = TRACING_ENABLED &&
+    (instrumentations & otelInstrumentations.command) !== 0;
+  FS_TRACING_ENABLED = TRACING_ENABLED &&
+    (instrumentations & otelInstrumentations.fs) !== 0;
+  // little endian u32
+  FS_TRACING_THRESHOLD_MS = (fsThreshold0 | fsThreshold1 << 8 |
+    fsThreshold2 << 16 | fsThreshold3 << 24) >>> 0;
+  if (TRACING_ENABLED && spanMarks === 1) {
+    // JS code switches the async context through `setAsyncContext`, while V8
+    // switches it itself around promise reactions, such as the continuation
+    // of an `await`.
+    core.setAsyncContextChangeHook(markCurrentSpan);
+    core.setPromiseHooks(undefined, markCurrentSpan, markNoSpan, undefined);
+  }
 
   PROPAGATORS = ArrayPrototypeMap(
     ArrayPrototypeFilter(
@@ -1844,6 +2136,12 @@ export function bootstrap(
           return new W3CTraceContextPropagator();
         case otelPropagators.baggage:
           return new W3CBaggagePropagator();
+        case otelPropagators.b3:
+          return new B3Propagator(true);
+        case otelPropagators.b3Multi:
+          return new B3Propagator(false);
+        case otelPropagators.jaeger:
+          return new JaegerPropagator();
       }
     },
   );
@@ -1,9 +1,15 @@
 // Copyright 2018-2026 the Deno authors. MIT license.
 
 import { primordials } from "ext:core/mod.js";
+import {
+  builtinTracer,
+  enterSpan,
+  restoreSnapshot,
+} from "ext:deno_telemetry/telemetry.ts";
 import type { Span } from "ext:deno_telemetry/telemetry.ts";
+import { now } from "ext:deno_web/15_performance.js";
 
-const { String, StringPrototypeSlice } = primordials;
+const { DateNow, String, StringPrototypeSlice } = primordials;
 
 export function updateSpanFromRequest(span: Span, request: Request) {
   span.updateName(request.method);
@@ -43,3 +49,92 @@ export function updateSpanFromError(span: Span, error: any) {
   }
   span.setStatus({ code: 2, message: error.message ?? String(error) });
 }
+
+type SpanAttributes = Record<string, string | number | boolean>;
+
+/**
+ * Runs `fn` inside a new span from the builtin tracer. The span ends once the
+ * result of `fn` settles, and records the error if it throws or rejects.
+ */
+export async function traceAsync<T>(
+  name: string,
+  kind: number,
+  attributes: SpanAttributes,
+  fn: (span: Span) => T | Promise<T>,
+): Promise<T> {
+  const span = builtinTracer().startSpan(name, { kind, attributes });
+  try {
+    const snapshot = enterSpan(span);
+    let result;
+    try {
+      result = fn(span);
+    } finally {
+      if (snapshot) restoreSnapshot(snapshot);
+    }
+    return await result;
+  } catch (error) {
+    updateSpanFromError(span, error);
+    throw error;
+  } finally {
+    span.end();
+  }
+}
+
+/**
+ * Like {@linkcode traceAsync}, but the span is only recorded if the result of
+ * `fn` takes at least `thresholdMs` milliseconds to settle. The span is
+ * created once `fn` settles, so it is not the current span while `fn` runs.
+ */
+export async function traceAsyncAboveThreshold<T>(
+  name: string,
+  kind: number,
+  attributes: SpanAttributes,
+  thresholdMs: number,
+  fn: () => T | Promise<T>,
+): Promise<T> {
+  const startTime = DateNow();
+  const start = now();
+  let failed = false;
+  // deno-lint-ignore no-explicit-any
+  let error: any;
+  try {
+    return await fn();
+  } catch (e) {
+    failed = true;
+    error = e;
+    throw e;
+  } finally {
+    const duration = now() - start;
+    if (duration >= thresholdMs) {
+      const span = builtinTracer().startSpan(name, {
+        kind,
+        attributes,
+        startTime,
+      });
+      if (failed) updateSpanFromError(span, error);
+      span.end(startTime + duration);
+    }
+  }
+}
+
+/**
+ * Synchronous variant of {@linkcode traceAsync}.
+ */
+export function traceSync<T>(
+  name: string,
+  kind: number,
+  attributes: SpanAttributes,
+  fn: (span: Span) => T,
+): T {
+  const span = builtinTracer().startSpan(name, { kind, attributes });
+  const snapshot = enterSpan(span);
+  try {
+    return fn(span);
+  } catch (error) {
+    updateSpanFromError(span, error);
+    throw error;
+  } finally {
+    if (snapshot) restoreSnapshot(snapshot);
+    span.end();
+  }
+}
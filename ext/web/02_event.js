@@ -1223,6 +1223,72 @@ class CloseEvent extends Event {
 
 const CloseEventPrototype = CloseEvent.prototype;
 
+class StorageEvent extends Event {
+  #key = null;
+  #oldValue = null;
+  #newValue = null;
+  #url = "";
+  #storageArea = null;
+
+  get key() {
+    return this.#key;
+  }
+  get oldValue() {
+    return this.#oldValue;
+  }
+  get newValue() {
+    return this.#newValue;
+  }
+  get url() {
+    return this.#url;
+  }
+  get storageArea() {
+    return this.#storageArea;
+  }
+
+  constructor(type, {
+    bubbles,
+    cancelable,
+    composed,
+    key = null,
+    oldValue = null,
+    newValue = null,
+    url = "",
+    storageArea = null,
+  } = { __proto__: null }) {
+    super(type, {
+      bubbles: bubbles,
+      cancelable: cancelable,
+      composed: composed,
+    });
+
+    this.#key = key;
+    this.#oldValue = oldValue;
+    this.#newValue = newValue;
+    this.#url = url;
+    this.#storageArea = storageArea;
+  }
+
+  [SymbolFor("Deno.privateCustomInspect")](inspect, inspectOptions) {
+    return inspect(
+      createFilteredInspectProxy({
+        object: this,
+        evaluate: ObjectPrototypeIsPrototypeOf(StorageEventPrototype, this),
+        keys: [
+          ...new SafeArrayIterator(EVENT_PROPS),
+          "key",
+          "oldValue",
+          "newValue",
+          "url",
+        ],
+      }),
+      inspectOptions,
+    );
+  }
+}
+
+const StorageEventPrototype = StorageEvent.prototype;
+
 class MessageEvent extends Event {
   get source() {
     return null;
@@ -1561,4 +1627,5 @@ export {
   setEventTargetData,
   setIsTrusted,
   setTarget,
+  StorageEvent,
 };
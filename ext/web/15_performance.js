@@ -805,6 +805,7 @@ webidl.converters["Performance"] = webidl.createInterfaceConverter(
 const performance = new Performance(illegalConstructorKey);
 
 export {
+  now,
   Performance,
   performance,
   PerformanceEntry,
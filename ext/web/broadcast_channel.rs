@@ -3,6 +3,7 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::sync::Once;
 
 use deno_core::JsBuffer;
 use deno_core::OpState;
@@ -15,6 +16,12 @@ use tokio::sync::mpsc;
 use tokio::sync::mpsc::error::SendError as MpscSendError;
 use uuid::Uuid;
 
+#[cfg(unix)]
+mod unix_socket;
+
+#[cfg(unix)]
+pub use unix_socket::UnixSocketBroadcastChannelBackend;
+
 #[derive(Debug, thiserror::Error, deno_error::JsError)]
 pub enum BroadcastChannelError {
   #[class(inherit)]
@@ -104,11 +111,51 @@ pub async fn op_broadcast_recv(
   bc.recv(&resource).await
 }
 
+/// Connects the broadcast channels of this process to those of other
+/// processes, so that messages posted in one are received in all of them.
+pub trait BroadcastChannelBackend: Send + Sync {
+  /// Starts receiving messages from other processes, handing them to
+  /// `sink`. Called once, when a channel is first subscribed to.
+  fn start(&self, sink: BroadcastChannelSink) -> Result<(), std::io::Error>;
+
+  /// Sends a message posted in this process to all other processes.
+  fn publish(&self, name: &str, data: &[u8]);
+}
+
+/// Delivers messages received by a [`BroadcastChannelBackend`] to the
+/// channels of this process.
 #[derive(Clone)]
-pub struct InMemoryBroadcastChannel(
+pub struct BroadcastChannelSink(
   Arc<Mutex<broadcast::Sender<InMemoryChannelMessage>>>,
 );
 
+impl BroadcastChannelSink {
+  pub fn deliver(&self, name: String, data: Vec<u8>) {
+    // Fails only if nothing is subscribed, in which case there is no one to
+    // deliver the message to anyway.
+    let _ = self.0.lock().send(InMemoryChannelMessage {
+      name: Arc::new(name),
+      data: Arc::new(data),
+      // Never matches a resource, so it is delivered to all of them.
+      uuid: Uuid::nil(),
+    });
+  }
+}
+
+struct ConnectedBackend {
+  backend: Box<dyn BroadcastChannelBackend>,
+  started: Once,
+}
+
+/// Broadcasts messages between the channels of all workers sharing it and,
+/// if created with [`InMemoryBroadcastChannel::with_backend`], those of
+/// other processes.
+#[derive(Clone)]
+pub struct InMemoryBroadcastChannel {
+  tx: Arc<Mutex<broadcast::Sender<InMemoryChannelMessage>>>,
+  backend: Option<Arc<ConnectedBackend>>,
+}
+
 pub struct InMemoryBroadcastChannelResource {
   rx: tokio::sync::Mutex<(
     broadcast::Receiver<InMemoryChannelMessage>,
@@ -130,16 +177,40 @@ struct InMemoryChannelMessage {
 impl Default for InMemoryBroadcastChannel {
   fn default() -> Self {
     let (tx, _) = broadcast::channel(256);
-    Self(Arc::new(Mutex::new(tx)))
+    Self {
+      tx: Arc::new(Mutex::new(tx)),
+      backend: None,
+    }
   }
 }
 
 impl InMemoryBroadcastChannel {
+  pub fn with_backend(backend: Box<dyn BroadcastChannelBackend>) -> Self {
+    Self {
+      backend: Some(Arc::new(ConnectedBackend {
+        backend,
+        started: Once::new(),
+      })),
+      ..Default::default()
+    }
+  }
+
   fn subscribe(
     &self,
   ) -> Result<InMemoryBroadcastChannelResource, BroadcastChannelError> {
+    if let Some(connected) = &self.backend {
+      connected.started.call_once(|| {
+        let sink = BroadcastChannelSink(self.tx.clone());
+        if let Err(err) = connected.backend.start(sink) {
+          // Channels still work within this process.
+          log::warn!(
+            "Failed to connect BroadcastChannel to other processes: {err}"
+          );
+        }
+      });
+    }
     let (cancel_tx, cancel_rx) = mpsc::unbounded_channel();
-    let broadcast_rx = self.0.lock().subscribe();
+    let broadcast_rx = self.tx.lock().subscribe();
     let rx = tokio::sync::Mutex::new((broadcast_rx, cancel_rx));
     let uuid = Uuid::new_v4();
     Ok(InMemoryBroadcastChannelResource {
@@ -165,8 +236,11 @@ impl InMemoryBroadcastChannel {
     let name = Arc::new(name);
     let data = Arc::new(data);
     let uuid = resource.uuid;
+    if let Some(connected) = &self.backend {
+      connected.backend.publish(&name, &data);
+    }
     self
-      .0
+      .tx
       .lock()
       .send(InMemoryChannelMessage { name, data, uuid })?;
     Ok(())
@@ -0,0 +1,295 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+//! Shares broadcast channel messages between processes through Unix domain
+//! sockets in a common directory.
+//!
+//! Every process listens on `<pid>.sock` in the directory. To publish a
+//! message, a writer thread writes it to the socket of every other process
+//! found there, over a connection that is kept open for later messages.
+//! Sockets left behind by processes that have exited are removed by whoever
+//! first fails to connect to them.
+
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::io::Write;
+use std::os::unix::fs::DirBuilderExt;
+use std::os::unix::fs::MetadataExt;
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::UnixListener;
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use super::BroadcastChannelBackend;
+use super::BroadcastChannelSink;
+
+/// A peer that doesn't read its messages within this time is disconnected,
+/// so that it can't stall the process publishing to it.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Guards against allocating whatever length a corrupt frame claims.
+const MAX_FRAME_PART_LEN: usize = 256 * 1024 * 1024;
+
+/// Messages posted while this many are still waiting to be written to the
+/// peers are dropped, so that slow peers can't make them pile up in memory.
+const MAX_QUEUED_FRAMES: usize = 1024;
+
+pub struct UnixSocketBroadcastChannelBackend {
+  dir: PathBuf,
+  /// Set once the backend has started listening. Frames sent to it are
+  /// written to the peers by the writer thread.
+  writer: OnceLock<mpsc::SyncSender<Vec<u8>>>,
+}
+
+impl UnixSocketBroadcastChannelBackend {
+  /// Shares messages with all other processes that use the same `app_id`.
+  ///
+  /// The sockets are placed in a directory under `root_dir` that only the
+  /// current user can access, so processes of other users can neither
+  /// receive nor inject messages. Unix socket paths are limited to about
+  /// 100 bytes, so both `root_dir` and `app_id` should be short.
+  pub fn new(root_dir: &Path, app_id: &str) -> Self {
+    // SAFETY: libc call, geteuid(2) always succeeds
+    let uid = unsafe { libc::geteuid() };
+    Self {
+      dir: root_dir
+        .join(format!("deno_broadcast_channel_{uid}"))
+        .join(app_id),
+      writer: OnceLock::new(),
+    }
+  }
+
+  fn socket_path(&self) -> PathBuf {
+    self.dir.join(format!("{}.sock", std::process::id()))
+  }
+
+  /// Creates the directory, or checks that the existing one can't have been
+  /// tampered with by another user.
+  fn create_dir(&self) -> Result<(), std::io::Error> {
+    std::fs::DirBuilder::new()
+      .recursive(true)
+      .mode(0o700)
+      .create(&self.dir)?;
+    // SAFETY: libc call, geteuid(2) always succeeds
+    let uid = unsafe { libc::geteuid() };
+    for dir in [self.dir.parent().unwrap(), &self.dir] {
+      let metadata = std::fs::symlink_metadata(dir)?;
+      if !metadata.is_dir()
+        || metadata.uid() != uid
+        || metadata.permissions().mode() & 0o077 != 0
+      {
+        return Err(std::io::Error::new(
+          ErrorKind::PermissionDenied,
+          format!(
+            "{} must be a directory owned by and only accessible to the current user",
+            dir.display()
+          ),
+        ));
+      }
+    }
+    Ok(())
+  }
+}
+
+fn peer_socket_paths(dir: &Path, own: &Path) -> Vec<PathBuf> {
+  let Ok(entries) = std::fs::read_dir(dir) else {
+    return Vec::new();
+  };
+  entries
+    .filter_map(|entry| entry.ok())
+    .map(|entry| entry.path())
+    .filter(|path| {
+      path.extension().is_some_and(|ext| ext == "sock") && path != own
+    })
+    .collect()
+}
+
+impl BroadcastChannelBackend for UnixSocketBroadcastChannelBackend {
+  fn start(&self, sink: BroadcastChannelSink) -> Result<(), std::io::Error> {
+    self.create_dir()?;
+    let socket_path = self.socket_path();
+    // Left behind by an earlier process that had the same pid.
+    match std::fs::remove_file(&socket_path) {
+      Err(err) if err.kind() != ErrorKind::NotFound => return Err(err),
+      _ => {}
+    }
+    let listener = UnixListener::bind(&socket_path)?;
+    std::thread::Builder::new()
+      .name("broadcast-channel-listener".to_string())
+      .spawn(move || {
+        for stream in listener.incoming() {
+          let Ok(stream) = stream else {
+            continue;
+          };
+          let sink = sink.clone();
+          let result = std::thread::Builder::new()
+            .name("broadcast-channel-reader".to_string())
+            .spawn(move || read_frames(stream, sink));
+          if let Err(err) = result {
+            log::debug!("Failed to start BroadcastChannel reader: {err}");
+          }
+        }
+      })?;
+    let (tx, rx) = mpsc::sync_channel(MAX_QUEUED_FRAMES);
+    let dir = self.dir.clone();
+    std::thread::Builder::new()
+      .name("broadcast-channel-writer".to_string())
+      .spawn(move || write_frames(&dir, &socket_path, rx))?;
+    let _ = self.writer.set(tx);
+    Ok(())
+  }
+
+  fn publish(&self, name: &str, data: &[u8]) {
+    let Some(writer) = self.writer.get() else {
+      return;
+    };
+    if data.len() > MAX_FRAME_PART_LEN {
+      log::warn!(
+        "BroadcastChannel message of {} bytes is too large to send to other processes",
+        data.len()
+      );
+      return;
+    }
+    if let Err(mpsc::TrySendError::Full(_)) =
+      writer.try_send(encode_frame(name, data))
+    {
+      log::debug!(
+        "Dropped BroadcastChannel message, other processes are not reading them fast enough"
+      );
+    }
+  }
+}
+
+impl Drop for UnixSocketBroadcastChannelBackend {
+  fn drop(&mut self) {
+    if self.writer.get().is_some() {
+      let _ = std::fs::remove_file(self.socket_path());
+    }
+  }
+}
+
+/// Writes the frames sent by `publish` to all peers until the backend is
+/// dropped.
+fn write_frames(dir: &Path, own: &Path, rx: mpsc::Receiver<Vec<u8>>) {
+  let mut peers = HashMap::<PathBuf, UnixStream>::new();
+  for frame in rx {
+    let peer_paths = peer_socket_paths(dir, own);
+    peers.retain(|path, _| peer_paths.contains(path));
+    for path in peer_paths {
+      if let Some(stream) = peers.get_mut(&path) {
+        if stream.write_all(&frame).is_ok() {
+          continue;
+        }
+        // The peer may have restarted with the same pid, so reconnect.
+        peers.remove(&path);
+      }
+      match connect(&path) {
+        Ok(mut stream) => {
+          if stream.write_all(&frame).is_ok() {
+            peers.insert(path, stream);
+          }
+        }
+        Err(err)
+          if matches!(
+            err.kind(),
+            ErrorKind::ConnectionRefused | ErrorKind::NotFound
+          ) =>
+        {
+          // Nothing listens on it anymore.
+          let _ = std::fs::remove_file(&path);
+        }
+        Err(err) => {
+          log::debug!(
+            "Failed to connect to BroadcastChannel peer {}: {err}",
+            path.display()
+          );
+        }
+      }
+    }
+  }
+}
+
+fn connect(path: &Path) -> Result<UnixStream, std::io::Error> {
+  let stream = UnixStream::connect(path)?;
+  stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+  Ok(stream)
+}
+
+/// A frame is the name and the data of a message, each prefixed with its
+/// length as a big endian `u32`.
+fn encode_frame(name: &str, data: &[u8]) -> Vec<u8> {
+  let mut frame = Vec::with_capacity(8 + name.len() + data.len());
+  frame.extend_from_slice(&(name.len() as u32).to_be_bytes());
+  frame.extend_from_slice(name.as_bytes());
+  frame.extend_from_slice(&(data.len() as u32).to_be_bytes());
+  frame.extend_from_slice(data);
+  frame
+}
+
+fn read_frame_part(stream: &mut UnixStream) -> Result<Vec<u8>, std::io::Error> {
+  let mut len = [0; 4];
+  stream.read_exact(&mut len)?;
+  let len = u32::from_be_bytes(len) as usize;
+  if len > MAX_FRAME_PART_LEN {
+    return Err(std::io::Error::new(
+      ErrorKind::InvalidData,
+      "BroadcastChannel message is too large",
+    ));
+  }
+  let mut buf = vec![0; len];
+  stream.read_exact(&mut buf)?;
+  Ok(buf)
+}
+
+/// Reads messages from a peer until it disconnects.
+fn read_frames(mut stream: UnixStream, sink: BroadcastChannelSink) {
+  loop {
+    let frame = read_frame_part(&mut stream).and_then(|name| {
+      let name = String::from_utf8(name)
+        .map_err(|err| std::io::Error::new(ErrorKind::InvalidData, err))?;
+      Ok((name, read_frame_part(&mut stream)?))
+    });
+    match frame {
+      Ok((name, data)) => sink.deliver(name, data),
+      Err(err) => {
+        if err.kind() != ErrorKind::UnexpectedEof {
+          log::debug!("Failed to read BroadcastChannel message: {err}");
+        }
+        return;
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::InMemoryBroadcastChannel;
+
+  #[tokio::test]
+  async fn frames_roundtrip() {
+    let bc = InMemoryBroadcastChannel::default();
+    let resource = bc.subscribe().unwrap();
+    let (mut writer, reader) = UnixStream::pair().unwrap();
+    let sink = BroadcastChannelSink(bc.tx.clone());
+    let reader = std::thread::spawn(move || read_frames(reader, sink));
+
+    writer.write_all(&encode_frame("a", b"hello")).unwrap();
+    writer.write_all(&encode_frame("b", b"")).unwrap();
+    drop(writer);
+    reader.join().unwrap();
+
+    assert_eq!(
+      bc.recv(&resource).await.unwrap(),
+      Some(("a".to_string(), b"hello".to_vec()))
+    );
+    assert_eq!(
+      bc.recv(&resource).await.unwrap(),
+      Some(("b".to_string(), vec![]))
+    );
+  }
+}
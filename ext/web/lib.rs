@@ -41,7 +41,11 @@ use crate::blob::op_blob_read_part;
 use crate::blob::op_blob_remove_part;
 use crate::blob::op_blob_revoke_object_url;
 use crate::blob::op_blob_slice_part;
+pub use crate::broadcast_channel::BroadcastChannelBackend;
+pub use crate::broadcast_channel::BroadcastChannelSink;
 pub use crate::broadcast_channel::InMemoryBroadcastChannel;
+#[cfg(unix)]
+pub use crate::broadcast_channel::UnixSocketBroadcastChannelBackend;
 pub use crate::message_port::JsMessageData;
 pub use crate::message_port::MessagePort;
 pub use crate::message_port::Transferable;
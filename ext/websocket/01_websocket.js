@@ -36,6 +36,7 @@ const {
   ObjectPrototypeIsPrototypeOf,
   PromisePrototypeCatch,
   PromisePrototypeThen,
+  RangeError,
   RegExpPrototypeExec,
   SafeSet,
   SetPrototypeGetSize,
@@ -74,6 +75,92 @@ import {
 } from "ext:deno_fetch/20_headers.js";
 import { HttpClientPrototype } from "ext:deno_fetch/22_http_client.js";
 
+webidl.converters["WebSocketPerMessageDeflateOptions"] = webidl
+  .createDictionaryConverter(
+    "WebSocketPerMessageDeflateOptions",
+    [
+      {
+        key: "serverNoContextTakeover",
+        converter: webidl.converters.boolean,
+        defaultValue: false,
+      },
+      {
+        key: "clientNoContextTakeover",
+        converter: webidl.converters.boolean,
+        defaultValue: false,
+      },
+      {
+        key: "serverMaxWindowBits",
+        converter: (V, prefix, context, opts) =>
+          webidl.converters.octet(V, prefix, context, {
+            ...opts,
+            enforceRange: true,
+          }),
+      },
+      {
+        key: "clientMaxWindowBits",
+        converter: (V, prefix, context, opts) =>
+          webidl.converters.octet(V, prefix, context, {
+            ...opts,
+            enforceRange: true,
+          }),
+      },
+    ],
+  );
+
+webidl.converters["boolean or WebSocketPerMessageDeflateOptions"] = (
+  V,
+  prefix,
+  context,
+  opts,
+) => {
+  if (webidl.type(V) === "Object") {
+    return webidl.converters["WebSocketPerMessageDeflateOptions"](
+      V,
+      prefix,
+      context,
+      opts,
+    );
+  }
+  return webidl.converters.boolean(V, prefix, context, opts);
+};
+
+/**
+ * Converts the non-standard `perMessageDeflate` option to the options passed
+ * to the ops, or `null` if compression is disabled.
+ * @param {unknown} value
+ * @param {string} prefix
+ * @param {string} context
+ */
+function toPerMessageDeflateOptions(value, prefix, context) {
+  if (value === undefined) {
+    return null;
+  }
+  value = webidl.converters["boolean or WebSocketPerMessageDeflateOptions"](
+    value,
+    prefix,
+    context,
+  );
+  if (value === false) {
+    return null;
+  }
+  if (value === true) {
+    return { __proto__: null };
+  }
+  checkWindowBits(value, "serverMaxWindowBits", prefix, context);
+  checkWindowBits(value, "clientMaxWindowBits", prefix, context);
+  return value;
+}
+
+function checkWindowBits(options, key, prefix, context) {
+  const bits = options[key];
+  if (bits !== undefined && (bits < 8 || bits > 15)) {
+    throw new RangeError(
+      `${prefix}: ${context}: '${key}' must be between 8 and 15, received ${bits}`,
+    );
+  }
+}
+
 webidl.converters["WebSocketInit"] = webidl.createDictionaryConverter(
   "WebSocketInit",
   [
@@ -86,6 +173,7 @@ webidl.converters["WebSocketInit"] = webidl.createDictionaryConverter(
       converter: webidl.converters["sequence<DOMString>"],
     },
     { key: "client", converter: webidl.converters.any },
+    { key: "perMessageDeflate", converter: webidl.converters.any },
   ],
 );
 
@@ -210,6 +298,7 @@ class WebSocket extends EventTarget {
     let protocols;
     let headers = null;
     let clientRid = null;
+    let perMessageDeflate = null;
 
     if (typeof initOrProtocols === "string") {
       protocols = [initOrProtocols];
@@ -241,6 +330,13 @@ class WebSocket extends EventTarget {
         }
         clientRid = initOrProtocols.client?.[internalRidSymbol] ?? null;
       }
+
+      // NOTE: non standard extension.
+      perMessageDeflate = toPerMessageDeflateOptions(
+        initOrProtocols.perMessageDeflate,
+        prefix,
+        "Argument 2",
+      );
     }
 
     if (
@@ -286,6 +382,7 @@ class WebSocket extends EventTarget {
         cancelRid,
         headers ? headerListFromHeaders(headers) : null,
         clientRid,
+        perMessageDeflate,
       ),
       (create) => {
         this[_rid] = create.rid;
@@ -761,6 +858,7 @@ function createWebSocketBranded() {
 
 export {
   _eventLoop,
+  _extensions,
   _idleTimeoutDuration,
   _idleTimeoutTimeout,
   _protocol,
@@ -771,5 +869,6 @@ export {
   CLIENT,
   createWebSocketBranded,
   SERVER,
+  toPerMessageDeflateOptions,
   WebSocket,
 };
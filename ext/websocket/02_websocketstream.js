@@ -42,6 +42,7 @@ import {
   headerListFromHeaders,
   headersFromHeaderList,
 } from "ext:deno_fetch/20_headers.js";
+import { toPerMessageDeflateOptions } from "ext:deno_websocket/01_websocket.js";
 
 webidl.converters.WebSocketStreamOptions = webidl.createDictionaryConverter(
   "WebSocketStreamOptions",
@@ -61,6 +62,10 @@ webidl.converters.WebSocketStreamOptions = webidl.createDictionaryConverter(
       key: "headers",
       converter: webidl.converters.HeadersInit,
     },
+    {
+      key: "perMessageDeflate",
+      converter: webidl.converters.any,
+    },
   ],
 );
 webidl.converters.WebSocketCloseInfo = webidl.createDictionaryConverter(
@@ -150,6 +155,12 @@ class WebSocketStream {
       fillHeaders(headers, options.headers);
     }
 
+    const perMessageDeflate = toPerMessageDeflateOptions(
+      options.perMessageDeflate,
+      prefix,
+      "Argument 2",
+    );
+
     const cancelRid = op_ws_check_permission_and_cancel_handle(
       "WebSocketStream.abort()",
       this[_url],
@@ -173,6 +184,8 @@ class WebSocketStream {
           options.protocols ? ArrayPrototypeJoin(options.protocols, ", ") : "",
           cancelRid,
           headerListFromHeaders(headers),
+          null,
+          perMessageDeflate,
         ),
         (create) => {
           options.signal?.[remove](abort);
@@ -12,7 +12,9 @@ const { serve } = Deno;
 
 // A message-based WebSocket echo server.
 serve({ port }, (request) => {
-  const { socket, response } = Deno.upgradeWebSocket(request);
+  const { socket, response } = Deno.upgradeWebSocket(request, {
+    perMessageDeflate: true,
+  });
   socket.onmessage = (event) => {
     socket.send(event.data);
   };
@@ -0,0 +1,691 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+//! permessage-deflate (RFC 7692) negotiation and message compression.
+//!
+//! fastwebsockets rejects frames with reserved bits set and offers no way of
+//! setting them on outgoing frames, so compression is split in two parts:
+//! [`PerMessageDeflate`] (de)compresses whole message payloads, and
+//! [`DeflateFraming`] sits in the underlying stream and rewrites the RSV1 bit
+//! of frame headers as they pass through.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io;
+use std::rc::Rc;
+
+use fastwebsockets::Role;
+use flate2::Compress;
+use flate2::Compression;
+use flate2::Decompress;
+use flate2::FlushCompress;
+use flate2::FlushDecompress;
+use flate2::Status;
+use serde::Deserialize;
+
+const EXTENSION_NAME: &str = "permessage-deflate";
+const SERVER_NO_CONTEXT_TAKEOVER: &str = "server_no_context_takeover";
+const CLIENT_NO_CONTEXT_TAKEOVER: &str = "client_no_context_takeover";
+const SERVER_MAX_WINDOW_BITS: &str = "server_max_window_bits";
+const CLIENT_MAX_WINDOW_BITS: &str = "client_max_window_bits";
+
+/// The tail that is stripped from every compressed message (RFC 7692,
+/// section 7.2.1) and appended again before decompressing.
+const DEFLATE_TAIL: [u8; 4] = [0x00, 0x00, 0xff, 0xff];
+
+/// Same limit fastwebsockets applies to a single frame.
+const MAX_MESSAGE_SIZE: usize = 64 << 20;
+
+const RSV1: u8 = 0b0100_0000;
+const OPCODE_MASK: u8 = 0b0000_1111;
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_BINARY: u8 = 0x2;
+
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+pub enum DeflateError {
+  #[class(generic)]
+  #[error("Invalid permessage-deflate response: {0}")]
+  InvalidResponse(&'static str),
+  #[class(generic)]
+  #[error(transparent)]
+  Compress(#[from] flate2::CompressError),
+  #[class(generic)]
+  #[error(transparent)]
+  Decompress(#[from] flate2::DecompressError),
+  #[class(generic)]
+  #[error("Truncated compressed message")]
+  Truncated,
+  #[class(generic)]
+  #[error("Decompressed message is too large")]
+  MessageTooLarge,
+}
+
+/// User facing permessage-deflate options, shared by clients and servers.
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PerMessageDeflateOptions {
+  pub server_no_context_takeover: bool,
+  pub client_no_context_takeover: bool,
+  pub server_max_window_bits: Option<u8>,
+  pub client_max_window_bits: Option<u8>,
+}
+
+/// Negotiated permessage-deflate parameters. Window bits that are `None`
+/// were not part of the negotiation and default to 15.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DeflateParams {
+  pub server_no_context_takeover: bool,
+  pub client_no_context_takeover: bool,
+  pub server_max_window_bits: Option<u8>,
+  pub client_max_window_bits: Option<u8>,
+}
+
+impl DeflateParams {
+  /// Formats the parameters as a `Sec-WebSocket-Extensions` value.
+  pub fn to_header(self) -> String {
+    let mut header = EXTENSION_NAME.to_string();
+    if self.server_no_context_takeover {
+      header.push_str("; ");
+      header.push_str(SERVER_NO_CONTEXT_TAKEOVER);
+    }
+    if self.client_no_context_takeover {
+      header.push_str("; ");
+      header.push_str(CLIENT_NO_CONTEXT_TAKEOVER);
+    }
+    if let Some(bits) = self.server_max_window_bits {
+      header.push_str(&format!("; {SERVER_MAX_WINDOW_BITS}={bits}"));
+    }
+    if let Some(bits) = self.client_max_window_bits {
+      header.push_str(&format!("; {CLIENT_MAX_WINDOW_BITS}={bits}"));
+    }
+    header
+  }
+}
+
+/// Parameters of a single extension offer or response. Unlike
+/// [`DeflateParams`], this keeps track of a valueless
+/// `client_max_window_bits`.
+#[derive(Default)]
+struct ParsedParams {
+  server_no_context_takeover: bool,
+  client_no_context_takeover: bool,
+  server_max_window_bits: Option<u8>,
+  client_max_window_bits: Option<Option<u8>>,
+}
+
+/// Splits a `Sec-WebSocket-Extensions` value into the parameter lists of
+/// every `permessage-deflate` element.
+fn deflate_elements(header: &str) -> impl Iterator<Item = &str> {
+  header.split(',').filter_map(|element| {
+    let (name, params) = element.split_once(';').unwrap_or((element, ""));
+    name
+      .trim()
+      .eq_ignore_ascii_case(EXTENSION_NAME)
+      .then_some(params)
+  })
+}
+
+fn parse_window_bits(value: &str) -> Option<u8> {
+  let value = value.trim();
+  let value = value
+    .strip_prefix('"')
+    .and_then(|v| v.strip_suffix('"'))
+    .unwrap_or(value);
+  if value.starts_with('0') {
+    return None;
+  }
+  value.parse().ok().filter(|bits| (8..=15).contains(bits))
+}
+
+/// Parses the parameters of a single `permessage-deflate` element, returning
+/// `None` if a parameter is unknown, duplicated or has an invalid value.
+fn parse_params(params: &str) -> Option<ParsedParams> {
+  let mut parsed = ParsedParams::default();
+  for param in params.split(';') {
+    let param = param.trim();
+    if param.is_empty() {
+      continue;
+    }
+    let (name, value) = match param.split_once('=') {
+      Some((name, value)) => (name.trim(), Some(value)),
+      None => (param, None),
+    };
+    match (name.to_ascii_lowercase().as_str(), value) {
+      (SERVER_NO_CONTEXT_TAKEOVER, None)
+        if !parsed.server_no_context_takeover =>
+      {
+        parsed.server_no_context_takeover = true;
+      }
+      (CLIENT_NO_CONTEXT_TAKEOVER, None)
+        if !parsed.client_no_context_takeover =>
+      {
+        parsed.client_no_context_takeover = true;
+      }
+      (SERVER_MAX_WINDOW_BITS, Some(value))
+        if parsed.server_max_window_bits.is_none() =>
+      {
+        parsed.server_max_window_bits = Some(parse_window_bits(value)?);
+      }
+      (CLIENT_MAX_WINDOW_BITS, value)
+        if parsed.client_max_window_bits.is_none() =>
+      {
+        parsed.client_max_window_bits = Some(match value {
+          Some(value) => Some(parse_window_bits(value)?),
+          None => None,
+        });
+      }
+      _ => return None,
+    }
+  }
+  Some(parsed)
+}
+
+/// Parses a `Sec-WebSocket-Extensions` value produced by [`negotiate_server`].
+pub fn parse_negotiated(header: &str) -> Option<DeflateParams> {
+  let parsed = deflate_elements(header).next().and_then(parse_params)?;
+  Some(DeflateParams {
+    server_no_context_takeover: parsed.server_no_context_takeover,
+    client_no_context_takeover: parsed.client_no_context_takeover,
+    server_max_window_bits: parsed.server_max_window_bits,
+    client_max_window_bits: parsed.client_max_window_bits.flatten(),
+  })
+}
+
+/// Builds the `Sec-WebSocket-Extensions` value a client offers.
+pub fn client_offer(options: &PerMessageDeflateOptions) -> String {
+  let mut offer = DeflateParams {
+    server_no_context_takeover: options.server_no_context_takeover,
+    client_no_context_takeover: options.client_no_context_takeover,
+    server_max_window_bits: options.server_max_window_bits,
+    client_max_window_bits: options.client_max_window_bits,
+  }
+  .to_header();
+  // Always let the server limit our window, we can honor any size.
+  if options.client_max_window_bits.is_none() {
+    offer.push_str("; ");
+    offer.push_str(CLIENT_MAX_WINDOW_BITS);
+  }
+  offer
+}
+
+/// Validates the server's response to [`client_offer`]. Returns `None` if
+/// the server declined compression.
+pub fn accept_server_response(
+  options: &PerMessageDeflateOptions,
+  header: &str,
+) -> Result<Option<DeflateParams>, DeflateError> {
+  let mut elements = deflate_elements(header);
+  let Some(params) = elements.next() else {
+    return Ok(None);
+  };
+  if elements.next().is_some() {
+    return Err(DeflateError::InvalidResponse("extension accepted twice"));
+  }
+  let parsed = parse_params(params).ok_or(DeflateError::InvalidResponse(
+    "invalid extension parameters",
+  ))?;
+  let client_max_window_bits = match parsed.client_max_window_bits {
+    None => None,
+    Some(Some(bits)) => Some(bits),
+    Some(None) => {
+      return Err(DeflateError::InvalidResponse(
+        "client_max_window_bits without a value",
+      ));
+    }
+  };
+  validate_response(options, parsed, client_max_window_bits).map(Some)
+}
+
+fn validate_response(
+  options: &PerMessageDeflateOptions,
+  parsed: ParsedParams,
+  client_max_window_bits: Option<u8>,
+) -> Result<DeflateParams, DeflateError> {
+  if options.server_no_context_takeover && !parsed.server_no_context_takeover {
+    return Err(DeflateError::InvalidResponse(
+      "server_no_context_takeover was not accepted",
+    ));
+  }
+  if let Some(requested) = options.server_max_window_bits {
+    match parsed.server_max_window_bits {
+      Some(bits) if bits <= requested => {}
+      _ => {
+        return Err(DeflateError::InvalidResponse(
+          "server_max_window_bits was not accepted",
+        ));
+      }
+    }
+  }
+  if let (Some(requested), Some(bits)) =
+    (options.client_max_window_bits, client_max_window_bits)
+    && bits > requested
+  {
+    return Err(DeflateError::InvalidResponse(
+      "client_max_window_bits exceeds the offered value",
+    ));
+  }
+  Ok(DeflateParams {
+    server_no_context_takeover: parsed.server_no_context_takeover,
+    client_no_context_takeover: parsed.client_no_context_takeover,
+    server_max_window_bits: parsed.server_max_window_bits,
+    client_max_window_bits,
+  })
+}
+
+/// Picks the first acceptable `permessage-deflate` offer from a client's
+/// `Sec-WebSocket-Extensions` header. Returns `None` if compression should
+/// not be used.
+pub fn negotiate_server(
+  options: &PerMessageDeflateOptions,
+  offer: &str,
+) -> Option<DeflateParams> {
+  deflate_elements(offer)
+    .filter_map(parse_params)
+    .map(|offer| DeflateParams {
+      server_no_context_takeover: offer.server_no_context_takeover
+        || options.server_no_context_takeover,
+      client_no_context_takeover: offer.client_no_context_takeover
+        || options.client_no_context_takeover,
+      server_max_window_bits: match (
+        offer.server_max_window_bits,
+        options.server_max_window_bits,
+      ) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+      },
+      // The limit can only be sent if the client offered the parameter.
+      client_max_window_bits: match (
+        offer.client_max_window_bits,
+        options.client_max_window_bits,
+      ) {
+        (Some(offered), Some(bits)) => Some(offered.unwrap_or(15).min(bits)),
+        _ => None,
+      },
+    })
+    .next()
+}
+
+/// Per message compression state of a single WebSocket.
+pub(crate) struct PerMessageDeflate {
+  compress: RefCell<Compress>,
+  decompress: RefCell<Decompress>,
+  compress_no_context_takeover: bool,
+  decompress_no_context_takeover: bool,
+  inbound: InboundQueue,
+}
+
+impl PerMessageDeflate {
+  pub fn new(params: &DeflateParams, role: Role) -> (Self, DeflateFraming) {
+    let (window_bits, compress_reset, decompress_reset) = match role {
+      Role::Server => (
+        params.server_max_window_bits,
+        params.server_no_context_takeover,
+        params.client_no_context_takeover,
+      ),
+      Role::Client => (
+        params.client_max_window_bits,
+        params.client_no_context_takeover,
+        params.server_no_context_takeover,
+      ),
+    };
+    let compress = match window_bits.unwrap_or(15) {
+      // zlib can't produce raw deflate streams with a 256 byte window.
+      // Stored blocks never reference earlier data, so they are valid for
+      // any window size.
+      8 => Compress::new(Compression::none(), false),
+      bits => {
+        Compress::new_with_window_bits(Compression::default(), false, bits)
+      }
+    };
+    let inbound = InboundQueue::default();
+    let this = Self {
+      compress: RefCell::new(compress),
+      // A 15 bit window can inflate data compressed with any window size.
+      decompress: RefCell::new(Decompress::new(false)),
+      compress_no_context_takeover: compress_reset,
+      decompress_no_context_takeover: decompress_reset,
+      inbound: inbound.clone(),
+    };
+    let framing = DeflateFraming {
+      read: FrameScanner::default(),
+      write: FrameScanner::default(),
+      inbound,
+    };
+    (this, framing)
+  }
+
+  /// Compresses the payload of an outgoing text or binary message.
+  pub fn compress(&self, data: &[u8]) -> Result<Vec<u8>, DeflateError> {
+    let mut compress = self.compress.borrow_mut();
+    let start = compress.total_in();
+    let mut out = Vec::with_capacity(data.len() / 2 + 64);
+    loop {
+      if out.len() == out.capacity() {
+        out.reserve(out.capacity());
+      }
+      let consumed = (compress.total_in() - start) as usize;
+      compress.compress_vec(
+        &data[consumed..],
+        &mut out,
+        FlushCompress::Sync,
+      )?;
+      let consumed = (compress.total_in() - start) as usize;
+      if consumed == data.len() && out.len() < out.capacity() {
+        break;
+      }
+    }
+    if out.ends_with(&DEFLATE_TAIL) {
+      out.truncate(out.len() - DEFLATE_TAIL.len());
+    }
+    if self.compress_no_context_takeover {
+      compress.reset();
+    }
+    Ok(out)
+  }
+
+  /// Returns how the next inbound text or binary message was sent.
+  pub fn next_inbound(&self) -> InboundMessage {
+    self
+      .inbound
+      .borrow_mut()
+      .pop_front()
+      .unwrap_or(InboundMessage::Uncompressed)
+  }
+
+  /// Decompresses the payload of an inbound compressed message.
+  pub fn decompress(&self, data: &[u8]) -> Result<Vec<u8>, DeflateError> {
+    let mut decompress = self.decompress.borrow_mut();
+    let input = [data, &DEFLATE_TAIL].concat();
+    let start = decompress.total_in();
+    let mut out = Vec::with_capacity((data.len() * 2).clamp(64, 1 << 16));
+    loop {
+      if out.len() == out.capacity() {
+        if out.len() >= MAX_MESSAGE_SIZE {
+          return Err(DeflateError::MessageTooLarge);
+        }
+        out.reserve(out.capacity());
+      }
+      let consumed = (decompress.total_in() - start) as usize;
+      let produced = out.len();
+      let status = decompress.decompress_vec(
+        &input[consumed..],
+        &mut out,
+        FlushDecompress::Sync,
+      )?;
+      let now_consumed = (decompress.total_in() - start) as usize;
+      if status == Status::StreamEnd {
+        // The sender finished the deflate stream, the next message starts a
+        // new one.
+        decompress.reset(false);
+        break;
+      }
+      if now_consumed == input.len() && out.len() < out.capacity() {
+        break;
+      }
+      if now_consumed == consumed && out.len() == produced {
+        return Err(DeflateError::Truncated);
+      }
+    }
+    if self.decompress_no_context_takeover {
+      decompress.reset(false);
+    }
+    Ok(out)
+  }
+}
+
+/// How an inbound text or binary message was sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum InboundMessage {
+  Uncompressed,
+  /// Compressed messages are handed to fastwebsockets as binary, so that it
+  /// doesn't validate compressed data as UTF-8.
+  Compressed {
+    text: bool,
+  },
+}
+
+type InboundQueue = Rc<RefCell<VecDeque<InboundMessage>>>;
+
+/// Tracks frame boundaries in a stream of raw WebSocket bytes.
+#[derive(Debug, Default, Clone, Copy)]
+struct FrameScanner {
+  header: [u8; 14],
+  header_len: usize,
+  payload_remaining: u64,
+}
+
+impl FrameScanner {
+  /// Advances over `buf`, calling `on_frame_start` with the offset of the
+  /// first byte of every frame header.
+  fn advance(&mut self, buf: &[u8], mut on_frame_start: impl FnMut(usize)) {
+    let mut i = 0;
+    while i < buf.len() {
+      if self.payload_remaining > 0 {
+        let skip = self.payload_remaining.min((buf.len() - i) as u64);
+        self.payload_remaining -= skip;
+        i += skip as usize;
+        continue;
+      }
+      if self.header_len == 0 {
+        on_frame_start(i);
+      }
+      self.header[self.header_len] = buf[i];
+      self.header_len += 1;
+      i += 1;
+      if let Some(payload_len) = self.payload_len() {
+        self.header_len = 0;
+        self.payload_remaining = payload_len;
+      }
+    }
+  }
+
+  /// Returns the payload length once the full header has been seen.
+  fn payload_len(&self) -> Option<u64> {
+    if self.header_len < 2 {
+      return None;
+    }
+    let masked = self.header[1] & 0x80 != 0;
+    let length_code = self.header[1] & 0x7f;
+    let extra = match length_code {
+      126 => 2,
+      127 => 8,
+      _ => 0,
+    };
+    if self.header_len < 2 + extra + masked as usize * 4 {
+      return None;
+    }
+    Some(match extra {
+      0 => length_code as u64,
+      2 => u16::from_be_bytes([self.header[2], self.header[3]]) as u64,
+      _ => u64::from_be_bytes(self.header[2..10].try_into().unwrap()),
+    })
+  }
+}
+
+/// Rewrites the RSV1 bit of frame headers flowing through a
+/// [`crate::stream::WebSocketStream`].
+pub(crate) struct DeflateFraming {
+  read: FrameScanner,
+  write: FrameScanner,
+  inbound: InboundQueue,
+}
+
+impl DeflateFraming {
+  /// Clears RSV1 from freshly read frames and records which messages were
+  /// compressed.
+  pub fn on_read(&mut self, buf: &mut [u8]) -> io::Result<()> {
+    let mut starts = Vec::new();
+    self.read.advance(buf, |i| starts.push(i));
+    for i in starts {
+      let byte = &mut buf[i];
+      let opcode = *byte & OPCODE_MASK;
+      let compressed = *byte & RSV1 != 0;
+      let message = match opcode {
+        OPCODE_TEXT | OPCODE_BINARY if compressed => {
+          *byte = (*byte & !(RSV1 | OPCODE_MASK)) | OPCODE_BINARY;
+          InboundMessage::Compressed {
+            text: opcode == OPCODE_TEXT,
+          }
+        }
+        OPCODE_TEXT | OPCODE_BINARY => InboundMessage::Uncompressed,
+        _ if compressed => {
+          return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "RSV1 set on a control or continuation frame",
+          ));
+        }
+        _ => continue,
+      };
+      self.inbound.borrow_mut().push_back(message);
+    }
+    Ok(())
+  }
+
+  /// Returns `buf` with RSV1 set on every outgoing message, or `None` if
+  /// `buf` contains no frame header.
+  pub fn prepare_write(&self, buf: &[u8]) -> Option<Vec<u8>> {
+    let mut starts = Vec::new();
+    let mut scanner = self.write;
+    scanner.advance(buf, |i| starts.push(i));
+    if starts.is_empty() {
+      return None;
+    }
+    let mut out = buf.to_vec();
+    for i in starts {
+      if matches!(out[i] & OPCODE_MASK, OPCODE_TEXT | OPCODE_BINARY) {
+        out[i] |= RSV1;
+      }
+    }
+    Some(out)
+  }
+
+  /// Advances over the bytes that were actually written.
+  pub fn commit_write(&mut self, written: &[u8]) {
+    self.write.advance(written, |_| {});
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn negotiate_default_offer() {
+    let options = PerMessageDeflateOptions::default();
+    let offer = client_offer(&options);
+    assert_eq!(offer, "permessage-deflate; client_max_window_bits");
+    let params = negotiate_server(&options, &offer).unwrap();
+    assert_eq!(params, DeflateParams::default());
+    assert_eq!(params.to_header(), "permessage-deflate");
+    assert_eq!(
+      accept_server_response(&options, &params.to_header()).unwrap(),
+      Some(params)
+    );
+  }
+
+  #[test]
+  fn negotiate_parameters() {
+    let server = PerMessageDeflateOptions {
+      client_no_context_takeover: true,
+      client_max_window_bits: Some(10),
+      ..Default::default()
+    };
+    let params = negotiate_server(
+      &server,
+      "x-webkit-deflate-frame, permessage-deflate; server_max_window_bits=16, \
+       permessage-deflate; server_max_window_bits=\"12\"; client_max_window_bits",
+    )
+    .unwrap();
+    assert_eq!(
+      params.to_header(),
+      "permessage-deflate; client_no_context_takeover; \
+       server_max_window_bits=12; client_max_window_bits=10"
+    );
+
+    assert!(negotiate_server(&server, "permessage-deflate; foo").is_none());
+    assert!(
+      negotiate_server(
+        &server,
+        "permessage-deflate; server_no_context_takeover; server_no_context_takeover"
+      )
+      .is_none()
+    );
+  }
+
+  #[test]
+  fn reject_invalid_server_response() {
+    let client = PerMessageDeflateOptions {
+      server_max_window_bits: Some(10),
+      ..Default::default()
+    };
+    assert_eq!(accept_server_response(&client, "").unwrap(), None);
+    assert!(accept_server_response(&client, "permessage-deflate").is_err());
+    assert!(
+      accept_server_response(
+        &client,
+        "permessage-deflate; server_max_window_bits=11"
+      )
+      .is_err()
+    );
+    assert!(
+      accept_server_response(
+        &client,
+        "permessage-deflate; server_max_window_bits=9, permessage-deflate"
+      )
+      .is_err()
+    );
+    assert!(
+      accept_server_response(
+        &client,
+        "permessage-deflate; server_max_window_bits=9; client_max_window_bits"
+      )
+      .is_err()
+    );
+  }
+
+  #[test]
+  fn compress_round_trip() {
+    let params = DeflateParams {
+      server_no_context_takeover: true,
+      ..Default::default()
+    };
+    let (server, _) = PerMessageDeflate::new(&params, Role::Server);
+    let (client, _) = PerMessageDeflate::new(&params, Role::Client);
+    let message = "Hello, World! ".repeat(100);
+    for _ in 0..2 {
+      let compressed = server.compress(message.as_bytes()).unwrap();
+      assert!(compressed.len() < message.len());
+      assert_eq!(client.decompress(&compressed).unwrap(), message.as_bytes());
+    }
+    let compressed = client.compress(b"").unwrap();
+    assert_eq!(server.decompress(&compressed).unwrap(), b"");
+  }
+
+  #[test]
+  fn framing_rewrites_rsv1() {
+    let (deflate, mut framing) =
+      PerMessageDeflate::new(&DeflateParams::default(), Role::Server);
+
+    // A compressed, masked text frame followed by an empty ping.
+    let mut read = vec![0xc1, 0x82, 0, 0, 0, 0, 0xab, 0xcd, 0x89, 0x00];
+    framing.on_read(&mut read[..4]).unwrap();
+    framing.on_read(&mut read[4..]).unwrap();
+    assert_eq!(read[0], 0x82);
+    assert_eq!(read[8], 0x89);
+    assert_eq!(
+      deflate.next_inbound(),
+      InboundMessage::Compressed { text: true }
+    );
+    assert_eq!(deflate.next_inbound(), InboundMessage::Uncompressed);
+
+    // RSV1 is only valid on the first frame of a data message.
+    let mut read = vec![0xc0, 0x00];
+    assert!(framing.on_read(&mut read).is_err());
+
+    let write = [0x81, 0x01, b'a', 0x8a, 0x00];
+    let out = framing.prepare_write(&write[..2]).unwrap();
+    assert_eq!(out, [0xc1, 0x01]);
+    framing.commit_write(&write[..2]);
+    let out = framing.prepare_write(&write[2..]).unwrap();
+    assert_eq!(out, [b'a', 0x8a, 0x00]);
+  }
+}
@@ -44,6 +44,7 @@ use http::StatusCode;
 use http::Uri;
 use http::header::CONNECTION;
 use http::header::HOST;
+use http::header::SEC_WEBSOCKET_EXTENSIONS;
 use http::header::SEC_WEBSOCKET_KEY;
 use http::header::SEC_WEBSOCKET_PROTOCOL;
 use http::header::SEC_WEBSOCKET_VERSION;
@@ -55,10 +56,16 @@ use tokio::io::AsyncWrite;
 use tokio::io::ReadHalf;
 use tokio::io::WriteHalf;
 
+use crate::deflate::InboundMessage;
+use crate::deflate::PerMessageDeflate;
 use crate::stream::WebSocketStream;
 
+mod deflate;
 mod stream;
 
+pub use deflate::DeflateError;
+pub use deflate::PerMessageDeflateOptions;
+
 static USE_WRITEV: Lazy<bool> = Lazy::new(|| {
   let enable = std::env::var("DENO_USE_WRITEV").ok();
 
@@ -98,6 +105,9 @@ pub enum WebsocketError {
   #[class(inherit)]
   #[error(transparent)]
   Canceled(#[from] deno_core::Canceled),
+  #[class(inherit)]
+  #[error(transparent)]
+  Deflate(#[from] DeflateError),
 }
 
 pub struct WsCancelResource(Rc<CancelHandle>);
@@ -195,6 +205,9 @@ pub enum HandshakeError {
   #[class(type)]
   #[error(transparent)]
   HeaderValue(#[from] http::header::InvalidHeaderValue),
+  #[class(inherit)]
+  #[error(transparent)]
+  PerMessageDeflate(#[from] DeflateError),
 }
 
 async fn handshake_websocket(
@@ -202,6 +215,7 @@ async fn handshake_websocket(
   allow_host: bool,
   uri: Uri,
   protocols: &str,
+  extensions: Option<&str>,
   headers: Option<Vec<(ByteString, ByteString)>>,
 ) -> Result<(WebSocket<WebSocketStream>, http::HeaderMap), HandshakeError> {
   let parts = uri.into_parts();
@@ -224,6 +238,7 @@ async fn handshake_websocket(
     &authority,
     &path_and_query,
     protocols,
+    extensions,
     &headers,
   )
   .await;
@@ -236,12 +251,14 @@ async fn handshake_websocket(
         .authority(authority)
         .path_and_query(path_and_query)
         .build()?;
-      handshake_http2(client, allow_host, uri, protocols, &headers).await
+      handshake_http2(client, allow_host, uri, protocols, extensions, &headers)
+        .await
     }
     Err(e) => Err(e),
   }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn handshake_http1(
   client: deno_fetch::Client,
   allow_host: bool,
@@ -249,6 +266,7 @@ async fn handshake_http1(
   authority: &http::uri::Authority,
   path_and_query: &http::uri::PathAndQuery,
   protocols: &str,
+  extensions: Option<&str>,
   headers: &Option<Vec<(ByteString, ByteString)>>,
 ) -> Result<(WebSocket<WebSocketStream>, http::HeaderMap), HandshakeError> {
   let connection_uri = Uri::builder()
@@ -279,8 +297,9 @@ async fn handshake_http1(
   let mut request = Request::builder().method(Method::GET).uri(req_uri);
 
   client.inject_common_headers(&mut request);
-  request =
-    populate_common_request_headers(request, protocols, headers, allow_host)?;
+  request = populate_common_request_headers(
+    request, protocols, extensions, headers, allow_host,
+  )?;
 
   if let Some(headers) = request.headers_ref()
     && !headers.contains_key(HOST)
@@ -306,6 +325,7 @@ async fn handshake_http2(
   allow_host: bool,
   uri: Uri,
   protocols: &str,
+  extensions: Option<&str>,
   headers: &Option<Vec<(ByteString, ByteString)>>,
 ) -> Result<(WebSocket<WebSocketStream>, http::HeaderMap), HandshakeError> {
   let connection = client.connect(uri.clone(), SocketUse::Http2Only).await?;
@@ -320,8 +340,9 @@ async fn handshake_http2(
   request = request.method(Method::CONNECT);
   request = request.uri(uri);
   client.inject_common_headers(&mut request);
-  request =
-    populate_common_request_headers(request, protocols, headers, allow_host)?;
+  request = populate_common_request_headers(
+    request, protocols, extensions, headers, allow_host,
+  )?;
   request = request.extension(h2::ext::Protocol::from("websocket"));
   let (resp, send) = send.send_request(request.body(())?, false)?;
   let resp = resp.await?;
@@ -361,6 +382,7 @@ async fn handshake_connection<
 fn populate_common_request_headers(
   mut request: http::request::Builder,
   protocols: &str,
+  extensions: Option<&str>,
   headers: &Option<Vec<(ByteString, ByteString)>>,
   allow_host: bool,
 ) -> Result<http::request::Builder, HandshakeError> {
@@ -370,6 +392,10 @@ fn populate_common_request_headers(
     request = request.header(SEC_WEBSOCKET_PROTOCOL, protocols);
   }
 
+  if let Some(extensions) = extensions {
+    request = request.header(SEC_WEBSOCKET_EXTENSIONS, extensions);
+  }
+
   if let Some(headers) = headers {
     for (key, value) in headers {
       let name = HeaderName::from_bytes(key)?;
@@ -403,6 +429,7 @@ pub async fn op_ws_create(
   #[smi] cancel_handle: Option<ResourceId>,
   #[scoped] headers: Option<Vec<(ByteString, ByteString)>>,
   #[smi] client_rid: Option<u32>,
+  #[serde] per_message_deflate: Option<PerMessageDeflateOptions>,
 ) -> Result<CreateResponse, WebsocketError> {
   let (client, allow_host) = {
     let mut s = state.borrow_mut();
@@ -434,9 +461,16 @@ pub async fn op_ws_create(
 
   let uri: Uri = url.parse()?;
 
-  let handshake =
-    handshake_websocket(client, allow_host, uri, &protocols, headers)
-      .map_err(WebsocketError::ConnectionFailed);
+  let offer = per_message_deflate.as_ref().map(deflate::client_offer);
+  let handshake = handshake_websocket(
+    client,
+    allow_host,
+    uri,
+    &protocols,
+    offer.as_deref(),
+    headers,
+  )
+  .map_err(WebsocketError::ConnectionFailed);
   let (stream, response) = match cancel_resource {
     Some(rc) => handshake.try_or_cancel(rc).await?,
     None => handshake.await?,
@@ -448,18 +482,38 @@ pub async fn op_ws_create(
     res.close();
   }
 
+  let extensions = response
+    .get_all(SEC_WEBSOCKET_EXTENSIONS)
+    .iter()
+    .map(|header| header.to_str().unwrap())
+    .collect::<Vec<_>>()
+    .join(", ");
+  let deflate_params = match &per_message_deflate {
+    Some(options) => deflate::accept_server_response(options, &extensions)
+      .map_err(|err| WebsocketError::ConnectionFailed(err.into()))?,
+    None => None,
+  };
+  let (stream, deflate) = match deflate_params {
+    Some(params) => {
+      let mut stream = stream.into_inner();
+      let (deflate, framing) = PerMessageDeflate::new(&params, Role::Client);
+      stream.set_deflate_framing(framing);
+      let mut stream = WebSocket::after_handshake(stream, Role::Client);
+      stream.set_writev(false);
+      (stream, Some(deflate))
+    }
+    None => (stream, None),
+  };
+
   let mut state = state.borrow_mut();
-  let rid = state.resource_table.add(ServerWebSocket::new(stream));
+  let rid = state
+    .resource_table
+    .add(ServerWebSocket::new(stream, deflate));
 
   let protocol = match response.get("Sec-WebSocket-Protocol") {
     Some(header) => header.to_str().unwrap(),
     None => "",
   };
-  let extensions = response
-    .get_all("Sec-WebSocket-Extensions")
-    .iter()
-    .map(|header| header.to_str().unwrap())
-    .collect::<String>();
   Ok(CreateResponse {
     rid,
     protocol: protocol.to_string(),
@@ -486,10 +540,14 @@ pub struct ServerWebSocket {
   string: Cell<Option<String>>,
   ws_read: AsyncRefCell<FragmentCollectorRead<ReadHalf<WebSocketStream>>>,
   ws_write: AsyncRefCell<WebSocketWrite<WriteHalf<WebSocketStream>>>,
+  deflate: Option<PerMessageDeflate>,
 }
 
 impl ServerWebSocket {
-  fn new(ws: WebSocket<WebSocketStream>) -> Self {
+  fn new(
+    ws: WebSocket<WebSocketStream>,
+    deflate: Option<PerMessageDeflate>,
+  ) -> Self {
     let (ws_read, ws_write) = ws.split(tokio::io::split);
     Self {
       buffered: Cell::new(0),
@@ -500,6 +558,7 @@ impl ServerWebSocket {
       string: Cell::new(None),
       ws_read: AsyncRefCell::new(FragmentCollectorRead::new(ws_read)),
       ws_write: AsyncRefCell::new(ws_write),
+      deflate,
     }
   }
 
@@ -530,6 +589,15 @@ impl ServerWebSocket {
     if ws.is_closed() {
       return Ok(());
     }
+    let frame = match &self.deflate {
+      Some(deflate)
+        if matches!(frame.opcode, OpCode::Text | OpCode::Binary) =>
+      {
+        let payload = deflate.compress(&frame.payload)?;
+        Frame::new(frame.fin, frame.opcode, None, payload.into())
+      }
+      _ => frame,
+    };
     ws.write_frame(frame).await?;
     Ok(())
   }
@@ -541,23 +609,40 @@ impl Resource for ServerWebSocket {
   }
 }
 
+/// Picks the `Sec-WebSocket-Extensions` response for a client's offer, if
+/// permessage-deflate should be used.
+#[op2]
+#[string]
+pub fn op_ws_negotiate_per_message_deflate(
+  #[string] offer: &str,
+  #[serde] options: PerMessageDeflateOptions,
+) -> Option<String> {
+  deflate::negotiate_server(&options, offer).map(|params| params.to_header())
+}
+
+/// `extensions` is the `Sec-WebSocket-Extensions` value the server responded
+/// with, as returned by `op_ws_negotiate_per_message_deflate`.
 pub fn ws_create_server_stream(
   state: &mut OpState,
   transport: NetworkStream,
   read_buf: Bytes,
+  extensions: &str,
 ) -> ResourceId {
-  let mut ws = WebSocket::after_handshake(
-    WebSocketStream::new(
-      stream::WsStreamKind::Network(transport),
-      Some(read_buf),
-    ),
-    Role::Server,
+  let mut stream = WebSocketStream::new(
+    stream::WsStreamKind::Network(transport),
+    Some(read_buf),
   );
-  ws.set_writev(*USE_WRITEV);
+  let deflate = deflate::parse_negotiated(extensions).map(|params| {
+    let (deflate, framing) = PerMessageDeflate::new(&params, Role::Server);
+    stream.set_deflate_framing(framing);
+    deflate
+  });
+  let mut ws = WebSocket::after_handshake(stream, Role::Server);
+  ws.set_writev(*USE_WRITEV && deflate.is_none());
   ws.set_auto_close(true);
   ws.set_auto_pong(true);
 
-  state.resource_table.add(ServerWebSocket::new(ws))
+  state.resource_table.add(ServerWebSocket::new(ws, deflate))
 }
 
 fn send_binary(state: &mut OpState, rid: ResourceId, data: &[u8]) {
@@ -808,8 +893,29 @@ pub async fn op_ws_next_event(
       }
     };
 
-    break match val.opcode {
-      OpCode::Text => match String::from_utf8(val.payload.to_vec()) {
+    let (opcode, payload) = match (&resource.deflate, val.opcode) {
+      (Some(deflate), OpCode::Text | OpCode::Binary) => {
+        match deflate.next_inbound() {
+          InboundMessage::Compressed { text } => {
+            match deflate.decompress(&val.payload) {
+              Ok(payload) if text => (OpCode::Text, Cow::Owned(payload)),
+              Ok(payload) => (OpCode::Binary, Cow::Owned(payload)),
+              Err(err) => {
+                resource.set_error(Some(err.to_string()));
+                return MessageKind::Error as u16;
+              }
+            }
+          }
+          InboundMessage::Uncompressed => {
+            (val.opcode, Cow::Borrowed(&*val.payload))
+          }
+        }
+      }
+      _ => (val.opcode, Cow::Borrowed(&*val.payload)),
+    };
+
+    break match opcode {
+      OpCode::Text => match String::from_utf8(payload.into_owned()) {
         Ok(s) => {
           resource.string.set(Some(s));
           MessageKind::Text as u16
@@ -820,20 +926,18 @@ pub async fn op_ws_next_event(
         }
       },
       OpCode::Binary => {
-        resource.buffer.set(Some(val.payload.to_vec()));
+        resource.buffer.set(Some(payload.into_owned()));
         MessageKind::Binary as u16
       }
       OpCode::Close => {
         // Close reason is returned through error
-        if val.payload.len() < 2 {
+        if payload.len() < 2 {
           resource.set_error(None);
           MessageKind::ClosedDefault as u16
         } else {
-          let close_code = CloseCode::from(u16::from_be_bytes([
-            val.payload[0],
-            val.payload[1],
-          ]));
-          let reason = String::from_utf8(val.payload[2..].to_vec()).ok();
+          let close_code =
+            CloseCode::from(u16::from_be_bytes([payload[0], payload[1]]));
+          let reason = String::from_utf8(payload[2..].to_vec()).ok();
           resource.set_error(reason);
           close_code.into()
         }
@@ -864,6 +968,7 @@ deno_core::extension!(
     op_ws_send_text_async,
     op_ws_send_ping,
     op_ws_get_buffered_amount,
+    op_ws_negotiate_per_message_deflate,
   ],
   esm = ["01_websocket.js", "02_websocketstream.js"],
 );
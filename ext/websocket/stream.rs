@@ -15,6 +15,8 @@ use tokio::io::AsyncRead;
 use tokio::io::AsyncWrite;
 use tokio::io::ReadBuf;
 
+use crate::deflate::DeflateFraming;
+
 // TODO(bartlomieju): remove this
 #[allow(clippy::large_enum_variant)]
 pub(crate) enum WsStreamKind {
@@ -26,6 +28,7 @@ pub(crate) enum WsStreamKind {
 pub(crate) struct WebSocketStream {
   stream: WsStreamKind,
   pre: Option<Bytes>,
+  deflate: Option<DeflateFraming>,
 }
 
 impl WebSocketStream {
@@ -33,14 +36,18 @@ impl WebSocketStream {
     Self {
       stream,
       pre: buffer,
+      deflate: None,
     }
   }
-}
 
-impl AsyncRead for WebSocketStream {
+  /// Marks compressed frames with RSV1 as they pass through this stream.
+  pub fn set_deflate_framing(&mut self, framing: DeflateFraming) {
+    self.deflate = Some(framing);
+  }
+
   // From hyper's Rewind (https://github.com/hyperium/hyper), MIT License, Copyright (c) Sean McArthur
-  fn poll_read(
-    mut self: Pin<&mut Self>,
+  fn poll_read_inner(
+    &mut self,
     cx: &mut std::task::Context<'_>,
     buf: &mut ReadBuf<'_>,
   ) -> Poll<std::io::Result<()>> {
@@ -85,11 +92,9 @@ impl AsyncRead for WebSocketStream {
       }
     }
   }
-}
 
-impl AsyncWrite for WebSocketStream {
-  fn poll_write(
-    mut self: Pin<&mut Self>,
+  fn poll_write_inner(
+    &mut self,
     cx: &mut std::task::Context<'_>,
     buf: &[u8],
   ) -> std::task::Poll<Result<usize, std::io::Error>> {
@@ -122,6 +127,42 @@ impl AsyncWrite for WebSocketStream {
       }
     }
   }
+}
+
+impl AsyncRead for WebSocketStream {
+  fn poll_read(
+    self: Pin<&mut Self>,
+    cx: &mut std::task::Context<'_>,
+    buf: &mut ReadBuf<'_>,
+  ) -> Poll<std::io::Result<()>> {
+    let this = self.get_mut();
+    let filled = buf.filled().len();
+    ready!(this.poll_read_inner(cx, buf))?;
+    if let Some(deflate) = &mut this.deflate {
+      deflate.on_read(&mut buf.filled_mut()[filled..])?;
+    }
+    Poll::Ready(Ok(()))
+  }
+}
+
+impl AsyncWrite for WebSocketStream {
+  fn poll_write(
+    self: Pin<&mut Self>,
+    cx: &mut std::task::Context<'_>,
+    buf: &[u8],
+  ) -> std::task::Poll<Result<usize, std::io::Error>> {
+    let this = self.get_mut();
+    let Some(deflate) = &this.deflate else {
+      return this.poll_write_inner(cx, buf);
+    };
+    let prepared = deflate.prepare_write(buf);
+    let written =
+      ready!(this.poll_write_inner(cx, prepared.as_deref().unwrap_or(buf)))?;
+    if let Some(deflate) = &mut this.deflate {
+      deflate.commit_write(&buf[..written]);
+    }
+    Poll::Ready(Ok(written))
+  }
 
   fn poll_flush(
     mut self: Pin<&mut Self>,
@@ -152,6 +193,9 @@ impl AsyncWrite for WebSocketStream {
   }
 
   fn is_write_vectored(&self) -> bool {
+    if self.deflate.is_some() {
+      return false;
+    }
     match &self.stream {
       WsStreamKind::Network(stream) => stream.is_write_vectored(),
       WsStreamKind::Upgraded(stream) => stream.is_write_vectored(),
@@ -164,6 +208,10 @@ impl AsyncWrite for WebSocketStream {
     cx: &mut std::task::Context<'_>,
     bufs: &[std::io::IoSlice<'_>],
   ) -> std::task::Poll<Result<usize, std::io::Error>> {
+    if self.deflate.is_some() {
+      let buf = bufs.iter().find(|b| !b.is_empty()).map_or(&[][..], |b| b);
+      return self.poll_write(cx, buf);
+    }
     match &mut self.stream {
       WsStreamKind::Network(stream) => {
         Pin::new(stream).poll_write_vectored(cx, bufs)
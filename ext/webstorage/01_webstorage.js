@@ -2,8 +2,13 @@
 
 /// <reference path="../../core/internal.d.ts" />
 
-import { primordials } from "ext:core/mod.js";
-import { op_webstorage_iterate_keys, Storage } from "ext:core/ops";
+import { core, primordials } from "ext:core/mod.js";
+import {
+  op_webstorage_iterate_keys,
+  op_webstorage_recv,
+  op_webstorage_subscribe,
+  Storage,
+} from "ext:core/ops";
 const {
   SymbolFor,
   ObjectFromEntries,
@@ -15,6 +20,9 @@ const {
   Proxy,
 } = primordials;
 
+import { setIsTrusted, StorageEvent } from "ext:deno_web/02_event.js";
+import { getLocationHref } from "ext:deno_web/12_location.js";
+
 function createStorage(persistent) {
   const storage = new Storage(persistent);
 
@@ -123,4 +131,33 @@ function sessionStorage() {
   return sessionStorageStorage;
 }
 
-export { localStorage, sessionStorage, Storage };
+/**
+ * Dispatches a `storage` event on `target` whenever another worker of this
+ * process changes `localStorage`. The pending receive is unrefed, so it
+ * never keeps the event loop alive.
+ * @param {EventTarget} target
+ */
+async function receiveStorageEvents(target) {
+  const rid = op_webstorage_subscribe();
+  while (true) {
+    const promise = op_webstorage_recv(rid);
+    core.unrefOpPromise(promise);
+    const change = await promise;
+    if (change === null) {
+      break;
+    }
+
+    const event = new StorageEvent("storage", {
+      key: change.key,
+      oldValue: change.oldValue,
+      newValue: change.newValue,
+      url: getLocationHref() ?? "",
+      storageArea: localStorage(),
+    });
+    setIsTrusted(event, true);
+    target.dispatchEvent(event);
+  }
+  core.close(rid);
+}
+
+export { localStorage, receiveStorageEvents, sessionStorage, Storage };
@@ -2,15 +2,35 @@
 
 // NOTE to all: use **cached** prepared statements when interfacing with SQLite.
 
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::Path;
 use std::path::PathBuf;
-
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+use deno_core::AsyncRefCell;
+use deno_core::CancelFuture;
+use deno_core::CancelHandle;
 use deno_core::GarbageCollected;
 use deno_core::OpState;
+use deno_core::RcRef;
+use deno_core::Resource;
+use deno_core::ResourceId;
 use deno_core::op2;
+use deno_core::parking_lot::MappedMutexGuard;
+use deno_core::parking_lot::Mutex;
+use deno_core::parking_lot::MutexGuard;
 pub use rusqlite;
 use rusqlite::Connection;
 use rusqlite::OptionalExtension;
 use rusqlite::params;
+use serde::Serialize;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
 
 #[derive(Debug, thiserror::Error, deno_error::JsError)]
 pub enum WebStorageError {
@@ -26,94 +46,178 @@ pub enum WebStorageError {
   #[class("DOMExceptionQuotaExceededError")]
   #[error("Exceeded maximum storage size")]
   StorageExceeded,
+  #[class(inherit)]
+  #[error(transparent)]
+  Resource(
+    #[from]
+    #[inherit]
+    deno_core::error::ResourceError,
+  ),
 }
 
-#[derive(Clone)]
-struct OriginStorageDir(PathBuf);
+/// The maximum size of a storage area, in bytes, when no quota is set.
+pub const DEFAULT_STORAGE_QUOTA: u64 = 10 * 1024 * 1024;
 
-const MAX_STORAGE_BYTES: usize = 10 * 1024 * 1024;
+/// Maximum number of unreceived storage events kept for a worker before the
+/// oldest are dropped.
+const STORAGE_EVENT_CAPACITY: usize = 256;
 
 deno_core::extension!(deno_webstorage,
-  deps = [ deno_webidl ],
+  deps = [ deno_webidl, deno_web ],
   ops = [
     op_webstorage_iterate_keys,
+    op_webstorage_subscribe,
+    op_webstorage_recv,
   ],
   objects = [
     Storage
   ],
   esm = [ "01_webstorage.js" ],
   options = {
-      origin_storage_dir: Option<PathBuf>
+    origin_storage_dir: Option<PathBuf>,
+    quota: Option<u64>,
+    registry: WebStorageRegistry,
   },
   state = |state, options| {
-    if let Some(origin_storage_dir) = options.origin_storage_dir {
-      state.put(OriginStorageDir(origin_storage_dir));
-    }
+    let origin = options.registry.origin(options.origin_storage_dir);
+    let id = origin.next_worker_id.fetch_add(1, Ordering::Relaxed);
+    state.put(WorkerStorage {
+      origin,
+      id,
+      quota: options.quota.unwrap_or(DEFAULT_STORAGE_QUOTA),
+      session: Default::default(),
+    });
   },
 );
 
-struct LocalStorage(Connection);
-struct SessionStorage(Connection);
+/// The `localStorage` areas of a process, shared by all workers that use the
+/// same origin storage directory.
+///
+/// Changes that one worker makes to a storage area are delivered to the
+/// others as `storage` events. `sessionStorage` is not shared, every worker
+/// has its own.
+#[derive(Clone, Default)]
+pub struct WebStorageRegistry(
+  Arc<Mutex<HashMap<Option<PathBuf>, Arc<OriginStorage>>>>,
+);
 
-fn get_webstorage(
-  state: &mut OpState,
-  persistent: bool,
-) -> Result<&Connection, WebStorageError> {
-  let conn = if persistent {
-    if state.try_borrow::<LocalStorage>().is_none() {
-      let path = state
-        .try_borrow::<OriginStorageDir>()
-        .ok_or(WebStorageError::ContextNotSupported)?;
-      std::fs::create_dir_all(&path.0).map_err(WebStorageError::Io)?;
-      let conn = Connection::open(path.0.join("local_storage"))?;
-      // Enable write-ahead-logging and tweak some other stuff.
-      let initial_pragmas = "
-        -- enable write-ahead-logging mode
-        PRAGMA journal_mode=WAL;
-        PRAGMA synchronous=NORMAL;
-        PRAGMA temp_store=memory;
-        PRAGMA page_size=4096;
-        PRAGMA mmap_size=6000000;
-        PRAGMA optimize;
-      ";
-
-      conn.execute_batch(initial_pragmas)?;
-      conn.set_prepared_statement_cache_capacity(128);
-      {
-        let mut stmt = conn.prepare_cached(
-          "CREATE TABLE IF NOT EXISTS data (key VARCHAR UNIQUE, value VARCHAR)",
-        )?;
-        stmt.execute(params![])?;
-      }
-      state.put(LocalStorage(conn));
-    }
+impl WebStorageRegistry {
+  fn origin(&self, dir: Option<PathBuf>) -> Arc<OriginStorage> {
+    self
+      .0
+      .lock()
+      .entry(dir.clone())
+      .or_insert_with(|| Arc::new(OriginStorage::new(dir)))
+      .clone()
+  }
+}
 
-    &state.borrow::<LocalStorage>().0
-  } else {
-    if state.try_borrow::<SessionStorage>().is_none() {
-      let conn = Connection::open_in_memory()?;
-      {
-        let mut stmt = conn.prepare_cached(
-          "CREATE TABLE data (key VARCHAR UNIQUE, value VARCHAR)",
-        )?;
-        stmt.execute(params![])?;
-      }
-      state.put(SessionStorage(conn));
+struct OriginStorage {
+  dir: Option<PathBuf>,
+  local: Mutex<Option<Connection>>,
+  changes: broadcast::Sender<(u64, StorageChange)>,
+  next_worker_id: AtomicU64,
+}
+
+impl OriginStorage {
+  fn new(dir: Option<PathBuf>) -> Self {
+    Self {
+      dir,
+      local: Default::default(),
+      changes: broadcast::channel(STORAGE_EVENT_CAPACITY).0,
+      next_worker_id: Default::default(),
     }
+  }
+}
 
-    &state.borrow::<SessionStorage>().0
-  };
+struct WorkerStorage {
+  origin: Arc<OriginStorage>,
+  /// Identifies the worker among those sharing `origin`, so it does not
+  /// receive events for its own changes.
+  id: u64,
+  quota: u64,
+  session: Mutex<Option<Connection>>,
+}
 
+/// A change to a `localStorage` area, delivered to the other workers using
+/// it.
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StorageChange {
+  key: Option<String>,
+  old_value: Option<String>,
+  new_value: Option<String>,
+}
+
+fn open_local_storage(dir: &Path) -> Result<Connection, WebStorageError> {
+  std::fs::create_dir_all(dir).map_err(WebStorageError::Io)?;
+  let conn = Connection::open(dir.join("local_storage"))?;
+  // Enable write-ahead-logging and tweak some other stuff.
+  let initial_pragmas = "
+    -- enable write-ahead-logging mode
+    PRAGMA journal_mode=WAL;
+    PRAGMA synchronous=NORMAL;
+    PRAGMA temp_store=memory;
+    PRAGMA page_size=4096;
+    PRAGMA mmap_size=6000000;
+    PRAGMA optimize;
+  ";
+
+  conn.execute_batch(initial_pragmas)?;
+  conn.set_prepared_statement_cache_capacity(128);
+  {
+    let mut stmt = conn.prepare_cached(
+      "CREATE TABLE IF NOT EXISTS data (key VARCHAR UNIQUE, value VARCHAR)",
+    )?;
+    stmt.execute(params![])?;
+  }
   Ok(conn)
 }
 
-#[inline]
-fn size_check(input: usize) -> Result<(), WebStorageError> {
-  if input >= MAX_STORAGE_BYTES {
-    return Err(WebStorageError::StorageExceeded);
+fn open_session_storage() -> Result<Connection, WebStorageError> {
+  let conn = Connection::open_in_memory()?;
+  {
+    let mut stmt = conn.prepare_cached(
+      "CREATE TABLE data (key VARCHAR UNIQUE, value VARCHAR)",
+    )?;
+    stmt.execute(params![])?;
+  }
+  Ok(conn)
+}
+
+fn get_webstorage(
+  state: &OpState,
+  persistent: bool,
+) -> Result<MappedMutexGuard<'_, Connection>, WebStorageError> {
+  let storage = state.borrow::<WorkerStorage>();
+  let mut conn = if persistent {
+    if storage.origin.dir.is_none() {
+      return Err(WebStorageError::ContextNotSupported);
+    }
+    storage.origin.local.lock()
+  } else {
+    storage.session.lock()
+  };
+  if conn.is_none() {
+    *conn = Some(match &storage.origin.dir {
+      Some(dir) if persistent => open_local_storage(dir)?,
+      _ => open_session_storage()?,
+    });
   }
 
-  Ok(())
+  Ok(MutexGuard::map(conn, |conn| conn.as_mut().unwrap()))
+}
+
+/// Notifies the other workers using the storage area of a change to it.
+/// `sessionStorage` belongs to a single worker, so its changes are not
+/// delivered anywhere.
+fn notify(state: &OpState, persistent: bool, change: StorageChange) {
+  if !persistent {
+    return;
+  }
+  let storage = state.borrow::<WorkerStorage>();
+  // Fails only when nobody is listening.
+  let _ = storage.origin.changes.send((storage.id, change));
 }
 
 struct Storage {
@@ -175,20 +279,45 @@ impl Storage {
     #[string] key: &str,
     #[string] value: &str,
   ) -> Result<(), WebStorageError> {
-    let conn = get_webstorage(state, self.persistent)?;
-
-    size_check(key.len() + value.len())?;
-
-    let mut stmt = conn
-      .prepare_cached("SELECT SUM(pgsize) FROM dbstat WHERE name = 'data'")?;
-    let size: u32 = stmt.query_row(params![], |row| row.get(0))?;
+    let old_value = {
+      let conn = get_webstorage(state, self.persistent)?;
+      let quota = state.borrow::<WorkerStorage>().quota;
+
+      let mut stmt =
+        conn.prepare_cached("SELECT value FROM data WHERE key = ?")?;
+      let old_value: Option<String> =
+        stmt.query_row(params![key], |row| row.get(0)).optional()?;
+      if old_value.as_deref() == Some(value) {
+        return Ok(());
+      }
 
-    size_check(size as usize)?;
+      // The size of the storage area once the item is set: the length in
+      // bytes of every key and value.
+      let mut stmt = conn.prepare_cached(
+        "SELECT COALESCE(SUM(LENGTH(CAST(key AS BLOB)) + \
+         LENGTH(CAST(value AS BLOB))), 0) FROM data WHERE key != ?",
+      )?;
+      let size: u64 = stmt.query_row(params![key], |row| row.get(0))?;
+      if size + (key.len() + value.len()) as u64 > quota {
+        return Err(WebStorageError::StorageExceeded);
+      }
 
-    let mut stmt = conn.prepare_cached(
-      "INSERT OR REPLACE INTO data (key, value) VALUES (?, ?)",
-    )?;
-    stmt.execute(params![key, value])?;
+      let mut stmt = conn.prepare_cached(
+        "INSERT OR REPLACE INTO data (key, value) VALUES (?, ?)",
+      )?;
+      stmt.execute(params![key, value])?;
+      old_value
+    };
+
+    notify(
+      state,
+      self.persistent,
+      StorageChange {
+        key: Some(key.to_string()),
+        old_value,
+        new_value: Some(value.to_string()),
+      },
+    );
 
     Ok(())
   }
@@ -216,20 +345,51 @@ impl Storage {
     state: &mut OpState,
     #[string] key: &str,
   ) -> Result<(), WebStorageError> {
-    let conn = get_webstorage(state, self.persistent)?;
-
-    let mut stmt = conn.prepare_cached("DELETE FROM data WHERE key = ?")?;
-    stmt.execute(params![key])?;
+    let old_value = {
+      let conn = get_webstorage(state, self.persistent)?;
+
+      let mut stmt = conn
+        .prepare_cached("DELETE FROM data WHERE key = ? RETURNING value")?;
+      let old_value: Option<String> =
+        stmt.query_row(params![key], |row| row.get(0)).optional()?;
+      old_value
+    };
+
+    if old_value.is_some() {
+      notify(
+        state,
+        self.persistent,
+        StorageChange {
+          key: Some(key.to_string()),
+          old_value,
+          new_value: None,
+        },
+      );
+    }
 
     Ok(())
   }
 
   #[fast]
   fn clear(&self, state: &mut OpState) -> Result<(), WebStorageError> {
-    let conn = get_webstorage(state, self.persistent)?;
-
-    let mut stmt = conn.prepare_cached("DELETE FROM data")?;
-    stmt.execute(params![])?;
+    let removed = {
+      let conn = get_webstorage(state, self.persistent)?;
+
+      let mut stmt = conn.prepare_cached("DELETE FROM data")?;
+      stmt.execute(params![])?
+    };
+
+    if removed > 0 {
+      notify(
+        state,
+        self.persistent,
+        StorageChange {
+          key: None,
+          old_value: None,
+          new_value: None,
+        },
+      );
+    }
 
     Ok(())
   }
@@ -250,3 +410,54 @@ fn op_webstorage_iterate_keys(
 
   Ok(keys)
 }
+
+struct StorageEventResource {
+  receiver: AsyncRefCell<broadcast::Receiver<(u64, StorageChange)>>,
+  cancel: CancelHandle,
+}
+
+impl Resource for StorageEventResource {
+  fn name(&self) -> Cow<'_, str> {
+    "storageEvents".into()
+  }
+
+  fn close(self: Rc<Self>) {
+    self.cancel.cancel();
+  }
+}
+
+/// Starts receiving the changes that other workers make to the
+/// `localStorage` area of this worker.
+#[op2(fast)]
+#[smi]
+fn op_webstorage_subscribe(state: &mut OpState) -> ResourceId {
+  let receiver = state.borrow::<WorkerStorage>().origin.changes.subscribe();
+  state.resource_table.add(StorageEventResource {
+    receiver: AsyncRefCell::new(receiver),
+    cancel: Default::default(),
+  })
+}
+
+#[op2]
+#[serde]
+async fn op_webstorage_recv(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+) -> Result<Option<StorageChange>, WebStorageError> {
+  let (resource, id) = {
+    let state = state.borrow();
+    let resource = state.resource_table.get::<StorageEventResource>(rid)?;
+    (resource, state.borrow::<WorkerStorage>().id)
+  };
+  let cancel = RcRef::map(&resource, |r| &r.cancel);
+  let mut receiver = RcRef::map(&resource, |r| &r.receiver).borrow_mut().await;
+  loop {
+    match receiver.recv().or_cancel(&cancel).await {
+      Ok(Ok((source, change))) if source != id => return Ok(Some(change)),
+      // Changes made by this worker and those missed because the worker
+      // was too slow to receive them are skipped.
+      Ok(Ok(_)) | Ok(Err(RecvError::Lagged(_))) => continue,
+      Ok(Err(RecvError::Closed)) | Err(_) => return Ok(None),
+    }
+  }
+}
@@ -1126,6 +1126,8 @@ pub struct ConfigFileJson {
   pub publish: Option<Value>,
   pub deploy: Option<Value>,
   pub allow_scripts: Option<Value>,
+  pub catalog: Option<Value>,
+  pub catalogs: Option<Value>,
 
   pub name: Option<String>,
   pub version: Option<String>,
@@ -1268,6 +1270,15 @@ pub enum ToInvalidConfigError {
   },
 }
 
+/// The dependency versions shared by the members of a workspace.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CatalogsConfig {
+  /// The `"catalog"` entries, which are referenced via `catalog:`.
+  pub default: IndexMap<String, String>,
+  /// The `"catalogs"` entries, which are referenced via `catalog:<name>`.
+  pub named: IndexMap<String, IndexMap<String, String>>,
+}
+
 #[derive(Debug, Error, JsError)]
 #[class(type)]
 pub enum ResolveTaskConfigError {
@@ -1996,6 +2007,31 @@ impl ConfigFile {
     }
   }
 
+  pub fn to_catalogs_config(
+    &self,
+  ) -> Result<CatalogsConfig, ToInvalidConfigError> {
+    let mut config = CatalogsConfig::default();
+    if let Some(value) = &self.json.catalog {
+      config.default =
+        serde_json::from_value(value.clone()).map_err(|source| {
+          ToInvalidConfigError::Parse {
+            config: "catalog",
+            source,
+          }
+        })?;
+    }
+    if let Some(value) = &self.json.catalogs {
+      config.named =
+        serde_json::from_value(value.clone()).map_err(|source| {
+          ToInvalidConfigError::Parse {
+            config: "catalogs",
+            source,
+          }
+        })?;
+    }
+    Ok(config)
+  }
+
   pub fn to_workspace_config(
     &self,
   ) -> Result<Option<WorkspaceConfig>, WorkspaceConfigParseError> {
@@ -3267,6 +3303,40 @@ mod tests {
     }
   }
 
+  #[test]
+  fn test_to_catalogs_config() {
+    fn get_result(text: &str) -> Result<CatalogsConfig, ToInvalidConfigError> {
+      let config_specifier = root_url().join("deno.json").unwrap();
+      let config_file = ConfigFile::new(text, config_specifier).unwrap();
+      config_file.to_catalogs_config()
+    }
+
+    assert_eq!(get_result(r#"{}"#).unwrap(), CatalogsConfig::default());
+    assert_eq!(
+      get_result(
+        r#"{
+        "catalog": { "react": "^18.2.0" },
+        "catalogs": { "legacy": { "react": "npm:react@^17" } }
+      }"#
+      )
+      .unwrap(),
+      CatalogsConfig {
+        default: IndexMap::from([("react".to_string(), "^18.2.0".to_string())]),
+        named: IndexMap::from([(
+          "legacy".to_string(),
+          IndexMap::from([("react".to_string(), "npm:react@^17".to_string())])
+        )]),
+      }
+    );
+    assert!(matches!(
+      get_result(r#"{ "catalog": ["react"] }"#).unwrap_err(),
+      ToInvalidConfigError::Parse {
+        config: "catalog",
+        ..
+      }
+    ));
+  }
+
   #[test]
   fn test_to_allow_scripts() {
     fn get_result(
@@ -11,15 +11,21 @@ use std::sync::OnceLock;
 use boxed_error::Boxed;
 use deno_error::JsError;
 use deno_maybe_sync::new_rc;
+use deno_package_json::DEFAULT_CATALOG_NAME;
 use deno_package_json::PackageJson;
+use deno_package_json::PackageJsonCatalogs;
 use deno_package_json::PackageJsonDepValue;
+use deno_package_json::PackageJsonDepValueParseError;
+use deno_package_json::PackageJsonDepValueParseErrorKind;
 use deno_package_json::PackageJsonDepWorkspaceReq;
+use deno_package_json::PackageJsonDepsRc;
 use deno_package_json::PackageJsonLoadError;
 use deno_package_json::PackageJsonRc;
 use deno_path_util::url_from_directory_path;
 use deno_path_util::url_parent;
 use deno_path_util::url_to_file_path;
 use deno_semver::RangeSetOrTag;
+use deno_semver::StackString;
 use deno_semver::Version;
 use deno_semver::VersionReq;
 use deno_semver::jsr::JsrDepPackageReq;
@@ -69,6 +75,7 @@ use crate::glob::FilePatterns;
 use crate::glob::PathOrPattern;
 use crate::glob::PathOrPatternParseError;
 use crate::glob::PathOrPatternSet;
+use crate::import_map::value_to_dep_req;
 
 mod discovery;
 
@@ -185,6 +192,15 @@ pub enum WorkspaceDiagnosticKind {
     "\"minimumDependencyAge.exclude\" entry \"{entry}\" missing jsr: or npm: prefix."
   )]
   MinimumDependencyAgeExcludeMissingPrefix { entry: String },
+  #[error(
+    "\"{0}\" field was ignored because it is not an object of package names to versions."
+  )]
+  InvalidCatalog(&'static str),
+  #[error("\"imports\" entry \"{key}\" could not be resolved. {source}")]
+  UnresolvedCatalogImport {
+    key: String,
+    source: PackageJsonDepValueParseError,
+  },
 }
 
 #[derive(Debug, Error, JsError, Clone, PartialEq, Eq)]
@@ -446,6 +462,7 @@ pub struct Workspace {
   root_dir_url: UrlRc,
   config_folders: IndexMap<UrlRc, FolderConfigs>,
   links: BTreeMap<UrlRc, FolderConfigs>,
+  catalogs: PackageJsonCatalogs,
   pub(crate) vendor_dir: Option<PathBuf>,
   cached: WorkspaceCachedValues,
 }
@@ -458,11 +475,10 @@ impl Workspace {
     vendor_dir: Option<PathBuf>,
   ) -> Self {
     let root_dir_url = new_rc(root.folder_url());
+    let root = FolderConfigs::from_config_folder(root);
+    let catalogs = resolve_catalogs(&root);
     let mut config_folders = IndexMap::with_capacity(members.len() + 1);
-    config_folders.insert(
-      root_dir_url.clone(),
-      FolderConfigs::from_config_folder(root),
-    );
+    config_folders.insert(root_dir_url.clone(), root);
     config_folders.extend(members.into_iter().map(
       |(folder_url, config_folder)| {
         (folder_url, FolderConfigs::from_config_folder(config_folder))
@@ -475,6 +491,7 @@ impl Workspace {
         .into_iter()
         .map(|(url, folder)| (url, FolderConfigs::from_config_folder(folder)))
         .collect(),
+      catalogs,
       vendor_dir,
       cached: Default::default(),
     }
@@ -504,6 +521,117 @@ impl Workspace {
     self.root_folder_configs().pkg_json.as_ref()
   }
 
+  /// Gets the dependency catalogs of the root deno.json and package.json.
+  pub fn catalogs(&self) -> &PackageJsonCatalogs {
+    &self.catalogs
+  }
+
+  /// Resolves the dependencies of a package.json in the workspace,
+  /// which includes resolving its `catalog:` dependencies.
+  pub fn resolve_pkg_json_deps(
+    &self,
+    pkg_json: &PackageJson,
+  ) -> PackageJsonDepsRc {
+    let deps = pkg_json.resolve_local_package_json_deps();
+    if deps.has_catalog_deps() {
+      new_rc(deps.with_catalogs(&self.catalogs))
+    } else {
+      deps.clone()
+    }
+  }
+
+  /// Resolves an import map value of `catalog:` or `catalog:<name>` to
+  /// the specifier of the entry in the catalog.
+  ///
+  /// Returns `None` when the value doesn't reference a catalog.
+  pub fn resolve_catalog_import_value(
+    &self,
+    key: &str,
+    value: &str,
+  ) -> Option<Result<String, PackageJsonDepValueParseError>> {
+    let catalog = value.strip_prefix("catalog:")?;
+    let catalog = if catalog.is_empty() {
+      DEFAULT_CATALOG_NAME
+    } else {
+      catalog
+    };
+    let alias = key.strip_suffix('/').unwrap_or(key);
+    let Some(entry) = self.catalogs.get(catalog, alias) else {
+      return Some(Err(
+        PackageJsonDepValueParseErrorKind::CatalogEntryNotFound {
+          catalog: catalog.to_string(),
+          alias: alias.to_string(),
+        }
+        .into_box(),
+      ));
+    };
+    let mut specifier = match entry.split_once(':') {
+      Some(("npm", _)) => entry.to_string(),
+      Some(("jsr", value)) => {
+        if value.starts_with('@') {
+          entry.to_string()
+        } else {
+          format!("jsr:{}@{}", alias, value)
+        }
+      }
+      Some((scheme, _)) => {
+        return Some(Err(
+          PackageJsonDepValueParseErrorKind::Unsupported {
+            scheme: scheme.to_string(),
+          }
+          .into_box(),
+        ));
+      }
+      None => format!("npm:{}@{}", alias, entry),
+    };
+    if key.ends_with('/') {
+      specifier.push('/');
+    }
+    Some(Ok(specifier))
+  }
+
+  /// Replaces the `catalog:` values in the "imports" of an import map
+  /// value with the specifiers found in the catalogs.
+  pub fn resolve_import_map_catalogs(
+    &self,
+    import_map: &mut serde_json::Value,
+  ) {
+    let Some(serde_json::Value::Object(imports)) =
+      import_map.get_mut("imports")
+    else {
+      return;
+    };
+    for (key, value) in imports.iter_mut() {
+      if let serde_json::Value::String(text) = value
+        && let Some(Ok(specifier)) =
+          self.resolve_catalog_import_value(key, text)
+      {
+        *text = specifier;
+      }
+    }
+  }
+
+  /// Gets the dependencies of a deno.json in the workspace, including
+  /// the ones its "imports" reference from the catalogs.
+  pub fn resolve_deno_json_deps(
+    &self,
+    deno_json: &ConfigFile,
+  ) -> HashSet<JsrDepPackageReq> {
+    let mut deps = deno_json.dependencies();
+    if let Some(serde_json::Value::Object(imports)) = &deno_json.json.imports {
+      for (key, value) in imports {
+        if let serde_json::Value::String(value) = value
+          && let Some(Ok(specifier)) =
+            self.resolve_catalog_import_value(key, value)
+          && let Some(dep) = value_to_dep_req(&specifier)
+        {
+          deps.insert(dep);
+        }
+      }
+    }
+    deps
+  }
+
   /// Returns the npm overrides from the root package.json, if any.
   pub fn npm_overrides(
     &self,
@@ -686,7 +814,7 @@ impl Workspace {
         deps: folder
           .deno_json
           .as_ref()
-          .map(|d| d.dependencies().into_iter().map(Dep::Req))
+          .map(|d| self.resolve_deno_json_deps(d).into_iter().map(Dep::Req))
           .into_iter()
           .flatten()
           .chain(
@@ -694,7 +822,7 @@ impl Workspace {
               .pkg_json
               .as_ref()
               .map(|d| {
-                let deps = d.resolve_local_package_json_deps();
+                let deps = self.resolve_pkg_json_deps(d);
                 deps
                   .dependencies
                   .iter()
@@ -726,7 +854,10 @@ impl Workspace {
                         },
                       }))
                     }
+                    // resolved above
+                    PackageJsonDepValue::Catalog(_) => None,
                   })
+                  .collect::<Vec<_>>()
               })
               .into_iter()
               .flatten(),
@@ -1126,6 +1257,18 @@ impl Workspace {
           kind: WorkspaceDiagnosticKind::RootOnlyOption("allowScripts"),
         });
       }
+      if member_config.json.catalog.is_some() {
+        diagnostics.push(WorkspaceDiagnostic {
+          config_url: member_config.specifier.clone(),
+          kind: WorkspaceDiagnosticKind::RootOnlyOption("catalog"),
+        });
+      }
+      if member_config.json.catalogs.is_some() {
+        diagnostics.push(WorkspaceDiagnostic {
+          config_url: member_config.specifier.clone(),
+          kind: WorkspaceDiagnosticKind::RootOnlyOption("catalogs"),
+        });
+      }
       if let Some(value) = &member_config.json.lint
         && value.get("report").is_some()
       {
@@ -1224,15 +1367,52 @@ impl Workspace {
         }
 
         check_all_configs(config, &mut diagnostics);
+
+        if is_root
+          && let Err(ToInvalidConfigError::Parse { config: field, .. }) =
+            config.to_catalogs_config()
+        {
+          diagnostics.push(WorkspaceDiagnostic {
+            config_url: config.specifier.clone(),
+            kind: WorkspaceDiagnosticKind::InvalidCatalog(field),
+          });
+        }
+        if let Some(serde_json::Value::Object(imports)) = &config.json.imports {
+          for (key, value) in imports {
+            if let serde_json::Value::String(value) = value
+              && let Some(Err(err)) =
+                self.resolve_catalog_import_value(key, value)
+            {
+              diagnostics.push(WorkspaceDiagnostic {
+                config_url: config.specifier.clone(),
+                kind: WorkspaceDiagnosticKind::UnresolvedCatalogImport {
+                  key: key.clone(),
+                  source: err,
+                },
+              });
+            }
+          }
+        }
       }
-      if !is_root
-        && let Some(pkg_json) = &folder.pkg_json
-        && pkg_json.overrides.is_some()
-      {
-        diagnostics.push(WorkspaceDiagnostic {
-          config_url: pkg_json.specifier(),
-          kind: WorkspaceDiagnosticKind::PkgJsonRootOnlyOption("overrides"),
-        });
+      if !is_root && let Some(pkg_json) = &folder.pkg_json {
+        if pkg_json.overrides.is_some() {
+          diagnostics.push(WorkspaceDiagnostic {
+            config_url: pkg_json.specifier(),
+            kind: WorkspaceDiagnosticKind::PkgJsonRootOnlyOption("overrides"),
+          });
+        }
+        if pkg_json.catalog.is_some() {
+          diagnostics.push(WorkspaceDiagnostic {
+            config_url: pkg_json.specifier(),
+            kind: WorkspaceDiagnosticKind::PkgJsonRootOnlyOption("catalog"),
+          });
+        }
+        if pkg_json.catalogs.is_some() {
+          diagnostics.push(WorkspaceDiagnostic {
+            config_url: pkg_json.specifier(),
+            kind: WorkspaceDiagnosticKind::PkgJsonRootOnlyOption("catalogs"),
+          });
+        }
       }
     }
 
@@ -1624,6 +1804,7 @@ impl WorkspaceDirectory {
       )]),
       root_dir_url: opts.root_dir.clone(),
       links: BTreeMap::new(),
+      catalogs: Default::default(),
       vendor_dir: match opts.use_vendor_dir {
         VendorEnablement::Enable { cwd } => Some(cwd.join("vendor")),
         VendorEnablement::Disable => None,
@@ -1711,6 +1892,7 @@ impl WorkspaceDirectory {
           )]),
           root_dir_url: start_dir.clone(),
           links: BTreeMap::new(),
+          catalogs: Default::default(),
           vendor_dir,
           cached: Default::default(),
         });
@@ -2758,6 +2940,36 @@ fn parent_specifier_str(specifier: &str) -> Option<&str> {
   }
 }
 
+/// Gets the catalogs of the root folder where the entries of the deno.json
+/// take precedence over the ones in the package.json.
+fn resolve_catalogs(root: &FolderConfigs) -> PackageJsonCatalogs {
+  let mut catalogs = root
+    .pkg_json
+    .as_ref()
+    .map(|pkg_json| pkg_json.resolve_catalogs())
+    .unwrap_or_default();
+  // invalid catalogs are surfaced in the workspace diagnostics
+  if let Some(config) = root
+    .deno_json
+    .as_ref()
+    .and_then(|deno_json| deno_json.to_catalogs_config().ok())
+  {
+    fn to_entries(
+      entries: IndexMap<String, String>,
+    ) -> impl Iterator<Item = (StackString, String)> {
+      entries
+        .into_iter()
+        .map(|(name, value)| (StackString::from_string(name), value))
+    }
+
+    catalogs.extend(DEFAULT_CATALOG_NAME, to_entries(config.default));
+    for (name, entries) in config.named {
+      catalogs.extend(&name, to_entries(entries));
+    }
+  }
+  catalogs
+}
+
 fn is_valid_jsr_pkg_name(name: &str) -> bool {
   let jsr = deno_semver::jsr::JsrPackageReqReference::from_str(&format!(
     "jsr:{}@*",
@@ -4055,6 +4267,130 @@ pub mod test {
     );
   }
 
+  #[test]
+  fn test_catalogs() {
+    let sys = InMemorySys::default();
+    sys.fs_insert_json(
+      root_dir().join("deno.json"),
+      json!({
+        "workspace": ["./member", "./pkg"],
+        "catalog": {
+          "react": "^18.2.0",
+          "@std/path": "jsr:^1"
+        },
+        "catalogs": {
+          "legacy": {
+            "react": "^17"
+          }
+        }
+      }),
+    );
+    sys.fs_insert_json(
+      root_dir().join("package.json"),
+      json!({
+        "catalog": {
+          "react": "^16",
+          "zod": "npm:zod@^3"
+        }
+      }),
+    );
+    sys.fs_insert_json(
+      root_dir().join("member/deno.json"),
+      json!({
+        "imports": {
+          "react": "catalog:",
+          "react/": "catalog:legacy",
+          "@std/path": "catalog:",
+          "missing": "catalog:"
+        },
+        "catalog": {}
+      }),
+    );
+    sys.fs_insert_json(
+      root_dir().join("pkg/package.json"),
+      json!({
+        "dependencies": {
+          "zod": "catalog:",
+          "react": "catalog:legacy"
+        }
+      }),
+    );
+    let workspace_dir = workspace_at_start_dir(&sys, &root_dir());
+    let workspace = &workspace_dir.workspace;
+    assert_eq!(
+      workspace.catalogs().get("default", "react"),
+      Some("^18.2.0")
+    );
+    assert_eq!(
+      workspace.catalogs().get("default", "zod"),
+      Some("npm:zod@^3")
+    );
+    assert_eq!(
+      workspace.diagnostics(),
+      vec![
+        WorkspaceDiagnostic {
+          kind: WorkspaceDiagnosticKind::RootOnlyOption("catalog"),
+          config_url: url_from_file_path(&root_dir().join("member/deno.json"))
+            .unwrap(),
+        },
+        WorkspaceDiagnostic {
+          kind: WorkspaceDiagnosticKind::UnresolvedCatalogImport {
+            key: "missing".to_string(),
+            source: PackageJsonDepValueParseErrorKind::CatalogEntryNotFound {
+              catalog: "default".to_string(),
+              alias: "missing".to_string(),
+            }
+            .into_box(),
+          },
+          config_url: url_from_file_path(&root_dir().join("member/deno.json"))
+            .unwrap(),
+        },
+      ]
+    );
+
+    let member_deno_json = workspace
+      .deno_jsons()
+      .find(|c| c.specifier.path().ends_with("/member/deno.json"))
+      .unwrap();
+    let mut import_map = member_deno_json.to_import_map_value_from_imports();
+    workspace.resolve_import_map_catalogs(&mut import_map);
+    assert_eq!(
+      import_map,
+      json!({
+        "imports": {
+          "react": "npm:react@^18.2.0",
+          "react/": "npm:react@^17/",
+          "@std/path": "jsr:@std/path@^1",
+          "missing": "catalog:"
+        }
+      })
+    );
+    let mut deps = workspace
+      .resolve_deno_json_deps(member_deno_json)
+      .into_iter()
+      .map(|d| d.to_string())
+      .collect::<Vec<_>>();
+    deps.sort();
+    assert_eq!(
+      deps,
+      vec!["jsr:@std/path@^1", "npm:react@^17", "npm:react@^18.2.0"]
+    );
+
+    let pkg_json = workspace.package_jsons().last().unwrap();
+    let deps = workspace.resolve_pkg_json_deps(pkg_json);
+    assert_eq!(
+      deps.dependencies.values().cloned().collect::<Vec<_>>(),
+      vec![
+        Ok(PackageJsonDepValue::Req(
+          PackageReq::from_str("zod@^3").unwrap()
+        )),
+        Ok(PackageJsonDepValue::Req(
+          PackageReq::from_str("react@^17").unwrap()
+        )),
+      ]
+    );
+  }
+
   #[test]
   fn test_root_member_node_modules_dir_suggestions() {
     fn suggest(
@@ -9,16 +9,20 @@
     ErrorCaptureStackTrace,
     FunctionPrototypeBind,
     ObjectAssign,
+    ObjectDefineProperty,
     ObjectFreeze,
     ObjectFromEntries,
     ObjectKeys,
     ObjectHasOwn,
+    ReflectApply,
     setQueueMicrotask,
     SafeMap,
     SafeWeakMap,
+    String,
     StringPrototypeSlice,
     Symbol,
     SymbolFor,
+    SymbolToStringTag,
     TypedArrayPrototypeGetLength,
     TypedArrayPrototypeJoin,
     TypedArrayPrototypeSlice,
@@ -862,8 +866,20 @@
     };
   }
 
+  let asyncContextChangeHook = undefined;
+
   const getAsyncContext = getContinuationPreservedEmbedderData;
-  const setAsyncContext = setContinuationPreservedEmbedderData;
+  function setAsyncContext(ctx) {
+    setContinuationPreservedEmbedderData(ctx);
+    if (asyncContextChangeHook !== undefined) asyncContextChangeHook();
+  }
+
+  // Calls `hook` after every `setAsyncContext` call. V8 also switches the
+  // context when it runs a promise reaction, which can be observed with
+  // `setPromiseHooks`.
+  function setAsyncContextChangeHook(hook) {
+    asyncContextChangeHook = hook;
+  }
 
   function scopeAsyncContext(ctx) {
     const old = getAsyncContext();
@@ -904,6 +920,96 @@
     }
   }
 
+  // Implements `AsyncContext.Variable` and `AsyncContext.Snapshot` from
+  // https://github.com/tc39/proposal-async-context on top of the same
+  // continuation-preserved slot that `AsyncVariable` uses, so that both see
+  // the same context.
+  let enterAsyncContextVariable;
+
+  class AsyncContextVariable {
+    // Values are boxed so that a variable that was run with `undefined` can
+    // be told apart from one that was never run and uses its default value.
+    #variable = new AsyncVariable();
+    #name;
+    #defaultValue;
+
+    constructor(options = undefined) {
+      this.#name = options?.name === undefined ? "" : String(options.name);
+      this.#defaultValue = options?.defaultValue;
+    }
+
+    get name() {
+      return this.#name;
+    }
+
+    get() {
+      const box = this.#variable.get();
+      return box === undefined ? this.#defaultValue : box.value;
+    }
+
+    run(value, fn, ...args) {
+      const previous = this.#variable.enter({ value });
+      try {
+        return ReflectApply(fn, undefined, args);
+      } finally {
+        setAsyncContext(previous);
+      }
+    }
+
+    static {
+      // Enters the variable without a callback and returns the previous
+      // context, which the caller must restore with `setAsyncContext`.
+      enterAsyncContextVariable = (variable, value) =>
+        variable.#variable.enter({ value });
+    }
+  }
+
+  class AsyncContextSnapshot {
+    #context = getAsyncContext();
+
+    run(fn, ...args) {
+      const previous = getAsyncContext();
+      setAsyncContext(this.#context);
+      try {
+        return ReflectApply(fn, undefined, args);
+      } finally {
+        setAsyncContext(previous);
+      }
+    }
+
+    static wrap(fn) {
+      if (typeof fn !== "function") {
+        throw new TypeError("Cannot wrap a non-function");
+      }
+      const context = getAsyncContext();
+      return function wrapped(...args) {
+        const previous = getAsyncContext();
+        setAsyncContext(context);
+        try {
+          return ReflectApply(fn, this, args);
+        } finally {
+          setAsyncContext(previous);
+        }
+      };
+    }
+  }
+
+  const AsyncContext = {
+    __proto__: null,
+    Variable: AsyncContextVariable,
+    Snapshot: AsyncContextSnapshot,
+  };
+  function setToStringTag(object, tag) {
+    ObjectDefineProperty(object, SymbolToStringTag, {
+      __proto__: null,
+      value: tag,
+      configurable: true,
+    });
+  }
+  setToStringTag(AsyncContext, "AsyncContext");
+  setToStringTag(AsyncContextVariable.prototype, "AsyncContext.Variable");
+  setToStringTag(AsyncContextSnapshot.prototype, "AsyncContext.Snapshot");
+
   // Extra Deno.core.* exports
   const core = ObjectAssign(globalThis.Deno.core, {
     internalRidSymbol: Symbol("Deno.internal.rid"),
@@ -1128,8 +1234,11 @@
     createCancelHandle: () => op_cancel_handle(),
     getAsyncContext,
     setAsyncContext,
+    setAsyncContextChangeHook,
     scopeAsyncContext,
     AsyncVariable,
+    AsyncContext,
+    enterAsyncContextVariable,
   });
 
   const internals = {};
@@ -1133,6 +1133,55 @@ async fn test_promise_rejection_handler_preserves_async_context() {
   drop(result);
 }
 
+#[tokio::test]
+async fn test_async_context_variable_and_snapshot() {
+  let mut runtime = JsRuntime::new(Default::default());
+
+  let script = r#"
+    const { Variable, Snapshot } = Deno.core.AsyncContext;
+    const v = new Variable({ name: "request", defaultValue: "default" });
+    globalThis.results = [];
+
+    if (v.name !== "request") throw new Error("wrong name");
+    if (v.get() !== "default") throw new Error("wrong default");
+
+    let snapshot;
+    v.run("outer", async () => {
+      snapshot = new Snapshot();
+      const wrapped = Snapshot.wrap(() => v.get());
+      await null;
+      results.push(v.get());
+      v.run(undefined, () => results.push(v.get()));
+      results.push(v.get());
+      results.push(await v.run("inner", async () => {
+        await null;
+        return v.get();
+      }));
+      v.run("other", () => results.push(wrapped()));
+    });
+    results.push(v.get());
+    results.push(snapshot.run(() => v.get()));
+  "#;
+
+  runtime.execute_script("", script).unwrap();
+  runtime
+    .run_event_loop(Default::default())
+    .await
+    .expect("Event loop should complete without error");
+
+  runtime
+    .execute_script(
+      "",
+      r#"
+        const expected = "default,outer,outer,undefined,outer,inner,outer";
+        if (results.map(String).join() !== expected) {
+          throw new Error("unexpected results: " + results.map(String));
+        }
+      "#,
+    )
+    .unwrap();
+}
+
 // Make sure that stalled top-level awaits (that is, top-level awaits that
 // aren't tied to the progress of some op) are correctly reported, even in a
 // realm other than the main one.
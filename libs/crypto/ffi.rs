@@ -91,3 +91,70 @@ impl std::ops::Deref for Bio {
     &self.0
   }
 }
+
+pub struct PKeyCtx(pub *mut aws_lc_sys::EVP_PKEY_CTX);
+
+impl PKeyCtx {
+  pub fn new(pkey: &PKey) -> Result<Self, &'static str> {
+    // SAFETY: `pkey` is a valid EVP_PKEY, the context takes its own reference.
+    let ctx = unsafe {
+      aws_lc_sys::EVP_PKEY_CTX_new(pkey.as_ptr(), std::ptr::null_mut())
+    };
+    if ctx.is_null() {
+      return Err("Failed to create key context");
+    }
+    Ok(PKeyCtx(ctx))
+  }
+
+  pub fn new_id(id: std::ffi::c_int) -> Result<Self, &'static str> {
+    // SAFETY: Creating a new context for a key type has no preconditions.
+    let ctx =
+      unsafe { aws_lc_sys::EVP_PKEY_CTX_new_id(id, std::ptr::null_mut()) };
+    if ctx.is_null() {
+      return Err("Failed to create key context");
+    }
+    Ok(PKeyCtx(ctx))
+  }
+
+  pub fn as_ptr(&self) -> *mut aws_lc_sys::EVP_PKEY_CTX {
+    self.0
+  }
+}
+
+impl Drop for PKeyCtx {
+  fn drop(&mut self) {
+    // SAFETY: We need to free the underlying EVP_PKEY_CTX when the PKeyCtx
+    // wrapper is dropped. The constructors never create a null context.
+    unsafe {
+      aws_lc_sys::EVP_PKEY_CTX_free(self.0);
+    }
+  }
+}
+
+/// Copies the raw public key of `pkey` into a new buffer.
+pub fn raw_public_key(pkey: &PKey) -> Result<Vec<u8>, &'static str> {
+  // SAFETY: The first call only queries the length, the second writes at
+  // most `len` bytes into a buffer of that size.
+  unsafe {
+    let mut len = 0;
+    if aws_lc_sys::EVP_PKEY_get_raw_public_key(
+      pkey.as_ptr(),
+      std::ptr::null_mut(),
+      &mut len,
+    ) != 1
+    {
+      return Err("Failed to get public key length");
+    }
+    let mut out = vec![0; len];
+    if aws_lc_sys::EVP_PKEY_get_raw_public_key(
+      pkey.as_ptr(),
+      out.as_mut_ptr(),
+      &mut len,
+    ) != 1
+    {
+      return Err("Failed to get public key");
+    }
+    out.truncate(len);
+    Ok(out)
+  }
+}
@@ -0,0 +1,49 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+//! Parser for the known-answer test files in `testdata/`.
+//!
+//! Vectors are blocks of `Key = value` lines separated by blank lines.
+//! Lines starting with `#` are comments.
+
+use std::collections::HashMap;
+
+pub struct Vector<'a>(HashMap<&'a str, &'a str>);
+
+impl<'a> Vector<'a> {
+  pub fn get(&self, key: &str) -> Option<&'a str> {
+    self.0.get(key).copied()
+  }
+
+  pub fn bytes(&self, key: &str) -> Option<Vec<u8>> {
+    let value = self.get(key)?;
+    assert!(value.len() % 2 == 0, "odd length hex value for {key}");
+    Some(
+      (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&value[i..i + 2], 16).unwrap())
+        .collect(),
+    )
+  }
+}
+
+pub fn parse(data: &str) -> Vec<Vector<'_>> {
+  let mut vectors = Vec::new();
+  let mut current = HashMap::new();
+  for line in data.lines().map(str::trim) {
+    if line.is_empty() {
+      if !current.is_empty() {
+        vectors.push(Vector(std::mem::take(&mut current)));
+      }
+      continue;
+    }
+    if line.starts_with('#') {
+      continue;
+    }
+    let (key, value) = line.split_once('=').expect("malformed vector line");
+    current.insert(key.trim(), value.trim());
+  }
+  if !current.is_empty() {
+    vectors.push(Vector(current));
+  }
+  vectors
+}
@@ -6,4 +6,8 @@
 #![deny(clippy::unnecessary_wraps)]
 
 mod ffi;
+#[cfg(test)]
+mod kat;
+pub mod ml_dsa;
+pub mod ml_kem;
 pub mod spki;
@@ -0,0 +1,247 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+//! ML-DSA (FIPS 204) signatures.
+//!
+//! Private keys are represented by their 32 byte seed `ξ`. Messages are
+//! signed and verified in their pre-hashed "external mu" form, which lets
+//! callers bind a context string to the signature.
+
+use crate::ffi::PKey;
+use crate::ffi::PKeyCtx;
+use crate::ffi::raw_public_key;
+
+pub const SEED_LEN: usize = 32;
+pub const MU_LEN: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MlDsa {
+  MlDsa44,
+  MlDsa65,
+  MlDsa87,
+}
+
+impl MlDsa {
+  fn nid(self) -> std::ffi::c_int {
+    match self {
+      MlDsa::MlDsa44 => aws_lc_sys::NID_MLDSA44,
+      MlDsa::MlDsa65 => aws_lc_sys::NID_MLDSA65,
+      MlDsa::MlDsa87 => aws_lc_sys::NID_MLDSA87,
+    }
+  }
+
+  pub fn public_key_len(self) -> usize {
+    match self {
+      MlDsa::MlDsa44 => 1312,
+      MlDsa::MlDsa65 => 1952,
+      MlDsa::MlDsa87 => 2592,
+    }
+  }
+
+  pub fn signature_len(self) -> usize {
+    match self {
+      MlDsa::MlDsa44 => 2420,
+      MlDsa::MlDsa65 => 3309,
+      MlDsa::MlDsa87 => 4627,
+    }
+  }
+
+  fn key_from_seed(self, seed: &[u8]) -> Result<PKey, &'static str> {
+    if seed.len() != SEED_LEN {
+      return Err("Invalid ML-DSA seed length");
+    }
+    // SAFETY: The length of `seed` is passed along with it. A buffer of
+    // the seed length is expanded into a key pair.
+    let pkey = unsafe {
+      aws_lc_sys::EVP_PKEY_pqdsa_new_raw_private_key(
+        self.nid(),
+        seed.as_ptr(),
+        seed.len(),
+      )
+    };
+    PKey::from_ptr(pkey).ok_or("Failed to derive ML-DSA key from seed")
+  }
+
+  fn key_from_public_key(
+    self,
+    public_key: &[u8],
+  ) -> Result<PKey, &'static str> {
+    if public_key.len() != self.public_key_len() {
+      return Err("Invalid ML-DSA public key length");
+    }
+    // SAFETY: The length of `public_key` is passed along with it.
+    let pkey = unsafe {
+      aws_lc_sys::EVP_PKEY_pqdsa_new_raw_public_key(
+        self.nid(),
+        public_key.as_ptr(),
+        public_key.len(),
+      )
+    };
+    PKey::from_ptr(pkey).ok_or("Invalid ML-DSA public key")
+  }
+
+  /// Derives the public key belonging to a seed.
+  pub fn public_key_from_seed(
+    self,
+    seed: &[u8],
+  ) -> Result<Vec<u8>, &'static str> {
+    raw_public_key(&self.key_from_seed(seed)?)
+  }
+
+  /// Checks that `public_key` is a well formed public key.
+  pub fn check_public_key(self, public_key: &[u8]) -> Result<(), &'static str> {
+    self.key_from_public_key(public_key).map(|_| ())
+  }
+
+  /// Signs the message representative `mu`, computed as
+  /// `SHAKE256(SHAKE256(pk, 64) || 0 || len(ctx) || ctx || M, 64)`.
+  pub fn sign_mu(
+    self,
+    seed: &[u8],
+    mu: &[u8; MU_LEN],
+  ) -> Result<Vec<u8>, &'static str> {
+    let pkey = self.key_from_seed(seed)?;
+    let ctx = PKeyCtx::new(&pkey)?;
+    let mut signature = vec![0; self.signature_len()];
+    let mut signature_len = signature.len();
+    // SAFETY: The output buffer is as large as its passed length, which is
+    // the exact signature length the operation requires.
+    unsafe {
+      if aws_lc_sys::EVP_PKEY_sign_init(ctx.as_ptr()) != 1
+        || aws_lc_sys::EVP_PKEY_sign(
+          ctx.as_ptr(),
+          signature.as_mut_ptr(),
+          &mut signature_len,
+          mu.as_ptr(),
+          mu.len(),
+        ) != 1
+      {
+        return Err("ML-DSA signing failed");
+      }
+    }
+    signature.truncate(signature_len);
+    Ok(signature)
+  }
+
+  /// Verifies a signature over the message representative `mu`, see
+  /// [`MlDsa::sign_mu`].
+  pub fn verify_mu(
+    self,
+    public_key: &[u8],
+    mu: &[u8; MU_LEN],
+    signature: &[u8],
+  ) -> Result<bool, &'static str> {
+    let pkey = self.key_from_public_key(public_key)?;
+    let ctx = PKeyCtx::new(&pkey)?;
+    // SAFETY: The lengths of the input buffers are passed along with them.
+    unsafe {
+      if aws_lc_sys::EVP_PKEY_verify_init(ctx.as_ptr()) != 1 {
+        return Err("Failed to initialize ML-DSA verification");
+      }
+      Ok(
+        aws_lc_sys::EVP_PKEY_verify(
+          ctx.as_ptr(),
+          signature.as_ptr(),
+          signature.len(),
+          mu.as_ptr(),
+          mu.len(),
+        ) == 1,
+      )
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn sign_verify() {
+    for alg in [MlDsa::MlDsa44, MlDsa::MlDsa65, MlDsa::MlDsa87] {
+      let seed = [3; SEED_LEN];
+      let public_key = alg.public_key_from_seed(&seed).unwrap();
+      assert_eq!(public_key.len(), alg.public_key_len());
+
+      let mu = [1; MU_LEN];
+      let signature = alg.sign_mu(&seed, &mu).unwrap();
+      assert_eq!(signature.len(), alg.signature_len());
+      assert!(alg.verify_mu(&public_key, &mu, &signature).unwrap());
+      assert!(
+        !alg
+          .verify_mu(&public_key, &[2; MU_LEN], &signature)
+          .unwrap()
+      );
+      assert!(!alg.verify_mu(&public_key, &mu, &signature[1..]).unwrap());
+    }
+  }
+
+  #[test]
+  fn rejects_invalid_lengths() {
+    let alg = MlDsa::MlDsa65;
+    assert!(alg.public_key_from_seed(&[0; 64]).is_err());
+    assert!(alg.check_public_key(&[0; 1312]).is_err());
+  }
+
+  fn shake256(parts: &[&[u8]], out: &mut [u8]) {
+    // SAFETY: The context is freed before returning and the lengths of all
+    // buffers are passed along with them.
+    unsafe {
+      let ctx = aws_lc_sys::EVP_MD_CTX_new();
+      assert!(!ctx.is_null());
+      assert_eq!(
+        aws_lc_sys::EVP_DigestInit_ex(
+          ctx,
+          aws_lc_sys::EVP_shake256(),
+          std::ptr::null_mut()
+        ),
+        1
+      );
+      for part in parts {
+        assert_eq!(
+          aws_lc_sys::EVP_DigestUpdate(ctx, part.as_ptr().cast(), part.len()),
+          1
+        );
+      }
+      assert_eq!(
+        aws_lc_sys::EVP_DigestFinalXOF(ctx, out.as_mut_ptr(), out.len()),
+        1
+      );
+      aws_lc_sys::EVP_MD_CTX_free(ctx);
+    }
+  }
+
+  #[test]
+  fn acvp_vectors() {
+    let vectors = crate::kat::parse(include_str!("testdata/ml_dsa_acvp.txt"));
+    assert_eq!(vectors.len(), 3);
+    for vector in vectors {
+      let alg = match vector.get("Algorithm").unwrap() {
+        "ML-DSA-44" => MlDsa::MlDsa44,
+        "ML-DSA-65" => MlDsa::MlDsa65,
+        "ML-DSA-87" => MlDsa::MlDsa87,
+        name => panic!("unknown algorithm {name}"),
+      };
+      let public_key = vector.bytes("PublicKey").unwrap();
+
+      if let Some(seed) = vector.bytes("Seed") {
+        assert_eq!(alg.public_key_from_seed(&seed).unwrap(), public_key);
+      }
+
+      if let Some(signature) = vector.bytes("Signature") {
+        let message = vector.bytes("Message").unwrap();
+        let mut tr = [0; 64];
+        shake256(&[&public_key], &mut tr);
+        let mut mu = [0; MU_LEN];
+        shake256(&[&tr, &message], &mut mu);
+        let expected = vector.get("Result").unwrap() == "pass";
+        assert_eq!(
+          alg.verify_mu(&public_key, &mu, &signature).unwrap(),
+          expected
+        );
+
+        let mut tampered = signature.clone();
+        tampered[0] ^= 1;
+        assert!(!alg.verify_mu(&public_key, &mu, &tampered).unwrap());
+      }
+    }
+  }
+}
@@ -0,0 +1,274 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+//! ML-KEM (FIPS 203) key encapsulation.
+//!
+//! Private keys are represented by their 64 byte seed `d || z`, from which
+//! the expanded decapsulation key is derived whenever it is needed.
+
+use crate::ffi::PKey;
+use crate::ffi::PKeyCtx;
+use crate::ffi::raw_public_key;
+
+pub const SEED_LEN: usize = 64;
+pub const SHARED_SECRET_LEN: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MlKem {
+  MlKem512,
+  MlKem768,
+  MlKem1024,
+}
+
+impl MlKem {
+  fn nid(self) -> std::ffi::c_int {
+    match self {
+      MlKem::MlKem512 => aws_lc_sys::NID_MLKEM512,
+      MlKem::MlKem768 => aws_lc_sys::NID_MLKEM768,
+      MlKem::MlKem1024 => aws_lc_sys::NID_MLKEM1024,
+    }
+  }
+
+  pub fn public_key_len(self) -> usize {
+    match self {
+      MlKem::MlKem512 => 800,
+      MlKem::MlKem768 => 1184,
+      MlKem::MlKem1024 => 1568,
+    }
+  }
+
+  pub fn ciphertext_len(self) -> usize {
+    match self {
+      MlKem::MlKem512 => 768,
+      MlKem::MlKem768 => 1088,
+      MlKem::MlKem1024 => 1568,
+    }
+  }
+
+  fn key_from_seed(self, seed: &[u8]) -> Result<PKey, &'static str> {
+    if seed.len() != SEED_LEN {
+      return Err("Invalid ML-KEM seed length");
+    }
+    let ctx = PKeyCtx::new_id(aws_lc_sys::EVP_PKEY_KEM)?;
+    // SAFETY: `ctx` is a valid KEM context and `seed` is SEED_LEN bytes long,
+    // which is the length the deterministic key generation expects.
+    unsafe {
+      if aws_lc_sys::EVP_PKEY_CTX_kem_set_params(ctx.as_ptr(), self.nid()) != 1
+        || aws_lc_sys::EVP_PKEY_keygen_init(ctx.as_ptr()) != 1
+      {
+        return Err("Failed to initialize ML-KEM key generation");
+      }
+      let mut pkey = std::ptr::null_mut();
+      let mut seed_len = seed.len();
+      if aws_lc_sys::EVP_PKEY_keygen_deterministic(
+        ctx.as_ptr(),
+        &mut pkey,
+        seed.as_ptr(),
+        &mut seed_len,
+      ) != 1
+      {
+        return Err("Failed to derive ML-KEM key from seed");
+      }
+      PKey::from_ptr(pkey).ok_or("Failed to derive ML-KEM key from seed")
+    }
+  }
+
+  fn key_from_public_key(
+    self,
+    public_key: &[u8],
+  ) -> Result<PKey, &'static str> {
+    if public_key.len() != self.public_key_len() {
+      return Err("Invalid ML-KEM public key length");
+    }
+    // SAFETY: The length of `public_key` is passed along with it.
+    let pkey = unsafe {
+      aws_lc_sys::EVP_PKEY_kem_new_raw_public_key(
+        self.nid(),
+        public_key.as_ptr(),
+        public_key.len(),
+      )
+    };
+    PKey::from_ptr(pkey).ok_or("Invalid ML-KEM public key")
+  }
+
+  /// Derives the encapsulation key belonging to a seed.
+  pub fn public_key_from_seed(
+    self,
+    seed: &[u8],
+  ) -> Result<Vec<u8>, &'static str> {
+    raw_public_key(&self.key_from_seed(seed)?)
+  }
+
+  /// Checks that `public_key` is a well formed encapsulation key.
+  pub fn check_public_key(self, public_key: &[u8]) -> Result<(), &'static str> {
+    self.key_from_public_key(public_key).map(|_| ())
+  }
+
+  /// Returns the ciphertext and the shared secret.
+  pub fn encapsulate(
+    self,
+    public_key: &[u8],
+  ) -> Result<(Vec<u8>, Vec<u8>), &'static str> {
+    let pkey = self.key_from_public_key(public_key)?;
+    let ctx = PKeyCtx::new(&pkey)?;
+    let mut ciphertext = vec![0; self.ciphertext_len()];
+    let mut shared_secret = vec![0; SHARED_SECRET_LEN];
+    let mut ciphertext_len = ciphertext.len();
+    let mut shared_secret_len = shared_secret.len();
+    // SAFETY: Both output buffers are as large as their passed lengths.
+    let result = unsafe {
+      aws_lc_sys::EVP_PKEY_encapsulate(
+        ctx.as_ptr(),
+        ciphertext.as_mut_ptr(),
+        &mut ciphertext_len,
+        shared_secret.as_mut_ptr(),
+        &mut shared_secret_len,
+      )
+    };
+    if result != 1 {
+      return Err("ML-KEM encapsulation failed");
+    }
+    ciphertext.truncate(ciphertext_len);
+    shared_secret.truncate(shared_secret_len);
+    Ok((ciphertext, shared_secret))
+  }
+
+  pub fn decapsulate(
+    self,
+    seed: &[u8],
+    ciphertext: &[u8],
+  ) -> Result<Vec<u8>, &'static str> {
+    if ciphertext.len() != self.ciphertext_len() {
+      return Err("Invalid ML-KEM ciphertext length");
+    }
+    let pkey = self.key_from_seed(seed)?;
+    let ctx = PKeyCtx::new(&pkey)?;
+    let mut shared_secret = vec![0; SHARED_SECRET_LEN];
+    let mut shared_secret_len = shared_secret.len();
+    // SAFETY: The output buffer is as large as its passed length.
+    let result = unsafe {
+      aws_lc_sys::EVP_PKEY_decapsulate(
+        ctx.as_ptr(),
+        shared_secret.as_mut_ptr(),
+        &mut shared_secret_len,
+        ciphertext.as_ptr(),
+        ciphertext.len(),
+      )
+    };
+    if result != 1 {
+      return Err("ML-KEM decapsulation failed");
+    }
+    shared_secret.truncate(shared_secret_len);
+    Ok(shared_secret)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn encapsulate_decapsulate() {
+    for alg in [MlKem::MlKem512, MlKem::MlKem768, MlKem::MlKem1024] {
+      let seed = [7; SEED_LEN];
+      let public_key = alg.public_key_from_seed(&seed).unwrap();
+      assert_eq!(public_key.len(), alg.public_key_len());
+      // Key derivation is deterministic.
+      assert_eq!(alg.public_key_from_seed(&seed).unwrap(), public_key);
+
+      let (ciphertext, shared_secret) = alg.encapsulate(&public_key).unwrap();
+      assert_eq!(ciphertext.len(), alg.ciphertext_len());
+      assert_eq!(alg.decapsulate(&seed, &ciphertext).unwrap(), shared_secret);
+
+      // Implicit rejection yields a different secret for another key.
+      let other = alg.decapsulate(&[8; SEED_LEN], &ciphertext).unwrap();
+      assert_ne!(other, shared_secret);
+    }
+  }
+
+  #[test]
+  fn rejects_invalid_lengths() {
+    let alg = MlKem::MlKem768;
+    assert!(alg.public_key_from_seed(&[0; 32]).is_err());
+    assert!(alg.check_public_key(&[0; 800]).is_err());
+    assert!(alg.decapsulate(&[0; SEED_LEN], &[0; 10]).is_err());
+  }
+
+  fn encapsulate_deterministic(
+    alg: MlKem,
+    public_key: &[u8],
+    entropy: &[u8],
+  ) -> (Vec<u8>, Vec<u8>) {
+    let pkey = alg.key_from_public_key(public_key).unwrap();
+    let ctx = PKeyCtx::new(&pkey).unwrap();
+    let mut ciphertext = vec![0; alg.ciphertext_len()];
+    let mut shared_secret = vec![0; SHARED_SECRET_LEN];
+    let mut ciphertext_len = ciphertext.len();
+    let mut shared_secret_len = shared_secret.len();
+    let mut entropy_len = entropy.len();
+    // SAFETY: Both output buffers are as large as their passed lengths and
+    // the length of `entropy` is passed along with it.
+    let result = unsafe {
+      aws_lc_sys::EVP_PKEY_encapsulate_deterministic(
+        ctx.as_ptr(),
+        ciphertext.as_mut_ptr(),
+        &mut ciphertext_len,
+        shared_secret.as_mut_ptr(),
+        &mut shared_secret_len,
+        entropy.as_ptr(),
+        &mut entropy_len,
+      )
+    };
+    assert_eq!(result, 1);
+    (ciphertext, shared_secret)
+  }
+
+  fn sha256(data: &[u8]) -> Vec<u8> {
+    let mut digest = vec![0; 32];
+    // SAFETY: `digest` is SHA256_DIGEST_LENGTH bytes long.
+    unsafe {
+      aws_lc_sys::SHA256(data.as_ptr(), data.len(), digest.as_mut_ptr())
+    };
+    digest
+  }
+
+  #[test]
+  fn known_answers() {
+    let vectors = crate::kat::parse(include_str!("testdata/ml_kem_kat.txt"));
+    assert_eq!(vectors.len(), 4);
+    for vector in vectors {
+      let alg = match vector.get("Algorithm").unwrap() {
+        "ML-KEM-512" => MlKem::MlKem512,
+        "ML-KEM-768" => MlKem::MlKem768,
+        "ML-KEM-1024" => MlKem::MlKem1024,
+        name => panic!("unknown algorithm {name}"),
+      };
+      let seed = vector.bytes("Seed").unwrap();
+      let shared_secret = vector.bytes("SharedSecret").unwrap();
+
+      let public_key = alg.public_key_from_seed(&seed).unwrap();
+      if let Some(expected) = vector.bytes("PublicKey") {
+        assert_eq!(public_key, expected);
+      }
+
+      let (ciphertext, encapsulated) = encapsulate_deterministic(
+        alg,
+        &public_key,
+        &vector.bytes("Entropy").unwrap(),
+      );
+      match vector.bytes("Ciphertext") {
+        Some(expected) => assert_eq!(ciphertext, expected),
+        None => assert_eq!(
+          sha256(&ciphertext),
+          vector.bytes("CiphertextSha256").unwrap()
+        ),
+      }
+      assert_eq!(encapsulated, shared_secret);
+      assert_eq!(alg.decapsulate(&seed, &ciphertext).unwrap(), shared_secret);
+
+      if let Some(expected) = vector.bytes("RejectSharedSecret") {
+        let zero = vec![0; alg.ciphertext_len()];
+        assert_eq!(alg.decapsulate(&seed, &zero).unwrap(), expected);
+      }
+    }
+  }
+}
@@ -2,6 +2,7 @@
 
 use std::borrow::Cow;
 use std::path::Path;
+use std::path::PathBuf;
 
 const CHAR_NL: u8 = b'\n';
 const CHAR_CR: u8 = b'\r';
@@ -47,9 +48,15 @@ impl From<std::io::Error> for Error {
   }
 }
 
+pub fn parse_env_content_hook(content: &str, mut cb: impl FnMut(&str, &str)) {
+  parse_env_content(content, |key, value, _| cb(key, value));
+}
+
 /// Ported from:
 /// https://github.com/nodejs/node/blob/9cc7fcc26dece769d9ffa06c453f0171311b01f8/src/node_dotenv.cc#L138-L315
-pub fn parse_env_content_hook(content: &str, mut cb: impl FnMut(&str, &str)) {
+///
+/// The callback is also provided whether the value was single quoted.
+fn parse_env_content(content: &str, mut cb: impl FnMut(&str, &str, bool)) {
   let raw = content.as_bytes();
   let mut filtered = Vec::new();
   let mut saw_cr = false;
@@ -130,7 +137,7 @@ pub fn parse_env_content_hook(content: &str, mut cb: impl FnMut(&str, &str)) {
     // If the value is not present (e.g. KEY=) set it to an empty string
     if text.is_empty() || text[0] == CHAR_NL {
       let key_str = std::str::from_utf8(key).unwrap();
-      cb(key_str, "");
+      cb(key_str, "", false);
       continue;
     }
 
@@ -166,7 +173,7 @@ pub fn parse_env_content_hook(content: &str, mut cb: impl FnMut(&str, &str)) {
     // In case the last line is a single key without value
     // Example: KEY= (without a newline at the EOF)
     if text.is_empty() {
-      cb(key_str, "");
+      cb(key_str, "", false);
       break;
     }
 
@@ -203,7 +210,7 @@ pub fn parse_env_content_hook(content: &str, mut cb: impl FnMut(&str, &str)) {
         }
         Cow::Owned(String::from_utf8(out).unwrap())
       };
-      cb(key_str, &value_str);
+      cb(key_str, &value_str, false);
 
       if let Some(newline) = find_char(text, CHAR_NL, closing + 1) {
         text = &text[newline + 1..];
@@ -222,7 +229,11 @@ pub fn parse_env_content_hook(content: &str, mut cb: impl FnMut(&str, &str)) {
       if let Some(closing) = find_char(text, quote, 1) {
         // Found closing quote - take content between quotes
         let value = &text[1..closing];
-        cb(key_str, std::str::from_utf8(value).unwrap());
+        cb(
+          key_str,
+          std::str::from_utf8(value).unwrap(),
+          quote == CHAR_SQUOTE,
+        );
 
         if let Some(newline) = find_char(text, CHAR_NL, closing + 1) {
           text = &text[newline + 1..];
@@ -239,11 +250,11 @@ pub fn parse_env_content_hook(content: &str, mut cb: impl FnMut(&str, &str)) {
         // The value pair should be `"value`
         if let Some(newline) = find_char(text, CHAR_NL, 0) {
           let value = &text[..newline];
-          cb(key_str, std::str::from_utf8(value).unwrap());
+          cb(key_str, std::str::from_utf8(value).unwrap(), false);
           text = &text[newline + 1..];
         } else {
           // No newline - take rest of content
-          cb(key_str, std::str::from_utf8(text).unwrap());
+          cb(key_str, std::str::from_utf8(text).unwrap(), false);
           break;
         }
       }
@@ -259,7 +270,7 @@ pub fn parse_env_content_hook(content: &str, mut cb: impl FnMut(&str, &str)) {
           value = &value[..hash];
         }
         let value = trim_spaces_slice(value);
-        cb(key_str, std::str::from_utf8(value).unwrap());
+        cb(key_str, std::str::from_utf8(value).unwrap(), false);
         text = &text[newline + 1..];
       } else {
         // Last line without newline
@@ -268,7 +279,7 @@ pub fn parse_env_content_hook(content: &str, mut cb: impl FnMut(&str, &str)) {
           value = &value[..hash];
         }
         let value = trim_spaces_slice(value);
-        cb(key_str, std::str::from_utf8(value).unwrap());
+        cb(key_str, std::str::from_utf8(value).unwrap(), false);
         text = &[];
       }
     }
@@ -279,22 +290,50 @@ pub fn parse_env_content_hook(content: &str, mut cb: impl FnMut(&str, &str)) {
 
 type IterElement = Result<(String, String), Error>;
 
+/// A variable declared in an environment file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvVar {
+  pub key: String,
+  pub value: String,
+  /// Whether the value was single quoted, which makes it literal so
+  /// references in it are not expanded.
+  pub is_literal: bool,
+}
+
 pub fn from_path_sanitized_iter(
   path: impl AsRef<Path>,
 ) -> Result<std::vec::IntoIter<IterElement>, Error> {
+  let vars = from_path_sanitized_vars(path)?;
+  Ok(
+    vars
+      .map(|item| item.map(|var| (var.key, var.value)))
+      .collect::<Vec<_>>()
+      .into_iter(),
+  )
+}
+
+/// Same as `from_path_sanitized_iter`, but also provides whether the
+/// values are literal for `expand_vars`.
+pub fn from_path_sanitized_vars(
+  path: impl AsRef<Path>,
+) -> Result<std::vec::IntoIter<Result<EnvVar, Error>>, Error> {
   let content = std::fs::read_to_string(path.as_ref()).map_err(Error::Io)?;
-  let mut pairs = Vec::new();
-  parse_env_content_hook(&content, |k, v| {
+  let mut vars = Vec::new();
+  parse_env_content(&content, |k, v, is_literal| {
     if let Some(index) = k
       .find('\0')
       .or_else(|| v.find('\0').map(|i| k.len() + i + 1))
     {
-      pairs.push(Err(Error::LineParse(format!("{}={}", k, v), index)));
+      vars.push(Err(Error::LineParse(format!("{}={}", k, v), index)));
     } else {
-      pairs.push(Ok((k.to_string(), v.to_string())));
+      vars.push(Ok(EnvVar {
+        key: k.to_string(),
+        value: v.to_string(),
+        is_literal,
+      }));
     }
   });
-  Ok(pairs.into_iter())
+  Ok(vars.into_iter())
 }
 
 pub fn from_path(filename: impl AsRef<Path>) -> Result<(), Error> {
@@ -308,6 +347,161 @@ pub fn from_path(filename: impl AsRef<Path>) -> Result<(), Error> {
   Ok(())
 }
 
+/// Gets the environment files layered for the provided mode, from the
+/// lowest to the highest precedence.
+///
+/// For example, `.env` with the `production` mode is layered as `.env`,
+/// `.env.local`, `.env.production` and `.env.production.local`.
+pub fn layered_paths(path: &Path, mode: Option<&str>) -> Vec<PathBuf> {
+  let Some(mode) = mode else {
+    return vec![path.to_path_buf()];
+  };
+  let with_suffix = |suffix: &str| {
+    let mut path = path.as_os_str().to_owned();
+    path.push(suffix);
+    PathBuf::from(path)
+  };
+  vec![
+    path.to_path_buf(),
+    with_suffix(".local"),
+    with_suffix(&format!(".{}", mode)),
+    with_suffix(&format!(".{}.local", mode)),
+  ]
+}
+
+/// Expands the `$VAR`, `${VAR}`, `${VAR:-default}` and `${VAR-default}`
+/// references in the values of the provided variables.
+///
+/// References resolve with `lookup` (ex. the process environment) first
+/// and then with the other variables. References that don't resolve
+/// expand to an empty string and a `$` may be escaped as `\$`. Literal
+/// values are left as-is.
+pub fn expand_vars(
+  vars: &mut [EnvVar],
+  lookup: impl Fn(&str) -> Option<String>,
+) {
+  let mut expander = Expander {
+    vars,
+    lookup,
+    expanded: vec![None; vars.len()],
+    expanding: vec![false; vars.len()],
+  };
+  let values = (0..vars.len())
+    .map(|index| expander.expand_var(index).unwrap_or_default())
+    .collect::<Vec<_>>();
+  for (var, expanded) in vars.iter_mut().zip(values) {
+    var.value = expanded;
+  }
+}
+
+struct Expander<'a, TLookup: Fn(&str) -> Option<String>> {
+  vars: &'a [EnvVar],
+  lookup: TLookup,
+  expanded: Vec<Option<String>>,
+  /// Used to detect variables that reference themselves.
+  expanding: Vec<bool>,
+}
+
+impl<TLookup: Fn(&str) -> Option<String>> Expander<'_, TLookup> {
+  fn resolve(&mut self, name: &str) -> Option<String> {
+    if let Some(value) = (self.lookup)(name) {
+      return Some(value);
+    }
+    let index = self.vars.iter().position(|var| var.key == name)?;
+    self.expand_var(index)
+  }
+
+  fn expand_var(&mut self, index: usize) -> Option<String> {
+    if let Some(value) = &self.expanded[index] {
+      return Some(value.clone());
+    }
+    if self.expanding[index] {
+      return None;
+    }
+    let var = &self.vars[index];
+    if var.is_literal {
+      return Some(var.value.clone());
+    }
+    self.expanding[index] = true;
+    let value = self.expand(&var.value);
+    self.expanding[index] = false;
+    self.expanded[index] = Some(value.clone());
+    Some(value)
+  }
+
+  fn expand(&mut self, value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(index) = rest.find(['\\', '$']) {
+      output.push_str(&rest[..index]);
+      rest = &rest[index..];
+      if let Some(after) = rest.strip_prefix("\\$") {
+        output.push('$');
+        rest = after;
+      } else if let Some(after) = rest.strip_prefix('\\') {
+        output.push('\\');
+        rest = after;
+      } else if let Some(after) = rest.strip_prefix("${")
+        && let Some(end) = find_closing_brace(after)
+      {
+        output.push_str(&self.expand_braced(&after[..end]));
+        rest = &after[end + 1..];
+      } else {
+        let after = &rest[1..];
+        let name_len = after
+          .char_indices()
+          .find(|(i, c)| {
+            !(c.is_ascii_alphabetic()
+              || *c == '_'
+              || *i > 0 && c.is_ascii_digit())
+          })
+          .map(|(i, _)| i)
+          .unwrap_or(after.len());
+        if name_len == 0 {
+          output.push('$');
+        } else {
+          output
+            .push_str(&self.resolve(&after[..name_len]).unwrap_or_default());
+        }
+        rest = &after[name_len..];
+      }
+    }
+    output.push_str(rest);
+    output
+  }
+
+  fn expand_braced(&mut self, inner: &str) -> String {
+    if let Some((name, default)) = inner.split_once(":-") {
+      match self.resolve(name) {
+        Some(value) if !value.is_empty() => value,
+        _ => self.expand(default),
+      }
+    } else if let Some((name, default)) = inner.split_once('-') {
+      match self.resolve(name) {
+        Some(value) => value,
+        None => self.expand(default),
+      }
+    } else {
+      self.resolve(inner).unwrap_or_default()
+    }
+  }
+}
+
+/// Finds the `}` that closes a `${`, accounting for nested references
+/// in default values.
+fn find_closing_brace(text: &str) -> Option<usize> {
+  let mut depth = 0;
+  for (index, c) in text.char_indices() {
+    match c {
+      '{' => depth += 1,
+      '}' if depth == 0 => return Some(index),
+      '}' => depth -= 1,
+      _ => {}
+    }
+  }
+  None
+}
+
 fn trim_spaces_slice(input: &[u8]) -> &[u8] {
   if input.is_empty() {
     return input;
@@ -654,4 +848,112 @@ u4QuUoobAgMBAAE=
     let content = "KEY='value'";
     assert_parsed_eq(content, &[("KEY", "value")]);
   }
+
+  fn expand(vars: &[(&str, &str)], env: &[(&str, &str)]) -> Vec<String> {
+    let mut vars = vars
+      .iter()
+      .map(|(k, v)| EnvVar {
+        key: (*k).to_string(),
+        value: (*v).to_string(),
+        is_literal: false,
+      })
+      .collect::<Vec<_>>();
+    expand_vars(&mut vars, |name| {
+      env
+        .iter()
+        .find(|(k, _)| *k == name)
+        .map(|(_, v)| (*v).to_string())
+    });
+    vars.into_iter().map(|var| var.value).collect()
+  }
+
+  #[test]
+  fn test_expand_vars() {
+    assert_eq!(
+      expand(
+        &[
+          ("HOST", "localhost"),
+          ("URL", "http://${HOST}:$PORT/$PATH_PART/x"),
+          ("PORT", "8000"),
+          ("PATH_PART", "api"),
+        ],
+        &[],
+      ),
+      vec!["localhost", "http://localhost:8000/api/x", "8000", "api"]
+    );
+    // the environment takes precedence
+    assert_eq!(
+      expand(
+        &[("HOST", "localhost"), ("URL", "$HOST")],
+        &[("HOST", "env")]
+      ),
+      vec!["localhost", "env"]
+    );
+    // unresolved, escaped and non-references
+    assert_eq!(
+      expand(&[("A", "$MISSING|\\$B|${B|$|$1|a\\b")], &[]),
+      vec!["|$B|${B|$|$1|a\\b"]
+    );
+    // self references don't recurse
+    assert_eq!(expand(&[("A", "$A-x")], &[]), vec!["-x"]);
+    assert_eq!(
+      expand(&[("PATH", "$PATH:/bin")], &[("PATH", "/usr/bin")]),
+      vec!["/usr/bin:/bin"]
+    );
+  }
+
+  #[test]
+  fn test_expand_vars_defaults() {
+    assert_eq!(
+      expand(
+        &[
+          ("EMPTY", ""),
+          ("A", "${MISSING:-default}"),
+          ("B", "${EMPTY:-default}"),
+          ("C", "${EMPTY-default}"),
+          ("D", "${MISSING-${A}}"),
+          ("E", "${A:-other}"),
+        ],
+        &[],
+      ),
+      vec!["", "default", "default", "", "default", "default"]
+    );
+  }
+
+  #[test]
+  fn test_expand_vars_single_quoted() {
+    let content = "A=a\nB='$A ${A}'\nC=\"$A\"\nD=$B";
+    let mut vars = Vec::new();
+    parse_env_content(content, |key, value, is_literal| {
+      vars.push(EnvVar {
+        key: key.to_string(),
+        value: value.to_string(),
+        is_literal,
+      });
+    });
+    assert_eq!(
+      vars.iter().map(|var| var.is_literal).collect::<Vec<_>>(),
+      vec![false, true, false, false]
+    );
+    expand_vars(&mut vars, |_| None);
+    assert_eq!(
+      vars.into_iter().map(|var| var.value).collect::<Vec<_>>(),
+      vec!["a", "$A ${A}", "a", "$A ${A}"]
+    );
+  }
+
+  #[test]
+  fn test_layered_paths() {
+    let path = Path::new("dir/.env");
+    assert_eq!(layered_paths(path, None), vec![PathBuf::from("dir/.env")]);
+    assert_eq!(
+      layered_paths(path, Some("production")),
+      vec![
+        PathBuf::from("dir/.env"),
+        PathBuf::from("dir/.env.local"),
+        PathBuf::from("dir/.env.production"),
+        PathBuf::from("dir/.env.production.local"),
+      ]
+    );
+  }
 }
@@ -0,0 +1,1502 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+//! Reads the lockfiles of npm, pnpm and Yarn so that the versions they
+//! resolved can be used to seed the npm section of a Deno lockfile.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use deno_semver::SmallStackString;
+use deno_semver::StackString;
+use deno_semver::Version;
+use deno_semver::package::PackageReq;
+use serde::Deserialize;
+use thiserror::Error;
+use yaml_rust2::Yaml;
+use yaml_rust2::YamlLoader;
+
+use crate::NpmPackageInfo;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExternalLockfileKind {
+  Npm,
+  Pnpm,
+  Yarn,
+}
+
+impl ExternalLockfileKind {
+  /// The kinds in the order they should be searched for.
+  pub const ALL: [ExternalLockfileKind; 3] = [
+    ExternalLockfileKind::Npm,
+    ExternalLockfileKind::Pnpm,
+    ExternalLockfileKind::Yarn,
+  ];
+
+  pub fn file_name(&self) -> &'static str {
+    match self {
+      ExternalLockfileKind::Npm => "package-lock.json",
+      ExternalLockfileKind::Pnpm => "pnpm-lock.yaml",
+      ExternalLockfileKind::Yarn => "yarn.lock",
+    }
+  }
+}
+
+#[derive(Debug, Error)]
+pub enum ExternalLockfileError {
+  #[error("Failed parsing {file_name}")]
+  Json {
+    file_name: &'static str,
+    #[source]
+    source: serde_json::Error,
+  },
+  #[error("Failed parsing {file_name}")]
+  Yaml {
+    file_name: &'static str,
+    #[source]
+    source: yaml_rust2::ScanError,
+  },
+  #[error("Unsupported {file_name} version '{version}'")]
+  UnsupportedVersion {
+    file_name: &'static str,
+    version: String,
+  },
+  #[error("Invalid package '{key}' in {file_name}")]
+  InvalidPackage {
+    file_name: &'static str,
+    key: String,
+  },
+}
+
+/// The npm packages resolved by an npm, pnpm or Yarn lockfile.
+#[derive(Debug, Default, Clone)]
+pub struct ExternalLockfile {
+  /// The packages keyed by their id (ex. `chalk@5.0.1`), which includes
+  /// the peer dependencies a package was resolved with the same way Deno
+  /// identifies them (ex. `react-dom@18.2.0_react@18.2.0`).
+  pub(crate) packages: BTreeMap<StackString, NpmPackageInfo>,
+  /// The ids of the packages the workspace depends on directly.
+  root_ids: Vec<StackString>,
+  /// Descriptors (ex. `chalk@^5.0.0`) to the id they resolve to, which
+  /// only Yarn lockfiles have.
+  descriptors: HashMap<String, StackString>,
+}
+
+impl ExternalLockfile {
+  pub fn parse(
+    kind: ExternalLockfileKind,
+    text: &str,
+  ) -> Result<Self, ExternalLockfileError> {
+    match kind {
+      ExternalLockfileKind::Npm => parse_npm(text),
+      ExternalLockfileKind::Pnpm => parse_pnpm(text),
+      ExternalLockfileKind::Yarn => parse_yarn(text),
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.packages.is_empty()
+  }
+
+  /// Gets the id of the package the lockfile resolved for the provided
+  /// package requirement.
+  pub fn resolve_req(&self, req: &PackageReq) -> Option<&StackString> {
+    let descriptor = format!("{}@{}", req.name, req.version_req.version_text());
+    if let Some(id) = self.descriptors.get(&descriptor) {
+      // prefer the peer dependency variant the workspace depends on
+      return Some(
+        self
+          .root_ids
+          .iter()
+          .find(|root_id| id_without_peers(root_id) == id.as_str())
+          .unwrap_or(id),
+      );
+    }
+    let matches = |id: &&StackString| {
+      extract_nv_from_id(id_without_peers(id)).is_some_and(|(name, version)| {
+        name == req.name
+          && Version::parse_from_npm(version)
+            .is_ok_and(|version| req.version_req.matches(&version))
+      })
+    };
+    self.root_ids.iter().find(matches).or_else(|| {
+      self.packages.keys().filter(matches).max_by_key(|id| {
+        extract_nv_from_id(id_without_peers(id))
+          .and_then(|(_, version)| Version::parse_from_npm(version).ok())
+      })
+    })
+  }
+}
+
+/// A package id along with the peer dependencies the package was
+/// resolved with.
+#[derive(Debug, Clone)]
+struct PeerId {
+  name: String,
+  version: String,
+  peers: Vec<PeerId>,
+}
+
+impl PeerId {
+  fn new(name: &str, version: &str) -> Self {
+    Self {
+      name: name.to_string(),
+      version: version.to_string(),
+      peers: Vec::new(),
+    }
+  }
+
+  /// Serializes the id the same way as Deno's npm package ids
+  /// (ex. `a@1.0.0_b@2.0.0__c@3.0.0`).
+  fn to_id(&self) -> StackString {
+    fn write(id: &PeerId, level: usize, text: &mut String) {
+      if level == 0 {
+        text.push_str(&id.name);
+      } else {
+        text.push_str(&id.name.replace('/', "+"));
+      }
+      text.push('@');
+      text.push_str(&id.version);
+      for peer in &id.peers {
+        for _ in 0..level + 1 {
+          text.push('_');
+        }
+        write(peer, level + 1, text);
+      }
+    }
+
+    let mut text = String::new();
+    write(self, 0, &mut text);
+    StackString::from_string(text)
+  }
+}
+
+/// Gets the id without the peer dependencies (ex. `a@1.0.0_b@2.0.0`
+/// -> `a@1.0.0`), which works because versions never contain a `_`.
+fn id_without_peers(id: &str) -> &str {
+  match extract_nv_from_id(id) {
+    Some((name, version)) => {
+      &id[..name.len() + 1 + version.find('_').unwrap_or(version.len())]
+    }
+    None => id,
+  }
+}
+
+pub(crate) fn extract_nv_from_id(value: &str) -> Option<(&str, &str)> {
+  if value.is_empty() {
+    return None;
+  }
+  let at_index = value[1..].find('@').map(|i| i + 1)?;
+  Some((&value[..at_index], &value[at_index + 1..]))
+}
+
+fn to_id(name: &str, version: &str) -> StackString {
+  let mut text = StackString::with_capacity(name.len() + 1 + version.len());
+  text.push_str(name);
+  text.push('@');
+  text.push_str(version);
+  text
+}
+
+fn new_package_info(integrity: Option<String>) -> NpmPackageInfo {
+  NpmPackageInfo {
+    integrity,
+    dependencies: Default::default(),
+    optional_dependencies: Default::default(),
+    optional_peers: Default::default(),
+    os: Vec::new(),
+    cpu: Vec::new(),
+    tarball: None,
+    deprecated: false,
+    scripts: false,
+    bin: false,
+  }
+}
+
+fn parse_npm(text: &str) -> Result<ExternalLockfile, ExternalLockfileError> {
+  const FILE_NAME: &str = "package-lock.json";
+
+  #[derive(Deserialize)]
+  #[serde(rename_all = "camelCase")]
+  struct PackageLock {
+    #[serde(default)]
+    lockfile_version: u32,
+    #[serde(default)]
+    packages: BTreeMap<String, PackageLockEntry>,
+  }
+
+  #[derive(Deserialize)]
+  #[serde(rename_all = "camelCase")]
+  struct PackageLockEntry {
+    name: Option<String>,
+    version: Option<String>,
+    integrity: Option<String>,
+    #[serde(default)]
+    link: bool,
+    #[serde(default)]
+    dependencies: BTreeMap<String, String>,
+    #[serde(default)]
+    dev_dependencies: BTreeMap<String, String>,
+    #[serde(default)]
+    optional_dependencies: BTreeMap<String, String>,
+    #[serde(default)]
+    peer_dependencies: BTreeMap<String, String>,
+    #[serde(default)]
+    peer_dependencies_meta: BTreeMap<String, PeerDependencyMeta>,
+    #[serde(default)]
+    os: Vec<SmallStackString>,
+    #[serde(default)]
+    cpu: Vec<SmallStackString>,
+    #[serde(default)]
+    has_install_script: bool,
+    bin: Option<serde_json::Value>,
+    deprecated: Option<String>,
+  }
+
+  #[derive(Deserialize)]
+  struct PeerDependencyMeta {
+    #[serde(default)]
+    optional: bool,
+  }
+
+  /// Finds the path of a dependency the same way node_modules
+  /// resolution does, by walking up the ancestor node_modules folders.
+  fn resolve_path<'a>(
+    packages: &'a BTreeMap<String, PackageLockEntry>,
+    mut from: &str,
+    name: &str,
+  ) -> Option<(&'a String, &'a PackageLockEntry)> {
+    loop {
+      let candidate = if from.is_empty() {
+        format!("node_modules/{name}")
+      } else {
+        format!("{from}/node_modules/{name}")
+      };
+      if let Some(entry) = packages.get_key_value(&candidate) {
+        return Some(entry);
+      }
+      if from.is_empty() {
+        return None;
+      }
+      // a top level package or workspace member goes up to the root
+      from = from
+        .rfind("/node_modules/")
+        .map(|index| &from[..index])
+        .unwrap_or("");
+    }
+  }
+
+  let lockfile: PackageLock = serde_json::from_str(text).map_err(|source| {
+    ExternalLockfileError::Json {
+      file_name: FILE_NAME,
+      source,
+    }
+  })?;
+  if lockfile.lockfile_version < 2 {
+    // version 1 lockfiles don't have the "packages" section
+    return Err(ExternalLockfileError::UnsupportedVersion {
+      file_name: FILE_NAME,
+      version: lockfile.lockfile_version.to_string(),
+    });
+  }
+
+  /// Gets the ids of the packages in the node_modules tree, which
+  /// includes the peer dependencies they resolved from their location.
+  struct PathIds<'a> {
+    packages: &'a BTreeMap<String, PackageLockEntry>,
+    ids: HashMap<&'a str, Option<PeerId>>,
+  }
+
+  impl<'a> PathIds<'a> {
+    fn get(&mut self, path: &'a str) -> Option<PeerId> {
+      if let Some(id) = self.ids.get(path) {
+        // this is also hit for a circular peer dependency, which will
+        // be left out
+        return id.clone();
+      }
+      self.ids.insert(path, None);
+      let entry = &self.packages[path];
+      if entry.link {
+        return None;
+      }
+      let name = entry.name.as_deref().or_else(|| {
+        path
+          .rfind("node_modules/")
+          .map(|index| &path[index + "node_modules/".len()..])
+      })?;
+      let mut id = PeerId::new(name, entry.version.as_deref()?);
+      for peer_name in entry.peer_dependencies.keys() {
+        if let Some(peer) = self.resolve(path, peer_name) {
+          id.peers.push(peer);
+        }
+      }
+      self.ids.insert(path, Some(id.clone()));
+      Some(id)
+    }
+
+    fn resolve(&mut self, from: &str, name: &str) -> Option<PeerId> {
+      let (path, _) = resolve_path(self.packages, from, name)?;
+      self.get(path)
+    }
+  }
+
+  let packages = &lockfile.packages;
+  let mut path_ids = PathIds {
+    packages,
+    ids: HashMap::new(),
+  };
+  let mut resolve_id =
+    |from: &str, name: &str| path_ids.resolve(from, name).map(|id| id.to_id());
+
+  let mut external = ExternalLockfile::default();
+  let mut package_paths = Vec::new();
+  for (path, entry) in packages {
+    if !path.starts_with("node_modules/") && !path.contains("/node_modules/") {
+      // the root or a workspace member
+      let deps = entry
+        .dependencies
+        .keys()
+        .chain(entry.dev_dependencies.keys())
+        .chain(entry.optional_dependencies.keys())
+        .chain(entry.peer_dependencies.keys());
+      for name in deps {
+        if let Some(id) = resolve_id(path, name)
+          && !external.root_ids.contains(&id)
+        {
+          external.root_ids.push(id);
+        }
+      }
+    } else {
+      package_paths.push((path, entry));
+    }
+  }
+  for (path, entry) in package_paths {
+    let Some(id) = path_ids.get(path).map(|id| id.to_id()) else {
+      continue;
+    };
+    if external.packages.contains_key(&id) {
+      continue;
+    }
+    let mut resolve_id =
+      |name: &str| path_ids.resolve(path, name).map(|id| id.to_id());
+    let mut info = new_package_info(entry.integrity.clone());
+    for name in entry.dependencies.keys() {
+      if let Some(dep_id) = resolve_id(name) {
+        info.dependencies.insert(name.as_str().into(), dep_id);
+      }
+    }
+    for name in entry.optional_dependencies.keys() {
+      if let Some(dep_id) = resolve_id(name) {
+        info
+          .optional_dependencies
+          .insert(name.as_str().into(), dep_id);
+      }
+    }
+    for name in entry.peer_dependencies.keys() {
+      let Some(dep_id) = resolve_id(name) else {
+        continue;
+      };
+      let is_optional = entry
+        .peer_dependencies_meta
+        .get(name)
+        .is_some_and(|meta| meta.optional);
+      if is_optional {
+        info.optional_peers.insert(name.as_str().into(), dep_id);
+      } else {
+        info.dependencies.insert(name.as_str().into(), dep_id);
+      }
+    }
+    info.os.clone_from(&entry.os);
+    info.cpu.clone_from(&entry.cpu);
+    info.deprecated = entry.deprecated.is_some();
+    info.scripts = entry.has_install_script;
+    info.bin = entry.bin.is_some();
+    external.packages.insert(id, info);
+  }
+  Ok(external)
+}
+
+fn parse_yaml(
+  file_name: &'static str,
+  text: &str,
+) -> Result<Yaml, ExternalLockfileError> {
+  let mut docs = YamlLoader::load_from_str(text)
+    .map_err(|source| ExternalLockfileError::Yaml { file_name, source })?;
+  Ok(if docs.is_empty() {
+    Yaml::Null
+  } else {
+    docs.swap_remove(0)
+  })
+}
+
+/// Gets the text of a scalar, which may have been parsed as a number.
+fn yaml_text(value: &Yaml) -> Option<String> {
+  match value {
+    Yaml::String(text) | Yaml::Real(text) => Some(text.clone()),
+    Yaml::Integer(value) => Some(value.to_string()),
+    _ => None,
+  }
+}
+
+fn yaml_entries(value: &Yaml) -> impl Iterator<Item = (String, &Yaml)> {
+  value
+    .as_hash()
+    .into_iter()
+    .flatten()
+    .filter_map(|(key, value)| Some((yaml_text(key)?, value)))
+}
+
+fn yaml_str_list(value: &Yaml) -> Vec<SmallStackString> {
+  value
+    .as_vec()
+    .into_iter()
+    .flatten()
+    .filter_map(yaml_text)
+    .map(SmallStackString::from_string)
+    .collect()
+}
+
+fn parse_pnpm(text: &str) -> Result<ExternalLockfile, ExternalLockfileError> {
+  const FILE_NAME: &str = "pnpm-lock.yaml";
+
+  /// Parses a package id that has its peer dependencies in parentheses.
+  ///
+  /// ex. `a@1.0.0(b@2.0.0(c@3.0.0))(d@4.0.0)`
+  fn parse_id(text: &str) -> Option<PeerId> {
+    fn parse(text: &str) -> Option<(PeerId, &str)> {
+      let end = text.find(['(', ')']).unwrap_or(text.len());
+      let (name, version) = extract_nv_from_id(&text[..end])?;
+      let mut id = PeerId::new(name, version);
+      let mut rest = &text[end..];
+      while let Some(inner) = rest.strip_prefix('(') {
+        rest = match parse(inner) {
+          Some((peer, rest)) => {
+            id.peers.push(peer);
+            rest.strip_prefix(')')?
+          }
+          // not a peer dependency (ex. `(patch_hash=...)`)
+          None => &inner[inner.find(')')? + 1..],
+        };
+      }
+      Some((id, rest))
+    }
+
+    let text = text.strip_prefix('/').unwrap_or(text);
+    match parse(text)? {
+      (id, "") => Some(id),
+      _ => None,
+    }
+  }
+
+  /// Converts the version of a dependency to an id.
+  ///
+  /// ex. `1.0.0(bar@2.0.0)` or an alias like `qux@1.0.0`
+  fn dep_to_id(name: &str, version: &str) -> Option<StackString> {
+    if version.starts_with("link:") || version.starts_with("file:") {
+      return None;
+    }
+    let version = version.strip_prefix('/').unwrap_or(version);
+    let version_without_peers =
+      version.split_once('(').map(|(v, _)| v).unwrap_or(version);
+    let id = if extract_nv_from_id(version_without_peers).is_some() {
+      parse_id(version)
+    } else {
+      parse_id(&format!("{name}@{version}"))
+    };
+    id.map(|id| id.to_id())
+  }
+
+  fn add_deps(value: &Yaml, deps: &mut BTreeMap<StackString, StackString>) {
+    for (name, version) in yaml_entries(value) {
+      if let Some(id) = yaml_text(version).and_then(|v| dep_to_id(&name, &v)) {
+        deps.insert(StackString::from_string(name), id);
+      }
+    }
+  }
+
+  /// Moves the resolved optional peer dependencies out of the
+  /// dependencies, which is where pnpm puts them.
+  fn move_optional_peers(value: &Yaml, info: &mut NpmPackageInfo) {
+    for (name, meta) in yaml_entries(&value["peerDependenciesMeta"]) {
+      if meta["optional"].as_bool().unwrap_or(false)
+        && let Some(id) = info.dependencies.remove(name.as_str())
+      {
+        info
+          .optional_peers
+          .insert(StackString::from_string(name), id);
+      }
+    }
+  }
+
+  let root = parse_yaml(FILE_NAME, text)?;
+  let version = yaml_text(&root["lockfileVersion"]).unwrap_or_default();
+  let major_version = version
+    .split('.')
+    .next()
+    .and_then(|v| v.parse::<u32>().ok())
+    .unwrap_or(0);
+  if major_version < 6 {
+    return Err(ExternalLockfileError::UnsupportedVersion {
+      file_name: FILE_NAME,
+      version,
+    });
+  }
+
+  let mut external = ExternalLockfile::default();
+  // single package lockfiles don't have the "importers" section
+  let importers = match root["importers"].as_hash() {
+    Some(_) => yaml_entries(&root["importers"]).map(|(_, v)| v).collect(),
+    None => vec![&root],
+  };
+  for importer in importers {
+    for section in ["dependencies", "devDependencies", "optionalDependencies"] {
+      for (name, dep) in yaml_entries(&importer[section]) {
+        let version = match dep {
+          Yaml::Hash(_) => yaml_text(&dep["version"]),
+          _ => yaml_text(dep),
+        };
+        if let Some(id) = version.and_then(|v| dep_to_id(&name, &v))
+          && !external.root_ids.contains(&id)
+        {
+          external.root_ids.push(id);
+        }
+      }
+    }
+  }
+
+  let has_snapshots = root["snapshots"].as_hash().is_some();
+  let mut base_packages = HashMap::new();
+  for (key, value) in yaml_entries(&root["packages"]) {
+    let Some(id) = parse_id(&key) else {
+      return Err(ExternalLockfileError::InvalidPackage {
+        file_name: FILE_NAME,
+        key,
+      });
+    };
+    let mut info =
+      new_package_info(yaml_text(&value["resolution"]["integrity"]));
+    info.os = yaml_str_list(&value["os"]);
+    info.cpu = yaml_str_list(&value["cpu"]);
+    info.deprecated = !value["deprecated"].is_badvalue();
+    info.scripts = value["requiresBuild"].as_bool().unwrap_or(false);
+    info.bin = value["hasBin"].as_bool().unwrap_or(false);
+    if has_snapshots {
+      // version 9 lockfiles have a snapshot for each peer dependency
+      // variant of the package
+      base_packages.insert(to_id(&id.name, &id.version), (info, value));
+    } else {
+      // while version 6 lockfiles have them in the packages section
+      add_deps(&value["dependencies"], &mut info.dependencies);
+      add_deps(
+        &value["optionalDependencies"],
+        &mut info.optional_dependencies,
+      );
+      move_optional_peers(value, &mut info);
+      external.packages.insert(id.to_id(), info);
+    }
+  }
+  for (key, value) in yaml_entries(&root["snapshots"]) {
+    let Some(id) = parse_id(&key) else {
+      continue;
+    };
+    let Some((base_info, base_value)) =
+      base_packages.get(&to_id(&id.name, &id.version))
+    else {
+      continue;
+    };
+    let mut info = base_info.clone();
+    add_deps(&value["dependencies"], &mut info.dependencies);
+    add_deps(
+      &value["optionalDependencies"],
+      &mut info.optional_dependencies,
+    );
+    move_optional_peers(base_value, &mut info);
+    external.packages.insert(id.to_id(), info);
+  }
+  Ok(external)
+}
+
+fn parse_yarn(text: &str) -> Result<ExternalLockfile, ExternalLockfileError> {
+  const FILE_NAME: &str = "yarn.lock";
+
+  /// An entry that may be resolved by several descriptors.
+  struct YarnEntry {
+    descriptors: Vec<String>,
+    is_workspace: bool,
+    version: String,
+    integrity: Option<String>,
+    dependencies: Vec<(String, String)>,
+    optional_dependencies: Vec<(String, String)>,
+    /// The peer dependencies and whether they're optional, which only
+    /// Yarn 2+ lockfiles have.
+    peer_dependencies: Vec<(String, bool)>,
+    bin: bool,
+  }
+
+  /// Normalizes a descriptor so Yarn 1 and Yarn 2+ descriptors are the
+  /// same (ex. `chalk@npm:^5.0.0` -> `chalk@^5.0.0`).
+  fn normalize_descriptor(name: &str, range: &str) -> String {
+    let range = match range.strip_prefix("npm:") {
+      // an alias keeps its name
+      Some(rest) if rest.contains('@') => range,
+      Some(rest) => rest,
+      None => range,
+    };
+    format!("{name}@{range}")
+  }
+
+  /// Gets the name of the package a descriptor resolves to.
+  fn descriptor_package_name(descriptor: &str) -> Option<&str> {
+    let (name, range) = extract_nv_from_id(descriptor)?;
+    match range.strip_prefix("npm:") {
+      Some(alias) => extract_nv_from_id(alias).map(|(name, _)| name),
+      None => Some(name),
+    }
+  }
+
+  fn parse_key(key: &str) -> Vec<String> {
+    key
+      .split(',')
+      .map(|descriptor| descriptor.trim().trim_matches('"'))
+      .filter_map(|descriptor| {
+        let (name, range) = extract_nv_from_id(descriptor)?;
+        if range.starts_with("workspace:")
+          || range.starts_with("patch:")
+          || range.starts_with("link:")
+          || range.starts_with("portal:")
+          || range.starts_with("file:")
+        {
+          return None;
+        }
+        Some(normalize_descriptor(name, range))
+      })
+      .collect()
+  }
+
+  fn parse_v1(text: &str) -> Vec<YarnEntry> {
+    fn unquote(text: &str) -> &str {
+      text.trim().trim_matches('"')
+    }
+
+    let mut entries = Vec::new();
+    let mut section = "";
+    for line in text.lines() {
+      if line.trim().is_empty() || line.trim_start().starts_with('#') {
+        continue;
+      }
+      let indent = line.len() - line.trim_start().len();
+      let line = line.trim();
+      if indent == 0 {
+        entries.push(YarnEntry {
+          descriptors: parse_key(line.trim_end_matches(':')),
+          is_workspace: false,
+          version: String::new(),
+          integrity: None,
+          dependencies: Vec::new(),
+          optional_dependencies: Vec::new(),
+          peer_dependencies: Vec::new(),
+          bin: false,
+        });
+        section = "";
+        continue;
+      }
+      let Some(entry) = entries.last_mut() else {
+        continue;
+      };
+      if indent <= 2 {
+        if let Some(name) = line.strip_suffix(':') {
+          section = name;
+          continue;
+        }
+        section = "";
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+        match key {
+          "version" => entry.version = unquote(value).to_string(),
+          "integrity" => entry.integrity = Some(unquote(value).to_string()),
+          _ => {}
+        }
+        continue;
+      }
+      // the name may be quoted and contain spaces in theory, but
+      // package names never do
+      let (name, range) = line.split_once(' ').unwrap_or((line, ""));
+      let dep = (unquote(name).to_string(), unquote(range).to_string());
+      match section {
+        "dependencies" => entry.dependencies.push(dep),
+        "optionalDependencies" => entry.optional_dependencies.push(dep),
+        "bin" => entry.bin = true,
+        _ => {}
+      }
+    }
+    entries
+  }
+
+  fn parse_berry(root: &Yaml) -> Vec<YarnEntry> {
+    fn deps(value: &Yaml) -> Vec<(String, String)> {
+      yaml_entries(value)
+        .filter_map(|(name, range)| Some((name, yaml_text(range)?)))
+        .collect()
+    }
+
+    yaml_entries(root)
+      .filter(|(key, _)| key != "__metadata")
+      .map(|(key, value)| {
+        let dependencies = deps(&value["dependencies"]);
+        // optional dependencies are listed in "dependenciesMeta"
+        let (optional_dependencies, dependencies) =
+          dependencies.into_iter().partition(|(name, _)| {
+            value["dependenciesMeta"][name.as_str()]["optional"]
+              .as_bool()
+              .unwrap_or(false)
+          });
+        let peer_dependencies = yaml_entries(&value["peerDependencies"])
+          .map(|(name, _)| {
+            let is_optional =
+              value["peerDependenciesMeta"][name.as_str()]["optional"]
+                .as_bool()
+                .unwrap_or(false);
+            (name, is_optional)
+          })
+          .collect();
+        YarnEntry {
+          descriptors: parse_key(&key),
+          is_workspace: key.contains("@workspace:"),
+          version: yaml_text(&value["version"]).unwrap_or_default(),
+          // the checksum is of Yarn's zip archive rather than the
+          // tarball, so the integrity is filled in from the registry
+          // when importing
+          integrity: None,
+          dependencies,
+          optional_dependencies,
+          peer_dependencies,
+          bin: value["bin"].as_hash().is_some(),
+        }
+      })
+      .collect()
+  }
+
+  let (entries, is_berry) = if text.contains("__metadata:") {
+    let root = parse_yaml(FILE_NAME, text)?;
+    (parse_berry(&root), true)
+  } else {
+    (parse_v1(text), false)
+  };
+
+  let mut external = ExternalLockfile::default();
+  for entry in &entries {
+    let Some(descriptor) = entry.descriptors.first() else {
+      continue;
+    };
+    let Some(name) = descriptor_package_name(descriptor) else {
+      return Err(ExternalLockfileError::InvalidPackage {
+        file_name: FILE_NAME,
+        key: descriptor.clone(),
+      });
+    };
+    if entry.version.is_empty() {
+      return Err(ExternalLockfileError::InvalidPackage {
+        file_name: FILE_NAME,
+        key: descriptor.clone(),
+      });
+    }
+    let id = to_id(name, &entry.version);
+    for descriptor in &entry.descriptors {
+      external.descriptors.insert(descriptor.clone(), id.clone());
+    }
+  }
+  let descriptors = external.descriptors.clone();
+  let resolve_deps = |deps: &[(String, String)]| {
+    deps
+      .iter()
+      .filter_map(|(name, range)| {
+        let descriptor = normalize_descriptor(name, range);
+        let id = descriptors.get(&descriptor)?;
+        Some((name.clone(), id.clone()))
+      })
+      .collect::<Vec<_>>()
+  };
+
+  if !is_berry {
+    // Yarn 1 lockfiles don't have the peer dependencies
+    for entry in &entries {
+      let Some(id) = entry.descriptors.first().and_then(|d| descriptors.get(d))
+      else {
+        continue;
+      };
+      let mut info = new_package_info(entry.integrity.clone());
+      info.bin = entry.bin;
+      for (name, dep_id) in resolve_deps(&entry.dependencies) {
+        info
+          .dependencies
+          .insert(StackString::from_string(name), dep_id);
+      }
+      for (name, dep_id) in resolve_deps(&entry.optional_dependencies) {
+        info
+          .optional_dependencies
+          .insert(StackString::from_string(name), dep_id);
+      }
+      external.packages.insert(id.clone(), info);
+    }
+    return Ok(external);
+  }
+
+  // Yarn 2+ lockfiles don't store the peer dependency variants of
+  // packages, so walk from the workspaces resolving the peer
+  // dependencies from the dependent packages like node_modules
+  // resolution does
+  let mut packages = HashMap::new();
+  for entry in &entries {
+    if let Some(id) = entry.descriptors.first().and_then(|d| descriptors.get(d))
+    {
+      packages.insert(
+        id.clone(),
+        YarnPackage {
+          dependencies: resolve_deps(&entry.dependencies),
+          optional_dependencies: resolve_deps(&entry.optional_dependencies),
+          peer_dependencies: entry.peer_dependencies.clone(),
+          integrity: entry.integrity.clone(),
+          bin: entry.bin,
+        },
+      );
+    }
+  }
+  let mut walker = YarnPeerWalker {
+    packages: &packages,
+    scopes: Vec::new(),
+    output: BTreeMap::new(),
+  };
+  for entry in entries.iter().filter(|entry| entry.is_workspace) {
+    let deps = resolve_deps(&entry.dependencies)
+      .into_iter()
+      .chain(resolve_deps(&entry.optional_dependencies))
+      .collect::<Vec<_>>();
+    let scope = walker.resolve_scope(&deps);
+    for (_, dep_id) in &deps {
+      if let Some(id) = scope.get(dep_id) {
+        let id = id.to_id();
+        if !external.root_ids.contains(&id) {
+          external.root_ids.push(id);
+        }
+      }
+    }
+    walker.visit_scope(&deps, scope);
+  }
+  external.packages = walker.output;
+  Ok(external)
+}
+
+/// A package in a Yarn 2+ lockfile with its dependencies resolved to
+/// ids without peer dependencies.
+struct YarnPackage {
+  dependencies: Vec<(String, StackString)>,
+  optional_dependencies: Vec<(String, StackString)>,
+  peer_dependencies: Vec<(String, bool)>,
+  integrity: Option<String>,
+  bin: bool,
+}
+
+struct YarnPeerWalker<'a> {
+  packages: &'a HashMap<StackString, YarnPackage>,
+  /// The packages the ancestors of the current package depend on by
+  /// name, where the closest ancestor is last.
+  scopes: Vec<HashMap<String, PeerId>>,
+  output: BTreeMap<StackString, NpmPackageInfo>,
+}
+
+impl YarnPeerWalker<'_> {
+  fn lookup(&self, name: &str) -> Option<&PeerId> {
+    self.scopes.iter().rev().find_map(|scope| scope.get(name))
+  }
+
+  /// Resolves the peer dependencies of the provided dependencies, which
+  /// may be provided by each other.
+  ///
+  /// Returns the resolved ids keyed by the ids without peer dependencies.
+  fn resolve_scope(
+    &self,
+    deps: &[(String, StackString)],
+  ) -> HashMap<StackString, PeerId> {
+    fn resolve(
+      walker: &YarnPeerWalker,
+      deps: &[(String, StackString)],
+      id: &StackString,
+      resolved: &mut HashMap<StackString, PeerId>,
+      pending: &mut Vec<StackString>,
+    ) -> Option<PeerId> {
+      if let Some(peer_id) = resolved.get(id) {
+        return Some(peer_id.clone());
+      }
+      let (name, version) = extract_nv_from_id(id)?;
+      let mut peer_id = PeerId::new(name, version);
+      pending.push(id.clone());
+      let peer_deps = walker
+        .packages
+        .get(id)
+        .map(|p| p.peer_dependencies.as_slice())
+        .unwrap_or_default();
+      for (peer_name, _) in peer_deps {
+        let sibling = deps.iter().find(|(name, _)| name == peer_name);
+        let peer = match sibling {
+          // a circular peer dependency is left out
+          Some((_, peer_id)) if pending.contains(peer_id) => None,
+          Some((_, peer_id)) => {
+            resolve(walker, deps, peer_id, resolved, pending)
+          }
+          None => walker.lookup(peer_name).cloned(),
+        };
+        peer_id.peers.extend(peer);
+      }
+      pending.pop();
+      resolved.insert(id.clone(), peer_id.clone());
+      Some(peer_id)
+    }
+
+    let mut resolved = HashMap::new();
+    for (_, id) in deps {
+      resolve(self, deps, id, &mut resolved, &mut Vec::new());
+    }
+    resolved
+  }
+
+  /// Adds the provided dependencies, which were resolved to the
+  /// provided scope, and their dependencies to the output.
+  fn visit_scope(
+    &mut self,
+    deps: &[(String, StackString)],
+    resolved: HashMap<StackString, PeerId>,
+  ) {
+    let scope = deps
+      .iter()
+      .filter_map(|(name, id)| Some((name.clone(), resolved.get(id)?.clone())))
+      .collect::<HashMap<_, _>>();
+    self.scopes.push(scope);
+    for (_, id) in deps {
+      if let Some(peer_id) = resolved.get(id) {
+        self.visit(id, peer_id);
+      }
+    }
+    self.scopes.pop();
+  }
+
+  fn visit(&mut self, id: &StackString, peer_id: &PeerId) {
+    let output_id = peer_id.to_id();
+    if self.output.contains_key(&output_id) {
+      return;
+    }
+    let Some(package) = self.packages.get(id) else {
+      return;
+    };
+    let deps = package
+      .dependencies
+      .iter()
+      .chain(&package.optional_dependencies)
+      .cloned()
+      .collect::<Vec<_>>();
+    let resolved = self.resolve_scope(&deps);
+    let mut info = new_package_info(package.integrity.clone());
+    info.bin = package.bin;
+    for (name, dep_id) in &package.dependencies {
+      if let Some(dep_peer_id) = resolved.get(dep_id) {
+        info
+          .dependencies
+          .insert(name.as_str().into(), dep_peer_id.to_id());
+      }
+    }
+    for (name, dep_id) in &package.optional_dependencies {
+      if let Some(dep_peer_id) = resolved.get(dep_id) {
+        info
+          .optional_dependencies
+          .insert(name.as_str().into(), dep_peer_id.to_id());
+      }
+    }
+    for (name, is_optional) in &package.peer_dependencies {
+      let Some(peer) = self.lookup(name) else {
+        continue;
+      };
+      let peer = peer.to_id();
+      if *is_optional {
+        info.optional_peers.insert(name.as_str().into(), peer);
+      } else {
+        info.dependencies.insert(name.as_str().into(), peer);
+      }
+    }
+    // insert before visiting the dependencies to handle cycles
+    self.output.insert(output_id, info);
+    self.visit_scope(&deps, resolved);
+  }
+}
+
+/// Gets the ids of the packages reachable from the provided ids.
+pub(crate) fn reachable_ids<'a>(
+  packages: &'a BTreeMap<StackString, NpmPackageInfo>,
+  ids: impl IntoIterator<Item = &'a StackString>,
+) -> HashSet<&'a StackString> {
+  let mut pending = ids.into_iter().collect::<Vec<_>>();
+  let mut seen = HashSet::new();
+  while let Some(id) = pending.pop() {
+    let Some((id, info)) = packages.get_key_value(id) else {
+      continue;
+    };
+    if !seen.insert(id) {
+      continue;
+    }
+    pending.extend(
+      info
+        .dependencies
+        .values()
+        .chain(info.optional_dependencies.values())
+        .chain(info.optional_peers.values()),
+    );
+  }
+  seen
+}
+
+#[cfg(test)]
+mod test {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  fn req(text: &str) -> PackageReq {
+    PackageReq::from_str(text).unwrap()
+  }
+
+  fn deps(info: &NpmPackageInfo) -> Vec<(&str, &str)> {
+    info
+      .dependencies
+      .iter()
+      .map(|(k, v)| (k.as_str(), v.as_str()))
+      .collect()
+  }
+
+  #[test]
+  fn parses_npm_lockfile() {
+    let external = ExternalLockfile::parse(
+      ExternalLockfileKind::Npm,
+      r#"{
+  "name": "project",
+  "lockfileVersion": 3,
+  "packages": {
+    "": {
+      "dependencies": { "a": "^1.0.0", "b": "npm:c@^2.0.0" }
+    },
+    "node_modules/a": {
+      "version": "1.2.0",
+      "integrity": "sha512-a",
+      "dependencies": { "d": "^1.0.0" },
+      "bin": { "a": "bin.js" }
+    },
+    "node_modules/a/node_modules/d": {
+      "version": "1.5.0",
+      "integrity": "sha512-d1"
+    },
+    "node_modules/b": {
+      "name": "c",
+      "version": "2.1.0",
+      "integrity": "sha512-c",
+      "dependencies": { "d": "^2.0.0" },
+      "hasInstallScript": true
+    },
+    "node_modules/d": {
+      "version": "2.0.0",
+      "integrity": "sha512-d2",
+      "os": ["linux"]
+    }
+  }
+}"#,
+    )
+    .unwrap();
+    assert_eq!(
+      external
+        .packages
+        .keys()
+        .map(|k| k.as_str())
+        .collect::<Vec<_>>(),
+      vec!["a@1.2.0", "c@2.1.0", "d@1.5.0", "d@2.0.0"]
+    );
+    let a = &external.packages["a@1.2.0"];
+    assert_eq!(a.integrity.as_deref(), Some("sha512-a"));
+    assert!(a.bin);
+    assert_eq!(deps(a), vec![("d", "d@1.5.0")]);
+    let c = &external.packages["c@2.1.0"];
+    assert!(c.scripts);
+    assert_eq!(deps(c), vec![("d", "d@2.0.0")]);
+    assert_eq!(external.packages["d@2.0.0"].os, vec!["linux"]);
+    assert_eq!(external.resolve_req(&req("a@^1.0.0")).unwrap(), "a@1.2.0");
+    assert_eq!(external.resolve_req(&req("c@^2.0.0")).unwrap(), "c@2.1.0");
+    assert_eq!(external.resolve_req(&req("d@1")).unwrap(), "d@1.5.0");
+    assert!(external.resolve_req(&req("a@^2.0.0")).is_none());
+  }
+
+  #[test]
+  fn parses_npm_lockfile_peer_dependencies() {
+    let external = ExternalLockfile::parse(
+      ExternalLockfileKind::Npm,
+      r#"{
+  "lockfileVersion": 3,
+  "packages": {
+    "": {
+      "dependencies": { "a": "^1.0.0", "b": "^2.0.0", "c": "^1.0.0" }
+    },
+    "node_modules/a": {
+      "version": "1.0.0",
+      "peerDependencies": { "b": "*", "d": "*" },
+      "peerDependenciesMeta": { "d": { "optional": true } }
+    },
+    "node_modules/b": {
+      "version": "2.0.0",
+      "peerDependencies": { "d": "*" }
+    },
+    "node_modules/c": {
+      "version": "1.0.0",
+      "dependencies": { "a": "^1.0.0", "b": "^3.0.0" }
+    },
+    "node_modules/c/node_modules/a": {
+      "version": "1.0.0",
+      "peerDependencies": { "b": "*" }
+    },
+    "node_modules/c/node_modules/b": {
+      "version": "3.0.0"
+    },
+    "node_modules/d": {
+      "version": "4.0.0"
+    }
+  }
+}"#,
+    )
+    .unwrap();
+    assert_eq!(
+      external
+        .packages
+        .keys()
+        .map(|k| k.as_str())
+        .collect::<Vec<_>>(),
+      vec![
+        "a@1.0.0_b@2.0.0__d@4.0.0_d@4.0.0",
+        "a@1.0.0_b@3.0.0",
+        "b@2.0.0_d@4.0.0",
+        "b@3.0.0",
+        "c@1.0.0",
+        "d@4.0.0",
+      ]
+    );
+    let a = &external.packages["a@1.0.0_b@2.0.0__d@4.0.0_d@4.0.0"];
+    assert_eq!(deps(a), vec![("b", "b@2.0.0_d@4.0.0")]);
+    assert_eq!(
+      a.optional_peers.get("d").map(|s| s.as_str()),
+      Some("d@4.0.0")
+    );
+    assert_eq!(
+      deps(&external.packages["c@1.0.0"]),
+      vec![("a", "a@1.0.0_b@3.0.0"), ("b", "b@3.0.0")]
+    );
+    assert_eq!(
+      external.resolve_req(&req("a@^1.0.0")).unwrap(),
+      "a@1.0.0_b@2.0.0__d@4.0.0_d@4.0.0"
+    );
+  }
+
+  #[test]
+  fn errors_npm_lockfile_v1() {
+    let err = ExternalLockfile::parse(
+      ExternalLockfileKind::Npm,
+      r#"{ "lockfileVersion": 1, "dependencies": {} }"#,
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "Unsupported package-lock.json version '1'");
+  }
+
+  #[test]
+  fn parses_pnpm_v9_lockfile() {
+    let external = ExternalLockfile::parse(
+      ExternalLockfileKind::Pnpm,
+      "lockfileVersion: '9.0'
+
+importers:
+
+  .:
+    dependencies:
+      a:
+        specifier: ^1.0.0
+        version: 1.2.0(b@2.0.0)
+      alias:
+        specifier: npm:@scope/c@^3.0.0
+        version: '@scope/c@3.1.0'
+
+packages:
+
+  '@scope/c@3.1.0':
+    resolution: {integrity: sha512-c}
+    hasBin: true
+
+  a@1.2.0:
+    resolution: {integrity: sha512-a}
+    peerDependencies:
+      b: ^2.0.0
+    cpu: [x64]
+
+  b@2.0.0:
+    resolution: {integrity: sha512-b}
+    deprecated: no longer maintained
+
+snapshots:
+
+  '@scope/c@3.1.0': {}
+
+  a@1.2.0(b@2.0.0):
+    dependencies:
+      b: 2.0.0
+
+  b@2.0.0: {}
+",
+    )
+    .unwrap();
+    assert_eq!(
+      external
+        .packages
+        .keys()
+        .map(|k| k.as_str())
+        .collect::<Vec<_>>(),
+      vec!["@scope/c@3.1.0", "a@1.2.0_b@2.0.0", "b@2.0.0"]
+    );
+    let a = &external.packages["a@1.2.0_b@2.0.0"];
+    assert_eq!(a.integrity.as_deref(), Some("sha512-a"));
+    assert_eq!(a.cpu, vec!["x64"]);
+    assert_eq!(deps(a), vec![("b", "b@2.0.0")]);
+    assert!(external.packages["b@2.0.0"].deprecated);
+    assert!(external.packages["@scope/c@3.1.0"].bin);
+    assert_eq!(
+      external
+        .root_ids
+        .iter()
+        .map(|k| k.as_str())
+        .collect::<Vec<_>>(),
+      vec!["a@1.2.0_b@2.0.0", "@scope/c@3.1.0"]
+    );
+    assert_eq!(
+      external.resolve_req(&req("a@^1.0.0")).unwrap(),
+      "a@1.2.0_b@2.0.0"
+    );
+    assert_eq!(
+      external.resolve_req(&req("@scope/c@^3")).unwrap(),
+      "@scope/c@3.1.0"
+    );
+  }
+
+  #[test]
+  fn parses_pnpm_v6_lockfile() {
+    let external = ExternalLockfile::parse(
+      ExternalLockfileKind::Pnpm,
+      "lockfileVersion: '6.0'
+
+dependencies:
+  a:
+    specifier: ^1.0.0
+    version: 1.2.0
+
+packages:
+
+  /a@1.2.0:
+    resolution: {integrity: sha512-a}
+    dependencies:
+      b: 2.0.0
+    requiresBuild: true
+    dev: false
+
+  /b@2.0.0:
+    resolution: {integrity: sha512-b}
+    dev: false
+",
+    )
+    .unwrap();
+    let a = &external.packages["a@1.2.0"];
+    assert!(a.scripts);
+    assert_eq!(deps(a), vec![("b", "b@2.0.0")]);
+    assert_eq!(external.resolve_req(&req("a@^1.0.0")).unwrap(), "a@1.2.0");
+
+    let err = ExternalLockfile::parse(
+      ExternalLockfileKind::Pnpm,
+      "lockfileVersion: 5.4\n",
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "Unsupported pnpm-lock.yaml version '5.4'");
+  }
+
+  #[test]
+  fn parses_yarn_v1_lockfile() {
+    let external = ExternalLockfile::parse(
+      ExternalLockfileKind::Yarn,
+      r#"# THIS IS AN AUTOGENERATED FILE. DO NOT EDIT THIS FILE DIRECTLY.
+# yarn lockfile v1
+
+
+"@scope/b@^2.0.0", "@scope/b@^2.1.0":
+  version "2.1.0"
+  resolved "https://registry.yarnpkg.com/@scope/b/-/b-2.1.0.tgz#abc"
+  integrity sha512-b
+
+a@^1.0.0:
+  version "1.2.0"
+  resolved "https://registry.yarnpkg.com/a/-/a-1.2.0.tgz#def"
+  integrity sha512-a
+  dependencies:
+    "@scope/b" "^2.1.0"
+  optionalDependencies:
+    c "~3.0.0"
+
+c@~3.0.0:
+  version "3.0.1"
+  integrity sha512-c
+"#,
+    )
+    .unwrap();
+    let a = &external.packages["a@1.2.0"];
+    assert_eq!(a.integrity.as_deref(), Some("sha512-a"));
+    assert_eq!(deps(a), vec![("@scope/b", "@scope/b@2.1.0")]);
+    assert_eq!(
+      a.optional_dependencies.get("c").map(|s| s.as_str()),
+      Some("c@3.0.1")
+    );
+    assert_eq!(external.resolve_req(&req("a@^1.0.0")).unwrap(), "a@1.2.0");
+    assert_eq!(
+      external.resolve_req(&req("@scope/b@^2.0.0")).unwrap(),
+      "@scope/b@2.1.0"
+    );
+  }
+
+  #[test]
+  fn parses_yarn_berry_lockfile() {
+    let external = ExternalLockfile::parse(
+      ExternalLockfileKind::Yarn,
+      r#"# This file is generated by running "yarn install" inside your project.
+
+__metadata:
+  version: 8
+  cacheKey: 10c0
+
+"a@npm:^1.0.0":
+  version: 1.2.0
+  resolution: "a@npm:1.2.0"
+  dependencies:
+    b: "npm:^2.0.0"
+  checksum: 10c0/abc
+  languageName: node
+  linkType: hard
+
+"b@npm:^2.0.0":
+  version: 2.0.0
+  resolution: "b@npm:2.0.0"
+  checksum: 10c0/def
+  languageName: node
+  linkType: hard
+
+"project@workspace:.":
+  version: 0.0.0-use.local
+  resolution: "project@workspace:."
+  dependencies:
+    a: "npm:^1.0.0"
+  languageName: unknown
+  linkType: soft
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+      external
+        .packages
+        .keys()
+        .map(|k| k.as_str())
+        .collect::<Vec<_>>(),
+      vec!["a@1.2.0", "b@2.0.0"]
+    );
+    let a = &external.packages["a@1.2.0"];
+    assert_eq!(a.integrity, None);
+    assert_eq!(deps(a), vec![("b", "b@2.0.0")]);
+    assert_eq!(external.resolve_req(&req("a@^1.0.0")).unwrap(), "a@1.2.0");
+  }
+
+  #[test]
+  fn parses_yarn_berry_lockfile_peer_dependencies() {
+    let external = ExternalLockfile::parse(
+      ExternalLockfileKind::Yarn,
+      r#"__metadata:
+  version: 8
+  cacheKey: 10c0
+
+"a@npm:^1.0.0":
+  version: 1.0.0
+  resolution: "a@npm:1.0.0"
+  peerDependencies:
+    b: "*"
+    d: "*"
+  peerDependenciesMeta:
+    d:
+      optional: true
+  languageName: node
+  linkType: hard
+
+"b@npm:^2.0.0":
+  version: 2.0.0
+  resolution: "b@npm:2.0.0"
+  languageName: node
+  linkType: hard
+
+"b@npm:^3.0.0":
+  version: 3.0.0
+  resolution: "b@npm:3.0.0"
+  languageName: node
+  linkType: hard
+
+"c@npm:^1.0.0":
+  version: 1.0.0
+  resolution: "c@npm:1.0.0"
+  dependencies:
+    a: "npm:^1.0.0"
+    b: "npm:^3.0.0"
+  languageName: node
+  linkType: hard
+
+"project@workspace:.":
+  version: 0.0.0-use.local
+  resolution: "project@workspace:."
+  dependencies:
+    a: "npm:^1.0.0"
+    b: "npm:^2.0.0"
+    c: "npm:^1.0.0"
+  languageName: unknown
+  linkType: soft
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+      external
+        .packages
+        .keys()
+        .map(|k| k.as_str())
+        .collect::<Vec<_>>(),
+      vec![
+        "a@1.0.0_b@2.0.0",
+        "a@1.0.0_b@3.0.0",
+        "b@2.0.0",
+        "b@3.0.0",
+        "c@1.0.0",
+      ]
+    );
+    assert_eq!(
+      deps(&external.packages["a@1.0.0_b@2.0.0"]),
+      vec![("b", "b@2.0.0")]
+    );
+    assert!(
+      external.packages["a@1.0.0_b@2.0.0"]
+        .optional_peers
+        .is_empty()
+    );
+    assert_eq!(
+      deps(&external.packages["c@1.0.0"]),
+      vec![("a", "a@1.0.0_b@3.0.0"), ("b", "b@3.0.0")]
+    );
+    assert_eq!(
+      external.resolve_req(&req("a@^1.0.0")).unwrap(),
+      "a@1.0.0_b@2.0.0"
+    );
+  }
+}
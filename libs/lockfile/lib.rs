@@ -4,6 +4,7 @@
 #![deny(clippy::print_stdout)]
 
 mod error;
+mod external;
 mod graphs;
 
 use std::borrow::Cow;
@@ -20,6 +21,7 @@ use deno_semver::Version;
 use deno_semver::jsr::JsrDepPackageReq;
 use deno_semver::package::PackageKind;
 use deno_semver::package::PackageNv;
+use deno_semver::package::PackageReq;
 use serde::Deserialize;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
@@ -30,6 +32,9 @@ mod transforms;
 pub use error::DeserializationError;
 pub use error::LockfileError;
 pub use error::LockfileErrorReason;
+pub use external::ExternalLockfile;
+pub use external::ExternalLockfileError;
+pub use external::ExternalLockfileKind;
 pub use transforms::Lockfile5NpmInfo;
 pub use transforms::NpmPackageInfoProvider;
 
@@ -1080,6 +1085,45 @@ impl Lockfile {
     }
   }
 
+  /// Seeds the npm packages from an npm, pnpm or Yarn lockfile so that
+  /// the versions it resolved for the provided package requirements are
+  /// kept when migrating to Deno.
+  ///
+  /// Packages already in the lockfile are left as-is. Returns the ids
+  /// of the packages that were inserted.
+  pub fn import_external_lockfile<'a>(
+    &mut self,
+    external: &ExternalLockfile,
+    reqs: impl IntoIterator<Item = &'a PackageReq>,
+  ) -> Vec<StackString> {
+    let mut root_ids = Vec::new();
+    for req in reqs {
+      let Some(id) = external.resolve_req(req) else {
+        continue;
+      };
+      let Some((_, version)) = external::extract_nv_from_id(id) else {
+        continue;
+      };
+      let dep = JsrDepPackageReq::npm(req.clone());
+      if !self.content.packages.specifiers.contains_key(&dep) {
+        self.insert_package_specifier(dep, version.into());
+      }
+      root_ids.push(id);
+    }
+
+    let mut inserted_ids = Vec::new();
+    for id in external::reachable_ids(&external.packages, root_ids) {
+      if let BTreeMapEntry::Vacant(entry) =
+        self.content.packages.npm.entry(id.clone())
+      {
+        entry.insert(external.packages[id].clone());
+        self.has_content_changed = true;
+        inserted_ids.push(id.clone());
+      }
+    }
+    inserted_ids
+  }
+
   /// Inserts a package specifier into the lockfile.
   pub fn insert_package_specifier(
     &mut self,
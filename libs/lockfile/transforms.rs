@@ -351,6 +351,7 @@ pub async fn transform4_to_5(
 #[serde(rename_all = "camelCase", default)]
 pub struct Lockfile5NpmInfo {
   pub tarball_url: Option<String>,
+  pub integrity: Option<String>,
   pub optional_dependencies: BTreeMap<String, String>,
   pub optional_peers: BTreeMap<String, String>,
   pub cpu: Vec<String>,
@@ -524,6 +524,9 @@ impl deno_lockfile::NpmPackageInfoProvider for TestNpmRegistryApi {
           .dist
           .as_ref()
           .map(|dist| dist.tarball.clone()),
+        integrity: version_info.dist.as_ref().and_then(|dist| {
+          dist.integrity().for_lockfile().map(|i| i.into_owned())
+        }),
         optional_dependencies: Default::default(),
         cpu: version_info.cpu.iter().map(|s| s.to_string()).collect(),
         os: version_info.os.iter().map(|s| s.to_string()).collect(),
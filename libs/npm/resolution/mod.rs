@@ -25,6 +25,7 @@ pub use snapshot::AddPkgReqsOptions;
 pub use snapshot::AddPkgReqsResult;
 pub use snapshot::DefaultTarballUrlProvider;
 pub use snapshot::IncompleteSnapshotFromLockfileError;
+pub use snapshot::NpmDependencyPathStep;
 pub use snapshot::NpmPackagesPartitioned;
 pub use snapshot::NpmRegistryDefaultTarballUrlProvider;
 pub use snapshot::NpmResolutionSnapshot;
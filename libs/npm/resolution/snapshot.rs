@@ -807,6 +807,150 @@ impl NpmResolutionSnapshot {
       .into_iter()
       .flatten()
   }
+
+  /// Gets every path of dependencies from the package the provided
+  /// requirement resolves to down to the packages matching `is_target`.
+  ///
+  /// A package is never visited twice within the same path, so cycles
+  /// in the dependency graph won't cause infinite paths. The number of
+  /// paths grows exponentially with the number of shared dependencies,
+  /// so this stops once `max_paths` paths have been found, and remembers
+  /// the packages that led to no path so they aren't walked again.
+  pub fn dependency_paths(
+    &self,
+    req: &PackageReq,
+    is_target: impl Fn(&NpmPackageId) -> bool,
+    max_paths: usize,
+  ) -> Vec<Vec<NpmDependencyPathStep>> {
+    let Ok(root) = self.resolve_pkg_from_pkg_req(req) else {
+      return Vec::new();
+    };
+
+    // only walk into packages that have a target somewhere below them
+    let mut dependents: HashMap<&NpmPackageId, Vec<&NpmPackageId>> =
+      HashMap::new();
+    for package in self.packages.values() {
+      for dep_id in package.dependencies.values() {
+        dependents.entry(dep_id).or_default().push(&package.id);
+      }
+    }
+    let mut reaches_target = HashSet::new();
+    let mut pending = self
+      .packages
+      .keys()
+      .filter(|id| is_target(id))
+      .collect::<VecDeque<_>>();
+    while let Some(id) = pending.pop_front() {
+      if reaches_target.insert(id)
+        && let Some(dependents) = dependents.get(id)
+      {
+        pending.extend(dependents.iter().copied());
+      }
+    }
+
+    struct Context<'a, F: Fn(&NpmPackageId) -> bool> {
+      snapshot: &'a NpmResolutionSnapshot,
+      is_target: F,
+      reaches_target: HashSet<&'a NpmPackageId>,
+      max_paths: usize,
+      current_path: Vec<NpmDependencyPathStep>,
+      paths: Vec<Vec<NpmDependencyPathStep>>,
+      /// Packages that led to no path, with how many packages of the
+      /// current path blocked them.
+      dead_ends: HashMap<&'a NpmPackageId, usize>,
+      /// The dead ends to forget once the path gets shorter than the index.
+      dead_ends_by_len: Vec<Vec<&'a NpmPackageId>>,
+    }
+
+    /// Returns `None` if a path was found below the package, otherwise how
+    /// many packages at the start of the current path kept it from finding
+    /// one.
+    fn visit<'a, F: Fn(&NpmPackageId) -> bool>(
+      ctx: &mut Context<'a, F>,
+      name: &StackString,
+      package: &'a NpmResolutionPackage,
+    ) -> Option<usize> {
+      if ctx.paths.len() >= ctx.max_paths {
+        return None;
+      }
+      if !ctx.reaches_target.contains(&package.id) {
+        return Some(0);
+      }
+      if let Some(index) = ctx
+        .current_path
+        .iter()
+        .position(|step| step.id == package.id)
+      {
+        return Some(index + 1);
+      }
+      if let Some(blocked_by) = ctx.dead_ends.get(&package.id) {
+        return Some(*blocked_by);
+      }
+
+      let len = ctx.current_path.len();
+      ctx.current_path.push(NpmDependencyPathStep {
+        name: name.clone(),
+        id: package.id.clone(),
+      });
+      let mut found = (ctx.is_target)(&package.id);
+      if found {
+        ctx.paths.push(ctx.current_path.clone());
+      }
+      let mut blocked_by = 0;
+      let mut deps = package.dependencies.iter().collect::<Vec<_>>();
+      deps.sort();
+      for (dep_name, dep_id) in deps {
+        if let Some(dep) = ctx.snapshot.packages.get(dep_id) {
+          match visit(ctx, dep_name, dep) {
+            Some(dep_blocked_by) => blocked_by = blocked_by.max(dep_blocked_by),
+            None => found = true,
+          }
+        }
+      }
+      ctx.current_path.pop();
+
+      // dead ends below this package may have been blocked by it
+      if let Some(ids) = ctx.dead_ends_by_len.get_mut(len + 1) {
+        for id in ids.drain(..) {
+          ctx.dead_ends.remove(id);
+        }
+      }
+      if found {
+        return None;
+      }
+      let blocked_by = blocked_by.min(len);
+      ctx.dead_ends.insert(&package.id, blocked_by);
+      if blocked_by > 0 {
+        if ctx.dead_ends_by_len.len() <= blocked_by {
+          ctx.dead_ends_by_len.resize_with(blocked_by + 1, Vec::new);
+        }
+        ctx.dead_ends_by_len[blocked_by].push(&package.id);
+      }
+      Some(blocked_by)
+    }
+
+    let mut ctx = Context {
+      snapshot: self,
+      is_target,
+      reaches_target,
+      max_paths,
+      current_path: Vec::new(),
+      paths: Vec::new(),
+      dead_ends: HashMap::new(),
+      dead_ends_by_len: Vec::new(),
+    };
+    visit(&mut ctx, &req.name, root);
+    ctx.paths
+  }
+}
+
+/// A package found while walking a path of dependencies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NpmDependencyPathStep {
+  /// What the dependent package refers to this package as, which
+  /// could be different from the package name.
+  pub name: StackString,
+  pub id: NpmPackageId,
 }
 
 pub struct SnapshotPackageCopyIndexResolver {
@@ -1286,6 +1430,172 @@ mod tests {
     );
   }
 
+  #[test]
+  fn dependency_paths() {
+    let snapshot = NpmResolutionSnapshot::new(
+      SerializedNpmResolutionSnapshot {
+        root_packages: root_pkgs(&[("a@1", "a@1.0.0")]),
+        packages: vec![
+          SerializedNpmResolutionSnapshotPackage {
+            dependencies: deps(&[
+              ("b", "b@1.0.0"),
+              ("c-alias", "c@1.0.0"),
+              ("e", "e@1.0.0"),
+            ]),
+            ..pkg_with_id("a@1.0.0")
+          },
+          SerializedNpmResolutionSnapshotPackage {
+            dependencies: deps(&[("d", "d@1.0.0")]),
+            ..pkg_with_id("b@1.0.0")
+          },
+          SerializedNpmResolutionSnapshotPackage {
+            dependencies: deps(&[("b", "b@1.0.0"), ("d", "d@2.0.0")]),
+            ..pkg_with_id("c@1.0.0")
+          },
+          SerializedNpmResolutionSnapshotPackage {
+            // cycle back to a
+            dependencies: deps(&[("a", "a@1.0.0")]),
+            ..pkg_with_id("d@1.0.0")
+          },
+          pkg_with_id("d@2.0.0"),
+          pkg_with_id("e@1.0.0"),
+        ],
+      }
+      .into_valid()
+      .unwrap(),
+    );
+
+    let paths = snapshot
+      .dependency_paths(
+        &PackageReq::from_str("a@1").unwrap(),
+        |id| id.nv.name == "d",
+        usize::MAX,
+      )
+      .into_iter()
+      .map(|path| {
+        path
+          .into_iter()
+          .map(|step| format!("{}:{}", step.name, step.id.as_serialized()))
+          .collect::<Vec<_>>()
+          .join(" > ")
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(
+      paths,
+      vec![
+        "a:a@1.0.0 > b:b@1.0.0 > d:d@1.0.0",
+        "a:a@1.0.0 > c-alias:c@1.0.0 > b:b@1.0.0 > d:d@1.0.0",
+        "a:a@1.0.0 > c-alias:c@1.0.0 > d:d@2.0.0",
+      ]
+    );
+
+    assert!(
+      snapshot
+        .dependency_paths(
+          &PackageReq::from_str("z@1").unwrap(),
+          |_| true,
+          usize::MAX
+        )
+        .is_empty()
+    );
+  }
+
+  #[test]
+  fn dependency_paths_max_paths() {
+    // a chain of 40 diamonds has 2^40 paths to the end
+    let mut packages = Vec::new();
+    for i in 0..40 {
+      let next = format!("p{}@1.0.0", i + 1);
+      let left = format!("l{}@1.0.0", i);
+      let right = format!("r{}@1.0.0", i);
+      packages.push(SerializedNpmResolutionSnapshotPackage {
+        dependencies: deps(&[("l", left.as_str()), ("r", right.as_str())]),
+        ..pkg_with_id(&format!("p{}@1.0.0", i))
+      });
+      packages.push(SerializedNpmResolutionSnapshotPackage {
+        dependencies: deps(&[("p", next.as_str())]),
+        ..pkg_with_id(&left)
+      });
+      packages.push(SerializedNpmResolutionSnapshotPackage {
+        dependencies: deps(&[("p", next.as_str())]),
+        ..pkg_with_id(&right)
+      });
+    }
+    packages.push(pkg_with_id("p40@1.0.0"));
+    let snapshot = NpmResolutionSnapshot::new(
+      SerializedNpmResolutionSnapshot {
+        root_packages: root_pkgs(&[("p0@1", "p0@1.0.0")]),
+        packages,
+      }
+      .into_valid()
+      .unwrap(),
+    );
+
+    let paths = snapshot.dependency_paths(
+      &PackageReq::from_str("p0@1").unwrap(),
+      |id| id.nv.name == "p40",
+      10,
+    );
+    assert_eq!(paths.len(), 10);
+  }
+
+  #[test]
+  fn dependency_paths_dead_ends() {
+    // a chain of 40 diamonds that cycles back to the root, so every package
+    // in it reaches the target, but only through the root
+    let mut packages = vec![SerializedNpmResolutionSnapshotPackage {
+      dependencies: deps(&[("p", "p0@1.0.0"), ("target", "target@1.0.0")]),
+      ..pkg_with_id("root@1.0.0")
+    }];
+    for i in 0..40 {
+      let next = if i == 39 {
+        "root@1.0.0".to_string()
+      } else {
+        format!("p{}@1.0.0", i + 1)
+      };
+      let left = format!("l{}@1.0.0", i);
+      let right = format!("r{}@1.0.0", i);
+      packages.push(SerializedNpmResolutionSnapshotPackage {
+        dependencies: deps(&[("l", left.as_str()), ("r", right.as_str())]),
+        ..pkg_with_id(&format!("p{}@1.0.0", i))
+      });
+      packages.push(SerializedNpmResolutionSnapshotPackage {
+        dependencies: deps(&[("p", next.as_str())]),
+        ..pkg_with_id(&left)
+      });
+      packages.push(SerializedNpmResolutionSnapshotPackage {
+        dependencies: deps(&[("p", next.as_str())]),
+        ..pkg_with_id(&right)
+      });
+    }
+    packages.push(pkg_with_id("target@1.0.0"));
+    let snapshot = NpmResolutionSnapshot::new(
+      SerializedNpmResolutionSnapshot {
+        root_packages: root_pkgs(&[("root@1", "root@1.0.0")]),
+        packages,
+      }
+      .into_valid()
+      .unwrap(),
+    );
+
+    let paths = snapshot
+      .dependency_paths(
+        &PackageReq::from_str("root@1").unwrap(),
+        |id| id.nv.name == "target",
+        usize::MAX,
+      )
+      .into_iter()
+      .map(|path| {
+        path
+          .into_iter()
+          .map(|step| step.id.as_serialized().to_string())
+          .collect::<Vec<_>>()
+          .join(" > ")
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(paths, vec!["root@1.0.0 > target@1.0.0"]);
+  }
+
   fn npm_cache_folder_id(
     name: &str,
     version: &str,
@@ -376,7 +376,8 @@ impl<TNpmCacheHttpClient: NpmCacheHttpClient, TSys: NpmInstallerSys>
     for err in self.npm_install_deps_provider.pkg_json_dep_errors() {
       match err.source.as_kind() {
         deno_package_json::PackageJsonDepValueParseErrorKind::JsrRequiresScope { .. } |
-        deno_package_json::PackageJsonDepValueParseErrorKind::VersionReq { .. } => {
+        deno_package_json::PackageJsonDepValueParseErrorKind::VersionReq { .. } |
+        deno_package_json::PackageJsonDepValueParseErrorKind::CatalogEntryNotFound { .. } => {
           return Err(Box::new(err.clone()));
         }
         deno_package_json::PackageJsonDepValueParseErrorKind::Unsupported {
@@ -1,5 +1,6 @@
 // Copyright 2018-2026 the Deno authors. MIT license.
 
+use std::borrow::Cow;
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -74,11 +75,18 @@ impl NpmInstallDepsProvider {
         // should inline their import map to get this behaviour
         if let Some(serde_json::Value::Object(obj)) = &deno_json.json.imports {
           let mut pkg_pkgs = Vec::with_capacity(obj.len());
-          for (_alias, value) in obj {
+          for (alias, value) in obj {
             let serde_json::Value::String(specifier) = value else {
               continue;
             };
-            let Ok(npm_req_ref) = NpmPackageReqReference::from_str(specifier)
+            let specifier =
+              match workspace.resolve_catalog_import_value(alias, specifier) {
+                Some(Ok(specifier)) => Cow::Owned(specifier),
+                // surfaced in the workspace diagnostics
+                Some(Err(_)) => continue,
+                None => Cow::Borrowed(specifier),
+              };
+            let Ok(npm_req_ref) = NpmPackageReqReference::from_str(&specifier)
             else {
               continue;
             };
@@ -108,7 +116,7 @@ impl NpmInstallDepsProvider {
       }
 
       if let Some(pkg_json) = &folder.pkg_json {
-        let deps = pkg_json.resolve_local_package_json_deps();
+        let deps = workspace.resolve_pkg_json_deps(pkg_json);
         let mut pkg_pkgs = Vec::with_capacity(
           deps.dependencies.len() + deps.dev_dependencies.len(),
         );
@@ -174,6 +182,8 @@ impl NpmInstallDepsProvider {
                 });
               }
             }
+            // resolved above
+            PackageJsonDepValue::Catalog(_) => {}
           }
         }
 
@@ -70,6 +70,9 @@ pub enum PackageJsonDepValueParseErrorKind {
   #[class(inherit)]
   #[error(transparent)]
   JsrRequiresScope(#[from] JsrDepPackageParseError),
+  #[class(type)]
+  #[error("No catalog entry '{alias}' was found for catalog '{catalog}'.")]
+  CatalogEntryNotFound { catalog: String, alias: String },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -142,11 +145,18 @@ pub fn parse_jsr_dep_value<'a>(
   Ok((npm_name, version_str))
 }
 
+/// The name of the catalog used by `catalog:`.
+pub const DEFAULT_CATALOG_NAME: &str = "default";
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum PackageJsonDepValue {
   File(String),
   Req(PackageReq),
   Workspace(PackageJsonDepWorkspaceReq),
+  /// "catalog:" or "catalog:<name>" where the value is the catalog name.
+  ///
+  /// These are resolved using the catalogs of the workspace root.
+  Catalog(StackString),
 }
 
 impl PackageJsonDepValue {
@@ -200,6 +210,11 @@ impl PackageJsonDepValue {
           };
           Ok(Self::Workspace(workspace_req))
         }
+        "catalog" => Ok(Self::Catalog(if value.is_empty() {
+          StackString::from_static(DEFAULT_CATALOG_NAME)
+        } else {
+          StackString::from(value)
+        })),
         scheme => Err(
           PackageJsonDepValueParseErrorKind::Unsupported {
             scheme: scheme.to_string(),
@@ -225,6 +240,42 @@ pub struct PackageJsonDeps {
 }
 
 impl PackageJsonDeps {
+  /// Resolves the `catalog:` dependencies to the values found in the
+  /// provided catalogs.
+  pub fn with_catalogs(&self, catalogs: &PackageJsonCatalogs) -> Self {
+    fn resolve_map(
+      deps: &PackageJsonDepsMap,
+      catalogs: &PackageJsonCatalogs,
+    ) -> PackageJsonDepsMap {
+      deps
+        .iter()
+        .map(|(alias, dep)| {
+          let dep = match dep {
+            Ok(PackageJsonDepValue::Catalog(catalog)) => {
+              catalogs.resolve_dep(catalog, alias)
+            }
+            dep => dep.clone(),
+          };
+          (alias.clone(), dep)
+        })
+        .collect()
+    }
+
+    Self {
+      dependencies: resolve_map(&self.dependencies, catalogs),
+      dev_dependencies: resolve_map(&self.dev_dependencies, catalogs),
+    }
+  }
+
+  /// Gets if any of the dependencies use the `catalog:` protocol.
+  pub fn has_catalog_deps(&self) -> bool {
+    self
+      .dependencies
+      .values()
+      .chain(self.dev_dependencies.values())
+      .any(|dep| matches!(dep, Ok(PackageJsonDepValue::Catalog(_))))
+  }
+
   /// Gets a package.json dependency entry by alias.
   pub fn get(
     &self,
@@ -237,6 +288,75 @@ impl PackageJsonDeps {
   }
 }
 
+/// Named sets of dependency versions that workspace members reference
+/// via `catalog:` (the default catalog) or `catalog:<name>`.
+///
+/// The values are in the same format as package.json dependency values
+/// (ex. `^1.2.0`, `npm:other@^1` or `jsr:@scope/name@^1`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackageJsonCatalogs(
+  IndexMap<StackString, IndexMap<StackString, String>>,
+);
+
+impl PackageJsonCatalogs {
+  pub fn is_empty(&self) -> bool {
+    self.0.values().all(|entries| entries.is_empty())
+  }
+
+  /// Adds the entries to the catalog with the provided name, overwriting
+  /// any existing entries with the same package name.
+  pub fn extend(
+    &mut self,
+    catalog: &str,
+    entries: impl IntoIterator<Item = (StackString, String)>,
+  ) {
+    self
+      .0
+      .entry(StackString::from(catalog))
+      .or_default()
+      .extend(entries);
+  }
+
+  pub fn get(&self, catalog: &str, alias: &str) -> Option<&str> {
+    self.0.get(catalog)?.get(alias).map(|v| v.as_str())
+  }
+
+  pub fn iter(
+    &self,
+  ) -> impl Iterator<Item = (&StackString, &IndexMap<StackString, String>)> {
+    self.0.iter()
+  }
+
+  /// Resolves the dependency value of an entry in a catalog.
+  pub fn resolve_dep(
+    &self,
+    catalog: &str,
+    alias: &str,
+  ) -> Result<PackageJsonDepValue, PackageJsonDepValueParseError> {
+    let Some(value) = self.get(catalog, alias) else {
+      return Err(
+        PackageJsonDepValueParseErrorKind::CatalogEntryNotFound {
+          catalog: catalog.to_string(),
+          alias: alias.to_string(),
+        }
+        .into_box(),
+      );
+    };
+    match PackageJsonDepValue::parse(alias, value)? {
+      // catalogs referencing the workspace or other catalogs is not supported
+      PackageJsonDepValue::Workspace(_) | PackageJsonDepValue::Catalog(_) => {
+        Err(
+          PackageJsonDepValueParseErrorKind::Unsupported {
+            scheme: value.split_once(':').unwrap().0.to_string(),
+          }
+          .into_box(),
+        )
+      }
+      dep => Ok(dep),
+    }
+  }
+}
+
 #[derive(Debug, Error, JsError)]
 pub enum PackageJsonLoadError {
   #[class(inherit)]
@@ -292,6 +412,10 @@ pub struct PackageJson {
   pub cpu: Option<Vec<String>>,
   #[serde(skip_serializing_if = "Option::is_none")]
   pub overrides: Option<Map<String, Value>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub catalog: Option<IndexMap<String, String>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub catalogs: Option<IndexMap<String, IndexMap<String, String>>>,
   #[serde(skip_serializing)]
   resolved_deps: PackageJsonDepsRcCell,
 }
@@ -364,6 +488,8 @@ impl PackageJson {
         os: None,
         cpu: None,
         overrides: None,
+        catalog: None,
+        catalogs: None,
         resolved_deps: Default::default(),
       });
     }
@@ -514,6 +640,17 @@ impl PackageJson {
     let os = package_json.remove("os").and_then(parse_string_array);
     let cpu = package_json.remove("cpu").and_then(parse_string_array);
     let overrides = package_json.remove("overrides").and_then(map_object);
+    let catalog = package_json.remove("catalog").and_then(parse_string_map);
+    let catalogs =
+      package_json
+        .remove("catalogs")
+        .and_then(map_object)
+        .map(|catalogs| {
+          catalogs
+            .into_iter()
+            .filter_map(|(name, value)| Some((name, parse_string_map(value)?)))
+            .collect()
+        });
 
     Ok(PackageJson {
       path,
@@ -540,6 +677,8 @@ impl PackageJson {
       os,
       cpu,
       overrides,
+      catalog,
+      catalogs,
       resolved_deps: Default::default(),
     })
   }
@@ -552,7 +691,32 @@ impl PackageJson {
     self.path.parent().unwrap()
   }
 
+  /// Gets the catalogs defined in the package.json.
+  pub fn resolve_catalogs(&self) -> PackageJsonCatalogs {
+    fn to_entries(
+      entries: &IndexMap<String, String>,
+    ) -> impl Iterator<Item = (StackString, String)> + '_ {
+      entries
+        .iter()
+        .map(|(name, value)| (StackString::from(name.as_str()), value.clone()))
+    }
+
+    let mut catalogs = PackageJsonCatalogs::default();
+    if let Some(catalog) = &self.catalog {
+      catalogs.extend(DEFAULT_CATALOG_NAME, to_entries(catalog));
+    }
+    if let Some(named_catalogs) = &self.catalogs {
+      for (name, catalog) in named_catalogs {
+        catalogs.extend(name, to_entries(catalog));
+      }
+    }
+    catalogs
+  }
+
   /// Resolve the package.json's dependencies.
+  ///
+  /// Note that `catalog:` dependencies are not resolved here because
+  /// the catalogs are defined in the workspace root.
   pub fn resolve_local_package_json_deps(&self) -> &PackageJsonDepsRc {
     fn get_map(deps: Option<&IndexMap<String, String>>) -> PackageJsonDepsMap {
       let Some(deps) = deps else {
@@ -940,6 +1104,91 @@ mod test {
     );
   }
 
+  #[test]
+  fn test_resolve_catalog_deps() {
+    let root_package_json = PackageJson::load_from_value(
+      PathBuf::from("/package.json"),
+      serde_json::json!({
+        "catalog": {
+          "react": "^18.2.0",
+          "zod": "npm:zod@^3"
+        },
+        "catalogs": {
+          "legacy": {
+            "react": "^17",
+            "other": "workspace:*"
+          }
+        }
+      }),
+    )
+    .unwrap();
+    let catalogs = root_package_json.resolve_catalogs();
+    let mut package_json =
+      PackageJson::load_from_string(PathBuf::from("/a/package.json"), "{}")
+        .unwrap();
+    package_json.dependencies = Some(IndexMap::from([
+      ("react".to_string(), "catalog:".to_string()),
+      ("zod".to_string(), "catalog:default".to_string()),
+      ("legacy-react".to_string(), "catalog:legacy".to_string()),
+      ("other".to_string(), "catalog:legacy".to_string()),
+    ]));
+    package_json.dev_dependencies = Some(IndexMap::from([(
+      "react".to_string(),
+      "catalog:legacy".to_string(),
+    )]));
+    let deps = package_json.resolve_local_package_json_deps();
+    assert!(deps.has_catalog_deps());
+    assert_eq!(
+      deps.dependencies.get("react").unwrap(),
+      &Ok(PackageJsonDepValue::Catalog(StackString::from("default")))
+    );
+
+    let deps = deps.with_catalogs(&catalogs);
+    assert!(!deps.has_catalog_deps());
+    let result = deps
+      .dependencies
+      .into_iter()
+      .chain(deps.dev_dependencies)
+      .map(|(k, v)| (k.to_string(), v.map_err(|err| err.into_kind())))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      result,
+      vec![
+        (
+          "react".to_string(),
+          Ok(PackageJsonDepValue::Req(
+            PackageReq::from_str("react@^18.2.0").unwrap()
+          ))
+        ),
+        (
+          "zod".to_string(),
+          Ok(PackageJsonDepValue::Req(
+            PackageReq::from_str("zod@^3").unwrap()
+          ))
+        ),
+        (
+          "legacy-react".to_string(),
+          Err(PackageJsonDepValueParseErrorKind::CatalogEntryNotFound {
+            catalog: "legacy".to_string(),
+            alias: "legacy-react".to_string(),
+          })
+        ),
+        (
+          "other".to_string(),
+          Err(PackageJsonDepValueParseErrorKind::Unsupported {
+            scheme: "workspace".to_string(),
+          })
+        ),
+        (
+          "react".to_string(),
+          Ok(PackageJsonDepValue::Req(
+            PackageReq::from_str("react@^17").unwrap()
+          ))
+        ),
+      ]
+    );
+  }
+
   #[test]
   fn test_deserialize_serialize() {
     let json_value = serde_json::json!({
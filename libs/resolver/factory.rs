@@ -203,6 +203,8 @@ pub struct WorkspaceFactoryOptions {
   pub config_discovery: ConfigDiscoveryOption,
   pub is_package_manager_subcommand: bool,
   pub frozen_lockfile: Option<bool>,
+  /// Whether to seed the lockfile from an npm, pnpm or Yarn lockfile.
+  pub import_external_lockfile: bool,
   pub lock_arg: Option<PathBuf>,
   /// Whether to skip writing to the lockfile.
   pub lockfile_skip_write: bool,
@@ -493,6 +495,7 @@ impl<TSys: WorkspaceFactorySys> WorkspaceFactory<TSys> {
           crate::lockfile::LockfileFlags {
             no_lock: self.options.no_lock,
             frozen_lockfile: self.options.frozen_lockfile,
+            import_external: self.options.import_external_lockfile,
             lock: self.options.lock_arg.as_ref().map(|path| {
               #[cfg(not(target_arch = "wasm32"))]
               debug_assert!(path.is_absolute());
@@ -11,6 +11,7 @@ use deno_cache_dir::npm::NpmCacheDir;
 use deno_error::JsError;
 use deno_package_json::PackageJsonDepValue;
 use deno_package_json::PackageJsonDepValueParseError;
+use deno_package_json::PackageJsonDepValueParseErrorKind;
 use deno_semver::npm::NpmPackageReqReference;
 pub use node_resolver::DenoIsBuiltInNodeModuleChecker;
 use node_resolver::InNpmPackageChecker;
@@ -455,6 +456,18 @@ impl<
                     })
                 })
                 .and_then(|r| Ok(r.into_url()?)),
+              // the workspace resolver resolves catalogs, so this
+              // means the catalog entry doesn't exist
+              PackageJsonDepValue::Catalog(catalog) => Err(
+                DenoResolveErrorKind::PackageJsonDepValueParse(
+                  PackageJsonDepValueParseErrorKind::CatalogEntryNotFound {
+                    catalog: catalog.to_string(),
+                    alias: alias.to_string(),
+                  }
+                  .into_box(),
+                )
+                .into_box(),
+              ),
             })
         }
         MappedResolution::PackageJsonImport { pkg_json } => self
@@ -1,6 +1,7 @@
 // Copyright 2018-2026 the Deno authors. MIT license.
 
 use std::collections::HashSet;
+use std::path::Path;
 use std::path::PathBuf;
 
 use anyhow::Context;
@@ -8,6 +9,8 @@ use anyhow::Error as AnyError;
 use capacity_builder::StringBuilder;
 use deno_config::workspace::Workspace;
 use deno_error::JsErrorBox;
+use deno_lockfile::ExternalLockfile;
+use deno_lockfile::ExternalLockfileKind;
 use deno_lockfile::Lockfile;
 use deno_lockfile::NpmPackageInfoProvider;
 use deno_lockfile::WorkspaceMemberConfig;
@@ -18,8 +21,11 @@ use deno_npm::resolution::DefaultTarballUrlProvider;
 use deno_npm::resolution::NpmRegistryDefaultTarballUrlProvider;
 use deno_package_json::PackageJsonDepValue;
 use deno_path_util::fs::atomic_write_file_with_retries;
+use deno_semver::StackString;
 use deno_semver::jsr::JsrDepPackageReq;
+use deno_semver::package::PackageKind;
 use deno_semver::package::PackageNv;
+use deno_semver::package::PackageReq;
 use futures::TryStreamExt;
 use futures::stream::FuturesOrdered;
 use indexmap::IndexMap;
@@ -75,6 +81,9 @@ impl LockfileNpmPackageInfoApiAdapter {
                 Some(d.tarball.clone())
               }
             }),
+            integrity: version_info.dist.as_ref().and_then(|d| {
+              d.integrity().for_lockfile().map(|i| i.into_owned())
+            }),
             optional_dependencies: version_info
               .optional_dependencies
               .iter()
@@ -174,6 +183,8 @@ impl<T> std::ops::DerefMut for Guard<'_, T> {
 pub struct LockfileFlags {
   pub no_lock: bool,
   pub frozen_lockfile: Option<bool>,
+  /// Seed the lockfile from an npm, pnpm or Yarn lockfile.
+  pub import_external: bool,
   pub lock: Option<PathBuf>,
   pub skip_write: bool,
   pub no_config: bool,
@@ -279,12 +290,13 @@ impl<TSys: LockfileSys> LockfileLock<TSys> {
     api: &dyn NpmPackageInfoProvider,
   ) -> Result<Option<Self>, AnyError> {
     fn pkg_json_deps(
+      workspace: &Workspace,
       maybe_pkg_json: Option<&PackageJson>,
     ) -> HashSet<JsrDepPackageReq> {
       let Some(pkg_json) = maybe_pkg_json else {
         return Default::default();
       };
-      let deps = pkg_json.resolve_local_package_json_deps();
+      let deps = workspace.resolve_pkg_json_deps(pkg_json);
 
       deps
         .dependencies
@@ -299,7 +311,8 @@ impl<TSys: LockfileSys> LockfileLock<TSys> {
           PackageJsonDepValue::Req(req) => {
             Some(JsrDepPackageReq::npm(req.clone()))
           }
-          PackageJsonDepValue::Workspace(_) => None,
+          PackageJsonDepValue::Workspace(_)
+          | PackageJsonDepValue::Catalog(_) => None,
         })
         .collect()
     }
@@ -335,7 +348,10 @@ impl<TSys: LockfileSys> LockfileLock<TSys> {
     let root_url = workspace.root_dir_url();
     let config = deno_lockfile::WorkspaceConfig {
       root: WorkspaceMemberConfig {
-        package_json_deps: pkg_json_deps(root_folder.pkg_json.as_deref()),
+        package_json_deps: pkg_json_deps(
+          workspace,
+          root_folder.pkg_json.as_deref(),
+        ),
         dependencies: if let Some(map) = maybe_external_import_map {
           deno_config::import_map::import_map_deps_from_value(map)
             .collect::<HashSet<_>>()
@@ -343,7 +359,7 @@ impl<TSys: LockfileSys> LockfileLock<TSys> {
           root_folder
             .deno_json
             .as_deref()
-            .map(|d| d.dependencies())
+            .map(|d| workspace.resolve_deno_json_deps(d))
             .unwrap_or_default()
         },
       },
@@ -365,11 +381,14 @@ impl<TSys: LockfileSys> LockfileLock<TSys> {
             },
             {
               let config = WorkspaceMemberConfig {
-                package_json_deps: pkg_json_deps(folder.pkg_json.as_deref()),
+                package_json_deps: pkg_json_deps(
+                  workspace,
+                  folder.pkg_json.as_deref(),
+                ),
                 dependencies: folder
                   .deno_json
                   .as_deref()
-                  .map(|d| d.dependencies())
+                  .map(|d| workspace.resolve_deno_json_deps(d))
                   .unwrap_or_default(),
               };
               if config.package_json_deps.is_empty()
@@ -399,7 +418,8 @@ impl<TSys: LockfileSys> LockfileLock<TSys> {
                     }
                     // not supported
                     PackageJsonDepValue::File(_)
-                    | PackageJsonDepValue::Workspace(_) => None,
+                    | PackageJsonDepValue::Workspace(_)
+                    | PackageJsonDepValue::Catalog(_) => None,
                   })
                   .collect()
               })
@@ -458,6 +478,29 @@ impl<TSys: LockfileSys> LockfileLock<TSys> {
         .npm_overrides()
         .map(|m| serde_json::Value::Object(m.clone())),
     };
+    // when migrating a project to Deno, keep the versions resolved by
+    // its previous package manager
+    if flags.import_external && !frozen && !flags.no_npm {
+      let npm_reqs = config
+        .root
+        .package_json_deps
+        .iter()
+        .chain(config.root.dependencies.iter())
+        .chain(config.members.values().flat_map(|member| {
+          member
+            .package_json_deps
+            .iter()
+            .chain(member.dependencies.iter())
+        }))
+        .filter(|dep| dep.kind == PackageKind::Npm)
+        .map(|dep| &dep.req)
+        .collect::<Vec<_>>();
+      if !npm_reqs.is_empty() {
+        lockfile
+          .import_external_lockfile(&workspace.root_dir_path(), npm_reqs, api)
+          .await;
+      }
+    }
     lockfile.set_workspace_config(deno_lockfile::SetWorkspaceConfigOptions {
       no_npm: flags.no_npm,
       no_config: flags.no_config,
@@ -466,6 +509,95 @@ impl<TSys: LockfileSys> LockfileLock<TSys> {
     Ok(Some(lockfile))
   }
 
+  /// Seeds the lockfile with the npm packages resolved by an npm, pnpm
+  /// or Yarn lockfile in the provided directory.
+  async fn import_external_lockfile<'a>(
+    &self,
+    dir_path: &Path,
+    npm_reqs: impl IntoIterator<Item = &'a PackageReq>,
+    api: &dyn NpmPackageInfoProvider,
+  ) {
+    for kind in ExternalLockfileKind::ALL {
+      let path = dir_path.join(kind.file_name());
+      let Ok(text) = self.sys.fs_read_to_string(&path) else {
+        continue;
+      };
+      let external = match ExternalLockfile::parse(kind, &text) {
+        Ok(external) => external,
+        Err(err) => {
+          log::warn!("Failed importing '{}': {:#}", path.display(), err);
+          return;
+        }
+      };
+      let ids = self
+        .lockfile
+        .lock()
+        .import_external_lockfile(&external, npm_reqs);
+      if ids.is_empty() {
+        return;
+      }
+      self.fill_missing_integrity(&ids, api).await;
+      log::info!(
+        "Imported {} npm package{} from '{}' into the lockfile.",
+        ids.len(),
+        if ids.len() == 1 { "" } else { "s" },
+        path.display()
+      );
+      return;
+    }
+    log::warn!(
+      "Did not find a package-lock.json, pnpm-lock.yaml or yarn.lock in '{}' to import.",
+      dir_path.display()
+    );
+  }
+
+  /// Fills in the integrity of imported packages that didn't have one
+  /// (ex. Yarn 2+ lockfiles only store a checksum of Yarn's own archive)
+  /// from the registry, so that the tarballs are still verified.
+  async fn fill_missing_integrity(
+    &self,
+    ids: &[StackString],
+    api: &dyn NpmPackageInfoProvider,
+  ) {
+    let ids = {
+      let lockfile = self.lockfile.lock();
+      ids
+        .iter()
+        .filter(|id| {
+          lockfile
+            .content
+            .packages
+            .npm
+            .get(*id)
+            .is_some_and(|info| info.integrity.is_none())
+        })
+        .filter_map(|id| {
+          let nv = deno_npm::NpmPackageId::from_serialized(id).ok()?.nv;
+          Some((id, nv))
+        })
+        .collect::<Vec<_>>()
+    };
+    if ids.is_empty() {
+      return;
+    }
+    let nvs = ids.iter().map(|(_, nv)| nv.clone()).collect::<Vec<_>>();
+    let infos = match api.get_npm_package_info(&nvs).await {
+      Ok(infos) => infos,
+      Err(err) => {
+        log::warn!(
+          "Failed getting the integrity of imported packages: {err:#}"
+        );
+        return;
+      }
+    };
+    let mut lockfile = self.lockfile.lock();
+    for ((id, _), info) in ids.into_iter().zip(infos) {
+      if let Some(package) = lockfile.content.packages.npm.get_mut(id) {
+        package.integrity = info.integrity;
+      }
+    }
+  }
+
   pub async fn read_from_path(
     sys: TSys,
     opts: LockfileReadFromPathOptions,
@@ -214,6 +214,13 @@ impl<TSys: ByonmNpmResolverSys> ByonmNpmResolver<TSys> {
                 return Ok(Some(key.clone()));
               }
             }
+            PackageJsonDepValue::Catalog(_catalog) => {
+              // the catalogs are resolved by the package manager that
+              // populated the node_modules directory
+              if key.as_str() == req.name {
+                return Ok(Some(key.clone()));
+              }
+            }
           }
         }
       }
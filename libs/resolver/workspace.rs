@@ -20,6 +20,7 @@ use deno_npm::registry::NpmPackageVersionInfo;
 use deno_package_json::PackageJsonDepValue;
 use deno_package_json::PackageJsonDepValueParseError;
 use deno_package_json::PackageJsonDepWorkspaceReq;
+use deno_package_json::PackageJsonDeps;
 use deno_package_json::PackageJsonDepsRc;
 use deno_package_json::PackageJsonRc;
 use deno_path_util::SpecifierError;
@@ -65,6 +66,36 @@ struct PkgJsonResolverFolderConfig {
   pkg_json: PackageJsonRc,
 }
 
+/// Replaces the `catalog:` dependency values of a serialized package.json
+/// with the resolved dependency values.
+fn inline_catalog_deps(
+  pkg_json: &mut serde_json::Value,
+  deps: &PackageJsonDeps,
+) {
+  for (key, deps) in [
+    ("dependencies", &deps.dependencies),
+    ("devDependencies", &deps.dev_dependencies),
+  ] {
+    let Some(serde_json::Value::Object(values)) = pkg_json.get_mut(key) else {
+      continue;
+    };
+    for (alias, value) in values.iter_mut() {
+      if !value.as_str().is_some_and(|v| v.starts_with("catalog:")) {
+        continue;
+      }
+      match deps.get(alias.as_str()) {
+        Some(Ok(PackageJsonDepValue::Req(req))) => {
+          *value = format!("npm:{}", req).into();
+        }
+        Some(Ok(PackageJsonDepValue::File(path))) => {
+          *value = format!("file:{}", path).into();
+        }
+        _ => {}
+      }
+    }
+  }
+}
+
 #[derive(Debug, Error, JsError)]
 pub enum WorkspaceResolverCreateError {
   #[class(inherit)]
@@ -885,7 +916,7 @@ impl<TSys: FsMetadata + FsRead> WorkspaceResolver<TSys> {
             return Ok(None);
           }
 
-          let config_specified_import_map = match root_deno_json.as_ref() {
+          let mut config_specified_import_map = match root_deno_json.as_ref() {
             Some(deno_json) => deno_json
               .to_import_map_value(sys)
               .map_err(|source| WorkspaceResolverCreateError::ImportMapFetch {
@@ -903,6 +934,8 @@ impl<TSys: FsMetadata + FsRead> WorkspaceResolver<TSys> {
               serde_json::Value::Object(Default::default()),
             ),
           };
+          workspace
+            .resolve_import_map_catalogs(&mut config_specified_import_map.1);
           let base_import_map_config = import_map::ext::ImportMapConfig {
             base_url: config_specified_import_map.0.into_owned(),
             import_map_value: config_specified_import_map.1,
@@ -921,7 +954,9 @@ impl<TSys: FsMetadata + FsRead> WorkspaceResolver<TSys> {
                 if let Some(imports) = &config.json.imports {
                   value.insert("imports".to_string(), imports.clone());
                 }
-                value.into()
+                let mut value = value.into();
+                workspace.resolve_import_map_catalogs(&mut value);
+                value
               },
             })
             .collect::<Vec<_>>();
@@ -950,11 +985,10 @@ impl<TSys: FsMetadata + FsRead> WorkspaceResolver<TSys> {
     let pkg_jsons = workspace
       .resolver_pkg_jsons()
       .map(|(dir_url, pkg_json)| {
-        let deps = pkg_json.resolve_local_package_json_deps();
         (
           dir_url.clone(),
           PkgJsonResolverFolderConfig {
-            deps: deps.clone(),
+            deps: workspace.resolve_pkg_json_deps(pkg_json),
             pkg_json: pkg_json.clone(),
           },
         )
@@ -1064,13 +1098,23 @@ impl<TSys: FsMetadata + FsRead> WorkspaceResolver<TSys> {
         })
         .collect(),
       package_jsons: self
-        .package_jsons()
-        .map(|pkg_json| {
+        .pkg_jsons
+        .values()
+        .map(|config| {
+          let mut value = serde_json::to_value(&config.pkg_json).unwrap();
+          // the catalogs are not serialized, so inline the resolved values
+          if config
+            .pkg_json
+            .resolve_local_package_json_deps()
+            .has_catalog_deps()
+          {
+            inline_catalog_deps(&mut value, &config.deps);
+          }
           (
             root_dir_url
-              .make_relative_if_descendant(&pkg_json.specifier())
+              .make_relative_if_descendant(&config.pkg_json.specifier())
               .into_owned(),
-            serde_json::to_value(pkg_json).unwrap(),
+            value,
           )
         })
         .collect(),
@@ -1157,6 +1201,12 @@ impl<TSys: FsMetadata + FsRead> WorkspaceResolver<TSys> {
     self.pkg_jsons.values().map(|c| &c.pkg_json)
   }
 
+  /// Gets the dependencies of each package.json with any `catalog:`
+  /// dependencies resolved.
+  pub fn package_json_deps(&self) -> impl Iterator<Item = &PackageJsonDepsRc> {
+    self.pkg_jsons.values().map(|c| &c.deps)
+  }
+
   pub fn jsr_packages(&self) -> &[ResolverWorkspaceJsrPackage] {
     &self.jsr_pkgs
   }
@@ -0,0 +1,770 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicI32;
+use std::task::Context;
+use std::task::Waker;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+
+use deno_core::InspectorMsg;
+use deno_core::InspectorSessionChannels;
+use deno_core::InspectorSessionKind;
+use deno_core::InspectorSessionProxy;
+use deno_core::JsRuntime;
+use deno_core::futures::StreamExt;
+use deno_core::futures::channel::mpsc;
+use deno_core::serde_json;
+use deno_core::serde_json::json;
+use deno_core::url::Url;
+use deno_telemetry::SpanId;
+use deno_telemetry::SpanMark;
+use deno_telemetry::SpanMarks;
+use deno_telemetry::TraceId;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper_util::client::legacy::Client;
+use hyper_util::rt::TokioExecutor;
+use tokio::sync::oneshot;
+use uuid::Uuid;
+
+/// V8's sampling interval, in microseconds.
+const SAMPLING_INTERVAL_US: i64 = 1000;
+
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+static NEXT_MSG_ID: AtomicI32 = AtomicI32::new(0);
+
+fn next_msg_id() -> i32 {
+  NEXT_MSG_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+}
+
+fn message(method: &str, params: serde_json::Value) -> String {
+  json!({ "id": next_msg_id(), "method": method, "params": params }).to_string()
+}
+
+/// Where finished profiles are written to.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CpuProfileTarget {
+  /// A directory that gets one `.pb.gz` file per profile.
+  Dir(PathBuf),
+  /// An `http:` URL that each profile is POSTed to.
+  Endpoint(Url),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CpuProfilerOptions {
+  pub target: CpuProfileTarget,
+  /// How long each profile covers before it is exported and a new one is
+  /// started.
+  pub period: Duration,
+}
+
+/// Keeps V8's sampling profiler running on a worker and periodically exports
+/// what it collected in pprof format.
+///
+/// The profiler is driven from a background thread through an inspector
+/// session, so that profiles keep being rotated while the worker is busy or
+/// idle. Samples are labelled with the trace and span id that was current
+/// when they were taken, as recorded by `ext/telemetry` in [`SpanMarks`].
+pub struct CpuProfiler {
+  to_inspector_tx: mpsc::UnboundedSender<String>,
+  shutdown_tx: Option<oneshot::Sender<()>>,
+  thread: Option<JoinHandle<()>>,
+}
+
+impl CpuProfiler {
+  pub fn new(js_runtime: &mut JsRuntime, options: CpuProfilerOptions) -> Self {
+    js_runtime.maybe_init_inspector();
+
+    let (to_inspector_tx, to_inspector_rx) = mpsc::unbounded();
+    let (from_inspector_tx, from_inspector_rx) = mpsc::unbounded();
+    let _ = js_runtime.inspector().get_session_sender().unbounded_send(
+      InspectorSessionProxy {
+        channels: InspectorSessionChannels::Regular {
+          tx: from_inspector_tx,
+          rx: to_inspector_rx,
+        },
+        kind: InspectorSessionKind::NonBlocking {
+          wait_for_disconnect: false,
+        },
+      },
+    );
+
+    let span_marks = SpanMarks::default();
+    js_runtime.op_state().borrow_mut().put(span_marks.clone());
+
+    let _ =
+      to_inspector_tx.unbounded_send(message("Profiler.enable", json!({})));
+    let _ = to_inspector_tx.unbounded_send(message(
+      "Profiler.setSamplingInterval",
+      json!({ "interval": SAMPLING_INTERVAL_US }),
+    ));
+    let _ =
+      to_inspector_tx.unbounded_send(message("Profiler.start", json!({})));
+
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    let exporter = ProfileExporter {
+      target: options.target,
+      span_marks,
+      pending_marks: VecDeque::new(),
+      current_mark: None,
+      epoch: Instant::now(),
+      clock_offset_us: None,
+    };
+    let tx = to_inspector_tx.clone();
+    let thread = std::thread::Builder::new()
+      .name("cpu-profiler".to_string())
+      .spawn(move || {
+        let rt = tokio::runtime::Builder::new_current_thread()
+          .enable_all()
+          .build()
+          .unwrap();
+        rt.block_on(exporter.run(
+          options.period,
+          tx,
+          from_inspector_rx,
+          shutdown_rx,
+        ));
+      })
+      .unwrap();
+
+    Self {
+      to_inspector_tx,
+      shutdown_tx: Some(shutdown_tx),
+      thread: Some(thread),
+    }
+  }
+
+  /// Stops profiling and waits for the last profile to be exported.
+  ///
+  /// Must be called after the worker's event loop has finished, as it polls
+  /// the inspector directly to get the final profile out of V8.
+  pub fn stop(&mut self, js_runtime: &mut JsRuntime) {
+    let Some(thread) = self.thread.take() else {
+      return;
+    };
+    let _ = self
+      .to_inspector_tx
+      .unbounded_send(message("Profiler.stop", json!({})));
+    js_runtime
+      .inspector()
+      .poll_sessions_from_event_loop(&mut Context::from_waker(Waker::noop()));
+    if let Some(shutdown_tx) = self.shutdown_tx.take() {
+      let _ = shutdown_tx.send(());
+    }
+    let _ = thread.join();
+  }
+}
+
+struct ProfileExporter {
+  target: CpuProfileTarget,
+  span_marks: SpanMarks,
+  /// Marks that were recorded after the end of the last exported profile.
+  pending_marks: VecDeque<SpanMark>,
+  current_mark: Option<SpanMark>,
+  epoch: Instant,
+  /// The difference between V8's profiler clock and [`Self::epoch`], in
+  /// microseconds.
+  clock_offset_us: Option<i64>,
+}
+
+impl ProfileExporter {
+  async fn run(
+    mut self,
+    period: Duration,
+    to_inspector_tx: mpsc::UnboundedSender<String>,
+    mut from_inspector_rx: mpsc::UnboundedReceiver<InspectorMsg>,
+    mut shutdown_rx: oneshot::Receiver<()>,
+  ) {
+    let mut interval = tokio::time::interval(period);
+    interval.tick().await;
+    loop {
+      tokio::select! {
+        _ = interval.tick() => {
+          let _ = to_inspector_tx
+            .unbounded_send(message("Profiler.stop", json!({})));
+          let _ = to_inspector_tx
+            .unbounded_send(message("Profiler.start", json!({})));
+        }
+        msg = from_inspector_rx.next() => {
+          let Some(msg) = msg else {
+            return;
+          };
+          self.handle_message(msg).await;
+        }
+        _ = &mut shutdown_rx => {
+          while let Ok(Some(msg)) = from_inspector_rx.try_next() {
+            self.handle_message(msg).await;
+          }
+          return;
+        }
+      }
+    }
+  }
+
+  async fn handle_message(&mut self, msg: InspectorMsg) {
+    let received_us = self.epoch.elapsed().as_micros() as i64;
+    let Ok(mut message) =
+      serde_json::from_str::<serde_json::Value>(&msg.content)
+    else {
+      return;
+    };
+    let Some(profile) = message
+      .get_mut("result")
+      .and_then(|result| result.get_mut("profile"))
+      .map(serde_json::Value::take)
+    else {
+      return;
+    };
+    let profile: cdp::Profile = match serde_json::from_value(profile) {
+      Ok(profile) => profile,
+      Err(err) => {
+        log::error!("Failed to parse CPU profile, reason: {:?}", err);
+        return;
+      }
+    };
+
+    // V8 timestamps samples with its own monotonic clock. The profile is
+    // sent right after its end time is taken, so the smallest delay seen so
+    // far between the two gives the best estimate of the clock offset.
+    let offset = received_us - profile.end_time;
+    let offset = *self
+      .clock_offset_us
+      .insert(self.clock_offset_us.map_or(offset, |o| o.min(offset)));
+
+    self.pending_marks.extend(self.span_marks.take());
+    let encoded = encode_pprof(&profile, SystemTime::now(), |time| {
+      let time = time + offset;
+      while let Some(mark) = self.pending_marks.front() {
+        let mark_us =
+          mark.time.saturating_duration_since(self.epoch).as_micros() as i64;
+        if mark_us > time {
+          break;
+        }
+        self.current_mark = self.pending_marks.pop_front();
+      }
+      self
+        .current_mark
+        .filter(|mark| mark.trace_id != TraceId::INVALID)
+        .map(|mark| (mark.trace_id, mark.span_id))
+    });
+    let compressed = {
+      let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+      let _ = encoder.write_all(&encoded);
+      match encoder.finish() {
+        Ok(compressed) => compressed,
+        Err(err) => {
+          log::error!("Failed to compress CPU profile, reason: {:?}", err);
+          return;
+        }
+      }
+    };
+    self.export(compressed).await;
+  }
+
+  #[allow(clippy::disallowed_methods)]
+  async fn export(&self, profile: Vec<u8>) {
+    match &self.target {
+      CpuProfileTarget::Dir(dir) => {
+        if let Err(err) = std::fs::create_dir_all(dir) {
+          log::error!(
+            "Failed to create CPU profile directory at {:?}, reason: {:?}",
+            dir,
+            err
+          );
+          return;
+        }
+        let filepath = dir.join(format!("{}.pb.gz", Uuid::new_v4()));
+        if let Err(err) = std::fs::write(&filepath, profile) {
+          log::error!(
+            "Failed to write CPU profile at {:?}, reason: {:?}",
+            filepath,
+            err
+          );
+        }
+      }
+      CpuProfileTarget::Endpoint(url) => {
+        let client = Client::builder(TokioExecutor::new()).build_http();
+        let req = match http::Request::post(url.as_str())
+          .header(http::header::CONTENT_TYPE, "application/octet-stream")
+          .body(Full::new(Bytes::from(profile)))
+        {
+          Ok(req) => req,
+          Err(err) => {
+            log::error!("Failed to upload CPU profile, reason: {:?}", err);
+            return;
+          }
+        };
+        match tokio::time::timeout(UPLOAD_TIMEOUT, client.request(req)).await {
+          Ok(Ok(res)) if res.status().is_success() => {}
+          Ok(Ok(res)) => {
+            log::error!(
+              "Failed to upload CPU profile to {}, status: {}",
+              url,
+              res.status()
+            );
+          }
+          Ok(Err(err)) => {
+            log::error!(
+              "Failed to upload CPU profile to {}, reason: {:?}",
+              url,
+              err
+            );
+          }
+          Err(_) => {
+            log::error!("Failed to upload CPU profile to {}, timed out", url);
+          }
+        }
+      }
+    }
+  }
+}
+
+/// The trace and span id that a sample is labelled with.
+type SpanLabels = Option<(TraceId, SpanId)>;
+
+/// Converts a V8 CPU profile into an uncompressed pprof protobuf message.
+/// `labels_at` is called with the timestamp of every sample, in order, and
+/// returns the trace and span id to label it with.
+fn encode_pprof(
+  profile: &cdp::Profile,
+  now: SystemTime,
+  mut labels_at: impl FnMut(i64) -> SpanLabels,
+) -> Vec<u8> {
+  let mut strings = StringTable::default();
+  let samples_str = strings.get("samples");
+  let count_str = strings.get("count");
+  let cpu_str = strings.get("cpu");
+  let nanoseconds_str = strings.get("nanoseconds");
+  let trace_id_str = strings.get("trace_id");
+  let span_id_str = strings.get("span_id");
+
+  let mut parents = HashMap::new();
+  for node in &profile.nodes {
+    for child in &node.children {
+      parents.insert(*child, node.id);
+    }
+  }
+  let nodes = profile
+    .nodes
+    .iter()
+    .map(|node| (node.id, node))
+    .collect::<HashMap<_, _>>();
+
+  let mut functions = HashMap::new();
+  let mut function_msgs = Vec::new();
+  let mut location_msgs = Vec::new();
+  for node in &profile.nodes {
+    let frame = &node.call_frame;
+    let name = if frame.function_name.is_empty() {
+      "(anonymous)"
+    } else {
+      &frame.function_name
+    };
+    let key = (name, &frame.url, frame.line_number, frame.column_number);
+    let next_id = functions.len() as u64 + 1;
+    let function_id = *functions.entry(key).or_insert_with(|| {
+      let mut function = ProtoWriter::default();
+      function.uint64(1, next_id);
+      function.uint64(2, strings.get(name) as u64);
+      function.uint64(4, strings.get(&frame.url) as u64);
+      function.uint64(5, (frame.line_number + 1) as u64);
+      function_msgs.push(function.buf);
+      next_id
+    });
+    let mut line = ProtoWriter::default();
+    line.uint64(1, function_id);
+    line.uint64(2, (frame.line_number + 1) as u64);
+    line.uint64(3, (frame.column_number + 1) as u64);
+    let mut location = ProtoWriter::default();
+    location.uint64(1, node.id);
+    location.message(4, &line.buf);
+    location_msgs.push(location.buf);
+  }
+
+  // Samples with the same stack and labels are merged into one.
+  let mut samples: Vec<(Vec<u64>, SpanLabels, i64)> = vec![];
+  let mut sample_index = HashMap::new();
+  let mut time = profile.start_time;
+  for (i, node_id) in profile.samples.iter().enumerate() {
+    time += profile.time_deltas.get(i).copied().unwrap_or(0);
+    let labels = labels_at(time);
+    let Some(node) = nodes.get(node_id) else {
+      continue;
+    };
+    if node.call_frame.function_name == "(idle)" {
+      continue;
+    }
+    let mut stack = vec![];
+    let mut id = *node_id;
+    loop {
+      let Some(parent) = parents.get(&id) else {
+        // The root node has no parent and is not part of any stack.
+        break;
+      };
+      stack.push(id);
+      id = *parent;
+    }
+    let index =
+      *sample_index
+        .entry((stack.clone(), labels))
+        .or_insert_with(|| {
+          samples.push((stack, labels, 0));
+          samples.len() - 1
+        });
+    samples[index].2 += 1;
+  }
+
+  let mut out = ProtoWriter::default();
+  for (type_str, unit_str) in
+    [(samples_str, count_str), (cpu_str, nanoseconds_str)]
+  {
+    let mut value_type = ProtoWriter::default();
+    value_type.uint64(1, type_str as u64);
+    value_type.uint64(2, unit_str as u64);
+    out.message(1, &value_type.buf);
+  }
+  for (stack, labels, count) in &samples {
+    let mut sample = ProtoWriter::default();
+    sample.packed_uint64(1, stack.iter().copied());
+    sample.packed_uint64(
+      2,
+      [*count as u64, (*count * SAMPLING_INTERVAL_US * 1000) as u64],
+    );
+    if let Some((trace_id, span_id)) = labels {
+      for (key, value) in [
+        (trace_id_str, strings.get(&trace_id.to_string())),
+        (span_id_str, strings.get(&span_id.to_string())),
+      ] {
+        let mut label = ProtoWriter::default();
+        label.uint64(1, key as u64);
+        label.uint64(2, value as u64);
+        sample.message(3, &label.buf);
+      }
+    }
+    out.message(2, &sample.buf);
+  }
+  for location in &location_msgs {
+    out.message(4, location);
+  }
+  for function in &function_msgs {
+    out.message(5, function);
+  }
+  for string in &strings.strings {
+    out.message(6, string.as_bytes());
+  }
+  let duration_us = profile.end_time - profile.start_time;
+  let time_nanos = now
+    .duration_since(SystemTime::UNIX_EPOCH)
+    .unwrap_or_default()
+    .as_nanos() as i64
+    - duration_us * 1000;
+  out.uint64(9, time_nanos as u64);
+  out.uint64(10, (duration_us * 1000) as u64);
+  let mut period_type = ProtoWriter::default();
+  period_type.uint64(1, cpu_str as u64);
+  period_type.uint64(2, nanoseconds_str as u64);
+  out.message(11, &period_type.buf);
+  out.uint64(12, (SAMPLING_INTERVAL_US * 1000) as u64);
+  out.buf
+}
+
+/// The string table of a pprof profile. Index 0 is always the empty string.
+struct StringTable {
+  strings: Vec<String>,
+  indices: HashMap<String, usize>,
+}
+
+impl Default for StringTable {
+  fn default() -> Self {
+    Self {
+      strings: vec![String::new()],
+      indices: HashMap::from([(String::new(), 0)]),
+    }
+  }
+}
+
+impl StringTable {
+  fn get(&mut self, s: &str) -> usize {
+    if let Some(index) = self.indices.get(s) {
+      return *index;
+    }
+    let index = self.strings.len();
+    self.strings.push(s.to_string());
+    self.indices.insert(s.to_string(), index);
+    index
+  }
+}
+
+/// A minimal protobuf encoder, enough for the pprof message types. Zero
+/// values are omitted, as proto3 does.
+#[derive(Default)]
+struct ProtoWriter {
+  buf: Vec<u8>,
+}
+
+impl ProtoWriter {
+  fn varint(&mut self, mut value: u64) {
+    while value >= 0x80 {
+      self.buf.push((value as u8) | 0x80);
+      value >>= 7;
+    }
+    self.buf.push(value as u8);
+  }
+
+  fn uint64(&mut self, field: u32, value: u64) {
+    if value != 0 {
+      self.varint((field as u64) << 3);
+      self.varint(value);
+    }
+  }
+
+  fn message(&mut self, field: u32, bytes: &[u8]) {
+    self.varint((field as u64) << 3 | 2);
+    self.varint(bytes.len() as u64);
+    self.buf.extend_from_slice(bytes);
+  }
+
+  fn packed_uint64(
+    &mut self,
+    field: u32,
+    values: impl IntoIterator<Item = u64>,
+  ) {
+    let mut packed = ProtoWriter::default();
+    for value in values {
+      packed.varint(value);
+    }
+    self.message(field, &packed.buf);
+  }
+}
+
+mod cdp {
+  use serde::Deserialize;
+
+  /// <https://chromedevtools.github.io/devtools-protocol/tot/Profiler/#type-Profile>
+  #[derive(Debug, Deserialize)]
+  #[serde(rename_all = "camelCase")]
+  pub struct Profile {
+    pub nodes: Vec<ProfileNode>,
+    pub start_time: i64,
+    pub end_time: i64,
+    #[serde(default)]
+    pub samples: Vec<u64>,
+    #[serde(default)]
+    pub time_deltas: Vec<i64>,
+  }
+
+  /// <https://chromedevtools.github.io/devtools-protocol/tot/Profiler/#type-ProfileNode>
+  #[derive(Debug, Deserialize)]
+  #[serde(rename_all = "camelCase")]
+  pub struct ProfileNode {
+    pub id: u64,
+    pub call_frame: CallFrame,
+    #[serde(default)]
+    pub children: Vec<u64>,
+  }
+
+  /// <https://chromedevtools.github.io/devtools-protocol/tot/Runtime/#type-CallFrame>
+  #[derive(Debug, Deserialize)]
+  #[serde(rename_all = "camelCase")]
+  pub struct CallFrame {
+    pub function_name: String,
+    pub url: String,
+    pub line_number: i64,
+    pub column_number: i64,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  enum Field<'a> {
+    Varint(u64),
+    Bytes(&'a [u8]),
+  }
+
+  fn read_varint(buf: &mut &[u8]) -> u64 {
+    let mut value = 0;
+    let mut shift = 0;
+    loop {
+      let byte = buf[0];
+      *buf = &buf[1..];
+      value |= ((byte & 0x7f) as u64) << shift;
+      if byte < 0x80 {
+        return value;
+      }
+      shift += 7;
+    }
+  }
+
+  fn decode(mut buf: &[u8]) -> Vec<(u32, Field<'_>)> {
+    let mut fields = vec![];
+    while !buf.is_empty() {
+      let key = read_varint(&mut buf);
+      let field = (key >> 3) as u32;
+      match key & 7 {
+        0 => fields.push((field, Field::Varint(read_varint(&mut buf)))),
+        2 => {
+          let len = read_varint(&mut buf) as usize;
+          fields.push((field, Field::Bytes(&buf[..len])));
+          buf = &buf[len..];
+        }
+        wire_type => panic!("unexpected wire type {wire_type}"),
+      }
+    }
+    fields
+  }
+
+  fn varints(fields: &[(u32, Field<'_>)], field: u32) -> Vec<u64> {
+    fields
+      .iter()
+      .filter_map(|(f, value)| match value {
+        Field::Varint(value) if *f == field => Some(*value),
+        _ => None,
+      })
+      .collect()
+  }
+
+  fn messages<'a>(fields: &[(u32, Field<'a>)], field: u32) -> Vec<&'a [u8]> {
+    fields
+      .iter()
+      .filter_map(|(f, value)| match value {
+        Field::Bytes(bytes) if *f == field => Some(*bytes),
+        _ => None,
+      })
+      .collect()
+  }
+
+  fn packed(mut buf: &[u8]) -> Vec<u64> {
+    let mut values = vec![];
+    while !buf.is_empty() {
+      values.push(read_varint(&mut buf));
+    }
+    values
+  }
+
+  #[test]
+  fn encode_pprof_labels_samples() {
+    let profile: cdp::Profile = serde_json::from_value(json!({
+      "nodes": [
+        {
+          "id": 1,
+          "callFrame": {
+            "functionName": "(root)",
+            "url": "",
+            "lineNumber": -1,
+            "columnNumber": -1,
+          },
+          "children": [2, 3],
+        },
+        {
+          "id": 2,
+          "callFrame": {
+            "functionName": "fib",
+            "url": "file:///main.ts",
+            "lineNumber": 0,
+            "columnNumber": 9,
+          },
+        },
+        {
+          "id": 3,
+          "callFrame": {
+            "functionName": "(idle)",
+            "url": "",
+            "lineNumber": -1,
+            "columnNumber": -1,
+          },
+        },
+      ],
+      "startTime": 0,
+      "endTime": 4000,
+      "samples": [2, 2, 3, 2],
+      "timeDeltas": [1000, 1000, 1000, 1000],
+    }))
+    .unwrap();
+    let trace_id =
+      TraceId::from_hex("0af7651916cd43dd8448eb211c80319c").unwrap();
+    let span_id = SpanId::from_hex("b7ad6b7169203331").unwrap();
+    let mut times = vec![];
+    let encoded = encode_pprof(&profile, SystemTime::UNIX_EPOCH, |time| {
+      times.push(time);
+      (time <= 2000).then_some((trace_id, span_id))
+    });
+    assert_eq!(times, [1000, 2000, 3000, 4000]);
+
+    let fields = decode(&encoded);
+    let strings = messages(&fields, 6)
+      .into_iter()
+      .map(|s| std::str::from_utf8(s).unwrap())
+      .collect::<Vec<_>>();
+    assert_eq!(strings[0], "");
+
+    let functions = messages(&fields, 5)
+      .into_iter()
+      .map(|function| {
+        let function = decode(function);
+        (
+          varints(&function, 1)[0],
+          strings[varints(&function, 2)[0] as usize],
+        )
+      })
+      .collect::<HashMap<_, _>>();
+    let locations = messages(&fields, 4)
+      .into_iter()
+      .map(|location| {
+        let location = decode(location);
+        let line = decode(messages(&location, 4)[0]);
+        (varints(&location, 1)[0], functions[&varints(&line, 1)[0]])
+      })
+      .collect::<HashMap<_, _>>();
+
+    let samples = messages(&fields, 2)
+      .into_iter()
+      .map(|sample| {
+        let sample = decode(sample);
+        let stack = packed(messages(&sample, 1)[0])
+          .into_iter()
+          .map(|id| locations[&id])
+          .collect::<Vec<_>>();
+        let values = packed(messages(&sample, 2)[0]);
+        let labels = messages(&sample, 3)
+          .into_iter()
+          .map(|label| {
+            let label = decode(label);
+            (
+              strings[varints(&label, 1)[0] as usize],
+              strings[varints(&label, 2)[0] as usize],
+            )
+          })
+          .collect::<Vec<_>>();
+        (stack, values, labels)
+      })
+      .collect::<Vec<_>>();
+    // the idle sample is dropped and the others are merged by their labels
+    assert_eq!(
+      samples,
+      [
+        (
+          vec!["fib"],
+          vec![2, 2_000_000],
+          vec![
+            ("trace_id", "0af7651916cd43dd8448eb211c80319c"),
+            ("span_id", "b7ad6b7169203331"),
+          ],
+        ),
+        (vec!["fib"], vec![1, 1_000_000], vec![]),
+      ]
+    );
+    // duration_nanos
+    assert_eq!(varints(&fields, 10), [4_000_000]);
+  }
+}
@@ -35,8 +35,7 @@ use crate::structs::UnstableFeatureKind;
 pub static UNSTABLE_FEATURES: &[UnstableFeatureDefinition] = &[\n",
   );
 
-  let mut descriptions = data::FEATURE_DESCRIPTIONS.to_vec();
-  descriptions.sort_by_key(|desc| desc.name);
+  let descriptions = data::FEATURE_DESCRIPTIONS;
 
   for (id, feature) in descriptions.iter().enumerate() {
     let flag_name = format!("unstable-{}", feature.name);
@@ -76,7 +75,7 @@ pub static UNSTABLE_FEATURES: &[UnstableFeatureDefinition] = &[\n",
   let mut env_var_impl =
     "pub static UNSTABLE_ENV_VAR_NAMES: UnstableEnvVarNames = UnstableEnvVarNames {\n"
       .to_string();
-  for feature in &descriptions {
+  for feature in descriptions {
     let value = match feature.env_var {
       Some(v) => v,
       None => continue,
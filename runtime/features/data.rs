@@ -16,6 +16,8 @@ pub struct UnstableFeatureDescription {
   pub env_var: Option<&'static str>,
 }
 
+// A feature's id is its index in this list, so new features must be appended
+// to keep the ids of the existing ones.
 pub static FEATURE_DESCRIPTIONS: &[UnstableFeatureDescription] = &[
   UnstableFeatureDescription {
     name: "bare-node-builtins",
@@ -26,8 +28,15 @@ pub static FEATURE_DESCRIPTIONS: &[UnstableFeatureDescription] = &[
   },
   UnstableFeatureDescription {
     name: "broadcast-channel",
-    help_text: "Enable unstable `BroadcastChannel` API",
-    show_in_help: false,
+    help_text: "Share `BroadcastChannel` messages with other processes of the same app",
+    show_in_help: true,
+    kind: UnstableFeatureKind::Runtime,
+    env_var: None,
+  },
+  UnstableFeatureDescription {
+    name: "bundle",
+    help_text: "Enable unstable bundle runtime API",
+    show_in_help: true,
     kind: UnstableFeatureKind::Runtime,
     env_var: None,
   },
@@ -164,6 +173,13 @@ pub static FEATURE_DESCRIPTIONS: &[UnstableFeatureDescription] = &[
     kind: UnstableFeatureKind::Runtime,
     env_var: None,
   },
+  UnstableFeatureDescription {
+    name: "tsgo",
+    help_text: "Enable unstable TypeScript Go integration",
+    show_in_help: true,
+    kind: UnstableFeatureKind::Cli,
+    env_var: Some("DENO_UNSTABLE_TSGO"),
+  },
   UnstableFeatureDescription {
     name: "unsafe-proto",
     help_text: "Enable unsafe __proto__ support. This is a security risk.",
@@ -193,17 +209,10 @@ pub static FEATURE_DESCRIPTIONS: &[UnstableFeatureDescription] = &[
     env_var: None,
   },
   UnstableFeatureDescription {
-    name: "bundle",
-    help_text: "Enable unstable bundle runtime API",
+    name: "async-context",
+    help_text: "Enable unstable `AsyncContext` API",
     show_in_help: true,
     kind: UnstableFeatureKind::Runtime,
     env_var: None,
   },
-  UnstableFeatureDescription {
-    name: "tsgo",
-    help_text: "Enable unstable TypeScript Go integration",
-    show_in_help: true,
-    kind: UnstableFeatureKind::Cli,
-    env_var: Some("DENO_UNSTABLE_TSGO"),
-  },
 ];
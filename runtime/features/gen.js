@@ -25,4 +25,5 @@ export const unstableIds = {
   vsock: 24,
   webgpu: 25,
   workerOptions: 26,
+  asyncContext: 27,
 };
@@ -18,8 +18,8 @@ pub static UNSTABLE_FEATURES: &[UnstableFeatureDefinition] = &[
   UnstableFeatureDefinition {
     name: "broadcast-channel",
     flag_name: "unstable-broadcast-channel",
-    help_text: "Enable unstable `BroadcastChannel` API",
-    show_in_help: false,
+    help_text: "Share `BroadcastChannel` messages with other processes of the same app",
+    show_in_help: true,
     id: 1,
     kind: UnstableFeatureKind::Runtime,
   },
@@ -223,6 +223,14 @@ pub static UNSTABLE_FEATURES: &[UnstableFeatureDefinition] = &[
     id: 26,
     kind: UnstableFeatureKind::Runtime,
   },
+  UnstableFeatureDefinition {
+    name: "async-context",
+    flag_name: "unstable-async-context",
+    help_text: "Enable unstable `AsyncContext` API",
+    show_in_help: true,
+    id: 27,
+    kind: UnstableFeatureKind::Runtime,
+  },
 ];
 pub struct UnstableEnvVarNames {
   pub bare_node_builtins: &'static str,
@@ -0,0 +1,294 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicI32;
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::Ordering;
+use std::task::Context;
+use std::task::Waker;
+use std::thread::JoinHandle;
+use std::time::SystemTime;
+
+use deno_core::InspectorMsg;
+use deno_core::InspectorSessionChannels;
+use deno_core::InspectorSessionKind;
+use deno_core::InspectorSessionProxy;
+use deno_core::JsRuntime;
+use deno_core::futures::StreamExt;
+use deno_core::futures::channel::mpsc;
+use deno_core::serde_json;
+use deno_core::serde_json::json;
+use tokio::sync::oneshot;
+
+static NEXT_MSG_ID: AtomicI32 = AtomicI32::new(0);
+
+/// Shared by all workers so that files written at the same time by
+/// different workers don't collide.
+static NEXT_FILE_SEQ: AtomicU32 = AtomicU32::new(1);
+
+fn message(id: i32, method: &str, params: serde_json::Value) -> String {
+  json!({ "id": id, "method": method, "params": params }).to_string()
+}
+
+fn next_msg_id() -> i32 {
+  NEXT_MSG_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Returns a path like `Heap.1760000000000.1234.001.heapsnapshot`, made of
+/// the current time in milliseconds, the process id and a sequence number.
+fn heap_file_path(dir: &Path, extension: &str) -> PathBuf {
+  let now = SystemTime::now()
+    .duration_since(SystemTime::UNIX_EPOCH)
+    .unwrap_or_default()
+    .as_millis();
+  let seq = NEXT_FILE_SEQ.fetch_add(1, Ordering::Relaxed);
+  dir.join(format!(
+    "Heap.{}.{}.{:03}.{}",
+    now,
+    std::process::id(),
+    seq,
+    extension
+  ))
+}
+
+#[allow(clippy::disallowed_methods)]
+fn write_heap_file(dir: &Path, extension: &str, contents: &[u8]) {
+  if let Err(err) = std::fs::create_dir_all(dir) {
+    log::error!(
+      "Failed to create heap profile directory at {:?}, reason: {:?}",
+      dir,
+      err
+    );
+    return;
+  }
+  let filepath = heap_file_path(dir, extension);
+  match std::fs::write(&filepath, contents) {
+    Ok(()) => log::info!("Wrote {}", filepath.display()),
+    Err(err) => log::error!(
+      "Failed to write heap profile at {:?}, reason: {:?}",
+      filepath,
+      err
+    ),
+  }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HeapProfilerOptions {
+  /// Directory that `.heapsnapshot` and `.heapprofile` files are written to.
+  pub dir: PathBuf,
+  /// Signal that makes the worker write a heap snapshot when received.
+  pub snapshot_signal: Option<i32>,
+  /// Whether to run V8's sampling heap profiler for the lifetime of the
+  /// worker and write its profile when the worker exits.
+  pub sampling: bool,
+}
+
+/// Writes heap snapshots on a signal and, optionally, an allocation sampling
+/// profile when the worker exits.
+///
+/// Like the CPU profiler, this is driven from a background thread through an
+/// inspector session. Snapshots are taken the next time the worker's event
+/// loop polls the inspector, so a worker that is stuck in a synchronous loop
+/// will not respond to the signal until it yields.
+pub struct HeapProfiler {
+  to_inspector_tx: mpsc::UnboundedSender<String>,
+  sampling: bool,
+  shutdown_tx: Option<oneshot::Sender<()>>,
+  thread: Option<JoinHandle<()>>,
+}
+
+impl HeapProfiler {
+  pub fn new(js_runtime: &mut JsRuntime, options: HeapProfilerOptions) -> Self {
+    js_runtime.maybe_init_inspector();
+
+    let (to_inspector_tx, to_inspector_rx) = mpsc::unbounded();
+    let (from_inspector_tx, from_inspector_rx) = mpsc::unbounded();
+    let _ = js_runtime.inspector().get_session_sender().unbounded_send(
+      InspectorSessionProxy {
+        channels: InspectorSessionChannels::Regular {
+          tx: from_inspector_tx,
+          rx: to_inspector_rx,
+        },
+        kind: InspectorSessionKind::NonBlocking {
+          wait_for_disconnect: false,
+        },
+      },
+    );
+
+    let _ = to_inspector_tx.unbounded_send(message(
+      next_msg_id(),
+      "HeapProfiler.enable",
+      json!({}),
+    ));
+    if options.sampling {
+      let _ = to_inspector_tx.unbounded_send(message(
+        next_msg_id(),
+        "HeapProfiler.startSampling",
+        json!({}),
+      ));
+    }
+
+    let (signal_tx, signal_rx) = tokio::sync::mpsc::unbounded_channel();
+    let signal_id = options.snapshot_signal.and_then(|signo| {
+      match deno_signals::register(
+        signo,
+        true,
+        Box::new(move || {
+          let _ = signal_tx.send(());
+        }),
+      ) {
+        Ok(id) => Some((signo, id)),
+        Err(err) => {
+          log::error!(
+            "Failed to listen for heap snapshot signal, reason: {:?}",
+            err
+          );
+          None
+        }
+      }
+    });
+
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    let writer = HeapFileWriter {
+      dir: options.dir,
+      snapshot: None,
+    };
+    let tx = to_inspector_tx.clone();
+    let thread = std::thread::Builder::new()
+      .name("heap-profiler".to_string())
+      .spawn(move || {
+        let rt = tokio::runtime::Builder::new_current_thread()
+          .enable_all()
+          .build()
+          .unwrap();
+        rt.block_on(writer.run(tx, from_inspector_rx, signal_rx, shutdown_rx));
+        if let Some((signo, id)) = signal_id {
+          deno_signals::unregister(signo, id);
+        }
+      })
+      .unwrap();
+
+    Self {
+      to_inspector_tx,
+      sampling: options.sampling,
+      shutdown_tx: Some(shutdown_tx),
+      thread: Some(thread),
+    }
+  }
+
+  /// Stops listening for the snapshot signal and writes the sampling
+  /// profile, if enabled.
+  ///
+  /// Must be called after the worker's event loop has finished, as it polls
+  /// the inspector directly to get the profile out of V8.
+  pub fn stop(&mut self, js_runtime: &mut JsRuntime) {
+    let Some(thread) = self.thread.take() else {
+      return;
+    };
+    if self.sampling {
+      let _ = self.to_inspector_tx.unbounded_send(message(
+        next_msg_id(),
+        "HeapProfiler.stopSampling",
+        json!({}),
+      ));
+    }
+    js_runtime
+      .inspector()
+      .poll_sessions_from_event_loop(&mut Context::from_waker(Waker::noop()));
+    if let Some(shutdown_tx) = self.shutdown_tx.take() {
+      let _ = shutdown_tx.send(());
+    }
+    let _ = thread.join();
+  }
+}
+
+/// A heap snapshot that V8 is in the middle of streaming.
+struct PendingSnapshot {
+  msg_id: i32,
+  chunks: String,
+}
+
+struct HeapFileWriter {
+  dir: PathBuf,
+  snapshot: Option<PendingSnapshot>,
+}
+
+impl HeapFileWriter {
+  async fn run(
+    mut self,
+    to_inspector_tx: mpsc::UnboundedSender<String>,
+    mut from_inspector_rx: mpsc::UnboundedReceiver<InspectorMsg>,
+    mut signal_rx: tokio::sync::mpsc::UnboundedReceiver<()>,
+    mut shutdown_rx: oneshot::Receiver<()>,
+  ) {
+    loop {
+      tokio::select! {
+        Some(()) = signal_rx.recv() => {
+          // Signals that arrive while a snapshot is being taken are folded
+          // into it.
+          if self.snapshot.is_some() {
+            continue;
+          }
+          let msg_id = next_msg_id();
+          self.snapshot = Some(PendingSnapshot {
+            msg_id,
+            chunks: String::new(),
+          });
+          let _ = to_inspector_tx.unbounded_send(message(
+            msg_id,
+            "HeapProfiler.takeHeapSnapshot",
+            json!({ "reportProgress": false }),
+          ));
+        }
+        msg = from_inspector_rx.next() => {
+          let Some(msg) = msg else {
+            return;
+          };
+          self.handle_message(msg);
+        }
+        _ = &mut shutdown_rx => {
+          while let Ok(Some(msg)) = from_inspector_rx.try_next() {
+            self.handle_message(msg);
+          }
+          return;
+        }
+      }
+    }
+  }
+
+  fn handle_message(&mut self, msg: InspectorMsg) {
+    let Ok(message) = serde_json::from_str::<serde_json::Value>(&msg.content)
+    else {
+      return;
+    };
+
+    if message["method"] == "HeapProfiler.addHeapSnapshotChunk" {
+      if let (Some(snapshot), Some(chunk)) =
+        (self.snapshot.as_mut(), message["params"]["chunk"].as_str())
+      {
+        snapshot.chunks.push_str(chunk);
+      }
+      return;
+    }
+
+    if let Some(snapshot) = self
+      .snapshot
+      .take_if(|snapshot| message["id"] == snapshot.msg_id)
+    {
+      if let Some(err) = message.get("error") {
+        log::error!("Failed to take heap snapshot, reason: {}", err);
+        return;
+      }
+      write_heap_file(&self.dir, "heapsnapshot", snapshot.chunks.as_bytes());
+      return;
+    }
+
+    if let Some(profile) = message
+      .get("result")
+      .and_then(|result| result.get("profile"))
+    {
+      write_heap_file(&self.dir, "heapprofile", profile.to_string().as_bytes());
+    }
+  }
+}
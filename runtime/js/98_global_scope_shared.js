@@ -46,6 +46,7 @@ import * as webgpuSurface from "ext:deno_webgpu/02_surface.js";
 import { unstableIds } from "ext:runtime/90_deno_ns.js";
 
 const loadImage = core.createLazyLoader("ext:deno_image/01_image.js");
+const loadCanvas = core.createLazyLoader("ext:deno_image/02_canvas.js");
 const loadWebTransport = core.createLazyLoader("ext:deno_web/webtransport.js");
 
 // https://developer.mozilla.org/en-US/docs/Web/API/WindowOrWorkerGlobalScope
@@ -70,6 +71,14 @@ const windowOrWorkerGlobalScope = {
   Event: core.propNonEnumerable(event.Event),
   EventTarget: core.propNonEnumerable(event.EventTarget),
   File: core.propNonEnumerable(file.File),
+  FontFace: core.propNonEnumerableLazyLoaded(
+    (canvas) => canvas.FontFace,
+    loadCanvas,
+  ),
+  FontFaceSet: core.propNonEnumerableLazyLoaded(
+    (canvas) => canvas.FontFaceSet,
+    loadCanvas,
+  ),
   FileReader: core.propNonEnumerable(fileReader.FileReader),
   FormData: core.propNonEnumerable(formData.FormData),
   Headers: core.propNonEnumerable(headers.Headers),
@@ -79,6 +88,18 @@ const windowOrWorkerGlobalScope = {
     loadImage,
   ),
   MessageEvent: core.propNonEnumerable(event.MessageEvent),
+  OffscreenCanvas: core.propNonEnumerableLazyLoaded(
+    (canvas) => canvas.OffscreenCanvas,
+    loadCanvas,
+  ),
+  OffscreenCanvasRenderingContext2D: core.propNonEnumerableLazyLoaded(
+    (canvas) => canvas.OffscreenCanvasRenderingContext2D,
+    loadCanvas,
+  ),
+  Path2D: core.propNonEnumerableLazyLoaded(
+    (canvas) => canvas.Path2D,
+    loadCanvas,
+  ),
   Performance: core.propNonEnumerable(performance.Performance),
   PerformanceEntry: core.propNonEnumerable(performance.PerformanceEntry),
   PerformanceMark: core.propNonEnumerable(performance.PerformanceMark),
@@ -96,6 +117,10 @@ const windowOrWorkerGlobalScope = {
   Request: core.propNonEnumerable(request.Request),
   Response: core.propNonEnumerable(response.Response),
   TextDecoder: core.propNonEnumerable(encoding.TextDecoder),
+  TextMetrics: core.propNonEnumerableLazyLoaded(
+    (canvas) => canvas.TextMetrics,
+    loadCanvas,
+  ),
   TextEncoder: core.propNonEnumerable(encoding.TextEncoder),
   TextDecoderStream: core.propNonEnumerable(encoding.TextDecoderStream),
   TextEncoderStream: core.propNonEnumerable(encoding.TextEncoderStream),
@@ -137,12 +162,21 @@ const windowOrWorkerGlobalScope = {
   ),
   clearInterval: core.propWritable(timers.clearInterval),
   clearTimeout: core.propWritable(timers.clearTimeout),
+  fonts: {
+    enumerable: true,
+    configurable: true,
+    get: () => loadCanvas().fonts,
+  },
   caches: {
     enumerable: true,
     configurable: true,
     get: caches.cacheStorage,
   },
   CacheStorage: core.propNonEnumerable(caches.CacheStorage),
+  CanvasGradient: core.propNonEnumerableLazyLoaded(
+    (canvas) => canvas.CanvasGradient,
+    loadCanvas,
+  ),
   Cache: core.propNonEnumerable(caches.Cache),
   console: core.propNonEnumerable(
     new console.Console((msg, level) => core.print(msg, level > 1)),
@@ -350,6 +384,10 @@ unstableForWindowOrWorkerGlobalScope[unstableIds.net] = {
   ),
 };
 
+unstableForWindowOrWorkerGlobalScope[unstableIds.asyncContext] = {
+  AsyncContext: core.propNonEnumerable(core.AsyncContext),
+};
+
 unstableForWindowOrWorkerGlobalScope[unstableIds.webgpu] = {};
 
 unstableForWindowOrWorkerGlobalScope[unstableIds.nodeGlobals] = {
@@ -16,6 +16,7 @@ const {
 
 import * as location from "ext:deno_web/12_location.js";
 import * as console from "ext:deno_web/01_console.js";
+import * as event from "ext:deno_web/02_event.js";
 import * as webidl from "ext:deno_webidl/00_webidl.js";
 import * as globalInterfaces from "ext:deno_web/04_global_interfaces.js";
 import * as webStorage from "ext:deno_webstorage/01_webstorage.js";
@@ -175,6 +176,7 @@ const mainRuntimeGlobalProperties = {
   localStorage: core.propGetterOnly(webStorage.localStorage),
   sessionStorage: core.propGetterOnly(webStorage.sessionStorage),
   Storage: core.propNonEnumerable(webStorage.Storage),
+  StorageEvent: core.propNonEnumerable(event.StorageEvent),
 };
 
 export { mainRuntimeGlobalProperties, memoizeLazy };
@@ -54,6 +54,7 @@ const {
 import { registerDeclarativeServer } from "ext:deno_http/00_serve.ts";
 import * as event from "ext:deno_web/02_event.js";
 import * as location from "ext:deno_web/12_location.js";
+import * as webStorage from "ext:deno_webstorage/01_webstorage.js";
 import * as version from "ext:runtime/01_version.ts";
 import * as os from "ext:deno_os/30_os.js";
 import * as timers from "ext:deno_web/02_timers.js";
@@ -390,6 +391,24 @@ core.registerErrorBuilder(
     return new DOMException(msg, "InvalidStateError");
   },
 );
+core.registerErrorBuilder(
+  "DOMExceptionIndexSizeError",
+  function DOMExceptionIndexSizeError(msg) {
+    return new DOMException(msg, "IndexSizeError");
+  },
+);
+core.registerErrorBuilder(
+  "DOMExceptionSyntaxError",
+  function DOMExceptionSyntaxError(msg) {
+    return new DOMException(msg, "SyntaxError");
+  },
+);
+core.registerErrorBuilder(
+  "DOMExceptionEncodingError",
+  function DOMExceptionEncodingError(msg) {
+    return new DOMException(msg, "EncodingError");
+  },
+);
 
 function runtimeStart(
   denoVersion,
@@ -761,6 +780,8 @@ function bootstrapMainRuntime(runtimeOptions, warmup = false) {
     event.defineEventHandler(globalThis, "load");
     event.defineEventHandler(globalThis, "beforeunload");
     event.defineEventHandler(globalThis, "unload");
+    event.defineEventHandler(globalThis, "storage");
+    webStorage.receiveStorageEvents(globalThis);
 
     runtimeStart(
       denoVersion,
@@ -881,6 +902,8 @@ function bootstrapWorkerRuntime(
 
     event.defineEventHandler(globalThis, "message");
     event.defineEventHandler(globalThis, "error", undefined, true);
+    event.defineEventHandler(globalThis, "storage");
+    webStorage.receiveStorageEvents(globalThis);
 
     // `Deno.exit()` is an alias to `self.close()`. Setting and exit
     // code using an op in worker context is a no-op.
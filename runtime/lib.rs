@@ -29,6 +29,8 @@ pub use deno_webstorage;
 
 pub mod code_cache;
 pub mod coverage;
+pub mod cpu_profiler;
+pub mod heap_profiler;
 pub mod fmt_errors;
 pub mod js;
 pub mod ops;
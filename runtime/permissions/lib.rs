@@ -67,6 +67,15 @@ pub struct PermissionDeniedError {
   pub state: PermissionState,
 }
 
+/// Returned when a permission can't be expressed as a list of granted
+/// descriptors because some were also denied.
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+#[error(
+  "Cannot derive an allow list from --allow-{0} while parts of it are denied, specify the allowed {0} access explicitly instead"
+)]
+#[class("NotCapable")]
+pub struct PartiallyDeniedError(pub &'static str);
+
 fn format_permission_error(name: &'static str) -> String {
   if is_standalone() {
     format!(
@@ -954,6 +963,36 @@ impl<
       && !has_broker()
   }
 
+  /// Returns the granted descriptors, or `None` if everything is granted.
+  ///
+  /// This is for enforcing permissions with mechanisms that can only express
+  /// an allow list, like OS sandboxes, so it errors if anything was denied as
+  /// that can't be subtracted from what's granted.
+  pub fn granted_allow_list(
+    &self,
+  ) -> Result<Option<Vec<&TAllowDesc>>, PartiallyDeniedError> {
+    if self.flag_denied_global
+      || self.prompt_denied_global
+      || self.flag_ignored_global
+      || self.descriptors.has_any_denied_or_ignored()
+    {
+      return Err(PartiallyDeniedError(TAllowDesc::QueryDesc::flag_name()));
+    }
+    if self.granted_global {
+      return Ok(None);
+    }
+    Ok(Some(
+      self
+        .descriptors
+        .iter()
+        .filter_map(|item| match item {
+          UnaryPermissionDesc::Granted(desc) => Some(desc),
+          _ => None,
+        })
+        .collect(),
+    ))
+  }
+
   pub fn check_all_api(
     &mut self,
     api_name: Option<&str>,
@@ -4047,6 +4086,36 @@ impl PermissionsContainer {
     self.inner.lock().read.query(None) == PermissionState::Granted
   }
 
+  /// The paths that reading is currently granted for, or `None` if reading is
+  /// granted everywhere.
+  pub fn granted_read_paths(
+    &self,
+  ) -> Result<Option<Vec<PathBuf>>, PartiallyDeniedError> {
+    Ok(
+      self
+        .inner
+        .lock()
+        .read
+        .granted_allow_list()?
+        .map(|descs| descs.into_iter().map(|d| d.0.path.clone()).collect()),
+    )
+  }
+
+  /// The paths that writing is currently granted for, or `None` if writing is
+  /// granted everywhere.
+  pub fn granted_write_paths(
+    &self,
+  ) -> Result<Option<Vec<PathBuf>>, PartiallyDeniedError> {
+    Ok(
+      self
+        .inner
+        .lock()
+        .write
+        .granted_allow_list()?
+        .map(|descs| descs.into_iter().map(|d| d.0.path.clone()).collect()),
+    )
+  }
+
   #[inline(always)]
   pub fn check_write_all(
     &self,
@@ -4341,6 +4410,17 @@ impl PermissionsContainer {
     Ok(())
   }
 
+  #[inline(always)]
+  pub fn query_net_all(&self) -> bool {
+    self.inner.lock().net.query(None) == PermissionState::Granted
+  }
+
+  #[inline(always)]
+  pub fn check_net_all(&self) -> Result<(), PermissionCheckError> {
+    self.inner.lock().net.check_all()?;
+    Ok(())
+  }
+
   #[inline(always)]
   pub fn check_net<T: AsRef<str>>(
     &mut self,
@@ -6724,6 +6804,30 @@ mod tests {
     assert!(perms.write.check(&write_query, None).is_err());
   }
 
+  #[test]
+  fn test_granted_allow_list() {
+    let parser = TestPermissionDescriptorParser;
+    let perms = Permissions::from_options(
+      &parser,
+      &PermissionsOptions {
+        allow_read: Some(vec![]),
+        allow_write: Some(svec!["/foo", "/bar"]),
+        allow_net: Some(svec!["example.com"]),
+        deny_net: Some(svec!["example.com:8080"]),
+        ..Default::default()
+      },
+    )
+    .unwrap();
+
+    assert!(perms.read.granted_allow_list().unwrap().is_none());
+    let write = perms.write.granted_allow_list().unwrap().unwrap();
+    let mut write = write.iter().map(|d| d.0.path.clone()).collect::<Vec<_>>();
+    write.sort();
+    assert_eq!(write, vec![PathBuf::from("/bar"), PathBuf::from("/foo")]);
+    assert_eq!(perms.ffi.granted_allow_list().unwrap().unwrap().len(), 0);
+    assert!(perms.net.granted_allow_list().is_err());
+  }
+
   #[test]
   fn test_check_allow_global_deny_global() {
     let parser = TestPermissionDescriptorParser;
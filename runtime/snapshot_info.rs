@@ -27,7 +27,7 @@ pub fn get_extensions_in_snapshot() -> Vec<Extension> {
     deno_fetch::deno_fetch::init(Default::default()),
     deno_cache::deno_cache::init(None),
     deno_websocket::deno_websocket::init(),
-    deno_webstorage::deno_webstorage::init(None),
+    deno_webstorage::deno_webstorage::init(None, None, Default::default()),
     deno_crypto::deno_crypto::init(None),
     deno_ffi::deno_ffi::init(None),
     deno_net::deno_net::init(None, None),
@@ -57,6 +57,7 @@ use deno_web::MessagePort;
 use deno_web::Transferable;
 use deno_web::create_entangled_message_port;
 use deno_web::serialize_transferables;
+use deno_webstorage::WebStorageRegistry;
 use log::debug;
 use node_resolver::InNpmPackageChecker;
 use node_resolver::NpmPackageFolderResolver;
@@ -64,7 +65,11 @@ use node_resolver::NpmPackageFolderResolver;
 use crate::BootstrapOptions;
 use crate::FeatureChecker;
 use crate::coverage::CoverageCollector;
+use crate::cpu_profiler::CpuProfiler;
+use crate::cpu_profiler::CpuProfilerOptions;
 use crate::deno_inspector_server::MainInspectorSessionChannel;
+use crate::heap_profiler::HeapProfiler;
+use crate::heap_profiler::HeapProfilerOptions;
 use crate::ops;
 use crate::shared::runtime;
 use crate::worker::FormatJsErrorFn;
@@ -396,6 +401,9 @@ pub struct WebWorkerServiceOptions<
   pub root_cert_store_provider: Option<Arc<dyn RootCertStoreProvider>>,
   pub shared_array_buffer_store: Option<SharedArrayBufferStore>,
   pub bundle_provider: Option<Arc<dyn deno_bundle_runtime::BundleProvider>>,
+  /// The storage areas to use for `localStorage`, usually the same
+  /// [WebStorageRegistry] as the main worker's.
+  pub web_storage: WebStorageRegistry,
 }
 
 pub struct WebWorkerOptions {
@@ -413,11 +421,17 @@ pub struct WebWorkerOptions {
   pub format_js_error_fn: Option<Arc<FormatJsErrorFn>>,
   pub worker_type: WorkerThreadType,
   pub cache_storage_dir: Option<std::path::PathBuf>,
+  pub origin_storage_dir: Option<std::path::PathBuf>,
+  /// Maximum size of a storage area in bytes, defaults to
+  /// [deno_webstorage::DEFAULT_STORAGE_QUOTA].
+  pub storage_quota: Option<u64>,
   pub stdio: Stdio,
   pub trace_ops: Option<Vec<String>>,
   pub close_on_idle: bool,
   pub maybe_worker_metadata: Option<WorkerMetadata>,
   pub maybe_coverage_dir: Option<PathBuf>,
+  pub maybe_cpu_profiler: Option<CpuProfilerOptions>,
+  pub maybe_heap_profiler: Option<HeapProfilerOptions>,
   pub enable_raw_imports: bool,
   pub enable_stack_trace_arg_in_ops: bool,
 }
@@ -441,6 +455,8 @@ pub struct WebWorker {
   maybe_worker_metadata: Option<WorkerMetadata>,
   memory_trim_handle: Option<tokio::task::JoinHandle<()>>,
   maybe_coverage_dir: Option<PathBuf>,
+  maybe_cpu_profiler: Option<CpuProfilerOptions>,
+  maybe_heap_profiler: Option<HeapProfilerOptions>,
   bootstrap_error: Option<CoreError>,
 }
 
@@ -548,7 +564,11 @@ impl WebWorker {
       }),
       deno_cache::deno_cache::init(create_cache),
       deno_websocket::deno_websocket::init(),
-      deno_webstorage::deno_webstorage::init(None).disable(),
+      deno_webstorage::deno_webstorage::init(
+        options.origin_storage_dir.clone(),
+        options.storage_quota,
+        services.web_storage,
+      ),
       deno_crypto::deno_crypto::init(options.seed),
       deno_ffi::deno_ffi::init(services.deno_rt_native_addon_loader.clone()),
       deno_net::deno_net::init(
@@ -754,6 +774,8 @@ impl WebWorker {
         maybe_worker_metadata: options.maybe_worker_metadata,
         memory_trim_handle: None,
         maybe_coverage_dir: options.maybe_coverage_dir,
+        maybe_cpu_profiler: options.maybe_cpu_profiler,
+        maybe_heap_profiler: options.maybe_heap_profiler,
         bootstrap_error: None,
       },
       external_handle,
@@ -855,6 +877,16 @@ impl WebWorker {
     Some(coverage_collector)
   }
 
+  pub fn maybe_setup_cpu_profiler(&mut self) -> Option<CpuProfiler> {
+    let options = self.maybe_cpu_profiler.clone()?;
+    Some(CpuProfiler::new(&mut self.js_runtime, options))
+  }
+
+  pub fn maybe_setup_heap_profiler(&mut self) -> Option<HeapProfiler> {
+    let options = self.maybe_heap_profiler.clone()?;
+    Some(HeapProfiler::new(&mut self.js_runtime, options))
+  }
+
   #[cfg(not(target_os = "linux"))]
   pub fn setup_memory_trim_handler(&mut self) {
     // Noop
@@ -1097,6 +1129,8 @@ pub async fn run_web_worker(
 ) -> Result<(), CoreError> {
   worker.setup_memory_trim_handler();
   let mut maybe_coverage_collector = worker.maybe_setup_coverage_collector();
+  let mut maybe_cpu_profiler = worker.maybe_setup_cpu_profiler();
+  let mut maybe_heap_profiler = worker.maybe_setup_heap_profiler();
 
   let name = worker.name.to_string();
   let mut internal_handle = worker.internal_handle.clone();
@@ -1133,6 +1167,12 @@ pub async fn run_web_worker(
     if let Some(coverage_collector) = maybe_coverage_collector.as_mut() {
       coverage_collector.stop_collecting()?;
     }
+    if let Some(cpu_profiler) = maybe_cpu_profiler.as_mut() {
+      cpu_profiler.stop(&mut worker.js_runtime);
+    }
+    if let Some(heap_profiler) = maybe_heap_profiler.as_mut() {
+      heap_profiler.stop(&mut worker.js_runtime);
+    }
     return Ok(());
   }
 
@@ -1146,6 +1186,12 @@ pub async fn run_web_worker(
     if let Some(coverage_collector) = maybe_coverage_collector.as_mut() {
       coverage_collector.stop_collecting()?;
     }
+    if let Some(cpu_profiler) = maybe_cpu_profiler.as_mut() {
+      cpu_profiler.stop(&mut worker.js_runtime);
+    }
+    if let Some(heap_profiler) = maybe_heap_profiler.as_mut() {
+      heap_profiler.stop(&mut worker.js_runtime);
+    }
     r
   } else {
     result
@@ -49,6 +49,7 @@ use deno_tls::RootCertStoreProvider;
 use deno_tls::TlsKeys;
 use deno_web::BlobStore;
 use deno_web::InMemoryBroadcastChannel;
+use deno_webstorage::WebStorageRegistry;
 use log::debug;
 use node_resolver::InNpmPackageChecker;
 use node_resolver::NpmPackageFolderResolver;
@@ -197,6 +198,11 @@ pub struct WorkerServiceOptions<
   pub root_cert_store_provider: Option<Arc<dyn RootCertStoreProvider>>,
   pub fetch_dns_resolver: deno_fetch::dns::Resolver,
 
+  /// The storage areas to use for `localStorage`. Workers created with the
+  /// same [WebStorageRegistry] receive `storage` events for each other's
+  /// changes; `sessionStorage` is never shared.
+  pub web_storage: WebStorageRegistry,
+
   /// The store to use for transferring SharedArrayBuffers between isolates.
   /// If multiple isolates should have the possibility of sharing
   /// SharedArrayBuffers, they should use the same [SharedArrayBufferStore]. If
@@ -256,6 +262,9 @@ pub struct WorkerOptions {
 
   pub cache_storage_dir: Option<std::path::PathBuf>,
   pub origin_storage_dir: Option<std::path::PathBuf>,
+  /// Maximum size in bytes of each Web Storage area. Defaults to
+  /// [deno_webstorage::DEFAULT_STORAGE_QUOTA].
+  pub storage_quota: Option<u64>,
   pub stdio: Stdio,
   pub enable_raw_imports: bool,
   pub enable_stack_trace_arg_in_ops: bool,
@@ -277,6 +286,7 @@ impl Default for WorkerOptions {
       trace_ops: Default::default(),
       format_js_error_fn: Default::default(),
       origin_storage_dir: Default::default(),
+      storage_quota: Default::default(),
       cache_storage_dir: Default::default(),
       extensions: Default::default(),
       startup_snapshot: Default::default(),
@@ -556,6 +566,8 @@ impl MainWorker {
         deno_websocket::deno_websocket::args(),
         deno_webstorage::deno_webstorage::args(
           options.origin_storage_dir.clone(),
+          options.storage_quota,
+          services.web_storage.clone(),
         ),
         deno_crypto::deno_crypto::args(options.seed),
         deno_ffi::deno_ffi::args(services.deno_rt_native_addon_loader.clone()),
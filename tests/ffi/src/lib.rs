@@ -566,3 +566,47 @@ pub unsafe extern "C" fn create_mixed(
 pub extern "C" fn print_mixed(mixed: Mixed) {
   println!("{mixed:?}");
 }
+
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub union Number {
+  int: i64,
+  float: f64,
+}
+
+#[repr(C)]
+pub struct Tagged {
+  is_float: bool,
+  value: Number,
+  name: [u8; 7],
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn number_from_int(int: i64) -> Number {
+  Number { int }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn number_as_f64(number: Number, is_float: bool) -> f64 {
+  unsafe {
+    if is_float {
+      number.float
+    } else {
+      number.int as f64
+    }
+  }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn make_tagged(value: f64) -> Tagged {
+  Tagged {
+    is_float: true,
+    value: Number { float: value },
+    name: *b"tagged\0",
+  }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn tagged_name_length(tagged: Tagged) -> u32 {
+  tagged.name.iter().take_while(|&&byte| byte != 0).count() as u32
+}
@@ -47,6 +47,17 @@ const remote = Deno.dlopen("dummy_lib.so", {
     result: "void",
     optional: true,
   },
+  method26: {
+    parameters: ["buffer"],
+    result: "i32",
+    variadic: true,
+  },
+  method27: {
+    parameters: [{
+      union: ["i64", { struct: ["u8", { array: { type: "u8", length: 7 } }] }],
+    }],
+    result: { union: ["f64", "u64"] },
+  },
   static1: { type: "usize" },
   static2: { type: "pointer" },
   static3: { type: "usize" },
@@ -281,6 +292,17 @@ let r42_1: number = remote.symbols.method24(true);
 // @ts-expect-error: Optional symbol; can be null.
 remote.symbols.method25();
 
+remote.symbols.method26(null);
+remote.symbols.method26(null, ["i32", "f64"], 1, 2.5);
+// @ts-expect-error: Invalid variadic argument type
+remote.symbols.method26(null, ["int"], 1);
+// @ts-expect-error: Invalid argument
+remote.symbols.method26(0);
+
+const r27: Uint8Array = remote.symbols.method27(new Uint8Array(8));
+// @ts-expect-error: Invalid argument
+remote.symbols.method27(0);
+
 // @ts-expect-error: Invalid member type
 const static1_wrong: number = remote.symbols.static1;
 const static1_right: bigint = remote.symbols.static1;
@@ -81,6 +81,19 @@ const Rect = ["f64", "f64", "f64", "f64"];
 const RectNested = [{ struct: Point }, { struct: Size }];
 const RectNestedCached = [{ struct: Size }, { struct: Size }];
 const Mixed = ["u8", "f32", { struct: Rect }, "usize", { struct: ["u32", "u32"] }];
+const Number = ["i64", "f64"];
+const Tagged = ["bool", { union: Number }, { array: { type: "u8", length: 7 } }];
+
+assertThrows(() => {
+  Deno.dlopen(libPath, {
+    print_something: {
+      parameters: [{ array: { type: "u8", length: 4 } }],
+      result: "void",
+    },
+  }),
+  TypeError,
+  "Arrays can only be used as struct or union fields"
+});
 
 const dylib = Deno.dlopen(libPath, {
   "printSomething": {
@@ -283,6 +296,22 @@ const dylib = Deno.dlopen(libPath, {
     result: "void",
     optional: true,
   },
+  number_from_int: {
+    parameters: ["i64"],
+    result: { union: Number },
+  },
+  number_as_f64: {
+    parameters: [{ union: Number }, "bool"],
+    result: "f64",
+  },
+  make_tagged: {
+    parameters: ["f64"],
+    result: { struct: Tagged },
+  },
+  tagged_name_length: {
+    parameters: [{ struct: Tagged }],
+    result: "u32",
+  },
   non_existent_symbol: {
     parameters: [],
     result: "void",
@@ -670,6 +699,88 @@ assertEquals(new BigUint64Array(mixedStruct.buffer, 40, 1)[0], 12456789n);
 assertEquals(new Uint32Array(mixedStruct.buffer, 48, 2), new Uint32Array([8, 32]));
 dylib.symbols.print_mixed(mixedStruct);
 
+// Test union and inline array returning and passing
+const number = dylib.symbols.number_from_int(-42n);
+assertEquals(number.length, 8);
+assertEquals(new BigInt64Array(number.buffer)[0], -42n);
+assertEquals(dylib.symbols.number_as_f64(number, false), -42);
+assertEquals(dylib.symbols.number_as_f64(new Float64Array([1.5]), true), 1.5);
+const tagged = dylib.symbols.make_tagged(2.5);
+assertEquals(tagged.length, 24);
+assertEquals(tagged[0], 1);
+assertEquals(new Float64Array(tagged.buffer, 8, 1)[0], 2.5);
+assertEquals(new TextDecoder().decode(tagged.subarray(16, 22)), "tagged");
+assertEquals(dylib.symbols.tagged_name_length(tagged), 6);
+
+// Test variadic calls
+{
+  const libcPath = {
+    darwin: "libSystem.dylib",
+    linux: "libc.so.6",
+    windows: "msvcrt.dll",
+  }[Deno.build.os];
+  const libc = Deno.dlopen(libcPath, {
+    sprintf: {
+      parameters: ["buffer", "buffer"],
+      result: "i32",
+      variadic: true,
+    },
+    sprintf_async: {
+      name: "sprintf",
+      parameters: ["buffer", "buffer"],
+      result: "i32",
+      nonblocking: true,
+      variadic: true,
+    },
+  });
+  const encoder = new TextEncoder();
+  const buffer = new Uint8Array(64);
+  const format = encoder.encode("%d %s %.2f %lld\0");
+  const string = encoder.encode("variadic\0");
+  const length = libc.symbols.sprintf(
+    buffer,
+    format,
+    ["i32", "buffer", "f64", "i64"],
+    42,
+    string,
+    1.5,
+    -7n,
+  );
+  assertEquals(new TextDecoder().decode(buffer.subarray(0, length)), "42 variadic 1.50 -7");
+  assertEquals(libc.symbols.sprintf(buffer, encoder.encode("none\0")), 4);
+  assertEquals(
+    await libc.symbols.sprintf_async(buffer, encoder.encode("%u\0"), ["u32"], 7),
+    1,
+  );
+  assertEquals(buffer[0], "7".charCodeAt(0));
+  assertThrows(
+    () => libc.symbols.sprintf(buffer, format, ["f32"], 1.5),
+    TypeError,
+    "Invalid variadic argument type 'f32', variadic arguments are promoted to 'f64'",
+  );
+  assertThrows(
+    () => libc.symbols.sprintf(buffer, format, ["void"], undefined),
+    TypeError,
+    "Invalid FFI variadic argument type 'void'",
+  );
+  assertThrows(
+    () => libc.symbols.sprintf(buffer, format, [{ array: { type: "u8", length: 4 } }], new Uint8Array(4)),
+    TypeError,
+    "Invalid FFI variadic argument type 'array'",
+  );
+  assertThrows(
+    () => libc.symbols.sprintf(buffer, format, ["i32", "i32"], 1),
+    TypeError,
+    "Invalid FFI variadic arguments",
+  );
+  assertThrows(
+    () => new Deno.UnsafeCallback({ parameters: [], result: "void", variadic: true }, () => {}),
+    TypeError,
+    "Cannot construct UnsafeCallback: cannot be variadic",
+  );
+  libc.close();
+}
+
 const cb = new Deno.UnsafeCallback({
   parameters: [{ struct: Rect }],
   result: { struct: Rect },
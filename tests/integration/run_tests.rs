@@ -132,6 +132,102 @@ fn webstorage_main_module() {
     .assert_matches_text("Storage { hello: \"deno\", length: 1 }\n");
 }
 
+// tests to ensure that `--storage-dir` persists storage in the given
+// directory regardless of the location.
+#[test]
+fn webstorage_storage_dir() {
+  let context = TestContext::default();
+  let storage_dir = context.temp_dir().path().join("storage");
+
+  context
+    .new_command()
+    .args_vec([
+      "run",
+      "--location",
+      "https://example.com/a.ts",
+      "--storage-dir",
+      storage_dir.to_string_lossy().as_ref(),
+      "run/webstorage/fixture.ts",
+    ])
+    .run()
+    .assert_matches_text("Storage { length: 0 }\n");
+
+  context
+    .new_command()
+    .args_vec([
+      "run",
+      "--location",
+      "https://example.net/b.ts",
+      "--storage-dir",
+      storage_dir.to_string_lossy().as_ref(),
+      "run/webstorage/logger.ts",
+    ])
+    .run()
+    .assert_matches_text("Storage { hello: \"deno\", length: 1 }\n");
+
+  assert!(storage_dir.join("local_storage").exists());
+}
+
+#[test]
+fn webstorage_storage_quota() {
+  let context = TestContext::default();
+
+  context
+    .new_command()
+    .args_vec([
+      "eval",
+      "--storage-quota=16",
+      r#"
+      localStorage.setItem("a", "b".repeat(15));
+      try {
+        localStorage.setItem("c", "d");
+      } catch (e) {
+        console.log(e.name);
+      }
+      localStorage.setItem("a", "b");
+      localStorage.setItem("c", "d");
+      console.log(localStorage.length);
+      localStorage.clear();
+      "#,
+    ])
+    .run()
+    .assert_matches_text("QuotaExceededError\n2\n");
+}
+
+// tests to ensure that changes to `localStorage` are delivered to the other
+// workers as `storage` events, but not to the worker that made them, and that
+// `sessionStorage` is not shared.
+#[test]
+fn webstorage_storage_events() {
+  let context = TestContext::default();
+  let storage_dir = context.temp_dir().path().join("storage");
+
+  context
+    .new_command()
+    .args_vec([
+      "run",
+      "--allow-read",
+      "--storage-dir",
+      storage_dir.to_string_lossy().as_ref(),
+      "run/webstorage/events.ts",
+    ])
+    .run()
+    .assert_matches_text(
+      "worker: a null 1
+worker: a 1 2
+worker: a 2 null
+worker: b null 3
+worker: null null null
+worker storageArea: true
+worker session: worker
+main: from null worker
+main storageArea: true
+worker: end null 1
+main session: main
+",
+    );
+}
+
 #[test]
 fn _083_legacy_external_source_map() {
   let _g = util::http_server();
@@ -0,0 +1,25 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+const file = Deno.makeTempFileSync();
+const { success } = await new Deno.Command(Deno.execPath(), {
+  args: ["run", "--env-file=env_file", "-A", "-q", "file_exporter_child.ts"],
+  env: {
+    OTEL_DENO_EXPORTER_FILE: file,
+    OTEL_DENO_METRICS: "false",
+    OTEL_DENO_CONSOLE: "ignore",
+  },
+}).output();
+
+// Every export request is written as a single line of OTLP JSON.
+const spans = Deno.readTextFileSync(file)
+  .trimEnd()
+  .split("\n")
+  .map((line) => JSON.parse(line))
+  .flatMap((body) => body.resourceSpans ?? [])
+  .flatMap((resourceSpans) => resourceSpans.scopeSpans)
+  .flatMap((scopeSpans) => scopeSpans.spans);
+
+console.log(success);
+for (const span of spans.sort((a, b) => a.name.localeCompare(b.name))) {
+  console.log(span.name, span.traceId, span.parentSpanId || "-");
+}
@@ -0,0 +1,13 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+// fast calls are not traced
+await Deno.readTextFile("deno.json");
+await Deno.stat("deno.json");
+const dir = await Deno.makeTempDir();
+const fifo = `${dir}/fifo`;
+await new Deno.Command("mkfifo", { args: [fifo] }).output();
+
+// reading from a FIFO blocks until something is written to it
+setTimeout(() => Deno.writeTextFile(fifo, "slow"), 500);
+await Deno.readTextFile(fifo);
+await Deno.remove(dir, { recursive: true });
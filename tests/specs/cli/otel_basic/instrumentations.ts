@@ -0,0 +1,13 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+const kv = await Deno.openKv(":memory:");
+await kv.set(["a"], 1);
+await kv.get(["a"]);
+kv.close();
+
+await Deno.readTextFile("deno.json");
+
+await new Deno.Command(Deno.execPath(), {
+  args: ["--version"],
+  env: { OTEL_SDK_DISABLED: "true" },
+}).output();
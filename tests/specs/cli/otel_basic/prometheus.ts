@@ -0,0 +1,20 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+import { metrics } from "npm:@opentelemetry/api@1.9.0";
+
+const meter = metrics.getMeter("m");
+const counter = meter.createCounter("requests", {
+  description: "Number of requests",
+});
+counter.add(2, { route: "/" });
+
+const port = Deno.env.get("OTEL_EXPORTER_PROMETHEUS_PORT");
+const res = await fetch(`http://localhost:${port}/metrics`);
+console.log(res.status, res.headers.get("content-type"));
+for (const line of (await res.text()).split("\n")) {
+  if (line.includes("requests")) console.log(line);
+}
+
+const notFound = await fetch(`http://localhost:${port}/`);
+await notFound.body?.cancel();
+console.log(notFound.status);
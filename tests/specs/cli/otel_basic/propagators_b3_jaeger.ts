@@ -0,0 +1,43 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+import { context, propagation } from "npm:@opentelemetry/api@1.9.0";
+
+const getter = {
+  get(carrier: Map<string, string>, key: string) {
+    return carrier.get(key);
+  },
+  keys(carrier: Map<string, string>) {
+    return Array.from(carrier.keys());
+  },
+};
+
+function roundtrip(headers: [string, string][]) {
+  const ctx = propagation.extract(
+    context.active(),
+    new Map(headers),
+    getter,
+  );
+  const carrier = new Map<string, string>();
+  propagation.inject(ctx, carrier, {
+    set(carrier, key, value) {
+      carrier.set(key, value);
+    },
+  });
+  for (const [key, value] of [...carrier].sort()) {
+    console.log(`${key}: ${value}`);
+  }
+  console.log("---");
+}
+
+roundtrip([
+  ["b3", "80f198ee56343ba864fe8b2a57d3eff7-e457b5a2e4d86bd1-1"],
+  ["uberctx-userid", "alice"],
+]);
+roundtrip([
+  ["x-b3-traceid", "64fe8b2a57d3eff7"],
+  ["x-b3-spanid", "e457b5a2e4d86bd1"],
+  ["x-b3-sampled", "0"],
+]);
+roundtrip([
+  ["uber-trace-id", "64fe8b2a57d3eff7%3Ae457b5a2e4d86bd1%3A0%3A1"],
+]);
@@ -0,0 +1,28 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+import { context, trace } from "npm:@opentelemetry/api@1.9.0";
+
+const tracer = trace.getTracer("example-tracer");
+
+tracer.startActiveSpan("unsampled", (span) => {
+  console.log(span.isRecording(), span.spanContext().traceFlags);
+  tracer.startActiveSpan("unsampled child", (child) => {
+    console.log(
+      child.isRecording(),
+      child.spanContext().traceId === span.spanContext().traceId,
+    );
+    child.end();
+  });
+  span.end();
+});
+
+const remote = trace.setSpanContext(context.active(), {
+  traceId: "4bf92f3577b34da6a3ce929d0e0e4736",
+  spanId: "00f067aa0ba902b7",
+  traceFlags: 1,
+  isRemote: true,
+});
+tracer.startActiveSpan("sampled", {}, remote, (span) => {
+  console.log(span.isRecording(), span.spanContext().traceFlags);
+  span.end();
+});
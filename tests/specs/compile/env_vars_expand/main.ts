@@ -0,0 +1,2 @@
+console.log(Deno.env.get("GREETING"));
+console.log(Deno.env.get("LITERAL"));
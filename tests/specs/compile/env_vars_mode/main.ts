@@ -0,0 +1,2 @@
+console.log(Deno.env.get("NAME"));
+console.log(Deno.env.get("HOST"));
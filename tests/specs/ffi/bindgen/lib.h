@@ -0,0 +1,50 @@
+#ifndef GEOMETRY_H
+#define GEOMETRY_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#define GEOMETRY_VERSION "1.2.0"
+#define GEOMETRY_MAX_POINTS 64
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum { SHAPE_CIRCLE, SHAPE_POLYGON } ShapeKind;
+
+typedef struct {
+  double x;
+  double y;
+} Point;
+
+typedef struct Shape {
+  ShapeKind kind;
+  uint32_t count;
+  Point points[3];
+  const char *label;
+} Shape;
+
+typedef union {
+  Point center;
+  double radius;
+} Extent;
+
+typedef void (*VisitFn)(const Shape *shape, void *user_data);
+
+double geometry_distance(Point a, Point b);
+Shape *geometry_shape_new(ShapeKind kind, const char *label);
+void geometry_shape_free(Shape *shape);
+void geometry_visit(VisitFn visit, void *user_data);
+Extent geometry_extent(const Shape *shape);
+size_t geometry_count(const uint32_t *counts, size_t len);
+#ifndef GEOMETRY_NO_LOG
+int geometry_log(const char *format, ...);
+#endif
+extern int geometry_debug;
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,13 @@
+import { writeHeapSnapshot } from "node:v8";
+
+class LeakyItem {
+  constructor(public value: number) {}
+}
+
+const cache: LeakyItem[] = [];
+writeHeapSnapshot("before.heapsnapshot");
+for (let i = 0; i < 10000; i++) {
+  cache.push(new LeakyItem(i));
+}
+writeHeapSnapshot("after.heapsnapshot");
+console.log(cache.length > 0 ? "done" : "");
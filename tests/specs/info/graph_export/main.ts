@@ -0,0 +1,4 @@
+import { a } from "./lib/a.ts";
+
+console.log(a);
+await import("./lazy.ts");
@@ -0,0 +1,3 @@
+import { returnsHi } from "http://localhost:4545/subdir/mod1.ts";
+
+console.log(returnsHi());
@@ -0,0 +1 @@
+import "npm:@denotest/different-nested-dep@1.0.0";
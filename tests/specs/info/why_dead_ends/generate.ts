@@ -0,0 +1,9 @@
+for (let i = 0; i < 40; i++) {
+  const next = i === 39 ? "main" : `d${i + 1}`;
+  Deno.writeTextFileSync(
+    `d${i}.ts`,
+    `import "./l${i}.ts";\nimport "./r${i}.ts";\n`,
+  );
+  Deno.writeTextFileSync(`l${i}.ts`, `import "./${next}.ts";\n`);
+  Deno.writeTextFileSync(`r${i}.ts`, `import "./${next}.ts";\n`);
+}
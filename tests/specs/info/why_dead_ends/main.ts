@@ -0,0 +1,2 @@
+import "./d0.ts";
+import "npm:@denotest/different-nested-dep@1.0.0";
@@ -0,0 +1,4 @@
+import * as add from "@denotest/add";
+
+// the sum export only exists in 0.5.0
+console.log(typeof add.sum);
@@ -0,0 +1,30 @@
+const requestId = new AsyncContext.Variable<string>({
+  name: "requestId",
+  defaultValue: "none",
+});
+console.log(requestId.name, requestId.get(), String(requestId));
+
+const server = requestId.run("server", () =>
+  Deno.serve({ port: 0, onListen() {} }, (req) => {
+    const outer = requestId.get();
+    return requestId.run(new URL(req.url).pathname, async () => {
+      await new Promise((resolve) => setTimeout(resolve, 1));
+      return new Response(`${outer} ${requestId.get()}`);
+    });
+  }));
+
+for (const path of ["/a", "/b"]) {
+  const res = await fetch(`http://localhost:${server.addr.port}${path}`);
+  console.log(await res.text());
+}
+await server.shutdown();
+
+const wrapped = requestId.run("wrapped", () =>
+  AsyncContext.Snapshot.wrap(() => requestId.get()));
+const snapshot = requestId.run("snapshot", () => new AsyncContext.Snapshot());
+requestId.run("timer", () => {
+  setTimeout(() => {
+    const fromSnapshot = snapshot.run(() => requestId.get());
+    console.log(requestId.get(), wrapped(), fromSnapshot);
+  }, 0);
+});
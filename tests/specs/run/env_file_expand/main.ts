@@ -0,0 +1,4 @@
+console.log(Deno.env.get("URL"));
+console.log(Deno.env.get("GREETING"));
+console.log(Deno.env.get("LITERAL"));
+console.log(Deno.env.get("ESCAPED"));
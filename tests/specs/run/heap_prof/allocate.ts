@@ -0,0 +1,5 @@
+const items = [];
+for (let i = 0; i < 100000; i++) {
+  items.push({ i });
+}
+console.log(items.length > 0 ? "done" : "");
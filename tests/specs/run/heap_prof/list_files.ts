@@ -0,0 +1,12 @@
+let snapshots = 0;
+let profiles = 0;
+for (const entry of Deno.readDirSync(Deno.args[0])) {
+  if (entry.name.endsWith(".heapsnapshot")) {
+    snapshots++;
+  } else if (entry.name.endsWith(".heapprofile")) {
+    profiles++;
+  }
+}
+console.log(
+  `${snapshots} .heapsnapshot file(s), ${profiles} .heapprofile file(s)`,
+);
@@ -0,0 +1,4 @@
+Deno.kill(Deno.pid, "SIGUSR2");
+// Give the worker's event loop a chance to pick up the snapshot request.
+await new Promise((resolve) => setTimeout(resolve, 500));
+console.log("done");
@@ -0,0 +1,14 @@
+const dir = Deno.args[0];
+let count = 0;
+let fibSampled = false;
+for (const entry of Deno.readDirSync(dir)) {
+  if (!entry.name.endsWith(".pb.gz")) continue;
+  count++;
+  const file = await Deno.open(`${dir}/${entry.name}`);
+  const profile = await new Response(
+    file.readable.pipeThrough(new DecompressionStream("gzip")),
+  ).text();
+  // The function names end up in the pprof string table.
+  fibSampled ||= profile.includes("fib");
+}
+console.log(`${count} profile(s), fib sampled: ${fibSampled}`);
@@ -0,0 +1,125 @@
+// Decodes the pprof profiles and checks that the samples taken in `busy()`
+// are labelled with the trace and span id of the server span that was
+// current while it ran.
+
+type Field = [number, number | Uint8Array];
+
+function decode(buf: Uint8Array): Field[] {
+  const fields: Field[] = [];
+  let pos = 0;
+  const varint = () => {
+    let value = 0;
+    for (let shift = 0;; shift += 7) {
+      const byte = buf[pos++];
+      value += (byte & 0x7f) * 2 ** shift;
+      if (byte < 0x80) return value;
+    }
+  };
+  while (pos < buf.length) {
+    const key = varint();
+    const wireType = key & 7;
+    if (wireType === 0) {
+      fields.push([Math.floor(key / 8), varint()]);
+    } else if (wireType === 2) {
+      const len = varint();
+      fields.push([Math.floor(key / 8), buf.subarray(pos, pos + len)]);
+      pos += len;
+    } else {
+      throw new Error(`unexpected wire type ${wireType}`);
+    }
+  }
+  return fields;
+}
+
+function decodePacked(buf: Uint8Array): number[] {
+  // a packed field is a run of varints, same as a message of field 0 varints
+  const values: number[] = [];
+  let value = 0;
+  let shift = 0;
+  for (const byte of buf) {
+    value += (byte & 0x7f) * 2 ** shift;
+    shift += 7;
+    if (byte < 0x80) {
+      values.push(value);
+      value = 0;
+      shift = 0;
+    }
+  }
+  return values;
+}
+
+const get = (fields: Field[], field: number) =>
+  fields.filter(([f]) => f === field).map(([, value]) => value);
+
+const [dir, spansFile] = Deno.args;
+
+const serverSpan = Deno.readTextFileSync(spansFile)
+  .trimEnd()
+  .split("\n")
+  .map((line) => JSON.parse(line))
+  .flatMap((body) => body.resourceSpans ?? [])
+  .flatMap((resourceSpans) => resourceSpans.scopeSpans)
+  .flatMap((scopeSpans) => scopeSpans.spans)
+  // SPAN_KIND_SERVER
+  .find((span) => span.kind === 2);
+
+let busySamples = 0;
+let labelledSamples = 0;
+for (const entry of Deno.readDirSync(dir)) {
+  const file = await Deno.open(`${dir}/${entry.name}`);
+  const profile = decode(
+    await new Response(
+      file.readable.pipeThrough(new DecompressionStream("gzip")),
+    ).bytes(),
+  );
+  const strings = get(profile, 6).map((s) =>
+    new TextDecoder().decode(s as Uint8Array)
+  );
+  const functionNames = new Map<number, string>();
+  for (const fn of get(profile, 5)) {
+    const fields = decode(fn as Uint8Array);
+    functionNames.set(
+      get(fields, 1)[0] as number,
+      strings[get(fields, 2)[0] as number],
+    );
+  }
+  const locationNames = new Map<number, string>();
+  for (const location of get(profile, 4)) {
+    const fields = decode(location as Uint8Array);
+    const line = decode(get(fields, 4)[0] as Uint8Array);
+    locationNames.set(
+      get(fields, 1)[0] as number,
+      functionNames.get(get(line, 1)[0] as number)!,
+    );
+  }
+  for (const sample of get(profile, 2)) {
+    const fields = decode(sample as Uint8Array);
+    const stack = decodePacked(get(fields, 1)[0] as Uint8Array);
+    if (!stack.some((id) => locationNames.get(id) === "busy")) continue;
+    const [count] = decodePacked(get(fields, 2)[0] as Uint8Array);
+    const labels = Object.fromEntries(
+      get(fields, 3).map((label) => {
+        const labelFields = decode(label as Uint8Array);
+        return [
+          strings[get(labelFields, 1)[0] as number],
+          strings[get(labelFields, 2)[0] as number],
+        ];
+      }),
+    );
+    busySamples += count;
+    if (
+      labels.trace_id === serverSpan.traceId &&
+      labels.span_id === serverSpan.spanId
+    ) {
+      labelledSamples += count;
+    }
+  }
+}
+
+// samples right at the end of `busy()` may be attributed to the next mark, as
+// the profiler only estimates how V8's clock relates to its own
+console.log(
+  `busy sampled: ${busySamples > 0}, labelled with the server span: ${
+    labelledSamples > busySamples / 2
+  }`,
+);
@@ -0,0 +1,9 @@
+function fib(n: number): number {
+  return n < 2 ? n : fib(n - 1) + fib(n - 2);
+}
+
+const start = Date.now();
+while (Date.now() - start < 200) {
+  fib(20);
+}
+console.log("done");
@@ -0,0 +1,18 @@
+function busy() {
+  const end = Date.now() + 300;
+  while (Date.now() < end) {
+    // spin
+  }
+}
+
+await using server = Deno.serve({ port: 0, onListen() {} }, async () => {
+  // V8 resumes the handler after the `await` itself, without going through
+  // JS code that switches the context. The samples taken afterwards must
+  // still be labelled with the server span.
+  await Promise.resolve();
+  busy();
+  return new Response("ok");
+});
+const response = await fetch(`http://localhost:${server.addr.port}/`);
+await response.text();
+console.log("done");
@@ -0,0 +1 @@
+Deno.createHttpClient({ http3: "alt-svc" });
@@ -0,0 +1 @@
+Deno.createHttpClient({ http3Fallback: false });
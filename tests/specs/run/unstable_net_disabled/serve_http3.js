@@ -0,0 +1,7 @@
+Deno.serve({
+  port: 0,
+  cert: Deno.readTextFileSync("../../../testdata/tls/localhost.crt"),
+  key: Deno.readTextFileSync("../../../testdata/tls/localhost.key"),
+  http3: true,
+  onListen() {},
+}, () => new Response());
@@ -0,0 +1 @@
+new Deno.Command(Deno.execPath(), { processGroup: true }).spawn();
@@ -0,0 +1 @@
+new Deno.Command(Deno.execPath(), { pty: true }).spawn();
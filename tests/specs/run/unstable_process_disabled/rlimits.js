@@ -0,0 +1 @@
+new Deno.Command(Deno.execPath(), { rlimits: { cpu: 1 } }).spawn();
@@ -0,0 +1 @@
+new Deno.Command(Deno.execPath(), { timeout: 1000 }).spawn();
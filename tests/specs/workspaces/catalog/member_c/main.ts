@@ -0,0 +1,3 @@
+import { add } from "@denotest/add";
+
+console.log(add(3, 4));
@@ -0,0 +1,3 @@
+import { sum } from "@denotest/add";
+
+console.log(sum(2, 3));
@@ -0,0 +1,23 @@
+const worker = new Worker(import.meta.resolve("./events_worker.ts"), {
+  type: "module",
+});
+
+addEventListener("storage", (e) => {
+  console.log("main:", e.key, e.oldValue, e.newValue);
+  console.log("main storageArea:", e.storageArea === localStorage);
+  localStorage.setItem("end", "1");
+});
+
+worker.onmessage = (e) => {
+  if (e.data === "ready") {
+    sessionStorage.setItem("who", "main");
+    localStorage.setItem("a", "1");
+    localStorage.setItem("a", "2");
+    localStorage.removeItem("a");
+    localStorage.setItem("b", "3");
+    localStorage.clear();
+  } else {
+    console.log("main session:", sessionStorage.getItem("who"));
+    worker.terminate();
+  }
+};
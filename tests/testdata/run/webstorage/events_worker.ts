@@ -0,0 +1,14 @@
+sessionStorage.setItem("who", "worker");
+
+addEventListener("storage", (e) => {
+  console.log("worker:", e.key, e.oldValue, e.newValue);
+  if (e.key === null) {
+    console.log("worker storageArea:", e.storageArea === localStorage);
+    console.log("worker session:", sessionStorage.getItem("who"));
+    localStorage.setItem("from", "worker");
+  } else if (e.key === "end") {
+    postMessage("done");
+  }
+});
+
+postMessage("ready");
@@ -1,5 +1,5 @@
 // Copyright 2018-2026 the Deno authors. MIT license.
-import { assertEquals } from "@std/assert";
+import { assert, assertEquals } from "@std/assert";
 
 Deno.test("BroadcastChannel worker", async () => {
   const intercom = new BroadcastChannel("intercom");
@@ -32,3 +32,45 @@ Deno.test("BroadcastChannel immediate close after post", () => {
   bc.postMessage("New listening connected!");
   bc.close();
 });
+
+Deno.test({
+  name: "BroadcastChannel across processes",
+  permissions: { run: true, read: true },
+  ignore: Deno.build.os === "windows",
+}, async () => {
+  // A unique location, so that no other process shares the channel.
+  const location = `http://${crypto.randomUUID()}.test/`;
+  const receiver = new Deno.Command(Deno.execPath(), {
+    args: [
+      "eval",
+      "--unstable-broadcast-channel",
+      `--location=${location}`,
+      `const bc = new BroadcastChannel("intercom");
+      bc.onmessage = (e) => {
+        console.log(e.data);
+        bc.close();
+      };
+      console.log("ready");`,
+    ],
+    stdout: "piped",
+  }).spawn();
+  const lines = receiver.stdout
+    .pipeThrough(new TextDecoderStream())
+    .values();
+  assertEquals((await lines.next()).value, "ready\n");
+
+  const sender = await new Deno.Command(Deno.execPath(), {
+    args: [
+      "eval",
+      "--unstable-broadcast-channel",
+      `--location=${location}`,
+      `const bc = new BroadcastChannel("intercom");
+      bc.postMessage({ hello: "world" });
+      bc.close();`,
+    ],
+  }).output();
+  assert(sender.success);
+
+  assertEquals((await lines.next()).value, "{ hello: \"world\" }\n");
+  assert((await receiver.status).success);
+});
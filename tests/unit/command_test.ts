@@ -1499,3 +1499,366 @@ Deno.test(
     );
   },
 );
+
+Deno.test(
+  {
+    permissions: { run: true, read: true },
+    ignore: Deno.build.os === "windows",
+  },
+  async function commandPty() {
+    const command = new Deno.Command(Deno.execPath(), {
+      args: [
+        "eval",
+        "console.log(JSON.stringify([Deno.stdin.isTerminal(), Deno.stdout.isTerminal(), Deno.consoleSize()]))",
+      ],
+      pty: { rows: 30, cols: 100 },
+    });
+    const child = command.spawn();
+    const output = await new Response(child.pty.readable).text();
+    const status = await child.status;
+
+    assertEquals(status.success, true);
+    assertStringIncludes(output, '[true,true,{"columns":100,"rows":30}]');
+  },
+);
+
+Deno.test(
+  {
+    permissions: { run: true, read: true },
+    ignore: Deno.build.os === "windows",
+  },
+  async function commandPtyWriteAndResize() {
+    const command = new Deno.Command(Deno.execPath(), {
+      args: [
+        "eval",
+        `
+        Deno.addSignalListener("SIGWINCH", () => {
+          console.log("size:" + JSON.stringify(Deno.consoleSize()));
+          Deno.exit(0);
+        });
+        const buffer = new Uint8Array(64);
+        const n = await Deno.stdin.read(buffer);
+        console.log("got:" + new TextDecoder().decode(buffer.subarray(0, n)).trim());
+        `,
+      ],
+      pty: true,
+    });
+    const child = command.spawn();
+    const decoder = new TextDecoder();
+    const reader = child.pty.readable.getReader();
+    let output = "";
+    async function readUntil(text: string) {
+      while (!output.includes(text)) {
+        const { value, done } = await reader.read();
+        if (done) break;
+        output += decoder.decode(value, { stream: true });
+      }
+    }
+
+    const writer = child.pty.writable.getWriter();
+    await writer.write(new TextEncoder().encode("hello\n"));
+    await readUntil("got:hello");
+    child.pty.resize(40, 120);
+    await readUntil("size:");
+    await readUntil("}");
+    reader.releaseLock();
+    writer.releaseLock();
+    const status = await child.status;
+
+    assertEquals(status.success, true);
+    assertStringIncludes(output, "got:hello");
+    assertStringIncludes(output, 'size:{"columns":120,"rows":40}');
+  },
+);
+
+Deno.test(
+  { permissions: { run: true, read: true } },
+  function commandPtyWithStdioThrows() {
+    assertThrows(
+      () => {
+        new Deno.Command(Deno.execPath(), {
+          args: ["eval", "console.log('hello')"],
+          stdout: "piped",
+          pty: true,
+        }).spawn();
+      },
+      TypeError,
+      "Cannot set 'stdin', 'stdout' or 'stderr' when 'pty' is enabled",
+    );
+  },
+);
+
+Deno.test(
+  { permissions: { run: true, read: true } },
+  async function commandOutputPtyThrows() {
+    await assertRejects(
+      async () => {
+        await new Deno.Command(Deno.execPath(), {
+          args: ["eval", "console.log('hello')"],
+          pty: true,
+        }).output();
+      },
+      TypeError,
+      "'pty' is not supported for this function",
+    );
+  },
+);
+
+Deno.test(
+  { permissions: { run: true, read: true } },
+  async function commandPtyNotEnabled() {
+    const child = new Deno.Command(Deno.execPath(), {
+      args: ["eval", ""],
+      stdout: "null",
+      stderr: "null",
+    }).spawn();
+    assertThrows(
+      () => child.pty,
+      TypeError,
+      "Cannot get 'pty': 'pty' is not enabled",
+    );
+    await child.status;
+  },
+);
+
+Deno.test(
+  { permissions: { run: true, read: true } },
+  async function commandTimeout() {
+    const { success, signal } = await new Deno.Command(Deno.execPath(), {
+      args: ["eval", "setTimeout(() => {}, 60000)"],
+      timeout: 100,
+    }).output();
+
+    assertEquals(success, false);
+    if (Deno.build.os !== "windows") {
+      assertEquals(signal, "SIGTERM");
+    }
+  },
+);
+
+Deno.test(
+  {
+    permissions: { run: true, read: true },
+    ignore: Deno.build.os === "windows",
+  },
+  function commandTimeoutEscalatesToSigkill() {
+    const { success, signal } = new Deno.Command(Deno.execPath(), {
+      args: [
+        "eval",
+        `Deno.addSignalListener("SIGTERM", () => {});
+        setTimeout(() => {}, 60000);`,
+      ],
+      // Long enough for the child to have installed its listener.
+      timeout: 3000,
+      killGracePeriod: 100,
+    }).outputSync();
+
+    assertEquals(success, false);
+    assertEquals(signal, "SIGKILL");
+  },
+);
+
+Deno.test(
+  {
+    permissions: { run: true, read: true },
+    ignore: Deno.build.os === "windows",
+  },
+  async function commandTimeoutKillSignal() {
+    const { signal } = await new Deno.Command(Deno.execPath(), {
+      args: ["eval", "setTimeout(() => {}, 60000)"],
+      timeout: 100,
+      killSignal: "SIGINT",
+    }).output();
+
+    assertEquals(signal, "SIGINT");
+  },
+);
+
+Deno.test(
+  { permissions: { run: true, read: true } },
+  function commandInvalidKillSignalThrows() {
+    assertThrows(
+      () => {
+        new Deno.Command(Deno.execPath(), {
+          args: ["eval", ""],
+          timeout: 100,
+          // @ts-expect-error - invalid signal
+          killSignal: "SIGFOO",
+        }).spawn();
+      },
+      TypeError,
+      "Invalid signal: SIGFOO",
+    );
+  },
+);
+
+Deno.test(
+  {
+    permissions: { run: true, read: true },
+    ignore: Deno.build.os === "windows",
+  },
+  function commandResourceLimits() {
+    const { stdout } = new Deno.Command("sh", {
+      args: ["-c", "ulimit -n; ulimit -t"],
+      rlimits: { openFiles: 64, cpu: 10 },
+    }).outputSync();
+
+    assertEquals(new TextDecoder().decode(stdout), "64\n10\n");
+  },
+);
+
+Deno.test(
+  {
+    permissions: { run: true, read: true },
+    ignore: Deno.build.os === "windows",
+  },
+  async function commandResourceLimitsWithExtraStdio() {
+    const { core, getExtraPipeRids, kExtraStdio } = Deno[Deno.internal];
+    // The piped stdio ends up at fd 8, which is already past the open files
+    // limit. fds 3 to 7 stay free for the child itself.
+    const child = new Deno.Command("sh", {
+      args: ["-c", "echo ok >&8 && ulimit -n"],
+      rlimits: { openFiles: 8 },
+      stdout: "piped",
+      [kExtraStdio]: ["null", "null", "null", "null", "null", "piped"],
+    }).spawn();
+    const { success, stdout } = await child.output();
+    core.close(getExtraPipeRids(child)[5]);
+
+    assert(success);
+    assertEquals(new TextDecoder().decode(stdout), "8\n");
+  },
+);
+
+Deno.test(
+  {
+    permissions: { run: true, read: true },
+    ignore: Deno.build.os === "windows",
+  },
+  async function commandKillProcessGroup() {
+    // The grandchild writes its pid and would otherwise outlive the child.
+    const child = new Deno.Command("sh", {
+      args: ["-c", "sleep 60 & echo $!; wait"],
+      stdout: "piped",
+      processGroup: true,
+    }).spawn();
+    const reader = child.stdout.getReader();
+    const { value } = await reader.read();
+    const grandchildPid = parseInt(new TextDecoder().decode(value));
+    reader.releaseLock();
+
+    child.kill("SIGKILL");
+    await child.status;
+    await child.stdout.cancel();
+
+    // Signal 0 only checks whether the process exists. The grandchild may
+    // still be a zombie for a moment after being killed, so retry.
+    let alive = true;
+    for (let i = 0; i < 50 && alive; i++) {
+      try {
+        Deno.kill(grandchildPid, 0 as unknown as Deno.Signal);
+        await new Promise((resolve) => setTimeout(resolve, 20));
+      } catch {
+        alive = false;
+      }
+    }
+    assert(!alive);
+  },
+);
+
+// Landlock may be disabled in the kernel or unavailable in a container.
+const landlockUnavailable = (() => {
+  if (Deno.build.os !== "linux") return true;
+  try {
+    new Deno.Command("true", { sandbox: { read: ["/"] } }).outputSync();
+    return false;
+  } catch (error) {
+    if (error instanceof Deno.errors.NotSupported) return true;
+    throw error;
+  }
+})();
+
+Deno.test(
+  {
+    permissions: { run: true, read: true, write: true },
+    ignore: landlockUnavailable,
+  },
+  async function commandSandboxRead() {
+    const allowed = await Deno.makeTempDir();
+    const denied = await Deno.makeTempDir();
+    await Deno.writeTextFile(`${allowed}/a.txt`, "allowed");
+    await Deno.writeTextFile(`${denied}/b.txt`, "denied");
+
+    const { stdout, stderr } = await new Deno.Command("sh", {
+      args: ["-c", `cat ${allowed}/a.txt; cat ${denied}/b.txt`],
+      sandbox: { read: [allowed] },
+    }).output();
+
+    assertEquals(new TextDecoder().decode(stdout), "allowed");
+    assertStringIncludes(
+      new TextDecoder().decode(stderr),
+      "Permission denied",
+    );
+
+    await Deno.remove(allowed, { recursive: true });
+    await Deno.remove(denied, { recursive: true });
+  },
+);
+
+Deno.test(
+  {
+    permissions: { run: true, read: true, write: true },
+    ignore: landlockUnavailable,
+  },
+  async function commandSandboxWrite() {
+    const allowed = await Deno.makeTempDir();
+    const denied = await Deno.makeTempDir();
+
+    const { code } = await new Deno.Command("sh", {
+      args: ["-c", `echo a > ${allowed}/a.txt && echo b > ${denied}/b.txt`],
+      stderr: "null",
+      sandbox: { write: [allowed] },
+    }).output();
+
+    assertEquals(code, 2);
+    assertEquals(await Deno.readTextFile(`${allowed}/a.txt`), "a\n");
+    assertThrows(
+      () => Deno.statSync(`${denied}/b.txt`),
+      Deno.errors.NotFound,
+    );
+
+    await Deno.remove(allowed, { recursive: true });
+    await Deno.remove(denied, { recursive: true });
+  },
+);
+
+Deno.test(
+  {
+    permissions: { run: true, read: true, write: true },
+    ignore: landlockUnavailable,
+  },
+  function commandSandboxNet() {
+    const { code, stderr } = new Deno.Command(Deno.execPath(), {
+      args: ["eval", "Deno.listen({ port: 0 })"],
+      sandbox: { net: false },
+    }).outputSync();
+
+    assertEquals(code, 1);
+    assertStringIncludes(new TextDecoder().decode(stderr), "PermissionDenied");
+  },
+);
+
+Deno.test(
+  {
+    permissions: { run: true, read: true, write: false },
+    ignore: Deno.build.os !== "linux",
+  },
+  function commandSandboxChecksPermissions() {
+    assertThrows(() => {
+      new Deno.Command(Deno.execPath(), {
+        args: ["eval", ""],
+        sandbox: { write: ["/tmp"] },
+      }).outputSync();
+    }, Deno.errors.NotCapable);
+  },
+);
@@ -236,6 +236,26 @@ Deno.test(async function multipleCrons() {
   }
 });
 
+Deno.test(async function handlerRunsInCronContext() {
+  Deno.env.set("DENO_CRON_TEST_SCHEDULE_OFFSET", "100");
+
+  const requestId = new AsyncContext.Variable({ defaultValue: "none" });
+  const { promise, resolve } = Promise.withResolvers<string[]>();
+  const ac = new AbortController();
+  const c = requestId.run("cron", () =>
+    Deno.cron("abc", "*/20 * * * *", { signal: ac.signal }, async () => {
+      const before = requestId.get();
+      await sleep(10);
+      resolve([before, requestId.get()]);
+    }));
+  try {
+    assertEquals(await promise, ["cron", "cron"]);
+  } finally {
+    ac.abort();
+    await c;
+  }
+});
+
 Deno.test(async function overlappingExecutions() {
   Deno.env.set("DENO_CRON_TEST_SCHEDULE_OFFSET", "100");
 
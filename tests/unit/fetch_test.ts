@@ -1218,6 +1218,40 @@ Deno.test(
   },
 );
 
+Deno.test(
+  { permissions: { net: true } },
+  async function fetchResponseProtocol() {
+    const response = await fetch("http://localhost:4545/assets/fixture.json");
+    assertEquals(response.protocol, "http/1.1");
+    assertEquals(response.clone().protocol, "http/1.1");
+    await response.body?.cancel();
+
+    assertEquals(new Response("hello").protocol, null);
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function createHttpClientInvalidHttp3Mode() {
+    assertThrows(
+      () =>
+        Deno.createHttpClient({
+          // @ts-expect-error testing invalid value
+          http3: "always",
+        }),
+    );
+    const client = Deno.createHttpClient({ http3: "force" });
+    // plain http requests are never upgraded
+    const response = await fetch("http://localhost:4545/assets/fixture.json", {
+      client,
+    });
+    assertEquals(response.status, 200);
+    assertEquals(response.protocol, "http/1.1");
+    await response.body?.cancel();
+    client.close();
+  },
+);
+
 Deno.test(
   { permissions: { net: true, read: true } },
   function createHttpClientAcceptPoolIdleTimeout() {
@@ -1336,6 +1370,240 @@ Deno.test(
   },
 );
 
+Deno.test(
+  { permissions: { net: true } },
+  async function fetchHttpCacheServesFreshResponses() {
+    let requests = 0;
+    await using _server = Deno.serve({
+      port: listenPort,
+      onListen() {},
+      handler: () => {
+        requests++;
+        return new Response(`response ${requests}`, {
+          headers: { "cache-control": "max-age=60" },
+        });
+      },
+    });
+    using client = Deno.createHttpClient({ cache: "memory" });
+    const url = `http://127.0.0.1:${listenPort}/`;
+
+    const first = await fetch(url, { client });
+    assertEquals(await first.text(), "response 1");
+    const second = await fetch(url, { client });
+    assertEquals(await second.text(), "response 1");
+    assert(second.headers.has("age"));
+    assertEquals(second.protocol, null);
+
+    // the request can ask for a fresh response
+    const third = await fetch(url, {
+      client,
+      headers: { "cache-control": "no-cache" },
+    });
+    assertEquals(await third.text(), "response 2");
+
+    // unsafe methods invalidate the stored response
+    await (await fetch(url, { client, method: "POST" })).text();
+    const fourth = await fetch(url, { client });
+    assertEquals(await fourth.text(), "response 4");
+    assertEquals(requests, 4);
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function fetchHttpCacheRevalidatesWithETag() {
+    let requests = 0;
+    await using _server = Deno.serve({
+      port: listenPort,
+      onListen() {},
+      handler: (req) => {
+        requests++;
+        if (req.headers.get("if-none-match") === `"v1"`) {
+          return new Response(null, {
+            status: 304,
+            headers: { etag: `"v1"` },
+          });
+        }
+        return new Response("hello", {
+          headers: { "cache-control": "no-cache", etag: `"v1"` },
+        });
+      },
+    });
+    using client = Deno.createHttpClient({ cache: "memory" });
+    const url = `http://127.0.0.1:${listenPort}/`;
+
+    const first = await fetch(url, { client });
+    assertEquals(await first.text(), "hello");
+    const second = await fetch(url, { client });
+    assertEquals(second.status, 200);
+    assertEquals(await second.text(), "hello");
+    assertEquals(requests, 2);
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function fetchHttpCacheVary() {
+    let requests = 0;
+    await using _server = Deno.serve({
+      port: listenPort,
+      onListen() {},
+      handler: (req) => {
+        requests++;
+        return new Response(req.headers.get("accept-language"), {
+          headers: { "cache-control": "max-age=60", vary: "accept-language" },
+        });
+      },
+    });
+    using client = Deno.createHttpClient({ cache: "memory" });
+    const url = `http://127.0.0.1:${listenPort}/`;
+    const get = async (language: string) => {
+      const response = await fetch(url, {
+        client,
+        headers: { "accept-language": language },
+      });
+      return await response.text();
+    };
+
+    assertEquals(await get("en"), "en");
+    assertEquals(await get("de"), "de");
+    assertEquals(await get("en"), "en");
+    assertEquals(requests, 2);
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function fetchHttpCacheSkipsLargeResponses() {
+    let requests = 0;
+    await using _server = Deno.serve({
+      port: listenPort,
+      onListen() {},
+      handler: () => {
+        requests++;
+        return new Response(new Uint8Array(9 * 1024 * 1024), {
+          headers: { "cache-control": "max-age=60" },
+        });
+      },
+    });
+    using client = Deno.createHttpClient({ cache: "memory" });
+    const url = `http://127.0.0.1:${listenPort}/`;
+
+    const first = await fetch(url, { client });
+    assertEquals((await first.arrayBuffer()).byteLength, 9 * 1024 * 1024);
+    const second = await fetch(url, { client });
+    assertEquals((await second.arrayBuffer()).byteLength, 9 * 1024 * 1024);
+    assertEquals(requests, 2);
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function fetchHttpCacheSkipsLargeStreamedResponses() {
+    let requests = 0;
+    await using _server = Deno.serve({
+      port: listenPort,
+      onListen() {},
+      handler: () => {
+        requests++;
+        // without a content-length header the size is only known once the
+        // body has been read
+        let chunks = 0;
+        const body = new ReadableStream({
+          pull(controller) {
+            if (chunks++ === 9) {
+              controller.close();
+            } else {
+              controller.enqueue(new Uint8Array(1024 * 1024));
+            }
+          },
+        });
+        return new Response(body, {
+          headers: { "cache-control": "max-age=60" },
+        });
+      },
+    });
+    using client = Deno.createHttpClient({ cache: "memory" });
+    const url = `http://127.0.0.1:${listenPort}/`;
+
+    const first = await fetch(url, { client });
+    assertEquals(first.headers.get("content-length"), null);
+    assertEquals((await first.arrayBuffer()).byteLength, 9 * 1024 * 1024);
+    const second = await fetch(url, { client });
+    assertEquals((await second.arrayBuffer()).byteLength, 9 * 1024 * 1024);
+    assertEquals(requests, 2);
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function fetchHttpCacheStaleWhileRevalidate() {
+    let requests = 0;
+    await using _server = Deno.serve({
+      port: listenPort,
+      onListen() {},
+      handler: () => {
+        requests++;
+        return new Response(`response ${requests}`, {
+          headers: { "cache-control": "max-age=0, stale-while-revalidate=60" },
+        });
+      },
+    });
+    using client = Deno.createHttpClient({ cache: "memory" });
+    const url = `http://127.0.0.1:${listenPort}/`;
+
+    assertEquals(await (await fetch(url, { client })).text(), "response 1");
+    // the stale response is served while it's revalidated in the background
+    assertEquals(await (await fetch(url, { client })).text(), "response 1");
+    await delay(100);
+    assertEquals(requests, 2);
+    assertEquals(await (await fetch(url, { client })).text(), "response 2");
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function fetchHttpCacheStorage() {
+    let requests = 0;
+    await using _server = Deno.serve({
+      port: listenPort,
+      onListen() {},
+      handler: () => {
+        requests++;
+        return new Response("hello", {
+          headers: { "cache-control": "max-age=60" },
+        });
+      },
+    });
+    const cacheName = "fetch-http-cache-test";
+    await caches.delete(cacheName);
+    const url = `http://127.0.0.1:${listenPort}/`;
+
+    {
+      using client = Deno.createHttpClient({ cache: { name: cacheName } });
+      assertEquals(await (await fetch(url, { client })).text(), "hello");
+      await delay(100);
+    }
+    // a new client using the same cache reuses the stored response
+    using client = Deno.createHttpClient({ cache: { name: cacheName } });
+    assertEquals(await (await fetch(url, { client })).text(), "hello");
+    assertEquals(requests, 1);
+    await caches.delete(cacheName);
+  },
+);
+
+Deno.test(function createHttpClientInvalidCache() {
+  assertThrows(
+    () =>
+      Deno.createHttpClient({
+        // @ts-expect-error testing invalid value
+        cache: "disk",
+      }),
+    TypeError,
+    "Invalid value for 'cache' option",
+  );
+});
+
 Deno.test(
   { permissions: { net: true } },
   async function fetchNoServerReadableStreamBody() {
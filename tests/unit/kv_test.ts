@@ -1632,6 +1632,24 @@ queueTest("listenQueue with async callback", async (db) => {
   }
 });
 
+queueTest("listenQueue handler runs in the listener's context", async (db) => {
+  const requestId = new AsyncContext.Variable({ defaultValue: "none" });
+  const { promise, resolve } = Promise.withResolvers<unknown[]>();
+  const listener = requestId.run("listener", () =>
+    db.listenQueue(async (msg) => {
+      const before = requestId.get();
+      await sleep(10);
+      resolve([msg, before, requestId.get()]);
+    }));
+  try {
+    await requestId.run("enqueuer", () => db.enqueue("test"));
+    assertEquals(await promise, ["test", "listener", "listener"]);
+  } finally {
+    db.close();
+    await listener;
+  }
+});
+
 queueTest("queue retries", async (db) => {
   let count = 0;
   const listener = db.listenQueue(async (_msg) => {
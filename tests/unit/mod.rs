@@ -92,6 +92,10 @@ fn run_test(test: &CollectedTest) -> TestResult {
     deno = deno.arg("--unstable-bundle");
   }
 
+  if test.name.ends_with("::command_test") {
+    deno = deno.arg("--unstable-process");
+  }
+
   if test.name.ends_with("::cron_test") {
     deno = deno.arg("--unstable-cron");
   }
@@ -100,6 +104,10 @@ fn run_test(test: &CollectedTest) -> TestResult {
     deno = deno.arg("--unstable-kv");
   }
 
+  if test.name.ends_with("::cron_test") || test.name.ends_with("::kv_test") {
+    deno = deno.arg("--unstable-async-context");
+  }
+
   if test.name.ends_with("::worker_permissions_test")
     || test.name.ends_with("::worker_test")
   {
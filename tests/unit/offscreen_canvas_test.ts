@@ -0,0 +1,286 @@
+// Copyright 2018-2026 the Deno authors. MIT license.
+
+import {
+  assert,
+  assertEquals,
+  assertRejects,
+  assertStrictEquals,
+  assertThrows,
+} from "./test_util.ts";
+
+const prefix = "tests/testdata/image";
+
+function pixel(
+  ctx: OffscreenCanvasRenderingContext2D,
+  x: number,
+  y: number,
+): number[] {
+  return Array.from(ctx.getImageData(x, y, 1, 1).data);
+}
+
+Deno.test(function offscreenCanvasGetContext() {
+  const canvas = new OffscreenCanvas(10, 20);
+  assertEquals(canvas.width, 10);
+  assertEquals(canvas.height, 20);
+  const ctx = canvas.getContext("2d");
+  assertStrictEquals(canvas.getContext("2d"), ctx);
+  assertStrictEquals(ctx.canvas, canvas);
+  assertEquals(canvas.getContext("webgl"), null);
+  assertThrows(
+    // @ts-expect-error: invalid context id
+    () => canvas.getContext("3d"),
+    TypeError,
+  );
+  assertThrows(
+    // @ts-expect-error: not constructible
+    () => new OffscreenCanvasRenderingContext2D(),
+    TypeError,
+    "Illegal constructor",
+  );
+});
+
+Deno.test(function offscreenCanvasFillRect() {
+  const ctx = new OffscreenCanvas(10, 10).getContext("2d");
+  assertEquals(pixel(ctx, 0, 0), [0, 0, 0, 0]);
+  ctx.fillStyle = "red";
+  assertEquals(ctx.fillStyle, "#ff0000");
+  ctx.fillRect(0, 0, 5, 10);
+  assertEquals(pixel(ctx, 2, 2), [255, 0, 0, 255]);
+  assertEquals(pixel(ctx, 7, 2), [0, 0, 0, 0]);
+
+  ctx.fillStyle = "rgb(0 0 255 / 50%)";
+  assertEquals(ctx.fillStyle, "rgba(0, 0, 255, 0.5)");
+  ctx.fillStyle = "not a color";
+  assertEquals(ctx.fillStyle, "rgba(0, 0, 255, 0.5)");
+
+  ctx.clearRect(0, 0, 2, 2);
+  assertEquals(pixel(ctx, 1, 1), [0, 0, 0, 0]);
+});
+
+Deno.test(function offscreenCanvasPathsAndTransforms() {
+  const ctx = new OffscreenCanvas(20, 20).getContext("2d");
+  ctx.translate(10, 0);
+  assertEquals(ctx.getTransform(), { a: 1, b: 0, c: 0, d: 1, e: 10, f: 0 });
+  ctx.beginPath();
+  ctx.moveTo(0, 0);
+  ctx.lineTo(10, 0);
+  ctx.lineTo(10, 10);
+  ctx.closePath();
+  ctx.resetTransform();
+  ctx.fill();
+  assertEquals(pixel(ctx, 18, 2), [0, 0, 0, 255]);
+  assertEquals(pixel(ctx, 12, 8), [0, 0, 0, 0]);
+  assertEquals(pixel(ctx, 2, 2), [0, 0, 0, 0]);
+
+  const path = new Path2D("M0 10 h10 v10 h-10 z");
+  ctx.fillStyle = "lime";
+  ctx.fill(path);
+  assertEquals(pixel(ctx, 5, 15), [0, 255, 0, 255]);
+
+  ctx.strokeStyle = "blue";
+  ctx.lineWidth = 4;
+  ctx.beginPath();
+  ctx.arc(15, 15, 4, 0, Math.PI * 2);
+  ctx.stroke();
+  assertEquals(pixel(ctx, 18, 15), [0, 0, 255, 255]);
+  assertEquals(pixel(ctx, 15, 15), [0, 0, 0, 0]);
+
+  assertThrows(() => ctx.arc(0, 0, -1, 0, 1), DOMException, "negative");
+});
+
+Deno.test(function offscreenCanvasSaveRestoreAndClip() {
+  const ctx = new OffscreenCanvas(10, 10).getContext("2d");
+  ctx.save();
+  ctx.fillStyle = "red";
+  ctx.beginPath();
+  ctx.rect(0, 0, 5, 5);
+  ctx.clip();
+  ctx.fillRect(0, 0, 10, 10);
+  ctx.restore();
+  assertEquals(ctx.fillStyle, "#000000");
+  assertEquals(pixel(ctx, 2, 2), [255, 0, 0, 255]);
+  assertEquals(pixel(ctx, 7, 7), [0, 0, 0, 0]);
+  ctx.fillRect(0, 0, 10, 10);
+  assertEquals(pixel(ctx, 7, 7), [0, 0, 0, 255]);
+});
+
+Deno.test(function offscreenCanvasGradient() {
+  const ctx = new OffscreenCanvas(100, 1).getContext("2d");
+  const gradient = ctx.createLinearGradient(0, 0, 100, 0);
+  gradient.addColorStop(0, "black");
+  gradient.addColorStop(1, "white");
+  assertThrows(() => gradient.addColorStop(2, "red"), DOMException);
+  assertThrows(() => gradient.addColorStop(0, "nope"), DOMException);
+  ctx.fillStyle = gradient;
+  assertStrictEquals(ctx.fillStyle, gradient);
+  ctx.fillRect(0, 0, 100, 1);
+  assert(pixel(ctx, 10, 0)[0] < 40);
+  assert(pixel(ctx, 90, 0)[0] > 215);
+});
+
+Deno.test(function offscreenCanvasImageData() {
+  const ctx = new OffscreenCanvas(4, 4).getContext("2d");
+  const data = new ImageData(
+    new Uint8ClampedArray([255, 0, 0, 255, 0, 255, 0, 128]),
+    2,
+    1,
+  );
+  ctx.putImageData(data, 1, 1);
+  assertEquals(pixel(ctx, 1, 1), [255, 0, 0, 255]);
+  assertEquals(pixel(ctx, 2, 1), [0, 255, 0, 128]);
+  assertEquals(pixel(ctx, 0, 0), [0, 0, 0, 0]);
+
+  const region = ctx.getImageData(3, 1, -2, 1);
+  assertEquals(region.width, 2);
+  assertEquals(
+    Array.from(region.data),
+    [255, 0, 0, 255, 0, 255, 0, 128],
+  );
+  assertEquals(ctx.getImageData(-1, -1, 1, 1).data.length, 4);
+  assertThrows(() => ctx.getImageData(0, 0, 0, 1), DOMException);
+  assertThrows(
+    () => ctx.getImageData(0, 0, 1 << 15, -(1 << 14)),
+    RangeError,
+    "Out of memory at ImageData creation",
+  );
+  assertEquals(ctx.createImageData(3, 2).data.length, 24);
+});
+
+Deno.test(async function offscreenCanvasDrawImage() {
+  const bitmap = await createImageBitmap(
+    new ImageData(
+      new Uint8ClampedArray([
+        ...[255, 0, 0, 255],
+        ...[0, 255, 0, 255],
+        ...[0, 0, 255, 255],
+        ...[255, 255, 255, 255],
+      ]),
+      2,
+      2,
+    ),
+  );
+  const ctx = new OffscreenCanvas(10, 10).getContext("2d");
+  ctx.imageSmoothingEnabled = false;
+  ctx.drawImage(bitmap, 2, 2, 4, 4);
+  assertEquals(pixel(ctx, 2, 2), [255, 0, 0, 255]);
+  assertEquals(pixel(ctx, 5, 2), [0, 255, 0, 255]);
+  assertEquals(pixel(ctx, 2, 5), [0, 0, 255, 255]);
+  assertEquals(pixel(ctx, 6, 6), [0, 0, 0, 0]);
+
+  const other = new OffscreenCanvas(10, 10).getContext("2d");
+  other.drawImage(ctx.canvas, 1, 0, 1, 1, 0, 0, 1, 1);
+  assertEquals(pixel(other, 0, 0), [0, 0, 0, 0]);
+  other.drawImage(ctx.canvas, 0, 0);
+  assertEquals(pixel(other, 5, 5), [255, 255, 255, 255]);
+
+  // @ts-expect-error: invalid arity
+  assertThrows(() => ctx.drawImage(bitmap, 0), TypeError);
+  bitmap.close();
+  assertThrows(() => ctx.drawImage(bitmap, 0, 0), DOMException);
+});
+
+Deno.test(function offscreenCanvasTransferToImageBitmap() {
+  const canvas = new OffscreenCanvas(3, 2);
+  assertThrows(() => canvas.transferToImageBitmap(), DOMException);
+  const ctx = canvas.getContext("2d");
+  ctx.fillRect(0, 0, 3, 2);
+  const bitmap = canvas.transferToImageBitmap();
+  assertEquals(bitmap.width, 3);
+  assertEquals(bitmap.height, 2);
+  assertEquals(pixel(ctx, 0, 0), [0, 0, 0, 0]);
+});
+
+Deno.test(function offscreenCanvasResize() {
+  const canvas = new OffscreenCanvas(2, 2);
+  const ctx = canvas.getContext("2d");
+  ctx.fillStyle = "red";
+  ctx.fillRect(0, 0, 2, 2);
+  canvas.width = 4;
+  assertEquals(ctx.fillStyle, "#000000");
+  assertEquals(pixel(ctx, 0, 0), [0, 0, 0, 0]);
+  assertEquals(ctx.getImageData(0, 0, 4, 2).width, 4);
+});
+
+Deno.test(async function offscreenCanvasConvertToBlob() {
+  const canvas = new OffscreenCanvas(2, 1);
+  const ctx = canvas.getContext("2d");
+  ctx.fillStyle = "red";
+  ctx.fillRect(0, 0, 1, 1);
+
+  const png = await canvas.convertToBlob();
+  assertEquals(png.type, "image/png");
+  const decoded = await createImageBitmap(png);
+  assertEquals(
+    // @ts-ignore: Deno[Deno.internal].core allowed
+    Deno[Deno.internal].getBitmapData(decoded),
+    new Uint8Array([255, 0, 0, 255, 0, 0, 0, 0]),
+  );
+
+  const jpeg = await canvas.convertToBlob({
+    type: "image/jpeg",
+    quality: 0.5,
+  });
+  assertEquals(jpeg.type, "image/jpeg");
+  assertEquals((await createImageBitmap(jpeg)).width, 2);
+
+  const webp = await canvas.convertToBlob({ type: "image/webp" });
+  assertEquals(webp.type, "image/webp");
+  assertEquals((await createImageBitmap(webp)).width, 2);
+
+  const fallback = await canvas.convertToBlob({ type: "image/avif" });
+  assertEquals(fallback.type, "image/png");
+
+  await assertRejects(
+    () => new OffscreenCanvas(0, 1).convertToBlob(),
+    DOMException,
+  );
+});
+
+Deno.test(async function offscreenCanvasText() {
+  const face = new FontFace(
+    "Test",
+    await Deno.readFile(`${prefix}/test-font.ttf`),
+  );
+  assertEquals(face.status, "loaded");
+  assertStrictEquals(await face.loaded, face);
+  fonts.add(face);
+  try {
+    assert(fonts.has(face));
+    assertEquals(fonts.size, 1);
+
+    // The test font only has a square "X" glyph covering the em box,
+    // which advances by 1em, and a space which advances by 0.5em.
+    const ctx = new OffscreenCanvas(100, 100).getContext("2d");
+    ctx.font = "bold 20px Test, sans-serif";
+    assertEquals(ctx.font, "bold 20px Test, sans-serif");
+    const metrics = ctx.measureText("X X");
+    assertEquals(metrics.width, 50);
+    assertEquals(metrics.fontBoundingBoxAscent, 16);
+    assertEquals(metrics.fontBoundingBoxDescent, 4);
+
+    ctx.textBaseline = "top";
+    ctx.fillText("X", 10, 10);
+    assertEquals(pixel(ctx, 15, 15), [0, 0, 0, 255]);
+    assertEquals(pixel(ctx, 15, 31), [0, 0, 0, 0]);
+
+    ctx.textAlign = "right";
+    ctx.fillText("XX", 90, 50, 20);
+    assertEquals(pixel(ctx, 71, 55), [0, 0, 0, 255]);
+    assertEquals(pixel(ctx, 69, 55), [0, 0, 0, 0]);
+  } finally {
+    assert(fonts.delete(face));
+  }
+  assertEquals(fonts.size, 0);
+});
+
+Deno.test(async function fontFaceInvalidData() {
+  const face = new FontFace("Broken", new Uint8Array([1, 2, 3]));
+  assertEquals(face.status, "error");
+  await assertRejects(() => face.loaded, DOMException);
+  assertThrows(
+    // @ts-expect-error: not constructible
+    () => new FontFaceSet(),
+    TypeError,
+    "Illegal constructor",
+  );
+});
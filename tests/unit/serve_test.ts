@@ -3142,6 +3142,183 @@ Deno.test(
   },
 );
 
+Deno.test(
+  { permissions: { read: true, net: true } },
+  async function httpServerWithHttp3AdvertisesAltSvc() {
+    const ac = new AbortController();
+    const { promise, resolve } = Promise.withResolvers<void>();
+    const hostname = "127.0.0.1";
+
+    await using server = Deno.serve({
+      handler: () => new Response("Hello World"),
+      hostname,
+      port: servePort,
+      signal: ac.signal,
+      onListen: onListen(resolve),
+      onError: createOnErrorCb(ac),
+      cert: Deno.readTextFileSync("tests/testdata/tls/localhost.crt"),
+      key: Deno.readTextFileSync("tests/testdata/tls/localhost.key"),
+      http3: true,
+    });
+
+    await promise;
+    const caCert = Deno.readTextFileSync("tests/testdata/tls/RootCA.pem");
+    const client = Deno.createHttpClient({ caCerts: [caCert] });
+    const resp = await fetch(`https://localhost:${servePort}/`, {
+      client,
+      headers: { "connection": "close" },
+    });
+
+    assertEquals(resp.headers.get("alt-svc"), `h3=":${servePort}"; ma=86400`);
+    assertEquals(await resp.text(), "Hello World");
+
+    client.close();
+    ac.abort();
+    await server.finished;
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, net: true } },
+  async function httpServerWithHttp3CompletesRequestViaAltSvc() {
+    const cert = Deno.readTextFileSync("tests/testdata/tls/localhost.crt");
+    const key = Deno.readTextFileSync("tests/testdata/tls/localhost.key");
+    const caCert = Deno.readTextFileSync("tests/testdata/tls/RootCA.pem");
+
+    // The HTTP/3 server is only reachable through the Alt-Svc header sent
+    // by the other server, so getting its response means the request was
+    // completed over HTTP/3. It listens on both IPv4 and IPv6 as localhost
+    // may resolve to either.
+    await using h3Server = Deno.serve({
+      handler: () => new Response("Hello over HTTP/3"),
+      hostname: "::",
+      port: 0,
+      onListen() {},
+      cert,
+      key,
+      http3: true,
+    });
+    await using server = Deno.serve({
+      handler: () =>
+        new Response("Hello over TCP", {
+          headers: { "alt-svc": `h3=":${h3Server.addr.port}"` },
+        }),
+      hostname: "127.0.0.1",
+      port: servePort,
+      onListen() {},
+      cert,
+      key,
+    });
+
+    const client = Deno.createHttpClient({
+      caCerts: [caCert],
+      http3: "alt-svc",
+      http3Fallback: false,
+    });
+    const url = `https://localhost:${servePort}/`;
+
+    const first = await fetch(url, { client });
+    assertEquals(await first.text(), "Hello over TCP");
+    const second = await fetch(url, { client });
+    assertEquals(await second.text(), "Hello over HTTP/3");
+
+    client.close();
+  },
+);
+
+Deno.test(
+  {
+    permissions: {
+      read: true,
+      net: [`127.0.0.1:${servePort}`, `localhost:${servePort}`],
+    },
+  },
+  async function httpServerAltSvcRequiresNetPermission() {
+    const cert = Deno.readTextFileSync("tests/testdata/tls/localhost.crt");
+    const key = Deno.readTextFileSync("tests/testdata/tls/localhost.key");
+    const caCert = Deno.readTextFileSync("tests/testdata/tls/RootCA.pem");
+
+    // nothing listens on the advertised port, so an upgrade would fail the
+    // request as fallback is disabled
+    await using _server = Deno.serve({
+      handler: () =>
+        new Response("Hello over TCP", {
+          headers: { "alt-svc": `h3=":${servePort + 1}"` },
+        }),
+      hostname: "127.0.0.1",
+      port: servePort,
+      onListen() {},
+      cert,
+      key,
+    });
+
+    using client = Deno.createHttpClient({
+      caCerts: [caCert],
+      http3: "alt-svc",
+      http3Fallback: false,
+    });
+    const url = `https://localhost:${servePort}/`;
+
+    const first = await fetch(url, { client });
+    assertEquals(await first.text(), "Hello over TCP");
+    const second = await fetch(url, { client });
+    assertEquals(await second.text(), "Hello over TCP");
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  function httpServerHttp3RequiresTls() {
+    assertThrows(
+      () => Deno.serve({ port: servePort, http3: true }, () => new Response()),
+      TypeError,
+      "Both 'cert' and 'key' must be provided to enable HTTP/3",
+    );
+  },
+);
+
+Deno.test(
+  { permissions: { net: true, read: true } },
+  async function httpServerHttp3FetchUpgradesViaAltSvc() {
+    const ac = new AbortController();
+    const { promise, resolve } = Promise.withResolvers<void>();
+
+    await using server = Deno.serve({
+      handler: () => new Response("Hello World"),
+      hostname: "127.0.0.1",
+      port: servePort,
+      signal: ac.signal,
+      onListen: onListen(resolve),
+      onError: createOnErrorCb(ac),
+      cert: Deno.readTextFileSync("tests/testdata/tls/localhost.crt"),
+      key: Deno.readTextFileSync("tests/testdata/tls/localhost.key"),
+      http3: true,
+    });
+
+    await promise;
+    const caCert = Deno.readTextFileSync("tests/testdata/tls/RootCA.pem");
+    const client = Deno.createHttpClient({
+      caCerts: [caCert],
+      http3: "alt-svc",
+      http3Fallback: false,
+    });
+    const url = `https://localhost:${servePort}/`;
+
+    // the first request is sent over TCP and the second one over HTTP/3,
+    // which fails the request if the upgrade doesn't work because fallback
+    // is disabled
+    const first = await fetch(url, { client });
+    assertEquals(await first.text(), "Hello World");
+
+    const second = await fetch(url, { client });
+    assertEquals(await second.text(), "Hello World");
+
+    client.close();
+    ac.abort();
+    await server.finished;
+  },
+);
+
 Deno.test(
   { permissions: { net: true, write: true, read: true } },
   async function httpServerRequestCLTE() {
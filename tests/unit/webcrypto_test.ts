@@ -2219,3 +2219,299 @@ Deno.test("crypto.subtle.importKey PKCS#8 with wrong algorithm does not panic",
     )
   );
 });
+
+function toHex(buf: ArrayBuffer): string {
+  return Array.from(new Uint8Array(buf))
+    .map((b) => b.toString(16).padStart(2, "0"))
+    .join("");
+}
+
+Deno.test(async function sha3AndCShakeDigest() {
+  assertEquals(
+    toHex(await crypto.subtle.digest("SHA3-256", new Uint8Array())),
+    "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a",
+  );
+
+  // NIST SP 800-185 cSHAKE sample #1
+  const digest = await crypto.subtle.digest(
+    {
+      name: "cSHAKE128",
+      length: 256,
+      customization: new TextEncoder().encode("Email Signature"),
+    },
+    new Uint8Array([0, 1, 2, 3]),
+  );
+  assertEquals(
+    toHex(digest),
+    "c1c36925b6409a04f1b504fcbca9d82b4017277cb5ed2b2065fc1d3814d5aaf5",
+  );
+
+  await assertRejects(
+    () =>
+      crypto.subtle.digest(
+        { name: "cSHAKE256", length: 12 },
+        new Uint8Array(),
+      ),
+    DOMException,
+  );
+});
+
+Deno.test(async function kmacSignVerify() {
+  const keyData = new Uint8Array(32).map((_, i) => 0x40 + i);
+  const key = await crypto.subtle.importKey(
+    "raw-secret",
+    keyData,
+    "KMAC128",
+    true,
+    ["sign", "verify"],
+  );
+  assertEquals((key.algorithm as KmacKeyAlgorithm).length, 256);
+
+  // NIST SP 800-185 KMAC sample #1
+  const data = new Uint8Array([0, 1, 2, 3]);
+  const signature = await crypto.subtle.sign(
+    { name: "KMAC128", length: 256 },
+    key,
+    data,
+  );
+  assertEquals(
+    toHex(signature),
+    "e5780b0d3ea6f7d3a429c5706aa43a00fadbd7d49628839e3187243f456ee14e",
+  );
+  assert(
+    await crypto.subtle.verify(
+      { name: "KMAC128", length: 256 },
+      key,
+      signature,
+      data,
+    ),
+  );
+  assert(
+    !await crypto.subtle.verify(
+      {
+        name: "KMAC128",
+        length: 256,
+        customization: new Uint8Array([1]),
+      },
+      key,
+      signature,
+      data,
+    ),
+  );
+
+  const jwk = await crypto.subtle.exportKey("jwk", key);
+  assertEquals(jwk.alg, "K128");
+
+  const generated = await crypto.subtle.generateKey("KMAC256", true, [
+    "sign",
+  ]) as CryptoKey;
+  assertEquals((generated.algorithm as KmacKeyAlgorithm).length, 256);
+});
+
+Deno.test(async function chaCha20Poly1305EncryptDecrypt() {
+  const key = await crypto.subtle.generateKey(
+    "ChaCha20-Poly1305",
+    true,
+    ["encrypt", "decrypt"],
+  ) as CryptoKey;
+  const iv = crypto.getRandomValues(new Uint8Array(12));
+  const additionalData = new Uint8Array([1, 2, 3]);
+  const plaintext = new TextEncoder().encode("hello world");
+
+  const ciphertext = await crypto.subtle.encrypt(
+    { name: "ChaCha20-Poly1305", iv, additionalData },
+    key,
+    plaintext,
+  );
+  assertEquals(ciphertext.byteLength, plaintext.byteLength + 16);
+
+  const decrypted = await crypto.subtle.decrypt(
+    { name: "ChaCha20-Poly1305", iv, additionalData },
+    key,
+    ciphertext,
+  );
+  assertEquals(new Uint8Array(decrypted), plaintext);
+
+  await assertRejects(
+    () =>
+      crypto.subtle.decrypt(
+        { name: "ChaCha20-Poly1305", iv },
+        key,
+        ciphertext,
+      ),
+    DOMException,
+  );
+
+  const raw = await crypto.subtle.exportKey("raw-secret", key);
+  assertEquals(raw.byteLength, 32);
+  const jwk = await crypto.subtle.exportKey("jwk", key);
+  assertEquals(jwk.alg, "C20P");
+});
+
+Deno.test(async function mlKemEncapsulateDecapsulate() {
+  const { publicKey, privateKey } = await crypto.subtle.generateKey(
+    "ML-KEM-768",
+    true,
+    ["encapsulateBits", "decapsulateBits", "decapsulateKey"],
+  ) as CryptoKeyPair;
+
+  const { sharedKey, ciphertext } = await crypto.subtle.encapsulateBits(
+    "ML-KEM-768",
+    publicKey,
+  );
+  assertEquals(sharedKey.byteLength, 32);
+  assertEquals(ciphertext.byteLength, 1088);
+
+  const decapsulated = await crypto.subtle.decapsulateBits(
+    "ML-KEM-768",
+    privateKey,
+    ciphertext,
+  );
+  assertEquals(new Uint8Array(decapsulated), new Uint8Array(sharedKey));
+
+  const aesKey = await crypto.subtle.decapsulateKey(
+    "ML-KEM-768",
+    privateKey,
+    ciphertext,
+    "AES-GCM",
+    true,
+    ["encrypt"],
+  );
+  assertEquals(
+    new Uint8Array(await crypto.subtle.exportKey("raw", aesKey)),
+    new Uint8Array(sharedKey),
+  );
+
+  // The seed and the public key round trip through all of their formats.
+  const seed = await crypto.subtle.exportKey("raw-seed", privateKey);
+  assertEquals(seed.byteLength, 64);
+  const pkcs8 = await crypto.subtle.exportKey("pkcs8", privateKey);
+  const imported = await crypto.subtle.importKey(
+    "pkcs8",
+    pkcs8,
+    "ML-KEM-768",
+    true,
+    ["decapsulateBits"],
+  );
+  assertEquals(
+    new Uint8Array(await crypto.subtle.exportKey("raw-seed", imported)),
+    new Uint8Array(seed),
+  );
+  const spki = await crypto.subtle.exportKey("spki", publicKey);
+  const importedPublic = await crypto.subtle.importKey(
+    "spki",
+    spki,
+    "ML-KEM-768",
+    true,
+    ["encapsulateBits"],
+  );
+  assertEquals(
+    new Uint8Array(await crypto.subtle.exportKey("raw-public", importedPublic)),
+    new Uint8Array(await crypto.subtle.exportKey("raw-public", publicKey)),
+  );
+
+  await assertRejects(
+    () => crypto.subtle.exportKey("raw-seed", publicKey),
+    DOMException,
+  );
+});
+
+Deno.test(async function mlDsaSignVerify() {
+  const { publicKey, privateKey } = await crypto.subtle.generateKey(
+    "ML-DSA-65",
+    true,
+    ["sign", "verify"],
+  ) as CryptoKeyPair;
+  const data = new TextEncoder().encode("hello world");
+  const context = new Uint8Array([1, 2, 3]);
+
+  const signature = await crypto.subtle.sign(
+    { name: "ML-DSA-65", context },
+    privateKey,
+    data,
+  );
+  assertEquals(signature.byteLength, 3309);
+  assert(
+    await crypto.subtle.verify(
+      { name: "ML-DSA-65", context },
+      publicKey,
+      signature,
+      data,
+    ),
+  );
+  assert(
+    !await crypto.subtle.verify("ML-DSA-65", publicKey, signature, data),
+  );
+
+  await assertRejects(
+    () =>
+      crypto.subtle.sign(
+        { name: "ML-DSA-65", context: new Uint8Array(256) },
+        privateKey,
+        data,
+      ),
+    DOMException,
+  );
+
+  const jwk = await crypto.subtle.exportKey("jwk", privateKey);
+  assertEquals(jwk.kty, "AKP");
+  assertEquals(jwk.alg, "ML-DSA-65");
+  assert(jwk.pub);
+  assert(jwk.priv);
+  const imported = await crypto.subtle.importKey(
+    "jwk",
+    jwk,
+    "ML-DSA-65",
+    true,
+    ["sign"],
+  );
+  const signature2 = await crypto.subtle.sign("ML-DSA-65", imported, data);
+  assert(
+    await crypto.subtle.verify("ML-DSA-65", publicKey, signature2, data),
+  );
+});
+
+Deno.test(async function argon2idDeriveBits() {
+  const key = await crypto.subtle.importKey(
+    "raw-secret",
+    new Uint8Array(32).fill(1),
+    "Argon2id",
+    false,
+    ["deriveBits"],
+  );
+
+  // RFC 9106 section 5.3
+  const bits = await crypto.subtle.deriveBits(
+    {
+      name: "Argon2id",
+      nonce: new Uint8Array(16).fill(2),
+      secretValue: new Uint8Array(8).fill(3),
+      associatedData: new Uint8Array(12).fill(4),
+      parallelism: 4,
+      memory: 32,
+      passes: 3,
+    },
+    key,
+    256,
+  );
+  assertEquals(
+    toHex(bits),
+    "0d640df58d78766c08c037a34a8b53c9d01ef0452d75b65eb52520e96b01e659",
+  );
+
+  await assertRejects(
+    () =>
+      crypto.subtle.deriveBits(
+        {
+          name: "Argon2id",
+          nonce: new Uint8Array(4),
+          parallelism: 1,
+          memory: 8,
+          passes: 1,
+        },
+        key,
+        256,
+      ),
+    DOMException,
+  );
+});
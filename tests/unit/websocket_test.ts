@@ -1154,3 +1154,72 @@ Deno.test("WebSocket close with reason but no code doesn't send 1005", async ()
   conn.close();
   await server.finished;
 });
+
+Deno.test("WebSocket permessage-deflate", async () => {
+  const ac = new AbortController();
+  const listeningDeferred = Promise.withResolvers<void>();
+  const doneDeferred = Promise.withResolvers<void>();
+  await using server = Deno.serve({
+    handler: (request) => {
+      assertEquals(
+        request.headers.get("sec-websocket-extensions"),
+        "permessage-deflate; client_no_context_takeover; client_max_window_bits",
+      );
+      const { response, socket } = Deno.upgradeWebSocket(request, {
+        perMessageDeflate: { serverMaxWindowBits: 10 },
+      });
+      socket.onerror = () => fail();
+      socket.onmessage = (m) => socket.send(m.data);
+      socket.onclose = () => doneDeferred.resolve();
+      return response;
+    },
+    port: servePort,
+    signal: ac.signal,
+    onListen: onListen(listeningDeferred.resolve),
+    onError: createOnErrorCb(ac),
+  });
+
+  await listeningDeferred.promise;
+  const ws = new WebSocket(serveUrl, {
+    perMessageDeflate: { clientNoContextTakeover: true },
+  });
+  ws.binaryType = "arraybuffer";
+  const text = "compress me ".repeat(10_000);
+  const binary = new Uint8Array(100_000).map((_, i) => i % 7);
+  const messages: unknown[] = [];
+  const closed = Promise.withResolvers<void>();
+  ws.onerror = () => fail();
+  ws.onopen = () => {
+    assertEquals(
+      ws.extensions,
+      "permessage-deflate; client_no_context_takeover; server_max_window_bits=10",
+    );
+    ws.send(text);
+    ws.send(binary);
+    ws.send("");
+  };
+  ws.onmessage = (m) => {
+    messages.push(m.data);
+    if (messages.length === 3) ws.close();
+  };
+  ws.onclose = () => closed.resolve();
+
+  await closed.promise;
+  assertEquals(messages[0], text);
+  assertEquals(new Uint8Array(messages[1] as ArrayBuffer), binary);
+  assertEquals(messages[2], "");
+  await doneDeferred.promise;
+  ac.abort();
+  await server.finished;
+});
+
+Deno.test("WebSocket permessage-deflate invalid window bits", () => {
+  assertThrows(
+    () =>
+      new WebSocket(serveUrl, {
+        perMessageDeflate: { clientMaxWindowBits: 16 },
+      }),
+    RangeError,
+    "'clientMaxWindowBits' must be between 8 and 15",
+  );
+});
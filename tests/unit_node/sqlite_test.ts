@@ -1,8 +1,9 @@
 // Copyright 2018-2026 the Deno authors. MIT license.
-import sqlite, { backup, DatabaseSync } from "node:sqlite";
+import sqlite, { backup, Database, DatabaseSync } from "node:sqlite";
 import {
   assert,
   assertEquals,
+  assertRejects,
   assertStrictEquals,
   assertThrows,
 } from "@std/assert";
@@ -1236,3 +1237,184 @@ Deno.test("sql.db returns the associated DatabaseSync instance", () => {
   sql.clear();
   assertStrictEquals(sql.db, db);
 });
+
+Deno.test("[node/sqlite] Database runs queries asynchronously", async () => {
+  await using db = new Database(":memory:");
+  assert(db.isOpen);
+  await db.exec("CREATE TABLE data (key INTEGER PRIMARY KEY, value TEXT)");
+
+  const insert = await db.prepare(
+    "INSERT INTO data (key, value) VALUES (?, ?)",
+  );
+  assertEquals(await insert.run(1, "one"), {
+    lastInsertRowid: 1,
+    changes: 1,
+  });
+  await insert.run(2, new Uint8Array([1, 2]));
+
+  const query = await db.prepare("SELECT * FROM data ORDER BY key");
+  assertEquals(query.sourceSQL, "SELECT * FROM data ORDER BY key");
+  assertEquals(query.columns().map((column) => column.name), [
+    "key",
+    "value",
+  ]);
+  assertEquals(await query.all(), [
+    { __proto__: null, key: 1, value: "one" },
+    { __proto__: null, key: 2, value: new Uint8Array([1, 2]) },
+  ]);
+
+  const get = await db.prepare("SELECT value FROM data WHERE key = $key");
+  assertEquals(await get.get({ $key: 1 }), { __proto__: null, value: "one" });
+  assertStrictEquals(await get.get({ $key: 3 }), undefined);
+  get.setReturnArrays(true);
+  assertEquals(await get.get({ $key: 1 }), ["one"]);
+
+  await assertRejects(
+    () => insert.run(3, {}),
+    TypeError,
+    "Provided value cannot be bound to SQLite parameter 2.",
+  );
+  await assertRejects(() => db.exec("SELECT * FROM missing"), Error);
+});
+
+Deno.test("[node/sqlite] Database statement iterate", async () => {
+  await using db = new Database(":memory:");
+  await db.exec("CREATE TABLE data (key INTEGER PRIMARY KEY)");
+  await db.exec(
+    "WITH RECURSIVE n(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM n " +
+      "WHERE x < 250) INSERT INTO data SELECT x FROM n",
+  );
+
+  const query = await db.prepare("SELECT key FROM data WHERE key > ?");
+  let count = 0;
+  for await (const row of query.iterate(0)) {
+    assertEquals(row.key, ++count);
+  }
+  assertEquals(count, 250);
+
+  // Stopping early resets the statement for the next query.
+  for await (const row of query.iterate(0)) {
+    assertEquals(row.key, 1);
+    break;
+  }
+  assertEquals((await query.all(200)).length, 50);
+});
+
+Deno.test("[node/sqlite] Database tag store", async () => {
+  await using db = new Database(":memory:");
+  await db.exec("CREATE TABLE foo (id INTEGER PRIMARY KEY, text TEXT)");
+  const sql = db.createTagStore(10);
+  assertStrictEquals(sql.db, db);
+  assertStrictEquals(sql.capacity, 10);
+
+  assertStrictEquals(
+    (await sql.run`INSERT INTO foo (text) VALUES (${"one"})`).changes,
+    1,
+  );
+  assertStrictEquals(sql.size(), 1);
+  assertEquals(await sql.get`SELECT text FROM foo WHERE id = ${1}`, {
+    __proto__: null,
+    text: "one",
+  });
+  assertStrictEquals(
+    await sql.get`SELECT text FROM foo WHERE id = ${2}`,
+    undefined,
+  );
+  assertStrictEquals(sql.size(), 2);
+  assertStrictEquals((await sql.all`SELECT * FROM foo`).length, 1);
+
+  const rows = [];
+  for await (const row of sql.iterate`SELECT text FROM foo`) {
+    rows.push(row.text);
+  }
+  assertEquals(rows, ["one"]);
+
+  sql.clear();
+  assertStrictEquals(sql.size(), 0);
+});
+
+Deno.test("[node/sqlite] Database sessions and changesets", async () => {
+  await using source = new Database(":memory:");
+  await using target = new Database(":memory:");
+  const create = "CREATE TABLE data (key INTEGER PRIMARY KEY, value TEXT)";
+  await source.exec(create);
+  await target.exec(create);
+
+  await using session = await source.createSession();
+  await source.exec("INSERT INTO data VALUES (1, 'hello')");
+  const changeset = await session.changeset();
+  assert(changeset.length > 0);
+
+  assert(await target.applyChangeset(changeset));
+  assertEquals(
+    await (await target.prepare("SELECT value FROM data")).all(),
+    [{ __proto__: null, value: "hello" }],
+  );
+
+  // Applying it again conflicts and aborts by default.
+  assertEquals(await target.applyChangeset(changeset), false);
+  assert(
+    await target.applyChangeset(changeset, {
+      onConflict: sqlite.constants.SQLITE_CHANGESET_OMIT,
+    }),
+  );
+});
+
+Deno.test("[node/sqlite] Database backup in steps", async () => {
+  const db = new Database(`${tempDir}/async_original.db`);
+  await db.exec("CREATE TABLE data (key INTEGER PRIMARY KEY, value TEXT)");
+  const insert = await db.prepare("INSERT INTO data (value) VALUES (?)");
+  for (let i = 0; i < 100; i++) {
+    await insert.run("x".repeat(1000));
+  }
+
+  let progressCalls = 0;
+  const totalPages = await backup(db, `${tempDir}/async_backup.db`, {
+    rate: 1,
+    progress: ({ totalPages, remainingPages }) => {
+      progressCalls++;
+      assert(remainingPages < totalPages);
+    },
+  });
+  assert(totalPages > 1);
+  assert(progressCalls > 0);
+
+  await using backupDb = new Database(`${tempDir}/async_backup.db`);
+  const count = await backupDb.prepare("SELECT count(*) AS n FROM data");
+  assertEquals(await count.get(), { __proto__: null, n: 100 });
+
+  await db.close();
+  Deno.removeSync(`${tempDir}/async_original.db`);
+  Deno.removeSync(`${tempDir}/async_backup.db`);
+});
+
+Deno.test("[node/sqlite] Database rejects queries after close", async () => {
+  const db = new Database(":memory:");
+  const stmt = await db.prepare("SELECT 1");
+  await db.close();
+  assert(!db.isOpen);
+
+  await assertRejects(() => db.exec("SELECT 1"), Error, "database is not open");
+  await assertRejects(() => stmt.all(), Error, "database is not open");
+  await assertRejects(() => db.close(), Error, "database is not open");
+
+  db.open();
+  assertEquals(await (await db.prepare("SELECT 1 AS one")).get(), {
+    __proto__: null,
+    one: 1,
+  });
+  await db.close();
+});
+
+Deno.test("[node/sqlite] Database enableDefensive", async () => {
+  await using db = new Database(":memory:");
+  await db.enableDefensive(true);
+  await db.enableDefensive(false);
+  nodeAssert.throws(() => {
+    // @ts-expect-error testing invalid input
+    db.enableDefensive("yes");
+  }, {
+    code: "ERR_INVALID_ARG_TYPE",
+    message: /The "active" argument must be a boolean/,
+  });
+});